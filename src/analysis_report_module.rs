@@ -0,0 +1,723 @@
+// src/analysis_report_module.rs
+
+/// Analysis report export for rows_and_columns
+///
+/// Writes the complete analysis of a CSV file (file facts, per-column
+/// statistics, full categorical value distributions, data quality warnings and
+/// ASCII charts) to a Markdown or plain-text file that can be read, shared and
+/// version-controlled without this tool.
+///
+/// # Core Responsibilities
+/// - Collect data quality warnings from the analysis results
+/// - Lay out the report once and render it as Markdown or plain text
+/// - Embed ASCII histograms and bar charts from the chart rendering module
+/// - Write the report under `analysis_cache/` or to a user-chosen path
+/// - Hand HTML reports to the HTML report module
+///
+/// # Design Philosophy
+/// - One report layout, two output formats: a small document builder decides
+///   how headings, tables and chart blocks look in each format
+/// - Complete rather than abbreviated: every categorical value is listed,
+///   unlike the terminal summary which shows the top five
+/// - ASCII-only charts so the report renders the same in any viewer
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import CSV analysis structures
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvFieldType,
+    EnhancedCsvColumnInformation,
+};
+
+// Import chart builders and the text renderer
+use super::chart_rendering_module::{
+    ChartGlyphMode,
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    build_bar_chart_from_categorical_statistics,
+    build_histogram_chart_from_csv_column,
+    render_chart_as_text_lines,
+};
+
+// Import the aligned text table formatter and display-width measurement
+use super::terminal_control_module::{calculate_text_display_width, format_aligned_text_table_lines};
+
+// Import the HTML report builder (HTML reports have their own layout)
+use super::html_report_module::build_html_report_text;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+/// Width and height of embedded charts, in characters
+const REPORT_CHART_COLUMN_COUNT: usize = 64;
+const REPORT_HISTOGRAM_ROW_COUNT: usize = 12;
+
+/// Most bars drawn in a categorical bar chart (the value table lists all values)
+const REPORT_MAXIMUM_BAR_COUNT: usize = 20;
+
+/// Widest column in plain-text tables
+const PLAIN_TEXT_MAXIMUM_TABLE_COLUMN_WIDTH: usize = 40;
+
+/// Missing percentage above which a column gets a warning
+const HIGH_MISSING_PERCENTAGE_THRESHOLD: f64 = 20.0;
+
+/// Fewest values before an all-unique text column is called identifier-like
+const IDENTIFIER_LIKE_MINIMUM_VALUE_COUNT: usize = 20;
+
+/// Unique value count above which a categorical column is high-cardinality
+const HIGH_CARDINALITY_UNIQUE_VALUE_COUNT: usize = 50;
+
+/// How many interquartile ranges beyond the quartiles count as extreme
+const EXTREME_VALUE_IQR_MULTIPLIER: f64 = 3.0;
+
+/// Output format of an analysis report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalysisReportFormat {
+    /// Markdown (`.md`) with pipe tables and fenced chart blocks
+    Markdown,
+
+    /// Plain text (`.txt`) with underlined headings and aligned tables
+    PlainText,
+
+    /// Self-contained HTML (`.html`) with inline SVG charts
+    Html,
+}
+
+impl AnalysisReportFormat {
+    /// Returns the file extension used for this format
+    ///
+    /// # Returns
+    /// * `&str` - `md`, `txt` or `html`
+    pub fn file_extension(&self) -> &str {
+        match self {
+            AnalysisReportFormat::Markdown => "md",
+            AnalysisReportFormat::PlainText => "txt",
+            AnalysisReportFormat::Html => "html",
+        }
+    }
+
+    /// Returns a human-readable name for this format
+    ///
+    /// # Returns
+    /// * `&str` - `Markdown`, `Plain text` or `HTML`
+    pub fn display_name(&self) -> &str {
+        match self {
+            AnalysisReportFormat::Markdown => "Markdown",
+            AnalysisReportFormat::PlainText => "Plain text",
+            AnalysisReportFormat::Html => "HTML",
+        }
+    }
+}
+
+/// Accumulates report text in the chosen format
+struct ReportDocumentBuilder {
+    /// Format every element is rendered in
+    report_format: AnalysisReportFormat,
+
+    /// Text written so far
+    report_text: String,
+}
+
+impl ReportDocumentBuilder {
+    /// Adds a heading (level 1 = report title, 2 = section, 3 = column)
+    fn add_heading(&mut self, heading_level: usize, heading_text: &str) {
+        match self.report_format {
+            AnalysisReportFormat::Markdown => {
+                self.report_text.push_str(&format!("{} {}\n\n", "#".repeat(heading_level), heading_text));
+            }
+            AnalysisReportFormat::PlainText | AnalysisReportFormat::Html => {
+                let underline_character = match heading_level {
+                    1 => '=',
+                    2 => '-',
+                    _ => '~',
+                };
+                if heading_level <= 2 {
+                    self.report_text.push('\n');
+                }
+                self.report_text.push_str(heading_text);
+                self.report_text.push('\n');
+                self.report_text.push_str(
+                    &underline_character.to_string().repeat(calculate_text_display_width(heading_text))
+                );
+                self.report_text.push_str("\n\n");
+            }
+        }
+    }
+
+    /// Adds a paragraph of text
+    fn add_paragraph(&mut self, paragraph_text: &str) {
+        self.report_text.push_str(paragraph_text);
+        self.report_text.push_str("\n\n");
+    }
+
+    /// Adds a bulleted list
+    fn add_bullet_list(&mut self, list_items: &[String]) {
+        for list_item in list_items {
+            self.report_text.push_str(&format!("- {}\n", list_item));
+        }
+        self.report_text.push('\n');
+    }
+
+    /// Adds a table; `right_aligned_columns` marks numeric columns
+    fn add_table(&mut self, header_cells: &[&str], body_rows: &[Vec<String>], right_aligned_columns: &[bool]) {
+        match self.report_format {
+            AnalysisReportFormat::Markdown => {
+                let escape_cell = |cell_text: &str| cell_text.replace('|', "\\|");
+                self.report_text.push_str(&format!(
+                    "| {} |\n",
+                    header_cells.iter().map(|header_cell| escape_cell(header_cell)).collect::<Vec<_>>().join(" | ")
+                ));
+                self.report_text.push_str(&format!(
+                    "|{}|\n",
+                    (0..header_cells.len())
+                        .map(|column_position| {
+                            if right_aligned_columns.get(column_position).copied().unwrap_or(false) { " ---: " } else { " --- " }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("|")
+                ));
+                for body_row in body_rows {
+                    self.report_text.push_str(&format!(
+                        "| {} |\n",
+                        body_row.iter().map(|cell_text| escape_cell(cell_text)).collect::<Vec<_>>().join(" | ")
+                    ));
+                }
+            }
+            AnalysisReportFormat::PlainText | AnalysisReportFormat::Html => {
+                let header_texts: Vec<String> = header_cells.iter().map(|header_cell| header_cell.to_string()).collect();
+                for table_line in format_aligned_text_table_lines(
+                    &header_texts,
+                    body_rows,
+                    right_aligned_columns,
+                    PLAIN_TEXT_MAXIMUM_TABLE_COLUMN_WIDTH,
+                    '-',
+                ) {
+                    self.report_text.push_str(&format!("  {}\n", table_line));
+                }
+            }
+        }
+        self.report_text.push('\n');
+    }
+
+    /// Adds pre-formatted lines (charts), fenced in Markdown and indented in plain text
+    fn add_preformatted_block(&mut self, block_lines: &[String]) {
+        match self.report_format {
+            AnalysisReportFormat::Markdown => {
+                self.report_text.push_str("```text\n");
+                for block_line in block_lines {
+                    self.report_text.push_str(block_line.trim_end());
+                    self.report_text.push('\n');
+                }
+                self.report_text.push_str("```\n\n");
+            }
+            AnalysisReportFormat::PlainText | AnalysisReportFormat::Html => {
+                for block_line in block_lines {
+                    self.report_text.push_str(&format!("    {}\n", block_line.trim_end()));
+                }
+                self.report_text.push('\n');
+            }
+        }
+    }
+}
+
+/// Collects data quality warnings for the file and each column
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (header, row count)
+/// * `enhanced_analysis_results` - Per-column statistics
+///
+/// # Returns
+/// * `Vec<String>` - One sentence per warning (empty when nothing stands out)
+pub fn collect_data_quality_warnings(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> Vec<String> {
+    let mut quality_warnings = Vec::new();
+
+    // Step 1: File-level warnings
+    if csv_analysis_results.total_data_row_count == 0 {
+        quality_warnings.push("The file has no data rows.".to_string());
+    }
+    if !csv_analysis_results.has_header_row {
+        quality_warnings.push("No header row was detected; column names were generated (column_1, column_2, ...).".to_string());
+    }
+
+    // Step 2: Column-level warnings
+    for enhanced_column_info in enhanced_analysis_results {
+        let column_name = &enhanced_column_info.basic_info.column_name;
+
+        if let Some(numerical_statistics) = &enhanced_column_info.numerical_statistics {
+            if numerical_statistics.missing_percentage > HIGH_MISSING_PERCENTAGE_THRESHOLD {
+                quality_warnings.push(format!(
+                    "'{}': {:.1}% of values are missing or not numeric.",
+                    column_name, numerical_statistics.missing_percentage
+                ));
+            }
+            if numerical_statistics.standard_deviation == 0.0 {
+                quality_warnings.push(format!(
+                    "'{}': every value is {} (constant column).",
+                    column_name, numerical_statistics.min_value
+                ));
+            }
+
+            let interquartile_range = numerical_statistics.q3_value - numerical_statistics.q1_value;
+            let lower_extreme_fence = numerical_statistics.q1_value - EXTREME_VALUE_IQR_MULTIPLIER * interquartile_range;
+            let upper_extreme_fence = numerical_statistics.q3_value + EXTREME_VALUE_IQR_MULTIPLIER * interquartile_range;
+            if interquartile_range > 0.0
+                && (numerical_statistics.min_value < lower_extreme_fence || numerical_statistics.max_value > upper_extreme_fence) {
+                quality_warnings.push(format!(
+                    "'{}': extreme values (min {}, max {}) lie more than {} IQRs beyond the quartiles; check for outliers or entry errors.",
+                    column_name, numerical_statistics.min_value, numerical_statistics.max_value, EXTREME_VALUE_IQR_MULTIPLIER
+                ));
+            }
+        }
+
+        if let Some(categorical_statistics) = &enhanced_column_info.categorical_statistics {
+            let non_empty_value_count: usize = categorical_statistics.value_frequencies
+                .iter()
+                .map(|value_frequency| value_frequency.count)
+                .sum();
+
+            if non_empty_value_count == 0 {
+                quality_warnings.push(format!("'{}': the column is entirely empty.", column_name));
+                continue;
+            }
+            if categorical_statistics.missing_percentage > HIGH_MISSING_PERCENTAGE_THRESHOLD {
+                quality_warnings.push(format!(
+                    "'{}': {:.1}% of values are missing.",
+                    column_name, categorical_statistics.missing_percentage
+                ));
+            }
+            if categorical_statistics.unique_value_count == 1 {
+                quality_warnings.push(format!(
+                    "'{}': every value is '{}' (constant column).",
+                    column_name,
+                    categorical_statistics.mode_value.as_deref().unwrap_or("")
+                ));
+            } else if categorical_statistics.unique_value_count == non_empty_value_count
+                && non_empty_value_count >= IDENTIFIER_LIKE_MINIMUM_VALUE_COUNT {
+                quality_warnings.push(format!(
+                    "'{}': every value is different; it looks like an identifier rather than a category.",
+                    column_name
+                ));
+            } else if categorical_statistics.unique_value_count > HIGH_CARDINALITY_UNIQUE_VALUE_COUNT {
+                quality_warnings.push(format!(
+                    "'{}': {} unique values (high cardinality); crosstabs and bar charts will pool rare values.",
+                    column_name, categorical_statistics.unique_value_count
+                ));
+            }
+        }
+    }
+
+    quality_warnings
+}
+
+/// Builds the complete report text in the chosen format
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file facts)
+/// * `enhanced_analysis_results` - Per-column statistics
+/// * `report_format` - Markdown, plain text or HTML
+///
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - The report text
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be re-read for histograms
+pub fn build_analysis_report_text(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    report_format: AnalysisReportFormat,
+) -> RowsAndColumnsResult<String> {
+    // HTML has its own layout (collapsible sections, inline SVG charts)
+    if report_format == AnalysisReportFormat::Html {
+        return build_html_report_text(csv_analysis_results, enhanced_analysis_results);
+    }
+
+    let mut report_builder = ReportDocumentBuilder { report_format, report_text: String::new() };
+    let csv_file_name = csv_analysis_results.csv_file_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // Step 1: Title and file facts
+    report_builder.add_heading(1, &format!("CSV Analysis Report: {}", csv_file_name));
+    let generated_seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    report_builder.add_paragraph(&format!(
+        "Generated {} by rows_and_columns.",
+        format_unix_timestamp_as_utc_text(generated_seconds)
+    ));
+
+    let file_size_text = std::fs::metadata(&csv_analysis_results.csv_file_path)
+        .map(|file_metadata| format!("{} bytes", file_metadata.len()))
+        .unwrap_or_else(|_| "unknown".to_string());
+    report_builder.add_heading(2, "File");
+    report_builder.add_table(&["Property", "Value"], &[
+        vec!["Path".to_string(), csv_analysis_results.csv_file_path.display().to_string()],
+        vec!["Size".to_string(), file_size_text],
+        vec!["Header row".to_string(), if csv_analysis_results.has_header_row { "yes" } else { "no" }.to_string()],
+        vec!["Columns".to_string(), csv_analysis_results.total_column_count.to_string()],
+        vec!["Data rows".to_string(), csv_analysis_results.total_data_row_count.to_string()],
+        vec!["Metadata file".to_string(), csv_analysis_results.metadata_file_path.display().to_string()],
+    ], &[false, false]);
+
+    // Step 2: One-line overview of every column
+    report_builder.add_heading(2, "Column Overview");
+    let overview_rows: Vec<Vec<String>> = enhanced_analysis_results
+        .iter()
+        .map(|enhanced_column_info| {
+            let (missing_percentage, summary_text) = match (
+                &enhanced_column_info.numerical_statistics,
+                &enhanced_column_info.categorical_statistics,
+            ) {
+                (Some(numerical_statistics), _) => (
+                    numerical_statistics.missing_percentage,
+                    format!("mean {:.3}", numerical_statistics.mean_value),
+                ),
+                (None, Some(categorical_statistics)) => (
+                    categorical_statistics.missing_percentage,
+                    format!("{} unique", categorical_statistics.unique_value_count),
+                ),
+                (None, None) => (0.0, String::new()),
+            };
+            vec![
+                (enhanced_column_info.basic_info.column_index + 1).to_string(),
+                enhanced_column_info.basic_info.column_name.clone(),
+                enhanced_column_info.basic_info.detected_data_type.to_toml_string().to_string(),
+                describe_field_type(&enhanced_column_info.field_type).to_string(),
+                format!("{:.1}%", missing_percentage),
+                summary_text,
+            ]
+        })
+        .collect();
+    report_builder.add_table(
+        &["#", "Column", "Data type", "Field type", "Missing", "Summary"],
+        &overview_rows,
+        &[true, false, false, false, true, false],
+    );
+
+    // Step 3: Warnings
+    report_builder.add_heading(2, "Warnings");
+    let quality_warnings = collect_data_quality_warnings(csv_analysis_results, enhanced_analysis_results);
+    if quality_warnings.is_empty() {
+        report_builder.add_paragraph("No data quality warnings.");
+    } else {
+        report_builder.add_bullet_list(&quality_warnings);
+    }
+
+    // Step 4: Full details for every column
+    report_builder.add_heading(2, "Columns");
+    for enhanced_column_info in enhanced_analysis_results {
+        append_column_report_section(&mut report_builder, csv_analysis_results, enhanced_column_info)?;
+    }
+
+    Ok(report_builder.report_text.trim_end().to_string() + "\n")
+}
+
+/// Appends the statistics, value table and chart for one column
+///
+/// # Arguments
+/// * `report_builder` - The document being built
+/// * `csv_analysis_results` - Basic analysis results (for streaming histograms)
+/// * `enhanced_column_info` - The column to describe
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or histogram read error
+fn append_column_report_section(
+    report_builder: &mut ReportDocumentBuilder,
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_column_info: &EnhancedCsvColumnInformation,
+) -> RowsAndColumnsResult<()> {
+    let basic_info = &enhanced_column_info.basic_info;
+    report_builder.add_heading(3, &format!(
+        "{}. {} ({}, {})",
+        basic_info.column_index + 1,
+        basic_info.column_name,
+        basic_info.detected_data_type.to_toml_string(),
+        describe_field_type(&enhanced_column_info.field_type)
+    ));
+
+    if let Some(numerical_statistics) = &enhanced_column_info.numerical_statistics {
+        let statistic_rows: Vec<Vec<String>> = [
+            ("min", numerical_statistics.min_value),
+            ("q1", numerical_statistics.q1_value),
+            ("median", numerical_statistics.q2_median_value),
+            ("q3", numerical_statistics.q3_value),
+            ("max", numerical_statistics.max_value),
+            ("mean", numerical_statistics.mean_value),
+            ("std", numerical_statistics.standard_deviation),
+        ]
+            .iter()
+            .map(|(statistic_name, statistic_value)| vec![statistic_name.to_string(), format!("{:.3}", statistic_value)])
+            .chain(std::iter::once(vec![
+                "missing".to_string(),
+                format!("{:.1}%", numerical_statistics.missing_percentage),
+            ]))
+            .collect();
+        report_builder.add_table(&["Statistic", "Value"], &statistic_rows, &[false, true]);
+
+        let histogram_chart = build_histogram_chart_from_csv_column(
+            &csv_analysis_results.csv_file_path,
+            csv_analysis_results.has_header_row,
+            basic_info.column_index,
+            &basic_info.column_name,
+            numerical_statistics,
+            DEFAULT_HISTOGRAM_BIN_COUNT,
+        )?;
+        report_builder.add_preformatted_block(&render_chart_as_text_lines(
+            &histogram_chart,
+            REPORT_CHART_COLUMN_COUNT,
+            REPORT_HISTOGRAM_ROW_COUNT,
+            ChartGlyphMode::Ascii,
+        ));
+    }
+
+    if let Some(categorical_statistics) = &enhanced_column_info.categorical_statistics {
+        let mut summary_text = format!(
+            "Unique values: {}. Missing: {:.1}%.",
+            categorical_statistics.unique_value_count,
+            categorical_statistics.missing_percentage
+        );
+        if let Some(mode_value) = &categorical_statistics.mode_value {
+            summary_text.push_str(&format!(" Mode: {} ({:.1}%).", mode_value, categorical_statistics.mode_percentage));
+        }
+        report_builder.add_paragraph(&summary_text);
+
+        // Every value, not just the top few shown in the terminal
+        let distribution_rows: Vec<Vec<String>> = categorical_statistics.value_frequencies
+            .iter()
+            .map(|value_frequency| vec![
+                value_frequency.value.clone(),
+                value_frequency.count.to_string(),
+                format!("{:.1}%", value_frequency.percentage),
+            ])
+            .collect();
+        report_builder.add_table(&["Value", "Count", "Percent"], &distribution_rows, &[false, true, true]);
+
+        let bar_chart = build_bar_chart_from_categorical_statistics(
+            &basic_info.column_name,
+            categorical_statistics,
+            REPORT_MAXIMUM_BAR_COUNT,
+        );
+        let bar_chart_row_count = categorical_statistics.value_frequencies.len().min(REPORT_MAXIMUM_BAR_COUNT) + 2;
+        report_builder.add_preformatted_block(&render_chart_as_text_lines(
+            &bar_chart,
+            REPORT_CHART_COLUMN_COUNT,
+            bar_chart_row_count,
+            ChartGlyphMode::Ascii,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the lowercase name of a field type
+pub fn describe_field_type(field_type: &CsvFieldType) -> &str {
+    match field_type {
+        CsvFieldType::Categorical => "categorical",
+        CsvFieldType::Continuous => "continuous",
+    }
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time
+///
+/// # Arguments
+/// * `unix_seconds` - Seconds since 1970-01-01 00:00:00 UTC
+///
+/// # Returns
+/// * `String` - e.g. `2024-03-01 14:05:09 UTC`
+pub fn format_unix_timestamp_as_utc_text(unix_seconds: u64) -> String {
+    let days_since_epoch = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+
+    // Civil-from-days conversion (proleptic Gregorian calendar, 400-year eras)
+    let shifted_days = days_since_epoch + 719_468;
+    let era_number = shifted_days.div_euclid(146_097);
+    let day_of_era = shifted_days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month_number = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year_number = year_of_era + era_number * 400 + if month_number <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year_number,
+        month_number,
+        day_of_month,
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Returns the default report path inside `analysis_cache/`
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (the CSV file name)
+/// * `analysis_cache_directory` - The application's `analysis_cache/` directory
+/// * `report_format` - Determines the file extension
+///
+/// # Returns
+/// * `PathBuf` - e.g. `analysis_cache/cats_analysis_report.md`
+pub fn determine_default_report_file_path(
+    csv_analysis_results: &CsvAnalysisResults,
+    analysis_cache_directory: &Path,
+    report_format: AnalysisReportFormat,
+) -> PathBuf {
+    let csv_file_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "csv".to_string());
+
+    analysis_cache_directory.join(format!("{}_analysis_report.{}", csv_file_stem, report_format.file_extension()))
+}
+
+/// Builds the report and writes it to a file
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results
+/// * `enhanced_analysis_results` - Per-column statistics
+/// * `report_format` - Markdown, plain text or HTML
+/// * `report_file_path` - Where to write (parent directories are created)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be read or the report cannot be written
+pub fn write_analysis_report_file(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    report_format: AnalysisReportFormat,
+    report_file_path: &Path,
+) -> RowsAndColumnsResult<()> {
+    let report_text = build_analysis_report_text(csv_analysis_results, enhanced_analysis_results, report_format)?;
+
+    if let Some(parent_directory) = report_file_path.parent()
+        && !parent_directory.as_os_str().is_empty() {
+        std::fs::create_dir_all(parent_directory).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to create report directory: {}", parent_directory.display()),
+                io_error
+            )
+        })?;
+    }
+
+    std::fs::write(report_file_path, report_text).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write analysis report: {}", report_file_path.display()),
+            io_error
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::{
+        CategoricalColumnStatistics,
+        CategoricalValueFrequency,
+        CsvColumnDataType,
+        NumericalColumnStatistics,
+    };
+    use super::super::test_fixture_module::{create_test_column_information, create_test_report_analysis};
+
+    /// Builds the report fixture: a color column with missing values and a weight column with an outlier
+    fn create_test_analysis(file_name: &str) -> (CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>) {
+        let value_frequencies: Vec<CategoricalValueFrequency> = (0..8)
+            .map(|value_number| CategoricalValueFrequency {
+                value: if value_number == 7 { "blue|green".to_string() } else { format!("shade{}", value_number) },
+                count: 1,
+                percentage: 12.5,
+            })
+            .collect();
+
+        create_test_report_analysis(
+            file_name,
+            "color,weight\nred,1\nblue|green,2\nred,3\nred,4\nred,5\nred,6\nred,1000\n",
+            vec![
+                EnhancedCsvColumnInformation {
+                    basic_info: create_test_column_information(0, "color", CsvColumnDataType::String, 7),
+                    field_type: CsvFieldType::Categorical,
+                    numerical_statistics: None,
+                    categorical_statistics: Some(CategoricalColumnStatistics {
+                        unique_value_count: 8,
+                        value_frequencies,
+                        missing_percentage: 30.0,
+                        mode_value: Some("shade0".to_string()),
+                        mode_percentage: 12.5,
+                    }),
+                },
+                EnhancedCsvColumnInformation {
+                    basic_info: create_test_column_information(1, "weight", CsvColumnDataType::Integer, 7),
+                    field_type: CsvFieldType::Continuous,
+                    numerical_statistics: Some(NumericalColumnStatistics {
+                        min_value: 1.0,
+                        q1_value: 2.5,
+                        q2_median_value: 4.0,
+                        q3_value: 5.5,
+                        max_value: 1000.0,
+                        mean_value: 145.857,
+                        standard_deviation: 348.8,
+                        missing_percentage: 0.0,
+                    }),
+                    categorical_statistics: None,
+                },
+            ],
+        )
+    }
+
+    /// Test UTC formatting of Unix timestamps, including a leap day
+    #[test]
+    fn test_format_unix_timestamp_as_utc_text() {
+        assert_eq!(format_unix_timestamp_as_utc_text(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_unix_timestamp_as_utc_text(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_unix_timestamp_as_utc_text(1_709_301_909), "2024-03-01 14:05:09 UTC");
+    }
+
+    /// Test that missing values and extreme values produce warnings
+    #[test]
+    fn test_collect_data_quality_warnings() {
+        let (csv_analysis_results, enhanced_analysis_results) = create_test_analysis("rows_and_columns_report_warnings_test.csv");
+        let quality_warnings = collect_data_quality_warnings(&csv_analysis_results, &enhanced_analysis_results);
+
+        assert_eq!(quality_warnings.len(), 2);
+        assert!(quality_warnings[0].starts_with("'color': 30.0% of values are missing"));
+        assert!(quality_warnings[1].starts_with("'weight': extreme values"));
+
+        let _ = std::fs::remove_dir_all(csv_analysis_results.csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test both formats list every value and embed the ASCII charts
+    #[test]
+    fn test_build_analysis_report_text_in_both_formats() {
+        let (csv_analysis_results, enhanced_analysis_results) = create_test_analysis("rows_and_columns_report_text_test.csv");
+
+        let markdown_text = build_analysis_report_text(
+            &csv_analysis_results, &enhanced_analysis_results, AnalysisReportFormat::Markdown,
+        ).expect("report should build");
+        assert!(markdown_text.starts_with("# CSV Analysis Report: rows_and_columns_report_text_test.csv\n"));
+        assert!(markdown_text.contains("| shade6 | 1 | 12.5% |"));
+        assert!(markdown_text.contains("| blue\\|green | 1 | 12.5% |"));
+        assert!(markdown_text.contains("```text\nweight (histogram)\n"));
+        assert!(markdown_text.contains("| Statistic | Value |\n| --- | ---: |\n"));
+
+        let plain_text = build_analysis_report_text(
+            &csv_analysis_results, &enhanced_analysis_results, AnalysisReportFormat::PlainText,
+        ).expect("report should build");
+        assert!(plain_text.contains("Warnings\n--------\n"));
+        assert!(plain_text.contains("  blue|green      1    12.5%"));
+        assert!(plain_text.contains("    color (bar chart)\n"));
+        assert!(!plain_text.contains("```"));
+
+        let _ = std::fs::remove_dir_all(csv_analysis_results.csv_file_path.parent().expect("test file has a directory"));
+    }
+}
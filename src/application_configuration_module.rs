@@ -0,0 +1,590 @@
+// src/application_configuration_module.rs
+
+/// Application configuration file (`rows_and_columns.toml`) for rows_and_columns
+///
+/// The analysis and display defaults (how many rows are sampled for type
+/// detection, how many of them must agree on a type, how many top values a
+/// categorical column lists, the chart glyphs, console color and the names of
+/// the data directories) are read from an optional TOML file. Without a file
+/// every setting keeps its built-in default.
+///
+/// ```toml
+/// [analysis]
+/// sample_rows_for_type_detection = 25
+/// type_detection_threshold_percent = 80
+///
+/// [display]
+/// top_value_count = 10
+/// chart_glyphs = "ascii"      # "unicode" or "ascii"
+/// color = "never"             # "auto", "always" or "never"
+///
+/// [directories]
+/// root = "rows_columns_data"
+/// csv_imports = "csv_imports"
+/// analysis_cache = "analysis_cache"
+/// temporary_work = "temporary_work"
+/// ```
+///
+/// # Core Responsibilities
+/// - Find the configuration file: `ROWS_AND_COLUMNS_CONFIG` if set, otherwise
+///   `rows_and_columns.toml` next to the executable
+/// - Parse and validate every key, reporting problems as `ConfigurationError`
+/// - Hold the loaded configuration for the rest of the process
+///
+/// # Design Philosophy
+/// - A missing default file is not an error; a missing file named by the
+///   environment variable is, because the user asked for it explicitly
+/// - Unknown tables and keys are rejected so that a typo does not silently
+///   leave a setting at its default
+/// - Loaded once at startup and read everywhere, like the console flags
+/// - An embedding application passes its own settings instead; they apply to
+///   its calls on the calling thread only, like silenced console output
+use std::cell::RefCell;
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+// Import the TOML reader shared with the metadata files
+use super::metadata_manager_module::{TomlValue, parse_toml_document};
+
+// Import the glyph mode used by the text chart renderer
+use super::chart_rendering_module::ChartGlyphMode;
+
+// Import the path management module for binary-relative operations
+use super::manage_absolute_executable_directory_relative_paths::make_input_path_name_abs_executabledirectoryrelative_nocheck;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsError,
+    RowsAndColumnsResult,
+    create_configuration_error,
+};
+
+/// File name of the configuration file looked up next to the executable
+pub const APPLICATION_CONFIGURATION_FILE_NAME: &str = "rows_and_columns.toml";
+
+/// Environment variable naming a configuration file to use instead
+pub const APPLICATION_CONFIGURATION_ENVIRONMENT_VARIABLE: &str = "ROWS_AND_COLUMNS_CONFIG";
+
+/// Default number of data rows sampled per column to detect its type
+pub const DEFAULT_TYPE_DETECTION_SAMPLE_ROW_COUNT: usize = 10;
+
+/// Default share of sampled values (in percent) that must agree on a type
+pub const DEFAULT_TYPE_DETECTION_THRESHOLD_PERCENT: usize = 70;
+
+/// Default number of most frequent values listed for a categorical column
+pub const DEFAULT_TOP_VALUE_DISPLAY_COUNT: usize = 5;
+
+/// Default directory names (the root is created next to the executable)
+pub const DEFAULT_ROOT_DIRECTORY_NAME: &str = "rows_columns_data";
+pub const DEFAULT_CSV_IMPORTS_DIRECTORY_NAME: &str = "csv_imports";
+pub const DEFAULT_ANALYSIS_CACHE_DIRECTORY_NAME: &str = "analysis_cache";
+pub const DEFAULT_TEMPORARY_WORK_DIRECTORY_NAME: &str = "temporary_work";
+
+/// Largest accepted value for the row and value counts
+const MAXIMUM_CONFIGURED_COUNT: usize = 1_000_000;
+
+/// Tables and keys accepted in the configuration file
+const KNOWN_CONFIGURATION_KEYS: &[(&str, &[&str])] = &[
+    ("analysis", &["sample_rows_for_type_detection", "type_detection_threshold_percent"]),
+    ("display", &["top_value_count", "chart_glyphs", "color"]),
+    ("directories", &["root", "csv_imports", "analysis_cache", "temporary_work"]),
+];
+
+/// When console output is colored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConsoleColorMode {
+    /// Color when writing to a terminal, not headless and `NO_COLOR` is unset
+    Auto,
+
+    /// Always color, even when piped
+    Always,
+
+    /// Never color
+    Never,
+}
+
+/// Settings read from `rows_and_columns.toml` (or the built-in defaults)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplicationConfiguration {
+    /// Data rows sampled per column to detect its type
+    pub type_detection_sample_row_count: usize,
+
+    /// Share of sampled values (1-100 percent) that must agree on a type
+    pub type_detection_threshold_percent: usize,
+
+    /// Most frequent values listed for a categorical column
+    pub top_value_display_count: usize,
+
+    /// Character set for text charts
+    pub chart_glyph_mode: ChartGlyphMode,
+
+    /// When warnings and errors are colored
+    pub console_color_mode: ConsoleColorMode,
+
+    /// Name of the data directory created next to the executable
+    pub root_directory_name: String,
+
+    /// Name of the imported CSV subdirectory
+    pub csv_imports_directory_name: String,
+
+    /// Name of the analysis cache subdirectory
+    pub analysis_cache_directory_name: String,
+
+    /// Name of the temporary work subdirectory
+    pub temporary_work_directory_name: String,
+}
+
+impl Default for ApplicationConfiguration {
+    fn default() -> Self {
+        ApplicationConfiguration {
+            type_detection_sample_row_count: DEFAULT_TYPE_DETECTION_SAMPLE_ROW_COUNT,
+            type_detection_threshold_percent: DEFAULT_TYPE_DETECTION_THRESHOLD_PERCENT,
+            top_value_display_count: DEFAULT_TOP_VALUE_DISPLAY_COUNT,
+            chart_glyph_mode: ChartGlyphMode::Unicode,
+            console_color_mode: ConsoleColorMode::Auto,
+            root_directory_name: DEFAULT_ROOT_DIRECTORY_NAME.to_string(),
+            csv_imports_directory_name: DEFAULT_CSV_IMPORTS_DIRECTORY_NAME.to_string(),
+            analysis_cache_directory_name: DEFAULT_ANALYSIS_CACHE_DIRECTORY_NAME.to_string(),
+            temporary_work_directory_name: DEFAULT_TEMPORARY_WORK_DIRECTORY_NAME.to_string(),
+        }
+    }
+}
+
+/// Process-wide configuration (set once by `load_application_configuration`)
+static LOADED_APPLICATION_CONFIGURATION: OnceLock<Arc<ApplicationConfiguration>> = OnceLock::new();
+
+thread_local! {
+    /// Settings an embedding application passed for the calls running on this thread
+    static THREAD_APPLICATION_CONFIGURATION: RefCell<Option<Arc<ApplicationConfiguration>>> = const { RefCell::new(None) };
+}
+
+/// Restores the thread's previous settings when dropped, including when the
+/// closure panics
+struct ThreadApplicationConfigurationGuard {
+    previous_configuration: Option<Arc<ApplicationConfiguration>>,
+}
+
+impl Drop for ThreadApplicationConfigurationGuard {
+    fn drop(&mut self) {
+        let previous_configuration = self.previous_configuration.take();
+        THREAD_APPLICATION_CONFIGURATION.with(|thread_configuration| {
+            *thread_configuration.borrow_mut() = previous_configuration;
+        });
+    }
+}
+
+/// Returns the configuration in effect on this thread
+///
+/// Settings passed to `run_with_application_configuration` come first;
+/// otherwise this is the process configuration, which is the built-in default
+/// before `load_application_configuration` runs (and in unit tests).
+///
+/// # Returns
+/// * `Arc<ApplicationConfiguration>` - The active settings
+pub fn application_configuration() -> Arc<ApplicationConfiguration> {
+    THREAD_APPLICATION_CONFIGURATION
+        .with(|thread_configuration| thread_configuration.borrow().clone())
+        .unwrap_or_else(|| {
+            LOADED_APPLICATION_CONFIGURATION
+                .get_or_init(|| Arc::new(ApplicationConfiguration::default()))
+                .clone()
+        })
+}
+
+/// Runs a closure with the given settings in place of the process configuration
+///
+/// Used by the library API so that an embedding application chooses the
+/// settings instead of a `rows_and_columns.toml` next to its own executable.
+/// Only the calling thread is affected, and its previous settings are
+/// restored afterwards (even if the closure panics), so calls may nest.
+///
+/// # Arguments
+/// * `thread_configuration` - The settings to use
+/// * `configured_operation` - The work to run with them
+///
+/// # Returns
+/// * `T` - Whatever the closure returns
+pub fn run_with_application_configuration<T>(
+    thread_configuration: &Arc<ApplicationConfiguration>,
+    configured_operation: impl FnOnce() -> T,
+) -> T {
+    let _configuration_guard = ThreadApplicationConfigurationGuard {
+        previous_configuration: THREAD_APPLICATION_CONFIGURATION.with(|current_configuration| {
+            current_configuration.replace(Some(Arc::clone(thread_configuration)))
+        }),
+    };
+    configured_operation()
+}
+
+/// Finds, reads and validates the configuration file for this process
+///
+/// Called once at startup. Later calls keep the first configuration.
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success, or the first problem in the file
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the file named by
+///   `ROWS_AND_COLUMNS_CONFIG` does not exist, cannot be read, or contains
+///   malformed TOML, an unknown key or an invalid value
+pub fn load_application_configuration() -> RowsAndColumnsResult<()> {
+    // Step 1: Find the file (none means built-in defaults)
+    let loaded_configuration = match locate_application_configuration_file()? {
+        Some(configuration_file_path) => {
+            // Step 2: Read and validate it
+            let configuration_text = std::fs::read_to_string(&configuration_file_path).map_err(|io_error| {
+                create_configuration_error(&format!(
+                    "Cannot read configuration file {}: {}",
+                    configuration_file_path.display(),
+                    io_error
+                ))
+            })?;
+            parse_application_configuration(&configuration_text, &configuration_file_path.display().to_string())?
+        }
+        None => ApplicationConfiguration::default(),
+    };
+
+    // Step 3: Keep it for the rest of the process
+    let _ = LOADED_APPLICATION_CONFIGURATION.set(Arc::new(loaded_configuration));
+    Ok(())
+}
+
+/// Returns the configuration file to read, if any
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<PathBuf>>` - The file path, or None to use defaults
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If `ROWS_AND_COLUMNS_CONFIG`
+///   names a file that does not exist
+fn locate_application_configuration_file() -> RowsAndColumnsResult<Option<PathBuf>> {
+    // An explicit file from the environment must exist
+    if let Some(environment_path) = env::var_os(APPLICATION_CONFIGURATION_ENVIRONMENT_VARIABLE)
+        && !environment_path.is_empty()
+    {
+        let configuration_file_path = PathBuf::from(environment_path);
+        if !configuration_file_path.is_file() {
+            return Err(create_configuration_error(&format!(
+                "{} names a configuration file that does not exist: {}",
+                APPLICATION_CONFIGURATION_ENVIRONMENT_VARIABLE,
+                configuration_file_path.display()
+            )));
+        }
+        return Ok(Some(configuration_file_path));
+    }
+
+    // Otherwise the optional file next to the executable
+    match make_input_path_name_abs_executabledirectoryrelative_nocheck(APPLICATION_CONFIGURATION_FILE_NAME) {
+        Ok(configuration_file_path) if configuration_file_path.is_file() => Ok(Some(configuration_file_path)),
+        _ => Ok(None),
+    }
+}
+
+/// Parses and validates the text of a configuration file
+///
+/// Keys that are not present keep their default value.
+///
+/// # Arguments
+/// * `configuration_text` - The TOML text
+/// * `source_description` - File path used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ApplicationConfiguration>` - The validated settings
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the TOML is malformed, a
+///   table or key is unknown, or a value has the wrong type or range
+pub fn parse_application_configuration(
+    configuration_text: &str,
+    source_description: &str,
+) -> RowsAndColumnsResult<ApplicationConfiguration> {
+    // Step 1: Parse the TOML, reporting syntax errors as configuration errors
+    let configuration_document = parse_toml_document(configuration_text, source_description).map_err(|toml_error| {
+        match toml_error {
+            RowsAndColumnsError::MetadataError { metadata_operation_description, .. } => create_configuration_error(
+                &format!("{} in {}", metadata_operation_description, source_description)
+            ),
+            other_error => other_error,
+        }
+    })?;
+
+    // Step 2: Apply every entry on top of the defaults
+    let mut application_configuration = ApplicationConfiguration::default();
+    for configuration_table in &configuration_document.document_tables {
+        let known_table_keys = KNOWN_CONFIGURATION_KEYS
+            .iter()
+            .find(|(known_table_name, _)| *known_table_name == configuration_table.table_name)
+            .map(|(_, known_keys)| *known_keys);
+
+        match known_table_keys {
+            Some(known_keys) => {
+                for (entry_key, entry_value) in &configuration_table.table_entries {
+                    if !known_keys.contains(&entry_key.as_str()) {
+                        return Err(create_configuration_error(&format!(
+                            "Unknown key '{}' in [{}] of {} (expected one of: {})",
+                            entry_key,
+                            configuration_table.table_name,
+                            source_description,
+                            known_keys.join(", ")
+                        )));
+                    }
+                    apply_configuration_entry(
+                        &mut application_configuration,
+                        &configuration_table.table_name,
+                        entry_key,
+                        entry_value,
+                    )
+                    .map_err(|problem_description| {
+                        create_configuration_error(&format!(
+                            "Invalid value for {}.{} in {}: {}",
+                            configuration_table.table_name, entry_key, source_description, problem_description
+                        ))
+                    })?;
+                }
+            }
+            None if configuration_table.table_name.is_empty() => {
+                // Root table: only allowed to be empty
+                if let Some((entry_key, _)) = configuration_table.table_entries.first() {
+                    return Err(create_configuration_error(&format!(
+                        "Key '{}' in {} must be inside a table ([analysis], [display] or [directories])",
+                        entry_key, source_description
+                    )));
+                }
+            }
+            None => {
+                return Err(create_configuration_error(&format!(
+                    "Unknown table [{}] in {} (expected [analysis], [display] or [directories])",
+                    configuration_table.table_name, source_description
+                )));
+            }
+        }
+    }
+
+    Ok(application_configuration)
+}
+
+/// Stores one known key's value in the configuration
+///
+/// # Arguments
+/// * `application_configuration` - Settings being built
+/// * `table_name` - Table the key is in
+/// * `entry_key` - The key (already checked to be known for the table)
+/// * `entry_value` - The parsed value
+///
+/// # Returns
+/// * `Result<(), String>` - Success, or what is wrong with the value
+fn apply_configuration_entry(
+    application_configuration: &mut ApplicationConfiguration,
+    table_name: &str,
+    entry_key: &str,
+    entry_value: &TomlValue,
+) -> Result<(), String> {
+    match (table_name, entry_key) {
+        ("analysis", "sample_rows_for_type_detection") => {
+            application_configuration.type_detection_sample_row_count =
+                read_configured_count(entry_value, 1, MAXIMUM_CONFIGURED_COUNT)?;
+        }
+        ("analysis", "type_detection_threshold_percent") => {
+            application_configuration.type_detection_threshold_percent = read_configured_count(entry_value, 1, 100)?;
+        }
+        ("display", "top_value_count") => {
+            application_configuration.top_value_display_count =
+                read_configured_count(entry_value, 1, MAXIMUM_CONFIGURED_COUNT)?;
+        }
+        ("display", "chart_glyphs") => {
+            application_configuration.chart_glyph_mode = match read_configured_choice(entry_value, &["unicode", "ascii"])? {
+                "ascii" => ChartGlyphMode::Ascii,
+                _ => ChartGlyphMode::Unicode,
+            };
+        }
+        ("display", "color") => {
+            application_configuration.console_color_mode =
+                match read_configured_choice(entry_value, &["auto", "always", "never"])? {
+                    "always" => ConsoleColorMode::Always,
+                    "never" => ConsoleColorMode::Never,
+                    _ => ConsoleColorMode::Auto,
+                };
+        }
+        ("directories", "root") => {
+            application_configuration.root_directory_name = read_configured_directory_name(entry_value)?;
+        }
+        ("directories", "csv_imports") => {
+            application_configuration.csv_imports_directory_name = read_configured_directory_name(entry_value)?;
+        }
+        ("directories", "analysis_cache") => {
+            application_configuration.analysis_cache_directory_name = read_configured_directory_name(entry_value)?;
+        }
+        ("directories", "temporary_work") => {
+            application_configuration.temporary_work_directory_name = read_configured_directory_name(entry_value)?;
+        }
+        _ => return Err(String::from("unsupported key")),
+    }
+    Ok(())
+}
+
+/// Describes a value for a type-mismatch message
+///
+/// # Arguments
+/// * `entry_value` - The parsed value
+///
+/// # Returns
+/// * `String` - Its TOML type and value as written, e.g. `text "ten"` or `integer 10`
+fn describe_configured_value(entry_value: &TomlValue) -> String {
+    match entry_value {
+        TomlValue::Text(text_value) => format!("text \"{}\"", text_value),
+        TomlValue::Integer(integer_value) => format!("integer {}", integer_value),
+        TomlValue::Float(float_value) => format!("number {}", float_value),
+        TomlValue::Boolean(boolean_value) => format!("boolean {}", boolean_value),
+    }
+}
+
+/// Reads an integer setting within an inclusive range
+///
+/// # Arguments
+/// * `entry_value` - The parsed value
+/// * `minimum_count` - Smallest accepted value
+/// * `maximum_count` - Largest accepted value
+///
+/// # Returns
+/// * `Result<usize, String>` - The count, or what is wrong with it
+fn read_configured_count(entry_value: &TomlValue, minimum_count: usize, maximum_count: usize) -> Result<usize, String> {
+    let configured_integer = entry_value
+        .as_integer()
+        .ok_or_else(|| format!("expected an integer, found {}", describe_configured_value(entry_value)))?;
+
+    match usize::try_from(configured_integer) {
+        Ok(configured_count) if (minimum_count..=maximum_count).contains(&configured_count) => Ok(configured_count),
+        _ => Err(format!(
+            "{} is outside the allowed range {}-{}",
+            configured_integer, minimum_count, maximum_count
+        )),
+    }
+}
+
+/// Reads a string setting that must be one of a few words
+///
+/// # Arguments
+/// * `entry_value` - The parsed value
+/// * `allowed_choices` - Accepted words (lowercase)
+///
+/// # Returns
+/// * `Result<&'static str, String>` - The matching choice, or what is wrong
+fn read_configured_choice(entry_value: &TomlValue, allowed_choices: &[&'static str]) -> Result<&'static str, String> {
+    let configured_text = entry_value
+        .as_text()
+        .ok_or_else(|| format!("expected a string, found {}", describe_configured_value(entry_value)))?;
+
+    allowed_choices
+        .iter()
+        .find(|allowed_choice| allowed_choice.eq_ignore_ascii_case(configured_text.trim()))
+        .copied()
+        .ok_or_else(|| format!("'{}' is not one of: {}", configured_text, allowed_choices.join(", ")))
+}
+
+/// Reads a directory name setting (a single path component)
+///
+/// # Arguments
+/// * `entry_value` - The parsed value
+///
+/// # Returns
+/// * `Result<String, String>` - The name, or what is wrong with it
+fn read_configured_directory_name(entry_value: &TomlValue) -> Result<String, String> {
+    let directory_name = entry_value
+        .as_text()
+        .ok_or_else(|| format!("expected a string, found {}", describe_configured_value(entry_value)))?
+        .trim();
+
+    if directory_name.is_empty() || directory_name == "." || directory_name == ".." {
+        return Err(format!("'{}' is not a usable directory name", directory_name));
+    }
+    if directory_name.contains(['/', '\\']) {
+        return Err(format!("'{}' must be a single directory name without path separators", directory_name));
+    }
+
+    Ok(directory_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test an empty file yields the built-in defaults
+    #[test]
+    fn test_empty_configuration_uses_defaults() {
+        let parsed_configuration = parse_application_configuration("# nothing set\n", "test.toml").unwrap();
+        assert_eq!(parsed_configuration, ApplicationConfiguration::default());
+        assert_eq!(parsed_configuration.type_detection_sample_row_count, 10);
+        assert_eq!(parsed_configuration.type_detection_threshold_percent, 70);
+        assert_eq!(parsed_configuration.top_value_display_count, 5);
+        assert_eq!(parsed_configuration.root_directory_name, "rows_columns_data");
+    }
+
+    /// Test every key overrides its default
+    #[test]
+    fn test_configuration_overrides_defaults() {
+        let configuration_text = "[analysis]\n\
+            sample_rows_for_type_detection = 25\n\
+            type_detection_threshold_percent = 80\n\
+            [display]\n\
+            top_value_count = 12\n\
+            chart_glyphs = \"ASCII\"\n\
+            color = \"never\"\n\
+            [directories]\n\
+            root = \"my_data\"\n\
+            temporary_work = \"scratch\"\n";
+        let parsed_configuration = parse_application_configuration(configuration_text, "test.toml").unwrap();
+
+        assert_eq!(parsed_configuration.type_detection_sample_row_count, 25);
+        assert_eq!(parsed_configuration.type_detection_threshold_percent, 80);
+        assert_eq!(parsed_configuration.top_value_display_count, 12);
+        assert_eq!(parsed_configuration.chart_glyph_mode, ChartGlyphMode::Ascii);
+        assert_eq!(parsed_configuration.console_color_mode, ConsoleColorMode::Never);
+        assert_eq!(parsed_configuration.root_directory_name, "my_data");
+        assert_eq!(parsed_configuration.csv_imports_directory_name, "csv_imports");
+        assert_eq!(parsed_configuration.temporary_work_directory_name, "scratch");
+    }
+
+    /// Test unknown keys, bad values and malformed TOML are configuration errors
+    #[test]
+    fn test_invalid_configuration_is_rejected() {
+        let invalid_configuration_texts = [
+            "[analysis]\nsample_rows = 5\n",
+            "[analysis]\ntype_detection_threshold_percent = 150\n",
+            "[analysis]\nsample_rows_for_type_detection = \"ten\"\n",
+            "[display]\nchart_glyphs = \"braille\"\n",
+            "[directories]\nroot = \"../outside\"\n",
+            "[directories]\ncsv_imports = \"\"\n",
+            "[colours]\ncolor = \"auto\"\n",
+            "top_value_count = 3\n",
+            "[display\n",
+        ];
+
+        for invalid_configuration_text in invalid_configuration_texts {
+            match parse_application_configuration(invalid_configuration_text, "test.toml") {
+                Err(RowsAndColumnsError::ConfigurationError { configuration_issue_description }) => {
+                    assert!(configuration_issue_description.contains("test.toml"), "{}", configuration_issue_description);
+                }
+                other_result => panic!("Expected ConfigurationError for {:?}, got {:?}", invalid_configuration_text, other_result),
+            }
+        }
+    }
+
+    /// Test type mismatches name the value as the user wrote it
+    #[test]
+    fn test_type_mismatch_message_is_readable() {
+        for (mismatched_configuration_text, expected_description) in [
+            ("[analysis]\nsample_rows_for_type_detection = \"ten\"\n", "expected an integer, found text \"ten\""),
+            ("[display]\nchart_glyphs = 3\n", "expected a string, found integer 3"),
+            ("[directories]\nroot = true\n", "expected a string, found boolean true"),
+        ] {
+            match parse_application_configuration(mismatched_configuration_text, "test.toml") {
+                Err(RowsAndColumnsError::ConfigurationError { configuration_issue_description }) => {
+                    assert!(configuration_issue_description.contains(expected_description), "{}", configuration_issue_description);
+                    assert!(!configuration_issue_description.contains("Text("), "{}", configuration_issue_description);
+                }
+                other_result => panic!("Expected ConfigurationError for {:?}, got {:?}", mismatched_configuration_text, other_result),
+            }
+        }
+    }
+}
@@ -0,0 +1,844 @@
+// src/batch_analysis_module.rs
+
+/// Batch analysis of every CSV file in a directory for rows_and_columns
+///
+/// `rows_and_columns batch incoming/ --recursive` analyzes each file the
+/// file browser would mark as `[CSV]`, writes its `.csv_metadata.toml` and
+/// an analysis report, and finishes with `batch_index.md`: one line per
+/// file (rows, columns, schema, report, or the error that stopped it) and
+/// the schemas, with the files that share each one.
+///
+/// # Core Responsibilities
+/// - Find the CSV files of a directory, optionally in its subdirectories
+/// - Analyze each file, keeping going when one fails
+/// - Write one report per file into the batch output directory
+/// - Group files by identical schema (same column names, order and types)
+/// - Write the Markdown index and provide the summary as JSON
+///
+/// # Design Philosophy
+/// - One bad file is a line in the index, not the end of the batch
+/// - Same rules as single files: the browser's CSV test, the usual analysis,
+///   metadata next to the CSV and the usual report formats
+/// - Deterministic: files are processed and listed in path order
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import the analysis steps used for single files
+use super::csv_processor_module::{
+    CsvColumnDataType,
+    analyze_csv_file_structure_and_types,
+    perform_enhanced_statistical_analysis,
+};
+
+// Import report writing and the timestamp format used in reports
+use super::analysis_report_module::{
+    AnalysisReportFormat,
+    format_unix_timestamp_as_utc_text,
+    write_analysis_report_file,
+};
+
+// Import gzip spooling so .csv.gz files can be read more than once
+use super::csv_input_source_module::{
+    CsvInputSource,
+    MultiPassCsvInputFile,
+    resolve_csv_input_source,
+    spool_csv_input_to_work_file,
+};
+
+// Import the browser's CSV file name rule
+use super::file_system_bridge_module::is_csv_file_name;
+
+// Import silencing so per-file analysis output does not flood the batch output
+use super::console_output_module::run_with_silent_console_output;
+
+// Import JSON building for `batch --format json`
+use super::json_output_module::{ANALYSIS_JSON_SCHEMA_VERSION, JsonValue};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{RowsAndColumnsResult, create_file_system_error};
+
+/// File name of the summary index in the batch output directory
+pub const BATCH_INDEX_FILE_NAME: &str = "batch_index.md";
+
+/// Subdirectory of the batch output directory holding the per-file reports
+const BATCH_REPORTS_DIRECTORY_NAME: &str = "reports";
+
+/// What a batch run does besides analyzing
+#[derive(Debug, Clone)]
+pub struct BatchAnalysisOptions {
+    /// Whether subdirectories are searched too
+    pub include_subdirectories: bool,
+
+    /// Format of the per-file reports
+    pub report_format: AnalysisReportFormat,
+
+    /// Where the reports and the index are written
+    pub batch_output_directory: PathBuf,
+
+    /// Where compressed files are spooled while they are analyzed
+    pub temporary_work_directory: PathBuf,
+}
+
+/// One column of a file's schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSchemaColumn {
+    /// Column name from the header (or the generated name)
+    pub column_name: String,
+
+    /// Detected data type
+    pub detected_data_type: CsvColumnDataType,
+}
+
+/// Result of one file of the batch
+#[derive(Debug, Clone)]
+pub enum BatchFileOutcome {
+    /// The file was analyzed and its report written
+    Analyzed {
+        /// Number of data rows
+        total_data_row_count: usize,
+
+        /// Number of columns
+        total_column_count: usize,
+
+        /// Column names and types in file order
+        column_schema: Vec<BatchSchemaColumn>,
+
+        /// The written report
+        report_file_path: PathBuf,
+
+        /// The metadata file next to the CSV (None for compressed files, analyzed from a temporary copy)
+        metadata_file_path: Option<PathBuf>,
+
+        /// Label of the file's schema in the index ("A", "B", ...)
+        schema_label: String,
+    },
+
+    /// The analysis or the report failed
+    Failed {
+        /// The error, as shown to the user
+        error_message: String,
+    },
+}
+
+/// One analyzed (or failed) file of the batch
+#[derive(Debug, Clone)]
+pub struct BatchFileResult {
+    /// Path of the CSV file
+    pub csv_file_path: PathBuf,
+
+    /// Path relative to the batch directory, used in the index
+    pub relative_file_name: String,
+
+    /// What happened to the file
+    pub file_outcome: BatchFileOutcome,
+}
+
+/// A schema and the files that have it
+#[derive(Debug, Clone)]
+pub struct BatchSchemaGroup {
+    /// Label used in the index ("A", "B", ...), in order of first appearance
+    pub schema_label: String,
+
+    /// Column names and types in file order
+    pub column_schema: Vec<BatchSchemaColumn>,
+
+    /// Relative names of the files with this schema
+    pub member_file_names: Vec<String>,
+}
+
+/// Everything a batch run produced
+#[derive(Debug, Clone)]
+pub struct BatchAnalysisSummary {
+    /// The directory that was analyzed
+    pub batch_directory_path: PathBuf,
+
+    /// One result per CSV file, in path order
+    pub file_results: Vec<BatchFileResult>,
+
+    /// Distinct schemas of the analyzed files
+    pub schema_groups: Vec<BatchSchemaGroup>,
+
+    /// The written `batch_index.md`
+    pub index_file_path: PathBuf,
+}
+
+impl BatchAnalysisSummary {
+    /// Number of files analyzed without error
+    pub fn analyzed_file_count(&self) -> usize {
+        self.file_results.iter()
+            .filter(|file_result| matches!(file_result.file_outcome, BatchFileOutcome::Analyzed { .. }))
+            .count()
+    }
+
+    /// Number of files that failed
+    pub fn failed_file_count(&self) -> usize {
+        self.file_results.len() - self.analyzed_file_count()
+    }
+}
+
+/// Finds the CSV files of a directory
+///
+/// Dot files and dot directories are skipped, as in the file browser.
+///
+/// # Arguments
+/// * `batch_directory_path` - The directory to search
+/// * `include_subdirectories` - Whether to search subdirectories too
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<PathBuf>>` - The CSV files, sorted by path
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If a directory cannot be read
+pub fn find_batch_csv_files(
+    batch_directory_path: &Path,
+    include_subdirectories: bool,
+) -> RowsAndColumnsResult<Vec<PathBuf>> {
+    let mut csv_file_paths = Vec::new();
+    let mut pending_directory_paths = vec![batch_directory_path.to_path_buf()];
+
+    while let Some(directory_path) = pending_directory_paths.pop() {
+        let directory_entries = std::fs::read_dir(&directory_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to read directory: {}", directory_path.display()), io_error)
+        })?;
+        for entry_result in directory_entries {
+            let directory_entry = entry_result.map_err(|io_error| {
+                create_file_system_error(
+                    &format!("Failed to process directory entry in: {}", directory_path.display()),
+                    io_error
+                )
+            })?;
+            let entry_name = directory_entry.file_name().to_string_lossy().to_string();
+            if entry_name.starts_with('.') {
+                continue;
+            }
+            // Symlinked directories are not followed, so links cannot loop the search
+            let entry_path = directory_entry.path();
+            let is_real_directory = directory_entry.file_type().map(|file_type| file_type.is_dir()).unwrap_or(false);
+            if is_real_directory {
+                if include_subdirectories {
+                    pending_directory_paths.push(entry_path);
+                }
+            } else if entry_path.is_file() && is_csv_file_name(&entry_name) {
+                csv_file_paths.push(entry_path);
+            }
+        }
+    }
+
+    csv_file_paths.sort();
+    Ok(csv_file_paths)
+}
+
+/// Analyzes every CSV file of a directory and writes the reports and the index
+///
+/// # Arguments
+/// * `batch_directory_path` - The directory to analyze
+/// * `batch_analysis_options` - Recursion, report format and output directories
+///
+/// # Returns
+/// * `RowsAndColumnsResult<BatchAnalysisSummary>` - Per-file results and schema groups
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the directory cannot be searched or
+///   the output directory or index cannot be written (failures of single files
+///   are recorded in the summary instead)
+pub fn run_batch_analysis(
+    batch_directory_path: &Path,
+    batch_analysis_options: &BatchAnalysisOptions,
+) -> RowsAndColumnsResult<BatchAnalysisSummary> {
+    // Step 1: Find the files and prepare the output directory
+    let csv_file_paths = find_batch_csv_files(batch_directory_path, batch_analysis_options.include_subdirectories)?;
+    let reports_directory = batch_analysis_options.batch_output_directory.join(BATCH_REPORTS_DIRECTORY_NAME);
+    std::fs::create_dir_all(&reports_directory).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create batch output directory: {}", reports_directory.display()), io_error)
+    })?;
+
+    // Step 2: Analyze each file; a failure is recorded and the batch goes on
+    let mut file_results = Vec::new();
+    let mut schema_groups: Vec<BatchSchemaGroup> = Vec::new();
+    let mut used_report_file_stems = HashSet::new();
+    for csv_file_path in csv_file_paths {
+        let relative_file_name = csv_file_path.strip_prefix(batch_directory_path)
+            .unwrap_or(&csv_file_path)
+            .to_string_lossy()
+            .to_string();
+        let report_file_path = reports_directory.join(format!(
+            "{}_analysis_report.{}",
+            claim_unused_report_file_stem(&mut used_report_file_stems, flatten_relative_file_name(&relative_file_name)),
+            batch_analysis_options.report_format.file_extension()
+        ));
+        let file_outcome = match analyze_batch_csv_file(&csv_file_path, &report_file_path, batch_analysis_options) {
+            Ok(mut file_outcome) => {
+                if let BatchFileOutcome::Analyzed { column_schema, schema_label, .. } = &mut file_outcome {
+                    *schema_label = assign_schema_group(&mut schema_groups, column_schema, &relative_file_name);
+                }
+                file_outcome
+            }
+            Err(analysis_error) => BatchFileOutcome::Failed { error_message: analysis_error.to_string() },
+        };
+        file_results.push(BatchFileResult { csv_file_path, relative_file_name, file_outcome });
+    }
+
+    // Step 3: Write the index
+    let batch_analysis_summary = BatchAnalysisSummary {
+        batch_directory_path: batch_directory_path.to_path_buf(),
+        file_results,
+        schema_groups,
+        index_file_path: batch_analysis_options.batch_output_directory.join(BATCH_INDEX_FILE_NAME),
+    };
+    let generated_seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let index_markdown = format_batch_index_markdown(&batch_analysis_summary, &batch_analysis_options.batch_output_directory, generated_seconds);
+    std::fs::write(&batch_analysis_summary.index_file_path, index_markdown).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write batch index: {}", batch_analysis_summary.index_file_path.display()),
+            io_error
+        )
+    })?;
+    Ok(batch_analysis_summary)
+}
+
+/// Analyzes one file and writes its report
+///
+/// Compressed files are analyzed from a temporary decompressed copy; the
+/// metadata written next to that copy goes away with it.
+///
+/// # Arguments
+/// * `csv_file_path` - The CSV file
+/// * `report_file_path` - Where its report goes
+/// * `batch_analysis_options` - Report format and work directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<BatchFileOutcome>` - `Analyzed`, with the schema label still empty
+fn analyze_batch_csv_file(
+    csv_file_path: &Path,
+    report_file_path: &Path,
+    batch_analysis_options: &BatchAnalysisOptions,
+) -> RowsAndColumnsResult<BatchFileOutcome> {
+    run_with_silent_console_output(|| {
+        let csv_input_file = match resolve_csv_input_source(&csv_file_path.to_string_lossy()) {
+            CsvInputSource::RegularFile(regular_file_path) => MultiPassCsvInputFile::OriginalFile(regular_file_path),
+            read_once_source => MultiPassCsvInputFile::Spooled(spool_csv_input_to_work_file(
+                &read_once_source,
+                &batch_analysis_options.temporary_work_directory
+            )?),
+        };
+        let csv_analysis_results = analyze_csv_file_structure_and_types(csv_input_file.csv_file_path())?;
+        let enhanced_analysis_results = perform_enhanced_statistical_analysis(
+            csv_input_file.csv_file_path(),
+            &csv_analysis_results
+        )?;
+        write_analysis_report_file(
+            &csv_analysis_results,
+            &enhanced_analysis_results,
+            batch_analysis_options.report_format,
+            report_file_path,
+        )?;
+
+        Ok(BatchFileOutcome::Analyzed {
+            total_data_row_count: csv_analysis_results.total_data_row_count,
+            total_column_count: csv_analysis_results.total_column_count,
+            column_schema: csv_analysis_results.column_information_list.iter()
+                .map(|column_information| BatchSchemaColumn {
+                    column_name: column_information.column_name.clone(),
+                    detected_data_type: column_information.detected_data_type.clone(),
+                })
+                .collect(),
+            report_file_path: report_file_path.to_path_buf(),
+            metadata_file_path: match csv_input_file {
+                MultiPassCsvInputFile::OriginalFile(_) => Some(csv_analysis_results.metadata_file_path.clone()),
+                MultiPassCsvInputFile::Spooled(_) => None,
+            },
+            schema_label: String::new(),
+        })
+    })
+}
+
+/// Adds a file to the group of its schema, starting a new group if needed
+///
+/// # Arguments
+/// * `schema_groups` - Groups found so far
+/// * `column_schema` - The file's column names and types
+/// * `relative_file_name` - The file, as listed in the index
+///
+/// # Returns
+/// * `String` - The label of the file's schema group
+fn assign_schema_group(
+    schema_groups: &mut Vec<BatchSchemaGroup>,
+    column_schema: &[BatchSchemaColumn],
+    relative_file_name: &str,
+) -> String {
+    if let Some(existing_group) = schema_groups.iter_mut().find(|schema_group| schema_group.column_schema == column_schema) {
+        existing_group.member_file_names.push(relative_file_name.to_string());
+        return existing_group.schema_label.clone();
+    }
+    let schema_label = format_schema_group_label(schema_groups.len());
+    schema_groups.push(BatchSchemaGroup {
+        schema_label: schema_label.clone(),
+        column_schema: column_schema.to_vec(),
+        member_file_names: vec![relative_file_name.to_string()],
+    });
+    schema_label
+}
+
+/// Labels schema groups A, B, ..., Z, AA, AB, ...
+///
+/// # Arguments
+/// * `group_position` - 0-based position of the group
+///
+/// # Returns
+/// * `String` - The label
+fn format_schema_group_label(group_position: usize) -> String {
+    let mut remaining_position = group_position;
+    let mut label_characters = Vec::new();
+    loop {
+        label_characters.push((b'A' + (remaining_position % 26) as u8) as char);
+        if remaining_position < 26 {
+            break;
+        }
+        remaining_position = remaining_position / 26 - 1;
+    }
+    label_characters.iter().rev().collect()
+}
+
+/// Turns a relative path into a report file stem ("2024/sales.csv.gz" → "2024__sales")
+///
+/// # Arguments
+/// * `relative_file_name` - Path relative to the batch directory
+///
+/// # Returns
+/// * `String` - A file name stem without directory separators or CSV extensions
+fn flatten_relative_file_name(relative_file_name: &str) -> String {
+    let lowercase_file_name = relative_file_name.to_lowercase();
+    let extension_length = [".csv.gz", ".tsv.gz", ".csv", ".tsv"]
+        .iter()
+        .find(|csv_extension| lowercase_file_name.ends_with(*csv_extension))
+        .map(|csv_extension| csv_extension.len())
+        .unwrap_or(0);
+    relative_file_name[..relative_file_name.len() - extension_length]
+        .replace(['/', '\\'], "__")
+}
+
+/// Picks a report file stem no earlier file of the batch has used
+///
+/// "sales.csv", "sales.tsv" and "sales.csv.gz" all flatten to "sales"; the
+/// later ones get "sales_2", "sales_3", ... so no report overwrites another.
+/// Stems are compared without case for case-insensitive file systems.
+///
+/// # Arguments
+/// * `used_report_file_stems` - Lowercased stems taken so far (the result is added)
+/// * `flattened_file_stem` - The stem from `flatten_relative_file_name`
+///
+/// # Returns
+/// * `String` - The stem itself, or the stem with the first free number appended
+fn claim_unused_report_file_stem(used_report_file_stems: &mut HashSet<String>, flattened_file_stem: String) -> String {
+    let mut candidate_file_stem = flattened_file_stem.clone();
+    let mut duplicate_number = 1;
+    while !used_report_file_stems.insert(candidate_file_stem.to_lowercase()) {
+        duplicate_number += 1;
+        candidate_file_stem = format!("{}_{}", flattened_file_stem, duplicate_number);
+    }
+    candidate_file_stem
+}
+
+/// Formats the summary index as Markdown
+///
+/// # Arguments
+/// * `batch_analysis_summary` - Results of the batch
+/// * `batch_output_directory` - Directory of the index (report links are relative to it)
+/// * `generated_seconds` - Unix time shown as the generation time
+///
+/// # Returns
+/// * `String` - The Markdown text of `batch_index.md`
+pub fn format_batch_index_markdown(
+    batch_analysis_summary: &BatchAnalysisSummary,
+    batch_output_directory: &Path,
+    generated_seconds: u64,
+) -> String {
+    let escape_cell = |cell_text: &str| cell_text.replace('|', "\\|");
+    let shared_schema_count = batch_analysis_summary.schema_groups.iter()
+        .filter(|schema_group| schema_group.member_file_names.len() > 1)
+        .count();
+
+    // Step 1: Title and totals
+    let mut index_markdown = format!("# Batch analysis: {}\n\n", batch_analysis_summary.batch_directory_path.display());
+    index_markdown.push_str(&format!("Generated {}.\n\n", format_unix_timestamp_as_utc_text(generated_seconds)));
+    index_markdown.push_str(&format!(
+        "{} CSV files: {} analyzed, {} failed. {} distinct schemas, {} shared by more than one file.\n\n",
+        batch_analysis_summary.file_results.len(),
+        batch_analysis_summary.analyzed_file_count(),
+        batch_analysis_summary.failed_file_count(),
+        batch_analysis_summary.schema_groups.len(),
+        shared_schema_count
+    ));
+
+    // Step 2: One row per file
+    index_markdown.push_str("## Files\n\n");
+    index_markdown.push_str("| File | Rows | Columns | Schema | Report | Status |\n");
+    index_markdown.push_str("| --- | ---: | ---: | --- | --- | --- |\n");
+    for file_result in &batch_analysis_summary.file_results {
+        let file_cell = escape_cell(&file_result.relative_file_name);
+        match &file_result.file_outcome {
+            BatchFileOutcome::Analyzed {
+                total_data_row_count,
+                total_column_count,
+                report_file_path,
+                metadata_file_path,
+                schema_label,
+                ..
+            } => {
+                let report_link_target = report_file_path.strip_prefix(batch_output_directory)
+                    .unwrap_or(report_file_path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                // Spaces and parentheses end a plain Markdown link target early
+                let report_link_target = if report_link_target.contains([' ', '(', ')']) {
+                    format!("<{}>", escape_cell(&report_link_target))
+                } else {
+                    escape_cell(&report_link_target)
+                };
+                let status_text = if metadata_file_path.is_some() { "ok" } else { "ok (compressed; metadata not kept)" };
+                index_markdown.push_str(&format!(
+                    "| {} | {} | {} | {} | [report]({}) | {} |\n",
+                    file_cell, total_data_row_count, total_column_count, schema_label, report_link_target, status_text
+                ));
+            }
+            BatchFileOutcome::Failed { error_message } => {
+                index_markdown.push_str(&format!(
+                    "| {} |  |  |  |  | error: {} |\n",
+                    file_cell,
+                    escape_cell(&error_message.replace('\n', " "))
+                ));
+            }
+        }
+    }
+    index_markdown.push('\n');
+
+    // Step 3: The schemas, with the files sharing each one
+    index_markdown.push_str("## Schemas\n\n");
+    if batch_analysis_summary.schema_groups.is_empty() {
+        index_markdown.push_str("No file was analyzed.\n\n");
+    }
+    for schema_group in &batch_analysis_summary.schema_groups {
+        index_markdown.push_str(&format!(
+            "### Schema {} ({} {})\n\n",
+            schema_group.schema_label,
+            schema_group.member_file_names.len(),
+            if schema_group.member_file_names.len() == 1 { "file" } else { "files, identical schema" }
+        ));
+        for member_file_name in &schema_group.member_file_names {
+            index_markdown.push_str(&format!("- {}\n", member_file_name));
+        }
+        index_markdown.push_str("\n| # | Column | Type |\n| ---: | --- | --- |\n");
+        for (column_position, schema_column) in schema_group.column_schema.iter().enumerate() {
+            index_markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                column_position + 1,
+                escape_cell(&schema_column.column_name),
+                schema_column.detected_data_type.to_toml_string()
+            ));
+        }
+        index_markdown.push('\n');
+    }
+
+    // Step 4: The errors again, in full
+    let failed_file_results: Vec<&BatchFileResult> = batch_analysis_summary.file_results.iter()
+        .filter(|file_result| matches!(file_result.file_outcome, BatchFileOutcome::Failed { .. }))
+        .collect();
+    if !failed_file_results.is_empty() {
+        index_markdown.push_str("## Errors\n\n");
+        for file_result in failed_file_results {
+            if let BatchFileOutcome::Failed { error_message } = &file_result.file_outcome {
+                index_markdown.push_str(&format!("- {}: {}\n", file_result.relative_file_name, error_message));
+            }
+        }
+        index_markdown.push('\n');
+    }
+
+    index_markdown
+}
+
+/// Converts the batch summary to a schema-versioned JSON document
+///
+/// # Arguments
+/// * `batch_analysis_summary` - Results of the batch
+///
+/// # Returns
+/// * `JsonValue` - `{ "schema_version", "status", "directory", "index_file", "files", "schemas" }`
+pub fn batch_summary_to_json(batch_analysis_summary: &BatchAnalysisSummary) -> JsonValue {
+    let schema_to_json = |column_schema: &[BatchSchemaColumn]| {
+        JsonValue::Array(column_schema.iter()
+            .map(|schema_column| JsonValue::object(vec![
+                ("name", JsonValue::Text(schema_column.column_name.clone())),
+                ("data_type", JsonValue::Text(schema_column.detected_data_type.to_toml_string().to_string())),
+            ]))
+            .collect())
+    };
+    let file_values = batch_analysis_summary.file_results.iter()
+        .map(|file_result| {
+            let mut file_entries = vec![
+                ("path", JsonValue::Text(file_result.csv_file_path.display().to_string())),
+                ("relative_path", JsonValue::Text(file_result.relative_file_name.clone())),
+            ];
+            match &file_result.file_outcome {
+                BatchFileOutcome::Analyzed {
+                    total_data_row_count,
+                    total_column_count,
+                    report_file_path,
+                    metadata_file_path,
+                    schema_label,
+                    ..
+                } => file_entries.extend([
+                    ("status", JsonValue::Text("ok".to_string())),
+                    ("total_data_rows", JsonValue::from_count(*total_data_row_count)),
+                    ("total_columns", JsonValue::from_count(*total_column_count)),
+                    ("schema", JsonValue::Text(schema_label.clone())),
+                    ("report_file_path", JsonValue::Text(report_file_path.display().to_string())),
+                    ("metadata_file_path", metadata_file_path.as_ref()
+                        .map(|metadata_path| JsonValue::Text(metadata_path.display().to_string()))
+                        .unwrap_or(JsonValue::Null)),
+                ]),
+                BatchFileOutcome::Failed { error_message } => file_entries.extend([
+                    ("status", JsonValue::Text("error".to_string())),
+                    ("error", JsonValue::Text(error_message.clone())),
+                ]),
+            }
+            JsonValue::object(file_entries)
+        })
+        .collect();
+    let schema_values = batch_analysis_summary.schema_groups.iter()
+        .map(|schema_group| JsonValue::object(vec![
+            ("label", JsonValue::Text(schema_group.schema_label.clone())),
+            ("columns", schema_to_json(&schema_group.column_schema)),
+            ("files", JsonValue::Array(schema_group.member_file_names.iter().cloned().map(JsonValue::Text).collect())),
+        ]))
+        .collect();
+
+    JsonValue::object(vec![
+        ("schema_version", JsonValue::Integer(ANALYSIS_JSON_SCHEMA_VERSION)),
+        ("status", JsonValue::Text("ok".to_string())),
+        ("directory", JsonValue::Text(batch_analysis_summary.batch_directory_path.display().to_string())),
+        ("index_file_path", JsonValue::Text(batch_analysis_summary.index_file_path.display().to_string())),
+        ("analyzed_files", JsonValue::from_count(batch_analysis_summary.analyzed_file_count())),
+        ("failed_files", JsonValue::from_count(batch_analysis_summary.failed_file_count())),
+        ("files", JsonValue::Array(file_values)),
+        ("schemas", JsonValue::Array(schema_values)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::gzip_decompression_module::update_crc32_checksum;
+
+    /// Creates a batch directory with the given files (relative path, content)
+    fn create_batch_test_directory(test_name: &str, test_files: &[(&str, &str)]) -> PathBuf {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_batch_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        for (relative_file_name, file_text) in test_files {
+            let test_file_path = test_directory.join("incoming").join(relative_file_name);
+            if let Some(parent_directory) = test_file_path.parent() {
+                std::fs::create_dir_all(parent_directory).expect("temp dir should be created");
+            }
+            std::fs::write(&test_file_path, file_text).expect("test file should be writable");
+        }
+        test_directory
+    }
+
+    /// Builds options writing into the test directory
+    fn create_batch_test_options(test_directory: &Path, include_subdirectories: bool) -> BatchAnalysisOptions {
+        BatchAnalysisOptions {
+            include_subdirectories,
+            report_format: AnalysisReportFormat::Markdown,
+            batch_output_directory: test_directory.join("batch_output"),
+            temporary_work_directory: test_directory.join("work"),
+        }
+    }
+
+    /// Test file discovery follows the browser's CSV rule and the recursion switch
+    #[test]
+    fn test_find_batch_csv_files() {
+        let test_directory = create_batch_test_directory("find", &[
+            ("b.csv", "x\n1\n"),
+            ("a.TSV", "x\n1\n"),
+            ("notes.txt", "not a csv"),
+            (".hidden.csv", "x\n1\n"),
+            ("2024/c.csv.gz", "not really gzip"),
+        ]);
+        let batch_directory = test_directory.join("incoming");
+
+        let top_level_names: Vec<String> = find_batch_csv_files(&batch_directory, false)
+            .expect("search should succeed")
+            .iter()
+            .map(|csv_file_path| csv_file_path.strip_prefix(&batch_directory).expect("inside").to_string_lossy().to_string())
+            .collect();
+        assert_eq!(top_level_names, vec!["a.TSV", "b.csv"]);
+        assert_eq!(find_batch_csv_files(&batch_directory, true).expect("search should succeed").len(), 3);
+
+        assert_eq!(flatten_relative_file_name("2024/c.csv.gz"), "2024__c");
+        assert_eq!(format_schema_group_label(0), "A");
+        assert_eq!(format_schema_group_label(27), "AB");
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test a batch groups identical schemas, records failures and writes the index
+    #[test]
+    fn test_batch_groups_schemas_and_records_failures() {
+        let test_directory = create_batch_test_directory("run", &[
+            ("january.csv", "region,amount\nnorth,10\nsouth,20\n"),
+            ("february.csv", "region,amount\neast,30\nwest,40\nnorth,50\n"),
+            ("customers.csv", "id,name\n1,Ann\n2,Bo\n"),
+            ("archive/broken.csv.gz", "this is not gzip data"),
+        ]);
+        let batch_directory = test_directory.join("incoming");
+        let batch_analysis_options = create_batch_test_options(&test_directory, true);
+
+        let batch_analysis_summary = run_batch_analysis(&batch_directory, &batch_analysis_options)
+            .expect("batch should run even with a broken file");
+        assert_eq!(batch_analysis_summary.file_results.len(), 4);
+        assert_eq!(batch_analysis_summary.analyzed_file_count(), 3);
+        assert_eq!(batch_analysis_summary.failed_file_count(), 1);
+
+        // Files are in path order: archive/broken, customers, february, january
+        assert!(matches!(batch_analysis_summary.file_results[0].file_outcome, BatchFileOutcome::Failed { .. }));
+        let shared_group = batch_analysis_summary.schema_groups.iter()
+            .find(|schema_group| schema_group.member_file_names.len() == 2)
+            .expect("january and february share a schema");
+        assert_eq!(shared_group.member_file_names, vec!["february.csv", "january.csv"]);
+        assert_eq!(batch_analysis_summary.schema_groups.len(), 2);
+
+        // Metadata next to each CSV, a report per file, and the index
+        assert!(batch_directory.join("january.csv_metadata.toml").exists());
+        assert!(batch_analysis_options.batch_output_directory.join("reports").join("january_analysis_report.md").exists());
+        let index_markdown = std::fs::read_to_string(&batch_analysis_summary.index_file_path).expect("index should exist");
+        assert!(index_markdown.contains("| january.csv | 2 | 2 | B | [report](reports/january_analysis_report.md) | ok |"));
+        assert!(index_markdown.contains("### Schema B (2 files, identical schema)"));
+        assert!(index_markdown.contains("## Errors"));
+
+        let batch_json_text = batch_summary_to_json(&batch_analysis_summary).to_json_text(false);
+        assert!(batch_json_text.contains("\"failed_files\":1"));
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test a compressed file is analyzed from a temporary copy
+    #[test]
+    fn test_batch_analyzes_gzip_files_without_keeping_metadata() {
+        // gzip of "a,b\n1,2\n3,4\n" (stored block, no compression)
+        let gzip_bytes: Vec<u8> = {
+            let csv_bytes = b"a,b\n1,2\n3,4\n";
+            let mut gzip_bytes = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+            gzip_bytes.push(1);
+            gzip_bytes.extend_from_slice(&(csv_bytes.len() as u16).to_le_bytes());
+            gzip_bytes.extend_from_slice(&(!(csv_bytes.len() as u16)).to_le_bytes());
+            gzip_bytes.extend_from_slice(csv_bytes);
+            gzip_bytes.extend_from_slice(&update_crc32_checksum(0, csv_bytes).to_le_bytes());
+            gzip_bytes.extend_from_slice(&(csv_bytes.len() as u32).to_le_bytes());
+            gzip_bytes
+        };
+        let test_directory = create_batch_test_directory("gzip", &[]);
+        let batch_directory = test_directory.join("incoming");
+        std::fs::create_dir_all(&batch_directory).expect("temp dir should be created");
+        std::fs::write(batch_directory.join("values.csv.gz"), gzip_bytes).expect("test file should be writable");
+
+        let batch_analysis_summary = run_batch_analysis(&batch_directory, &create_batch_test_options(&test_directory, false))
+            .expect("batch should run");
+        match &batch_analysis_summary.file_results[0].file_outcome {
+            BatchFileOutcome::Analyzed { total_data_row_count, metadata_file_path, .. } => {
+                assert_eq!(*total_data_row_count, 2);
+                assert!(metadata_file_path.is_none());
+            }
+            BatchFileOutcome::Failed { error_message } => panic!("gzip file should be analyzed: {}", error_message),
+        }
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test clashing report names, odd file names, type-only schema changes and empty inputs
+    #[test]
+    fn test_batch_with_clashing_names_and_empty_files() {
+        // gzip of "a,b\n1,2\n" (stored block, no compression)
+        let gzip_payload = b"a,b\n1,2\n";
+        let mut gzip_bytes = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff, 0x01];
+        gzip_bytes.extend_from_slice(&(gzip_payload.len() as u16).to_le_bytes());
+        gzip_bytes.extend_from_slice(&(!(gzip_payload.len() as u16)).to_le_bytes());
+        gzip_bytes.extend_from_slice(gzip_payload);
+        gzip_bytes.extend_from_slice(&update_crc32_checksum(0, gzip_payload).to_le_bytes());
+        gzip_bytes.extend_from_slice(&(gzip_payload.len() as u32).to_le_bytes());
+
+        let test_directory = create_batch_test_directory("clashing", &[
+            ("sales.csv", "a,b\nnorth,10\nsouth,20\n"),
+            ("sales.tsv", "a,b\neast,30\nwest,40\n"),
+            ("SALES_2.csv", "a,b\nnorth,50\nwest,60\n"),
+            ("2024/q1.csv", "a,b\n1.5,10\n2.5,20\n"),
+            ("2024__q1.csv", "a,b\n3.5,30\n4.5,40\n"),
+            ("odd | name (copy).csv", "pipe|column,b\nnorth,10\nsouth,20\n"),
+            ("empty.csv", ""),
+            ("header_only.csv", "a,b\n"),
+        ]);
+        let batch_directory = test_directory.join("incoming");
+        std::fs::write(batch_directory.join("sales.csv.gz"), &gzip_bytes).expect("gzip file should be writable");
+        let batch_analysis_options = create_batch_test_options(&test_directory, true);
+
+        let batch_analysis_summary = run_batch_analysis(&batch_directory, &batch_analysis_options)
+            .expect("batch should run");
+        assert_eq!(batch_analysis_summary.file_results.len(), 9);
+
+        // Every analyzed file has a report of its own, and none was overwritten
+        let report_file_paths: Vec<&PathBuf> = batch_analysis_summary.file_results.iter()
+            .filter_map(|file_result| match &file_result.file_outcome {
+                BatchFileOutcome::Analyzed { report_file_path, .. } => Some(report_file_path),
+                BatchFileOutcome::Failed { .. } => None,
+            })
+            .collect();
+        let distinct_report_names: HashSet<String> = report_file_paths.iter()
+            .map(|report_file_path| report_file_path.to_string_lossy().to_lowercase())
+            .collect();
+        assert_eq!(distinct_report_names.len(), report_file_paths.len());
+        for report_file_path in &report_file_paths {
+            assert!(report_file_path.exists(), "missing report {}", report_file_path.display());
+        }
+        assert_eq!(flatten_relative_file_name("2024/q1.csv"), flatten_relative_file_name("2024__q1.csv"));
+
+        // Same column names with a different type are a different schema
+        let text_sales_label = batch_analysis_summary.file_results.iter()
+            .find(|file_result| file_result.relative_file_name == "sales.csv")
+            .and_then(|file_result| match &file_result.file_outcome {
+                BatchFileOutcome::Analyzed { schema_label, .. } => Some(schema_label.clone()),
+                BatchFileOutcome::Failed { .. } => None,
+            })
+            .expect("sales.csv should be analyzed");
+        let decimal_group = batch_analysis_summary.schema_groups.iter()
+            .find(|schema_group| schema_group.member_file_names.iter().any(|member_file_name| member_file_name.ends_with("q1.csv")))
+            .expect("q1 files should have a schema group");
+        assert_ne!(decimal_group.schema_label, text_sales_label);
+        assert_eq!(decimal_group.member_file_names.len(), 2);
+
+        // Pipes are escaped and odd report links are wrapped in angle brackets
+        let index_markdown = std::fs::read_to_string(&batch_analysis_summary.index_file_path).expect("index should exist");
+        assert!(index_markdown.contains("| odd \\| name (copy).csv |"));
+        assert!(index_markdown.contains("[report](<reports/odd \\| name (copy)_analysis_report.md>)"));
+        assert!(index_markdown.contains("| 1 | pipe\\|column |"));
+        for index_line in index_markdown.lines().filter(|index_line| index_line.starts_with("| ") && index_line.contains(".csv")) {
+            assert_eq!(index_line.replace("\\|", "").matches('|').count(), 7, "broken table row: {}", index_line);
+        }
+
+        // An empty directory still gets an index
+        let empty_directory = test_directory.join("nothing_here");
+        std::fs::create_dir_all(&empty_directory).expect("temp dir should be created");
+        let empty_batch_options = BatchAnalysisOptions {
+            batch_output_directory: test_directory.join("empty_output"),
+            ..create_batch_test_options(&test_directory, false)
+        };
+        let empty_batch_summary = run_batch_analysis(&empty_directory, &empty_batch_options).expect("empty batch should run");
+        assert!(empty_batch_summary.file_results.is_empty());
+        let empty_index_markdown = std::fs::read_to_string(&empty_batch_summary.index_file_path).expect("index should exist");
+        assert!(empty_index_markdown.contains("0 CSV files: 0 analyzed, 0 failed."));
+        assert!(empty_index_markdown.contains("No file was analyzed."));
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+}
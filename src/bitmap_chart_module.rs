@@ -0,0 +1,715 @@
+// src/bitmap_chart_module.rs
+
+/// Bitmap (PPM and PNG) chart output for rows_and_columns
+///
+/// Some downstream tools only accept raster images. This module rasterizes the
+/// same `ChartDrawing` layout that the SVG writer uses and encodes the pixels
+/// as binary PPM or as PNG, using nothing beyond the standard library, so
+/// charts can be produced on headless servers.
+///
+/// # Core Responsibilities
+/// - Rasterize rectangles, lines, circles (with opacity) and text into an RGB image
+/// - Draw text with a built-in 5×7 bitmap font (printable ASCII)
+/// - Encode images as binary PPM (`P6`) and as PNG (stored deflate blocks with
+///   CRC-32 and Adler-32 checksums)
+/// - Write chart bitmap files
+///
+/// # Design Philosophy
+/// - Layout lives in one place: the rasterizer only paints primitives, so a
+///   PNG shows the same axes, ticks and labels as the SVG of the same chart
+/// - Uncompressed PNG is larger than necessary but is valid everywhere and
+///   keeps the encoder small enough to verify by eye
+use std::path::Path;
+
+// Import the shared chart layout
+use super::chart_rendering_module::ChartDefinition;
+use super::svg_chart_module::{
+    CHART_BACKGROUND_COLOUR,
+    ChartColour,
+    ChartDrawing,
+    ChartDrawingElement,
+    ChartTextAnchor,
+    lay_out_chart_drawing,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+/// Width and height (above the baseline) of one font glyph, in font pixels
+const BITMAP_FONT_GLYPH_WIDTH: usize = 5;
+const BITMAP_FONT_GLYPH_HEIGHT: usize = 7;
+
+/// Rows stored per glyph: the seven above the baseline plus one descender row
+const BITMAP_FONT_GLYPH_ROW_COUNT: usize = 8;
+
+/// Horizontal distance from one glyph to the next, in font pixels
+const BITMAP_FONT_GLYPH_ADVANCE: usize = BITMAP_FONT_GLYPH_WIDTH + 1;
+
+/// Font size (in layout pixels) covered by one font pixel; larger text is scaled up
+const BITMAP_FONT_PIXELS_PER_SCALE_STEP: f64 = 7.0;
+
+/// Largest payload of one stored (uncompressed) deflate block
+const DEFLATE_STORED_BLOCK_MAXIMUM_LENGTH: usize = 65_535;
+
+/// PNG file signature
+const PNG_FILE_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// 5×7 glyphs for the printable ASCII characters (space to `~`)
+///
+/// Each glyph is five column bytes, left to right; bit 0 is the top row and
+/// bit 7 the descender row just below the baseline.
+const BITMAP_FONT_GLYPH_COLUMNS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5f, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7f, 0x14, 0x7f, 0x14], // '#'
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1c, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1c, 0x00], // ')'
+    [0x14, 0x08, 0x3e, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3e, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], // '0'
+    [0x00, 0x42, 0x7f, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4b, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7f, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3c, 0x4a, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1e], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3e], // '@'
+    [0x7e, 0x11, 0x11, 0x11, 0x7e], // 'A'
+    [0x7f, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3e, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], // 'D'
+    [0x7f, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7f, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3e, 0x41, 0x49, 0x49, 0x7a], // 'G'
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], // 'H'
+    [0x00, 0x41, 0x7f, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3f, 0x01], // 'J'
+    [0x7f, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7f, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7f, 0x02, 0x0c, 0x02, 0x7f], // 'M'
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], // 'N'
+    [0x3e, 0x41, 0x41, 0x41, 0x3e], // 'O'
+    [0x7f, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3e, 0x41, 0x51, 0x21, 0x5e], // 'Q'
+    [0x7f, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7f, 0x01, 0x01], // 'T'
+    [0x3f, 0x40, 0x40, 0x40, 0x3f], // 'U'
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], // 'V'
+    [0x3f, 0x40, 0x38, 0x40, 0x3f], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7f, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7f, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7f, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7f], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7e, 0x09, 0x01, 0x02], // 'f'
+    [0x18, 0xa4, 0xa4, 0xa4, 0x7c], // 'g'
+    [0x7f, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7d, 0x40, 0x00], // 'i'
+    [0x40, 0x80, 0x84, 0x7d, 0x00], // 'j'
+    [0x7f, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7f, 0x40, 0x00], // 'l'
+    [0x7c, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7c, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0xfc, 0x24, 0x24, 0x24, 0x18], // 'p'
+    [0x18, 0x24, 0x24, 0x24, 0xfc], // 'q'
+    [0x7c, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3f, 0x44, 0x40, 0x20], // 't'
+    [0x3c, 0x40, 0x40, 0x20, 0x7c], // 'u'
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], // 'v'
+    [0x3c, 0x40, 0x30, 0x40, 0x3c], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x1c, 0xa0, 0xa0, 0xa0, 0x7c], // 'y'
+    [0x44, 0x64, 0x54, 0x4c, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7f, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+/// CRC-32 (IEEE 802.3, reflected polynomial 0xEDB88320) lookup table
+const CRC32_LOOKUP_TABLE: [u32; 256] = build_crc32_lookup_table();
+
+/// Builds the CRC-32 lookup table at compile time
+const fn build_crc32_lookup_table() -> [u32; 256] {
+    let mut lookup_table = [0u32; 256];
+    let mut table_index = 0;
+    while table_index < 256 {
+        let mut crc_value = table_index as u32;
+        let mut bit_index = 0;
+        while bit_index < 8 {
+            crc_value = if crc_value & 1 == 1 { 0xedb8_8320 ^ (crc_value >> 1) } else { crc_value >> 1 };
+            bit_index += 1;
+        }
+        lookup_table[table_index] = crc_value;
+        table_index += 1;
+    }
+    lookup_table
+}
+
+/// Raster image file formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BitmapImageFormat {
+    /// Binary portable pixmap (`P6`), readable by most image tools
+    Ppm,
+
+    /// Portable Network Graphics with uncompressed image data
+    Png,
+}
+
+impl BitmapImageFormat {
+    /// Returns the file extension used for this format
+    ///
+    /// # Returns
+    /// * `&'static str` - `ppm` or `png`
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            BitmapImageFormat::Ppm => "ppm",
+            BitmapImageFormat::Png => "png",
+        }
+    }
+}
+
+/// An RGB image, 8 bits per channel, rows top to bottom
+#[derive(Debug, Clone)]
+pub struct RgbBitmapImage {
+    /// Width in pixels
+    pub image_width: usize,
+
+    /// Height in pixels
+    pub image_height: usize,
+
+    /// Red, green, blue bytes for each pixel, row by row
+    pixel_bytes: Vec<u8>,
+}
+
+impl RgbBitmapImage {
+    /// Creates an image filled with one colour
+    ///
+    /// # Arguments
+    /// * `image_width` - Width in pixels
+    /// * `image_height` - Height in pixels
+    /// * `background_colour` - Initial colour of every pixel
+    ///
+    /// # Returns
+    /// * `RgbBitmapImage` - The new image
+    pub fn new(image_width: usize, image_height: usize, background_colour: ChartColour) -> RgbBitmapImage {
+        let mut pixel_bytes = Vec::with_capacity(image_width * image_height * 3);
+        for _ in 0..image_width * image_height {
+            pixel_bytes.extend_from_slice(&[background_colour.red, background_colour.green, background_colour.blue]);
+        }
+        RgbBitmapImage { image_width, image_height, pixel_bytes }
+    }
+
+    /// Paints one pixel, mixing with the existing colour by `opacity` (clipped to the image)
+    fn blend_pixel(&mut self, pixel_x: i64, pixel_y: i64, paint_colour: ChartColour, opacity: f64) {
+        if pixel_x < 0 || pixel_y < 0 || pixel_x as usize >= self.image_width || pixel_y as usize >= self.image_height {
+            return;
+        }
+        let byte_offset = (pixel_y as usize * self.image_width + pixel_x as usize) * 3;
+        let opacity = opacity.clamp(0.0, 1.0);
+        for (channel_offset, paint_channel) in [paint_colour.red, paint_colour.green, paint_colour.blue].into_iter().enumerate() {
+            let existing_channel = self.pixel_bytes[byte_offset + channel_offset] as f64;
+            self.pixel_bytes[byte_offset + channel_offset] =
+                (existing_channel + (paint_channel as f64 - existing_channel) * opacity).round() as u8;
+        }
+    }
+
+    /// Fills the pixels from (left, top) up to but excluding (right, bottom)
+    fn fill_pixel_rectangle(&mut self, left_x: i64, top_y: i64, right_x: i64, bottom_y: i64, fill_colour: ChartColour) {
+        for pixel_y in top_y.max(0)..bottom_y.min(self.image_height as i64) {
+            for pixel_x in left_x.max(0)..right_x.min(self.image_width as i64) {
+                self.blend_pixel(pixel_x, pixel_y, fill_colour, 1.0);
+            }
+        }
+    }
+}
+
+/// Returns the font glyph for a character (`?` for characters the font lacks)
+fn find_font_glyph_columns(text_character: char) -> &'static [u8; 5] {
+    let glyph_character = match text_character {
+        '…' => '.',
+        ' '..='~' => text_character,
+        _ => '?',
+    };
+    &BITMAP_FONT_GLYPH_COLUMNS[glyph_character as usize - ' ' as usize]
+}
+
+/// Paints a line of text with the bitmap font
+///
+/// The anchor sits on the baseline (the bottom of the glyphs; descenders of
+/// `g`, `j`, `p`, `q` and `y` reach one font pixel below it). Vertical text
+/// is rotated a quarter turn anticlockwise around the anchor, like SVG's
+/// `rotate(-90)`.
+fn draw_bitmap_text(
+    bitmap_image: &mut RgbBitmapImage,
+    anchor_point: (f64, f64),
+    text_content: &str,
+    text_anchor: ChartTextAnchor,
+    font_size: f64,
+    text_colour: ChartColour,
+    is_vertical: bool,
+) {
+    let font_scale = ((font_size / BITMAP_FONT_PIXELS_PER_SCALE_STEP).floor() as i64).max(1);
+    let character_count = text_content.chars().count() as i64;
+    let text_width = (character_count * BITMAP_FONT_GLYPH_ADVANCE as i64 - 1).max(0) * font_scale;
+    let start_offset = match text_anchor {
+        ChartTextAnchor::Start => 0,
+        ChartTextAnchor::Middle => -text_width / 2,
+        ChartTextAnchor::End => -text_width,
+    };
+    let glyph_top_offset = -(BITMAP_FONT_GLYPH_HEIGHT as i64) * font_scale;
+    let (anchor_x, anchor_y) = (anchor_point.0.round() as i64, anchor_point.1.round() as i64);
+
+    for (character_position, text_character) in text_content.chars().enumerate() {
+        let glyph_columns = find_font_glyph_columns(text_character);
+        let glyph_left_offset = start_offset + character_position as i64 * BITMAP_FONT_GLYPH_ADVANCE as i64 * font_scale;
+
+        for (glyph_column, column_bits) in glyph_columns.iter().enumerate() {
+            for glyph_row in 0..BITMAP_FONT_GLYPH_ROW_COUNT {
+                if column_bits & (1 << glyph_row) == 0 {
+                    continue;
+                }
+                // Each font pixel becomes a font_scale × font_scale block
+                for block_x in 0..font_scale {
+                    for block_y in 0..font_scale {
+                        let along_offset = glyph_left_offset + glyph_column as i64 * font_scale + block_x;
+                        let across_offset = glyph_top_offset + glyph_row as i64 * font_scale + block_y;
+                        let (pixel_x, pixel_y) = if is_vertical {
+                            (anchor_x + across_offset, anchor_y - along_offset)
+                        } else {
+                            (anchor_x + along_offset, anchor_y + across_offset)
+                        };
+                        bitmap_image.blend_pixel(pixel_x, pixel_y, text_colour, 1.0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Paints a straight line with a square brush (Bresenham's algorithm)
+fn draw_bitmap_line(
+    bitmap_image: &mut RgbBitmapImage,
+    start_point: (f64, f64),
+    end_point: (f64, f64),
+    stroke_colour: ChartColour,
+    stroke_width: f64,
+) {
+    let brush_size = (stroke_width.round() as i64).max(1);
+    let brush_offset = (brush_size - 1) / 2;
+    let (mut current_x, mut current_y) = (start_point.0.round() as i64, start_point.1.round() as i64);
+    let (end_x, end_y) = (end_point.0.round() as i64, end_point.1.round() as i64);
+
+    let distance_x = (end_x - current_x).abs();
+    let distance_y = -(end_y - current_y).abs();
+    let step_x = if current_x < end_x { 1 } else { -1 };
+    let step_y = if current_y < end_y { 1 } else { -1 };
+    let mut error_term = distance_x + distance_y;
+
+    loop {
+        bitmap_image.fill_pixel_rectangle(
+            current_x - brush_offset,
+            current_y - brush_offset,
+            current_x - brush_offset + brush_size,
+            current_y - brush_offset + brush_size,
+            stroke_colour,
+        );
+        if current_x == end_x && current_y == end_y {
+            break;
+        }
+        let doubled_error = 2 * error_term;
+        if doubled_error >= distance_y {
+            error_term += distance_y;
+            current_x += step_x;
+        }
+        if doubled_error <= distance_x {
+            error_term += distance_x;
+            current_y += step_y;
+        }
+    }
+}
+
+/// Rasterizes a laid-out chart
+///
+/// # Arguments
+/// * `chart_drawing` - Primitives from `lay_out_chart_drawing`
+///
+/// # Returns
+/// * `RgbBitmapImage` - The painted image
+pub fn rasterize_chart_drawing(chart_drawing: &ChartDrawing) -> RgbBitmapImage {
+    let mut bitmap_image = RgbBitmapImage::new(
+        chart_drawing.drawing_width.round() as usize,
+        chart_drawing.drawing_height.round() as usize,
+        CHART_BACKGROUND_COLOUR,
+    );
+
+    for drawing_element in &chart_drawing.drawing_elements {
+        match drawing_element {
+            ChartDrawingElement::Rectangle { left_x, top_y, rectangle_width, rectangle_height, fill_colour, outline_colour } => {
+                let (pixel_left, pixel_top) = (left_x.round() as i64, top_y.round() as i64);
+                let (pixel_right, pixel_bottom) = ((left_x + rectangle_width).round() as i64, (top_y + rectangle_height).round() as i64);
+                bitmap_image.fill_pixel_rectangle(pixel_left, pixel_top, pixel_right, pixel_bottom, *fill_colour);
+
+                // One-pixel outline, only where there is room inside for the fill
+                if pixel_right - pixel_left >= 2 && pixel_bottom - pixel_top >= 2 {
+                    bitmap_image.fill_pixel_rectangle(pixel_left, pixel_top, pixel_right, pixel_top + 1, *outline_colour);
+                    bitmap_image.fill_pixel_rectangle(pixel_left, pixel_bottom - 1, pixel_right, pixel_bottom, *outline_colour);
+                    bitmap_image.fill_pixel_rectangle(pixel_left, pixel_top, pixel_left + 1, pixel_bottom, *outline_colour);
+                    bitmap_image.fill_pixel_rectangle(pixel_right - 1, pixel_top, pixel_right, pixel_bottom, *outline_colour);
+                }
+            }
+            ChartDrawingElement::Line { start_x, start_y, end_x, end_y, stroke_colour, stroke_width } => {
+                draw_bitmap_line(&mut bitmap_image, (*start_x, *start_y), (*end_x, *end_y), *stroke_colour, *stroke_width);
+            }
+            ChartDrawingElement::Text { anchor_x, baseline_y, text_content, text_anchor, font_size, text_colour, is_vertical } => {
+                draw_bitmap_text(&mut bitmap_image, (*anchor_x, *baseline_y), text_content, *text_anchor, *font_size, *text_colour, *is_vertical);
+            }
+            ChartDrawingElement::Circle { centre_x, centre_y, circle_radius, fill_colour, fill_opacity } => {
+                // Pixels whose centres fall inside the circle
+                let reach = circle_radius.ceil() as i64;
+                let (centre_pixel_x, centre_pixel_y) = (centre_x.floor() as i64, centre_y.floor() as i64);
+                for pixel_y in centre_pixel_y - reach..=centre_pixel_y + reach {
+                    for pixel_x in centre_pixel_x - reach..=centre_pixel_x + reach {
+                        let offset_x = pixel_x as f64 + 0.5 - centre_x;
+                        let offset_y = pixel_y as f64 + 0.5 - centre_y;
+                        if offset_x * offset_x + offset_y * offset_y <= circle_radius * circle_radius {
+                            bitmap_image.blend_pixel(pixel_x, pixel_y, *fill_colour, *fill_opacity);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    bitmap_image
+}
+
+/// Calculates the CRC-32 checksum used by PNG chunks
+///
+/// # Arguments
+/// * `checked_bytes` - Bytes to check
+///
+/// # Returns
+/// * `u32` - The checksum
+pub fn calculate_crc32_checksum(checked_bytes: &[u8]) -> u32 {
+    let mut crc_value = 0xffff_ffffu32;
+    for &checked_byte in checked_bytes {
+        crc_value = CRC32_LOOKUP_TABLE[((crc_value ^ checked_byte as u32) & 0xff) as usize] ^ (crc_value >> 8);
+    }
+    crc_value ^ 0xffff_ffff
+}
+
+/// Calculates the Adler-32 checksum that ends a zlib stream
+///
+/// # Arguments
+/// * `checked_bytes` - Uncompressed bytes
+///
+/// # Returns
+/// * `u32` - The checksum
+pub fn calculate_adler32_checksum(checked_bytes: &[u8]) -> u32 {
+    const ADLER32_MODULUS: u32 = 65_521;
+    let (mut low_sum, mut high_sum) = (1u32, 0u32);
+    for &checked_byte in checked_bytes {
+        low_sum = (low_sum + checked_byte as u32) % ADLER32_MODULUS;
+        high_sum = (high_sum + low_sum) % ADLER32_MODULUS;
+    }
+    (high_sum << 16) | low_sum
+}
+
+/// Wraps bytes in a zlib stream made of stored (uncompressed) deflate blocks
+fn wrap_bytes_in_zlib_stored_blocks(uncompressed_bytes: &[u8]) -> Vec<u8> {
+    let block_count = uncompressed_bytes.len().div_ceil(DEFLATE_STORED_BLOCK_MAXIMUM_LENGTH).max(1);
+    let mut zlib_bytes = Vec::with_capacity(uncompressed_bytes.len() + block_count * 5 + 6);
+
+    // zlib header: deflate with a 32K window, no preset dictionary, check bits valid
+    zlib_bytes.extend_from_slice(&[0x78, 0x01]);
+
+    let mut block_start = 0;
+    for block_index in 0..block_count {
+        let block_end = (block_start + DEFLATE_STORED_BLOCK_MAXIMUM_LENGTH).min(uncompressed_bytes.len());
+        let block_length = (block_end - block_start) as u16;
+        let is_final_block = block_index + 1 == block_count;
+
+        zlib_bytes.push(if is_final_block { 0x01 } else { 0x00 });
+        zlib_bytes.extend_from_slice(&block_length.to_le_bytes());
+        zlib_bytes.extend_from_slice(&(!block_length).to_le_bytes());
+        zlib_bytes.extend_from_slice(&uncompressed_bytes[block_start..block_end]);
+        block_start = block_end;
+    }
+
+    zlib_bytes.extend_from_slice(&calculate_adler32_checksum(uncompressed_bytes).to_be_bytes());
+    zlib_bytes
+}
+
+/// Appends one PNG chunk (length, type, data, CRC)
+fn append_png_chunk(png_bytes: &mut Vec<u8>, chunk_type: &[u8; 4], chunk_data: &[u8]) {
+    png_bytes.extend_from_slice(&(chunk_data.len() as u32).to_be_bytes());
+    let checked_start = png_bytes.len();
+    png_bytes.extend_from_slice(chunk_type);
+    png_bytes.extend_from_slice(chunk_data);
+    let chunk_crc = calculate_crc32_checksum(&png_bytes[checked_start..]);
+    png_bytes.extend_from_slice(&chunk_crc.to_be_bytes());
+}
+
+/// Encodes an image as PNG (8-bit RGB, no filtering, stored deflate blocks)
+///
+/// # Arguments
+/// * `bitmap_image` - The image to encode
+///
+/// # Returns
+/// * `Vec<u8>` - Complete PNG file contents
+pub fn encode_bitmap_as_png(bitmap_image: &RgbBitmapImage) -> Vec<u8> {
+    // Step 1: Scanlines, each prefixed with filter type 0 (none)
+    let row_byte_count = bitmap_image.image_width * 3;
+    let mut scanline_bytes = Vec::with_capacity((row_byte_count + 1) * bitmap_image.image_height);
+    for row_pixel_bytes in bitmap_image.pixel_bytes.chunks(row_byte_count.max(1)) {
+        scanline_bytes.push(0);
+        scanline_bytes.extend_from_slice(row_pixel_bytes);
+    }
+
+    // Step 2: Header chunk: size, bit depth 8, colour type 2 (RGB), default methods
+    let mut header_data = Vec::with_capacity(13);
+    header_data.extend_from_slice(&(bitmap_image.image_width as u32).to_be_bytes());
+    header_data.extend_from_slice(&(bitmap_image.image_height as u32).to_be_bytes());
+    header_data.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    // Step 3: Assemble the file
+    let mut png_bytes = PNG_FILE_SIGNATURE.to_vec();
+    append_png_chunk(&mut png_bytes, b"IHDR", &header_data);
+    append_png_chunk(&mut png_bytes, b"IDAT", &wrap_bytes_in_zlib_stored_blocks(&scanline_bytes));
+    append_png_chunk(&mut png_bytes, b"IEND", &[]);
+    png_bytes
+}
+
+/// Encodes an image as binary PPM (`P6`)
+///
+/// # Arguments
+/// * `bitmap_image` - The image to encode
+///
+/// # Returns
+/// * `Vec<u8>` - Complete PPM file contents
+pub fn encode_bitmap_as_ppm(bitmap_image: &RgbBitmapImage) -> Vec<u8> {
+    let mut ppm_bytes = format!("P6\n{} {}\n255\n", bitmap_image.image_width, bitmap_image.image_height).into_bytes();
+    ppm_bytes.extend_from_slice(&bitmap_image.pixel_bytes);
+    ppm_bytes
+}
+
+/// Renders a chart and saves it as a PPM or PNG file
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `chart_width` - Image width in pixels
+/// * `chart_height` - Image height in pixels
+/// * `image_format` - PPM or PNG
+/// * `image_file_path` - Where to write (parent directories are created)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the directory or file cannot be written
+pub fn write_chart_bitmap_file(
+    chart_definition: &ChartDefinition,
+    chart_width: usize,
+    chart_height: usize,
+    image_format: BitmapImageFormat,
+    image_file_path: &Path,
+) -> RowsAndColumnsResult<()> {
+    if let Some(parent_directory) = image_file_path.parent()
+        && !parent_directory.as_os_str().is_empty() {
+        std::fs::create_dir_all(parent_directory).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to create chart directory: {}", parent_directory.display()),
+                io_error
+            )
+        })?;
+    }
+
+    let bitmap_image = rasterize_chart_drawing(&lay_out_chart_drawing(chart_definition, chart_width, chart_height));
+    let image_bytes = match image_format {
+        BitmapImageFormat::Ppm => encode_bitmap_as_ppm(&bitmap_image),
+        BitmapImageFormat::Png => encode_bitmap_as_png(&bitmap_image),
+    };
+
+    std::fs::write(image_file_path, image_bytes).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write chart image: {}", image_file_path.display()),
+            io_error
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::chart_rendering_module::{BarChartBar, BarChartData};
+
+    /// Test the checksums against published check values
+    #[test]
+    fn test_crc32_and_adler32_checksums() {
+        assert_eq!(calculate_crc32_checksum(b"123456789"), 0xcbf4_3926);
+        assert_eq!(calculate_crc32_checksum(b"IEND"), 0xae42_6082);
+        assert_eq!(calculate_adler32_checksum(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(calculate_adler32_checksum(b""), 1);
+    }
+
+    /// Test the PNG structure and that the stored blocks hold the scanlines unchanged
+    #[test]
+    fn test_encode_bitmap_as_png_structure() {
+        let mut bitmap_image = RgbBitmapImage::new(200, 120, CHART_BACKGROUND_COLOUR);
+        bitmap_image.fill_pixel_rectangle(0, 0, 1, 1, ChartColour { red: 1, green: 2, blue: 3 });
+        let png_bytes = encode_bitmap_as_png(&bitmap_image);
+
+        assert_eq!(&png_bytes[..8], &PNG_FILE_SIGNATURE);
+        assert_eq!(&png_bytes[12..16], b"IHDR");
+        assert_eq!(&png_bytes[16..24], &[0, 0, 0, 200, 0, 0, 0, 120]);
+        assert_eq!(&png_bytes[png_bytes.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+
+        // IDAT starts after signature (8) + IHDR chunk (12 + 13)
+        let idat_length = u32::from_be_bytes([png_bytes[33], png_bytes[34], png_bytes[35], png_bytes[36]]) as usize;
+        assert_eq!(&png_bytes[37..41], b"IDAT");
+        let zlib_bytes = &png_bytes[41..41 + idat_length];
+        let stored_crc = u32::from_be_bytes(png_bytes[41 + idat_length..45 + idat_length].try_into().expect("four CRC bytes"));
+        assert_eq!(stored_crc, calculate_crc32_checksum(&png_bytes[37..41 + idat_length]));
+
+        // Undo the stored blocks: 72,120 scanline bytes need two blocks
+        let mut scanline_bytes = Vec::new();
+        let mut read_position = 2;
+        loop {
+            let block_header = zlib_bytes[read_position];
+            let block_length = u16::from_le_bytes([zlib_bytes[read_position + 1], zlib_bytes[read_position + 2]]) as usize;
+            let inverted_length = u16::from_le_bytes([zlib_bytes[read_position + 3], zlib_bytes[read_position + 4]]);
+            assert_eq!(inverted_length, !(block_length as u16));
+            scanline_bytes.extend_from_slice(&zlib_bytes[read_position + 5..read_position + 5 + block_length]);
+            read_position += 5 + block_length;
+            if block_header & 1 == 1 {
+                break;
+            }
+        }
+        assert_eq!(scanline_bytes.len(), (200 * 3 + 1) * 120);
+        assert_eq!(&scanline_bytes[..4], &[0, 1, 2, 3]);
+        assert_eq!(
+            u32::from_be_bytes(zlib_bytes[read_position..read_position + 4].try_into().expect("four Adler bytes")),
+            calculate_adler32_checksum(&scanline_bytes)
+        );
+    }
+
+    /// Test a rasterized bar chart contains bars, text and a PPM header
+    #[test]
+    fn test_rasterize_bar_chart_and_encode_ppm() {
+        let chart_definition = ChartDefinition::BarChart(BarChartData {
+            chart_title: "breed (bar chart)".to_string(),
+            chart_bars: vec![
+                BarChartBar { bar_label: "Tabby".to_string(), bar_count: 6, bar_percentage: 60.0 },
+                BarChartBar { bar_label: "Siamese".to_string(), bar_count: 4, bar_percentage: 40.0 },
+            ],
+            omitted_category_count: 0,
+        });
+        let chart_drawing = lay_out_chart_drawing(&chart_definition, 320, 200);
+        let bitmap_image = rasterize_chart_drawing(&chart_drawing);
+
+        let data_fill_colour = ChartColour { red: 0x4e, green: 0x79, blue: 0xa7 };
+        let text_colour = ChartColour { red: 0x33, green: 0x33, blue: 0x33 };
+        let mut colour_counts = (0, 0);
+        for pixel_channels in bitmap_image.pixel_bytes.chunks(3) {
+            let pixel_colour = ChartColour { red: pixel_channels[0], green: pixel_channels[1], blue: pixel_channels[2] };
+            if pixel_colour == data_fill_colour {
+                colour_counts.0 += 1;
+            } else if pixel_colour == text_colour {
+                colour_counts.1 += 1;
+            }
+        }
+        assert!(colour_counts.0 > 1_000, "bars should cover many pixels");
+        assert!(colour_counts.1 > 200, "axes and labels should be drawn");
+
+        let ppm_bytes = encode_bitmap_as_ppm(&bitmap_image);
+        assert!(ppm_bytes.starts_with(b"P6\n320 200\n255\n"));
+        assert_eq!(ppm_bytes.len(), "P6\n320 200\n255\n".len() + 320 * 200 * 3);
+    }
+
+    /// Test elements off the image are clipped (never indexing outside the
+    /// pixel buffer), and characters the font lacks draw as `?`
+    #[test]
+    fn test_rasterize_clips_elements_at_and_beyond_the_edges() {
+        let paint_colour = ChartColour { red: 0, green: 0, blue: 0 };
+        let create_text = |text_content: &str, anchor_x: f64, is_vertical: bool| ChartDrawingElement::Text {
+            anchor_x,
+            baseline_y: 8.0,
+            text_content: text_content.to_string(),
+            text_anchor: ChartTextAnchor::Start,
+            font_size: 7.0,
+            text_colour: paint_colour,
+            is_vertical,
+        };
+        let outside_elements = vec![
+            ChartDrawingElement::Rectangle { left_x: -50.0, top_y: -50.0, rectangle_width: 40.0, rectangle_height: 400.0, fill_colour: paint_colour, outline_colour: paint_colour },
+            ChartDrawingElement::Line { start_x: -1e6, start_y: -5.0, end_x: 1e6, end_y: -5.0, stroke_colour: paint_colour, stroke_width: 3.0 },
+            ChartDrawingElement::Circle { centre_x: 25.0, centre_y: 15.0, circle_radius: 2.5, fill_colour: paint_colour, fill_opacity: 1.0 },
+            create_text("far right", 1_000.0, false),
+            create_text("below", -100.0, true),
+        ];
+        let blank_image = rasterize_chart_drawing(&ChartDrawing { drawing_width: 20.0, drawing_height: 10.0, drawing_elements: Vec::new() });
+        let outside_image = rasterize_chart_drawing(&ChartDrawing { drawing_width: 20.0, drawing_height: 10.0, drawing_elements: outside_elements });
+        assert_eq!(outside_image.pixel_bytes, blank_image.pixel_bytes);
+
+        // Straddling the corners paints only the pixels inside
+        let straddling_image = rasterize_chart_drawing(&ChartDrawing {
+            drawing_width: 20.0,
+            drawing_height: 10.0,
+            drawing_elements: vec![
+                ChartDrawingElement::Circle { centre_x: 0.0, centre_y: 0.0, circle_radius: 2.5, fill_colour: paint_colour, fill_opacity: 1.0 },
+                ChartDrawingElement::Line { start_x: 15.0, start_y: 9.0, end_x: 40.0, end_y: 9.0, stroke_colour: paint_colour, stroke_width: 1.0 },
+            ],
+        });
+        let pixel_is_painted = |bitmap_image: &RgbBitmapImage, pixel_x: usize, pixel_y: usize| {
+            bitmap_image.pixel_bytes[(pixel_y * bitmap_image.image_width + pixel_x) * 3] == 0
+        };
+        assert!(pixel_is_painted(&straddling_image, 0, 0));
+        assert!(pixel_is_painted(&straddling_image, 19, 9));
+        assert!(!pixel_is_painted(&straddling_image, 10, 5));
+
+        // Non-ASCII and control characters fall back to the `?` glyph
+        let rasterize_text = |text_content: &str| {
+            rasterize_chart_drawing(&ChartDrawing {
+                drawing_width: 20.0,
+                drawing_height: 10.0,
+                drawing_elements: vec![create_text(text_content, 1.0, false)],
+            }).pixel_bytes
+        };
+        assert_eq!(rasterize_text("é\t"), rasterize_text("??"));
+        assert_ne!(rasterize_text("??"), blank_image.pixel_bytes);
+    }
+}
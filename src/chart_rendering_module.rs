@@ -0,0 +1,981 @@
+// src/chart_rendering_module.rs
+
+/// Chart definitions and ASCII/Unicode text rendering for rows_and_columns
+///
+/// This module separates *what* a chart shows (a `ChartDefinition` built from
+/// column statistics or streamed column values) from *how* it is drawn. The
+/// text renderer here draws charts as lines of characters for the TUI and the
+/// line-based terminal mode; other renderers can consume the same definitions.
+///
+/// # Core Responsibilities
+/// - Build chart definitions: histogram, box plot, bar chart, scatter plot, heatmap
+/// - Stream CSV column values for histograms and scatter plots (no pre-loading)
+/// - Render any chart definition into a fixed-size block of text lines
+/// - Support both Unicode (default) and plain ASCII glyph sets
+///
+/// # Design Philosophy
+/// - Definitions are plain data: no terminal or file knowledge inside them
+/// - Rendering is pure: same definition and size always produce the same lines
+/// - ASCII mode uses only 7-bit characters for headless and web-TUI use
+use std::path::Path;
+
+// Import CSV streaming and statistics structures
+use super::csv_processor_module::{
+    CategoricalColumnStatistics,
+    CsvRowStreamControl,
+    NumericalColumnStatistics,
+    stream_csv_data_rows,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::RowsAndColumnsResult;
+
+/// Default number of histogram bins used by the dashboard
+pub const DEFAULT_HISTOGRAM_BIN_COUNT: usize = 20;
+
+/// Default maximum number of bars shown in a categorical bar chart
+pub const DEFAULT_MAXIMUM_BAR_COUNT: usize = 15;
+
+/// Maximum number of points kept for a scatter plot
+///
+/// Scatter plots need both coordinates of each point, so the number of kept
+/// points is capped to keep memory bounded on very large files.
+pub const MAXIMUM_SCATTER_PLOT_POINT_COUNT: usize = 10_000;
+
+/// Which character set to draw charts with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartGlyphMode {
+    /// Box-drawing and block characters (default)
+    Unicode,
+
+    /// Plain 7-bit ASCII characters only
+    Ascii,
+}
+
+/// Histogram data: counts of values falling into equal-width bins
+#[derive(Debug, Clone)]
+pub struct HistogramChartData {
+    /// Chart title (usually the column name)
+    pub chart_title: String,
+
+    /// Lower edge of the first bin
+    pub minimum_value: f64,
+
+    /// Upper edge of the last bin
+    pub maximum_value: f64,
+
+    /// Count of values in each bin, lowest bin first
+    pub bin_counts: Vec<usize>,
+}
+
+/// Box plot data: the five-number summary of a numerical column
+#[derive(Debug, Clone)]
+pub struct BoxPlotChartData {
+    /// Chart title (usually the column name)
+    pub chart_title: String,
+
+    /// Minimum value (lower whisker end)
+    pub minimum_value: f64,
+
+    /// First quartile (bottom of the box)
+    pub first_quartile_value: f64,
+
+    /// Median (line inside the box)
+    pub median_value: f64,
+
+    /// Third quartile (top of the box)
+    pub third_quartile_value: f64,
+
+    /// Maximum value (upper whisker end)
+    pub maximum_value: f64,
+}
+
+/// One bar of a categorical bar chart
+#[derive(Debug, Clone)]
+pub struct BarChartBar {
+    /// Category label shown next to the bar
+    pub bar_label: String,
+
+    /// Number of occurrences of this category
+    pub bar_count: usize,
+
+    /// Percentage of non-empty values this category represents
+    pub bar_percentage: f64,
+}
+
+/// Bar chart data: one bar per category, largest first
+#[derive(Debug, Clone)]
+pub struct BarChartData {
+    /// Chart title (usually the column name)
+    pub chart_title: String,
+
+    /// Bars to draw, in display order
+    pub chart_bars: Vec<BarChartBar>,
+
+    /// Number of categories that were not included as bars
+    pub omitted_category_count: usize,
+}
+
+/// Scatter plot data: paired numerical values from two columns
+#[derive(Debug, Clone)]
+pub struct ScatterPlotChartData {
+    /// Chart title
+    pub chart_title: String,
+
+    /// Label of the horizontal axis (x column name)
+    pub x_axis_label: String,
+
+    /// Label of the vertical axis (y column name)
+    pub y_axis_label: String,
+
+    /// Points as (x, y) pairs
+    pub plot_points: Vec<(f64, f64)>,
+
+    /// Whether points were dropped because of `MAXIMUM_SCATTER_PLOT_POINT_COUNT`
+    pub points_were_capped: bool,
+}
+
+/// Heatmap data: a square matrix of coefficients between -1 and 1
+#[derive(Debug, Clone)]
+pub struct HeatmapChartData {
+    /// Chart title
+    pub chart_title: String,
+
+    /// Labels of the rows (and, by number, of the columns)
+    pub axis_labels: Vec<String>,
+
+    /// Cell values by row then column; None where no value could be computed
+    pub cell_values: Vec<Vec<Option<f64>>>,
+}
+
+/// A chart ready to be rendered by any renderer
+#[derive(Debug, Clone)]
+pub enum ChartDefinition {
+    /// Distribution of a numerical column
+    Histogram(HistogramChartData),
+
+    /// Five-number summary of a numerical column
+    BoxPlot(BoxPlotChartData),
+
+    /// Category frequencies of a categorical column
+    BarChart(BarChartData),
+
+    /// Relationship between two numerical columns
+    ScatterPlot(ScatterPlotChartData),
+
+    /// Shaded matrix of coefficients (e.g. correlations)
+    Heatmap(HeatmapChartData),
+}
+
+/// Builds a box plot definition from numerical column statistics
+///
+/// # Arguments
+/// * `column_name` - Name of the column, used as the chart title
+/// * `numerical_statistics` - Statistics containing the five-number summary
+///
+/// # Returns
+/// * `ChartDefinition` - A box plot chart definition
+pub fn build_box_plot_chart_from_statistics(
+    column_name: &str,
+    numerical_statistics: &NumericalColumnStatistics,
+) -> ChartDefinition {
+    ChartDefinition::BoxPlot(BoxPlotChartData {
+        chart_title: format!("{} (box plot)", column_name),
+        minimum_value: numerical_statistics.min_value,
+        first_quartile_value: numerical_statistics.q1_value,
+        median_value: numerical_statistics.q2_median_value,
+        third_quartile_value: numerical_statistics.q3_value,
+        maximum_value: numerical_statistics.max_value,
+    })
+}
+
+/// Builds a bar chart definition from categorical column statistics
+///
+/// # Arguments
+/// * `column_name` - Name of the column, used as the chart title
+/// * `categorical_statistics` - Statistics containing sorted value frequencies
+/// * `maximum_bar_count` - Maximum number of bars to include
+///
+/// # Returns
+/// * `ChartDefinition` - A bar chart chart definition
+pub fn build_bar_chart_from_categorical_statistics(
+    column_name: &str,
+    categorical_statistics: &CategoricalColumnStatistics,
+    maximum_bar_count: usize,
+) -> ChartDefinition {
+    let chart_bars: Vec<BarChartBar> = categorical_statistics.value_frequencies
+        .iter()
+        .take(maximum_bar_count)
+        .map(|value_frequency| BarChartBar {
+            bar_label: value_frequency.value.clone(),
+            bar_count: value_frequency.count,
+            bar_percentage: value_frequency.percentage,
+        })
+        .collect();
+
+    let omitted_category_count = categorical_statistics.value_frequencies.len() - chart_bars.len();
+
+    ChartDefinition::BarChart(BarChartData {
+        chart_title: format!("{} (bar chart)", column_name),
+        chart_bars,
+        omitted_category_count,
+    })
+}
+
+/// Builds a histogram definition by streaming one column of a CSV file
+///
+/// The bin range comes from the already-computed statistics, so the file is
+/// read exactly once and only the bin counters are held in memory.
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `has_header_row` - Whether the CSV has a header row to skip
+/// * `column_index` - 0-based index of the column to chart
+/// * `column_name` - Name of the column, used as the chart title
+/// * `numerical_statistics` - Statistics providing the min/max range
+/// * `bin_count` - Number of equal-width bins (at least 1)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ChartDefinition>` - Histogram definition or read error
+pub fn build_histogram_chart_from_csv_column(
+    csv_file_path: &Path,
+    has_header_row: bool,
+    column_index: usize,
+    column_name: &str,
+    numerical_statistics: &NumericalColumnStatistics,
+    bin_count: usize,
+) -> RowsAndColumnsResult<ChartDefinition> {
+    let effective_bin_count = bin_count.max(1);
+    let minimum_value = numerical_statistics.min_value;
+    let maximum_value = numerical_statistics.max_value;
+    let mut bin_counts = vec![0usize; effective_bin_count];
+
+    stream_csv_data_rows(csv_file_path, has_header_row, |_row_index, field_values| {
+        if let Some(field_value) = field_values.get(column_index)
+            && let Ok(numerical_value) = field_value.parse::<f64>() {
+            let bin_index = calculate_histogram_bin_index(
+                numerical_value,
+                minimum_value,
+                maximum_value,
+                effective_bin_count,
+            );
+            bin_counts[bin_index] += 1;
+        }
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    Ok(ChartDefinition::Histogram(HistogramChartData {
+        chart_title: format!("{} (histogram)", column_name),
+        minimum_value,
+        maximum_value,
+        bin_counts,
+    }))
+}
+
+/// Calculates which histogram bin a value belongs to
+///
+/// Values outside the range are clamped into the first or last bin, and a
+/// zero-width range puts everything into the first bin.
+///
+/// # Arguments
+/// * `numerical_value` - The value to place
+/// * `minimum_value` - Lower edge of the first bin
+/// * `maximum_value` - Upper edge of the last bin
+/// * `bin_count` - Number of bins (at least 1)
+///
+/// # Returns
+/// * `usize` - Bin index in `0..bin_count`
+pub fn calculate_histogram_bin_index(
+    numerical_value: f64,
+    minimum_value: f64,
+    maximum_value: f64,
+    bin_count: usize,
+) -> usize {
+    let value_range = maximum_value - minimum_value;
+    if value_range <= 0.0 || !numerical_value.is_finite() {
+        return 0;
+    }
+
+    let relative_position = (numerical_value - minimum_value) / value_range;
+    let raw_bin_index = (relative_position * bin_count as f64).floor();
+
+    if raw_bin_index < 0.0 {
+        0
+    } else {
+        (raw_bin_index as usize).min(bin_count - 1)
+    }
+}
+
+/// Builds a scatter plot definition by streaming two columns of a CSV file
+///
+/// Rows where either value is missing or non-numeric are skipped. At most
+/// `maximum_point_count` points are kept (the first ones encountered).
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `has_header_row` - Whether the CSV has a header row to skip
+/// * `x_column_index` - 0-based index of the horizontal-axis column
+/// * `x_column_name` - Name of the horizontal-axis column
+/// * `y_column_index` - 0-based index of the vertical-axis column
+/// * `y_column_name` - Name of the vertical-axis column
+/// * `maximum_point_count` - Cap on stored points
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ChartDefinition>` - Scatter plot definition or read error
+pub fn build_scatter_plot_chart_from_csv_columns(
+    csv_file_path: &Path,
+    has_header_row: bool,
+    x_column_index: usize,
+    x_column_name: &str,
+    y_column_index: usize,
+    y_column_name: &str,
+    maximum_point_count: usize,
+) -> RowsAndColumnsResult<ChartDefinition> {
+    let mut plot_points = Vec::new();
+    let mut points_were_capped = false;
+
+    stream_csv_data_rows(csv_file_path, has_header_row, |_row_index, field_values| {
+        let x_value = field_values.get(x_column_index).and_then(|text| text.parse::<f64>().ok());
+        let y_value = field_values.get(y_column_index).and_then(|text| text.parse::<f64>().ok());
+
+        if let (Some(x_value), Some(y_value)) = (x_value, y_value) {
+            if plot_points.len() >= maximum_point_count {
+                points_were_capped = true;
+                return Ok(CsvRowStreamControl::StopReading);
+            }
+            plot_points.push((x_value, y_value));
+        }
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    Ok(ChartDefinition::ScatterPlot(ScatterPlotChartData {
+        chart_title: format!("{} vs {} (scatter)", y_column_name, x_column_name),
+        x_axis_label: x_column_name.to_string(),
+        y_axis_label: y_column_name.to_string(),
+        plot_points,
+        points_were_capped,
+    }))
+}
+
+/// Renders any chart definition as lines of text
+///
+/// The result has at most `available_row_count` lines, each at most
+/// `available_column_count` characters wide.
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `available_column_count` - Width available in characters
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - The rendered chart lines
+pub fn render_chart_as_text_lines(
+    chart_definition: &ChartDefinition,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let rendered_lines = match chart_definition {
+        ChartDefinition::Histogram(histogram_data) => {
+            render_histogram_text_lines(histogram_data, available_column_count, available_row_count, glyph_mode)
+        }
+        ChartDefinition::BoxPlot(box_plot_data) => {
+            render_box_plot_text_lines(box_plot_data, available_row_count, glyph_mode)
+        }
+        ChartDefinition::BarChart(bar_chart_data) => {
+            render_bar_chart_text_lines(bar_chart_data, available_column_count, available_row_count, glyph_mode)
+        }
+        ChartDefinition::ScatterPlot(scatter_plot_data) => {
+            render_scatter_plot_text_lines(scatter_plot_data, available_column_count, available_row_count, glyph_mode)
+        }
+        ChartDefinition::Heatmap(heatmap_data) => {
+            render_heatmap_text_lines(heatmap_data, available_column_count, available_row_count, glyph_mode)
+        }
+    };
+
+    // Enforce the size contract for every chart type
+    rendered_lines
+        .into_iter()
+        .take(available_row_count)
+        .map(|line| line.chars().take(available_column_count).collect())
+        .collect()
+}
+
+/// Formats an axis value compactly (integers without decimals)
+///
+/// # Arguments
+/// * `axis_value` - The value to format
+///
+/// # Returns
+/// * `String` - Compact text representation
+pub fn format_compact_axis_value(axis_value: f64) -> String {
+    if axis_value.fract() == 0.0 && axis_value.abs() < 1e12 {
+        format!("{}", axis_value as i64)
+    } else if axis_value.abs() >= 1000.0 || axis_value.abs() < 0.01 {
+        format!("{:.3e}", axis_value)
+    } else {
+        format!("{:.2}", axis_value)
+    }
+}
+
+/// Renders a histogram as vertical bars
+///
+/// # Arguments
+/// * `histogram_data` - The histogram to draw
+/// * `available_column_count` - Width available in characters
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Rendered lines
+fn render_histogram_text_lines(
+    histogram_data: &HistogramChartData,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let mut rendered_lines = vec![histogram_data.chart_title.clone()];
+
+    // Reserve lines for the title, the x axis, and the x axis labels
+    let bar_area_row_count = available_row_count.saturating_sub(3).max(1);
+    let largest_bin_count = histogram_data.bin_counts.iter().copied().max().unwrap_or(0);
+
+    let y_axis_label_text = largest_bin_count.to_string();
+    let y_axis_label_width = y_axis_label_text.chars().count();
+    let plot_area_width = available_column_count.saturating_sub(y_axis_label_width + 2).max(1);
+
+    // Each bin gets an equal share of the plot width (at least one character)
+    let bin_count = histogram_data.bin_counts.len().max(1);
+    let characters_per_bin = (plot_area_width / bin_count).max(1);
+
+    let (vertical_axis_glyph, horizontal_axis_glyph, corner_glyph) = match glyph_mode {
+        ChartGlyphMode::Unicode => ('│', '─', '└'),
+        ChartGlyphMode::Ascii => ('|', '-', '+'),
+    };
+
+    // Bar heights are tracked in eighths of a line for smooth Unicode blocks
+    let eighths_per_line = 8;
+    let bar_heights_in_eighths: Vec<usize> = histogram_data.bin_counts
+        .iter()
+        .map(|&bin_count_value| {
+            (bin_count_value * bar_area_row_count * eighths_per_line)
+                .checked_div(largest_bin_count)
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for bar_row_index in 0..bar_area_row_count {
+        // Row 0 is the top of the bar area
+        let eighths_below_this_row = (bar_area_row_count - bar_row_index - 1) * eighths_per_line;
+
+        let y_axis_prefix = if bar_row_index == 0 {
+            format!("{:>width$} {}", y_axis_label_text, vertical_axis_glyph, width = y_axis_label_width)
+        } else {
+            format!("{:>width$} {}", "", vertical_axis_glyph, width = y_axis_label_width)
+        };
+
+        let mut bar_row_text = y_axis_prefix;
+        for &bar_height_in_eighths in &bar_heights_in_eighths {
+            let bar_glyph = select_histogram_bar_glyph(
+                bar_height_in_eighths.saturating_sub(eighths_below_this_row),
+                glyph_mode,
+            );
+            for _ in 0..characters_per_bin {
+                bar_row_text.push(bar_glyph);
+            }
+        }
+        rendered_lines.push(bar_row_text);
+    }
+
+    // X axis line
+    let x_axis_length = characters_per_bin * bin_count;
+    let mut x_axis_line = format!("{:>width$} {}", "0", corner_glyph, width = y_axis_label_width);
+    for _ in 0..x_axis_length {
+        x_axis_line.push(horizontal_axis_glyph);
+    }
+    rendered_lines.push(x_axis_line);
+
+    // X axis labels: minimum on the left, maximum on the right
+    let minimum_label = format_compact_axis_value(histogram_data.minimum_value);
+    let maximum_label = format_compact_axis_value(histogram_data.maximum_value);
+    let label_gap_width = x_axis_length
+        .saturating_sub(minimum_label.chars().count() + maximum_label.chars().count())
+        .max(1);
+    rendered_lines.push(format!(
+        "{:width$}  {}{:gap$}{}",
+        "",
+        minimum_label,
+        "",
+        maximum_label,
+        width = y_axis_label_width,
+        gap = label_gap_width
+    ));
+
+    rendered_lines
+}
+
+/// Selects the glyph for one cell of a histogram bar
+///
+/// # Arguments
+/// * `filled_eighths` - How many eighths of this cell are filled (8+ means full)
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `char` - The glyph to draw
+fn select_histogram_bar_glyph(filled_eighths: usize, glyph_mode: ChartGlyphMode) -> char {
+    match glyph_mode {
+        ChartGlyphMode::Unicode => {
+            const PARTIAL_BLOCK_GLYPHS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+            PARTIAL_BLOCK_GLYPHS[filled_eighths.min(8)]
+        }
+        ChartGlyphMode::Ascii => {
+            // A single pipe is a fine histogram bar in ASCII mode
+            if filled_eighths >= 4 { '|' } else { ' ' }
+        }
+    }
+}
+
+/// Renders a vertical box-and-whiskers plot three characters wide
+///
+/// The layout follows the project README: whisker caps at min and max, a box
+/// from q1 to q3, and a median line, each labelled on the right.
+///
+/// # Arguments
+/// * `box_plot_data` - The five-number summary to draw
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Rendered lines
+fn render_box_plot_text_lines(
+    box_plot_data: &BoxPlotChartData,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let mut rendered_lines = vec![box_plot_data.chart_title.clone()];
+
+    // Need at least five plot rows to show every marker on its own row
+    let plot_row_count = available_row_count.saturating_sub(1).max(5);
+    let value_range = box_plot_data.maximum_value - box_plot_data.minimum_value;
+
+    // Maps a value to a plot row (row 0 = maximum at the top)
+    let value_to_plot_row = |plotted_value: f64| -> usize {
+        if value_range <= 0.0 {
+            return plot_row_count / 2;
+        }
+        let relative_height = (plotted_value - box_plot_data.minimum_value) / value_range;
+        let row_from_bottom = (relative_height * (plot_row_count - 1) as f64).round() as usize;
+        (plot_row_count - 1).saturating_sub(row_from_bottom)
+    };
+
+    let maximum_row = value_to_plot_row(box_plot_data.maximum_value);
+    let third_quartile_row = value_to_plot_row(box_plot_data.third_quartile_value);
+    let median_row = value_to_plot_row(box_plot_data.median_value);
+    let first_quartile_row = value_to_plot_row(box_plot_data.first_quartile_value);
+    let minimum_row = value_to_plot_row(box_plot_data.minimum_value);
+
+    let (whisker_cap_top, whisker_cap_bottom, whisker_line, box_top, box_side, box_median, box_bottom) = match glyph_mode {
+        ChartGlyphMode::Unicode => ("─┬─", "─┴─", " │ ", "┌─┐", "│ │", "├─┤", "└─┘"),
+        ChartGlyphMode::Ascii => ("-|-", "-|-", " | ", "+-+", "| |", "---", "+-+"),
+    };
+
+    for plot_row_index in 0..plot_row_count {
+        // Priority when markers share a row: median, quartiles, then extremes
+        let (row_glyphs, row_label) = if plot_row_index == median_row {
+            (box_median, format!("(median) {}", format_compact_axis_value(box_plot_data.median_value)))
+        } else if plot_row_index == third_quartile_row {
+            (box_top, format!("(q3) {}", format_compact_axis_value(box_plot_data.third_quartile_value)))
+        } else if plot_row_index == first_quartile_row {
+            (box_bottom, format!("(q1) {}", format_compact_axis_value(box_plot_data.first_quartile_value)))
+        } else if plot_row_index == maximum_row {
+            (whisker_cap_top, format!("(max) {}", format_compact_axis_value(box_plot_data.maximum_value)))
+        } else if plot_row_index == minimum_row {
+            (whisker_cap_bottom, format!("(min) {}", format_compact_axis_value(box_plot_data.minimum_value)))
+        } else if plot_row_index > third_quartile_row && plot_row_index < first_quartile_row {
+            (box_side, String::new())
+        } else if plot_row_index > maximum_row && plot_row_index < minimum_row {
+            (whisker_line, String::new())
+        } else {
+            ("   ", String::new())
+        };
+
+        if row_label.is_empty() {
+            rendered_lines.push(format!("  {}", row_glyphs));
+        } else {
+            rendered_lines.push(format!("  {}  {}", row_glyphs, row_label));
+        }
+    }
+
+    rendered_lines
+}
+
+/// Renders a horizontal bar chart, one category per line
+///
+/// # Arguments
+/// * `bar_chart_data` - The bars to draw
+/// * `available_column_count` - Width available in characters
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Rendered lines
+fn render_bar_chart_text_lines(
+    bar_chart_data: &BarChartData,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let mut rendered_lines = vec![bar_chart_data.chart_title.clone()];
+
+    if bar_chart_data.chart_bars.is_empty() {
+        rendered_lines.push("  (no values)".to_string());
+        return rendered_lines;
+    }
+
+    // Keep one line for the title and one for the "more categories" note
+    let visible_bar_count = bar_chart_data.chart_bars.len().min(available_row_count.saturating_sub(2).max(1));
+    let hidden_bar_count = bar_chart_data.chart_bars.len() - visible_bar_count + bar_chart_data.omitted_category_count;
+
+    let label_column_width = bar_chart_data.chart_bars
+        .iter()
+        .take(visible_bar_count)
+        .map(|chart_bar| chart_bar.bar_label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(20);
+
+    let largest_bar_count = bar_chart_data.chart_bars
+        .iter()
+        .map(|chart_bar| chart_bar.bar_count)
+        .max()
+        .unwrap_or(0);
+
+    // Width left for the bar itself after label, separator, and count text
+    let count_text_width = format!(" {} (100.0%)", largest_bar_count).chars().count();
+    let bar_area_width = available_column_count
+        .saturating_sub(label_column_width + 3 + count_text_width)
+        .max(1);
+
+    let (separator_glyph, bar_glyph) = match glyph_mode {
+        ChartGlyphMode::Unicode => ('│', '█'),
+        ChartGlyphMode::Ascii => ('|', '#'),
+    };
+
+    for chart_bar in bar_chart_data.chart_bars.iter().take(visible_bar_count) {
+        let bar_length = (chart_bar.bar_count * bar_area_width)
+            .checked_div(largest_bar_count)
+            .map(|scaled_length| scaled_length.max(1))
+            .unwrap_or(0);
+
+        let clipped_label: String = chart_bar.bar_label.chars().take(label_column_width).collect();
+        let bar_text: String = std::iter::repeat_n(bar_glyph, bar_length).collect();
+
+        rendered_lines.push(format!(
+            "{:>width$} {} {} {} ({:.1}%)",
+            clipped_label,
+            separator_glyph,
+            bar_text,
+            chart_bar.bar_count,
+            chart_bar.bar_percentage,
+            width = label_column_width
+        ));
+    }
+
+    if hidden_bar_count > 0 {
+        rendered_lines.push(format!("  ... {} more categories not shown", hidden_bar_count));
+    }
+
+    rendered_lines
+}
+
+/// Renders a scatter plot as a density grid of characters
+///
+/// # Arguments
+/// * `scatter_plot_data` - The points to draw
+/// * `available_column_count` - Width available in characters
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Rendered lines
+fn render_scatter_plot_text_lines(
+    scatter_plot_data: &ScatterPlotChartData,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let mut title_text = scatter_plot_data.chart_title.clone();
+    if scatter_plot_data.points_were_capped {
+        title_text.push_str(&format!(" [first {} points]", scatter_plot_data.plot_points.len()));
+    }
+    let mut rendered_lines = vec![title_text];
+
+    if scatter_plot_data.plot_points.is_empty() {
+        rendered_lines.push("  (no paired numerical values)".to_string());
+        return rendered_lines;
+    }
+
+    // Determine data ranges
+    let (mut x_minimum, mut x_maximum) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut y_minimum, mut y_maximum) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &(x_value, y_value) in &scatter_plot_data.plot_points {
+        x_minimum = x_minimum.min(x_value);
+        x_maximum = x_maximum.max(x_value);
+        y_minimum = y_minimum.min(y_value);
+        y_maximum = y_maximum.max(y_value);
+    }
+
+    let y_maximum_label = format_compact_axis_value(y_maximum);
+    let y_minimum_label = format_compact_axis_value(y_minimum);
+    let y_label_width = y_maximum_label.chars().count().max(y_minimum_label.chars().count());
+
+    // Reserve lines for the title, x axis, x value labels, and axis names
+    let grid_row_count = available_row_count.saturating_sub(4).max(2);
+    let grid_column_count = available_column_count.saturating_sub(y_label_width + 2).max(2);
+
+    // Count points per grid cell
+    let mut cell_point_counts = vec![vec![0usize; grid_column_count]; grid_row_count];
+    for &(x_value, y_value) in &scatter_plot_data.plot_points {
+        let grid_column = calculate_histogram_bin_index(x_value, x_minimum, x_maximum, grid_column_count);
+        let grid_row_from_bottom = calculate_histogram_bin_index(y_value, y_minimum, y_maximum, grid_row_count);
+        let grid_row = grid_row_count - 1 - grid_row_from_bottom;
+        cell_point_counts[grid_row][grid_column] += 1;
+    }
+
+    let (vertical_axis_glyph, horizontal_axis_glyph, corner_glyph, density_glyphs) = match glyph_mode {
+        ChartGlyphMode::Unicode => ('│', '─', '└', ['·', '•', '●']),
+        ChartGlyphMode::Ascii => ('|', '-', '+', ['.', 'o', '@']),
+    };
+
+    for (grid_row_index, grid_row_counts) in cell_point_counts.iter().enumerate() {
+        let y_label = if grid_row_index == 0 {
+            y_maximum_label.clone()
+        } else if grid_row_index == grid_row_count - 1 {
+            y_minimum_label.clone()
+        } else {
+            String::new()
+        };
+
+        let mut grid_line = format!("{:>width$} {}", y_label, vertical_axis_glyph, width = y_label_width);
+        for &cell_count in grid_row_counts {
+            let cell_glyph = match cell_count {
+                0 => ' ',
+                1 => density_glyphs[0],
+                2..=4 => density_glyphs[1],
+                _ => density_glyphs[2],
+            };
+            grid_line.push(cell_glyph);
+        }
+        rendered_lines.push(grid_line);
+    }
+
+    // X axis line and labels
+    let mut x_axis_line = format!("{:>width$} {}", "", corner_glyph, width = y_label_width);
+    for _ in 0..grid_column_count {
+        x_axis_line.push(horizontal_axis_glyph);
+    }
+    rendered_lines.push(x_axis_line);
+
+    let x_minimum_label = format_compact_axis_value(x_minimum);
+    let x_maximum_label = format_compact_axis_value(x_maximum);
+    let x_label_gap_width = grid_column_count
+        .saturating_sub(x_minimum_label.chars().count() + x_maximum_label.chars().count())
+        .max(1);
+    rendered_lines.push(format!(
+        "{:width$}  {}{:gap$}{}",
+        "",
+        x_minimum_label,
+        "",
+        x_maximum_label,
+        width = y_label_width,
+        gap = x_label_gap_width
+    ));
+    rendered_lines.push(format!(
+        "{:width$}  x: {}  y: {}",
+        "",
+        scatter_plot_data.x_axis_label,
+        scatter_plot_data.y_axis_label,
+        width = y_label_width
+    ));
+
+    rendered_lines
+}
+
+/// Selects the shading glyph for a coefficient magnitude
+///
+/// Magnitudes are split into five equal bands from 0 to 1.
+///
+/// # Arguments
+/// * `coefficient_magnitude` - Absolute coefficient value (0.0 to 1.0)
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `char` - The shading glyph
+pub fn select_heatmap_shade_glyph(coefficient_magnitude: f64, glyph_mode: ChartGlyphMode) -> char {
+    let shade_glyphs = match glyph_mode {
+        ChartGlyphMode::Unicode => [' ', '░', '▒', '▓', '█'],
+        ChartGlyphMode::Ascii => [' ', '.', ':', '*', '#'],
+    };
+
+    let shade_level = ((coefficient_magnitude.clamp(0.0, 1.0) * 5.0).floor() as usize).min(4);
+    shade_glyphs[shade_level]
+}
+
+/// Renders a heatmap as a grid of shaded three-character cells
+///
+/// Each cell is a sign character (`-` for negative values) followed by two
+/// shading glyphs. Columns are numbered; the row labels give the names.
+///
+/// # Arguments
+/// * `heatmap_data` - The matrix to draw
+/// * `available_column_count` - Width available in characters
+/// * `available_row_count` - Height available in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Rendered lines
+fn render_heatmap_text_lines(
+    heatmap_data: &HeatmapChartData,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> Vec<String> {
+    let mut rendered_lines = vec![heatmap_data.chart_title.clone()];
+
+    if heatmap_data.axis_labels.is_empty() {
+        rendered_lines.push("  (no values)".to_string());
+        return rendered_lines;
+    }
+
+    let number_width = heatmap_data.axis_labels.len().to_string().len();
+    let label_column_width = heatmap_data.axis_labels
+        .iter()
+        .map(|axis_label| axis_label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(12);
+    let row_prefix_width = number_width + 1 + label_column_width + 1;
+
+    // Fit as many cells as the width allows; keep title, header and legend lines
+    let visible_cell_column_count = (available_column_count.saturating_sub(row_prefix_width) / 3)
+        .clamp(1, heatmap_data.axis_labels.len());
+    let visible_cell_row_count = available_row_count
+        .saturating_sub(3)
+        .clamp(1, heatmap_data.axis_labels.len());
+
+    let mut header_line = " ".repeat(row_prefix_width);
+    for cell_column_position in 0..visible_cell_column_count {
+        header_line.push_str(&format!("{:>3}", cell_column_position + 1));
+    }
+    rendered_lines.push(header_line);
+
+    let missing_glyph = match glyph_mode {
+        ChartGlyphMode::Unicode => '·',
+        ChartGlyphMode::Ascii => '?',
+    };
+
+    for (cell_row_position, axis_label) in heatmap_data.axis_labels.iter().enumerate().take(visible_cell_row_count) {
+        let clipped_label: String = axis_label.chars().take(label_column_width).collect();
+        let mut row_line = format!(
+            "{:>number_width$} {:<label_width$} ",
+            cell_row_position + 1,
+            clipped_label,
+            number_width = number_width,
+            label_width = label_column_width
+        );
+
+        for cell_column_position in 0..visible_cell_column_count {
+            let cell_value = heatmap_data.cell_values
+                .get(cell_row_position)
+                .and_then(|row_values| row_values.get(cell_column_position))
+                .copied()
+                .flatten();
+
+            match cell_value {
+                Some(coefficient_value) => {
+                    let shade_glyph = select_heatmap_shade_glyph(coefficient_value.abs(), glyph_mode);
+                    row_line.push(if coefficient_value < 0.0 { '-' } else { ' ' });
+                    row_line.push(shade_glyph);
+                    row_line.push(shade_glyph);
+                }
+                None => {
+                    row_line.push(' ');
+                    row_line.push(missing_glyph);
+                    row_line.push(' ');
+                }
+            }
+        }
+        rendered_lines.push(row_line);
+    }
+
+    let legend_glyphs: String = [0.0, 0.2, 0.4, 0.6, 0.8]
+        .iter()
+        .map(|band_start| select_heatmap_shade_glyph(*band_start, glyph_mode))
+        .collect();
+    rendered_lines.push(format!(
+        "|r| 0→1: [{}]  '-' negative  {} n/a{}",
+        legend_glyphs,
+        missing_glyph,
+        if visible_cell_column_count < heatmap_data.axis_labels.len() || visible_cell_row_count < heatmap_data.axis_labels.len() {
+            "  (clipped)"
+        } else {
+            ""
+        }
+    ));
+
+    rendered_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that histogram bin assignment clamps to the valid range
+    #[test]
+    fn test_calculate_histogram_bin_index() {
+        assert_eq!(calculate_histogram_bin_index(0.0, 0.0, 10.0, 5), 0);
+        assert_eq!(calculate_histogram_bin_index(10.0, 0.0, 10.0, 5), 4);
+        assert_eq!(calculate_histogram_bin_index(5.0, 0.0, 10.0, 5), 2);
+        assert_eq!(calculate_histogram_bin_index(-3.0, 0.0, 10.0, 5), 0);
+        assert_eq!(calculate_histogram_bin_index(7.0, 7.0, 7.0, 5), 0);
+    }
+
+    /// Test that rendered charts respect the requested size in both glyph modes
+    #[test]
+    fn test_render_chart_respects_size() {
+        let histogram_chart = ChartDefinition::Histogram(HistogramChartData {
+            chart_title: "age (histogram)".to_string(),
+            minimum_value: 1.0,
+            maximum_value: 5.0,
+            bin_counts: vec![1, 3, 0, 2],
+        });
+
+        for glyph_mode in [ChartGlyphMode::Unicode, ChartGlyphMode::Ascii] {
+            let rendered_lines = render_chart_as_text_lines(&histogram_chart, 30, 10, glyph_mode);
+            assert!(rendered_lines.len() <= 10);
+            assert!(rendered_lines.iter().all(|line| line.chars().count() <= 30));
+        }
+    }
+
+    /// Test that the ASCII box plot uses only ASCII and labels every marker
+    #[test]
+    fn test_box_plot_ascii_rendering() {
+        let box_plot_chart = ChartDefinition::BoxPlot(BoxPlotChartData {
+            chart_title: "age (box plot)".to_string(),
+            minimum_value: 1.0,
+            first_quartile_value: 2.0,
+            median_value: 3.0,
+            third_quartile_value: 4.0,
+            maximum_value: 5.0,
+        });
+
+        let rendered_lines = render_chart_as_text_lines(&box_plot_chart, 40, 12, ChartGlyphMode::Ascii);
+        let joined_text = rendered_lines.join("\n");
+
+        assert!(joined_text.is_ascii());
+        for marker_label in ["(max)", "(q3)", "(median)", "(q1)", "(min)"] {
+            assert!(joined_text.contains(marker_label), "missing {}", marker_label);
+        }
+    }
+}
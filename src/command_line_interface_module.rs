@@ -0,0 +1,1201 @@
+// src/command_line_interface_module.rs
+
+/// Subcommand command line parsing for rows_and_columns
+///
+/// `rows_and_columns describe cats.csv --columns age,breed --format json`
+///
+/// Every subcommand is described once by a `SubcommandSpecification` (its
+/// positional argument, the flags it accepts and their allowed values), and
+/// both the parser and the help text are driven by that table, so they
+/// cannot disagree.
+///
+/// # Core Responsibilities
+/// - Recognize the subcommands `analyze`, `describe`, `import`, `export`,
+///   `plot`, `query`, `validate`, `datasets` and `batch`
+/// - Parse `--flag value`, `--flag=value`, short `-f value` flags, switches
+///   such as `--no-interactive`, and `--` to end flag parsing
+/// - Accept the global `--headless` switch and `--data-dir` option anywhere before `--`
+/// - Report mistakes as `CommandLineUsageError`s that name the problem and
+///   suggest the closest subcommand or flag for typos
+/// - Produce the general help and the per-subcommand help text
+/// - Resolve `--columns` lists given as names or 1-based numbers
+///
+/// # Design Philosophy
+/// - Standard library only; no argument parsing crates
+/// - Older invocations keep working: `rows_and_columns cats.csv` analyzes the
+///   file and `rows_and_columns --json cats.csv` describes it as JSON
+/// - Parsing is pure (no I/O), so it is easy to test; running the parsed
+///   subcommand is the application module's job
+use std::collections::{BTreeMap, BTreeSet};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{EXIT_CODE_DESCRIPTIONS, RowsAndColumnsResult, create_command_line_usage_error};
+
+/// Name used in usage lines and help text
+const PROGRAM_NAME: &str = "rows_and_columns";
+
+/// Global switch for unattended runs (no banner, prompts, menu or emoji)
+const HEADLESS_SWITCH: &str = "--headless";
+
+/// Global option naming the data directory (`--data-dir <directory>` or `--data-dir=<directory>`)
+const DATA_DIRECTORY_OPTION: &str = "--data-dir";
+
+/// Largest edit distance for "did you mean" suggestions
+const MAXIMUM_SUGGESTION_EDIT_DISTANCE: usize = 2;
+
+/// The subcommands the application understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandLineSubcommand {
+    /// Full analysis followed by the interactive menu
+    Analyze,
+    /// Column statistics only
+    Describe,
+    /// Store a CSV in the directory store
+    Import,
+    /// Write an analysis report file
+    Export,
+    /// Write a chart file
+    Plot,
+    /// Filter and project rows
+    Query,
+    /// Check values against the column types in the metadata
+    Validate,
+    /// List datasets in the directory store
+    Datasets,
+    /// Analyze every CSV file of a directory
+    Batch,
+}
+
+impl CommandLineSubcommand {
+    /// Every subcommand, in help order
+    pub const ALL_SUBCOMMANDS: [CommandLineSubcommand; 9] = [
+        CommandLineSubcommand::Analyze,
+        CommandLineSubcommand::Describe,
+        CommandLineSubcommand::Import,
+        CommandLineSubcommand::Export,
+        CommandLineSubcommand::Plot,
+        CommandLineSubcommand::Query,
+        CommandLineSubcommand::Validate,
+        CommandLineSubcommand::Datasets,
+        CommandLineSubcommand::Batch,
+    ];
+
+    /// Returns the name typed on the command line
+    ///
+    /// # Returns
+    /// * `&'static str` - e.g. `describe`
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            CommandLineSubcommand::Analyze => "analyze",
+            CommandLineSubcommand::Describe => "describe",
+            CommandLineSubcommand::Import => "import",
+            CommandLineSubcommand::Export => "export",
+            CommandLineSubcommand::Plot => "plot",
+            CommandLineSubcommand::Query => "query",
+            CommandLineSubcommand::Validate => "validate",
+            CommandLineSubcommand::Datasets => "datasets",
+            CommandLineSubcommand::Batch => "batch",
+        }
+    }
+
+    /// Looks up a subcommand by its name
+    ///
+    /// # Arguments
+    /// * `command_name` - The name as typed (exact, lowercase)
+    ///
+    /// # Returns
+    /// * `Option<CommandLineSubcommand>` - The subcommand, if the name is known
+    pub fn from_command_name(command_name: &str) -> Option<CommandLineSubcommand> {
+        CommandLineSubcommand::ALL_SUBCOMMANDS
+            .into_iter()
+            .find(|subcommand| subcommand.command_name() == command_name)
+    }
+
+    /// Returns the table describing this subcommand
+    ///
+    /// # Returns
+    /// * `&'static SubcommandSpecification` - Arguments, flags and help text
+    pub fn specification(&self) -> &'static SubcommandSpecification {
+        match self {
+            CommandLineSubcommand::Analyze => &ANALYZE_SPECIFICATION,
+            CommandLineSubcommand::Describe => &DESCRIBE_SPECIFICATION,
+            CommandLineSubcommand::Import => &IMPORT_SPECIFICATION,
+            CommandLineSubcommand::Export => &EXPORT_SPECIFICATION,
+            CommandLineSubcommand::Plot => &PLOT_SPECIFICATION,
+            CommandLineSubcommand::Query => &QUERY_SPECIFICATION,
+            CommandLineSubcommand::Validate => &VALIDATE_SPECIFICATION,
+            CommandLineSubcommand::Datasets => &DATASETS_SPECIFICATION,
+            CommandLineSubcommand::Batch => &BATCH_SPECIFICATION,
+        }
+    }
+}
+
+/// One flag a subcommand accepts
+#[derive(Debug)]
+pub struct CommandLineFlagSpecification {
+    /// Long name without dashes, e.g. `format`
+    pub long_flag_name: &'static str,
+
+    /// Optional one-letter short form, e.g. `f` for `-f`
+    pub short_flag_letter: Option<char>,
+
+    /// Placeholder for the value in help (`None` for switches such as `--no-interactive`)
+    pub value_placeholder: Option<&'static str>,
+
+    /// Allowed values (empty when any value is accepted)
+    pub allowed_values: &'static [&'static str],
+
+    /// One-line description for help
+    pub flag_description: &'static str,
+}
+
+/// Everything the parser and the help text need to know about a subcommand
+#[derive(Debug)]
+pub struct SubcommandSpecification {
+    /// One-line summary for the general help
+    pub one_line_summary: &'static str,
+
+    /// Placeholder of the positional argument, e.g. `<csv_file>` (`None` for no argument)
+    pub positional_placeholder: Option<&'static str>,
+
+    /// Paragraph shown at the top of the subcommand help
+    pub detailed_description: &'static str,
+
+    /// Flags accepted after the subcommand
+    pub accepted_flags: &'static [CommandLineFlagSpecification],
+
+    /// Example invocations (without the program name)
+    pub usage_examples: &'static [&'static str],
+}
+
+/// `--format text|json`
+const TEXT_OR_JSON_FORMAT_FLAG: CommandLineFlagSpecification = CommandLineFlagSpecification {
+    long_flag_name: "format",
+    short_flag_letter: Some('f'),
+    value_placeholder: Some("text|json"),
+    allowed_values: &["text", "json"],
+    flag_description: "Print human-readable text (default) or a JSON document",
+};
+
+/// `--columns <list>`
+const COLUMNS_FLAG: CommandLineFlagSpecification = CommandLineFlagSpecification {
+    long_flag_name: "columns",
+    short_flag_letter: Some('c'),
+    value_placeholder: Some("<names|numbers>"),
+    allowed_values: &[],
+    flag_description: "Only these columns, e.g. age,breed or 1,3",
+};
+
+/// `--no-interactive`
+const NO_INTERACTIVE_FLAG: CommandLineFlagSpecification = CommandLineFlagSpecification {
+    long_flag_name: "no-interactive",
+    short_flag_letter: None,
+    value_placeholder: None,
+    allowed_values: &[],
+    flag_description: "Print the analysis and exit instead of opening the menu",
+};
+
+const ANALYZE_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Analyze a CSV file, then open the interactive menu",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Detects the header and column types, writes the .csv_metadata.toml, shows \
+statistics, correlations and derived columns, then opens the menu of further steps \
+(load, filter, sort, join, charts, ...). With --format json only the JSON document is printed.",
+    accepted_flags: &[TEXT_OR_JSON_FORMAT_FLAG, NO_INTERACTIVE_FLAG],
+    usage_examples: &["analyze data/cats.csv", "analyze data/cats.csv --no-interactive"],
+};
+
+const DESCRIBE_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Print column statistics (no menu)",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Prints the per-column statistics: quartiles, mean and standard deviation \
+for continuous columns, value frequencies for categorical ones, and missing percentages.",
+    accepted_flags: &[TEXT_OR_JSON_FORMAT_FLAG, COLUMNS_FLAG],
+    usage_examples: &[
+        "describe data/cats.csv --columns age,breed",
+        "describe data/cats.csv --format json | jq '.columns[].name'",
+        "describe - < data/cats.csv",
+    ],
+};
+
+const IMPORT_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Store a CSV file in the directory store",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Writes every value to column_NNN_name/row_NNNN/value.txt under a new \
+dataset directory, together with saved derived columns, so later steps can read columns without \
+loading the file.",
+    accepted_flags: &[CommandLineFlagSpecification {
+        long_flag_name: "output",
+        short_flag_letter: Some('o'),
+        value_placeholder: Some("<directory>"),
+        allowed_values: &[],
+        flag_description: "Directory to create the dataset in (default: rows_columns_data/csv_imports)",
+    }],
+    usage_examples: &["import data/cats.csv", "import data/cats.csv --output /srv/datasets"],
+};
+
+const EXPORT_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Write an analysis report (Markdown, text or HTML)",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Writes the analysis report with statistics, data quality warnings and \
+charts. Without --output the report goes to rows_columns_data/analysis_cache/.",
+    accepted_flags: &[
+        CommandLineFlagSpecification {
+            long_flag_name: "format",
+            short_flag_letter: Some('f'),
+            value_placeholder: Some("md|txt|html"),
+            allowed_values: &["md", "txt", "html"],
+            flag_description: "Report format (default: the --output extension, else md)",
+        },
+        COLUMNS_FLAG,
+        CommandLineFlagSpecification {
+            long_flag_name: "output",
+            short_flag_letter: Some('o'),
+            value_placeholder: Some("<file>"),
+            allowed_values: &[],
+            flag_description: "Where to write the report",
+        },
+    ],
+    usage_examples: &["export data/cats.csv --format html --output cats.html"],
+};
+
+const PLOT_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Write a chart file (SVG, PNG or PPM)",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "One column gives a histogram (numbers) or a bar chart (categories); \
+two numeric columns give a scatter plot of the second against the first. Without --output the \
+chart goes to rows_columns_data/analysis_cache/.",
+    accepted_flags: &[
+        CommandLineFlagSpecification {
+            long_flag_name: "columns",
+            short_flag_letter: Some('c'),
+            value_placeholder: Some("<x[,y]>"),
+            allowed_values: &[],
+            flag_description: "One or two columns to plot (required)",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "format",
+            short_flag_letter: Some('f'),
+            value_placeholder: Some("svg|png|ppm"),
+            allowed_values: &["svg", "png", "ppm"],
+            flag_description: "Image format (default: the --output extension, else svg)",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "output",
+            short_flag_letter: Some('o'),
+            value_placeholder: Some("<file>"),
+            allowed_values: &[],
+            flag_description: "Where to write the chart",
+        },
+    ],
+    usage_examples: &["plot data/cats.csv --columns age", "plot data/cats.csv --columns age,weight --output scatter.png"],
+};
+
+const QUERY_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Print or save the rows matching a filter",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Streams the rows that match --where (the filter language of menu option 8), \
+keeps the --columns given, and prints them as CSV or JSON, or writes a CSV with its metadata \
+when --output is given.",
+    accepted_flags: &[
+        CommandLineFlagSpecification {
+            long_flag_name: "where",
+            short_flag_letter: Some('w'),
+            value_placeholder: Some("<expression>"),
+            allowed_values: &[],
+            flag_description: "Filter, e.g. 'age >= 3 and breed != \"Persian\"' (default: all rows)",
+        },
+        COLUMNS_FLAG,
+        CommandLineFlagSpecification {
+            long_flag_name: "limit",
+            short_flag_letter: Some('n'),
+            value_placeholder: Some("<rows>"),
+            allowed_values: &[],
+            flag_description: "Stop after this many matching rows",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "format",
+            short_flag_letter: Some('f'),
+            value_placeholder: Some("csv|json"),
+            allowed_values: &["csv", "json"],
+            flag_description: "Printed format (default csv); --output always writes CSV",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "output",
+            short_flag_letter: Some('o'),
+            value_placeholder: Some("<file>"),
+            allowed_values: &[],
+            flag_description: "Write a CSV file (and its metadata) instead of printing",
+        },
+    ],
+    usage_examples: &["query data/cats.csv --where 'age > 2' --columns name,age", "query data/cats.csv -w 'is_indoor = true' --format json"],
+};
+
+const VALIDATE_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Check values against the column types in the metadata",
+    positional_placeholder: Some("<csv_file>"),
+    detailed_description: "Reads every row and checks each value against the data_type saved in \
+the .csv_metadata.toml, and that every row has the expected number of fields. Exits with an \
+error when anything does not match. A file without metadata gets one from the detected types.",
+    accepted_flags: &[TEXT_OR_JSON_FORMAT_FLAG, COLUMNS_FLAG],
+    usage_examples: &["validate data/cats.csv", "validate data/cats.csv --columns age --format json"],
+};
+
+const DATASETS_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "List the datasets in the directory store",
+    positional_placeholder: None,
+    detailed_description: "Lists every dataset imported into the csv_imports/ directory of the data \
+directory with its row and column counts and the CSV it came from, then shows which data directory \
+was used and why (--data-dir, ROWS_AND_COLUMNS_DATA_DIR, XDG or next to the executable).",
+    accepted_flags: &[TEXT_OR_JSON_FORMAT_FLAG],
+    usage_examples: &["datasets", "datasets --format json", "datasets --data-dir /srv/rows_and_columns"],
+};
+
+const BATCH_SPECIFICATION: SubcommandSpecification = SubcommandSpecification {
+    one_line_summary: "Analyze every CSV file in a directory and index the results",
+    positional_placeholder: Some("<directory>"),
+    detailed_description: "Analyzes each .csv, .tsv, .csv.gz and .tsv.gz file of the directory (dot files \
+skipped), writes its .csv_metadata.toml and an analysis report, and writes batch_index.md listing \
+row and column counts, detected schemas, errors, and which files share an identical schema. A file \
+that fails is listed with its error and the batch goes on. Without --output the reports and the \
+index go to rows_columns_data/analysis_cache/batch_<directory name>/.",
+    accepted_flags: &[
+        CommandLineFlagSpecification {
+            long_flag_name: "recursive",
+            short_flag_letter: Some('r'),
+            value_placeholder: None,
+            allowed_values: &[],
+            flag_description: "Include the CSV files of subdirectories",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "report",
+            short_flag_letter: None,
+            value_placeholder: Some("md|txt|html"),
+            allowed_values: &["md", "txt", "html"],
+            flag_description: "Format of the per-file reports (default md)",
+        },
+        CommandLineFlagSpecification {
+            long_flag_name: "output",
+            short_flag_letter: Some('o'),
+            value_placeholder: Some("<directory>"),
+            allowed_values: &[],
+            flag_description: "Directory for the reports and batch_index.md",
+        },
+        TEXT_OR_JSON_FORMAT_FLAG,
+    ],
+    usage_examples: &["batch incoming/", "batch incoming/ --recursive --report html --output incoming_reports"],
+};
+
+/// A subcommand with its parsed arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSubcommandArguments {
+    /// Which subcommand to run
+    pub subcommand: CommandLineSubcommand,
+
+    /// The positional argument (usually the CSV file), if given
+    pub positional_argument: Option<String>,
+
+    /// Flag values keyed by long flag name
+    flag_values: BTreeMap<&'static str, String>,
+
+    /// Switches that were given, by long flag name
+    enabled_switches: BTreeSet<&'static str>,
+
+    /// Whether the global `--headless` switch was given
+    pub headless_mode: bool,
+
+    /// Directory given with the global `--data-dir` option
+    pub data_directory_argument: Option<String>,
+}
+
+impl ParsedSubcommandArguments {
+    /// Returns the value of a flag, if it was given
+    ///
+    /// # Arguments
+    /// * `long_flag_name` - e.g. `format`
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The value
+    pub fn flag_value(&self, long_flag_name: &str) -> Option<&str> {
+        self.flag_values.get(long_flag_name).map(String::as_str)
+    }
+
+    /// Whether a switch such as `no-interactive` was given
+    ///
+    /// # Arguments
+    /// * `long_flag_name` - e.g. `no-interactive`
+    ///
+    /// # Returns
+    /// * `bool` - True when given
+    pub fn has_switch(&self, long_flag_name: &str) -> bool {
+        self.enabled_switches.contains(long_flag_name)
+    }
+
+    /// Returns the positional argument, which this subcommand requires
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<&str>` - The argument (usually the CSV file path)
+    ///
+    /// # Errors
+    /// * `CommandLineUsageError` - If it was not given
+    pub fn required_positional_argument(&self) -> RowsAndColumnsResult<&str> {
+        let placeholder = self.subcommand.specification().positional_placeholder.unwrap_or("<argument>");
+        self.positional_argument.as_deref().ok_or_else(|| {
+            create_command_line_usage_error(
+                &format!("{} needs {}", self.subcommand.command_name(), placeholder),
+                Some(self.subcommand.command_name())
+            )
+        })
+    }
+}
+
+/// What the command line asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedCommandLine {
+    /// No arguments: browse for a CSV file, preview it and analyze it
+    InteractiveFileSelection {
+        /// Directory given with the global `--data-dir` option
+        data_directory_argument: Option<String>,
+    },
+    /// Run a subcommand
+    RunSubcommand(ParsedSubcommandArguments),
+    /// `--help`, `-h` or `help`
+    ShowGeneralHelp,
+    /// `<subcommand> --help` or `help <subcommand>`
+    ShowSubcommandHelp(CommandLineSubcommand),
+    /// `--version` or `-V`
+    ShowVersion,
+}
+
+/// Options that apply to every subcommand
+#[derive(Debug, Clone, Default, PartialEq)]
+struct GlobalCommandLineOptions {
+    /// `--headless` was given
+    headless_mode: bool,
+
+    /// Value of `--data-dir`, if given
+    data_directory_argument: Option<String>,
+}
+
+/// Removes the global options from the arguments before `--`
+///
+/// # Arguments
+/// * `command_line_arguments` - Arguments after the program name
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(Vec<String>, GlobalCommandLineOptions)>` - The other arguments and the global options
+///
+/// # Errors
+/// * `CommandLineUsageError` - If `--data-dir` has no value or is given twice
+fn split_global_command_line_options(
+    command_line_arguments: &[String],
+) -> RowsAndColumnsResult<(Vec<String>, GlobalCommandLineOptions)> {
+    let mut remaining_arguments = Vec::with_capacity(command_line_arguments.len());
+    let mut global_options = GlobalCommandLineOptions::default();
+    let mut argument_iterator = command_line_arguments.iter();
+
+    while let Some(argument) = argument_iterator.next() {
+        if argument == "--" {
+            remaining_arguments.push(argument.clone());
+            remaining_arguments.extend(argument_iterator.by_ref().cloned());
+            break;
+        }
+
+        let data_directory_value = if argument == DATA_DIRECTORY_OPTION {
+            match argument_iterator.next() {
+                Some(option_value) if option_value != "--" => Some(option_value.clone()),
+                _ => {
+                    return Err(create_command_line_usage_error(
+                        &format!("{} needs a directory", DATA_DIRECTORY_OPTION),
+                        None
+                    ));
+                }
+            }
+        } else {
+            argument
+                .strip_prefix(DATA_DIRECTORY_OPTION)
+                .and_then(|option_remainder| option_remainder.strip_prefix('='))
+                .map(str::to_string)
+        };
+
+        if let Some(data_directory_value) = data_directory_value {
+            if data_directory_value.is_empty() {
+                return Err(create_command_line_usage_error(
+                    &format!("{} needs a directory", DATA_DIRECTORY_OPTION),
+                    None
+                ));
+            }
+            if global_options.data_directory_argument.is_some() {
+                return Err(create_command_line_usage_error(
+                    &format!("{} was given more than once", DATA_DIRECTORY_OPTION),
+                    None
+                ));
+            }
+            global_options.data_directory_argument = Some(data_directory_value);
+        } else if argument == HEADLESS_SWITCH {
+            global_options.headless_mode = true;
+        } else {
+            remaining_arguments.push(argument.clone());
+        }
+    }
+
+    Ok((remaining_arguments, global_options))
+}
+
+/// Parses the command line (without the program name)
+///
+/// # Arguments
+/// * `command_line_arguments` - Arguments after the program name
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ParsedCommandLine>` - What to do
+///
+/// # Errors
+/// * `CommandLineUsageError` - Unknown subcommands or flags, missing or
+///   invalid flag values, repeated flags or extra arguments
+pub fn parse_command_line_arguments(command_line_arguments: &[String]) -> RowsAndColumnsResult<ParsedCommandLine> {
+    // Step 0: Global options may appear anywhere before `--`; parse the rest without them
+    let (remaining_arguments, global_options) = split_global_command_line_options(command_line_arguments)?;
+    if global_options != GlobalCommandLineOptions::default() {
+        return match parse_command_line_arguments(&remaining_arguments)? {
+            ParsedCommandLine::RunSubcommand(mut parsed_arguments) => {
+                parsed_arguments.headless_mode = global_options.headless_mode;
+                parsed_arguments.data_directory_argument = global_options.data_directory_argument;
+                Ok(ParsedCommandLine::RunSubcommand(parsed_arguments))
+            }
+            ParsedCommandLine::InteractiveFileSelection { .. } if global_options.headless_mode => {
+                Err(create_command_line_usage_error(
+                    "--headless needs a subcommand and a CSV file, since nobody can answer prompts",
+                    None
+                ))
+            }
+            ParsedCommandLine::InteractiveFileSelection { .. } => Ok(ParsedCommandLine::InteractiveFileSelection {
+                data_directory_argument: global_options.data_directory_argument,
+            }),
+            other_command_line => Ok(other_command_line),
+        };
+    }
+
+    // Step 1: Global forms that do not name a subcommand
+    let Some(first_argument) = command_line_arguments.first() else {
+        return Ok(ParsedCommandLine::InteractiveFileSelection { data_directory_argument: None });
+    };
+    match first_argument.as_str() {
+        "--help" | "-h" | "help" => {
+            return match command_line_arguments.get(1) {
+                None => Ok(ParsedCommandLine::ShowGeneralHelp),
+                Some(help_topic) => CommandLineSubcommand::from_command_name(help_topic)
+                    .map(ParsedCommandLine::ShowSubcommandHelp)
+                    .ok_or_else(|| unknown_subcommand_error(help_topic)),
+            };
+        }
+        "--version" | "-V" => return Ok(ParsedCommandLine::ShowVersion),
+        _ => {}
+    }
+
+    // Step 2: Older forms: `--json <csv_file>` and a bare `<csv_file>`; a
+    // `--json` after a subcommand or after `--` is left to the flag parser
+    let names_subcommand = CommandLineSubcommand::from_command_name(first_argument).is_some();
+    let has_older_json_switch = command_line_arguments
+        .iter()
+        .take_while(|argument| argument.as_str() != "--")
+        .any(|argument| argument == "--json");
+    if !names_subcommand && has_older_json_switch {
+        let mut rewritten_arguments = vec!["describe".to_string(), "--format".to_string(), "json".to_string()];
+        rewritten_arguments.extend(command_line_arguments.iter().filter(|argument| argument.as_str() != "--json").cloned());
+        return parse_command_line_arguments(&rewritten_arguments);
+    }
+
+    let Some(subcommand) = CommandLineSubcommand::from_command_name(first_argument) else {
+        if first_argument.starts_with('-') {
+            return Err(create_command_line_usage_error(
+                &format!("unknown option '{}'; options go after a subcommand", first_argument),
+                None
+            ));
+        }
+        if looks_like_file_path(first_argument) {
+            let mut rewritten_arguments = vec!["analyze".to_string()];
+            rewritten_arguments.extend(command_line_arguments.iter().cloned());
+            return parse_command_line_arguments(&rewritten_arguments);
+        }
+        return Err(unknown_subcommand_error(first_argument));
+    };
+
+    // Step 3: Flags and the positional argument of the subcommand
+    let subcommand_name = subcommand.command_name();
+    let specification = subcommand.specification();
+    let mut parsed_arguments = ParsedSubcommandArguments {
+        subcommand,
+        positional_argument: None,
+        flag_values: BTreeMap::new(),
+        enabled_switches: BTreeSet::new(),
+        headless_mode: false,
+        data_directory_argument: None,
+    };
+    let mut flag_parsing_finished = false;
+    let mut argument_iterator = command_line_arguments[1..].iter();
+
+    while let Some(argument) = argument_iterator.next() {
+        // Positional arguments: anything not starting with '-', a lone '-', or after '--'
+        if flag_parsing_finished || !argument.starts_with('-') || argument == "-" {
+            if let Some(existing_argument) = &parsed_arguments.positional_argument {
+                return Err(create_command_line_usage_error(
+                    &match specification.positional_placeholder {
+                        Some(placeholder) => format!(
+                            "unexpected extra argument '{}' ({} is already '{}')",
+                            argument,
+                            placeholder,
+                            existing_argument
+                        ),
+                        None => format!("unexpected argument '{}'", argument),
+                    },
+                    Some(subcommand_name)
+                ));
+            }
+            if specification.positional_placeholder.is_none() {
+                return Err(create_command_line_usage_error(
+                    &format!("{} takes no arguments, but '{}' was given", subcommand_name, argument),
+                    Some(subcommand_name)
+                ));
+            }
+            parsed_arguments.positional_argument = Some(argument.clone());
+            continue;
+        }
+        if argument == "--" {
+            flag_parsing_finished = true;
+            continue;
+        }
+        if argument == "--help" || argument == "-h" {
+            return Ok(ParsedCommandLine::ShowSubcommandHelp(subcommand));
+        }
+
+        // Flags: --name, --name=value or -x
+        let (flag_specification, inline_value) = match argument.strip_prefix("--") {
+            Some(long_flag_text) => {
+                let (long_flag_name, inline_value) = match long_flag_text.split_once('=') {
+                    Some((long_flag_name, inline_value)) => (long_flag_name, Some(inline_value.to_string())),
+                    None => (long_flag_text, None),
+                };
+                let flag_specification = specification.accepted_flags
+                    .iter()
+                    .find(|flag_specification| flag_specification.long_flag_name == long_flag_name)
+                    .ok_or_else(|| unknown_flag_error(subcommand, &format!("--{}", long_flag_name)))?;
+                (flag_specification, inline_value)
+            }
+            None => {
+                let short_flag_letters: Vec<char> = argument.chars().skip(1).collect();
+                let flag_specification = match short_flag_letters.as_slice() {
+                    [short_flag_letter] => specification.accepted_flags
+                        .iter()
+                        .find(|flag_specification| flag_specification.short_flag_letter == Some(*short_flag_letter)),
+                    _ => None,
+                }
+                .ok_or_else(|| unknown_flag_error(subcommand, argument))?;
+                (flag_specification, None)
+            }
+        };
+        let long_flag_name = flag_specification.long_flag_name;
+
+        let Some(value_placeholder) = flag_specification.value_placeholder else {
+            if inline_value.is_some() {
+                return Err(create_command_line_usage_error(
+                    &format!("--{} is a switch and takes no value", long_flag_name),
+                    Some(subcommand_name)
+                ));
+            }
+            parsed_arguments.enabled_switches.insert(long_flag_name);
+            continue;
+        };
+
+        let flag_value = match inline_value {
+            Some(inline_value) => inline_value,
+            None => argument_iterator.next().cloned().ok_or_else(|| {
+                create_command_line_usage_error(
+                    &format!("--{} needs a value: --{} {}", long_flag_name, long_flag_name, value_placeholder),
+                    Some(subcommand_name)
+                )
+            })?,
+        };
+        if !flag_specification.allowed_values.is_empty() && !flag_specification.allowed_values.contains(&flag_value.as_str()) {
+            let suggestion = suggest_closest_name(&flag_value, flag_specification.allowed_values.iter().copied())
+                .map(|closest_value| format!(" (did you mean '{}'?)", closest_value))
+                .unwrap_or_default();
+            return Err(create_command_line_usage_error(
+                &format!(
+                    "--{} must be one of {}, not '{}'{}",
+                    long_flag_name,
+                    flag_specification.allowed_values.join(", "),
+                    flag_value,
+                    suggestion
+                ),
+                Some(subcommand_name)
+            ));
+        }
+        if parsed_arguments.flag_values.insert(long_flag_name, flag_value).is_some() {
+            return Err(create_command_line_usage_error(
+                &format!("--{} was given more than once", long_flag_name),
+                Some(subcommand_name)
+            ));
+        }
+    }
+
+    Ok(ParsedCommandLine::RunSubcommand(parsed_arguments))
+}
+
+/// Resolves a `--columns` list of names or 1-based numbers to column positions
+///
+/// # Arguments
+/// * `column_list_text` - e.g. `age,breed`, `2, 4` or a mix
+/// * `column_names` - The file's column names in order
+/// * `subcommand` - The subcommand, for the help hint in errors
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<usize>>` - 0-based positions in the order given (no duplicates)
+///
+/// # Errors
+/// * `CommandLineUsageError` - Unknown names (with a suggestion), numbers out of range or an empty list
+pub fn resolve_column_selection(
+    column_list_text: &str,
+    column_names: &[String],
+    subcommand: CommandLineSubcommand,
+) -> RowsAndColumnsResult<Vec<usize>> {
+    let subcommand_name = subcommand.command_name();
+    let mut selected_positions = Vec::new();
+
+    for column_entry in column_list_text.split(',').map(str::trim).filter(|column_entry| !column_entry.is_empty()) {
+        let column_position = match column_names.iter().position(|column_name| column_name == column_entry) {
+            Some(column_position) => column_position,
+            None => match column_entry.parse::<usize>() {
+                Ok(column_number) if (1..=column_names.len()).contains(&column_number) => column_number - 1,
+                Ok(column_number) => {
+                    return Err(create_command_line_usage_error(
+                        &format!("column {} is out of range; the file has {} columns", column_number, column_names.len()),
+                        Some(subcommand_name)
+                    ));
+                }
+                Err(_) => {
+                    let suggestion = suggest_closest_name(column_entry, column_names.iter().map(String::as_str))
+                        .map(|closest_name| format!("did you mean '{}'?", closest_name))
+                        .unwrap_or_else(|| format!("columns are: {}", column_names.join(", ")));
+                    return Err(create_command_line_usage_error(
+                        &format!("unknown column '{}'; {}", column_entry, suggestion),
+                        Some(subcommand_name)
+                    ));
+                }
+            },
+        };
+        if !selected_positions.contains(&column_position) {
+            selected_positions.push(column_position);
+        }
+    }
+
+    if selected_positions.is_empty() {
+        return Err(create_command_line_usage_error("--columns needs at least one column", Some(subcommand_name)));
+    }
+    Ok(selected_positions)
+}
+
+/// Builds the general help text
+///
+/// # Returns
+/// * `String` - Usage, subcommands with summaries, and examples
+pub fn format_general_help_text() -> String {
+    let mut help_text = String::new();
+    help_text.push_str("USAGE:\n");
+    help_text.push_str(&format!("  {} <subcommand> [arguments] [options]\n", PROGRAM_NAME));
+    help_text.push_str(&format!("  {} <csv_file>          Same as: analyze <csv_file>\n", PROGRAM_NAME));
+    help_text.push_str(&format!("  {}                     Browse for a CSV file, preview it, then analyze\n", PROGRAM_NAME));
+    help_text.push('\n');
+    help_text.push_str("  A <csv_file> of '-' reads standard input; named pipes and .csv.gz/.tsv.gz files\n");
+    help_text.push_str("  (gzip, also when piped) work too. describe reads these in one pass; other\n");
+    help_text.push_str("  subcommands copy the CSV text to rows_columns_data/temporary_work first.\n");
+    help_text.push('\n');
+
+    help_text.push_str("SUBCOMMANDS:\n");
+    for subcommand in CommandLineSubcommand::ALL_SUBCOMMANDS {
+        help_text.push_str(&format!("  {:<10} {}\n", subcommand.command_name(), subcommand.specification().one_line_summary));
+    }
+    help_text.push('\n');
+
+    help_text.push_str("OPTIONS:\n");
+    help_text.push_str("  -h, --help       Show help (after a subcommand: help for that subcommand)\n");
+    help_text.push_str("  -V, --version    Show the version\n");
+    help_text.push_str("      --headless   No banner, prompts, menu or emoji (for cron jobs and CI)\n");
+    help_text.push_str("      --data-dir <directory>\n");
+    help_text.push_str("                   Keep imports, cache and work files in this directory\n");
+    help_text.push('\n');
+
+    help_text.push_str("DATA DIRECTORY:\n");
+    help_text.push_str("  Imported datasets, the analysis cache and work files are kept in the first of:\n");
+    help_text.push_str("    1. --data-dir <directory>\n");
+    help_text.push_str("    2. $ROWS_AND_COLUMNS_DATA_DIR\n");
+    help_text.push_str("    3. $XDG_DATA_HOME/rows_columns_data (default ~/.local/share/rows_columns_data)\n");
+    help_text.push_str("    4. rows_columns_data next to the executable\n");
+    help_text.push('\n');
+
+    help_text.push_str("CONFIGURATION:\n");
+    help_text.push_str("  Defaults are read from rows_and_columns.toml next to the executable, or from\n");
+    help_text.push_str("  the file named by ROWS_AND_COLUMNS_CONFIG. Tables and keys:\n");
+    help_text.push_str("    [analysis]     sample_rows_for_type_detection (10), type_detection_threshold_percent (70)\n");
+    help_text.push_str("    [display]      top_value_count (5), chart_glyphs (\"unicode\"|\"ascii\"),\n");
+    help_text.push_str("                   color (\"auto\"|\"always\"|\"never\"; auto honors NO_COLOR)\n");
+    help_text.push_str("    [directories]  root, csv_imports, analysis_cache, temporary_work\n");
+    help_text.push('\n');
+
+    help_text.push_str("EXIT CODES:\n");
+    for (exit_code, exit_code_description) in EXIT_CODE_DESCRIPTIONS {
+        help_text.push_str(&format!("  {:>2}  {}\n", exit_code, exit_code_description));
+    }
+    help_text.push('\n');
+
+    help_text.push_str("EXAMPLES:\n");
+    for subcommand in [CommandLineSubcommand::Analyze, CommandLineSubcommand::Describe, CommandLineSubcommand::Query] {
+        if let Some(first_example) = subcommand.specification().usage_examples.first() {
+            help_text.push_str(&format!("  {} {}\n", PROGRAM_NAME, first_example));
+        }
+    }
+    help_text.push('\n');
+    help_text.push_str(&format!("Run '{} help <subcommand>' for the options of one subcommand.\n", PROGRAM_NAME));
+    help_text
+}
+
+/// Builds the help text of one subcommand
+///
+/// # Arguments
+/// * `subcommand` - The subcommand to describe
+///
+/// # Returns
+/// * `String` - Usage line, description, options and examples
+pub fn format_subcommand_help_text(subcommand: CommandLineSubcommand) -> String {
+    let specification = subcommand.specification();
+    let mut help_text = String::new();
+
+    help_text.push_str("USAGE:\n");
+    help_text.push_str(&format!(
+        "  {} {}{} [options]\n\n",
+        PROGRAM_NAME,
+        subcommand.command_name(),
+        specification.positional_placeholder.map(|placeholder| format!(" {}", placeholder)).unwrap_or_default()
+    ));
+    help_text.push_str(&format!("{}\n\n", specification.one_line_summary));
+    help_text.push_str(&format!("{}\n\n", specification.detailed_description));
+
+    help_text.push_str("OPTIONS:\n");
+    let option_labels: Vec<String> = specification.accepted_flags
+        .iter()
+        .map(|flag_specification| {
+            let short_form = flag_specification.short_flag_letter
+                .map(|short_flag_letter| format!("-{}, ", short_flag_letter))
+                .unwrap_or_else(|| "    ".to_string());
+            let value_form = flag_specification.value_placeholder
+                .map(|value_placeholder| format!(" {}", value_placeholder))
+                .unwrap_or_default();
+            format!("{}--{}{}", short_form, flag_specification.long_flag_name, value_form)
+        })
+        .chain(["    --headless".to_string(), "    --data-dir <directory>".to_string(), "-h, --help".to_string()])
+        .collect();
+    let label_width = option_labels.iter().map(|option_label| option_label.chars().count()).max().unwrap_or(0);
+    let option_descriptions = specification.accepted_flags
+        .iter()
+        .map(|flag_specification| flag_specification.flag_description)
+        .chain([
+            "No banner, prompts, menu or emoji (for cron jobs and CI)",
+            "Keep imports, cache and work files in this directory",
+            "Show this help",
+        ]);
+    for (option_label, option_description) in option_labels.iter().zip(option_descriptions) {
+        help_text.push_str(&format!("  {:<width$}  {}\n", option_label, option_description, width = label_width));
+    }
+    help_text.push('\n');
+
+    help_text.push_str("EXAMPLES:\n");
+    for usage_example in specification.usage_examples {
+        help_text.push_str(&format!("  {} {}\n", PROGRAM_NAME, usage_example));
+    }
+    help_text
+}
+
+/// Whether an unknown first argument is meant as a file rather than a subcommand
+fn looks_like_file_path(argument_text: &str) -> bool {
+    argument_text.contains(['.', '/', '\\']) || std::path::Path::new(argument_text).exists()
+}
+
+/// Builds the error for an unknown subcommand, with a suggestion
+fn unknown_subcommand_error(typed_name: &str) -> super::error_types_module::RowsAndColumnsError {
+    let suggestion = suggest_closest_name(
+        typed_name,
+        CommandLineSubcommand::ALL_SUBCOMMANDS.iter().map(|subcommand| subcommand.command_name())
+    )
+    .map(|closest_name| format!("did you mean '{}'?", closest_name))
+    .unwrap_or_else(|| {
+        format!(
+            "subcommands are: {}",
+            CommandLineSubcommand::ALL_SUBCOMMANDS
+                .iter()
+                .map(|subcommand| subcommand.command_name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    });
+    create_command_line_usage_error(
+        &format!("unknown subcommand '{}'; {}", typed_name, suggestion),
+        None
+    )
+}
+
+/// Builds the error for a flag the subcommand does not accept, with a suggestion
+fn unknown_flag_error(subcommand: CommandLineSubcommand, typed_flag: &str) -> super::error_types_module::RowsAndColumnsError {
+    let accepted_flag_names: Vec<String> = subcommand.specification().accepted_flags
+        .iter()
+        .map(|flag_specification| format!("--{}", flag_specification.long_flag_name))
+        .collect();
+    let suggestion = suggest_closest_name(typed_flag, accepted_flag_names.iter().map(String::as_str))
+        .map(|closest_flag| format!("; did you mean '{}'?", closest_flag))
+        .unwrap_or_default();
+    create_command_line_usage_error(
+        &format!("{} does not accept '{}'{}", subcommand.command_name(), typed_flag, suggestion),
+        Some(subcommand.command_name())
+    )
+}
+
+/// Returns the candidate closest to a typo, if it is close enough
+///
+/// # Arguments
+/// * `typed_text` - What was typed
+/// * `candidate_names` - The valid names
+///
+/// # Returns
+/// * `Option<&str>` - The closest name within `MAXIMUM_SUGGESTION_EDIT_DISTANCE` edits
+fn suggest_closest_name<'a>(typed_text: &str, candidate_names: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let typed_lowercase = typed_text.to_lowercase();
+    candidate_names
+        .map(|candidate_name| (calculate_edit_distance(&typed_lowercase, &candidate_name.to_lowercase()), candidate_name))
+        .filter(|(edit_distance, _)| *edit_distance <= MAXIMUM_SUGGESTION_EDIT_DISTANCE)
+        .min_by_key(|(edit_distance, _)| *edit_distance)
+        .map(|(_, candidate_name)| candidate_name)
+}
+
+/// Levenshtein distance between two strings (by character)
+fn calculate_edit_distance(first_text: &str, second_text: &str) -> usize {
+    let second_characters: Vec<char> = second_text.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=second_characters.len()).collect();
+
+    for (first_position, first_character) in first_text.chars().enumerate() {
+        let mut current_row = vec![first_position + 1; second_characters.len() + 1];
+        for (second_position, second_character) in second_characters.iter().enumerate() {
+            let substitution_cost = usize::from(first_character != *second_character);
+            current_row[second_position + 1] = (previous_row[second_position] + substitution_cost)
+                .min(previous_row[second_position + 1] + 1)
+                .min(current_row[second_position] + 1);
+        }
+        previous_row = current_row;
+    }
+
+    previous_row[second_characters.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::error_types_module::RowsAndColumnsError;
+
+    /// Converts string literals into owned arguments
+    fn create_arguments(argument_texts: &[&str]) -> Vec<String> {
+        argument_texts.iter().map(|argument_text| argument_text.to_string()).collect()
+    }
+
+    /// Extracts the description of a usage error
+    fn expect_usage_error(argument_texts: &[&str]) -> String {
+        match parse_command_line_arguments(&create_arguments(argument_texts)) {
+            Err(RowsAndColumnsError::CommandLineUsageError { usage_error_description, .. }) => usage_error_description,
+            other_result => panic!("Expected a usage error for {:?}, got {:?}", argument_texts, other_result),
+        }
+    }
+
+    /// Test flags in all their spellings, switches, help and the older forms
+    #[test]
+    fn test_parse_subcommands_and_flags() {
+        let parsed_command_line = parse_command_line_arguments(&create_arguments(&[
+            "query", "cats.csv", "--where", "age > 2", "-c", "name,age", "--format=json", "--limit", "5",
+        ])).expect("should parse");
+        let ParsedCommandLine::RunSubcommand(parsed_arguments) = parsed_command_line else {
+            panic!("Expected a subcommand");
+        };
+        assert_eq!(parsed_arguments.subcommand, CommandLineSubcommand::Query);
+        assert_eq!(parsed_arguments.positional_argument.as_deref(), Some("cats.csv"));
+        assert_eq!(parsed_arguments.flag_value("where"), Some("age > 2"));
+        assert_eq!(parsed_arguments.flag_value("columns"), Some("name,age"));
+        assert_eq!(parsed_arguments.flag_value("format"), Some("json"));
+        assert_eq!(parsed_arguments.flag_value("limit"), Some("5"));
+        assert_eq!(parsed_arguments.flag_value("output"), None);
+
+        let ParsedCommandLine::RunSubcommand(analyze_arguments) = parse_command_line_arguments(&create_arguments(&[
+            "analyze", "--no-interactive", "--", "-odd-name.csv",
+        ])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert!(analyze_arguments.has_switch("no-interactive"));
+        assert_eq!(analyze_arguments.positional_argument.as_deref(), Some("-odd-name.csv"));
+
+        let ParsedCommandLine::RunSubcommand(batch_arguments) = parse_command_line_arguments(&create_arguments(&[
+            "batch", "incoming", "-r", "--report=html",
+        ])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert_eq!(batch_arguments.subcommand, CommandLineSubcommand::Batch);
+        assert!(batch_arguments.has_switch("recursive"));
+        assert_eq!(batch_arguments.flag_value("report"), Some("html"));
+
+        assert_eq!(
+            parse_command_line_arguments(&[]).expect("should parse"),
+            ParsedCommandLine::InteractiveFileSelection { data_directory_argument: None }
+        );
+        assert_eq!(
+            parse_command_line_arguments(&create_arguments(&["plot", "--help"])).expect("should parse"),
+            ParsedCommandLine::ShowSubcommandHelp(CommandLineSubcommand::Plot)
+        );
+        assert_eq!(
+            parse_command_line_arguments(&create_arguments(&["help", "validate"])).expect("should parse"),
+            ParsedCommandLine::ShowSubcommandHelp(CommandLineSubcommand::Validate)
+        );
+
+        // Older invocations are rewritten to subcommands
+        let ParsedCommandLine::RunSubcommand(bare_path_arguments) =
+            parse_command_line_arguments(&create_arguments(&["data/cats.csv"])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert_eq!(bare_path_arguments.subcommand, CommandLineSubcommand::Analyze);
+        assert!(!bare_path_arguments.headless_mode);
+
+        // --headless is global: before or after the subcommand, but not after `--`
+        let ParsedCommandLine::RunSubcommand(headless_arguments) = parse_command_line_arguments(&create_arguments(&[
+            "--headless", "validate", "cats.csv",
+        ])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert!(headless_arguments.headless_mode);
+        assert_eq!(headless_arguments.subcommand, CommandLineSubcommand::Validate);
+        let ParsedCommandLine::RunSubcommand(late_headless_arguments) = parse_command_line_arguments(&create_arguments(&[
+            "data/cats.csv", "--headless",
+        ])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert!(late_headless_arguments.headless_mode);
+        assert_eq!(late_headless_arguments.positional_argument.as_deref(), Some("data/cats.csv"));
+
+        // --data-dir is global too, in both spellings, and also without a subcommand
+        let ParsedCommandLine::RunSubcommand(data_directory_arguments) = parse_command_line_arguments(&create_arguments(&[
+            "datasets", "--data-dir", "/srv/rows", "--headless",
+        ])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert_eq!(data_directory_arguments.data_directory_argument.as_deref(), Some("/srv/rows"));
+        assert!(data_directory_arguments.headless_mode);
+        assert_eq!(
+            parse_command_line_arguments(&create_arguments(&["--data-dir=my_data"])).expect("should parse"),
+            ParsedCommandLine::InteractiveFileSelection { data_directory_argument: Some("my_data".to_string()) }
+        );
+        assert!(parse_command_line_arguments(&create_arguments(&["datasets", "--data-dir"])).is_err());
+        assert!(parse_command_line_arguments(&create_arguments(&["--data-dir=a", "datasets", "--data-dir=b"])).is_err());
+        let ParsedCommandLine::RunSubcommand(json_arguments) =
+            parse_command_line_arguments(&create_arguments(&["--json", "cats.csv"])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        assert_eq!(json_arguments.subcommand, CommandLineSubcommand::Describe);
+        assert_eq!(json_arguments.flag_value("format"), Some("json"));
+        assert_eq!(json_arguments.positional_argument.as_deref(), Some("cats.csv"));
+    }
+
+    /// Test mistakes are reported with the problem and a suggestion
+    #[test]
+    fn test_usage_errors_explain_the_problem() {
+        assert!(expect_usage_error(&["descibe", "cats.csv"]).contains("did you mean 'describe'?"));
+        assert!(expect_usage_error(&["describe", "cats.csv", "--colums", "age"]).contains("did you mean '--columns'?"));
+        assert!(expect_usage_error(&["describe", "cats.csv", "--format", "jsn"]).contains("did you mean 'json'?"));
+        assert!(expect_usage_error(&["describe", "cats.csv", "--format"]).contains("needs a value"));
+        assert!(expect_usage_error(&["describe", "cats.csv", "-f", "json", "--format", "text"]).contains("more than once"));
+        assert!(expect_usage_error(&["describe", "a.csv", "b.csv"]).contains("extra argument 'b.csv'"));
+        assert!(expect_usage_error(&["datasets", "cats.csv"]).contains("takes no arguments"));
+        assert!(expect_usage_error(&["batch", "incoming", "--report", "pdf"]).contains("pdf"));
+        assert!(expect_usage_error(&["analyze", "cats.csv", "--no-interactive=yes"]).contains("takes no value"));
+        assert!(expect_usage_error(&["--bogus"]).contains("unknown option"));
+        assert!(expect_usage_error(&["--headless"]).contains("needs a subcommand"));
+        assert!(expect_usage_error(&["describe", "--", "--headless", "a.csv"]).contains("extra argument"));
+        assert!(expect_usage_error(&["query", "cats.csv", "--json"]).contains("query does not accept '--json'"));
+
+        let ParsedCommandLine::RunSubcommand(parsed_arguments) =
+            parse_command_line_arguments(&create_arguments(&["export"])).expect("should parse") else {
+            panic!("Expected a subcommand");
+        };
+        match parsed_arguments.required_positional_argument() {
+            Err(missing_error @ RowsAndColumnsError::CommandLineUsageError { .. }) => {
+                let error_text = missing_error.to_string();
+                assert!(error_text.contains("export needs <csv_file>"));
+                assert!(error_text.contains("rows_and_columns export --help"));
+            }
+            other_result => panic!("Expected a usage error, got {:?}", other_result),
+        }
+    }
+
+    /// Test column lists and the help text generated from the specifications
+    #[test]
+    fn test_column_selection_and_help_text() {
+        let column_names: Vec<String> = ["id", "name", "age", "breed"].iter().map(|name| name.to_string()).collect();
+        assert_eq!(
+            resolve_column_selection("age, 2,breed,age", &column_names, CommandLineSubcommand::Describe).expect("should resolve"),
+            vec![2, 1, 3]
+        );
+        assert!(resolve_column_selection("5", &column_names, CommandLineSubcommand::Describe).is_err());
+        match resolve_column_selection("aeg", &column_names, CommandLineSubcommand::Describe) {
+            Err(RowsAndColumnsError::CommandLineUsageError { usage_error_description, .. }) => {
+                assert!(usage_error_description.contains("did you mean 'age'?"));
+            }
+            other_result => panic!("Expected a usage error, got {:?}", other_result),
+        }
+
+        let general_help_text = format_general_help_text();
+        for subcommand in CommandLineSubcommand::ALL_SUBCOMMANDS {
+            assert!(general_help_text.contains(subcommand.command_name()));
+            let subcommand_help_text = format_subcommand_help_text(subcommand);
+            assert!(subcommand_help_text.contains(&format!("rows_and_columns {}", subcommand.command_name())));
+            for flag_specification in subcommand.specification().accepted_flags {
+                assert!(subcommand_help_text.contains(&format!("--{}", flag_specification.long_flag_name)));
+            }
+        }
+    }
+
+    /// Test arguments that look like flags or subcommands where a value or a
+    /// file name is expected
+    #[test]
+    fn test_flag_like_values_and_file_names() {
+        let parse_subcommand_arguments = |argument_texts: &[&str]| match parse_command_line_arguments(&create_arguments(argument_texts)) {
+            Ok(ParsedCommandLine::RunSubcommand(parsed_arguments)) => parsed_arguments,
+            other_result => panic!("Expected a subcommand for {:?}, got {:?}", argument_texts, other_result),
+        };
+
+        // After `--` even `--json` and `--help` are file names
+        let json_named_file = parse_subcommand_arguments(&["describe", "--", "--json"]);
+        assert_eq!(json_named_file.flag_value("format"), None);
+        assert_eq!(json_named_file.positional_argument.as_deref(), Some("--json"));
+        assert_eq!(
+            parse_subcommand_arguments(&["analyze", "--", "--help"]).positional_argument.as_deref(),
+            Some("--help")
+        );
+
+        // The older form still works with the switch after the file
+        let late_json_switch = parse_subcommand_arguments(&["cats.csv", "--json"]);
+        assert_eq!(late_json_switch.subcommand, CommandLineSubcommand::Describe);
+        assert_eq!(late_json_switch.positional_argument.as_deref(), Some("cats.csv"));
+
+        // Values are taken as given, even when they start with '-' or contain '='
+        let flag_like_values = parse_subcommand_arguments(&["query", "-", "--where", "-age < -2", "--columns=a=b"]);
+        assert_eq!(flag_like_values.positional_argument.as_deref(), Some("-"));
+        assert_eq!(flag_like_values.flag_value("where"), Some("-age < -2"));
+        assert_eq!(flag_like_values.flag_value("columns"), Some("a=b"));
+
+        // Grouped short flags are not supported and say so instead of guessing
+        assert!(expect_usage_error(&["batch", "incoming", "-rr"]).contains("does not accept '-rr'"));
+        assert_eq!(calculate_edit_distance("", "json"), 4);
+        assert_eq!(suggest_closest_name("ÄGE", ["äge", "name"].into_iter()), Some("äge"));
+    }
+}
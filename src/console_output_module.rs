@@ -0,0 +1,387 @@
+// src/console_output_module.rs
+
+/// Console output control for rows_and_columns
+///
+/// Analysis functions report their progress ("🔍 Analyzing CSV file
+/// structure...") as they work. When the results are requested as JSON, stdout
+/// must carry nothing but the JSON document, so progress lines are suppressed
+/// and warnings are moved to stderr. In headless mode (`--headless`, for cron
+/// jobs and CI logs) emoji are removed from every line printed through here.
+/// Warnings are shown in yellow and the final error in red when the
+/// configured color mode allows it. Library calls (see `embedding_api_module`)
+/// run silently: they return their results instead of printing anything.
+///
+/// # Core Responsibilities
+/// - Hold the process-wide quiet and headless flags and the per-thread silent flag
+/// - Print progress lines only when not quiet
+/// - Print warnings to stdout normally and to stderr when quiet
+/// - Print result lines ("✅ Chart written to ...") without emoji when headless
+/// - Color warnings and errors per the `[display] color` setting
+/// - Write piped output through a locked stdout, ending quietly when the
+///   reader goes away (`describe x.csv --format json | head`)
+///
+/// # Design Philosophy
+/// - One flag set once at startup from the command line, read everywhere
+/// - Silence belongs to the calling thread and is restored by a drop guard, so
+///   concurrent or panicking library calls cannot mute or unmute anyone else
+/// - Interactive screens (menus, prompts, dashboards) keep using `println!`
+///   directly; only output that can appear in a piped run goes through here,
+///   so a subcommand piped into `head` never panics on a closed pipe
+use std::cell::Cell;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Import the configured color mode
+use super::application_configuration_module::{ConsoleColorMode, application_configuration};
+
+// Import error types for stdout write failures
+use super::error_types_module::{RowsAndColumnsResult, create_file_system_error};
+
+/// ANSI escape sequences for colored lines
+const ANSI_YELLOW_FOREGROUND: &str = "\x1b[33m";
+const ANSI_RED_FOREGROUND: &str = "\x1b[31m";
+const ANSI_RESET_ATTRIBUTES: &str = "\x1b[0m";
+
+/// Process-wide quiet flag (false until `set_quiet_console_output(true)`)
+static QUIET_CONSOLE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Process-wide headless flag (false until `set_headless_console_output(true)`)
+static HEADLESS_CONSOLE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Set once stdout's reader has gone away; later output is dropped
+static STANDARD_OUTPUT_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// The first stdout write failure other than a closed pipe, reported when the command finishes
+static STANDARD_OUTPUT_WRITE_FAILURE: Mutex<Option<io::Error>> = Mutex::new(None);
+
+thread_local! {
+    /// Per-thread silent flag (true only inside `run_with_silent_console_output`)
+    static SILENT_CONSOLE_OUTPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the thread's previous silent setting when dropped, including
+/// when the silenced closure panics
+struct SilentConsoleOutputGuard {
+    previously_silent: bool,
+}
+
+impl Drop for SilentConsoleOutputGuard {
+    fn drop(&mut self) {
+        SILENT_CONSOLE_OUTPUT.with(|silent_flag| silent_flag.set(self.previously_silent));
+    }
+}
+
+/// Turns quiet mode on or off for the rest of the process
+///
+/// # Arguments
+/// * `quiet_output` - True to suppress progress lines on stdout
+pub fn set_quiet_console_output(quiet_output: bool) {
+    QUIET_CONSOLE_OUTPUT.store(quiet_output, Ordering::Relaxed);
+}
+
+/// Returns whether quiet mode is on
+///
+/// # Returns
+/// * `bool` - True when stdout is reserved for machine-readable output
+pub fn is_quiet_console_output() -> bool {
+    QUIET_CONSOLE_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Turns headless mode on or off for the rest of the process
+///
+/// # Arguments
+/// * `headless_output` - True to remove emoji from progress, warning and result lines
+pub fn set_headless_console_output(headless_output: bool) {
+    HEADLESS_CONSOLE_OUTPUT.store(headless_output, Ordering::Relaxed);
+}
+
+/// Returns whether headless mode is on
+///
+/// # Returns
+/// * `bool` - True when nobody is at the terminal (no prompts, no emoji)
+pub fn is_headless_console_output() -> bool {
+    HEADLESS_CONSOLE_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Removes emoji and status symbols (and the spaces after them) from a line
+///
+/// Box drawing characters, arrows and shade glyphs are kept; they are part
+/// of tables and charts rather than decoration.
+///
+/// # Arguments
+/// * `console_line` - The line as written for an interactive terminal
+///
+/// # Returns
+/// * `String` - The line without emoji, e.g. "✅ Stored 5 rows" -> "Stored 5 rows"
+pub fn remove_emoji_from_console_line(console_line: &str) -> String {
+    let mut cleaned_line = String::with_capacity(console_line.len());
+    let mut skipping_spaces_after_emoji = false;
+
+    for line_character in console_line.chars() {
+        if is_emoji_character(line_character) {
+            skipping_spaces_after_emoji = true;
+            continue;
+        }
+        if skipping_spaces_after_emoji && line_character == ' ' {
+            continue;
+        }
+        skipping_spaces_after_emoji = false;
+        cleaned_line.push(line_character);
+    }
+
+    cleaned_line
+}
+
+/// Whether a character is an emoji, dingbat or emoji modifier
+fn is_emoji_character(line_character: char) -> bool {
+    matches!(
+        u32::from(line_character),
+        0x2600..=0x27BF        // Miscellaneous symbols and dingbats (✓ ✗ ✅ ❌ ⚠)
+            | 0x2B50..=0x2B55  // Stars and circles
+            | 0x1F000..=0x1FAFF // Emoji blocks (🔍 📊 💡 📂 ...)
+            | 0xFE0F           // Emoji presentation selector
+            | 0x200D           // Zero-width joiner
+    )
+}
+
+/// Prepares a line for the console: unchanged, or without emoji when headless
+fn format_console_line(console_line: &str) -> String {
+    if is_headless_console_output() {
+        remove_emoji_from_console_line(console_line)
+    } else {
+        console_line.to_string()
+    }
+}
+
+/// Runs a closure with progress, warning and result lines suppressed
+///
+/// Used by the library API so that embedding applications (FF, Lines) get
+/// data back instead of text on their terminal. Only the calling thread is
+/// silenced, and its previous setting is restored afterwards (even if the
+/// closure panics), so calls may nest and run on several threads at once.
+///
+/// # Arguments
+/// * `silent_operation` - The work to run without console output
+///
+/// # Returns
+/// * `T` - Whatever the closure returns
+pub fn run_with_silent_console_output<T>(silent_operation: impl FnOnce() -> T) -> T {
+    let _silent_output_guard = SilentConsoleOutputGuard {
+        previously_silent: SILENT_CONSOLE_OUTPUT.with(|silent_flag| silent_flag.replace(true)),
+    };
+    silent_operation()
+}
+
+/// Returns whether console output is suppressed entirely on this thread
+fn is_silent_console_output() -> bool {
+    SILENT_CONSOLE_OUTPUT.with(Cell::get)
+}
+
+/// Whether lines written to a stream should be colored
+///
+/// # Arguments
+/// * `stream_is_terminal` - Whether the target stream is an interactive terminal
+///
+/// # Returns
+/// * `bool` - True for `color = "always"`, or for `"auto"` on a terminal when
+///   not headless and `NO_COLOR` is not set
+fn should_color_console_stream(stream_is_terminal: bool) -> bool {
+    match application_configuration().console_color_mode {
+        ConsoleColorMode::Always => true,
+        ConsoleColorMode::Never => false,
+        ConsoleColorMode::Auto => {
+            stream_is_terminal && !is_headless_console_output() && env::var_os("NO_COLOR").is_none()
+        }
+    }
+}
+
+/// Wraps a line in an ANSI color when coloring is on
+///
+/// # Arguments
+/// * `console_line` - The already formatted line
+/// * `ansi_color_sequence` - Escape sequence that selects the color
+/// * `color_enabled` - Whether to color at all
+///
+/// # Returns
+/// * `String` - The colored or unchanged line
+fn colorize_console_line(console_line: &str, ansi_color_sequence: &str, color_enabled: bool) -> String {
+    if color_enabled {
+        format!("{}{}{}", ansi_color_sequence, console_line, ANSI_RESET_ATTRIBUTES)
+    } else {
+        console_line.to_string()
+    }
+}
+
+/// Writes text to stdout through a locked handle
+///
+/// A closed pipe (the reader of `| head` has exited) is a normal end of the
+/// output: nothing more is written and the command still succeeds. Any other
+/// failure also stops the output and is reported by `finish_standard_output`.
+///
+/// # Arguments
+/// * `output_text` - The text, including its line breaks
+fn write_to_standard_output(output_text: &str) {
+    if STANDARD_OUTPUT_CLOSED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut standard_output = io::stdout().lock();
+    if let Err(write_error) = standard_output.write_all(output_text.as_bytes()) {
+        STANDARD_OUTPUT_CLOSED.store(true, Ordering::Relaxed);
+        if write_error.kind() != io::ErrorKind::BrokenPipe
+            && let Ok(mut write_failure) = STANDARD_OUTPUT_WRITE_FAILURE.lock()
+        {
+            write_failure.get_or_insert(write_error);
+        }
+    }
+}
+
+/// Prints already formatted output text (ending in a line break) to stdout
+///
+/// # Arguments
+/// * `output_text` - The text
+pub fn print_output_text(output_text: &str) {
+    write_to_standard_output(output_text);
+}
+
+/// Prints a line of a command's output (a table row, a JSON document, ...) to stdout
+///
+/// # Arguments
+/// * `output_line` - The line, without its line break
+pub fn print_output_line(output_line: &str) {
+    write_to_standard_output(&format!("{}\n", output_line));
+}
+
+/// Returns whether stdout's reader has gone away or stdout failed
+///
+/// # Returns
+/// * `bool` - True when further output would be dropped, so long scans can stop early
+pub fn is_standard_output_closed() -> bool {
+    STANDARD_OUTPUT_CLOSED.load(Ordering::Relaxed)
+}
+
+/// Flushes stdout at the end of a command and reports a failed write
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success, also when the reader closed the pipe early
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If writing to stdout failed otherwise (e.g. a full disk)
+pub fn finish_standard_output() -> RowsAndColumnsResult<()> {
+    if !is_standard_output_closed()
+        && let Err(flush_error) = io::stdout().lock().flush()
+        && flush_error.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(create_file_system_error("Failed to write to standard output", flush_error));
+    }
+    let write_failure = STANDARD_OUTPUT_WRITE_FAILURE.lock().ok().and_then(|mut write_failure| write_failure.take());
+    match write_failure {
+        Some(write_error) => Err(create_file_system_error("Failed to write to standard output", write_error)),
+        None => Ok(()),
+    }
+}
+
+/// Prints a progress line to stdout unless quiet mode is on
+///
+/// # Arguments
+/// * `progress_line` - The line to print
+pub fn print_progress_line(progress_line: &str) {
+    if !is_quiet_console_output() && !is_silent_console_output() {
+        print_output_line(&format_console_line(progress_line));
+    }
+}
+
+/// Prints a result line to stdout (without emoji when headless)
+///
+/// Used for the outcome of a command ("✅ Report written to ..."), which is
+/// printed even in quiet mode.
+///
+/// # Arguments
+/// * `result_line` - The line to print
+pub fn print_result_line(result_line: &str) {
+    if is_silent_console_output() {
+        return;
+    }
+    print_output_line(&format_console_line(result_line));
+}
+
+/// Prints a warning line to stdout, or to stderr when quiet mode is on
+///
+/// # Arguments
+/// * `warning_line` - The line to print
+pub fn print_warning_line(warning_line: &str) {
+    if is_silent_console_output() {
+        return;
+    }
+    let formatted_warning_line = format_console_line(warning_line);
+    if is_quiet_console_output() {
+        let color_enabled = should_color_console_stream(std::io::stderr().is_terminal());
+        eprintln!("{}", colorize_console_line(&formatted_warning_line, ANSI_YELLOW_FOREGROUND, color_enabled));
+    } else {
+        let color_enabled = should_color_console_stream(std::io::stdout().is_terminal());
+        print_output_line(&colorize_console_line(&formatted_warning_line, ANSI_YELLOW_FOREGROUND, color_enabled));
+    }
+}
+
+/// Prints the error that ends the program to stderr (red when colored)
+///
+/// # Arguments
+/// * `error_line` - The line to print
+pub fn print_error_line(error_line: &str) {
+    let color_enabled = should_color_console_stream(std::io::stderr().is_terminal());
+    eprintln!("{}", colorize_console_line(&format_console_line(error_line), ANSI_RED_FOREGROUND, color_enabled));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test emoji and the spaces after them are removed, other symbols kept
+    #[test]
+    fn test_remove_emoji_from_console_line() {
+        assert_eq!(remove_emoji_from_console_line("✅ Stored 5 rows × 7 columns"), "Stored 5 rows × 7 columns");
+        assert_eq!(remove_emoji_from_console_line("  ✓ Column types analyzed"), "  Column types analyzed");
+        assert_eq!(remove_emoji_from_console_line("⚠️  Warning: odd extension"), "Warning: odd extension");
+        assert_eq!(remove_emoji_from_console_line("🔍 Analyzing CSV file structure..."), "Analyzing CSV file structure...");
+        assert_eq!(remove_emoji_from_console_line("id ↔ age ─── ░▒▓█"), "id ↔ age ─── ░▒▓█");
+        assert_eq!(remove_emoji_from_console_line("plain text"), "plain text");
+    }
+
+    /// Test lines are wrapped in color codes only when coloring is enabled
+    #[test]
+    fn test_colorize_console_line() {
+        assert_eq!(colorize_console_line("Warning", ANSI_YELLOW_FOREGROUND, false), "Warning");
+        assert_eq!(colorize_console_line("Warning", ANSI_YELLOW_FOREGROUND, true), "\x1b[33mWarning\x1b[0m");
+        assert_eq!(colorize_console_line("Failed", ANSI_RED_FOREGROUND, true), "\x1b[31mFailed\x1b[0m");
+    }
+
+    /// Silence is restored after a panic and never leaks into other threads
+    #[test]
+    fn test_silent_console_output_is_per_thread_and_panic_safe() {
+        let panic_result = std::panic::catch_unwind(|| {
+            run_with_silent_console_output(|| panic!("analysis failed"))
+        });
+        assert!(panic_result.is_err());
+        assert!(!is_silent_console_output());
+
+        let both_threads_inside = std::sync::Arc::new(std::sync::Barrier::new(2));
+        let silent_thread_barrier = std::sync::Arc::clone(&both_threads_inside);
+        let silent_thread = std::thread::spawn(move || {
+            run_with_silent_console_output(|| {
+                silent_thread_barrier.wait();
+                silent_thread_barrier.wait();
+                is_silent_console_output()
+            })
+        });
+
+        both_threads_inside.wait();
+        let other_thread_silent = is_silent_console_output();
+        both_threads_inside.wait();
+
+        assert!(silent_thread.join().expect("silent thread should finish"));
+        assert!(!other_thread_silent);
+        assert!(run_with_silent_console_output(|| run_with_silent_console_output(is_silent_console_output) && is_silent_console_output()));
+        assert!(!is_silent_console_output());
+    }
+}
@@ -0,0 +1,777 @@
+// src/correlation_analysis_module.rs
+
+/// Correlation analysis across continuous columns for rows_and_columns
+///
+/// `perform_enhanced_statistical_analysis` describes each column on its own;
+/// this module looks at pairs of continuous columns and measures how strongly
+/// they move together, using Pearson (linear) and Spearman (rank) correlation.
+///
+/// # Core Responsibilities
+/// - Compute pairwise-complete Pearson correlation in one streaming pass
+/// - Compute pairwise-complete Spearman correlation, ranking each pair's values
+///   within the rows that pair shares
+/// - Present both as numeric matrices, a shaded heatmap, and a list of the
+///   most strongly correlated column pairs
+///
+/// # Design Philosophy
+/// - Pairwise-complete: each pair uses every row where *both* values parse,
+///   so a missing value in one column does not discard the row for all pairs
+/// - No-load processing: Pearson uses running co-moments (Welford's update);
+///   Spearman holds only one column's values at a time while building its
+///   rank table, then maps values to ranks in a final streaming pass
+/// - Ties get their average rank. Column-wide ranks are reused for a pair only
+///   when neither column has a value on a row where the other is missing (then
+///   they equal the pair's own ranks); other pairs are re-ranked in a separate
+///   pass that holds just that pair's complete rows
+use std::cmp::Ordering;
+
+// Import CSV structures and the shared row streaming loop
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvFieldType,
+    CsvRowStreamControl,
+    EnhancedCsvColumnInformation,
+    stream_csv_data_rows,
+};
+
+// Import the heatmap chart definition and text renderer
+use super::chart_rendering_module::{
+    ChartDefinition,
+    HeatmapChartData,
+    render_chart_as_text_lines,
+};
+
+// Import the configured chart glyphs
+use super::application_configuration_module::application_configuration;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::RowsAndColumnsResult;
+
+// Import the pipe-safe stdout writer (the analysis output can be piped)
+use super::console_output_module::print_output_line;
+
+/// Number of pairs shown in the "top correlated pairs" list
+pub const DEFAULT_TOP_CORRELATED_PAIR_COUNT: usize = 10;
+
+/// Size of the heatmap printed with the analysis results
+const DISPLAY_HEATMAP_COLUMN_COUNT: usize = 72;
+const DISPLAY_HEATMAP_ROW_COUNT: usize = 24;
+
+/// Width of one cell in the printed numeric matrix
+const MATRIX_CELL_WIDTH: usize = 8;
+
+/// Which correlation coefficient a matrix holds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CorrelationMethod {
+    /// Pearson product-moment correlation (linear relationship)
+    Pearson,
+
+    /// Spearman rank correlation (monotonic relationship)
+    Spearman,
+}
+
+impl CorrelationMethod {
+    /// Returns the display name of the method
+    ///
+    /// # Returns
+    /// * `&str` - "Pearson" or "Spearman"
+    pub fn display_name(&self) -> &str {
+        match self {
+            CorrelationMethod::Pearson => "Pearson",
+            CorrelationMethod::Spearman => "Spearman",
+        }
+    }
+}
+
+/// A symmetric correlation matrix over the continuous columns
+#[derive(Debug, Clone)]
+pub struct CorrelationMatrix {
+    /// Which coefficient the matrix holds
+    pub correlation_method: CorrelationMethod,
+
+    /// Names of the columns, in matrix order
+    pub column_names: Vec<String>,
+
+    /// Coefficients by row then column; None when a pair has no variance or < 2 rows
+    pub coefficient_values: Vec<Vec<Option<f64>>>,
+
+    /// Number of rows where both values of the pair were present
+    pub pair_observation_counts: Vec<Vec<usize>>,
+}
+
+/// Both correlation matrices for a CSV file
+#[derive(Debug, Clone)]
+pub struct CorrelationAnalysisResults {
+    /// Pearson correlation matrix
+    pub pearson_matrix: CorrelationMatrix,
+
+    /// Spearman correlation matrix
+    pub spearman_matrix: CorrelationMatrix,
+}
+
+/// One entry of the "top correlated pairs" list
+#[derive(Debug, Clone)]
+pub struct CorrelatedColumnPair {
+    /// Name of the first column
+    pub first_column_name: String,
+
+    /// Name of the second column
+    pub second_column_name: String,
+
+    /// Pearson coefficient, if defined
+    pub pearson_coefficient: Option<f64>,
+
+    /// Spearman coefficient, if defined
+    pub spearman_coefficient: Option<f64>,
+
+    /// Rows where both values were present
+    pub observation_count: usize,
+}
+
+/// Running co-moment accumulator for one pair of columns (Welford's update)
+#[derive(Debug, Clone, Default)]
+struct PairwiseCorrelationAccumulator {
+    /// Number of rows where both values were present
+    observation_count: usize,
+
+    /// Running mean of the first column over those rows
+    running_mean_x: f64,
+
+    /// Running mean of the second column over those rows
+    running_mean_y: f64,
+
+    /// Sum of squared deviations of the first column
+    sum_squared_deviations_x: f64,
+
+    /// Sum of squared deviations of the second column
+    sum_squared_deviations_y: f64,
+
+    /// Sum of co-deviations (the co-moment)
+    sum_co_deviations: f64,
+}
+
+impl PairwiseCorrelationAccumulator {
+    /// Adds one complete (x, y) observation
+    fn add_observation(&mut self, x_value: f64, y_value: f64) {
+        self.observation_count += 1;
+        let observation_count = self.observation_count as f64;
+
+        let deviation_x = x_value - self.running_mean_x;
+        self.running_mean_x += deviation_x / observation_count;
+        let deviation_y = y_value - self.running_mean_y;
+        self.running_mean_y += deviation_y / observation_count;
+
+        self.sum_squared_deviations_x += deviation_x * (x_value - self.running_mean_x);
+        self.sum_squared_deviations_y += deviation_y * (y_value - self.running_mean_y);
+        self.sum_co_deviations += deviation_x * (y_value - self.running_mean_y);
+    }
+
+    /// Returns the correlation coefficient, or None if it is undefined
+    fn correlation_coefficient(&self) -> Option<f64> {
+        if self.observation_count < 2
+            || self.sum_squared_deviations_x <= 0.0
+            || self.sum_squared_deviations_y <= 0.0
+        {
+            return None;
+        }
+
+        let coefficient = self.sum_co_deviations
+            / (self.sum_squared_deviations_x * self.sum_squared_deviations_y).sqrt();
+        Some(coefficient.clamp(-1.0, 1.0))
+    }
+}
+
+/// Computes Pearson and Spearman correlation matrices for all continuous columns
+///
+/// Makes one streaming pass for Pearson, one pass per continuous column to build
+/// rank tables, one pass for Spearman, and one more pass for each pair whose
+/// columns are missing values on different rows.
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `enhanced_analysis_results` - Per-column results used to pick continuous columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CorrelationAnalysisResults>` - Both matrices or an error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read
+pub fn perform_correlation_analysis(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> RowsAndColumnsResult<CorrelationAnalysisResults> {
+    // Step 1: Select continuous columns
+    let continuous_columns: Vec<(usize, String)> = enhanced_analysis_results
+        .iter()
+        .filter(|enhanced_column_info| {
+            enhanced_column_info.field_type == CsvFieldType::Continuous
+                && enhanced_column_info.numerical_statistics.is_some()
+        })
+        .map(|enhanced_column_info| {
+            (enhanced_column_info.basic_info.column_index, enhanced_column_info.basic_info.column_name.clone())
+        })
+        .collect();
+    let continuous_column_indices: Vec<usize> = continuous_columns.iter().map(|(column_index, _)| *column_index).collect();
+    let column_names: Vec<String> = continuous_columns.into_iter().map(|(_, column_name)| column_name).collect();
+
+    // Step 2: Pearson in one streaming pass
+    let pearson_accumulators = accumulate_pairwise_correlations(
+        csv_analysis_results,
+        &continuous_column_indices,
+        |_, parsed_value| Some(parsed_value),
+    )?;
+
+    // Step 3: Rank tables, one column in memory at a time
+    let mut column_rank_tables = Vec::with_capacity(continuous_column_indices.len());
+    let mut ranked_value_counts = Vec::with_capacity(continuous_column_indices.len());
+    for &column_index in &continuous_column_indices {
+        let (column_rank_table, ranked_value_count) = build_column_rank_table(csv_analysis_results, column_index)?;
+        column_rank_tables.push(column_rank_table);
+        ranked_value_counts.push(ranked_value_count);
+    }
+
+    // Step 4: Spearman = Pearson over ranks, in one more streaming pass
+    let mut spearman_accumulators = accumulate_pairwise_correlations(
+        csv_analysis_results,
+        &continuous_column_indices,
+        |column_position, parsed_value| lookup_value_rank(&column_rank_tables[column_position], parsed_value),
+    )?;
+
+    // Step 5: Column-wide ranks are only right when the pair shares every ranked
+    // row; re-rank the other pairs within their complete rows
+    for first_position in 0..continuous_column_indices.len() {
+        for second_position in (first_position + 1)..continuous_column_indices.len() {
+            let complete_row_count = spearman_accumulators[first_position][second_position].observation_count;
+            if complete_row_count != ranked_value_counts[first_position] || complete_row_count != ranked_value_counts[second_position] {
+                spearman_accumulators[first_position][second_position] = accumulate_pairwise_complete_ranks(
+                    csv_analysis_results,
+                    (continuous_column_indices[first_position], continuous_column_indices[second_position]),
+                )?;
+            }
+        }
+    }
+
+    Ok(CorrelationAnalysisResults {
+        pearson_matrix: build_correlation_matrix(CorrelationMethod::Pearson, &column_names, &pearson_accumulators),
+        spearman_matrix: build_correlation_matrix(CorrelationMethod::Spearman, &column_names, &spearman_accumulators),
+    })
+}
+
+/// Streams the CSV once and feeds every complete pair into its accumulator
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `continuous_column_indices` - CSV column indices to correlate
+/// * `transform_value` - Maps (column position, parsed value) to the value to
+///   correlate (identity for Pearson, rank for Spearman)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<Vec<PairwiseCorrelationAccumulator>>>` - Accumulators
+///   where `[i][j]` (with `i < j`) holds pair (i, j)
+fn accumulate_pairwise_correlations<F>(
+    csv_analysis_results: &CsvAnalysisResults,
+    continuous_column_indices: &[usize],
+    transform_value: F,
+) -> RowsAndColumnsResult<Vec<Vec<PairwiseCorrelationAccumulator>>>
+where
+    F: Fn(usize, f64) -> Option<f64>,
+{
+    let column_count = continuous_column_indices.len();
+    let mut pair_accumulators = vec![vec![PairwiseCorrelationAccumulator::default(); column_count]; column_count];
+
+    if column_count < 2 {
+        return Ok(pair_accumulators);
+    }
+
+    let mut row_values: Vec<Option<f64>> = vec![None; column_count];
+
+    stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_, field_values| {
+            for (column_position, &column_index) in continuous_column_indices.iter().enumerate() {
+                row_values[column_position] = parse_correlation_value(field_values, column_index)
+                    .and_then(|parsed_value| transform_value(column_position, parsed_value));
+            }
+
+            for first_position in 0..column_count {
+                let Some(first_value) = row_values[first_position] else {
+                    continue;
+                };
+                for second_position in (first_position + 1)..column_count {
+                    if let Some(second_value) = row_values[second_position] {
+                        pair_accumulators[first_position][second_position].add_observation(first_value, second_value);
+                    }
+                }
+            }
+
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    Ok(pair_accumulators)
+}
+
+/// Parses a cell as a finite number, the same way for every correlation pass
+fn parse_correlation_value(field_values: &[String], column_index: usize) -> Option<f64> {
+    field_values
+        .get(column_index)
+        .and_then(|field_value| field_value.parse::<f64>().ok())
+        .filter(|parsed_value| parsed_value.is_finite())
+}
+
+/// Builds a rank table for one column: distinct values with their average rank
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `column_index` - CSV column index to rank
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(Vec<(f64, f64)>, usize)>` - (value, average 1-based rank)
+///   sorted by value, and the number of values ranked
+fn build_column_rank_table(
+    csv_analysis_results: &CsvAnalysisResults,
+    column_index: usize,
+) -> RowsAndColumnsResult<(Vec<(f64, f64)>, usize)> {
+    let mut column_values = Vec::new();
+
+    stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_, field_values| {
+            if let Some(parsed_value) = parse_correlation_value(field_values, column_index) {
+                column_values.push(parsed_value);
+            }
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    let ranked_value_count = column_values.len();
+    Ok((build_rank_table_from_values(column_values), ranked_value_count))
+}
+
+/// Computes one pair's Spearman accumulator with ranks taken over its complete rows
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `column_index_pair` - CSV column indices of the two columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PairwiseCorrelationAccumulator>` - Accumulator over the rank pairs
+fn accumulate_pairwise_complete_ranks(
+    csv_analysis_results: &CsvAnalysisResults,
+    column_index_pair: (usize, usize),
+) -> RowsAndColumnsResult<PairwiseCorrelationAccumulator> {
+    let (first_column_index, second_column_index) = column_index_pair;
+    let mut complete_value_pairs = Vec::new();
+
+    stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_, field_values| {
+            if let (Some(first_value), Some(second_value)) = (
+                parse_correlation_value(field_values, first_column_index),
+                parse_correlation_value(field_values, second_column_index),
+            ) {
+                complete_value_pairs.push((first_value, second_value));
+            }
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    Ok(accumulate_rank_correlation(&complete_value_pairs))
+}
+
+/// Ranks both sides of a set of complete pairs and correlates the ranks
+///
+/// # Arguments
+/// * `complete_value_pairs` - (first, second) values from rows where both are present
+///
+/// # Returns
+/// * `PairwiseCorrelationAccumulator` - Accumulator over the (rank, rank) pairs
+fn accumulate_rank_correlation(complete_value_pairs: &[(f64, f64)]) -> PairwiseCorrelationAccumulator {
+    let first_rank_table = build_rank_table_from_values(complete_value_pairs.iter().map(|value_pair| value_pair.0).collect());
+    let second_rank_table = build_rank_table_from_values(complete_value_pairs.iter().map(|value_pair| value_pair.1).collect());
+
+    let mut rank_accumulator = PairwiseCorrelationAccumulator::default();
+    for &(first_value, second_value) in complete_value_pairs {
+        if let (Some(first_rank), Some(second_rank)) = (
+            lookup_value_rank(&first_rank_table, first_value),
+            lookup_value_rank(&second_rank_table, second_value),
+        ) {
+            rank_accumulator.add_observation(first_rank, second_rank);
+        }
+    }
+    rank_accumulator
+}
+
+/// Sorts values and assigns average ranks to ties
+///
+/// # Arguments
+/// * `column_values` - The values to rank (consumed)
+///
+/// # Returns
+/// * `Vec<(f64, f64)>` - (distinct value, average 1-based rank), sorted by value
+fn build_rank_table_from_values(mut column_values: Vec<f64>) -> Vec<(f64, f64)> {
+    column_values.sort_by(|first_value, second_value| first_value.total_cmp(second_value));
+
+    let mut rank_table = Vec::new();
+    let mut run_start_position = 0;
+
+    while run_start_position < column_values.len() {
+        let run_value = column_values[run_start_position];
+        let mut run_end_position = run_start_position;
+        while run_end_position + 1 < column_values.len() && column_values[run_end_position + 1] == run_value {
+            run_end_position += 1;
+        }
+
+        // Ranks are 1-based; ties share the mean of their ranks
+        let average_rank = (run_start_position + run_end_position) as f64 / 2.0 + 1.0;
+        rank_table.push((run_value, average_rank));
+        run_start_position = run_end_position + 1;
+    }
+
+    rank_table
+}
+
+/// Looks up the rank of a value in a rank table
+///
+/// # Arguments
+/// * `rank_table` - Table from `build_rank_table_from_values`
+/// * `lookup_value` - Value to look up
+///
+/// # Returns
+/// * `Option<f64>` - The average rank, or None if the value is not in the table
+fn lookup_value_rank(rank_table: &[(f64, f64)], lookup_value: f64) -> Option<f64> {
+    rank_table
+        .binary_search_by(|(table_value, _)| table_value.partial_cmp(&lookup_value).unwrap_or(Ordering::Less))
+        .ok()
+        .map(|table_position| rank_table[table_position].1)
+}
+
+/// Turns upper-triangle accumulators into a full symmetric matrix
+///
+/// # Arguments
+/// * `correlation_method` - Which coefficient the accumulators represent
+/// * `column_names` - Names of the columns in matrix order
+/// * `pair_accumulators` - Accumulators with pair (i, j) at `[i][j]`, `i < j`
+///
+/// # Returns
+/// * `CorrelationMatrix` - The symmetric matrix (diagonal is 1.0)
+fn build_correlation_matrix(
+    correlation_method: CorrelationMethod,
+    column_names: &[String],
+    pair_accumulators: &[Vec<PairwiseCorrelationAccumulator>],
+) -> CorrelationMatrix {
+    let column_count = column_names.len();
+    let mut coefficient_values = vec![vec![None; column_count]; column_count];
+    let mut pair_observation_counts = vec![vec![0; column_count]; column_count];
+
+    for first_position in 0..column_count {
+        coefficient_values[first_position][first_position] = Some(1.0);
+        for second_position in (first_position + 1)..column_count {
+            let pair_accumulator = &pair_accumulators[first_position][second_position];
+            let coefficient = pair_accumulator.correlation_coefficient();
+
+            coefficient_values[first_position][second_position] = coefficient;
+            coefficient_values[second_position][first_position] = coefficient;
+            pair_observation_counts[first_position][second_position] = pair_accumulator.observation_count;
+            pair_observation_counts[second_position][first_position] = pair_accumulator.observation_count;
+        }
+    }
+
+    CorrelationMatrix {
+        correlation_method,
+        column_names: column_names.to_vec(),
+        coefficient_values,
+        pair_observation_counts,
+    }
+}
+
+/// Lists column pairs ordered by the strength of their Pearson correlation
+///
+/// Pairs whose Pearson coefficient is undefined are ordered by Spearman and
+/// placed after all pairs with a Pearson value.
+///
+/// # Arguments
+/// * `correlation_results` - Both correlation matrices
+/// * `maximum_pair_count` - Maximum number of pairs to return
+///
+/// # Returns
+/// * `Vec<CorrelatedColumnPair>` - Strongest pairs first
+pub fn list_top_correlated_pairs(
+    correlation_results: &CorrelationAnalysisResults,
+    maximum_pair_count: usize,
+) -> Vec<CorrelatedColumnPair> {
+    let pearson_matrix = &correlation_results.pearson_matrix;
+    let spearman_matrix = &correlation_results.spearman_matrix;
+    let column_count = pearson_matrix.column_names.len();
+    let mut correlated_pairs = Vec::new();
+
+    for first_position in 0..column_count {
+        for second_position in (first_position + 1)..column_count {
+            correlated_pairs.push(CorrelatedColumnPair {
+                first_column_name: pearson_matrix.column_names[first_position].clone(),
+                second_column_name: pearson_matrix.column_names[second_position].clone(),
+                pearson_coefficient: pearson_matrix.coefficient_values[first_position][second_position],
+                spearman_coefficient: spearman_matrix.coefficient_values[first_position][second_position],
+                observation_count: pearson_matrix.pair_observation_counts[first_position][second_position],
+            });
+        }
+    }
+
+    let sort_key = |correlated_pair: &CorrelatedColumnPair| -> (bool, f64) {
+        match (correlated_pair.pearson_coefficient, correlated_pair.spearman_coefficient) {
+            (Some(pearson_value), _) => (false, pearson_value.abs()),
+            (None, Some(spearman_value)) => (true, spearman_value.abs()),
+            (None, None) => (true, -1.0),
+        }
+    };
+
+    correlated_pairs.sort_by(|first_pair, second_pair| {
+        let (first_undefined, first_strength) = sort_key(first_pair);
+        let (second_undefined, second_strength) = sort_key(second_pair);
+        first_undefined
+            .cmp(&second_undefined)
+            .then(second_strength.total_cmp(&first_strength))
+    });
+
+    correlated_pairs.truncate(maximum_pair_count);
+    correlated_pairs
+}
+
+/// Describes the strength of a correlation coefficient in words
+///
+/// # Arguments
+/// * `coefficient_value` - Coefficient between -1 and 1
+///
+/// # Returns
+/// * `&'static str` - e.g. "strong positive"
+pub fn describe_correlation_strength(coefficient_value: f64) -> &'static str {
+    let coefficient_magnitude = coefficient_value.abs();
+    match (coefficient_magnitude, coefficient_value >= 0.0) {
+        (magnitude, _) if magnitude < 0.1 => "negligible",
+        (magnitude, true) if magnitude < 0.3 => "weak positive",
+        (magnitude, false) if magnitude < 0.3 => "weak negative",
+        (magnitude, true) if magnitude < 0.7 => "moderate positive",
+        (magnitude, false) if magnitude < 0.7 => "moderate negative",
+        (_, true) => "strong positive",
+        (_, false) => "strong negative",
+    }
+}
+
+/// Builds a heatmap chart definition from a correlation matrix
+///
+/// # Arguments
+/// * `correlation_matrix` - The matrix to draw
+///
+/// # Returns
+/// * `ChartDefinition` - A heatmap chart
+pub fn build_correlation_heatmap_chart(correlation_matrix: &CorrelationMatrix) -> ChartDefinition {
+    ChartDefinition::Heatmap(HeatmapChartData {
+        chart_title: format!("{} correlation heatmap", correlation_matrix.correlation_method.display_name()),
+        axis_labels: correlation_matrix.column_names.clone(),
+        cell_values: correlation_matrix.coefficient_values.clone(),
+    })
+}
+
+/// Formats a correlation matrix as aligned numeric text lines
+///
+/// # Arguments
+/// * `correlation_matrix` - The matrix to format
+///
+/// # Returns
+/// * `Vec<String>` - Header line followed by one line per column
+pub fn format_correlation_matrix_lines(correlation_matrix: &CorrelationMatrix) -> Vec<String> {
+    let label_width = correlation_matrix.column_names
+        .iter()
+        .map(|column_name| column_name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(16);
+
+    let mut matrix_lines = Vec::new();
+
+    let mut header_line = " ".repeat(label_width + 1);
+    for column_name in &correlation_matrix.column_names {
+        let clipped_name: String = column_name.chars().take(MATRIX_CELL_WIDTH - 1).collect();
+        header_line.push_str(&format!("{:>width$}", clipped_name, width = MATRIX_CELL_WIDTH));
+    }
+    matrix_lines.push(header_line);
+
+    for (row_position, column_name) in correlation_matrix.column_names.iter().enumerate() {
+        let clipped_name: String = column_name.chars().take(label_width).collect();
+        let mut row_line = format!("{:<width$} ", clipped_name, width = label_width);
+        for coefficient_value in &correlation_matrix.coefficient_values[row_position] {
+            let cell_text = match coefficient_value {
+                Some(coefficient) => format!("{:+.3}", coefficient),
+                None => "n/a".to_string(),
+            };
+            row_line.push_str(&format!("{:>width$}", cell_text, width = MATRIX_CELL_WIDTH));
+        }
+        matrix_lines.push(row_line);
+    }
+
+    matrix_lines
+}
+
+/// Displays both matrices, the Pearson heatmap, and the top correlated pairs
+///
+/// # Arguments
+/// * `correlation_results` - The results to display
+pub fn display_correlation_analysis_results(correlation_results: &CorrelationAnalysisResults) {
+    if correlation_results.pearson_matrix.column_names.len() < 2 {
+        return;
+    }
+
+    print_output_line("═══════════════════════════════════════════════════════════════");
+    print_output_line("  Correlation Analysis (continuous columns, pairwise-complete)");
+    print_output_line("═══════════════════════════════════════════════════════════════");
+    print_output_line("");
+
+    for correlation_matrix in [&correlation_results.pearson_matrix, &correlation_results.spearman_matrix] {
+        print_output_line(&format!("{} correlation:", correlation_matrix.correlation_method.display_name()));
+        for matrix_line in format_correlation_matrix_lines(correlation_matrix) {
+            print_output_line(&format!("  {}", matrix_line));
+        }
+        print_output_line("");
+    }
+
+    let heatmap_lines = render_chart_as_text_lines(
+        &build_correlation_heatmap_chart(&correlation_results.pearson_matrix),
+        DISPLAY_HEATMAP_COLUMN_COUNT,
+        DISPLAY_HEATMAP_ROW_COUNT,
+        application_configuration().chart_glyph_mode,
+    );
+    for heatmap_line in heatmap_lines {
+        print_output_line(&format!("  {}", heatmap_line));
+    }
+    print_output_line("");
+
+    print_output_line("Top correlated pairs:");
+    for (pair_position, correlated_pair) in list_top_correlated_pairs(correlation_results, DEFAULT_TOP_CORRELATED_PAIR_COUNT)
+        .iter()
+        .enumerate()
+    {
+        let format_coefficient = |coefficient_value: Option<f64>| {
+            coefficient_value.map(|coefficient| format!("{:+.3}", coefficient)).unwrap_or_else(|| "n/a".to_string())
+        };
+        print_output_line(&format!(
+            "  {:>2}. {} ↔ {}: pearson {}  spearman {}  (n={}, {})",
+            pair_position + 1,
+            correlated_pair.first_column_name,
+            correlated_pair.second_column_name,
+            format_coefficient(correlated_pair.pearson_coefficient),
+            format_coefficient(correlated_pair.spearman_coefficient),
+            correlated_pair.observation_count,
+            correlated_pair.pearson_coefficient
+                .map(describe_correlation_strength)
+                .unwrap_or("undefined")
+        ));
+    }
+    print_output_line("");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the streaming accumulator against known coefficients
+    #[test]
+    fn test_pairwise_accumulator_coefficients() {
+        let mut perfect_positive = PairwiseCorrelationAccumulator::default();
+        let mut perfect_negative = PairwiseCorrelationAccumulator::default();
+        let mut constant_column = PairwiseCorrelationAccumulator::default();
+
+        for value in 1..=10 {
+            let x_value = value as f64;
+            perfect_positive.add_observation(x_value, 3.0 * x_value + 1.0);
+            perfect_negative.add_observation(x_value, -0.5 * x_value);
+            constant_column.add_observation(x_value, 7.0);
+        }
+
+        assert!((perfect_positive.correlation_coefficient().expect("defined") - 1.0).abs() < 1e-12);
+        assert!((perfect_negative.correlation_coefficient().expect("defined") + 1.0).abs() < 1e-12);
+        assert_eq!(constant_column.correlation_coefficient(), None);
+    }
+
+    /// Test average ranks for ties
+    #[test]
+    fn test_rank_table_assigns_average_ranks_to_ties() {
+        let rank_table = build_rank_table_from_values(vec![10.0, 20.0, 10.0, 30.0, 10.0]);
+        assert_eq!(rank_table, vec![(10.0, 2.0), (20.0, 4.0), (30.0, 5.0)]);
+        assert_eq!(lookup_value_rank(&rank_table, 20.0), Some(4.0));
+        assert_eq!(lookup_value_rank(&rank_table, 25.0), None);
+    }
+
+    /// Test that top pairs are ordered by magnitude with undefined pairs last
+    #[test]
+    fn test_top_correlated_pairs_ordering() {
+        let column_names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let make_matrix = |correlation_method, ab: Option<f64>, ac: Option<f64>, bc: Option<f64>| CorrelationMatrix {
+            correlation_method,
+            column_names: column_names.clone(),
+            coefficient_values: vec![
+                vec![Some(1.0), ab, ac],
+                vec![ab, Some(1.0), bc],
+                vec![ac, bc, Some(1.0)],
+            ],
+            pair_observation_counts: vec![vec![5; 3]; 3],
+        };
+
+        let correlation_results = CorrelationAnalysisResults {
+            pearson_matrix: make_matrix(CorrelationMethod::Pearson, Some(0.2), None, Some(-0.9)),
+            spearman_matrix: make_matrix(CorrelationMethod::Spearman, Some(0.3), Some(0.5), Some(-0.8)),
+        };
+
+        let top_pairs = list_top_correlated_pairs(&correlation_results, 10);
+        let pair_names: Vec<(&str, &str)> = top_pairs
+            .iter()
+            .map(|pair| (pair.first_column_name.as_str(), pair.second_column_name.as_str()))
+            .collect();
+        assert_eq!(pair_names, vec![("b", "c"), ("a", "b"), ("a", "c")]);
+        assert_eq!(describe_correlation_strength(-0.9), "strong negative");
+    }
+
+    /// Test ties within a pair's own rows share their average rank
+    #[test]
+    fn test_rank_correlation_averages_ties_within_the_pair() {
+        // x ranks 1, 2.5, 2.5, 4 and y ranks 1, 2, 3, 4: rho = 4.5 / sqrt(4.5 * 5)
+        let rank_accumulator = accumulate_rank_correlation(&[(1.0, 10.0), (2.0, 20.0), (2.0, 30.0), (9.0, 40.0)]);
+        let expected_coefficient = 4.5 / (4.5_f64 * 5.0).sqrt();
+        assert!((rank_accumulator.correlation_coefficient().expect("defined") - expected_coefficient).abs() < 1e-12);
+    }
+
+    /// Test Spearman is ranked within each pair's complete rows when values
+    /// are missing on different rows in the two columns
+    #[test]
+    fn test_spearman_with_staggered_missing_values() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_spearman_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("staggered.csv");
+        std::fs::write(&csv_file_path, "x,y,z\n1,10,1\n2,,2\n,30,3\n4,20,4\n3,50,5\n5,40,6\n100,,7\n")
+            .expect("write test CSV");
+
+        let csv_analysis_results = super::super::csv_processor_module::analyze_csv_file_structure_and_types(&csv_file_path)
+            .expect("analysis should succeed");
+        let enhanced_analysis_results = super::super::csv_processor_module::perform_enhanced_statistical_analysis(&csv_file_path, &csv_analysis_results)
+            .expect("statistics should succeed");
+        let correlation_results = perform_correlation_analysis(&csv_analysis_results, &enhanced_analysis_results)
+            .expect("correlation should succeed");
+
+        // Complete rows (1,10) (4,20) (3,50) (5,40) rank to (1,1) (3,2) (2,4) (4,3):
+        // sum of d squared is 6, so rho = 1 - 6 * 6 / (4 * (16 - 1)) = 0.4.
+        // Column-wide ranks would give 5 / sqrt(87.5) = 0.53 instead.
+        let spearman_matrix = &correlation_results.spearman_matrix;
+        assert_eq!(spearman_matrix.column_names, vec!["x", "y", "z"]);
+        assert_eq!(spearman_matrix.pair_observation_counts[0][1], 4);
+        assert!((spearman_matrix.coefficient_values[0][1].expect("defined") - 0.4).abs() < 1e-12);
+
+        // z is complete, but x and y still need re-ranking within their rows;
+        // x against z: complete rows z = 1,2,4,5,6,7 with x = 1,2,4,3,5,100
+        let expected_x_z = 1.0 - 6.0 * 2.0 / (6.0 * 35.0);
+        assert!((spearman_matrix.coefficient_values[0][2].expect("defined") - expected_x_z).abs() < 1e-12);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
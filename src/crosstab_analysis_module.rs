@@ -0,0 +1,895 @@
+// src/crosstab_analysis_module.rs
+
+/// Crosstab (contingency table) and chi-square test for two categorical columns
+///
+/// For a pair such as `breed` × `is_indoor` this module counts how often each
+/// combination of categories occurs, then tests whether the two columns are
+/// independent with Pearson's chi-square test and measures the strength of the
+/// association with Cramér's V.
+///
+/// # Core Responsibilities
+/// - Build a contingency table in one streaming pass over the CSV
+/// - Compute expected counts, chi-square, degrees of freedom, p-value, Cramér's V
+/// - Render the table as text (counts, row %, column %, expected) and as a
+///   shaded grid
+///
+/// # Design Philosophy
+/// - Reuse the categorical machinery: row and column categories (and their
+///   order) come from each column's `CategoricalColumnStatistics`
+/// - Bounded memory: at most `MAXIMUM_CROSSTAB_CATEGORY_COUNT` categories per
+///   side; rarer categories are pooled into an "(other)" category
+/// - Pairwise-complete: rows where either value is empty are skipped and counted
+/// - Vanilla Rust: the chi-square distribution is evaluated with a regularized
+///   incomplete gamma function implemented here
+use std::collections::HashMap;
+
+// Import CSV structures and the shared row streaming loop
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvFieldType,
+    CsvRowStreamControl,
+    EnhancedCsvColumnInformation,
+    stream_csv_data_rows,
+};
+
+// Import the shading glyphs shared with the heatmap chart
+use super::chart_rendering_module::{ChartGlyphMode, select_heatmap_shade_glyph};
+
+// Import the configured chart glyphs
+use super::application_configuration_module::application_configuration;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_statistical_analysis_error,
+};
+
+/// Maximum number of categories kept on each side of the table
+pub const MAXIMUM_CROSSTAB_CATEGORY_COUNT: usize = 12;
+
+/// Label of the pooled category for values beyond the category limit
+const OTHER_CATEGORY_LABEL: &str = "(other)";
+
+/// Expected cell count below which the chi-square approximation is questionable
+const MINIMUM_RELIABLE_EXPECTED_COUNT: f64 = 5.0;
+
+/// Widest category label printed in the text table
+const MAXIMUM_CROSSTAB_LABEL_WIDTH: usize = 16;
+
+/// Iteration limit and tolerance for the incomplete gamma evaluation
+const INCOMPLETE_GAMMA_MAXIMUM_ITERATIONS: usize = 500;
+const INCOMPLETE_GAMMA_TOLERANCE: f64 = 1e-15;
+
+/// Observed counts for every combination of two columns' categories
+#[derive(Debug, Clone)]
+pub struct ContingencyTable {
+    /// Name of the column whose categories form the rows
+    pub row_column_name: String,
+
+    /// Name of the column whose categories form the columns
+    pub column_column_name: String,
+
+    /// Row category labels, in table order
+    pub row_category_labels: Vec<String>,
+
+    /// Column category labels, in table order
+    pub column_category_labels: Vec<String>,
+
+    /// Observed counts by row then column
+    pub observed_counts: Vec<Vec<usize>>,
+
+    /// Total of each row
+    pub row_totals: Vec<usize>,
+
+    /// Total of each column
+    pub column_totals: Vec<usize>,
+
+    /// Total of all cells
+    pub grand_total: usize,
+
+    /// Data rows skipped because either value was empty
+    pub skipped_row_count: usize,
+}
+
+/// Results of Pearson's chi-square test of independence
+#[derive(Debug, Clone)]
+pub struct ChiSquareTestResults {
+    /// Expected counts under independence, by row then column
+    pub expected_counts: Vec<Vec<f64>>,
+
+    /// Chi-square statistic
+    pub chi_square_statistic: f64,
+
+    /// Degrees of freedom: (rows - 1) × (columns - 1)
+    pub degrees_of_freedom: usize,
+
+    /// Probability of a statistic at least this large under independence
+    pub p_value: f64,
+
+    /// Cramér's V association strength (0 = none, 1 = perfect)
+    pub cramers_v: f64,
+
+    /// Number of cells with an expected count below 5
+    pub low_expected_cell_count: usize,
+}
+
+/// A contingency table with its chi-square test (when the test is defined)
+#[derive(Debug, Clone)]
+pub struct CrosstabAnalysisResults {
+    /// The observed counts
+    pub contingency_table: ContingencyTable,
+
+    /// None when the table has fewer than two non-empty rows or columns
+    pub chi_square_results: Option<ChiSquareTestResults>,
+}
+
+/// Lists the categorical columns that can be cross-tabulated
+///
+/// # Arguments
+/// * `enhanced_analysis_results` - All analyzed columns
+///
+/// # Returns
+/// * `Vec<usize>` - Positions (into `enhanced_analysis_results`) of categorical columns
+pub fn list_crosstab_candidate_positions(enhanced_analysis_results: &[EnhancedCsvColumnInformation]) -> Vec<usize> {
+    enhanced_analysis_results
+        .iter()
+        .enumerate()
+        .filter(|(_, enhanced_column_info)| {
+            enhanced_column_info.field_type == CsvFieldType::Categorical
+                && enhanced_column_info.categorical_statistics.is_some()
+        })
+        .map(|(column_position, _)| column_position)
+        .collect()
+}
+
+/// Builds the contingency table and runs the chi-square test for two columns
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `row_column_info` - Column whose categories form the table rows
+/// * `column_column_info` - Column whose categories form the table columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CrosstabAnalysisResults>` - Table and test results
+///
+/// # Errors
+/// * `RowsAndColumnsError::StatisticalAnalysisError` - If a column is not categorical
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV file cannot be read
+pub fn perform_crosstab_analysis(
+    csv_analysis_results: &CsvAnalysisResults,
+    row_column_info: &EnhancedCsvColumnInformation,
+    column_column_info: &EnhancedCsvColumnInformation,
+) -> RowsAndColumnsResult<CrosstabAnalysisResults> {
+    let contingency_table = build_contingency_table(
+        csv_analysis_results,
+        row_column_info,
+        column_column_info,
+        MAXIMUM_CROSSTAB_CATEGORY_COUNT,
+    )?;
+    let chi_square_results = calculate_chi_square_test(&contingency_table);
+
+    Ok(CrosstabAnalysisResults {
+        contingency_table,
+        chi_square_results,
+    })
+}
+
+/// Takes a column's most frequent categories from its categorical statistics
+///
+/// # Arguments
+/// * `enhanced_column_info` - The column (must have categorical statistics)
+/// * `maximum_category_count` - Category limit including the "(other)" category
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(Vec<String>, bool)>` - Labels and whether "(other)" is needed
+fn select_crosstab_categories(
+    enhanced_column_info: &EnhancedCsvColumnInformation,
+    maximum_category_count: usize,
+) -> RowsAndColumnsResult<(Vec<String>, bool)> {
+    let categorical_statistics = enhanced_column_info.categorical_statistics.as_ref().ok_or_else(|| {
+        create_statistical_analysis_error(
+            "Crosstab requires a categorical column",
+            &enhanced_column_info.basic_info.column_name
+        )
+    })?;
+
+    let value_frequencies = &categorical_statistics.value_frequencies;
+    let needs_other_category = value_frequencies.len() > maximum_category_count;
+    let kept_category_count = if needs_other_category {
+        maximum_category_count.saturating_sub(1).max(1)
+    } else {
+        value_frequencies.len()
+    };
+
+    let category_labels = value_frequencies
+        .iter()
+        .take(kept_category_count)
+        .map(|value_frequency| value_frequency.value.clone())
+        .collect();
+
+    Ok((category_labels, needs_other_category))
+}
+
+/// Counts category combinations in one streaming pass
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `row_column_info` - Column whose categories form the rows
+/// * `column_column_info` - Column whose categories form the columns
+/// * `maximum_category_count` - Category limit per side
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ContingencyTable>` - The table (categories with no
+///   pairwise-complete rows are dropped)
+pub fn build_contingency_table(
+    csv_analysis_results: &CsvAnalysisResults,
+    row_column_info: &EnhancedCsvColumnInformation,
+    column_column_info: &EnhancedCsvColumnInformation,
+    maximum_category_count: usize,
+) -> RowsAndColumnsResult<ContingencyTable> {
+    // Step 1: Category labels from the existing categorical statistics
+    let (mut row_category_labels, row_needs_other) = select_crosstab_categories(row_column_info, maximum_category_count)?;
+    let (mut column_category_labels, column_needs_other) = select_crosstab_categories(column_column_info, maximum_category_count)?;
+    if row_needs_other {
+        row_category_labels.push(OTHER_CATEGORY_LABEL.to_string());
+    }
+    if column_needs_other {
+        column_category_labels.push(OTHER_CATEGORY_LABEL.to_string());
+    }
+
+    let row_category_positions: HashMap<String, usize> = row_category_labels
+        .iter()
+        .enumerate()
+        .map(|(category_position, category_label)| (category_label.clone(), category_position))
+        .collect();
+    let column_category_positions: HashMap<String, usize> = column_category_labels
+        .iter()
+        .enumerate()
+        .map(|(category_position, category_label)| (category_label.clone(), category_position))
+        .collect();
+
+    // Values not in the kept list go to "(other)", which is always last
+    let locate_category = |category_positions: &HashMap<String, usize>, needs_other: bool, category_count: usize, value: &str| {
+        category_positions
+            .get(value)
+            .copied()
+            .or(if needs_other { Some(category_count - 1) } else { None })
+    };
+
+    // Step 2: Stream rows and count combinations
+    let row_column_index = row_column_info.basic_info.column_index;
+    let column_column_index = column_column_info.basic_info.column_index;
+    let mut observed_counts = vec![vec![0usize; column_category_labels.len()]; row_category_labels.len()];
+    let mut skipped_row_count = 0;
+
+    stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_, field_values| {
+            let row_value = field_values.get(row_column_index).map(String::as_str).unwrap_or("");
+            let column_value = field_values.get(column_column_index).map(String::as_str).unwrap_or("");
+
+            if row_value.is_empty() || column_value.is_empty() {
+                skipped_row_count += 1;
+                return Ok(CsvRowStreamControl::ContinueReading);
+            }
+
+            let row_position = locate_category(&row_category_positions, row_needs_other, row_category_labels.len(), row_value);
+            let column_position = locate_category(&column_category_positions, column_needs_other, column_category_labels.len(), column_value);
+
+            match (row_position, column_position) {
+                (Some(row_position), Some(column_position)) => observed_counts[row_position][column_position] += 1,
+                // Value unseen during analysis and no "(other)" bucket: cannot be placed
+                _ => skipped_row_count += 1,
+            }
+
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    // Step 3: Drop categories without any pairwise-complete rows
+    let kept_row_positions: Vec<usize> = (0..row_category_labels.len())
+        .filter(|&row_position| observed_counts[row_position].iter().sum::<usize>() > 0)
+        .collect();
+    let kept_column_positions: Vec<usize> = (0..column_category_labels.len())
+        .filter(|&column_position| observed_counts.iter().map(|row_counts| row_counts[column_position]).sum::<usize>() > 0)
+        .collect();
+
+    let observed_counts: Vec<Vec<usize>> = kept_row_positions
+        .iter()
+        .map(|&row_position| {
+            kept_column_positions
+                .iter()
+                .map(|&column_position| observed_counts[row_position][column_position])
+                .collect()
+        })
+        .collect();
+    let row_category_labels: Vec<String> = kept_row_positions
+        .iter()
+        .map(|&row_position| row_category_labels[row_position].clone())
+        .collect();
+    let column_category_labels: Vec<String> = kept_column_positions
+        .iter()
+        .map(|&column_position| column_category_labels[column_position].clone())
+        .collect();
+
+    // Step 4: Margins
+    let row_totals: Vec<usize> = observed_counts.iter().map(|row_counts| row_counts.iter().sum()).collect();
+    let column_totals: Vec<usize> = (0..column_category_labels.len())
+        .map(|column_position| observed_counts.iter().map(|row_counts| row_counts[column_position]).sum())
+        .collect();
+    let grand_total = row_totals.iter().sum();
+
+    Ok(ContingencyTable {
+        row_column_name: row_column_info.basic_info.column_name.clone(),
+        column_column_name: column_column_info.basic_info.column_name.clone(),
+        row_category_labels,
+        column_category_labels,
+        observed_counts,
+        row_totals,
+        column_totals,
+        grand_total,
+        skipped_row_count,
+    })
+}
+
+/// Runs Pearson's chi-square test of independence on a contingency table
+///
+/// # Arguments
+/// * `contingency_table` - Table with all-zero rows/columns already removed
+///
+/// # Returns
+/// * `Option<ChiSquareTestResults>` - None if the table is smaller than 2×2
+pub fn calculate_chi_square_test(contingency_table: &ContingencyTable) -> Option<ChiSquareTestResults> {
+    let row_count = contingency_table.row_category_labels.len();
+    let column_count = contingency_table.column_category_labels.len();
+
+    if row_count < 2 || column_count < 2 || contingency_table.grand_total == 0 {
+        return None;
+    }
+
+    let grand_total = contingency_table.grand_total as f64;
+    let mut expected_counts = vec![vec![0.0; column_count]; row_count];
+    let mut chi_square_statistic = 0.0;
+    let mut low_expected_cell_count = 0;
+
+    for (row_position, row_expected_counts) in expected_counts.iter_mut().enumerate() {
+        for (column_position, expected_cell) in row_expected_counts.iter_mut().enumerate() {
+            let expected_count = contingency_table.row_totals[row_position] as f64
+                * contingency_table.column_totals[column_position] as f64
+                / grand_total;
+            *expected_cell = expected_count;
+
+            if expected_count < MINIMUM_RELIABLE_EXPECTED_COUNT {
+                low_expected_cell_count += 1;
+            }
+
+            let deviation = contingency_table.observed_counts[row_position][column_position] as f64 - expected_count;
+            chi_square_statistic += deviation * deviation / expected_count;
+        }
+    }
+
+    let degrees_of_freedom = (row_count - 1) * (column_count - 1);
+    let p_value = calculate_chi_square_upper_tail_probability(chi_square_statistic, degrees_of_freedom);
+    let smaller_dimension = (row_count.min(column_count) - 1) as f64;
+    let cramers_v = (chi_square_statistic / (grand_total * smaller_dimension)).sqrt().clamp(0.0, 1.0);
+
+    Some(ChiSquareTestResults {
+        expected_counts,
+        chi_square_statistic,
+        degrees_of_freedom,
+        p_value,
+        cramers_v,
+        low_expected_cell_count,
+    })
+}
+
+/// Probability that a chi-square variable exceeds the given statistic
+///
+/// # Arguments
+/// * `chi_square_statistic` - Observed statistic (≥ 0)
+/// * `degrees_of_freedom` - Degrees of freedom (≥ 1)
+///
+/// # Returns
+/// * `f64` - Upper tail probability (the p-value)
+pub fn calculate_chi_square_upper_tail_probability(chi_square_statistic: f64, degrees_of_freedom: usize) -> f64 {
+    calculate_regularized_upper_incomplete_gamma(degrees_of_freedom as f64 / 2.0, chi_square_statistic / 2.0)
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation, g = 7)
+///
+/// # Arguments
+/// * `x_value` - Positive argument
+///
+/// # Returns
+/// * `f64` - ln Γ(x)
+fn calculate_log_gamma(x_value: f64) -> f64 {
+    const LANCZOS_COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x_value < 0.5 {
+        // Reflection formula for small arguments
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x_value).sin()).ln() - calculate_log_gamma(1.0 - x_value);
+    }
+
+    let shifted_x = x_value - 1.0;
+    let mut series_sum = LANCZOS_COEFFICIENTS[0];
+    for (coefficient_position, coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        series_sum += coefficient / (shifted_x + coefficient_position as f64);
+    }
+    let t_value = shifted_x + 7.5;
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (shifted_x + 0.5) * t_value.ln() - t_value + series_sum.ln()
+}
+
+/// Regularized upper incomplete gamma function Q(a, x)
+///
+/// Uses the series expansion of P(a, x) for `x < a + 1` and a continued
+/// fraction (modified Lentz) otherwise.
+///
+/// # Arguments
+/// * `shape_parameter` - a > 0
+/// * `x_value` - x ≥ 0
+///
+/// # Returns
+/// * `f64` - Q(a, x) between 0 and 1
+fn calculate_regularized_upper_incomplete_gamma(shape_parameter: f64, x_value: f64) -> f64 {
+    if x_value <= 0.0 {
+        return 1.0;
+    }
+
+    let log_prefactor = -x_value + shape_parameter * x_value.ln() - calculate_log_gamma(shape_parameter);
+
+    if x_value < shape_parameter + 1.0 {
+        // Series for the lower function P(a, x); Q = 1 - P
+        let mut term_value = 1.0 / shape_parameter;
+        let mut series_sum = term_value;
+        let mut denominator = shape_parameter;
+        for _ in 0..INCOMPLETE_GAMMA_MAXIMUM_ITERATIONS {
+            denominator += 1.0;
+            term_value *= x_value / denominator;
+            series_sum += term_value;
+            if term_value.abs() < series_sum.abs() * INCOMPLETE_GAMMA_TOLERANCE {
+                break;
+            }
+        }
+        (1.0 - series_sum * log_prefactor.exp()).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q(a, x)
+        let tiny_value = f64::MIN_POSITIVE / INCOMPLETE_GAMMA_TOLERANCE;
+        let mut b_value = x_value + 1.0 - shape_parameter;
+        let mut c_value = 1.0 / tiny_value;
+        let mut d_value = 1.0 / b_value;
+        let mut fraction_value = d_value;
+
+        for iteration_number in 1..=INCOMPLETE_GAMMA_MAXIMUM_ITERATIONS {
+            let a_term = -(iteration_number as f64) * (iteration_number as f64 - shape_parameter);
+            b_value += 2.0;
+            d_value = a_term * d_value + b_value;
+            if d_value.abs() < tiny_value {
+                d_value = tiny_value;
+            }
+            c_value = b_value + a_term / c_value;
+            if c_value.abs() < tiny_value {
+                c_value = tiny_value;
+            }
+            d_value = 1.0 / d_value;
+            let delta_value = d_value * c_value;
+            fraction_value *= delta_value;
+            if (delta_value - 1.0).abs() < INCOMPLETE_GAMMA_TOLERANCE {
+                break;
+            }
+        }
+        (log_prefactor.exp() * fraction_value).clamp(0.0, 1.0)
+    }
+}
+
+/// Formats the contingency table and test results as text lines
+///
+/// Each cell shows the observed count followed by its row and column
+/// percentages; expected counts are shown in a second table.
+///
+/// # Arguments
+/// * `crosstab_results` - The table and test results
+///
+/// # Returns
+/// * `Vec<String>` - Printable lines
+pub fn format_crosstab_text_lines(crosstab_results: &CrosstabAnalysisResults) -> Vec<String> {
+    let contingency_table = &crosstab_results.contingency_table;
+    let mut text_lines = Vec::new();
+
+    text_lines.push(format!(
+        "Crosstab: {} (rows) × {} (columns), n = {}{}",
+        contingency_table.row_column_name,
+        contingency_table.column_column_name,
+        contingency_table.grand_total,
+        if contingency_table.skipped_row_count > 0 {
+            format!(", {} rows skipped (empty value)", contingency_table.skipped_row_count)
+        } else {
+            String::new()
+        }
+    ));
+    text_lines.push("Cells: count (row % / column %)".to_string());
+
+    // Observed counts with percentages
+    let observed_cell_texts: Vec<Vec<String>> = contingency_table.observed_counts
+        .iter()
+        .enumerate()
+        .map(|(row_position, row_counts)| {
+            row_counts
+                .iter()
+                .enumerate()
+                .map(|(column_position, &cell_count)| {
+                    format!(
+                        "{} ({:.0}%/{:.0}%)",
+                        cell_count,
+                        calculate_percentage(cell_count, contingency_table.row_totals[row_position]),
+                        calculate_percentage(cell_count, contingency_table.column_totals[column_position])
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    text_lines.extend(format_labelled_table_lines(
+        contingency_table,
+        &observed_cell_texts,
+        Some((
+            contingency_table.row_totals.iter().map(|row_total| row_total.to_string()).collect(),
+            contingency_table.column_totals.iter().map(|column_total| column_total.to_string()).collect(),
+            contingency_table.grand_total.to_string(),
+        )),
+    ));
+
+    // Expected counts and test statistics
+    match &crosstab_results.chi_square_results {
+        Some(chi_square_results) => {
+            text_lines.push(String::new());
+            text_lines.push("Expected counts under independence:".to_string());
+            let expected_cell_texts: Vec<Vec<String>> = chi_square_results.expected_counts
+                .iter()
+                .map(|row_expected| row_expected.iter().map(|expected_count| format!("{:.2}", expected_count)).collect())
+                .collect();
+            text_lines.extend(format_labelled_table_lines(contingency_table, &expected_cell_texts, None));
+
+            text_lines.push(String::new());
+            text_lines.push(format!(
+                "Chi-square = {:.4}   df = {}   p-value = {}   Cramér's V = {:.4} ({})",
+                chi_square_results.chi_square_statistic,
+                chi_square_results.degrees_of_freedom,
+                format_p_value(chi_square_results.p_value),
+                chi_square_results.cramers_v,
+                describe_cramers_v_strength(chi_square_results.cramers_v)
+            ));
+            if chi_square_results.low_expected_cell_count > 0 {
+                text_lines.push(format!(
+                    "Note: {} of {} cells have an expected count below 5; the p-value may be unreliable.",
+                    chi_square_results.low_expected_cell_count,
+                    contingency_table.row_category_labels.len() * contingency_table.column_category_labels.len()
+                ));
+            }
+        }
+        None => {
+            text_lines.push(String::new());
+            text_lines.push("Chi-square test not available: need at least 2 categories on each side.".to_string());
+        }
+    }
+
+    text_lines
+}
+
+/// Lays out a table with row labels, column headers, and optional margins
+///
+/// # Arguments
+/// * `contingency_table` - Source of labels
+/// * `cell_texts` - Cell text by row then column
+/// * `margin_texts` - Optional (row totals, column totals, grand total) texts
+///
+/// # Returns
+/// * `Vec<String>` - Aligned table lines
+fn format_labelled_table_lines(
+    contingency_table: &ContingencyTable,
+    cell_texts: &[Vec<String>],
+    margin_texts: Option<(Vec<String>, Vec<String>, String)>,
+) -> Vec<String> {
+    let clip_label = |label_text: &str| -> String { label_text.chars().take(MAXIMUM_CROSSTAB_LABEL_WIDTH).collect() };
+
+    let row_label_width = contingency_table.row_category_labels
+        .iter()
+        .map(|row_label| clip_label(row_label).chars().count())
+        .chain(std::iter::once("Total".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut column_headers: Vec<String> = contingency_table.column_category_labels.iter().map(|label| clip_label(label)).collect();
+    if margin_texts.is_some() {
+        column_headers.push("Total".to_string());
+    }
+
+    let mut all_rows: Vec<Vec<String>> = cell_texts.to_vec();
+    if let Some((row_total_texts, column_total_texts, grand_total_text)) = &margin_texts {
+        for (row_cells, row_total_text) in all_rows.iter_mut().zip(row_total_texts) {
+            row_cells.push(row_total_text.clone());
+        }
+        let mut total_row = column_total_texts.clone();
+        total_row.push(grand_total_text.clone());
+        all_rows.push(total_row);
+    }
+
+    let column_widths: Vec<usize> = (0..column_headers.len())
+        .map(|column_position| {
+            all_rows
+                .iter()
+                .filter_map(|row_cells| row_cells.get(column_position))
+                .map(|cell_text| cell_text.chars().count())
+                .chain(std::iter::once(column_headers[column_position].chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut table_lines = Vec::new();
+    let mut header_line = format!("  {:<width$}", "", width = row_label_width);
+    for (column_header, column_width) in column_headers.iter().zip(&column_widths) {
+        header_line.push_str(&format!("  {:>width$}", column_header, width = column_width));
+    }
+    table_lines.push(header_line);
+
+    for (row_position, row_cells) in all_rows.iter().enumerate() {
+        let row_label = contingency_table.row_category_labels
+            .get(row_position)
+            .map(|row_label| clip_label(row_label))
+            .unwrap_or_else(|| "Total".to_string());
+        let mut row_line = format!("  {:<width$}", row_label, width = row_label_width);
+        for (cell_text, column_width) in row_cells.iter().zip(&column_widths) {
+            row_line.push_str(&format!("  {:>width$}", cell_text, width = column_width));
+        }
+        table_lines.push(row_line);
+    }
+
+    table_lines
+}
+
+/// Renders the observed counts as a shaded grid
+///
+/// Shading reflects each cell's share of its row (row percentage), so rows of
+/// different sizes can be compared at a glance.
+///
+/// # Arguments
+/// * `contingency_table` - The table to draw
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `Vec<String>` - Grid lines with a legend
+pub fn render_crosstab_shaded_grid_lines(contingency_table: &ContingencyTable, glyph_mode: ChartGlyphMode) -> Vec<String> {
+    let mut grid_lines = vec![format!(
+        "Shaded by row share: {} (rows) × {} (columns)",
+        contingency_table.row_column_name,
+        contingency_table.column_column_name
+    )];
+
+    let row_label_width = contingency_table.row_category_labels
+        .iter()
+        .map(|row_label| row_label.chars().count().min(MAXIMUM_CROSSTAB_LABEL_WIDTH))
+        .max()
+        .unwrap_or(0);
+
+    let mut header_line = format!("  {:<width$} ", "", width = row_label_width);
+    for column_position in 0..contingency_table.column_category_labels.len() {
+        header_line.push_str(&format!("{:>4}", column_position + 1));
+    }
+    grid_lines.push(header_line);
+
+    for (row_position, row_label) in contingency_table.row_category_labels.iter().enumerate() {
+        let clipped_label: String = row_label.chars().take(row_label_width).collect();
+        let mut row_line = format!("  {:<width$} ", clipped_label, width = row_label_width);
+        for &cell_count in &contingency_table.observed_counts[row_position] {
+            let row_share = calculate_percentage(cell_count, contingency_table.row_totals[row_position]) / 100.0;
+            let shade_glyph = select_heatmap_shade_glyph(row_share, glyph_mode);
+            row_line.push(' ');
+            row_line.push_str(&shade_glyph.to_string().repeat(3));
+        }
+        grid_lines.push(row_line);
+    }
+
+    let column_key: Vec<String> = contingency_table.column_category_labels
+        .iter()
+        .enumerate()
+        .map(|(column_position, column_label)| format!("{}={}", column_position + 1, column_label))
+        .collect();
+    grid_lines.push(format!("  Columns: {}", column_key.join("  ")));
+
+    let legend_glyphs: String = [0.0, 0.2, 0.4, 0.6, 0.8]
+        .iter()
+        .map(|band_start| select_heatmap_shade_glyph(*band_start, glyph_mode))
+        .collect();
+    grid_lines.push(format!("  Row share 0→100%: [{}]", legend_glyphs));
+
+    grid_lines
+}
+
+/// Percentage of a part in a whole (0 when the whole is 0)
+fn calculate_percentage(part_count: usize, whole_count: usize) -> f64 {
+    if whole_count == 0 {
+        0.0
+    } else {
+        part_count as f64 * 100.0 / whole_count as f64
+    }
+}
+
+/// Formats a p-value compactly (scientific notation for very small values)
+///
+/// # Arguments
+/// * `p_value` - Probability between 0 and 1
+///
+/// # Returns
+/// * `String` - Formatted p-value
+pub fn format_p_value(p_value: f64) -> String {
+    if p_value < 0.0001 {
+        format!("{:.2e}", p_value)
+    } else {
+        format!("{:.4}", p_value)
+    }
+}
+
+/// Describes Cramér's V in words
+///
+/// # Arguments
+/// * `cramers_v` - Value between 0 and 1
+///
+/// # Returns
+/// * `&'static str` - Strength description
+pub fn describe_cramers_v_strength(cramers_v: f64) -> &'static str {
+    match cramers_v {
+        value if value < 0.1 => "negligible association",
+        value if value < 0.3 => "weak association",
+        value if value < 0.5 => "moderate association",
+        _ => "strong association",
+    }
+}
+
+/// Prints the crosstab text tables and the shaded grid
+///
+/// # Arguments
+/// * `crosstab_results` - The results to display
+pub fn display_crosstab_analysis_results(crosstab_results: &CrosstabAnalysisResults) {
+    println!("═══════════════════════════════════════════════════════════════");
+    for text_line in format_crosstab_text_lines(crosstab_results) {
+        println!("{}", text_line);
+    }
+    println!();
+    for grid_line in render_crosstab_shaded_grid_lines(&crosstab_results.contingency_table, application_configuration().chart_glyph_mode) {
+        println!("{}", grid_line);
+    }
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a table directly from counts for tests
+    fn create_test_contingency_table(observed_counts: Vec<Vec<usize>>) -> ContingencyTable {
+        let row_totals: Vec<usize> = observed_counts.iter().map(|row_counts| row_counts.iter().sum()).collect();
+        let column_totals: Vec<usize> = (0..observed_counts[0].len())
+            .map(|column_position| observed_counts.iter().map(|row_counts| row_counts[column_position]).sum())
+            .collect();
+        ContingencyTable {
+            row_column_name: "breed".to_string(),
+            column_column_name: "is_indoor".to_string(),
+            row_category_labels: (0..observed_counts.len()).map(|position| format!("r{}", position)).collect(),
+            column_category_labels: (0..observed_counts[0].len()).map(|position| format!("c{}", position)).collect(),
+            grand_total: row_totals.iter().sum(),
+            observed_counts,
+            row_totals,
+            column_totals,
+            skipped_row_count: 0,
+        }
+    }
+
+    /// Test the chi-square distribution tail against textbook critical values
+    #[test]
+    fn test_chi_square_upper_tail_probability() {
+        assert!((calculate_chi_square_upper_tail_probability(3.841_459, 1) - 0.05).abs() < 1e-5);
+        assert!((calculate_chi_square_upper_tail_probability(5.991_465, 2) - 0.05).abs() < 1e-5);
+        assert!((calculate_chi_square_upper_tail_probability(18.307_04, 10) - 0.05).abs() < 1e-5);
+        assert_eq!(calculate_chi_square_upper_tail_probability(0.0, 3), 1.0);
+    }
+
+    /// Test chi-square, degrees of freedom and Cramér's V on a 2×2 table
+    #[test]
+    fn test_chi_square_test_on_two_by_two_table() {
+        let contingency_table = create_test_contingency_table(vec![vec![20, 30], vec![30, 20]]);
+        let chi_square_results = calculate_chi_square_test(&contingency_table).expect("test should be defined");
+
+        assert!((chi_square_results.chi_square_statistic - 4.0).abs() < 1e-9);
+        assert_eq!(chi_square_results.degrees_of_freedom, 1);
+        assert!((chi_square_results.cramers_v - 0.2).abs() < 1e-9);
+        assert!((chi_square_results.expected_counts[0][0] - 25.0).abs() < 1e-9);
+        assert_eq!(chi_square_results.low_expected_cell_count, 0);
+    }
+
+    /// Test that degenerate tables have no test and still format
+    #[test]
+    fn test_single_column_table_has_no_test() {
+        let contingency_table = create_test_contingency_table(vec![vec![4], vec![6]]);
+        assert!(calculate_chi_square_test(&contingency_table).is_none());
+
+        let crosstab_results = CrosstabAnalysisResults {
+            contingency_table,
+            chi_square_results: None,
+        };
+        let text_lines = format_crosstab_text_lines(&crosstab_results);
+        assert!(text_lines.iter().any(|line| line.contains("not available")));
+        assert!(text_lines.iter().any(|line| line.contains("Total")));
+    }
+
+    /// Test a strong association: the p-value underflows to zero rather than
+    /// going negative or NaN, and Cramér's V is exactly 1
+    #[test]
+    fn test_perfect_association_with_large_counts() {
+        let contingency_table = create_test_contingency_table(vec![vec![50_000, 0, 0], vec![0, 50_000, 0], vec![0, 0, 50_000]]);
+        let chi_square_results = calculate_chi_square_test(&contingency_table).expect("test should be defined");
+
+        assert!((chi_square_results.chi_square_statistic - 300_000.0).abs() < 1e-6);
+        assert_eq!(chi_square_results.degrees_of_freedom, 4);
+        assert!(chi_square_results.p_value >= 0.0 && chi_square_results.p_value < 1e-300);
+        assert!((chi_square_results.cramers_v - 1.0).abs() < 1e-12);
+    }
+
+    /// Test rows with a missing value are skipped, categories beyond the limit
+    /// are pooled into "(other)", and a category whose only row was skipped is
+    /// dropped instead of leaving an all-zero row
+    #[test]
+    fn test_missing_values_rare_categories_and_empty_rows() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_crosstab_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("pets.csv");
+
+        let mut csv_text = String::from("breed,home,age\n");
+        for _ in 0..5 {
+            csv_text.push_str("tabby,indoor,3\nhound,outdoor,5\n");
+        }
+        for rare_position in 0..14 {
+            csv_text.push_str(&format!("rare{},indoor,{}\n", rare_position, rare_position));
+        }
+        csv_text.push_str("tabby,,2\n,outdoor,4\n");
+        std::fs::write(&csv_file_path, csv_text).expect("write test CSV");
+
+        let csv_analysis_results = super::super::csv_processor_module::analyze_csv_file_structure_and_types(&csv_file_path)
+            .expect("analysis should succeed");
+        let enhanced_analysis_results = super::super::csv_processor_module::perform_enhanced_statistical_analysis(&csv_file_path, &csv_analysis_results)
+            .expect("statistics should succeed");
+        let crosstab_results = perform_crosstab_analysis(&csv_analysis_results, &enhanced_analysis_results[0], &enhanced_analysis_results[1])
+            .expect("crosstab should succeed");
+
+        let contingency_table = &crosstab_results.contingency_table;
+        assert_eq!(contingency_table.skipped_row_count, 2);
+        assert_eq!(contingency_table.grand_total, 24);
+        assert_eq!(contingency_table.row_category_labels.len(), MAXIMUM_CROSSTAB_CATEGORY_COUNT);
+        assert_eq!(contingency_table.row_category_labels.last().map(String::as_str), Some(OTHER_CATEGORY_LABEL));
+        assert_eq!(contingency_table.row_category_labels[0], "tabby");
+        assert!(contingency_table.row_totals.iter().all(|&row_total| row_total > 0));
+        assert!(contingency_table.column_totals.iter().all(|&column_total| column_total > 0));
+
+        // 14 rare breeds minus the 9 kept: 5 rows pooled, all indoor
+        let other_position = contingency_table.row_category_labels.len() - 1;
+        assert_eq!(contingency_table.row_totals[other_position], 5);
+
+        let chi_square_results = crosstab_results.chi_square_results.expect("test should be defined");
+        assert!(chi_square_results.chi_square_statistic.is_finite());
+        assert!(chi_square_results.expected_counts.iter().flatten().all(|&expected_count| expected_count > 0.0));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
@@ -0,0 +1,343 @@
+// src/csv_file_preview_module.rs
+
+/// Quick CSV file preview for the rows_and_columns file browser
+///
+/// Before a full analysis (which reads every row, maybe several times) the
+/// file browser shows what a highlighted file looks like: its size, the
+/// delimiter it appears to use, the header, the first few rows and an
+/// estimate of how many rows there are. All of this comes from the first
+/// few kilobytes of the file.
+///
+/// # Core Responsibilities
+/// - Read a bounded sample from the start of a CSV, TSV or gzip-compressed file
+/// - Sniff the delimiter (comma, tab, semicolon or pipe) from the sampled lines
+/// - Split the header and the first data rows with that delimiter
+/// - Count the rows exactly for small files, estimate them for large ones
+///
+/// # Design Philosophy
+/// - Bounded work: never read more than the sample, whatever the file size
+/// - An estimate is labelled as an estimate; unknown is better than wrong
+///   (compressed files have no byte size to extrapolate from)
+/// - Data only: the browser decides how to display the preview
+use std::io::{BufRead, Read};
+use std::path::Path;
+
+// Import input opening that decompresses .csv.gz and .tsv.gz files
+use super::csv_input_source_module::{CsvInputSource, open_csv_input_stream, resolve_csv_input_source};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{RowsAndColumnsResult, create_file_system_error};
+
+/// Most bytes read from the start of a file for one preview
+pub const PREVIEW_SAMPLE_BYTE_LIMIT: usize = 64 * 1_024;
+
+/// Number of data rows shown under the header
+pub const PREVIEW_DATA_ROW_COUNT: usize = 5;
+
+/// Number of lines (header included) the delimiter sniffer looks at
+const DELIMITER_SNIFFING_LINE_COUNT: usize = 20;
+
+/// Delimiters the sniffer can recognize, in tie-breaking order
+const CANDIDATE_CSV_DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+/// How many data rows a previewed file has
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvDataRowCountEstimate {
+    /// The whole file fit in the sample, so the rows were counted
+    Exact(usize),
+
+    /// Extrapolated from the average size of the sampled rows
+    Estimated(usize),
+
+    /// Compressed and larger than the sample: no basis for an estimate
+    Unknown,
+}
+
+/// What the browser shows about a file before it is analyzed
+#[derive(Debug, Clone)]
+pub struct CsvFilePreview {
+    /// Size on disk (compressed size for .csv.gz and .tsv.gz files)
+    pub file_size_bytes: u64,
+
+    /// Whether the file is gzip-compressed
+    pub is_gzip_compressed: bool,
+
+    /// The delimiter the sampled lines appear to use
+    pub sniffed_delimiter: char,
+
+    /// Header fields, split with the sniffed delimiter
+    pub header_fields: Vec<String>,
+
+    /// Up to `PREVIEW_DATA_ROW_COUNT` data rows, split with the sniffed delimiter
+    pub first_data_rows: Vec<Vec<String>>,
+
+    /// Number of data rows (header excluded)
+    pub data_row_count_estimate: CsvDataRowCountEstimate,
+}
+
+/// Builds a preview from the start of a CSV file
+///
+/// # Arguments
+/// * `csv_file_path` - A .csv, .tsv, .csv.gz or .tsv.gz file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvFilePreview>` - The preview
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be opened or read
+pub fn build_csv_file_preview(csv_file_path: &Path) -> RowsAndColumnsResult<CsvFilePreview> {
+    // Step 1: Size on disk, and whether the text has to be decompressed
+    let file_size_bytes = std::fs::metadata(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(&format!("Failed to read file size: {}", csv_file_path.display()), io_error)
+        })?
+        .len();
+    let csv_input_source = resolve_csv_input_source(&csv_file_path.to_string_lossy());
+    let is_gzip_compressed = matches!(csv_input_source, CsvInputSource::GzipCompressedFile(_));
+
+    // Step 2: Read whole lines until the byte budget is spent or the file ends
+    let mut csv_input_stream = open_csv_input_stream(&csv_input_source)?;
+    let mut sampled_lines: Vec<String> = Vec::new();
+    let mut sampled_data_line_byte_count = 0usize;
+    let mut header_line_byte_count = 0usize;
+    let mut reached_end_of_file = false;
+    let mut raw_line_bytes = Vec::new();
+    while header_line_byte_count + sampled_data_line_byte_count < PREVIEW_SAMPLE_BYTE_LIMIT {
+        raw_line_bytes.clear();
+        let remaining_byte_budget = PREVIEW_SAMPLE_BYTE_LIMIT - header_line_byte_count - sampled_data_line_byte_count;
+        let line_byte_count = Read::take(&mut csv_input_stream, remaining_byte_budget as u64)
+            .read_until(b'\n', &mut raw_line_bytes)
+            .map_err(|io_error| {
+                create_file_system_error(&format!("Failed to read CSV preview: {}", csv_file_path.display()), io_error)
+            })?;
+        if line_byte_count == 0 {
+            reached_end_of_file = true;
+            break;
+        }
+
+        // A line cut off by the budget is only kept when it is the header
+        let line_was_cut_off = line_byte_count == remaining_byte_budget && !raw_line_bytes.ends_with(b"\n");
+        if line_was_cut_off && !sampled_lines.is_empty() {
+            break;
+        }
+        let line_text = String::from_utf8_lossy(&raw_line_bytes).trim_end_matches(['\r', '\n']).to_string();
+        if sampled_lines.is_empty() {
+            header_line_byte_count = line_byte_count;
+        } else {
+            sampled_data_line_byte_count += line_byte_count;
+        }
+        sampled_lines.push(line_text);
+    }
+
+    // Step 3: Sniff the delimiter and split the header and first rows
+    let sniffed_delimiter = sniff_csv_delimiter(&sampled_lines);
+    let split_line = |line_text: &str| -> Vec<String> {
+        line_text.split(sniffed_delimiter).map(|field| field.trim().to_string()).collect()
+    };
+    let header_fields = sampled_lines.first().map(|header_line| split_line(header_line)).unwrap_or_default();
+    let sampled_data_lines: Vec<&String> = sampled_lines.iter()
+        .skip(1)
+        .filter(|line_text| !line_text.trim().is_empty())
+        .collect();
+    let first_data_rows = sampled_data_lines.iter()
+        .take(PREVIEW_DATA_ROW_COUNT)
+        .map(|line_text| split_line(line_text))
+        .collect();
+
+    // Step 4: Count the rows, or extrapolate from the average sampled row size
+    let data_row_count_estimate = if reached_end_of_file {
+        CsvDataRowCountEstimate::Exact(sampled_data_lines.len())
+    } else if is_gzip_compressed || sampled_data_line_byte_count == 0 {
+        CsvDataRowCountEstimate::Unknown
+    } else {
+        let data_byte_count = file_size_bytes.saturating_sub(header_line_byte_count as u64) as f64;
+        let average_data_line_byte_count = sampled_data_line_byte_count as f64 / (sampled_lines.len() - 1) as f64;
+        CsvDataRowCountEstimate::Estimated((data_byte_count / average_data_line_byte_count).round() as usize)
+    };
+
+    Ok(CsvFilePreview {
+        file_size_bytes,
+        is_gzip_compressed,
+        sniffed_delimiter,
+        header_fields,
+        first_data_rows,
+        data_row_count_estimate,
+    })
+}
+
+/// Guesses the delimiter of CSV text from its first lines
+///
+/// A delimiter wins when it appears in the header and the same number of
+/// times on the most following lines; a tie goes to the delimiter that
+/// appears more often, then to the order comma, tab, semicolon, pipe.
+/// Delimiters inside double-quoted fields are not counted.
+///
+/// # Arguments
+/// * `sampled_lines` - Lines from the start of the file, header first
+///
+/// # Returns
+/// * `char` - The most likely delimiter (a comma when nothing fits)
+pub fn sniff_csv_delimiter(sampled_lines: &[String]) -> char {
+    let sniffing_lines: Vec<&String> = sampled_lines.iter()
+        .filter(|line_text| !line_text.trim().is_empty())
+        .take(DELIMITER_SNIFFING_LINE_COUNT)
+        .collect();
+    let Some((header_line, data_lines)) = sniffing_lines.split_first() else {
+        return ',';
+    };
+
+    let mut best_delimiter = ',';
+    let mut best_score = (0usize, 0usize);
+    for candidate_delimiter in CANDIDATE_CSV_DELIMITERS {
+        let header_delimiter_count = count_unquoted_delimiters(header_line, candidate_delimiter);
+        if header_delimiter_count == 0 {
+            continue;
+        }
+        let consistent_line_count = data_lines.iter()
+            .filter(|data_line| count_unquoted_delimiters(data_line, candidate_delimiter) == header_delimiter_count)
+            .count();
+        let candidate_score = (consistent_line_count, header_delimiter_count);
+        if candidate_score > best_score {
+            best_score = candidate_score;
+            best_delimiter = candidate_delimiter;
+        }
+    }
+    best_delimiter
+}
+
+/// Counts a delimiter on a line, skipping double-quoted sections
+fn count_unquoted_delimiters(line_text: &str, delimiter: char) -> usize {
+    let mut inside_quotes = false;
+    let mut delimiter_count = 0;
+    for line_character in line_text.chars() {
+        if line_character == '"' {
+            inside_quotes = !inside_quotes;
+        } else if line_character == delimiter && !inside_quotes {
+            delimiter_count += 1;
+        }
+    }
+    delimiter_count
+}
+
+/// Names a delimiter for display ("comma", "tab", ...)
+///
+/// # Arguments
+/// * `delimiter` - A delimiter returned by `sniff_csv_delimiter`
+///
+/// # Returns
+/// * `&'static str` - Its name
+pub fn describe_csv_delimiter(delimiter: char) -> &'static str {
+    match delimiter {
+        ',' => "comma",
+        '\t' => "tab",
+        ';' => "semicolon",
+        '|' => "pipe",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Writes a test file into its own temporary directory
+    fn write_preview_test_file(test_name: &str, file_name: &str, file_text: &str) -> (PathBuf, PathBuf) {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_preview_{}_{}", test_name, std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let test_file_path = test_directory.join(file_name);
+        std::fs::write(&test_file_path, file_text).expect("test file should be writable");
+        (test_directory, test_file_path)
+    }
+
+    /// Test the sniffer picks the delimiter that is consistent across lines
+    #[test]
+    fn test_sniff_csv_delimiter() {
+        let to_lines = |csv_text: &str| csv_text.lines().map(String::from).collect::<Vec<_>>();
+        assert_eq!(sniff_csv_delimiter(&to_lines("a,b,c\n1,2,3\n4,5,6")), ',');
+        assert_eq!(sniff_csv_delimiter(&to_lines("name\tprice\nwidget\t1,50\ngadget\t2,75")), '\t');
+        assert_eq!(sniff_csv_delimiter(&to_lines("name;price\nwidget;1,50\ngadget;2,75")), ';');
+        assert_eq!(sniff_csv_delimiter(&to_lines("\"last, first\"|age\n\"Doe, Jo\"|41")), '|');
+        assert_eq!(sniff_csv_delimiter(&to_lines("single_column\nvalue")), ',');
+        assert_eq!(sniff_csv_delimiter(&[]), ',');
+        assert_eq!(describe_csv_delimiter('\t'), "tab");
+    }
+
+    /// Test a small file is previewed completely and its rows counted exactly
+    #[test]
+    fn test_small_file_preview_counts_rows_exactly() {
+        let mut csv_text = String::from("id;name\r\n");
+        for row_number in 1..=8 {
+            csv_text.push_str(&format!("{};cat {}\r\n", row_number, row_number));
+        }
+        let (test_directory, csv_file_path) = write_preview_test_file("small", "cats.csv", &csv_text);
+
+        let csv_file_preview = build_csv_file_preview(&csv_file_path).expect("preview should succeed");
+        assert_eq!(csv_file_preview.sniffed_delimiter, ';');
+        assert_eq!(csv_file_preview.header_fields, vec!["id", "name"]);
+        assert_eq!(csv_file_preview.first_data_rows.len(), PREVIEW_DATA_ROW_COUNT);
+        assert_eq!(csv_file_preview.first_data_rows[0], vec!["1", "cat 1"]);
+        assert_eq!(csv_file_preview.data_row_count_estimate, CsvDataRowCountEstimate::Exact(8));
+        assert_eq!(csv_file_preview.file_size_bytes, csv_text.len() as u64);
+        assert!(!csv_file_preview.is_gzip_compressed);
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test a file larger than the sample gets an estimate close to the real count
+    #[test]
+    fn test_large_file_preview_estimates_rows() {
+        let mut csv_text = String::from("id,value\n");
+        let data_row_count = 20_000;
+        for row_number in 1..=data_row_count {
+            csv_text.push_str(&format!("{},{}\n", row_number % 1_000, row_number % 97));
+        }
+        assert!(csv_text.len() > PREVIEW_SAMPLE_BYTE_LIMIT);
+        let (test_directory, csv_file_path) = write_preview_test_file("large", "values.csv", &csv_text);
+
+        let csv_file_preview = build_csv_file_preview(&csv_file_path).expect("preview should succeed");
+        match csv_file_preview.data_row_count_estimate {
+            CsvDataRowCountEstimate::Estimated(estimated_row_count) => {
+                let relative_error = (estimated_row_count as f64 - data_row_count as f64).abs() / data_row_count as f64;
+                assert!(relative_error < 0.1, "estimate {} too far from {}", estimated_row_count, data_row_count);
+            }
+            other_estimate => panic!("expected an estimate, got {:?}", other_estimate),
+        }
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test a file that is one enormous line is read only up to the sample
+    /// limit, and empty or header-only files are counted exactly
+    #[test]
+    fn test_preview_of_unbounded_line_and_empty_files() {
+        let enormous_line_text = "x,".repeat(PREVIEW_SAMPLE_BYTE_LIMIT * 2);
+        let (test_directory, enormous_line_path) = write_preview_test_file("enormous", "one_line.csv", &enormous_line_text);
+        let enormous_line_preview = build_csv_file_preview(&enormous_line_path).expect("preview should succeed");
+        assert!(enormous_line_preview.header_fields.len() <= PREVIEW_SAMPLE_BYTE_LIMIT / 2 + 1);
+        assert!(enormous_line_preview.first_data_rows.is_empty());
+        assert_eq!(enormous_line_preview.data_row_count_estimate, CsvDataRowCountEstimate::Unknown);
+
+        // A long data line after the header ends the sample without being split
+        let long_row_text = format!("id,text\n1,short\n2,{}\n3,short\n", "y".repeat(PREVIEW_SAMPLE_BYTE_LIMIT));
+        let long_row_path = test_directory.join("long_row.csv");
+        std::fs::write(&long_row_path, &long_row_text).expect("test file should be writable");
+        let long_row_preview = build_csv_file_preview(&long_row_path).expect("preview should succeed");
+        assert_eq!(long_row_preview.first_data_rows, vec![vec!["1".to_string(), "short".to_string()]]);
+        assert!(matches!(long_row_preview.data_row_count_estimate, CsvDataRowCountEstimate::Estimated(_)));
+
+        for (file_name, file_text, expected_header_fields) in [
+            ("empty.csv", "", Vec::<&str>::new()),
+            ("header_only.csv", "id,name", vec!["id", "name"]),
+            ("blank_lines.csv", "id,name\n\n\r\n", vec!["id", "name"]),
+        ] {
+            let test_file_path = test_directory.join(file_name);
+            std::fs::write(&test_file_path, file_text).expect("test file should be writable");
+            let csv_file_preview = build_csv_file_preview(&test_file_path).expect("preview should succeed");
+            assert_eq!(csv_file_preview.header_fields, expected_header_fields, "{}", file_name);
+            assert_eq!(csv_file_preview.data_row_count_estimate, CsvDataRowCountEstimate::Exact(0), "{}", file_name);
+        }
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+}
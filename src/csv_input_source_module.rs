@@ -0,0 +1,436 @@
+// src/csv_input_source_module.rs
+
+/// CSV input from standard input, named pipes and gzip files for rows_and_columns
+///
+/// `zcat data.csv.gz | rows_and_columns describe -` hands the tool a stream
+/// that can be read only once, while most analysis reopens the file several
+/// times. A `.csv.gz` file is treated the same way: its decompressed text is
+/// a stream. This module tells regular files from streams, opens streams for
+/// single-pass analysis, and copies ("spools") a stream to a work file when
+/// an operation really needs to read it more than once.
+///
+/// # Core Responsibilities
+/// - Resolve a command line CSV argument: `-` (standard input), a named pipe
+///   (FIFO, including `<(...)` process substitution), a `.csv.gz`/`.tsv.gz`
+///   file or a regular file
+/// - Open a stream for single-pass reading, decompressing gzip data (also
+///   gzip data arriving on standard input or a pipe)
+/// - Spool a stream to rows_columns_data/temporary_work and remove the copy
+///   (and any metadata written next to it) when it is no longer needed
+///
+/// # Design Philosophy
+/// - Read streams once: single-pass callers never spool
+/// - Spool only on demand, into the application's own work directory
+/// - Cleanup is tied to a guard value, like the sort and join work directories
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{RowsAndColumnsResult, create_file_system_error};
+
+// Import progress output that stays silent when stdout carries JSON
+use super::console_output_module::print_progress_line;
+
+// Import the std-only gzip reader for compressed CSV files
+use super::gzip_decompression_module::{
+    GZIP_MAGIC_BYTES,
+    GzipDecompressingReader,
+    is_gzip_compressed_csv_file_name,
+};
+
+/// The command line argument that stands for standard input
+pub const STANDARD_INPUT_PATH_ARGUMENT: &str = "-";
+
+/// File stem of the spooled copy of standard input
+const STANDARD_INPUT_SPOOL_FILE_STEM: &str = "standard_input";
+
+/// Where a command line CSV argument reads from
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvInputSource {
+    /// A file that can be reopened as often as needed
+    RegularFile(PathBuf),
+
+    /// Standard input (`-`)
+    StandardInput,
+
+    /// A FIFO such as `mkfifo` creates or `<(zcat data.csv.gz)` provides
+    NamedPipe(PathBuf),
+
+    /// A `.csv.gz` or `.tsv.gz` file, decompressed while it is read
+    GzipCompressedFile(PathBuf),
+}
+
+impl CsvInputSource {
+    /// Whether the input can be read only once
+    pub fn is_read_once_stream(&self) -> bool {
+        !matches!(self, CsvInputSource::RegularFile(_))
+    }
+
+    /// Path used in place of a file path in results and messages: the
+    /// argument as given, so standard input is `-`
+    pub fn source_label(&self) -> PathBuf {
+        match self {
+            CsvInputSource::RegularFile(csv_file_path)
+            | CsvInputSource::NamedPipe(csv_file_path)
+            | CsvInputSource::GzipCompressedFile(csv_file_path) => csv_file_path.clone(),
+            CsvInputSource::StandardInput => PathBuf::from(STANDARD_INPUT_PATH_ARGUMENT),
+        }
+    }
+}
+
+/// Resolves a command line CSV argument to its kind of input
+///
+/// Regular files are not validated here; callers keep their own checks.
+/// A `.csv.gz` name only counts as compressed when the file exists, so a
+/// missing one gets the usual "does not exist" error.
+///
+/// # Arguments
+/// * `csv_file_path_argument` - The argument as typed (`-` for standard input)
+///
+/// # Returns
+/// * `CsvInputSource` - Standard input, a named pipe, a gzip file or a (possibly missing) file
+pub fn resolve_csv_input_source(csv_file_path_argument: &str) -> CsvInputSource {
+    let csv_file_path = PathBuf::from(csv_file_path_argument);
+    if csv_file_path_argument == STANDARD_INPUT_PATH_ARGUMENT {
+        CsvInputSource::StandardInput
+    } else if is_named_pipe(&csv_file_path) {
+        CsvInputSource::NamedPipe(csv_file_path)
+    } else if is_gzip_compressed_csv_file_name(csv_file_path_argument) && csv_file_path.is_file() {
+        CsvInputSource::GzipCompressedFile(csv_file_path)
+    } else {
+        CsvInputSource::RegularFile(csv_file_path)
+    }
+}
+
+/// Checks whether a path is a FIFO (following symlinks such as /dev/fd/63)
+#[cfg(unix)]
+fn is_named_pipe(file_path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    std::fs::metadata(file_path)
+        .map(|file_metadata| file_metadata.file_type().is_fifo())
+        .unwrap_or(false)
+}
+
+/// Named pipes are only recognized on Unix
+#[cfg(not(unix))]
+fn is_named_pipe(_file_path: &Path) -> bool {
+    false
+}
+
+/// Opens the input for one sequential read
+///
+/// Gzip files are decompressed; standard input and pipes are decompressed
+/// too when they start with the gzip signature (`cat data.csv.gz | ...`).
+/// Corrupt compressed data surfaces as a read error of the returned reader.
+///
+/// # Arguments
+/// * `csv_input_source` - Where to read from
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Box<dyn BufRead>>` - A buffered reader over the CSV text
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file or pipe cannot be opened
+pub fn open_csv_input_stream(csv_input_source: &CsvInputSource) -> RowsAndColumnsResult<Box<dyn BufRead>> {
+    let open_source_file = |csv_file_path: &Path| {
+        File::open(csv_file_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to open CSV input: {}", csv_file_path.display()), io_error)
+        })
+    };
+    match csv_input_source {
+        CsvInputSource::StandardInput => decompress_stream_if_gzip(Box::new(std::io::stdin().lock())),
+        CsvInputSource::NamedPipe(csv_file_path) => {
+            decompress_stream_if_gzip(Box::new(BufReader::new(open_source_file(csv_file_path)?)))
+        }
+        CsvInputSource::GzipCompressedFile(csv_file_path) => {
+            Ok(Box::new(BufReader::new(GzipDecompressingReader::new(open_source_file(csv_file_path)?))))
+        }
+        CsvInputSource::RegularFile(csv_file_path) => Ok(Box::new(BufReader::new(open_source_file(csv_file_path)?))),
+    }
+}
+
+/// Wraps a stream in the gzip reader when its first bytes are the gzip signature
+fn decompress_stream_if_gzip(mut input_stream: Box<dyn BufRead>) -> RowsAndColumnsResult<Box<dyn BufRead>> {
+    let starts_with_gzip_signature = input_stream.fill_buf()
+        .map_err(|io_error| create_file_system_error("Failed to read the start of the CSV input", io_error))?
+        .starts_with(&GZIP_MAGIC_BYTES);
+    if starts_with_gzip_signature {
+        return Ok(Box::new(BufReader::new(GzipDecompressingReader::new(input_stream))));
+    }
+    Ok(input_stream)
+}
+
+/// A stream copied to a work file; the copy is removed when this is dropped
+///
+/// The copy sits alone in its own directory, so a metadata file written next
+/// to it by the analysis is removed with it.
+#[derive(Debug)]
+pub struct SpooledCsvInputFile {
+    spool_directory_path: PathBuf,
+    spooled_csv_file_path: PathBuf,
+}
+
+impl SpooledCsvInputFile {
+    /// Path of the spooled CSV copy
+    pub fn csv_file_path(&self) -> &PathBuf {
+        &self.spooled_csv_file_path
+    }
+}
+
+impl Drop for SpooledCsvInputFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.spool_directory_path);
+    }
+}
+
+/// A CSV file that can be read several times: the original or a spooled copy
+#[derive(Debug)]
+pub enum MultiPassCsvInputFile {
+    /// A regular file, read in place
+    OriginalFile(PathBuf),
+
+    /// A stream copied to a work file for as long as this value lives
+    Spooled(SpooledCsvInputFile),
+}
+
+impl MultiPassCsvInputFile {
+    /// Path to read the CSV from
+    pub fn csv_file_path(&self) -> &PathBuf {
+        match self {
+            MultiPassCsvInputFile::OriginalFile(csv_file_path) => csv_file_path,
+            MultiPassCsvInputFile::Spooled(spooled_input_file) => spooled_input_file.csv_file_path(),
+        }
+    }
+}
+
+/// Copies a read-once input to a work file so it can be read several times
+///
+/// Compressed input is spooled decompressed.
+///
+/// # Arguments
+/// * `csv_input_source` - Standard input, a named pipe or a gzip file
+/// * `temporary_work_directory` - Parent directory for the spool directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<SpooledCsvInputFile>` - The copy, removed when dropped
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If reading the input or writing the copy fails
+pub fn spool_csv_input_to_work_file(
+    csv_input_source: &CsvInputSource,
+    temporary_work_directory: &Path,
+) -> RowsAndColumnsResult<SpooledCsvInputFile> {
+    let source_label = csv_input_source.source_label();
+    let source_file_name = source_label
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // data.csv.gz is spooled as data.csv, so its metadata is data.csv_metadata.toml
+    let uncompressed_file_name = source_file_name.strip_suffix(".gz").unwrap_or(&source_file_name);
+    let spooled_file_stem = match csv_input_source {
+        CsvInputSource::StandardInput => STANDARD_INPUT_SPOOL_FILE_STEM.to_string(),
+        _ => Path::new(uncompressed_file_name)
+            .file_stem()
+            .map(|file_stem| file_stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "csv_input".to_string()),
+    };
+    let csv_input_stream = open_csv_input_stream(csv_input_source)?;
+    let spooled_input_file = spool_csv_reader_to_work_file(
+        csv_input_stream,
+        &format!("{}.csv", spooled_file_stem),
+        temporary_work_directory
+    )?;
+    print_progress_line(&format!(
+        "📥 Copied {} to {} (this step reads it more than once)",
+        source_label.display(),
+        spooled_input_file.csv_file_path().display()
+    ));
+    Ok(spooled_input_file)
+}
+
+/// Writes everything from a reader to a new spool directory
+///
+/// # Arguments
+/// * `csv_reader` - The input to copy
+/// * `spooled_file_name` - File name of the copy
+/// * `temporary_work_directory` - Parent directory for the spool directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<SpooledCsvInputFile>` - The copy, removed when dropped
+fn spool_csv_reader_to_work_file<R: BufRead>(
+    mut csv_reader: R,
+    spooled_file_name: &str,
+    temporary_work_directory: &Path,
+) -> RowsAndColumnsResult<SpooledCsvInputFile> {
+    // Step 1: A fresh directory, owned by the guard from the start so failures clean up
+    let spool_directory_path = temporary_work_directory.join(format!(
+        "csv_input_spool_{}_{}",
+        std::process::id(),
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or_default()
+    ));
+    std::fs::create_dir_all(&spool_directory_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create spool directory: {}", spool_directory_path.display()), io_error)
+    })?;
+    let spooled_input_file = SpooledCsvInputFile {
+        spooled_csv_file_path: spool_directory_path.join(spooled_file_name),
+        spool_directory_path,
+    };
+
+    // Step 2: Copy the input
+    let spool_file = File::create(spooled_input_file.csv_file_path()).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to create spool file: {}", spooled_input_file.csv_file_path().display()),
+            io_error
+        )
+    })?;
+    let mut spool_writer = BufWriter::new(spool_file);
+    std::io::copy(&mut csv_reader, &mut spool_writer)
+        .and_then(|_| spool_writer.flush())
+        .map_err(|io_error| create_file_system_error("Failed to copy CSV input to the spool file", io_error))?;
+
+    Ok(spooled_input_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::{
+        analyze_csv_file_structure_and_types,
+        analyze_csv_reader_in_single_pass,
+        perform_enhanced_statistical_analysis,
+    };
+
+    /// Test `-` means standard input and other arguments are files
+    #[test]
+    fn test_resolve_csv_input_source() {
+        assert_eq!(resolve_csv_input_source("-"), CsvInputSource::StandardInput);
+        assert!(resolve_csv_input_source("-").is_read_once_stream());
+        assert_eq!(resolve_csv_input_source("-").source_label(), PathBuf::from("-"));
+        assert_eq!(
+            resolve_csv_input_source("no/such/file.csv"),
+            CsvInputSource::RegularFile(PathBuf::from("no/such/file.csv"))
+        );
+    }
+
+    /// Test the spooled copy holds the input and disappears with its guard
+    #[test]
+    fn test_spooled_copy_is_removed_when_dropped() {
+        let temporary_work_directory = std::env::temp_dir()
+            .join(format!("rows_and_columns_input_spool_{}", std::process::id()));
+        let spooled_input_file = spool_csv_reader_to_work_file(
+            std::io::Cursor::new("a,b\n1,2\n"),
+            "standard_input.csv",
+            &temporary_work_directory
+        )
+        .expect("spooling should succeed");
+        let spooled_csv_file_path = spooled_input_file.csv_file_path().clone();
+        assert_eq!(std::fs::read_to_string(&spooled_csv_file_path).expect("copy should exist"), "a,b\n1,2\n");
+
+        drop(spooled_input_file);
+        assert!(!spooled_csv_file_path.exists());
+        let _ = std::fs::remove_dir_all(&temporary_work_directory);
+    }
+
+    /// Test single-pass analysis of a stream agrees with the file analysis
+    #[test]
+    fn test_single_pass_analysis_matches_file_analysis() {
+        let csv_text = "name,age,weight\nTom,3,4.5\nMia,,3.9\nLeo,7,5.1\n";
+        let test_directory = std::env::temp_dir()
+            .join(format!("rows_and_columns_single_pass_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("cats.csv");
+        std::fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+
+        let file_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("file analysis should succeed");
+        let file_enhanced_results = perform_enhanced_statistical_analysis(&csv_file_path, &file_analysis_results)
+            .expect("enhanced analysis should succeed");
+        let (stream_analysis_results, stream_enhanced_results) = analyze_csv_reader_in_single_pass(
+            std::io::Cursor::new(csv_text),
+            Path::new("-")
+        )
+        .expect("single-pass analysis should succeed");
+
+        assert_eq!(stream_analysis_results.has_header_row, file_analysis_results.has_header_row);
+        assert_eq!(stream_analysis_results.total_data_row_count, 3);
+        assert_eq!(stream_analysis_results.total_data_row_count, file_analysis_results.total_data_row_count);
+        for (stream_column, file_column) in stream_enhanced_results.iter().zip(&file_enhanced_results) {
+            assert_eq!(stream_column.basic_info.column_name, file_column.basic_info.column_name);
+            assert_eq!(stream_column.basic_info.detected_data_type, file_column.basic_info.detected_data_type);
+            assert_eq!(stream_column.basic_info.empty_value_count, file_column.basic_info.empty_value_count);
+            assert_eq!(
+                stream_column.numerical_statistics.as_ref().map(|numerical_stats| numerical_stats.q2_median_value),
+                file_column.numerical_statistics.as_ref().map(|numerical_stats| numerical_stats.q2_median_value)
+            );
+        }
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test streams with CRLF endings, blank lines, no final newline or only a
+    /// header give the same counts as the file analysis, and unreadable
+    /// streams are errors rather than panics
+    #[test]
+    fn test_awkward_streams_match_file_analysis() {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rows_and_columns_awkward_streams_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+
+        for (text_position, csv_text) in [
+            "name,age\r\nTom,3\r\nMia,5\r\n",
+            "name,age\nTom,3\n\nMia,5\n",
+            "name,age\nTom,3\nMia,5",
+            "name,age\n",
+            "Tom,3",
+        ].into_iter().enumerate() {
+            let csv_file_path = test_directory.join(format!("awkward_{}.csv", text_position));
+            std::fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+            let file_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("file analysis should succeed");
+            let (stream_analysis_results, stream_enhanced_results) = analyze_csv_reader_in_single_pass(
+                std::io::Cursor::new(csv_text),
+                Path::new("-")
+            )
+            .expect("single-pass analysis should succeed");
+
+            assert_eq!(stream_analysis_results.has_header_row, file_analysis_results.has_header_row, "{:?}", csv_text);
+            assert_eq!(stream_analysis_results.total_column_count, file_analysis_results.total_column_count, "{:?}", csv_text);
+            assert_eq!(stream_analysis_results.total_data_row_count, file_analysis_results.total_data_row_count, "{:?}", csv_text);
+            assert_eq!(stream_enhanced_results.len(), stream_analysis_results.total_column_count);
+        }
+
+        assert!(analyze_csv_reader_in_single_pass(std::io::Cursor::new(""), Path::new("-")).is_err());
+        assert!(analyze_csv_reader_in_single_pass(
+            std::io::Cursor::new(b"name,age\nT\xffm,3\n".to_vec()),
+            Path::new("-")
+        ).is_err());
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test piped input is only decompressed when it starts with the gzip
+    /// signature, including inputs shorter than the signature
+    #[test]
+    fn test_stream_gzip_detection() {
+        let read_stream_text = |stream_bytes: Vec<u8>| {
+            let mut stream_text = String::new();
+            decompress_stream_if_gzip(Box::new(std::io::Cursor::new(stream_bytes)))
+                .expect("detection should succeed")
+                .read_to_string(&mut stream_text)
+                .map(|_| stream_text)
+        };
+
+        assert_eq!(read_stream_text(Vec::new()).expect("empty input"), "");
+        assert_eq!(read_stream_text(vec![0x1f]).expect("one byte"), "\u{1f}");
+        assert_eq!(read_stream_text(b"a,b\n1,2\n".to_vec()).expect("plain text"), "a,b\n1,2\n");
+
+        let mut stored_member = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff, 0x01, 0x04, 0x00, 0xfb, 0xff];
+        stored_member.extend_from_slice(b"a,b\n");
+        stored_member.extend_from_slice(&super::super::gzip_decompression_module::update_crc32_checksum(0, b"a,b\n").to_le_bytes());
+        stored_member.extend_from_slice(&4u32.to_le_bytes());
+        assert_eq!(read_stream_text(stored_member.clone()).expect("gzip input"), "a,b\n");
+
+        // A truncated member is a read error, not a silently shorter CSV
+        stored_member.truncate(stored_member.len() - 6);
+        assert!(read_stream_text(stored_member).is_err());
+    }
+}
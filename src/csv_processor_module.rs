@@ -13,37 +13,41 @@
 /// - Create and manage CSV metadata TOML files
 /// - Validate CSV format and accessibility
 /// - Generate column analysis reports
+/// - Write derived CSV files (filtered, sorted, joined) row by row with metadata
 /// 
 /// # Design Philosophy
 /// - Sample-based analysis: analyze first N rows for type detection
 /// - Memory-efficient: don't load entire CSV into memory
 /// - Metadata-driven: persistent TOML files track column information
 /// - Fallback handling: graceful handling of missing headers or mixed types
-
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 
 // Import our error handling system
 use super::error_types_module::{
-    RowsAndColumnsError,
     RowsAndColumnsResult,
     create_file_system_error,
     create_csv_processing_error,
-    create_metadata_error,
     create_configuration_error,
 };
 
-// Import path management for metadata file operations
-use super::manage_absolute_executable_directory_relative_paths::{
-    make_input_path_name_abs_executabledirectoryrelative_nocheck,
-    abs_executable_directory_relative_exists,
-    prepare_file_parent_directories_abs_executabledirectoryrelative,
+// Import TOML string escaping and derived column definitions for metadata writing
+use super::metadata_manager_module::{
+    DerivedColumnDefinition,
+    escape_toml_string_value,
+    format_derived_column_definitions_toml,
+    read_derived_column_definitions,
 };
 
+// Import progress output that stays silent when stdout carries JSON
+use super::console_output_module::{print_output_text, print_progress_line, print_warning_line};
+
+// Import the configured sample size, type threshold and display limits
+use super::application_configuration_module::application_configuration;
+
 /// Configuration constants for CSV processing
-const CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION: usize = 10;
 const METADATA_FILE_EXTENSION: &str = "csv_metadata.toml";
 
 /// Represents the detected data type for a CSV column
@@ -167,15 +171,15 @@ pub struct CsvAnalysisResults {
 /// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
 /// * `RowsAndColumnsError::MetadataError` - If metadata file operations fail
 pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndColumnsResult<CsvAnalysisResults> {
-    println!("🔍 Analyzing CSV file structure...");
+    print_progress_line("🔍 Analyzing CSV file structure...");
     
     // Step 1: Read and analyze the CSV file structure
     let (has_header_row, column_count, data_row_count) = analyze_csv_basic_structure(csv_file_path)?;
     
-    println!("  ✓ Basic structure detected:");
-    println!("    Columns: {}", column_count);
-    println!("    Data rows: {}", data_row_count);
-    println!("    Has header: {}", has_header_row);
+    print_progress_line("  ✓ Basic structure detected:");
+    print_progress_line(&format!("    Columns: {}", column_count));
+    print_progress_line(&format!("    Data rows: {}", data_row_count));
+    print_progress_line(&format!("    Has header: {}", has_header_row));
     
     // Step 2: Analyze column data types and content
     let column_information_list = analyze_csv_column_types_and_content(
@@ -184,22 +188,22 @@ pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndC
         column_count
     )?;
     
-    println!("  ✓ Column types analyzed");
+    print_progress_line("  ✓ Column types analyzed");
     
     // Step 3: Determine metadata file path and check if it exists
     let metadata_file_path = determine_metadata_file_path(csv_file_path)?;
     let metadata_file_already_existed = metadata_file_path.exists();
     
     if metadata_file_already_existed {
-        println!("  ✓ Found existing metadata file: {}", metadata_file_path.display());
+        print_progress_line(&format!("  ✓ Found existing metadata file: {}", metadata_file_path.display()));
     } else {
-        println!("  ✓ Will create metadata file: {}", metadata_file_path.display());
+        print_progress_line(&format!("  ✓ Will create metadata file: {}", metadata_file_path.display()));
     }
     
     // Step 4: Create or update metadata file
     create_or_update_metadata_file(&metadata_file_path, &column_information_list)?;
     
-    println!("  ✓ Metadata file updated");
+    print_progress_line("  ✓ Metadata file updated");
     
     // Return complete analysis results
     Ok(CsvAnalysisResults {
@@ -223,7 +227,7 @@ pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndC
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<(bool, usize, usize)>` - (has_header, column_count, data_rows)
-fn analyze_csv_basic_structure(csv_file_path: &PathBuf) -> RowsAndColumnsResult<(bool, usize, usize)> {
+pub fn analyze_csv_basic_structure(csv_file_path: &PathBuf) -> RowsAndColumnsResult<(bool, usize, usize)> {
     let csv_file = File::open(csv_file_path)
         .map_err(|io_error| {
             create_file_system_error(
@@ -254,11 +258,16 @@ fn analyze_csv_basic_structure(csv_file_path: &PathBuf) -> RowsAndColumnsResult<
     let column_count = count_csv_columns_in_line(&first_line);
     
     // Check if first line looks like a header by analyzing the second line
-    let has_header_row = detect_csv_header_row(&mut csv_lines, column_count, &first_line)?;
+    // (a file with only one line is data, not a header)
+    let second_line = csv_lines.next()
+        .transpose()
+        .map_err(|io_error| create_file_system_error("Failed to read second line of CSV", io_error))?;
+    let has_header_row = second_line.as_deref()
+        .is_some_and(|second_line| first_line_looks_like_header(&first_line, second_line, column_count));
     
     // Count total data rows (excluding header if present)
-    let total_rows = count_remaining_csv_lines(csv_lines)?;
-    let data_row_count = if has_header_row { total_rows } else { total_rows + 1 };
+    let total_line_count = 1 + usize::from(second_line.is_some()) + count_remaining_csv_lines(csv_lines)?;
+    let data_row_count = if has_header_row { total_line_count - 1 } else { total_line_count };
     
     Ok((has_header_row, column_count, data_row_count))
 }
@@ -277,36 +286,19 @@ fn count_csv_columns_in_line(csv_line: &str) -> usize {
     // Simple comma split for MVP - could be enhanced for quoted fields later
     csv_line.split(',').count()
 }
-/// Detects whether the CSV file has a header row
+/// Applies the header heuristic to the first two lines of a CSV file
 /// 
-/// This function uses heuristics to determine if the first row contains
-/// column headers rather than data.
+/// The first line is taken as a header when it has fewer numeric fields
+/// than the second line.
 /// 
 /// # Arguments
-/// * `remaining_lines` - Iterator of remaining lines after the first
+/// * `first_line` - The first line (possible header)
+/// * `second_line` - The second line (first data row if there is a header)
 /// * `expected_column_count` - Expected number of columns
-/// * `first_line` - The first line content for analysis
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<bool>` - True if header row detected
-fn detect_csv_header_row(
-    remaining_lines: &mut std::io::Lines<BufReader<File>>,
-    expected_column_count: usize,
-    first_line: &str,
-) -> RowsAndColumnsResult<bool> {
-    // Read the second line for comparison
-    let second_line = match remaining_lines.next() {
-        Some(line_result) => {
-            line_result.map_err(|io_error| {
-                create_file_system_error("Failed to read second line of CSV", io_error)
-            })?
-        }
-        None => {
-            // Only one line in file - assume it's data, not header
-            return Ok(false);
-        }
-    };
-    
+/// * `bool` - True if the first line looks like a header
+fn first_line_looks_like_header(first_line: &str, second_line: &str, expected_column_count: usize) -> bool {
     // Split both lines into fields
     let first_fields: Vec<&str> = first_line.split(',').collect();
     let second_fields: Vec<&str> = second_line.split(',').collect();
@@ -315,7 +307,7 @@ fn detect_csv_header_row(
     if first_fields.len() != expected_column_count || 
        second_fields.len() != expected_column_count {
         // Inconsistent column counts - this is suspicious but proceed
-        println!("  Warning: Inconsistent column counts detected");
+        print_warning_line("  Warning: Inconsistent column counts detected");
     }
     
     // Heuristic: if first line contains non-numeric values and second line
@@ -324,9 +316,7 @@ fn detect_csv_header_row(
     let second_line_numeric_fields = count_numeric_fields(&second_fields);
     
     // If first line has fewer numeric fields than second line, it's likely a header
-    let likely_header = first_line_numeric_fields < second_line_numeric_fields;
-    
-    Ok(likely_header)
+    first_line_numeric_fields < second_line_numeric_fields
 }
 
 /// Counts how many fields in a list appear to be numeric (int or float)
@@ -398,10 +388,7 @@ fn analyze_csv_column_types_and_content(
     let mut csv_lines = csv_reader.lines();
     
     // Initialize column information structures
-    let mut column_info_list = Vec::new();
-    let mut column_sample_values: Vec<Vec<String>> = vec![Vec::new(); column_count];
-    let mut column_non_empty_counts = vec![0usize; column_count];
-    let mut column_empty_counts = vec![0usize; column_count];
+    let mut column_sample_tally = ColumnSampleTally::new(column_count);
     
     // Read header row if it exists to get column names
     let column_names = if has_header_row {
@@ -421,16 +408,13 @@ fn analyze_csv_column_types_and_content(
             }
         }
     } else {
-        // Generate column names: column_1, column_2, etc.
-        (0..column_count)
-            .map(|index| format!("column_{}", index + 1))
-            .collect()
+        generate_default_column_names(column_count)
     };
     
     // Sample data rows for type detection
-    let mut rows_processed = 0;
+    let type_detection_sample_row_count = application_configuration().type_detection_sample_row_count;
     for (line_number, line_result) in csv_lines.enumerate() {
-        if rows_processed >= CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION {
+        if column_sample_tally.sampled_row_count >= type_detection_sample_row_count {
             break;
         }
         
@@ -441,11 +425,56 @@ fn analyze_csv_column_types_and_content(
             )
         })?;
         
-        let field_values = parse_csv_line_into_fields(&csv_line);
-        
-        // Process each field in this row
+        column_sample_tally.record_sampled_row(&parse_csv_line_into_fields(&csv_line));
+    }
+    
+    // Analyze data types for each column based on samples
+    Ok(column_sample_tally.into_column_information_list(&column_names))
+}
+
+/// Generates column names for a file without a header: column_1, column_2, etc.
+fn generate_default_column_names(column_count: usize) -> Vec<String> {
+    (0..column_count)
+        .map(|index| format!("column_{}", index + 1))
+        .collect()
+}
+
+/// Per-column counts and sample values from the first rows of a CSV file
+/// 
+/// Shared by the file analysis and the single-pass stream analysis so both
+/// detect the same types from the same number of rows.
+struct ColumnSampleTally {
+    /// Number of data rows recorded so far
+    sampled_row_count: usize,
+    
+    /// Up to five non-empty sample values per column
+    column_sample_values: Vec<Vec<String>>,
+    
+    /// Non-empty values seen per column
+    column_non_empty_counts: Vec<usize>,
+    
+    /// Empty values seen per column
+    column_empty_counts: Vec<usize>,
+}
+
+impl ColumnSampleTally {
+    /// Creates an empty tally for the given number of columns
+    fn new(column_count: usize) -> Self {
+        ColumnSampleTally {
+            sampled_row_count: 0,
+            column_sample_values: vec![Vec::new(); column_count],
+            column_non_empty_counts: vec![0usize; column_count],
+            column_empty_counts: vec![0usize; column_count],
+        }
+    }
+    
+    /// Counts the fields of one sampled data row (extra fields are ignored)
+    /// 
+    /// # Arguments
+    /// * `field_values` - The row's untrimmed field values
+    fn record_sampled_row(&mut self, field_values: &[String]) {
         for (column_index, field_value) in field_values.iter().enumerate() {
-            if column_index >= column_count {
+            if column_index >= self.column_sample_values.len() {
                 // More fields than expected - skip extras
                 continue;
             }
@@ -453,41 +482,132 @@ fn analyze_csv_column_types_and_content(
             let trimmed_value = field_value.trim();
             
             if trimmed_value.is_empty() {
-                column_empty_counts[column_index] += 1;
+                self.column_empty_counts[column_index] += 1;
             } else {
-                column_non_empty_counts[column_index] += 1;
+                self.column_non_empty_counts[column_index] += 1;
                 
                 // Store sample values (limit to prevent memory issues)
-                if column_sample_values[column_index].len() < 5 {
-                    column_sample_values[column_index].push(trimmed_value.to_string());
+                if self.column_sample_values[column_index].len() < 5 {
+                    self.column_sample_values[column_index].push(trimmed_value.to_string());
                 }
             }
         }
         
-        rows_processed += 1;
+        self.sampled_row_count += 1;
     }
     
-    // Analyze data types for each column based on samples
-    for column_index in 0..column_count {
-        let column_name = column_names.get(column_index)
-            .cloned()
-            .unwrap_or_else(|| format!("column_{}", column_index + 1));
-        
-        let detected_data_type = detect_column_data_type(&column_sample_values[column_index]);
-        
-        let column_info = CsvColumnInformation {
-            column_index,
-            column_name,
-            detected_data_type,
-            non_empty_value_count: column_non_empty_counts[column_index],
-            empty_value_count: column_empty_counts[column_index],
-            sample_values: column_sample_values[column_index].clone(),
-        };
-        
-        column_info_list.push(column_info);
+    /// Detects each column's type from its samples
+    /// 
+    /// # Arguments
+    /// * `column_names` - Header names (missing names are generated)
+    /// 
+    /// # Returns
+    /// * `Vec<CsvColumnInformation>` - One entry per column, in file order
+    fn into_column_information_list(self, column_names: &[String]) -> Vec<CsvColumnInformation> {
+        self.column_sample_values
+            .into_iter()
+            .enumerate()
+            .map(|(column_index, sample_values)| CsvColumnInformation {
+                column_index,
+                column_name: column_names.get(column_index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("column_{}", column_index + 1)),
+                detected_data_type: detect_column_data_type(&sample_values),
+                non_empty_value_count: self.column_non_empty_counts[column_index],
+                empty_value_count: self.column_empty_counts[column_index],
+                sample_values,
+            })
+            .collect()
+    }
+}
+
+/// Analyzes CSV text from a reader in one pass (standard input, pipes)
+/// 
+/// Structure, column types and the enhanced statistics are computed while
+/// reading, so a stream that cannot be reopened is read exactly once. Like
+/// `perform_enhanced_statistical_analysis`, all column values are held in
+/// memory. No metadata file is written: `metadata_file_path` is left empty.
+/// 
+/// # Arguments
+/// * `csv_reader` - The CSV text
+/// * `csv_source_label` - Stands in for the file path in results and messages
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<(CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)>` - Both analyses
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If reading fails
+/// * `RowsAndColumnsError::CsvProcessingError` - If the input is empty
+pub fn analyze_csv_reader_in_single_pass<R: BufRead>(
+    csv_reader: R,
+    csv_source_label: &Path,
+) -> RowsAndColumnsResult<(CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)> {
+    print_progress_line(&format!("🔍 Analyzing CSV input in one pass: {}", csv_source_label.display()));
+    
+    let mut csv_lines = csv_reader.lines().map(|line_result| {
+        line_result.map_err(|io_error| create_file_system_error("Failed to read line of CSV input", io_error))
+    });
+    
+    // Step 1: The first two lines give the column count and the header decision
+    let first_line = csv_lines.next().transpose()?.ok_or_else(|| {
+        create_csv_processing_error("CSV input appears to be empty", Some(1), None)
+    })?;
+    let column_count = count_csv_columns_in_line(&first_line);
+    let second_line = csv_lines.next().transpose()?;
+    let has_header_row = second_line.as_deref()
+        .is_some_and(|second_line| first_line_looks_like_header(&first_line, second_line, column_count));
+    
+    let (column_names, leading_data_lines) = if has_header_row {
+        (parse_csv_line_into_fields(&first_line), vec![second_line])
+    } else {
+        (generate_default_column_names(column_count), vec![Some(first_line), second_line])
+    };
+    
+    // Step 2: Sample the first rows for types and keep every value for statistics
+    let mut column_sample_tally = ColumnSampleTally::new(column_count);
+    let mut all_column_values: Vec<Vec<String>> = vec![Vec::new(); column_count];
+    let mut data_row_count = 0;
+    let type_detection_sample_row_count = application_configuration().type_detection_sample_row_count;
+    for csv_line_result in leading_data_lines.into_iter().flatten().map(Ok).chain(csv_lines) {
+        let csv_line = csv_line_result?;
+        let field_values = parse_csv_line_into_fields(&csv_line);
+        if column_sample_tally.sampled_row_count < type_detection_sample_row_count {
+            column_sample_tally.record_sampled_row(&field_values);
+        }
+        for (column_values, field_value) in all_column_values.iter_mut().zip(&field_values) {
+            column_values.push(field_value.trim().to_string());
+        }
+        data_row_count += 1;
     }
     
-    Ok(column_info_list)
+    print_progress_line("  ✓ Basic structure detected:");
+    print_progress_line(&format!("    Columns: {}", column_count));
+    print_progress_line(&format!("    Data rows: {}", data_row_count));
+    print_progress_line(&format!("    Has header: {}", has_header_row));
+    
+    // Step 3: Types from the samples, statistics from all values
+    let column_information_list = column_sample_tally.into_column_information_list(&column_names);
+    let enhanced_column_info_list = column_information_list
+        .iter()
+        .map(|basic_column_info| {
+            build_enhanced_column_information(basic_column_info, &all_column_values[basic_column_info.column_index])
+        })
+        .collect::<RowsAndColumnsResult<Vec<_>>>()?;
+    
+    print_progress_line("  ✓ Enhanced statistical analysis complete");
+    
+    Ok((
+        CsvAnalysisResults {
+            csv_file_path: csv_source_label.to_path_buf(),
+            has_header_row,
+            total_column_count: column_count,
+            total_data_row_count: data_row_count,
+            column_information_list,
+            metadata_file_path: PathBuf::new(),
+            metadata_file_already_existed: false,
+        },
+        enhanced_column_info_list,
+    ))
 }
 
 /// Parses a CSV line into individual field values
@@ -500,12 +620,89 @@ fn analyze_csv_column_types_and_content(
 /// 
 /// # Returns
 /// * `Vec<String>` - List of field values
-fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
+pub fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
     csv_line.split(',')
         .map(|field| field.to_string())
         .collect()
 }
 
+/// Tells `stream_csv_data_rows` whether to keep reading after a row
+///
+/// Row visitors return this so that callers which only need part of the file
+/// (previews, capped samples) can stop early without reading the rest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvRowStreamControl {
+    /// Continue with the next data row
+    ContinueReading,
+
+    /// Stop reading; no further rows will be visited
+    StopReading,
+}
+
+/// Streams every data row of a CSV file through a visitor, one row at a time
+///
+/// This is the shared no-load reading loop for features that need to look at
+/// row values (charts, correlations, aggregations). Only the current row is
+/// held in memory. Field values are trimmed, matching `collect_all_column_values`.
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `has_header_row` - Whether the first line is a header to skip
+/// * `row_visitor` - Called with (0-based data row index, trimmed field values)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - Number of data rows visited, or the first error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be opened or read
+/// * Any error returned by `row_visitor` is propagated unchanged
+pub fn stream_csv_data_rows<F>(
+    csv_file_path: &Path,
+    has_header_row: bool,
+    mut row_visitor: F,
+) -> RowsAndColumnsResult<usize>
+where
+    F: FnMut(usize, &[String]) -> RowsAndColumnsResult<CsvRowStreamControl>,
+{
+    let csv_file = File::open(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to open CSV file for streaming: {}", csv_file_path.display()),
+                io_error
+            )
+        })?;
+    
+    let csv_reader = BufReader::new(csv_file);
+    let mut csv_lines = csv_reader.lines();
+    
+    // Skip header row if present
+    if has_header_row {
+        csv_lines.next();
+    }
+    
+    let mut visited_row_count = 0;
+    
+    for line_result in csv_lines {
+        let csv_line = line_result.map_err(|io_error| {
+            create_file_system_error("Failed to read CSV line while streaming rows", io_error)
+        })?;
+        
+        let trimmed_field_values: Vec<String> = parse_csv_line_into_fields(&csv_line)
+            .iter()
+            .map(|field_value| field_value.trim().to_string())
+            .collect();
+        
+        let stream_control = row_visitor(visited_row_count, &trimmed_field_values)?;
+        visited_row_count += 1;
+        
+        if stream_control == CsvRowStreamControl::StopReading {
+            break;
+        }
+    }
+    
+    Ok(visited_row_count)
+}
+
 /// Detects the most likely data type for a column based on sample values
 /// 
 /// This function analyzes sample values and determines the most appropriate
@@ -545,8 +742,8 @@ fn detect_column_data_type(sample_values: &[String]) -> CsvColumnDataType {
     }
     
     // Determine type based on majority of samples
-    // Require at least 70% of samples to match a type
-    let threshold = (total_samples * 7) / 10; // 70% threshold
+    // Require the configured share (70% by default) of samples to match a type
+    let threshold = (total_samples * application_configuration().type_detection_threshold_percent) / 100;
     
     if boolean_count >= threshold {
         CsvColumnDataType::Boolean
@@ -566,7 +763,7 @@ fn detect_column_data_type(sample_values: &[String]) -> CsvColumnDataType {
 /// 
 /// # Returns
 /// * `bool` - True if the value appears to be boolean
-fn is_boolean_value(value: &str) -> bool {
+pub fn is_boolean_value(value: &str) -> bool {
     matches!(value, "true" | "false" | "yes" | "no" | "1" | "0" | "t" | "f" | "y" | "n")
 }
 
@@ -577,7 +774,7 @@ fn is_boolean_value(value: &str) -> bool {
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<PathBuf>` - Path to metadata file or error
-fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult<PathBuf> {
+pub fn determine_metadata_file_path(csv_file_path: &Path) -> RowsAndColumnsResult<PathBuf> {
     let csv_filename_stem = csv_file_path.file_stem()
         .and_then(|stem| stem.to_str())
         .ok_or_else(|| {
@@ -603,26 +800,58 @@ fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult
 
 /// Creates or updates the metadata TOML file with column information
 /// 
+/// Derived column definitions already saved in the file are kept and written
+/// back after the column tables, since the same dataset is being saved again.
+/// 
 /// # Arguments
 /// * `metadata_file_path` - Path where metadata file should be created/updated
 /// * `column_information_list` - List of column information to store
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or error
-fn create_or_update_metadata_file(
+pub fn create_or_update_metadata_file(
+    metadata_file_path: &PathBuf,
+    column_information_list: &[CsvColumnInformation],
+) -> RowsAndColumnsResult<()> {
+    // Keep saved derived column definitions (an unreadable old file is simply replaced)
+    let derived_column_definitions = read_derived_column_definitions(metadata_file_path).unwrap_or_default();
+    write_metadata_file(metadata_file_path, column_information_list, &derived_column_definitions)
+}
+
+/// Creates the metadata TOML file for a newly written CSV file
+/// 
+/// Any metadata file already at the path belonged to whatever CSV was there
+/// before, so it is replaced completely: its derived column definitions are
+/// not carried over to the new output.
+/// 
+/// # Arguments
+/// * `metadata_file_path` - Path where metadata file should be created
+/// * `column_information_list` - List of column information to store
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+pub fn create_new_output_metadata_file(
     metadata_file_path: &PathBuf,
     column_information_list: &[CsvColumnInformation],
+) -> RowsAndColumnsResult<()> {
+    write_metadata_file(metadata_file_path, column_information_list, &[])
+}
+
+/// Writes the column tables, then the given derived column definitions
+fn write_metadata_file(
+    metadata_file_path: &PathBuf,
+    column_information_list: &[CsvColumnInformation],
+    derived_column_definitions: &[DerivedColumnDefinition],
 ) -> RowsAndColumnsResult<()> {
     // Prepare parent directories if needed
-    if let Some(parent_dir) = metadata_file_path.parent() {
-        if !parent_dir.exists() {
-            std::fs::create_dir_all(parent_dir).map_err(|io_error| {
-                create_file_system_error(
-                    &format!("Failed to create metadata file parent directory: {}", parent_dir.display()),
-                    io_error
-                )
-            })?;
-        }
+    if let Some(parent_dir) = metadata_file_path.parent()
+        && !parent_dir.exists() {
+        std::fs::create_dir_all(parent_dir).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to create metadata file parent directory: {}", parent_dir.display()),
+                io_error
+            )
+        })?;
     }
     
     // Create TOML content
@@ -631,19 +860,20 @@ fn create_or_update_metadata_file(
     toml_content.push_str("# Generated by rows_and_columns\n\n");
     
     toml_content.push_str(&format!("total_columns = {}\n", column_information_list.len()));
-    toml_content.push_str("\n");
+    toml_content.push('\n');
     
     // Add column information
     for column_info in column_information_list {
         let column_section = format!("column_{}", column_info.column_index + 1);
         toml_content.push_str(&format!("[{}]\n", column_section));
-        toml_content.push_str(&format!("name = \"{}\"\n", column_info.column_name));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_string_value(&column_info.column_name)));
         toml_content.push_str(&format!("data_type = \"{}\"\n", column_info.detected_data_type.to_toml_string()));
         toml_content.push_str(&format!("column_index = {}\n", column_info.column_index));
         toml_content.push_str(&format!("non_empty_values = {}\n", column_info.non_empty_value_count));
         toml_content.push_str(&format!("empty_values = {}\n", column_info.empty_value_count));
-        toml_content.push_str("\n");
+        toml_content.push('\n');
     }
+    toml_content.push_str(&format_derived_column_definitions_toml(derived_column_definitions));
     
     // Write the file
     std::fs::write(metadata_file_path, toml_content)
@@ -657,6 +887,131 @@ fn create_or_update_metadata_file(
     Ok(())
 }
 
+/// Writes a derived CSV file one row at a time, then its metadata
+/// 
+/// Used by operations that produce a new CSV from streamed rows (filter,
+/// sort, join). Fields are written unchanged and joined with commas, matching
+/// what `parse_csv_line_into_fields` reads back. Empty cells are counted per
+/// column so the metadata is exact without a second pass.
+pub struct StreamingCsvExportWriter {
+    /// Buffered output file
+    output_writer: BufWriter<File>,
+    /// Output path, for error messages and the metadata location
+    output_csv_path: PathBuf,
+    /// Column names in column order
+    column_names: Vec<String>,
+    /// Column data types recorded in the metadata
+    column_data_types: Vec<CsvColumnDataType>,
+    /// Rows written so far
+    written_row_count: usize,
+    /// Empty (or missing) cells per column so far
+    empty_value_counts: Vec<usize>,
+}
+
+impl StreamingCsvExportWriter {
+    /// Creates the output file (and its directory) and writes the header row
+    /// 
+    /// # Arguments
+    /// * `output_csv_path` - Where to write the CSV file
+    /// * `column_names` - Header names in column order
+    /// * `column_data_types` - Types to record in the metadata, in column order
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<StreamingCsvExportWriter>` - The open writer
+    /// 
+    /// # Errors
+    /// * `FileSystemError` - The directory or file could not be created
+    pub fn create(
+        output_csv_path: &Path,
+        column_names: &[String],
+        column_data_types: &[CsvColumnDataType],
+    ) -> RowsAndColumnsResult<StreamingCsvExportWriter> {
+        if let Some(parent_directory) = output_csv_path.parent()
+            && !parent_directory.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent_directory).map_err(|io_error| {
+                create_file_system_error(
+                    &format!("Failed to create export directory: {}", parent_directory.display()),
+                    io_error
+                )
+            })?;
+        }
+        
+        let output_file = File::create(output_csv_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to create CSV file: {}", output_csv_path.display()), io_error)
+        })?;
+        
+        let mut export_writer = StreamingCsvExportWriter {
+            output_writer: BufWriter::new(output_file),
+            output_csv_path: output_csv_path.to_path_buf(),
+            column_names: column_names.to_vec(),
+            column_data_types: column_data_types.to_vec(),
+            written_row_count: 0,
+            empty_value_counts: vec![0; column_names.len()],
+        };
+        export_writer.write_line(&column_names.join(","))?;
+        
+        Ok(export_writer)
+    }
+    
+    /// Writes one line of text
+    fn write_line(&mut self, line_text: &str) -> RowsAndColumnsResult<()> {
+        writeln!(self.output_writer, "{}", line_text).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to write CSV file: {}", self.output_csv_path.display()), io_error)
+        })
+    }
+    
+    /// Writes one data row
+    /// 
+    /// # Arguments
+    /// * `field_values` - The row's values in column order
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or a write error
+    pub fn write_row(&mut self, field_values: &[String]) -> RowsAndColumnsResult<()> {
+        self.write_line(&field_values.join(","))?;
+        self.written_row_count += 1;
+        for (column_index, empty_value_count) in self.empty_value_counts.iter_mut().enumerate() {
+            if field_values.get(column_index).is_none_or(|field_value| field_value.is_empty()) {
+                *empty_value_count += 1;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Flushes the file and writes its `.csv_metadata.toml`
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<(usize, PathBuf)>` - Rows written and the metadata file path
+    /// 
+    /// # Errors
+    /// * `FileSystemError` - The CSV or metadata file could not be written
+    pub fn finish(mut self) -> RowsAndColumnsResult<(usize, PathBuf)> {
+        self.output_writer.flush().map_err(|io_error| {
+            create_file_system_error(&format!("Failed to write CSV file: {}", self.output_csv_path.display()), io_error)
+        })?;
+        
+        let column_information_list: Vec<CsvColumnInformation> = self.column_names
+            .iter()
+            .zip(&self.column_data_types)
+            .zip(&self.empty_value_counts)
+            .enumerate()
+            .map(|(column_index, ((column_name, column_data_type), &empty_value_count))| CsvColumnInformation {
+                column_index,
+                column_name: column_name.clone(),
+                detected_data_type: column_data_type.clone(),
+                non_empty_value_count: self.written_row_count - empty_value_count,
+                empty_value_count,
+                sample_values: Vec::new(),
+            })
+            .collect();
+        
+        let metadata_file_path = determine_metadata_file_path(&self.output_csv_path)?;
+        create_new_output_metadata_file(&metadata_file_path, &column_information_list)?;
+        
+        Ok((self.written_row_count, metadata_file_path))
+    }
+}
+
 /// Enhanced statistical information for numerical columns
 /// 
 /// This structure contains comprehensive statistical measures similar to
@@ -772,7 +1127,7 @@ pub fn perform_enhanced_statistical_analysis(
     csv_file_path: &PathBuf,
     basic_analysis_results: &CsvAnalysisResults,
 ) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
-    println!("📊 Performing enhanced statistical analysis...");
+    print_progress_line("📊 Performing enhanced statistical analysis...");
     
     let mut enhanced_column_info_list = Vec::new();
     
@@ -786,37 +1141,52 @@ pub fn perform_enhanced_statistical_analysis(
     // Analyze each column with enhanced statistics
     for basic_column_info in &basic_analysis_results.column_information_list {
         let column_values = &all_column_values[basic_column_info.column_index];
-        
-        // Determine field type (categorical vs continuous)
-        let field_type = determine_field_type(&basic_column_info.detected_data_type);
-        
-        // Generate appropriate statistics based on field type
-        let (numerical_statistics, categorical_statistics) = match field_type {
-            CsvFieldType::Continuous => {
-                let numerical_stats = calculate_numerical_statistics(column_values)?;
-                (Some(numerical_stats), None)
-            }
-            CsvFieldType::Categorical => {
-                let categorical_stats = calculate_categorical_statistics(column_values)?;
-                (None, Some(categorical_stats))
-            }
-        };
-        
-        let enhanced_column_info = EnhancedCsvColumnInformation {
-            basic_info: basic_column_info.clone(),
-            field_type,
-            numerical_statistics,
-            categorical_statistics,
-        };
-        
-        enhanced_column_info_list.push(enhanced_column_info);
+        enhanced_column_info_list.push(build_enhanced_column_information(basic_column_info, column_values)?);
     }
     
-    println!("  ✓ Enhanced statistical analysis complete");
+    print_progress_line("  ✓ Enhanced statistical analysis complete");
     
     Ok(enhanced_column_info_list)
 }
 
+/// Computes the enhanced statistics of one column from all of its values
+/// 
+/// Also used for values that do not come from a CSV column directly, such
+/// as derived columns computed while streaming.
+/// 
+/// # Arguments
+/// * `basic_column_info` - Name, index and detected type of the column
+/// * `column_values` - Every value of the column (trimmed, empty for missing)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<EnhancedCsvColumnInformation>` - Statistics suited to the column type
+pub fn build_enhanced_column_information(
+    basic_column_info: &CsvColumnInformation,
+    column_values: &[String],
+) -> RowsAndColumnsResult<EnhancedCsvColumnInformation> {
+    // Determine field type (categorical vs continuous)
+    let field_type = determine_field_type(&basic_column_info.detected_data_type);
+    
+    // Generate appropriate statistics based on field type
+    let (numerical_statistics, categorical_statistics) = match field_type {
+        CsvFieldType::Continuous => {
+            let numerical_stats = calculate_numerical_statistics(column_values)?;
+            (Some(numerical_stats), None)
+        }
+        CsvFieldType::Categorical => {
+            let categorical_stats = calculate_categorical_statistics(column_values)?;
+            (None, Some(categorical_stats))
+        }
+    };
+    
+    Ok(EnhancedCsvColumnInformation {
+        basic_info: basic_column_info.clone(),
+        field_type,
+        numerical_statistics,
+        categorical_statistics,
+    })
+}
+
 /// Collects all values from CSV columns for comprehensive statistical analysis
 /// 
 /// This function reads through the entire CSV file to collect all values
@@ -1029,8 +1399,12 @@ fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsR
         })
         .collect();
     
-    // Sort by count (descending)
-    value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
+    // Sort by count (descending); ties in value order so the listing is stable
+    value_frequencies.sort_by(|first_frequency, second_frequency| {
+        second_frequency.count
+            .cmp(&first_frequency.count)
+            .then_with(|| first_frequency.value.cmp(&second_frequency.value))
+    });
     
     // Find mode (most common value)
     let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
@@ -1069,17 +1443,30 @@ fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsR
 pub fn display_enhanced_csv_analysis_results(
     enhanced_analysis_results: &[EnhancedCsvColumnInformation]
 ) -> RowsAndColumnsResult<()> {
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  Enhanced CSV Analysis Results");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
+    print_output_text(&format_enhanced_csv_analysis_results(enhanced_analysis_results));
+    Ok(())
+}
+
+/// Formats enhanced CSV analysis results as the text `describe` prints
+/// 
+/// # Arguments
+/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// 
+/// # Returns
+/// * `String` - The pandas-style description, one section per column
+pub fn format_enhanced_csv_analysis_results(enhanced_analysis_results: &[EnhancedCsvColumnInformation]) -> String {
+    let mut description_text = String::new();
+    description_text.push_str("═══════════════════════════════════════════════════════════════\n");
+    description_text.push_str("  Enhanced CSV Analysis Results\n");
+    description_text.push_str("═══════════════════════════════════════════════════════════════\n");
+    description_text.push('\n');
     
     for (display_index, enhanced_column_info) in enhanced_analysis_results.iter().enumerate() {
         let display_number = display_index + 1;
         let basic_info = &enhanced_column_info.basic_info;
         
-        // Display column header
-        println!("{}. {} ({} - {})", 
+        // Column header
+        description_text.push_str(&format!("{}. {} ({} - {})\n", 
             display_number,
             basic_info.column_name,
             basic_info.detected_data_type.to_toml_string(),
@@ -1087,82 +1474,86 @@ pub fn display_enhanced_csv_analysis_results(
                 CsvFieldType::Categorical => "categorical",
                 CsvFieldType::Continuous => "continuous",
             }
-        );
+        ));
         
-        // Display appropriate statistics based on field type
+        // Statistics appropriate to the field type
         match enhanced_column_info.field_type {
             CsvFieldType::Continuous => {
                 if let Some(numerical_stats) = &enhanced_column_info.numerical_statistics {
-                    display_numerical_statistics(numerical_stats);
+                    append_numerical_statistics_text(&mut description_text, numerical_stats);
                 }
             }
             CsvFieldType::Categorical => {
                 if let Some(categorical_stats) = &enhanced_column_info.categorical_statistics {
-                    display_categorical_statistics(categorical_stats);
+                    append_categorical_statistics_text(&mut description_text, categorical_stats);
                 }
             }
         }
         
-        println!();
+        description_text.push('\n');
     }
     
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
-    
-    Ok(())
+    description_text.push_str("═══════════════════════════════════════════════════════════════\n");
+    description_text.push('\n');
+    description_text
 }
 
-/// Displays numerical statistics in pandas-style format
+/// Appends numerical statistics in pandas-style format
 /// 
 /// # Arguments
-/// * `numerical_stats` - The numerical statistics to display
-fn display_numerical_statistics(numerical_stats: &NumericalColumnStatistics) {
-    println!("   Field-type: continuous");
-    println!("   min: {:.3}    q1: {:.3}    q2: {:.3}    q3: {:.3}    max: {:.3}", 
+/// * `description_text` - Text being built
+/// * `numerical_stats` - The numerical statistics to describe
+fn append_numerical_statistics_text(description_text: &mut String, numerical_stats: &NumericalColumnStatistics) {
+    description_text.push_str("   Field-type: continuous\n");
+    description_text.push_str(&format!("   min: {:.3}    q1: {:.3}    q2: {:.3}    q3: {:.3}    max: {:.3}\n", 
         numerical_stats.min_value,
         numerical_stats.q1_value,
         numerical_stats.q2_median_value,
         numerical_stats.q3_value,
         numerical_stats.max_value
-    );
-    println!("   mean: {:.3}    stdev: {:.3}", 
+    ));
+    description_text.push_str(&format!("   mean: {:.3}    stdev: {:.3}\n", 
         numerical_stats.mean_value,
         numerical_stats.standard_deviation
-    );
-    println!("   %missing: {:.1}%", numerical_stats.missing_percentage);
+    ));
+    description_text.push_str(&format!("   %missing: {:.1}%\n", numerical_stats.missing_percentage));
 }
 
-/// Displays categorical statistics with value distribution
+/// Appends categorical statistics with value distribution
 /// 
 /// # Arguments
-/// * `categorical_stats` - The categorical statistics to display
-fn display_categorical_statistics(categorical_stats: &CategoricalColumnStatistics) {
-    println!("   Field-type: categorical");
-    println!("   Unique values: {}", categorical_stats.unique_value_count);
-    println!("   %missing: {:.1}%", categorical_stats.missing_percentage);
+/// * `description_text` - Text being built
+/// * `categorical_stats` - The categorical statistics to describe
+fn append_categorical_statistics_text(description_text: &mut String, categorical_stats: &CategoricalColumnStatistics) {
+    description_text.push_str("   Field-type: categorical\n");
+    description_text.push_str(&format!("   Unique values: {}\n", categorical_stats.unique_value_count));
+    description_text.push_str(&format!("   %missing: {:.1}%\n", categorical_stats.missing_percentage));
     
     if let Some(mode_value) = &categorical_stats.mode_value {
-        println!("   Mode: {} ({:.1}%)", mode_value, categorical_stats.mode_percentage);
+        description_text.push_str(&format!("   Mode: {} ({:.1}%)\n", mode_value, categorical_stats.mode_percentage));
     }
     
-    println!("   Value Distribution:");
+    description_text.push_str("   Value Distribution:\n");
     
-    // Show top values (limit to 5 for display)
-    let display_limit = 5.min(categorical_stats.value_frequencies.len());
+    // Show the top values (the configured top_value_count, 5 by default)
+    let display_limit = application_configuration()
+        .top_value_display_count
+        .min(categorical_stats.value_frequencies.len());
     for value_freq in categorical_stats.value_frequencies.iter().take(display_limit) {
-        println!("     {}: {:.1}% ({} values)", 
+        description_text.push_str(&format!("     {}: {:.1}% ({} values)\n", 
             value_freq.value, 
             value_freq.percentage, 
             value_freq.count
-        );
+        ));
     }
     
     // Show summary if there are more values
     if categorical_stats.value_frequencies.len() > display_limit {
         let remaining_count = categorical_stats.value_frequencies.len() - display_limit;
-        println!("     ... (showing top {} of {} unique values)", 
+        description_text.push_str(&format!("     ... (showing top {} of {} unique values, {} more not shown)\n", 
             display_limit, 
-            categorical_stats.unique_value_count
-        );
+            categorical_stats.unique_value_count,
+            remaining_count
+        ));
     }
 }
\ No newline at end of file
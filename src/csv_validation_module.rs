@@ -0,0 +1,405 @@
+// src/csv_validation_module.rs
+
+/// Checks a CSV file against the column types declared in its metadata
+///
+/// `rows_and_columns validate cats.csv` reads every row and reports values
+/// that do not parse as their column's declared `data_type` (for example
+/// `"three"` in an integer column) and rows whose field count differs from
+/// the column count. The declared types come from the `.csv_metadata.toml`,
+/// which a user may have edited, so they can disagree with the data.
+///
+/// # Core Responsibilities
+/// - Stream the rows once, counting checked and invalid values per column
+/// - Keep the first few invalid values per column with their line numbers
+/// - Count ragged rows (too few or too many fields)
+/// - Present the report as text or as a JSON document
+///
+/// # Design Philosophy
+/// - No pre-loading: one pass, only counters and a few examples in memory
+/// - Empty values are missing, not invalid (the statistics treat them the same way)
+/// - The checks use the same rules as type detection (`is_boolean_value`, Rust
+///   number parsing), so a freshly detected file always validates
+use std::path::{Path, PathBuf};
+
+// Import the shared row streaming and type rules from the CSV processor
+use super::csv_processor_module::{CsvColumnDataType, CsvRowStreamControl, is_boolean_value, stream_csv_data_rows};
+
+// Import the metadata column description
+use super::metadata_manager_module::CsvMetadataColumn;
+
+// Import the JSON model for machine-readable reports
+use super::json_output_module::JsonValue;
+
+// Import the pipe-safe stdout writer and result lines that drop their emoji in headless mode
+use super::console_output_module::{print_output_line, print_result_line};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::RowsAndColumnsResult;
+
+/// How many invalid values (and ragged rows) are kept as examples
+pub const MAXIMUM_VALIDATION_EXAMPLE_COUNT: usize = 5;
+
+/// A value that does not match its column's declared type
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidValueExample {
+    /// 1-based line number in the file (the header is line 1 when present)
+    pub csv_line_number: usize,
+
+    /// The value as read (trimmed)
+    pub invalid_value: String,
+}
+
+/// Validation results for one column
+#[derive(Debug, Clone)]
+pub struct ColumnValidationReport {
+    /// 0-based column position in the CSV
+    pub column_position: usize,
+
+    /// Column name from the metadata
+    pub column_name: String,
+
+    /// Type the metadata declares for the column
+    pub declared_data_type: CsvColumnDataType,
+
+    /// Non-empty values checked
+    pub checked_value_count: usize,
+
+    /// Values that do not parse as the declared type
+    pub invalid_value_count: usize,
+
+    /// The first `MAXIMUM_VALIDATION_EXAMPLE_COUNT` invalid values
+    pub invalid_value_examples: Vec<InvalidValueExample>,
+}
+
+/// Validation results for a whole file
+#[derive(Debug, Clone)]
+pub struct CsvValidationReport {
+    /// The file that was checked
+    pub csv_file_path: PathBuf,
+
+    /// Fields every row should have (the declared column count)
+    pub expected_field_count: usize,
+
+    /// Data rows read
+    pub scanned_row_count: usize,
+
+    /// Rows with a different number of fields
+    pub ragged_row_count: usize,
+
+    /// The first ragged rows as (line number, field count)
+    pub ragged_row_examples: Vec<(usize, usize)>,
+
+    /// One report per checked column, in the order requested
+    pub column_reports: Vec<ColumnValidationReport>,
+}
+
+impl CsvValidationReport {
+    /// Total invalid values across the checked columns
+    ///
+    /// # Returns
+    /// * `usize` - Sum of the per-column invalid counts
+    pub fn total_invalid_value_count(&self) -> usize {
+        self.column_reports.iter().map(|column_report| column_report.invalid_value_count).sum()
+    }
+
+    /// Whether the file matches its declared types and shape
+    ///
+    /// # Returns
+    /// * `bool` - True when there are no invalid values and no ragged rows
+    pub fn is_valid(&self) -> bool {
+        self.total_invalid_value_count() == 0 && self.ragged_row_count == 0
+    }
+}
+
+/// Checks whether a value parses as a column data type
+///
+/// # Arguments
+/// * `field_value` - The trimmed value (empty values are treated as missing and accepted)
+/// * `declared_data_type` - The type to check against
+///
+/// # Returns
+/// * `bool` - True when the value is missing or parses as the type
+pub fn value_matches_column_data_type(field_value: &str, declared_data_type: &CsvColumnDataType) -> bool {
+    if field_value.is_empty() {
+        return true;
+    }
+    match declared_data_type {
+        CsvColumnDataType::Boolean => is_boolean_value(&field_value.to_lowercase()),
+        CsvColumnDataType::Integer => field_value.parse::<i64>().is_ok(),
+        CsvColumnDataType::Float => field_value.parse::<f64>().is_ok(),
+        CsvColumnDataType::String => true,
+    }
+}
+
+/// Streams a CSV file and checks the selected columns against their declared types
+///
+/// # Arguments
+/// * `csv_file_path` - The CSV file to check
+/// * `has_header_row` - Whether the first line is a header (affects line numbers)
+/// * `declared_columns` - All columns from the metadata, in index order
+/// * `selected_column_positions` - 0-based positions of the columns to check
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvValidationReport>` - Counts and examples (an
+///   invalid file is a successful validation with findings)
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read
+pub fn validate_csv_file_against_column_types(
+    csv_file_path: &Path,
+    has_header_row: bool,
+    declared_columns: &[CsvMetadataColumn],
+    selected_column_positions: &[usize],
+) -> RowsAndColumnsResult<CsvValidationReport> {
+    // Step 1: One empty report per selected column
+    let mut column_reports: Vec<ColumnValidationReport> = selected_column_positions
+        .iter()
+        .filter_map(|column_position| declared_columns.get(*column_position))
+        .map(|declared_column| ColumnValidationReport {
+            column_position: declared_column.column_index,
+            column_name: declared_column.column_name.clone(),
+            declared_data_type: declared_column.column_data_type.clone(),
+            checked_value_count: 0,
+            invalid_value_count: 0,
+            invalid_value_examples: Vec::new(),
+        })
+        .collect();
+    let expected_field_count = declared_columns.len();
+    let first_data_line_number = if has_header_row { 2 } else { 1 };
+    let mut ragged_row_count = 0;
+    let mut ragged_row_examples = Vec::new();
+
+    // Step 2: Check each row's shape and each selected value
+    let scanned_row_count = stream_csv_data_rows(csv_file_path, has_header_row, |data_row_index, field_values| {
+        let csv_line_number = data_row_index + first_data_line_number;
+
+        if field_values.len() != expected_field_count {
+            ragged_row_count += 1;
+            if ragged_row_examples.len() < MAXIMUM_VALIDATION_EXAMPLE_COUNT {
+                ragged_row_examples.push((csv_line_number, field_values.len()));
+            }
+        }
+
+        for column_report in column_reports.iter_mut() {
+            let field_value = field_values.get(column_report.column_position).map(String::as_str).unwrap_or("");
+            if field_value.is_empty() {
+                continue;
+            }
+            column_report.checked_value_count += 1;
+            if !value_matches_column_data_type(field_value, &column_report.declared_data_type) {
+                column_report.invalid_value_count += 1;
+                if column_report.invalid_value_examples.len() < MAXIMUM_VALIDATION_EXAMPLE_COUNT {
+                    column_report.invalid_value_examples.push(InvalidValueExample {
+                        csv_line_number,
+                        invalid_value: field_value.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    Ok(CsvValidationReport {
+        csv_file_path: csv_file_path.to_path_buf(),
+        expected_field_count,
+        scanned_row_count,
+        ragged_row_count,
+        ragged_row_examples,
+        column_reports,
+    })
+}
+
+/// Prints a validation report for the terminal
+///
+/// # Arguments
+/// * `validation_report` - The report to show
+pub fn display_csv_validation_report(validation_report: &CsvValidationReport) {
+    print_output_line(&format!("Validation of {}", validation_report.csv_file_path.display()));
+    print_output_line(&format!("  Rows checked: {}   Columns checked: {}", validation_report.scanned_row_count, validation_report.column_reports.len()));
+    print_output_line("");
+
+    for column_report in &validation_report.column_reports {
+        let status_mark = if column_report.invalid_value_count == 0 { "✓" } else { "✗" };
+        print_result_line(&format!(
+            "  {} {} ({}): {} of {} values invalid",
+            status_mark,
+            column_report.column_name,
+            column_report.declared_data_type.to_toml_string(),
+            column_report.invalid_value_count,
+            column_report.checked_value_count
+        ));
+        for invalid_example in &column_report.invalid_value_examples {
+            print_output_line(&format!("      line {}: \"{}\"", invalid_example.csv_line_number, invalid_example.invalid_value));
+        }
+        if column_report.invalid_value_count > column_report.invalid_value_examples.len() {
+            print_output_line(&format!("      ... and {} more", column_report.invalid_value_count - column_report.invalid_value_examples.len()));
+        }
+    }
+
+    if validation_report.ragged_row_count > 0 {
+        print_output_line("");
+        print_result_line(&format!(
+            "  ✗ {} rows do not have {} fields:",
+            validation_report.ragged_row_count,
+            validation_report.expected_field_count
+        ));
+        for (csv_line_number, field_count) in &validation_report.ragged_row_examples {
+            print_output_line(&format!("      line {}: {} fields", csv_line_number, field_count));
+        }
+    }
+    print_output_line("");
+
+    if validation_report.is_valid() {
+        print_result_line("✅ All values match their declared types.");
+    } else {
+        print_result_line(&format!(
+            "❌ {} invalid values, {} ragged rows.",
+            validation_report.total_invalid_value_count(),
+            validation_report.ragged_row_count
+        ));
+    }
+}
+
+/// Converts a validation report to JSON
+///
+/// # Arguments
+/// * `validation_report` - The report to convert
+///
+/// # Returns
+/// * `JsonValue` - `{ "file", "valid", "scanned_rows", "ragged_rows", "ragged_row_examples", "columns" }`
+pub fn csv_validation_report_to_json(validation_report: &CsvValidationReport) -> JsonValue {
+    let column_values = validation_report.column_reports
+        .iter()
+        .map(|column_report| JsonValue::object(vec![
+            ("index", JsonValue::from_count(column_report.column_position)),
+            ("name", JsonValue::Text(column_report.column_name.clone())),
+            ("data_type", JsonValue::Text(column_report.declared_data_type.to_toml_string().to_string())),
+            ("checked_values", JsonValue::from_count(column_report.checked_value_count)),
+            ("invalid_values", JsonValue::from_count(column_report.invalid_value_count)),
+            ("invalid_value_examples", JsonValue::Array(
+                column_report.invalid_value_examples
+                    .iter()
+                    .map(|invalid_example| JsonValue::object(vec![
+                        ("line", JsonValue::from_count(invalid_example.csv_line_number)),
+                        ("value", JsonValue::Text(invalid_example.invalid_value.clone())),
+                    ]))
+                    .collect()
+            )),
+        ]))
+        .collect();
+
+    JsonValue::object(vec![
+        ("file", JsonValue::Text(validation_report.csv_file_path.display().to_string())),
+        ("valid", JsonValue::Boolean(validation_report.is_valid())),
+        ("scanned_rows", JsonValue::from_count(validation_report.scanned_row_count)),
+        ("expected_fields", JsonValue::from_count(validation_report.expected_field_count)),
+        ("ragged_rows", JsonValue::from_count(validation_report.ragged_row_count)),
+        ("ragged_row_examples", JsonValue::Array(
+            validation_report.ragged_row_examples
+                .iter()
+                .map(|(csv_line_number, field_count)| JsonValue::object(vec![
+                    ("line", JsonValue::from_count(*csv_line_number)),
+                    ("fields", JsonValue::from_count(*field_count)),
+                ]))
+                .collect()
+        )),
+        ("columns", JsonValue::Array(column_values)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds metadata columns from (name, type) pairs
+    fn create_declared_columns(column_specifications: &[(&str, CsvColumnDataType)]) -> Vec<CsvMetadataColumn> {
+        column_specifications
+            .iter()
+            .enumerate()
+            .map(|(column_index, (column_name, column_data_type))| CsvMetadataColumn {
+                column_index,
+                column_name: column_name.to_string(),
+                column_data_type: column_data_type.clone(),
+                store_directory_name: None,
+            })
+            .collect()
+    }
+
+    /// Writes a CSV into its own temporary directory
+    fn write_validation_test_csv(test_name: &str, csv_text: &str) -> PathBuf {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_validation_{}_{}", test_name, std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("cats.csv");
+        std::fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+        csv_file_path
+    }
+
+    /// Test the per-type rules, with empty values counting as missing
+    #[test]
+    fn test_value_matches_column_data_type() {
+        assert!(value_matches_column_data_type("TRUE", &CsvColumnDataType::Boolean));
+        assert!(value_matches_column_data_type("n", &CsvColumnDataType::Boolean));
+        assert!(!value_matches_column_data_type("maybe", &CsvColumnDataType::Boolean));
+        assert!(value_matches_column_data_type("-42", &CsvColumnDataType::Integer));
+        assert!(!value_matches_column_data_type("4.5", &CsvColumnDataType::Integer));
+        assert!(value_matches_column_data_type("4.5", &CsvColumnDataType::Float));
+        assert!(!value_matches_column_data_type("four", &CsvColumnDataType::Float));
+        assert!(value_matches_column_data_type("anything", &CsvColumnDataType::String));
+        assert!(value_matches_column_data_type("", &CsvColumnDataType::Integer));
+    }
+
+    /// Test invalid values are counted with line numbers and ragged rows are found
+    #[test]
+    fn test_validation_finds_invalid_values_and_ragged_rows() {
+        let csv_file_path = write_validation_test_csv(
+            "findings",
+            "name,age,is_indoor\nWhiskers,3,true\nMittens,three,yes\nShadow,,maybe\nLuna,4\n"
+        );
+        let declared_columns = create_declared_columns(&[
+            ("name", CsvColumnDataType::String),
+            ("age", CsvColumnDataType::Integer),
+            ("is_indoor", CsvColumnDataType::Boolean),
+        ]);
+
+        let validation_report = validate_csv_file_against_column_types(&csv_file_path, true, &declared_columns, &[1, 2])
+            .expect("validation should run");
+
+        assert_eq!(validation_report.scanned_row_count, 4);
+        assert!(!validation_report.is_valid());
+        assert_eq!(validation_report.ragged_row_count, 1);
+        assert_eq!(validation_report.ragged_row_examples, vec![(5, 2)]);
+
+        let age_report = &validation_report.column_reports[0];
+        assert_eq!(age_report.column_name, "age");
+        assert_eq!(age_report.checked_value_count, 3);
+        assert_eq!(age_report.invalid_value_examples, vec![InvalidValueExample {
+            csv_line_number: 3,
+            invalid_value: "three".to_string(),
+        }]);
+        assert_eq!(validation_report.column_reports[1].invalid_value_count, 1);
+        assert_eq!(validation_report.total_invalid_value_count(), 2);
+
+        let _ = std::fs::remove_dir_all(csv_file_path.parent().expect("temp dir"));
+    }
+
+    /// Test a clean file validates and the JSON report says so
+    #[test]
+    fn test_clean_file_is_valid_in_json_report() {
+        let csv_file_path = write_validation_test_csv("clean", "1,2.5\n2,3\n");
+        let declared_columns = create_declared_columns(&[
+            ("id", CsvColumnDataType::Integer),
+            ("weight", CsvColumnDataType::Float),
+        ]);
+
+        let validation_report = validate_csv_file_against_column_types(&csv_file_path, false, &declared_columns, &[0, 1])
+            .expect("validation should run");
+        assert!(validation_report.is_valid());
+
+        let report_json_text = csv_validation_report_to_json(&validation_report).to_json_text(false);
+        assert!(report_json_text.contains("\"valid\":true"));
+        assert!(report_json_text.contains("\"scanned_rows\":2"));
+
+        let _ = std::fs::remove_dir_all(csv_file_path.parent().expect("temp dir"));
+    }
+}
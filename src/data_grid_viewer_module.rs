@@ -0,0 +1,1180 @@
+// src/data_grid_viewer_module.rs
+
+/// Scrollable data grid viewer for rows_and_columns
+///
+/// A paged table view of the actual rows, in the spirit of the Lines tool:
+/// only the window of rows visible on screen is read, either from the CSV file
+/// or from a dataset in the directory store.
+///
+/// # Layout
+/// ```text
+/// rows_and_columns · Data Grid · people.csv · 1000 rows × 4 columns
+///        id │ name        age  city
+/// ───────━━━┼───────────────────────────
+/// >     1  1 │ Ada          36  London
+///       2  2 │ Bob          41  Paris
+/// Row 1/1000 │ id (integer): 1
+/// j/k rows  h/l columns  : jump  / search  n next  - hide  + unhide  p pin  q quit
+/// ```
+///
+/// # Keys
+/// - `↑`/`↓` or `k`/`j`: previous/next row; `PgUp`/`PgDn`: one page
+/// - `←`/`→` or `h`/`l`: previous/next column (scrolls horizontally)
+/// - `g`/`G`: first/last row; `Home`/`End`: first/last column
+/// - `:`: jump to a row number; `/`: incremental search; `n`: next match
+/// - `-`: hide the current column; `+`: show all columns; `p`: pin/unpin column
+/// - `q`/`Esc`/`Ctrl-C`: leave the viewer
+///
+/// # Design Philosophy
+/// - No-load reading: CSV files get a sparse byte-offset index (one offset per
+///   1024 rows), so any window can be read by seeking instead of scanning
+/// - Columns are aligned by terminal display width, so wide characters and
+///   combining marks do not break the grid
+/// - Graceful degradation: without a TTY, the first page is printed as a table
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+
+// Import CSV structures and field parsing
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    parse_csv_line_into_fields,
+};
+
+// Import the directory store as an alternative row source
+use super::directory_store_module::DirectoryStoreDataset;
+
+// Import terminal control for full-screen mode
+use super::terminal_control_module::{
+    KeyPress,
+    TerminalRawModeSession,
+    TerminalSize,
+    TerminalSizeTracker,
+    calculate_text_display_width,
+    draw_full_screen_frame,
+    fit_text_to_display_width,
+    is_interactive_terminal_available,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+/// Number of data rows between two entries of the CSV byte-offset index
+const CSV_ROW_OFFSET_INDEX_INTERVAL: usize = 1024;
+
+/// Column width limits in terminal cells
+const MINIMUM_GRID_COLUMN_WIDTH: usize = 3;
+const MAXIMUM_GRID_COLUMN_WIDTH: usize = 24;
+
+/// Rows read per step while searching
+const SEARCH_CHUNK_ROW_COUNT: usize = 512;
+
+/// Rows printed by the line-based (non-TTY) viewer
+const LINE_MODE_PREVIEW_ROW_COUNT: usize = 20;
+
+/// Screen lines used by the title, header and separator, and by the status and help bars
+const GRID_TOP_LINE_COUNT: usize = 3;
+const GRID_BOTTOM_LINE_COUNT: usize = 2;
+
+/// Smallest terminal the grid can use
+const MINIMUM_GRID_COLUMN_COUNT: usize = 30;
+const MINIMUM_GRID_ROW_COUNT: usize = 7;
+
+/// Sparse index of byte offsets into a CSV file
+///
+/// Entry `k` is the byte offset where data row `k * CSV_ROW_OFFSET_INDEX_INTERVAL`
+/// starts. Memory use is one `u64` per 1024 rows.
+#[derive(Debug, Clone)]
+pub struct CsvRowOffsetIndex {
+    /// Byte offsets of every indexed data row
+    checkpoint_byte_offsets: Vec<u64>,
+
+    /// Number of data rows in the file
+    total_data_row_count: usize,
+}
+
+/// Where the grid reads its rows from
+#[derive(Debug)]
+pub enum GridRowSource {
+    /// Rows are read directly from a CSV file via the offset index
+    CsvFile {
+        /// Path to the CSV file
+        csv_file_path: PathBuf,
+
+        /// Column names in column order
+        column_names: Vec<String>,
+
+        /// Column data types in column order
+        column_data_types: Vec<CsvColumnDataType>,
+
+        /// Sparse row offset index
+        row_offset_index: CsvRowOffsetIndex,
+    },
+
+    /// Rows are read cell by cell from a directory store dataset
+    DirectoryStore(DirectoryStoreDataset),
+}
+
+impl GridRowSource {
+    /// Creates a CSV-backed row source, building the offset index in one pass
+    ///
+    /// # Arguments
+    /// * `csv_analysis_results` - Analysis results for the CSV (path, header, columns)
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<GridRowSource>` - The row source or a file system error
+    pub fn open_csv_file(csv_analysis_results: &CsvAnalysisResults) -> RowsAndColumnsResult<GridRowSource> {
+        let row_offset_index = build_csv_row_offset_index(
+            &csv_analysis_results.csv_file_path,
+            csv_analysis_results.has_header_row,
+        )?;
+
+        Ok(GridRowSource::CsvFile {
+            csv_file_path: csv_analysis_results.csv_file_path.clone(),
+            column_names: csv_analysis_results.column_information_list
+                .iter()
+                .map(|column_info| column_info.column_name.clone())
+                .collect(),
+            column_data_types: csv_analysis_results.column_information_list
+                .iter()
+                .map(|column_info| column_info.detected_data_type.clone())
+                .collect(),
+            row_offset_index,
+        })
+    }
+
+    /// Short label for the title bar
+    fn source_label(&self) -> String {
+        match self {
+            GridRowSource::CsvFile { csv_file_path, .. } => csv_file_path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            GridRowSource::DirectoryStore(store_dataset) => format!("store: {}", store_dataset.dataset_name()),
+        }
+    }
+
+    /// Column names in column order
+    fn column_names(&self) -> &[String] {
+        match self {
+            GridRowSource::CsvFile { column_names, .. } => column_names,
+            GridRowSource::DirectoryStore(store_dataset) => &store_dataset.column_names,
+        }
+    }
+
+    /// Column data types in column order
+    fn column_data_types(&self) -> &[CsvColumnDataType] {
+        match self {
+            GridRowSource::CsvFile { column_data_types, .. } => column_data_types,
+            GridRowSource::DirectoryStore(store_dataset) => &store_dataset.column_data_types,
+        }
+    }
+
+    /// Number of data rows available
+    fn total_row_count(&self) -> usize {
+        match self {
+            GridRowSource::CsvFile { row_offset_index, .. } => row_offset_index.total_data_row_count,
+            GridRowSource::DirectoryStore(store_dataset) => store_dataset.total_row_count,
+        }
+    }
+
+    /// Reads a window of consecutive data rows
+    ///
+    /// # Arguments
+    /// * `first_row_index` - 0-based index of the first row to read
+    /// * `row_count` - Maximum number of rows to read
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<Vec<String>>>` - Rows (fewer near the end of the data)
+    pub fn read_row_window(&self, first_row_index: usize, row_count: usize) -> RowsAndColumnsResult<Vec<Vec<String>>> {
+        let last_row_index_exclusive = (first_row_index + row_count).min(self.total_row_count());
+        if first_row_index >= last_row_index_exclusive {
+            return Ok(Vec::new());
+        }
+
+        match self {
+            GridRowSource::CsvFile { csv_file_path, row_offset_index, .. } => read_csv_row_window(
+                csv_file_path,
+                row_offset_index,
+                first_row_index,
+                last_row_index_exclusive - first_row_index,
+            ),
+            GridRowSource::DirectoryStore(store_dataset) => (first_row_index..last_row_index_exclusive)
+                .map(|data_row_index| store_dataset.read_row_values(data_row_index))
+                .collect(),
+        }
+    }
+}
+
+/// Builds the sparse byte-offset index for a CSV file in a single pass
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `has_header_row` - Whether the first line is a header (not indexed)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvRowOffsetIndex>` - The index or a file system error
+pub fn build_csv_row_offset_index(csv_file_path: &PathBuf, has_header_row: bool) -> RowsAndColumnsResult<CsvRowOffsetIndex> {
+    let csv_file = File::open(csv_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to open CSV file for row indexing: {}", csv_file_path.display()),
+            io_error
+        )
+    })?;
+    let mut csv_reader = BufReader::new(csv_file);
+    let mut line_buffer = Vec::new();
+    let mut current_byte_offset: u64 = 0;
+
+    let mut read_next_line = |line_buffer: &mut Vec<u8>| -> RowsAndColumnsResult<usize> {
+        line_buffer.clear();
+        csv_reader.read_until(b'\n', line_buffer).map_err(|io_error| {
+            create_file_system_error("Failed to read CSV line while indexing rows", io_error)
+        })
+    };
+
+    if has_header_row {
+        current_byte_offset += read_next_line(&mut line_buffer)? as u64;
+    }
+
+    let mut checkpoint_byte_offsets = Vec::new();
+    let mut total_data_row_count = 0;
+
+    loop {
+        let line_byte_count = read_next_line(&mut line_buffer)?;
+        if line_byte_count == 0 {
+            break;
+        }
+        if total_data_row_count % CSV_ROW_OFFSET_INDEX_INTERVAL == 0 {
+            checkpoint_byte_offsets.push(current_byte_offset);
+        }
+        current_byte_offset += line_byte_count as u64;
+        total_data_row_count += 1;
+    }
+
+    Ok(CsvRowOffsetIndex {
+        checkpoint_byte_offsets,
+        total_data_row_count,
+    })
+}
+
+/// Reads a window of rows from a CSV file by seeking to the nearest indexed row
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `row_offset_index` - Offset index built by `build_csv_row_offset_index`
+/// * `first_row_index` - 0-based index of the first row to read
+/// * `row_count` - Number of rows to read
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<Vec<String>>>` - Rows with trimmed field values
+fn read_csv_row_window(
+    csv_file_path: &PathBuf,
+    row_offset_index: &CsvRowOffsetIndex,
+    first_row_index: usize,
+    row_count: usize,
+) -> RowsAndColumnsResult<Vec<Vec<String>>> {
+    let checkpoint_position = first_row_index / CSV_ROW_OFFSET_INDEX_INTERVAL;
+    let checkpoint_byte_offset = row_offset_index.checkpoint_byte_offsets
+        .get(checkpoint_position)
+        .copied()
+        .unwrap_or(0);
+
+    let mut csv_file = File::open(csv_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to open CSV file for reading rows: {}", csv_file_path.display()),
+            io_error
+        )
+    })?;
+    csv_file.seek(SeekFrom::Start(checkpoint_byte_offset)).map_err(|io_error| {
+        create_file_system_error("Failed to seek within CSV file", io_error)
+    })?;
+
+    let mut csv_reader = BufReader::new(csv_file);
+    let mut line_buffer = Vec::new();
+    let mut rows_to_skip = first_row_index - checkpoint_position * CSV_ROW_OFFSET_INDEX_INTERVAL;
+    let mut window_rows = Vec::with_capacity(row_count);
+
+    while window_rows.len() < row_count {
+        line_buffer.clear();
+        let line_byte_count = csv_reader.read_until(b'\n', &mut line_buffer).map_err(|io_error| {
+            create_file_system_error("Failed to read CSV line for the data grid", io_error)
+        })?;
+        if line_byte_count == 0 {
+            break;
+        }
+        if rows_to_skip > 0 {
+            rows_to_skip -= 1;
+            continue;
+        }
+
+        let csv_line = String::from_utf8_lossy(&line_buffer);
+        let csv_line = csv_line.trim_end_matches(['\n', '\r']);
+        window_rows.push(
+            parse_csv_line_into_fields(csv_line)
+                .iter()
+                .map(|field_value| field_value.trim().to_string())
+                .collect()
+        );
+    }
+
+    Ok(window_rows)
+}
+
+/// Text entry modes of the status bar
+#[derive(Debug, Clone, PartialEq)]
+enum GridInputMode {
+    /// Normal navigation
+    Browsing,
+
+    /// Typing a row number after `:`
+    JumpToRowPrompt(String),
+
+    /// Typing a search value after `/`
+    SearchPrompt(String),
+}
+
+/// Mutable state of the running grid viewer
+struct DataGridViewerState {
+    /// 0-based index of the highlighted data row
+    selected_row_index: usize,
+
+    /// 0-based index of the first row on screen
+    top_row_index: usize,
+
+    /// Index of the highlighted column
+    selected_column_position: usize,
+
+    /// First unpinned column shown after the pinned columns
+    first_scrolled_column_position: usize,
+
+    /// Per-column hidden flags
+    hidden_column_flags: Vec<bool>,
+
+    /// Per-column pinned flags
+    pinned_column_flags: Vec<bool>,
+
+    /// Current text entry mode
+    input_mode: GridInputMode,
+
+    /// Row where the current incremental search started
+    search_origin_row_index: usize,
+
+    /// Last confirmed search value (used by `n`)
+    last_search_text: String,
+
+    /// Rows currently on screen, keyed by their first row index
+    cached_row_window: Option<(usize, Vec<Vec<String>>)>,
+
+    /// One-line message shown in the status bar
+    status_message: String,
+}
+
+impl DataGridViewerState {
+    /// Creates the initial state for a source with the given column count
+    fn new(column_count: usize) -> DataGridViewerState {
+        DataGridViewerState {
+            selected_row_index: 0,
+            top_row_index: 0,
+            selected_column_position: 0,
+            first_scrolled_column_position: 0,
+            hidden_column_flags: vec![false; column_count],
+            pinned_column_flags: vec![false; column_count],
+            input_mode: GridInputMode::Browsing,
+            search_origin_row_index: 0,
+            last_search_text: String::new(),
+            cached_row_window: None,
+            status_message: String::new(),
+        }
+    }
+
+    /// Positions of all columns that are not hidden, in column order
+    fn visible_column_positions(&self) -> Vec<usize> {
+        (0..self.hidden_column_flags.len())
+            .filter(|&column_position| !self.hidden_column_flags[column_position])
+            .collect()
+    }
+}
+
+/// Launches the data grid viewer for a row source
+///
+/// Uses the full-screen interface when running in an interactive terminal and
+/// prints the first rows as a plain table otherwise.
+///
+/// # Arguments
+/// * `grid_row_source` - The CSV file or directory store dataset to view
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or terminal/IO error
+pub fn launch_data_grid_viewer(grid_row_source: &GridRowSource) -> RowsAndColumnsResult<()> {
+    if grid_row_source.column_names().is_empty() {
+        println!("No columns to display in the data grid.");
+        return Ok(());
+    }
+
+    if !is_interactive_terminal_available() {
+        return display_line_based_data_grid(grid_row_source);
+    }
+
+    run_full_screen_data_grid(grid_row_source)
+}
+
+/// Prints the first page of rows as an aligned plain-text table
+///
+/// # Arguments
+/// * `grid_row_source` - The rows to print
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or read error
+fn display_line_based_data_grid(grid_row_source: &GridRowSource) -> RowsAndColumnsResult<()> {
+    let preview_rows = grid_row_source.read_row_window(0, LINE_MODE_PREVIEW_ROW_COUNT)?;
+    let all_column_positions: Vec<usize> = (0..grid_row_source.column_names().len()).collect();
+    let column_widths = calculate_grid_column_widths(grid_row_source, &preview_rows, &all_column_positions);
+    let row_number_width = grid_row_source.total_row_count().max(1).to_string().len();
+
+    println!("═══════════════════════════════════════════════════════════════");
+    println!(
+        "  Data Grid: {} ({} rows × {} columns, line mode)",
+        grid_row_source.source_label(),
+        grid_row_source.total_row_count(),
+        grid_row_source.column_names().len()
+    );
+    if let GridRowSource::DirectoryStore(store_dataset) = grid_row_source {
+        println!("  Imported from: {}", store_dataset.source_csv_path);
+    }
+    println!("═══════════════════════════════════════════════════════════════");
+
+    let header_cells: Vec<String> = all_column_positions
+        .iter()
+        .map(|&column_position| fit_text_to_display_width(&grid_row_source.column_names()[column_position], column_widths[column_position]))
+        .collect();
+    println!("{}  {}", " ".repeat(row_number_width), header_cells.join("  ").trim_end());
+
+    for (row_offset, row_values) in preview_rows.iter().enumerate() {
+        let row_cells: Vec<String> = all_column_positions
+            .iter()
+            .map(|&column_position| format_grid_cell(grid_row_source, row_values, column_position, column_widths[column_position]))
+            .collect();
+        println!("{:>width$}  {}", row_offset + 1, row_cells.join("  ").trim_end(), width = row_number_width);
+    }
+
+    let remaining_row_count = grid_row_source.total_row_count().saturating_sub(preview_rows.len());
+    if remaining_row_count > 0 {
+        println!("... {} more rows (run in a terminal to scroll)", remaining_row_count);
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Runs the interactive full-screen grid loop
+///
+/// # Arguments
+/// * `grid_row_source` - The rows to view
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or terminal/IO error
+fn run_full_screen_data_grid(grid_row_source: &GridRowSource) -> RowsAndColumnsResult<()> {
+    // The session restores the terminal when it goes out of scope
+    let mut raw_mode_session = TerminalRawModeSession::enter_raw_mode_session()?;
+    let mut viewer_state = DataGridViewerState::new(grid_row_source.column_names().len());
+
+    let mut terminal_size_tracker = TerminalSizeTracker::start_tracking();
+    let mut last_drawn_terminal_size: Option<TerminalSize> = None;
+    let mut input_just_arrived = false;
+    let mut redraw_needed = true;
+
+    loop {
+        // Redraw on state change or terminal resize
+        let current_terminal_size = terminal_size_tracker.current_terminal_size(input_just_arrived);
+        if last_drawn_terminal_size != Some(current_terminal_size) {
+            redraw_needed = true;
+        }
+
+        if redraw_needed {
+            let frame_lines = build_data_grid_frame_lines(grid_row_source, &mut viewer_state, current_terminal_size)?;
+            draw_full_screen_frame(&frame_lines, current_terminal_size)?;
+            last_drawn_terminal_size = Some(current_terminal_size);
+            redraw_needed = false;
+        }
+
+        // Wait (up to ~100ms) for key presses
+        let key_presses = raw_mode_session.read_available_key_presses()?;
+        input_just_arrived = !key_presses.is_empty();
+        let page_row_count = current_terminal_size.row_count
+            .saturating_sub(GRID_TOP_LINE_COUNT + GRID_BOTTOM_LINE_COUNT)
+            .max(1);
+
+        for key_press in key_presses {
+            let should_quit = apply_data_grid_key_press(&key_press, grid_row_source, &mut viewer_state, page_row_count)?;
+            if should_quit {
+                return Ok(());
+            }
+            redraw_needed = true;
+        }
+    }
+}
+
+/// Applies one key press to the viewer state
+///
+/// # Arguments
+/// * `key_press` - The decoded key
+/// * `grid_row_source` - Row source (for bounds and searching)
+/// * `viewer_state` - State to update
+/// * `page_row_count` - Rows per screen page
+///
+/// # Returns
+/// * `RowsAndColumnsResult<bool>` - True if the viewer should close
+fn apply_data_grid_key_press(
+    key_press: &KeyPress,
+    grid_row_source: &GridRowSource,
+    viewer_state: &mut DataGridViewerState,
+    page_row_count: usize,
+) -> RowsAndColumnsResult<bool> {
+    let last_row_index = grid_row_source.total_row_count().saturating_sub(1);
+
+    // Step 1: Ctrl-C closes the viewer from any mode; text entry modes take every other key
+    if *key_press == KeyPress::Interrupt {
+        return Ok(true);
+    }
+    match viewer_state.input_mode.clone() {
+        GridInputMode::JumpToRowPrompt(mut typed_row_number) => {
+            match key_press {
+                KeyPress::Escape => viewer_state.input_mode = GridInputMode::Browsing,
+                KeyPress::Backspace => {
+                    typed_row_number.pop();
+                    viewer_state.input_mode = GridInputMode::JumpToRowPrompt(typed_row_number);
+                }
+                KeyPress::Character(typed_character) if typed_character.is_ascii_digit() => {
+                    typed_row_number.push(*typed_character);
+                    viewer_state.input_mode = GridInputMode::JumpToRowPrompt(typed_row_number);
+                }
+                KeyPress::Enter => {
+                    viewer_state.input_mode = GridInputMode::Browsing;
+                    match typed_row_number.parse::<usize>() {
+                        Ok(row_number) if row_number >= 1 => {
+                            viewer_state.selected_row_index = (row_number - 1).min(last_row_index);
+                            viewer_state.status_message = format!("Jumped to row {}", viewer_state.selected_row_index + 1);
+                        }
+                        _ => viewer_state.status_message = String::from("Enter a row number of 1 or more"),
+                    }
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+        GridInputMode::SearchPrompt(mut typed_search_text) => {
+            match key_press {
+                KeyPress::Escape => {
+                    viewer_state.input_mode = GridInputMode::Browsing;
+                    viewer_state.selected_row_index = viewer_state.search_origin_row_index;
+                    viewer_state.status_message = String::from("Search cancelled");
+                }
+                KeyPress::Enter => {
+                    viewer_state.input_mode = GridInputMode::Browsing;
+                    viewer_state.last_search_text = typed_search_text;
+                }
+                KeyPress::Backspace | KeyPress::Character(_) => {
+                    match key_press {
+                        KeyPress::Character(typed_character) => typed_search_text.push(*typed_character),
+                        _ => {
+                            typed_search_text.pop();
+                        }
+                    }
+                    // Incremental: search again from where the search began
+                    let search_origin_row_index = viewer_state.search_origin_row_index;
+                    move_to_next_search_match(grid_row_source, viewer_state, search_origin_row_index, &typed_search_text)?;
+                    viewer_state.input_mode = GridInputMode::SearchPrompt(typed_search_text);
+                }
+                _ => {}
+            }
+            return Ok(false);
+        }
+        GridInputMode::Browsing => {}
+    }
+
+    // Step 2: Navigation keys
+    viewer_state.status_message.clear();
+    let visible_column_positions = viewer_state.visible_column_positions();
+    let selected_visible_position = visible_column_positions
+        .iter()
+        .position(|&column_position| column_position == viewer_state.selected_column_position)
+        .unwrap_or(0);
+
+    match key_press {
+        KeyPress::Character('q') | KeyPress::Character('Q') | KeyPress::Escape => return Ok(true),
+
+        KeyPress::ArrowDown | KeyPress::Character('j') => {
+            viewer_state.selected_row_index = (viewer_state.selected_row_index + 1).min(last_row_index);
+        }
+        KeyPress::ArrowUp | KeyPress::Character('k') => {
+            viewer_state.selected_row_index = viewer_state.selected_row_index.saturating_sub(1);
+        }
+        KeyPress::PageDown => {
+            viewer_state.selected_row_index = (viewer_state.selected_row_index + page_row_count).min(last_row_index);
+        }
+        KeyPress::PageUp => {
+            viewer_state.selected_row_index = viewer_state.selected_row_index.saturating_sub(page_row_count);
+        }
+        KeyPress::Character('g') => viewer_state.selected_row_index = 0,
+        KeyPress::Character('G') => viewer_state.selected_row_index = last_row_index,
+
+        KeyPress::ArrowRight | KeyPress::Character('l') | KeyPress::Tab => {
+            if let Some(&next_column_position) = visible_column_positions.get(selected_visible_position + 1) {
+                viewer_state.selected_column_position = next_column_position;
+            }
+        }
+        KeyPress::ArrowLeft | KeyPress::Character('h') if selected_visible_position > 0 => {
+            viewer_state.selected_column_position = visible_column_positions[selected_visible_position - 1];
+        }
+        KeyPress::Home => {
+            if let Some(&first_column_position) = visible_column_positions.first() {
+                viewer_state.selected_column_position = first_column_position;
+            }
+        }
+        KeyPress::End => {
+            if let Some(&last_column_position) = visible_column_positions.last() {
+                viewer_state.selected_column_position = last_column_position;
+            }
+        }
+
+        KeyPress::Character(':') => viewer_state.input_mode = GridInputMode::JumpToRowPrompt(String::new()),
+        KeyPress::Character('/') => {
+            viewer_state.search_origin_row_index = viewer_state.selected_row_index;
+            viewer_state.input_mode = GridInputMode::SearchPrompt(String::new());
+        }
+        KeyPress::Character('n') => {
+            if viewer_state.last_search_text.is_empty() {
+                viewer_state.status_message = String::from("No previous search; press / to search");
+            } else {
+                let search_text = viewer_state.last_search_text.clone();
+                let search_start_row_index = viewer_state.selected_row_index + 1;
+                move_to_next_search_match(grid_row_source, viewer_state, search_start_row_index, &search_text)?;
+            }
+        }
+
+        KeyPress::Character('-') => {
+            if visible_column_positions.len() <= 1 {
+                viewer_state.status_message = String::from("Cannot hide the last visible column");
+            } else {
+                let hidden_column_position = viewer_state.selected_column_position;
+                viewer_state.hidden_column_flags[hidden_column_position] = true;
+                viewer_state.selected_column_position = visible_column_positions
+                    .get(selected_visible_position + 1)
+                    .or_else(|| visible_column_positions.get(selected_visible_position.saturating_sub(1)))
+                    .copied()
+                    .unwrap_or(0);
+                viewer_state.status_message = format!(
+                    "Hid column '{}' (+ shows all)",
+                    grid_row_source.column_names()[hidden_column_position]
+                );
+            }
+        }
+        KeyPress::Character('+') | KeyPress::Character('=') => {
+            viewer_state.hidden_column_flags.iter_mut().for_each(|hidden_flag| *hidden_flag = false);
+            viewer_state.status_message = String::from("All columns shown");
+        }
+        KeyPress::Character('p') => {
+            let pinned_flag = &mut viewer_state.pinned_column_flags[viewer_state.selected_column_position];
+            *pinned_flag = !*pinned_flag;
+            viewer_state.status_message = format!(
+                "{} column '{}'",
+                if *pinned_flag { "Pinned" } else { "Unpinned" },
+                grid_row_source.column_names()[viewer_state.selected_column_position]
+            );
+        }
+
+        _ => {}
+    }
+
+    Ok(false)
+}
+
+/// Searches for the next cell containing the text and moves the selection there
+///
+/// # Arguments
+/// * `grid_row_source` - Rows to search
+/// * `viewer_state` - State to update (selection and status message)
+/// * `search_start_row_index` - First row to look at (the search wraps around)
+/// * `search_text` - Text to find (case-insensitive substring)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or read error
+fn move_to_next_search_match(
+    grid_row_source: &GridRowSource,
+    viewer_state: &mut DataGridViewerState,
+    search_start_row_index: usize,
+    search_text: &str,
+) -> RowsAndColumnsResult<()> {
+    if search_text.is_empty() {
+        viewer_state.selected_row_index = viewer_state.search_origin_row_index;
+        viewer_state.status_message.clear();
+        return Ok(());
+    }
+
+    let searchable_column_positions = viewer_state.visible_column_positions();
+    match find_next_matching_cell(grid_row_source, search_start_row_index, search_text, &searchable_column_positions)? {
+        Some((matching_row_index, matching_column_position)) => {
+            viewer_state.selected_row_index = matching_row_index;
+            viewer_state.selected_column_position = matching_column_position;
+            viewer_state.status_message = format!("Match for '{}' at row {}", search_text, matching_row_index + 1);
+        }
+        None => {
+            viewer_state.status_message = format!("No match for '{}'", search_text);
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the next cell whose value contains the search text (case-insensitive)
+///
+/// Rows are read in chunks starting at `search_start_row_index`; the search wraps
+/// around to the first row and stops after every row has been looked at once.
+///
+/// # Arguments
+/// * `grid_row_source` - Rows to search
+/// * `search_start_row_index` - First row to look at
+/// * `search_text` - Text to find
+/// * `searchable_column_positions` - Columns to look in (hidden columns are skipped)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<(usize, usize)>>` - (row index, column position) of the match
+fn find_next_matching_cell(
+    grid_row_source: &GridRowSource,
+    search_start_row_index: usize,
+    search_text: &str,
+    searchable_column_positions: &[usize],
+) -> RowsAndColumnsResult<Option<(usize, usize)>> {
+    let total_row_count = grid_row_source.total_row_count();
+    if total_row_count == 0 {
+        return Ok(None);
+    }
+
+    let lowercase_search_text = search_text.to_lowercase();
+    let wrapped_start_row_index = search_start_row_index % total_row_count;
+    let mut rows_examined = 0;
+
+    while rows_examined < total_row_count {
+        let chunk_first_row_index = (wrapped_start_row_index + rows_examined) % total_row_count;
+        // Do not read past the end of the data in one chunk; wrap on the next one
+        let chunk_row_count = SEARCH_CHUNK_ROW_COUNT
+            .min(total_row_count - chunk_first_row_index)
+            .min(total_row_count - rows_examined);
+        let chunk_rows = grid_row_source.read_row_window(chunk_first_row_index, chunk_row_count)?;
+
+        for (row_offset, row_values) in chunk_rows.iter().enumerate() {
+            for &column_position in searchable_column_positions {
+                let cell_matches = row_values
+                    .get(column_position)
+                    .is_some_and(|cell_value| cell_value.to_lowercase().contains(&lowercase_search_text));
+                if cell_matches {
+                    return Ok(Some((chunk_first_row_index + row_offset, column_position)));
+                }
+            }
+        }
+
+        if chunk_rows.is_empty() {
+            break;
+        }
+        rows_examined += chunk_rows.len();
+    }
+
+    Ok(None)
+}
+
+/// Calculates display widths for the given columns from their header and window values
+///
+/// # Arguments
+/// * `grid_row_source` - Source of column names
+/// * `window_rows` - Rows currently on screen
+/// * `column_positions` - Columns to measure
+///
+/// # Returns
+/// * `Vec<usize>` - Width per column position (0 for columns not measured)
+fn calculate_grid_column_widths(
+    grid_row_source: &GridRowSource,
+    window_rows: &[Vec<String>],
+    column_positions: &[usize],
+) -> Vec<usize> {
+    let mut column_widths = vec![0; grid_row_source.column_names().len()];
+
+    for &column_position in column_positions {
+        let header_width = calculate_text_display_width(&grid_row_source.column_names()[column_position]);
+        let widest_value_width = window_rows
+            .iter()
+            .filter_map(|row_values| row_values.get(column_position))
+            .map(|cell_value| calculate_text_display_width(cell_value))
+            .max()
+            .unwrap_or(0);
+
+        column_widths[column_position] = header_width
+            .max(widest_value_width)
+            .clamp(MINIMUM_GRID_COLUMN_WIDTH, MAXIMUM_GRID_COLUMN_WIDTH);
+    }
+
+    column_widths
+}
+
+/// Formats one cell to an exact display width; numeric columns are right-aligned
+///
+/// # Arguments
+/// * `grid_row_source` - Source of column data types
+/// * `row_values` - Values of the row
+/// * `column_position` - Column to format
+/// * `column_width` - Width in terminal cells
+///
+/// # Returns
+/// * `String` - The padded/clipped cell text
+fn format_grid_cell(
+    grid_row_source: &GridRowSource,
+    row_values: &[String],
+    column_position: usize,
+    column_width: usize,
+) -> String {
+    let cell_value = row_values.get(column_position).map(String::as_str).unwrap_or("");
+    let is_numeric_column = matches!(
+        grid_row_source.column_data_types().get(column_position),
+        Some(CsvColumnDataType::Integer) | Some(CsvColumnDataType::Float)
+    );
+
+    let cell_width = calculate_text_display_width(cell_value);
+    if is_numeric_column && cell_width < column_width {
+        format!("{}{}", " ".repeat(column_width - cell_width), cell_value)
+    } else {
+        fit_text_to_display_width(cell_value, column_width)
+    }
+}
+
+/// Chooses the columns shown to the right of the pinned columns
+///
+/// Scrolls horizontally so the selected column (if unpinned) is fully visible.
+///
+/// # Arguments
+/// * `viewer_state` - State holding selection and scroll position (updated)
+/// * `scrollable_column_positions` - Visible, unpinned columns in order
+/// * `column_widths` - Display width per column position
+/// * `available_width` - Cells left after the row number gutter and pinned columns
+///
+/// # Returns
+/// * `Vec<usize>` - Column positions to draw, in order
+fn choose_scrolled_column_positions(
+    viewer_state: &mut DataGridViewerState,
+    scrollable_column_positions: &[usize],
+    column_widths: &[usize],
+    available_width: usize,
+) -> Vec<usize> {
+    if scrollable_column_positions.is_empty() {
+        return Vec::new();
+    }
+
+    let mut first_visible_index = scrollable_column_positions
+        .iter()
+        .position(|&column_position| column_position >= viewer_state.first_scrolled_column_position)
+        .unwrap_or(0);
+
+    if let Some(selected_index) = scrollable_column_positions
+        .iter()
+        .position(|&column_position| column_position == viewer_state.selected_column_position)
+    {
+        if selected_index < first_visible_index {
+            first_visible_index = selected_index;
+        }
+        // Advance until everything from the first visible column up to the selection fits
+        while first_visible_index < selected_index {
+            let required_width: usize = scrollable_column_positions[first_visible_index..=selected_index]
+                .iter()
+                .map(|&column_position| column_widths[column_position] + 2)
+                .sum();
+            if required_width <= available_width {
+                break;
+            }
+            first_visible_index += 1;
+        }
+    }
+
+    viewer_state.first_scrolled_column_position = scrollable_column_positions[first_visible_index];
+
+    let mut chosen_column_positions = Vec::new();
+    let mut used_width = 0;
+    for &column_position in &scrollable_column_positions[first_visible_index..] {
+        // Always show at least one (possibly clipped) column
+        if used_width >= available_width && !chosen_column_positions.is_empty() {
+            break;
+        }
+        used_width += column_widths[column_position] + 2;
+        chosen_column_positions.push(column_position);
+    }
+
+    chosen_column_positions
+}
+
+/// Builds every screen line of the grid for the current state and terminal size
+///
+/// # Arguments
+/// * `grid_row_source` - Rows to show
+/// * `viewer_state` - State (scroll positions are adjusted to keep the selection visible)
+/// * `terminal_size` - Current terminal dimensions
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<String>>` - Screen lines, top to bottom
+fn build_data_grid_frame_lines(
+    grid_row_source: &GridRowSource,
+    viewer_state: &mut DataGridViewerState,
+    terminal_size: TerminalSize,
+) -> RowsAndColumnsResult<Vec<String>> {
+    let screen_width = terminal_size.column_count;
+    let screen_height = terminal_size.row_count;
+
+    if screen_width < MINIMUM_GRID_COLUMN_COUNT || screen_height < MINIMUM_GRID_ROW_COUNT {
+        return Ok(vec![
+            "Terminal too small for the data grid.".to_string(),
+            format!("Need at least {}x{}; press q to leave.", MINIMUM_GRID_COLUMN_COUNT, MINIMUM_GRID_ROW_COUNT),
+        ]);
+    }
+
+    // Step 1: Keep the selected row on screen and read only that window
+    let visible_row_count = screen_height - GRID_TOP_LINE_COUNT - GRID_BOTTOM_LINE_COUNT;
+    if viewer_state.selected_row_index < viewer_state.top_row_index {
+        viewer_state.top_row_index = viewer_state.selected_row_index;
+    } else if viewer_state.selected_row_index >= viewer_state.top_row_index + visible_row_count {
+        viewer_state.top_row_index = viewer_state.selected_row_index + 1 - visible_row_count;
+    }
+
+    let window_is_current = matches!(
+        &viewer_state.cached_row_window,
+        Some((cached_top_row_index, cached_rows))
+            if *cached_top_row_index == viewer_state.top_row_index
+                && (cached_rows.len() == visible_row_count
+                    || viewer_state.top_row_index + cached_rows.len() >= grid_row_source.total_row_count())
+    );
+    if !window_is_current {
+        let window_rows = grid_row_source.read_row_window(viewer_state.top_row_index, visible_row_count)?;
+        viewer_state.cached_row_window = Some((viewer_state.top_row_index, window_rows));
+    }
+    let window_rows = viewer_state.cached_row_window
+        .as_ref()
+        .map(|(_, cached_rows)| cached_rows.clone())
+        .unwrap_or_default();
+
+    // Step 2: Column layout (pinned columns first, then the scrolled columns)
+    let visible_column_positions = viewer_state.visible_column_positions();
+    let column_widths = calculate_grid_column_widths(grid_row_source, &window_rows, &visible_column_positions);
+    let pinned_column_positions: Vec<usize> = visible_column_positions
+        .iter()
+        .copied()
+        .filter(|&column_position| viewer_state.pinned_column_flags[column_position])
+        .collect();
+    let scrollable_column_positions: Vec<usize> = visible_column_positions
+        .iter()
+        .copied()
+        .filter(|&column_position| !viewer_state.pinned_column_flags[column_position])
+        .collect();
+
+    let row_number_width = grid_row_source.total_row_count().max(1).to_string().len();
+    let gutter_width = row_number_width + 3;
+    let pinned_width: usize = pinned_column_positions
+        .iter()
+        .map(|&column_position| column_widths[column_position] + 2)
+        .sum::<usize>()
+        + if pinned_column_positions.is_empty() { 0 } else { 2 };
+    let scrolled_column_positions = choose_scrolled_column_positions(
+        viewer_state,
+        &scrollable_column_positions,
+        &column_widths,
+        screen_width.saturating_sub(gutter_width + pinned_width),
+    );
+
+    // Step 3: Assemble lines
+    let column_names = grid_row_source.column_names();
+    let hidden_column_count = viewer_state.hidden_column_flags.iter().filter(|&&hidden_flag| hidden_flag).count();
+    let mut frame_lines = Vec::with_capacity(screen_height);
+
+    frame_lines.push(format!(
+        "rows_and_columns · Data Grid · {} · {} rows × {} columns{}",
+        grid_row_source.source_label(),
+        grid_row_source.total_row_count(),
+        column_names.len(),
+        if hidden_column_count > 0 { format!(" ({} hidden)", hidden_column_count) } else { String::new() }
+    ));
+
+    let build_grid_line = |left_text: String, cell_texts_for: &dyn Fn(usize) -> String, separator_text: &str| -> String {
+        let mut grid_line = left_text;
+        for &column_position in &pinned_column_positions {
+            grid_line.push_str(&cell_texts_for(column_position));
+            grid_line.push_str("  ");
+        }
+        if !pinned_column_positions.is_empty() {
+            grid_line.push_str(separator_text);
+            grid_line.push(' ');
+        }
+        for &column_position in &scrolled_column_positions {
+            grid_line.push_str(&cell_texts_for(column_position));
+            grid_line.push_str("  ");
+        }
+        grid_line
+    };
+
+    // Header and underline (the selected column is underlined with heavy rules)
+    frame_lines.push(build_grid_line(
+        " ".repeat(gutter_width),
+        &|column_position| fit_text_to_display_width(&column_names[column_position], column_widths[column_position]),
+        "│",
+    ));
+    frame_lines.push(build_grid_line(
+        "─".repeat(gutter_width),
+        &|column_position| {
+            let rule_character = if column_position == viewer_state.selected_column_position { "━" } else { "─" };
+            format!("{}──", rule_character.repeat(column_widths[column_position]))
+                .chars()
+                .take(column_widths[column_position])
+                .collect()
+        },
+        "┼",
+    ));
+
+    // Data rows
+    if window_rows.is_empty() {
+        frame_lines.push(String::from("  (no data rows)"));
+    }
+    for (row_offset, row_values) in window_rows.iter().enumerate() {
+        let data_row_index = viewer_state.top_row_index + row_offset;
+        let selection_marker = if data_row_index == viewer_state.selected_row_index { ">" } else { " " };
+        frame_lines.push(build_grid_line(
+            format!("{} {:>width$} ", selection_marker, data_row_index + 1, width = row_number_width),
+            &|column_position| format_grid_cell(grid_row_source, row_values, column_position, column_widths[column_position]),
+            "│",
+        ));
+    }
+
+    // Pad so the status and help bars stay at the bottom
+    while frame_lines.len() < screen_height - GRID_BOTTOM_LINE_COUNT {
+        frame_lines.push(String::new());
+    }
+
+    // Status bar: selected cell, full value, and any message
+    let selected_cell_value = window_rows
+        .get(viewer_state.selected_row_index.saturating_sub(viewer_state.top_row_index))
+        .and_then(|row_values| row_values.get(viewer_state.selected_column_position))
+        .cloned()
+        .unwrap_or_default();
+    let selected_column_type = grid_row_source.column_data_types()
+        .get(viewer_state.selected_column_position)
+        .map(|data_type| data_type.to_toml_string().to_string())
+        .unwrap_or_default();
+    let mut status_line = format!(
+        "Row {}/{} │ {} ({}): {}",
+        (viewer_state.selected_row_index + 1).min(grid_row_source.total_row_count()),
+        grid_row_source.total_row_count(),
+        column_names[viewer_state.selected_column_position],
+        selected_column_type,
+        selected_cell_value
+    );
+    if !viewer_state.status_message.is_empty() {
+        status_line.push_str(&format!(" │ {}", viewer_state.status_message));
+    }
+    frame_lines.push(status_line);
+
+    // Help bar or text prompt
+    frame_lines.push(match &viewer_state.input_mode {
+        GridInputMode::Browsing => String::from(
+            "j/k rows  h/l columns  PgUp/PgDn  g/G top/end  : jump  / search  n next  - hide  + show all  p pin  q quit"
+        ),
+        GridInputMode::JumpToRowPrompt(typed_row_number) => format!("Jump to row (1-{}): {}_", grid_row_source.total_row_count(), typed_row_number),
+        GridInputMode::SearchPrompt(typed_search_text) => format!("Search (Enter keeps, Esc cancels): {}_", typed_search_text),
+    });
+
+    Ok(frame_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use super::super::csv_processor_module::CsvColumnInformation;
+
+    /// Builds a CSV row source over a file with the given content in the test's directory
+    fn create_test_csv_row_source(test_directory: &Path, csv_content: &str, column_names: &[&str]) -> GridRowSource {
+        std::fs::create_dir_all(test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("grid.csv");
+        std::fs::write(&csv_file_path, csv_content).expect("test csv should be writable");
+
+        let csv_analysis_results = CsvAnalysisResults {
+            csv_file_path: csv_file_path.clone(),
+            has_header_row: true,
+            total_column_count: column_names.len(),
+            total_data_row_count: 0,
+            column_information_list: column_names
+                .iter()
+                .enumerate()
+                .map(|(column_index, column_name)| CsvColumnInformation {
+                    column_index,
+                    column_name: column_name.to_string(),
+                    detected_data_type: CsvColumnDataType::String,
+                    non_empty_value_count: 0,
+                    empty_value_count: 0,
+                    sample_values: Vec::new(),
+                })
+                .collect(),
+            metadata_file_path: csv_file_path.with_extension("csv_metadata.toml"),
+            metadata_file_already_existed: false,
+        };
+
+        GridRowSource::open_csv_file(&csv_analysis_results).expect("row source should open")
+    }
+
+    /// Test that row windows past an index checkpoint are read correctly
+    #[test]
+    fn test_read_row_window_uses_offset_index() {
+        let mut csv_content = String::from("number,square\r\n");
+        for row_number in 0..3000 {
+            csv_content.push_str(&format!("{},{}\r\n", row_number, row_number * row_number));
+        }
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_grid_window_test_{}", std::process::id()));
+        let grid_row_source = create_test_csv_row_source(&test_directory, &csv_content, &["number", "square"]);
+
+        assert_eq!(grid_row_source.total_row_count(), 3000);
+        let window_rows = grid_row_source.read_row_window(2047, 3).expect("window should read");
+        assert_eq!(window_rows[0], vec!["2047", "4190209"]);
+        assert_eq!(window_rows[2], vec!["2049", "4198401"]);
+        assert!(grid_row_source.read_row_window(2999, 10).expect("tail").len() == 1);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test that search wraps around and skips hidden columns
+    #[test]
+    fn test_find_next_matching_cell_wraps_around() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_grid_search_test_{}", std::process::id()));
+        let grid_row_source = create_test_csv_row_source(
+            &test_directory,
+            "name,city\nAda,London\nBob,Paris\nCy,london\n",
+            &["name", "city"],
+        );
+
+        assert_eq!(find_next_matching_cell(&grid_row_source, 1, "LONDON", &[0, 1]).expect("search"), Some((2, 1)));
+        assert_eq!(find_next_matching_cell(&grid_row_source, 3, "london", &[0, 1]).expect("search"), Some((0, 1)));
+        assert_eq!(find_next_matching_cell(&grid_row_source, 0, "london", &[0]).expect("search"), None);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test that horizontal scrolling keeps the selected column on screen
+    #[test]
+    fn test_choose_scrolled_column_positions_follows_selection() {
+        let mut viewer_state = DataGridViewerState::new(6);
+        let column_widths = vec![10; 6];
+        let scrollable_column_positions: Vec<usize> = (0..6).collect();
+
+        viewer_state.selected_column_position = 5;
+        let chosen_positions = choose_scrolled_column_positions(&mut viewer_state, &scrollable_column_positions, &column_widths, 36);
+        assert_eq!(chosen_positions, vec![3, 4, 5]);
+
+        viewer_state.selected_column_position = 1;
+        let chosen_positions = choose_scrolled_column_positions(&mut viewer_state, &scrollable_column_positions, &column_widths, 36);
+        assert_eq!(chosen_positions.first(), Some(&1));
+    }
+}
@@ -0,0 +1,1179 @@
+// src/dataset_join_module.rs
+
+/// Joins between two datasets for rows_and_columns ("cats joined to owners on owner_id")
+///
+/// Two CSV files or directory store datasets are joined on one or more key
+/// columns (inner, left, right or full outer join) and the result is written
+/// as a new CSV file with its own `.csv_metadata.toml`.
+///
+/// # Core Responsibilities
+/// - Check that each key pair has compatible types according to both metadata files
+/// - Lay out the output columns: left columns, then right columns; a key pair
+///   with the same name on both sides becomes one column, other clashing names
+///   get `_left` / `_right` suffixes
+/// - Hash join when the smaller side fits in the memory budget, sort-merge join
+///   (using the external sort) otherwise
+/// - Count rows without a match on each side, including rows with empty keys
+///
+/// # Design Philosophy
+/// - Typed keys: integer and float keys compare numerically (so `3` matches
+///   `3.0`), boolean keys by truth value, text keys exactly
+/// - Empty or unreadable key cells never match anything, as in SQL; outer
+///   joins still keep those rows
+/// - Bounded memory: the hash table is abandoned as soon as its estimated size
+///   passes the budget, and the sort-merge join only holds one group of
+///   equal-key rows from the right side at a time
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import CSV structures, the row stream control, the line parser and the export writer
+use super::csv_processor_module::{
+    CsvColumnDataType,
+    CsvRowStreamControl,
+    StreamingCsvExportWriter,
+    parse_csv_line_into_fields,
+};
+
+// Import the directory store so either side can be an imported dataset
+use super::directory_store_module::DatasetRowSource;
+
+// Import the external sort used by the sort-merge join
+use super::external_sort_module::{
+    ExternalSortRequest,
+    NullPlacement,
+    SortDirection,
+    SortKeySpecification,
+    TypedKeyValue,
+    compare_typed_key_values,
+    perform_external_sort,
+    read_typed_key_value,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_csv_processing_error,
+    create_data_type_validation_error,
+    create_file_system_error,
+};
+
+/// Default memory budget for the hash table of the smaller side
+pub const DEFAULT_JOIN_MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// Estimated bookkeeping bytes per stored string (pointer, length, capacity)
+const ESTIMATED_STRING_OVERHEAD_BYTES: usize = 24;
+
+/// Estimated bookkeeping bytes per hashed row (row vector, key, table entry)
+const ESTIMATED_ROW_OVERHEAD_BYTES: usize = 96;
+
+/// Which rows a join keeps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinType {
+    /// Only rows whose key matches on both sides
+    Inner,
+    /// Every left row, with right values where the key matches
+    Left,
+    /// Every right row, with left values where the key matches
+    Right,
+    /// Every row from both sides
+    FullOuter,
+}
+
+impl JoinType {
+    /// Parses a join type as typed by the user
+    ///
+    /// # Arguments
+    /// * `join_type_text` - `inner`, `left`, `right`, `full` or `outer` (any case)
+    ///
+    /// # Returns
+    /// * `Option<JoinType>` - The join type, or `None` if not recognized
+    pub fn from_user_text(join_type_text: &str) -> Option<JoinType> {
+        match join_type_text.trim().to_lowercase().as_str() {
+            "inner" | "i" => Some(JoinType::Inner),
+            "left" | "l" => Some(JoinType::Left),
+            "right" | "r" => Some(JoinType::Right),
+            "full" | "outer" | "full outer" | "f" => Some(JoinType::FullOuter),
+            _ => None,
+        }
+    }
+
+    /// Returns a short name for messages and file names
+    ///
+    /// # Returns
+    /// * `&'static str` - `inner`, `left`, `right` or `full`
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            JoinType::Inner => "inner",
+            JoinType::Left => "left",
+            JoinType::Right => "right",
+            JoinType::FullOuter => "full",
+        }
+    }
+
+    /// Whether left rows without a match are written
+    fn keeps_unmatched_rows_of(&self, join_side: JoinSide) -> bool {
+        match join_side {
+            JoinSide::Left => matches!(self, JoinType::Left | JoinType::FullOuter),
+            JoinSide::Right => matches!(self, JoinType::Right | JoinType::FullOuter),
+        }
+    }
+}
+
+/// One side of a join
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinSide {
+    Left,
+    Right,
+}
+
+/// How the join was carried out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinStrategy {
+    /// One side was held in a hash table and the other streamed past it
+    HashJoin { build_side: JoinSide },
+    /// Both sides were sorted by key and merged
+    SortMergeJoin,
+}
+
+/// Roughly estimates the bytes one side would occupy in memory
+///
+/// Used only to pick the hash join's build side and to skip hopeless
+/// attempts; the real budget check happens while the table is built.
+fn estimate_join_side_size_bytes(join_row_source: &DatasetRowSource) -> u64 {
+    match join_row_source {
+        DatasetRowSource::CsvFile(csv_analysis_results) => std::fs::metadata(&csv_analysis_results.csv_file_path)
+            .map(|file_metadata| file_metadata.len())
+            .unwrap_or(u64::MAX),
+        DatasetRowSource::DirectoryStore(store_dataset) => {
+            (store_dataset.total_row_count * store_dataset.column_names.len() * ESTIMATED_STRING_OVERHEAD_BYTES) as u64
+        }
+    }
+}
+
+/// What to join and how much memory the hash table may use
+#[derive(Debug, Clone)]
+pub struct DatasetJoinRequest {
+    /// Inner, left, right or full outer
+    pub join_type: JoinType,
+
+    /// 0-based key column positions on the left side
+    pub left_key_positions: Vec<usize>,
+
+    /// 0-based key column positions on the right side, paired in order
+    pub right_key_positions: Vec<usize>,
+
+    /// Estimated bytes the hash join's build side may occupy
+    pub memory_budget_bytes: usize,
+}
+
+/// Outcome of a join
+#[derive(Debug, Clone)]
+pub struct DatasetJoinSummary {
+    /// Path of the joined CSV file
+    pub joined_csv_file_path: PathBuf,
+
+    /// Path of its metadata file
+    pub metadata_file_path: PathBuf,
+
+    /// The join type used
+    pub join_type: JoinType,
+
+    /// Hash join or sort-merge join
+    pub join_strategy: JoinStrategy,
+
+    /// Output column names
+    pub joined_column_names: Vec<String>,
+
+    /// Rows written
+    pub joined_row_count: usize,
+
+    /// Rows read from the left side
+    pub left_row_count: usize,
+
+    /// Rows read from the right side
+    pub right_row_count: usize,
+
+    /// Left rows whose key found no right row (including empty keys)
+    pub left_unmatched_row_count: usize,
+
+    /// Right rows whose key found no left row (including empty keys)
+    pub right_unmatched_row_count: usize,
+
+    /// Left rows with an empty or unreadable key cell
+    pub left_null_key_row_count: usize,
+
+    /// Right rows with an empty or unreadable key cell
+    pub right_null_key_row_count: usize,
+}
+
+/// Where an output column's value comes from
+#[derive(Debug, Clone, Copy)]
+enum JoinedColumnSource {
+    Left(usize),
+    Right(usize),
+    /// A key pair with the same name on both sides: whichever side is present
+    CoalescedKey { left_position: usize, right_position: usize },
+}
+
+/// Output columns of a join
+#[derive(Debug, Clone)]
+struct JoinedColumnLayout {
+    column_sources: Vec<JoinedColumnSource>,
+    column_names: Vec<String>,
+    column_data_types: Vec<CsvColumnDataType>,
+}
+
+/// Row and key counters for one side
+#[derive(Debug, Clone, Default)]
+struct JoinSideCounters {
+    row_count: usize,
+    unmatched_row_count: usize,
+    null_key_row_count: usize,
+}
+
+/// Key positions and types for one side
+struct JoinKeyColumns<'a> {
+    key_positions: &'a [usize],
+    /// Per key pair: the type both sides are read as (Integer only when both
+    /// sides are Integer, so the key is compared exactly; mixed Integer/Float
+    /// pairs are read as Float)
+    key_data_types: &'a [CsvColumnDataType],
+}
+
+/// Removes a join's work directory (sorted copies) when the join finishes or fails
+struct JoinWorkDirectoryGuard {
+    work_directory_path: PathBuf,
+}
+
+impl Drop for JoinWorkDirectoryGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.work_directory_path);
+    }
+}
+
+/// Joins two datasets and writes the result as a new CSV file with metadata
+///
+/// # Arguments
+/// * `left_row_source` - The left dataset
+/// * `right_row_source` - The right dataset
+/// * `join_request` - Join type, key columns and memory budget
+/// * `temporary_work_directory` - Parent directory for sorted copies (sort-merge join)
+/// * `output_csv_path` - Where to write the joined CSV file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DatasetJoinSummary>` - Output paths, strategy and counts
+///
+/// # Errors
+/// * `CsvProcessingError` - No keys, unequal key counts, or keys out of range
+/// * `DataTypeValidationError` - A key pair has incompatible types
+/// * `FileSystemError` - Reading, sorting or writing failed
+pub fn perform_dataset_join(
+    left_row_source: &DatasetRowSource,
+    right_row_source: &DatasetRowSource,
+    join_request: &DatasetJoinRequest,
+    temporary_work_directory: &Path,
+    output_csv_path: &Path,
+) -> RowsAndColumnsResult<DatasetJoinSummary> {
+    let left_column_names = left_row_source.column_names();
+    let left_column_types = left_row_source.column_data_types();
+    let right_column_names = right_row_source.column_names();
+    let right_column_types = right_row_source.column_data_types();
+
+    // Step 1: Validate the key columns and their types
+    if join_request.left_key_positions.is_empty() || join_request.left_key_positions.len() != join_request.right_key_positions.len() {
+        return Err(create_csv_processing_error(
+            &format!(
+                "A join needs the same number of key columns on both sides (got {} and {})",
+                join_request.left_key_positions.len(),
+                join_request.right_key_positions.len()
+            ),
+            None,
+            None
+        ));
+    }
+    for (side_name, key_positions, column_count) in [
+        ("left", &join_request.left_key_positions, left_column_names.len()),
+        ("right", &join_request.right_key_positions, right_column_names.len()),
+    ] {
+        if let Some(invalid_position) = key_positions.iter().find(|&&key_position| key_position >= column_count) {
+            return Err(create_csv_processing_error(
+                &format!("Join key column {} is outside the {} columns of the {} side", invalid_position + 1, column_count, side_name),
+                None,
+                None
+            ));
+        }
+    }
+    for (&left_key_position, &right_key_position) in join_request.left_key_positions.iter().zip(&join_request.right_key_positions) {
+        let left_key_type = &left_column_types[left_key_position];
+        let right_key_type = &right_column_types[right_key_position];
+        if !are_join_key_types_compatible(left_key_type, right_key_type) {
+            return Err(create_data_type_validation_error(
+                &format!(
+                    "Join key '{}' (left) and '{}' (right) have different types",
+                    left_column_names[left_key_position],
+                    right_column_names[right_key_position]
+                ),
+                right_key_type.to_toml_string(),
+                left_key_type.to_toml_string()
+            ));
+        }
+    }
+
+    let joined_column_layout = build_joined_column_layout(
+        (&left_column_names, &left_column_types),
+        (&right_column_names, &right_column_types),
+        join_request,
+    );
+    let key_data_types: Vec<CsvColumnDataType> = join_request.left_key_positions
+        .iter()
+        .zip(&join_request.right_key_positions)
+        .map(|(&left_key_position, &right_key_position)| {
+            let left_key_type = &left_column_types[left_key_position];
+            if left_key_type == &right_column_types[right_key_position] {
+                left_key_type.clone()
+            } else {
+                CsvColumnDataType::Float
+            }
+        })
+        .collect();
+    let left_key_columns = JoinKeyColumns {
+        key_positions: &join_request.left_key_positions,
+        key_data_types: &key_data_types,
+    };
+    let right_key_columns = JoinKeyColumns {
+        key_positions: &join_request.right_key_positions,
+        key_data_types: &key_data_types,
+    };
+
+    // Step 2: Hash join with the smaller side as the table, if it fits
+    let left_estimated_bytes = estimate_join_side_size_bytes(left_row_source);
+    let right_estimated_bytes = estimate_join_side_size_bytes(right_row_source);
+    let build_side = if left_estimated_bytes <= right_estimated_bytes { JoinSide::Left } else { JoinSide::Right };
+    let smaller_estimated_bytes = left_estimated_bytes.min(right_estimated_bytes);
+
+    let hash_join_table = if smaller_estimated_bytes <= join_request.memory_budget_bytes as u64 {
+        match build_side {
+            JoinSide::Left => build_hash_join_table(left_row_source, &left_key_columns, join_request.memory_budget_bytes)?,
+            JoinSide::Right => build_hash_join_table(right_row_source, &right_key_columns, join_request.memory_budget_bytes)?,
+        }
+    } else {
+        None
+    };
+
+    let mut export_writer = StreamingCsvExportWriter::create(
+        output_csv_path,
+        &joined_column_layout.column_names,
+        &joined_column_layout.column_data_types,
+    )?;
+    let mut left_counters = JoinSideCounters::default();
+    let mut right_counters = JoinSideCounters::default();
+
+    let join_strategy = match hash_join_table {
+        Some(hash_join_table) => {
+            let (probe_row_source, probe_key_columns) = match build_side {
+                JoinSide::Left => (right_row_source, &right_key_columns),
+                JoinSide::Right => (left_row_source, &left_key_columns),
+            };
+            let (build_counters, probe_counters) = run_hash_join(
+                hash_join_table,
+                build_side,
+                probe_row_source,
+                probe_key_columns,
+                join_request.join_type,
+                &joined_column_layout,
+                &mut export_writer,
+            )?;
+            match build_side {
+                JoinSide::Left => (left_counters, right_counters) = (build_counters, probe_counters),
+                JoinSide::Right => (left_counters, right_counters) = (probe_counters, build_counters),
+            }
+            JoinStrategy::HashJoin { build_side }
+        }
+        None => {
+            // Step 3: Otherwise sort both sides by key and merge them
+            let work_directory_guard = JoinWorkDirectoryGuard {
+                work_directory_path: temporary_work_directory.join(format!(
+                    "dataset_join_{}_{}",
+                    std::process::id(),
+                    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or_default()
+                )),
+            };
+            let left_sorted_path = sort_join_side_by_key(left_row_source, &left_key_columns, join_request.memory_budget_bytes, &work_directory_guard.work_directory_path, "left")?;
+            let right_sorted_path = sort_join_side_by_key(right_row_source, &right_key_columns, join_request.memory_budget_bytes, &work_directory_guard.work_directory_path, "right")?;
+
+            run_sort_merge_join(
+                (&left_sorted_path, &left_key_columns, &mut left_counters),
+                (&right_sorted_path, &right_key_columns, &mut right_counters),
+                join_request.join_type,
+                &joined_column_layout,
+                &mut export_writer,
+            )?;
+            JoinStrategy::SortMergeJoin
+        }
+    };
+
+    // Step 4: Finish the file and its metadata
+    let (joined_row_count, metadata_file_path) = export_writer.finish()?;
+
+    Ok(DatasetJoinSummary {
+        joined_csv_file_path: output_csv_path.to_path_buf(),
+        metadata_file_path,
+        join_type: join_request.join_type,
+        join_strategy,
+        joined_column_names: joined_column_layout.column_names,
+        joined_row_count,
+        left_row_count: left_counters.row_count,
+        right_row_count: right_counters.row_count,
+        left_unmatched_row_count: left_counters.unmatched_row_count,
+        right_unmatched_row_count: right_counters.unmatched_row_count,
+        left_null_key_row_count: left_counters.null_key_row_count,
+        right_null_key_row_count: right_counters.null_key_row_count,
+    })
+}
+
+/// Prints what a join produced and how many rows found no partner
+///
+/// # Arguments
+/// * `join_summary` - The join outcome
+/// * `left_label` - Name of the left dataset
+/// * `right_label` - Name of the right dataset
+pub fn display_dataset_join_summary(join_summary: &DatasetJoinSummary, left_label: &str, right_label: &str) {
+    let strategy_description = match join_summary.join_strategy {
+        JoinStrategy::HashJoin { build_side: JoinSide::Left } => format!("hash join, '{}' held in memory", left_label),
+        JoinStrategy::HashJoin { build_side: JoinSide::Right } => format!("hash join, '{}' held in memory", right_label),
+        JoinStrategy::SortMergeJoin => "sort-merge join, both sides sorted on disk".to_string(),
+    };
+
+    println!();
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  {} join: {} ⋈ {} ({})", join_summary.join_type.short_name(), left_label, right_label, strategy_description);
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Rows written:  {}", join_summary.joined_row_count);
+    println!("  Columns:       {}", join_summary.joined_column_names.join(", "));
+    for (side_label, row_count, unmatched_row_count, null_key_row_count) in [
+        (left_label, join_summary.left_row_count, join_summary.left_unmatched_row_count, join_summary.left_null_key_row_count),
+        (right_label, join_summary.right_row_count, join_summary.right_unmatched_row_count, join_summary.right_null_key_row_count),
+    ] {
+        println!("  {}: {} rows, {} without a match ({} with an empty key)",
+            side_label,
+            row_count,
+            unmatched_row_count,
+            null_key_row_count
+        );
+    }
+    println!("  Output:   {}", join_summary.joined_csv_file_path.display());
+    println!("  Metadata: {}", join_summary.metadata_file_path.display());
+    println!();
+}
+
+/// Checks whether two key columns can be compared
+fn are_join_key_types_compatible(left_key_type: &CsvColumnDataType, right_key_type: &CsvColumnDataType) -> bool {
+    let is_numeric = |column_data_type: &CsvColumnDataType| {
+        matches!(column_data_type, CsvColumnDataType::Integer | CsvColumnDataType::Float)
+    };
+    left_key_type == right_key_type || (is_numeric(left_key_type) && is_numeric(right_key_type))
+}
+
+/// Decides the output columns, names and types
+///
+/// # Arguments
+/// * `left_columns` - Left column names and types
+/// * `right_columns` - Right column names and types
+/// * `join_request` - The key column pairs
+///
+/// # Returns
+/// * `JoinedColumnLayout` - Left columns then right columns, with same-named key
+///   pairs merged and other clashing names suffixed `_left` / `_right`
+fn build_joined_column_layout(
+    left_columns: (&[String], &[CsvColumnDataType]),
+    right_columns: (&[String], &[CsvColumnDataType]),
+    join_request: &DatasetJoinRequest,
+) -> JoinedColumnLayout {
+    let (left_column_names, left_column_types) = left_columns;
+    let (right_column_names, right_column_types) = right_columns;
+
+    // Step 1: Same-named key pairs become one column at the left key's place
+    let coalesced_key_pairs: Vec<(usize, usize)> = join_request.left_key_positions
+        .iter()
+        .zip(&join_request.right_key_positions)
+        .filter(|&(&left_position, &right_position)| left_column_names[left_position] == right_column_names[right_position])
+        .map(|(&left_position, &right_position)| (left_position, right_position))
+        .collect();
+
+    let mut column_sources = Vec::new();
+    for left_position in 0..left_column_names.len() {
+        match coalesced_key_pairs.iter().find(|&&(coalesced_left, _)| coalesced_left == left_position) {
+            Some(&(_, right_position)) => column_sources.push(JoinedColumnSource::CoalescedKey { left_position, right_position }),
+            None => column_sources.push(JoinedColumnSource::Left(left_position)),
+        }
+    }
+    for right_position in 0..right_column_names.len() {
+        if !coalesced_key_pairs.iter().any(|&(_, coalesced_right)| coalesced_right == right_position) {
+            column_sources.push(JoinedColumnSource::Right(right_position));
+        }
+    }
+
+    // Step 2: Names, suffixing names that appear on both sides
+    let clashes_with_other_side = |column_name: &str, other_side_names: &[String], other_coalesced: &dyn Fn(usize) -> bool| {
+        other_side_names
+            .iter()
+            .enumerate()
+            .any(|(other_position, other_name)| other_name == column_name && !other_coalesced(other_position))
+    };
+    let is_coalesced_left = |left_position: usize| coalesced_key_pairs.iter().any(|&(coalesced_left, _)| coalesced_left == left_position);
+    let is_coalesced_right = |right_position: usize| coalesced_key_pairs.iter().any(|&(_, coalesced_right)| coalesced_right == right_position);
+
+    let mut column_names = Vec::new();
+    let mut column_data_types = Vec::new();
+    for column_source in &column_sources {
+        match *column_source {
+            JoinedColumnSource::CoalescedKey { left_position, right_position } => {
+                column_names.push(left_column_names[left_position].clone());
+                let merged_type = if left_column_types[left_position] == right_column_types[right_position] {
+                    left_column_types[left_position].clone()
+                } else {
+                    CsvColumnDataType::Float
+                };
+                column_data_types.push(merged_type);
+            }
+            JoinedColumnSource::Left(left_position) => {
+                let column_name = &left_column_names[left_position];
+                if clashes_with_other_side(column_name, right_column_names, &is_coalesced_right) {
+                    column_names.push(format!("{}_left", column_name));
+                } else {
+                    column_names.push(column_name.clone());
+                }
+                column_data_types.push(left_column_types[left_position].clone());
+            }
+            JoinedColumnSource::Right(right_position) => {
+                let column_name = &right_column_names[right_position];
+                if clashes_with_other_side(column_name, left_column_names, &is_coalesced_left) {
+                    column_names.push(format!("{}_right", column_name));
+                } else {
+                    column_names.push(column_name.clone());
+                }
+                column_data_types.push(right_column_types[right_position].clone());
+            }
+        }
+    }
+
+    JoinedColumnLayout { column_sources, column_names, column_data_types }
+}
+
+/// Builds one output row from a left row, a right row, or both
+fn assemble_joined_row(
+    joined_column_layout: &JoinedColumnLayout,
+    left_field_values: Option<&[String]>,
+    right_field_values: Option<&[String]>,
+) -> Vec<String> {
+    let read_field = |field_values: Option<&[String]>, column_position: usize| {
+        field_values
+            .and_then(|present_values| present_values.get(column_position))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    joined_column_layout.column_sources
+        .iter()
+        .map(|column_source| match *column_source {
+            JoinedColumnSource::Left(left_position) => read_field(left_field_values, left_position),
+            JoinedColumnSource::Right(right_position) => read_field(right_field_values, right_position),
+            JoinedColumnSource::CoalescedKey { left_position, right_position } => match left_field_values {
+                Some(_) => read_field(left_field_values, left_position),
+                None => read_field(right_field_values, right_position),
+            },
+        })
+        .collect()
+}
+
+/// Reads a row's typed join key
+///
+/// # Arguments
+/// * `field_values` - The row's trimmed field values
+/// * `join_key_columns` - Key positions and column types for this side
+///
+/// # Returns
+/// * `Option<Vec<TypedKeyValue>>` - The key, or `None` when any key cell is
+///   empty or cannot be read as its key type
+fn read_join_key(field_values: &[String], join_key_columns: &JoinKeyColumns) -> Option<Vec<TypedKeyValue>> {
+    join_key_columns.key_positions
+        .iter()
+        .zip(join_key_columns.key_data_types)
+        .map(|(&key_position, key_data_type)| {
+            let cell_text = field_values.get(key_position).map(String::as_str).unwrap_or("");
+            read_typed_key_value(cell_text, Some(key_data_type))
+        })
+        .collect()
+}
+
+/// Orders two keys the same way the external sort orders their columns
+fn compare_join_keys(left_key: &[TypedKeyValue], right_key: &[TypedKeyValue]) -> Ordering {
+    for (left_part, right_part) in left_key.iter().zip(right_key) {
+        let part_ordering = compare_typed_key_values(left_part, right_part);
+        if part_ordering != Ordering::Equal {
+            return part_ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// The build side of a hash join
+struct HashJoinTable {
+    /// Every build row, in input order (rows with empty keys included)
+    build_rows: Vec<Vec<String>>,
+    /// Build row positions per key
+    row_positions_by_key: HashMap<Vec<TypedKeyValue>, Vec<usize>>,
+    /// Build rows with an empty or unreadable key
+    null_key_row_count: usize,
+}
+
+/// Loads one side into a hash table, giving up if it outgrows the budget
+///
+/// # Arguments
+/// * `build_row_source` - The side to load
+/// * `build_key_columns` - Key positions and types for that side
+/// * `memory_budget_bytes` - Estimated bytes the table may occupy
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Option<HashJoinTable>>` - The table, or `None` when
+///   the side does not fit
+fn build_hash_join_table(
+    build_row_source: &DatasetRowSource,
+    build_key_columns: &JoinKeyColumns,
+    memory_budget_bytes: usize,
+) -> RowsAndColumnsResult<Option<HashJoinTable>> {
+    let mut hash_join_table = HashJoinTable {
+        build_rows: Vec::new(),
+        row_positions_by_key: HashMap::new(),
+        null_key_row_count: 0,
+    };
+    let mut estimated_table_bytes = 0;
+    let mut exceeded_budget = false;
+
+    build_row_source.stream_rows(|_row_index, field_values| {
+        estimated_table_bytes += ESTIMATED_ROW_OVERHEAD_BYTES + field_values
+            .iter()
+            .map(|field_value| field_value.len() + ESTIMATED_STRING_OVERHEAD_BYTES)
+            .sum::<usize>();
+        if estimated_table_bytes > memory_budget_bytes {
+            exceeded_budget = true;
+            return Ok(CsvRowStreamControl::StopReading);
+        }
+
+        let build_row_position = hash_join_table.build_rows.len();
+        match read_join_key(field_values, build_key_columns) {
+            Some(join_key) => hash_join_table.row_positions_by_key.entry(join_key).or_default().push(build_row_position),
+            None => hash_join_table.null_key_row_count += 1,
+        }
+        hash_join_table.build_rows.push(field_values.to_vec());
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    Ok(if exceeded_budget { None } else { Some(hash_join_table) })
+}
+
+/// Streams the probe side past the hash table and writes the joined rows
+///
+/// # Arguments
+/// * `hash_join_table` - The loaded build side
+/// * `build_side` - Which side the table holds
+/// * `probe_row_source` - The other side
+/// * `probe_key_columns` - Key positions and types for the probe side
+/// * `join_type` - Which unmatched rows to keep
+/// * `joined_column_layout` - Output columns
+/// * `export_writer` - The output file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(JoinSideCounters, JoinSideCounters)>` - Counters for
+///   the build side and the probe side
+fn run_hash_join(
+    hash_join_table: HashJoinTable,
+    build_side: JoinSide,
+    probe_row_source: &DatasetRowSource,
+    probe_key_columns: &JoinKeyColumns,
+    join_type: JoinType,
+    joined_column_layout: &JoinedColumnLayout,
+    export_writer: &mut StreamingCsvExportWriter,
+) -> RowsAndColumnsResult<(JoinSideCounters, JoinSideCounters)> {
+    let probe_side = match build_side {
+        JoinSide::Left => JoinSide::Right,
+        JoinSide::Right => JoinSide::Left,
+    };
+    let mut build_row_was_matched = vec![false; hash_join_table.build_rows.len()];
+    let mut probe_counters = JoinSideCounters::default();
+
+    // Puts the build and probe rows on the correct sides of the output
+    let assemble_row = |build_row: Option<&[String]>, probe_row: Option<&[String]>| match build_side {
+        JoinSide::Left => assemble_joined_row(joined_column_layout, build_row, probe_row),
+        JoinSide::Right => assemble_joined_row(joined_column_layout, probe_row, build_row),
+    };
+
+    // Step 1: Probe with every row of the streamed side
+    probe_row_source.stream_rows(|_row_index, probe_field_values| {
+        probe_counters.row_count += 1;
+        let probe_key = read_join_key(probe_field_values, probe_key_columns);
+        if probe_key.is_none() {
+            probe_counters.null_key_row_count += 1;
+        }
+
+        let matching_positions = probe_key
+            .as_ref()
+            .and_then(|join_key| hash_join_table.row_positions_by_key.get(join_key));
+        match matching_positions {
+            Some(build_row_positions) => {
+                for &build_row_position in build_row_positions {
+                    build_row_was_matched[build_row_position] = true;
+                    export_writer.write_row(&assemble_row(
+                        Some(&hash_join_table.build_rows[build_row_position]),
+                        Some(probe_field_values),
+                    ))?;
+                }
+            }
+            None => {
+                probe_counters.unmatched_row_count += 1;
+                if join_type.keeps_unmatched_rows_of(probe_side) {
+                    export_writer.write_row(&assemble_row(None, Some(probe_field_values)))?;
+                }
+            }
+        }
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    // Step 2: Build rows that nothing matched
+    let mut build_counters = JoinSideCounters {
+        row_count: hash_join_table.build_rows.len(),
+        unmatched_row_count: 0,
+        null_key_row_count: hash_join_table.null_key_row_count,
+    };
+    for (build_row, was_matched) in hash_join_table.build_rows.iter().zip(&build_row_was_matched) {
+        if !was_matched {
+            build_counters.unmatched_row_count += 1;
+            if join_type.keeps_unmatched_rows_of(build_side) {
+                export_writer.write_row(&assemble_row(Some(build_row), None))?;
+            }
+        }
+    }
+
+    Ok((build_counters, probe_counters))
+}
+
+/// Sorts one side by its key columns into the work directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path of the sorted CSV copy
+fn sort_join_side_by_key(
+    join_row_source: &DatasetRowSource,
+    join_key_columns: &JoinKeyColumns,
+    memory_budget_bytes: usize,
+    work_directory_path: &Path,
+    side_name: &str,
+) -> RowsAndColumnsResult<PathBuf> {
+    let sorted_csv_path = work_directory_path.join(format!("{}_sorted_by_key.csv", side_name));
+    // The keys are read as the join reads them, so the merge sees them in order
+    let sort_keys = join_key_columns.key_positions
+        .iter()
+        .zip(join_key_columns.key_data_types)
+        .map(|(&column_position, key_data_type)| SortKeySpecification {
+            column_position,
+            sort_direction: SortDirection::Ascending,
+            null_placement: NullPlacement::NullsFirst,
+            key_data_type: Some(key_data_type.clone()),
+        })
+        .collect();
+
+    perform_external_sort(
+        join_row_source,
+        &ExternalSortRequest { sort_keys, memory_budget_bytes },
+        work_directory_path,
+        &sorted_csv_path,
+    )?;
+
+    Ok(sorted_csv_path)
+}
+
+/// Reads a sorted CSV copy one row at a time, on demand
+struct SortedCsvRowReader {
+    csv_lines: Lines<BufReader<File>>,
+    sorted_csv_path: PathBuf,
+}
+
+impl SortedCsvRowReader {
+    /// Opens a sorted copy and skips its header row
+    fn open(sorted_csv_path: &Path) -> RowsAndColumnsResult<SortedCsvRowReader> {
+        let sorted_csv_file = File::open(sorted_csv_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to open sorted join input: {}", sorted_csv_path.display()), io_error)
+        })?;
+        let mut csv_lines = BufReader::new(sorted_csv_file).lines();
+        csv_lines.next();
+        Ok(SortedCsvRowReader { csv_lines, sorted_csv_path: sorted_csv_path.to_path_buf() })
+    }
+
+    /// Returns the next row's trimmed fields, or `None` at the end
+    fn next_row(&mut self) -> RowsAndColumnsResult<Option<Vec<String>>> {
+        match self.csv_lines.next() {
+            None => Ok(None),
+            Some(line_result) => {
+                let csv_line = line_result.map_err(|io_error| {
+                    create_file_system_error(&format!("Failed to read sorted join input: {}", self.sorted_csv_path.display()), io_error)
+                })?;
+                Ok(Some(
+                    parse_csv_line_into_fields(&csv_line)
+                        .iter()
+                        .map(|field_value| field_value.trim().to_string())
+                        .collect()
+                ))
+            }
+        }
+    }
+}
+
+/// Merges two key-sorted copies and writes the joined rows
+///
+/// Both inputs are read in key order. Equal keys form a group: the right
+/// group is buffered and every left row of the same key is paired with it.
+/// Rows with empty keys can appear anywhere and never match.
+///
+/// # Arguments
+/// * `left_input` - Left sorted copy, key columns and counters
+/// * `right_input` - Right sorted copy, key columns and counters
+/// * `join_type` - Which unmatched rows to keep
+/// * `joined_column_layout` - Output columns
+/// * `export_writer` - The output file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or a read or write error
+fn run_sort_merge_join(
+    left_input: (&Path, &JoinKeyColumns, &mut JoinSideCounters),
+    right_input: (&Path, &JoinKeyColumns, &mut JoinSideCounters),
+    join_type: JoinType,
+    joined_column_layout: &JoinedColumnLayout,
+    export_writer: &mut StreamingCsvExportWriter,
+) -> RowsAndColumnsResult<()> {
+    let (left_sorted_path, left_key_columns, left_counters) = left_input;
+    let (right_sorted_path, right_key_columns, right_counters) = right_input;
+    let mut left_reader = SortedCsvRowReader::open(left_sorted_path)?;
+    let mut right_reader = SortedCsvRowReader::open(right_sorted_path)?;
+
+    // Reads the next row of a side with its key, counting rows and empty keys
+    let read_keyed_row = |row_reader: &mut SortedCsvRowReader, key_columns: &JoinKeyColumns, side_counters: &mut JoinSideCounters| {
+        row_reader.next_row().map(|optional_row| {
+            optional_row.map(|field_values| {
+                side_counters.row_count += 1;
+                let join_key = read_join_key(&field_values, key_columns);
+                if join_key.is_none() {
+                    side_counters.null_key_row_count += 1;
+                }
+                (join_key, field_values)
+            })
+        })
+    };
+
+    let mut current_left_row = read_keyed_row(&mut left_reader, left_key_columns, left_counters)?;
+    let mut current_right_row = read_keyed_row(&mut right_reader, right_key_columns, right_counters)?;
+
+    loop {
+        // Step 1: Decide which side is behind (rows with empty keys are never matched)
+        let key_ordering = match (&current_left_row, &current_right_row) {
+            (None, None) => break,
+            (Some((None, _)), _) | (Some(_), None) => Ordering::Less,
+            (_, Some((None, _))) | (None, Some(_)) => Ordering::Greater,
+            (Some((Some(left_key), _)), Some((Some(right_key), _))) => compare_join_keys(left_key, right_key),
+        };
+
+        match key_ordering {
+            Ordering::Less => {
+                if let Some((_, left_field_values)) = &current_left_row {
+                    left_counters.unmatched_row_count += 1;
+                    if join_type.keeps_unmatched_rows_of(JoinSide::Left) {
+                        export_writer.write_row(&assemble_joined_row(joined_column_layout, Some(left_field_values), None))?;
+                    }
+                }
+                current_left_row = read_keyed_row(&mut left_reader, left_key_columns, left_counters)?;
+            }
+            Ordering::Greater => {
+                if let Some((_, right_field_values)) = &current_right_row {
+                    right_counters.unmatched_row_count += 1;
+                    if join_type.keeps_unmatched_rows_of(JoinSide::Right) {
+                        export_writer.write_row(&assemble_joined_row(joined_column_layout, None, Some(right_field_values)))?;
+                    }
+                }
+                current_right_row = read_keyed_row(&mut right_reader, right_key_columns, right_counters)?;
+            }
+            Ordering::Equal => {
+                // Step 2: Buffer the right group sharing this key
+                let Some((Some(group_key), first_right_values)) = current_right_row.take() else { break };
+                let mut right_group_rows = vec![first_right_values];
+                loop {
+                    current_right_row = read_keyed_row(&mut right_reader, right_key_columns, right_counters)?;
+                    match &current_right_row {
+                        Some((Some(next_key), next_values)) if compare_join_keys(next_key, &group_key) == Ordering::Equal => {
+                            right_group_rows.push(next_values.clone());
+                        }
+                        _ => break,
+                    }
+                }
+
+                // Step 3: Pair every left row of the same key with the group
+                while let Some((Some(left_key), left_field_values)) = &current_left_row
+                    && compare_join_keys(left_key, &group_key) == Ordering::Equal
+                {
+                    for right_field_values in &right_group_rows {
+                        export_writer.write_row(&assemble_joined_row(
+                            joined_column_layout,
+                            Some(left_field_values),
+                            Some(right_field_values),
+                        ))?;
+                    }
+                    current_left_row = read_keyed_row(&mut left_reader, left_key_columns, left_counters)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixture_module::write_test_csv_analysis_results;
+
+    /// Runs a full outer join with the given budget and returns the sorted output lines
+    fn run_full_outer_join(test_directory: &Path, memory_budget_bytes: usize) -> (DatasetJoinSummary, Vec<String>) {
+        let cats = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "owner_id,name\n1,Tom\n2,Ann\n2,Bob\n,Zed\n4,Cy\n",
+            &[CsvColumnDataType::Integer, CsvColumnDataType::String],
+        );
+        let owners = write_test_csv_analysis_results(
+            &test_directory.join("owners.csv"),
+            "owner_id,name\n2.0,Pat\n1,Lee\n3,Kim\n",
+            &[CsvColumnDataType::Float, CsvColumnDataType::String],
+        );
+
+        let output_csv_path = test_directory.join(format!("joined_{}.csv", memory_budget_bytes));
+        let join_summary = perform_dataset_join(
+            &DatasetRowSource::CsvFile(&cats),
+            &DatasetRowSource::CsvFile(&owners),
+            &DatasetJoinRequest {
+                join_type: JoinType::FullOuter,
+                left_key_positions: vec![0],
+                right_key_positions: vec![0],
+                memory_budget_bytes,
+            },
+            &test_directory.join("work"),
+            &output_csv_path,
+        ).expect("join should succeed");
+
+        let mut output_lines: Vec<String> = std::fs::read_to_string(&output_csv_path)
+            .expect("read output")
+            .lines()
+            .map(str::to_string)
+            .collect();
+        output_lines[1..].sort();
+        (join_summary, output_lines)
+    }
+
+    /// Hash join and sort-merge join give the same rows, names and counts
+    #[test]
+    fn test_hash_and_sort_merge_joins_agree() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_join_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+
+        let (hash_summary, hash_lines) = run_full_outer_join(&test_directory, DEFAULT_JOIN_MEMORY_BUDGET_BYTES);
+        let (merge_summary, merge_lines) = run_full_outer_join(&test_directory, 1);
+
+        assert!(matches!(hash_summary.join_strategy, JoinStrategy::HashJoin { .. }));
+        assert_eq!(merge_summary.join_strategy, JoinStrategy::SortMergeJoin);
+        assert_eq!(hash_lines, merge_lines);
+        assert_eq!(hash_lines, vec![
+            "owner_id,name_left,name_right",
+            ",Zed,",
+            "1,Tom,Lee",
+            "2,Ann,Pat",
+            "2,Bob,Pat",
+            "3,,Kim",
+            "4,Cy,",
+        ]);
+
+        for join_summary in [&hash_summary, &merge_summary] {
+            assert_eq!(join_summary.joined_row_count, 6);
+            assert_eq!(join_summary.left_unmatched_row_count, 2);
+            assert_eq!(join_summary.left_null_key_row_count, 1);
+            assert_eq!(join_summary.right_unmatched_row_count, 1);
+        }
+
+        // The sorted copies are removed after a sort-merge join
+        assert_eq!(std::fs::read_dir(test_directory.join("work")).map(|entries| entries.count()).unwrap_or(0), 0);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Inner joins drop unmatched rows; differently named keys stay separate
+    #[test]
+    fn test_inner_join_with_differently_named_keys() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_inner_join_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let cats = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "cat,is_indoor\nTom,yes\nAnn,no\n",
+            &[CsvColumnDataType::String, CsvColumnDataType::Boolean],
+        );
+        let rules = write_test_csv_analysis_results(
+            &test_directory.join("rules.csv"),
+            "indoor,rule\nTRUE,stay in\n",
+            &[CsvColumnDataType::Boolean, CsvColumnDataType::String],
+        );
+
+        let output_csv_path = test_directory.join("inner.csv");
+        let join_summary = perform_dataset_join(
+            &DatasetRowSource::CsvFile(&cats),
+            &DatasetRowSource::CsvFile(&rules),
+            &DatasetJoinRequest {
+                join_type: JoinType::Inner,
+                left_key_positions: vec![1],
+                right_key_positions: vec![0],
+                memory_budget_bytes: DEFAULT_JOIN_MEMORY_BUDGET_BYTES,
+            },
+            &test_directory.join("work"),
+            &output_csv_path,
+        ).expect("join should succeed");
+
+        assert_eq!(join_summary.joined_row_count, 1);
+        assert_eq!(join_summary.left_unmatched_row_count, 1);
+        assert_eq!(
+            std::fs::read_to_string(&output_csv_path).expect("read output"),
+            "cat,is_indoor,indoor,rule\nTom,yes,TRUE,stay in\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Keys of incompatible types are rejected before any row is read
+    #[test]
+    fn test_join_rejects_incompatible_key_types() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_join_types_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let cats = write_test_csv_analysis_results(&test_directory.join("cats.csv"), "id\n1\n", &[CsvColumnDataType::Integer]);
+        let names = write_test_csv_analysis_results(&test_directory.join("names.csv"), "id\nTom\n", &[CsvColumnDataType::String]);
+
+        let join_result = perform_dataset_join(
+            &DatasetRowSource::CsvFile(&cats),
+            &DatasetRowSource::CsvFile(&names),
+            &DatasetJoinRequest {
+                join_type: JoinType::Left,
+                left_key_positions: vec![0],
+                right_key_positions: vec![0],
+                memory_budget_bytes: DEFAULT_JOIN_MEMORY_BUDGET_BYTES,
+            },
+            &test_directory.join("work"),
+            &test_directory.join("never_written.csv"),
+        );
+
+        match join_result {
+            Err(join_error) => assert!(join_error.to_string().contains("different types"), "{}", join_error),
+            Ok(_) => panic!("Expected a type mismatch error"),
+        }
+        assert!(!test_directory.join("never_written.csv").exists());
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Integer IDs above 2^53 that differ only in the last digit never match each other
+    #[test]
+    fn test_large_integer_keys_match_exactly() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_join_large_id_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let cats = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "id,name
+9007199254740993,Tom
+9007199254740992,Ann
+",
+            &[CsvColumnDataType::Integer, CsvColumnDataType::String],
+        );
+        let owners = write_test_csv_analysis_results(
+            &test_directory.join("owners.csv"),
+            "id,owner
+9007199254740992,Pat
+",
+            &[CsvColumnDataType::Integer, CsvColumnDataType::String],
+        );
+
+        // Both strategies: the hash table and the sorted merge
+        for memory_budget_bytes in [DEFAULT_JOIN_MEMORY_BUDGET_BYTES, 1] {
+            let output_csv_path = test_directory.join(format!("joined_{}.csv", memory_budget_bytes));
+            let join_summary = perform_dataset_join(
+                &DatasetRowSource::CsvFile(&cats),
+                &DatasetRowSource::CsvFile(&owners),
+                &DatasetJoinRequest {
+                    join_type: JoinType::Inner,
+                    left_key_positions: vec![0],
+                    right_key_positions: vec![0],
+                    memory_budget_bytes,
+                },
+                &test_directory.join("work"),
+                &output_csv_path,
+            ).expect("join should succeed");
+
+            assert_eq!(join_summary.joined_row_count, 1, "budget {}", memory_budget_bytes);
+            assert_eq!(
+                std::fs::read_to_string(&output_csv_path).expect("read output"),
+                "id,name,owner\n9007199254740992,Ann,Pat\n"
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// A non-integer value in a column typed Integer still joins a Float key in both strategies
+    #[test]
+    fn test_integer_column_with_fractional_value_joins_float_key() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_join_fraction_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        // Integer by the 70% rule, but one cell is 3.5
+        let cats = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "owner_id,name\n5,Cy\n3.5,Tom\n1,Ann\n",
+            &[CsvColumnDataType::Integer, CsvColumnDataType::String],
+        );
+        let owners = write_test_csv_analysis_results(
+            &test_directory.join("owners.csv"),
+            "owner_id,owner\n1.0,Pat\n3.5,Lee\n5.0,Kim\n",
+            &[CsvColumnDataType::Float, CsvColumnDataType::String],
+        );
+
+        for memory_budget_bytes in [DEFAULT_JOIN_MEMORY_BUDGET_BYTES, 1] {
+            let output_csv_path = test_directory.join(format!("joined_{}.csv", memory_budget_bytes));
+            let join_summary = perform_dataset_join(
+                &DatasetRowSource::CsvFile(&cats),
+                &DatasetRowSource::CsvFile(&owners),
+                &DatasetJoinRequest {
+                    join_type: JoinType::FullOuter,
+                    left_key_positions: vec![0],
+                    right_key_positions: vec![0],
+                    memory_budget_bytes,
+                },
+                &test_directory.join("work"),
+                &output_csv_path,
+            ).expect("join should succeed");
+
+            assert_eq!(join_summary.joined_row_count, 3, "budget {}", memory_budget_bytes);
+            assert_eq!(join_summary.left_unmatched_row_count, 0, "budget {}", memory_budget_bytes);
+            assert_eq!(join_summary.right_unmatched_row_count, 0, "budget {}", memory_budget_bytes);
+            let mut output_lines: Vec<String> = std::fs::read_to_string(&output_csv_path)
+                .expect("read output")
+                .lines()
+                .map(str::to_string)
+                .collect();
+            output_lines[1..].sort();
+            assert_eq!(output_lines, vec!["owner_id,name,owner", "1,Ann,Pat", "3.5,Tom,Lee", "5,Cy,Kim"]);
+        }
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
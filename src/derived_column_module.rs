@@ -0,0 +1,1794 @@
+// src/derived_column_module.rs
+
+/// Derived (computed) columns for rows_and_columns ("age_months = age * 12")
+///
+/// A derived column is defined by a name and an expression over the other
+/// columns. The definition is type-checked once and saved as a
+/// `[derived_column_N]` table in the CSV's `.csv_metadata.toml`, so it is
+/// still there the next time the file is analyzed.
+///
+/// # Core Responsibilities
+/// - Tokenize and parse expressions, reporting errors with a caret position
+/// - Resolve column names and determine the result type of every expression
+/// - Evaluate the compiled expressions against one row at a time
+/// - Materialize derived columns on the fly during analysis (statistics are
+///   computed from the streamed values; nothing is written) or into the
+///   directory store as real column directories
+///
+/// # Expression Syntax
+/// - Arithmetic: `+ - * / %` and unary `-`; `/` always gives a float
+/// - Text: `||` joins values as text (`upper(name) || "-" || breed`)
+/// - Comparisons `= != < <= > >=`, logic `and`, `or`, `not`, parentheses
+/// - Conditionals: `if(age > 3, "adult", "young")` and
+///   `case when age > 3 then "adult" when age > 1 then "young" else "kitten" end`
+/// - Text functions: `upper`, `lower`, `trim`, `length`, `substr(text, start[, length])`,
+///   `replace(text, from, to)`, `concat(a, b, ...)`
+/// - Number functions: `abs`, `round(x[, digits])`, `floor`, `ceil`, `sqrt`, `pow(x, y)`
+/// - Casts: `int(x)`, `float(x)`, `text(x)`, `bool(x)`; `coalesce(a, b, ...)`
+/// - Column names are bare words or backquoted (`` `first name` ``); earlier
+///   derived columns can be used by later ones
+///
+/// # Design Philosophy
+/// - Typed: the result type is known before any row is read and is what the
+///   metadata records; mixing integer and float gives float
+/// - Empty or unreadable cells are missing values that flow through arithmetic
+///   and functions (`coalesce` and `concat` skip them); a failed cast, a
+///   division by zero or an overflow also gives a missing value, never an error
+/// - Streaming: only the current row is held in memory
+use std::cmp::Ordering;
+use std::path::Path;
+
+// Import CSV structures, the shared row streaming loop and per-column statistics
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvColumnInformation,
+    CsvRowStreamControl,
+    EnhancedCsvColumnInformation,
+    build_enhanced_column_information,
+    stream_csv_data_rows,
+};
+
+// Import the saved definitions in the metadata file
+use super::metadata_manager_module::{
+    DerivedColumnDefinition,
+    read_derived_column_definitions,
+    write_derived_column_definitions,
+};
+
+// Import the directory store for materialized derived columns
+use super::directory_store_module::{
+    DirectoryStoreDataset,
+    add_computed_column_to_directory_store,
+    import_csv_into_directory_store,
+};
+
+// Import warnings for derived columns skipped during an import
+use super::console_output_module::print_warning_line;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsError,
+    RowsAndColumnsResult,
+    create_derived_column_expression_error,
+};
+
+/// Number of sample values kept for each derived column
+const DERIVED_COLUMN_SAMPLE_VALUE_COUNT: usize = 5;
+
+/// Keywords of the expression language (matched case-insensitively)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DerivedKeyword {
+    And,
+    Or,
+    Not,
+    True,
+    False,
+    Case,
+    When,
+    Then,
+    Else,
+    End,
+}
+
+impl DerivedKeyword {
+    /// Looks up a bare word as a keyword
+    fn from_word(word_text: &str) -> Option<DerivedKeyword> {
+        match word_text.to_ascii_lowercase().as_str() {
+            "and" => Some(DerivedKeyword::And),
+            "or" => Some(DerivedKeyword::Or),
+            "not" => Some(DerivedKeyword::Not),
+            "true" => Some(DerivedKeyword::True),
+            "false" => Some(DerivedKeyword::False),
+            "case" => Some(DerivedKeyword::Case),
+            "when" => Some(DerivedKeyword::When),
+            "then" => Some(DerivedKeyword::Then),
+            "else" => Some(DerivedKeyword::Else),
+            "end" => Some(DerivedKeyword::End),
+            _ => None,
+        }
+    }
+}
+
+/// Binary operators, from loosest to tightest binding group
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DerivedOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Concatenate,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl DerivedOperator {
+    /// Returns the operator as written, for error messages
+    fn as_operator_text(&self) -> &'static str {
+        match self {
+            DerivedOperator::Equal => "=",
+            DerivedOperator::NotEqual => "!=",
+            DerivedOperator::Less => "<",
+            DerivedOperator::LessOrEqual => "<=",
+            DerivedOperator::Greater => ">",
+            DerivedOperator::GreaterOrEqual => ">=",
+            DerivedOperator::Concatenate => "||",
+            DerivedOperator::Add => "+",
+            DerivedOperator::Subtract => "-",
+            DerivedOperator::Multiply => "*",
+            DerivedOperator::Divide => "/",
+            DerivedOperator::Remainder => "%",
+        }
+    }
+
+    /// Whether this is one of the six comparison operators
+    fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            DerivedOperator::Equal
+                | DerivedOperator::NotEqual
+                | DerivedOperator::Less
+                | DerivedOperator::LessOrEqual
+                | DerivedOperator::Greater
+                | DerivedOperator::GreaterOrEqual
+        )
+    }
+}
+
+/// What a token is
+#[derive(Debug, Clone, PartialEq)]
+enum DerivedTokenKind {
+    /// A bare word that is not a keyword: a column or function name
+    Word(String),
+    /// A backquoted column name
+    QuotedColumnName(String),
+    IntegerLiteral(i64),
+    FloatLiteral(f64),
+    /// A quoted text literal, with escapes resolved
+    TextLiteral(String),
+    Keyword(DerivedKeyword),
+    Operator(DerivedOperator),
+    OpenParenthesis,
+    CloseParenthesis,
+    Comma,
+    EndOfExpression,
+}
+
+/// One token with the character position where it starts
+#[derive(Debug, Clone)]
+struct DerivedToken {
+    token_kind: DerivedTokenKind,
+    character_position: usize,
+}
+
+/// A value during evaluation
+#[derive(Debug, Clone, PartialEq)]
+enum DerivedValue {
+    Missing,
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Text(String),
+}
+
+impl DerivedValue {
+    /// Returns the value as a float if it is a number
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            DerivedValue::Integer(integer_value) => Some(*integer_value as f64),
+            DerivedValue::Float(float_value) => Some(*float_value),
+            _ => None,
+        }
+    }
+
+    /// Formats the value as a CSV cell (missing values are empty)
+    fn to_cell_text(&self) -> String {
+        match self {
+            DerivedValue::Missing => String::new(),
+            DerivedValue::Integer(integer_value) => integer_value.to_string(),
+            DerivedValue::Float(float_value) => float_value.to_string(),
+            DerivedValue::Boolean(boolean_value) => boolean_value.to_string(),
+            DerivedValue::Text(text_value) => text_value.clone(),
+        }
+    }
+}
+
+/// Built-in functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DerivedFunction {
+    Upper,
+    Lower,
+    Trim,
+    Length,
+    Substring,
+    Replace,
+    ConcatenateAll,
+    Absolute,
+    Round,
+    Floor,
+    Ceiling,
+    SquareRoot,
+    Power,
+    Coalesce,
+    CastToInteger,
+    CastToFloat,
+    CastToText,
+    CastToBoolean,
+}
+
+impl DerivedFunction {
+    /// Looks up a function by name (case-insensitive); `if` is handled by the parser
+    fn from_name(function_name: &str) -> Option<DerivedFunction> {
+        match function_name.to_ascii_lowercase().as_str() {
+            "upper" => Some(DerivedFunction::Upper),
+            "lower" => Some(DerivedFunction::Lower),
+            "trim" => Some(DerivedFunction::Trim),
+            "length" | "len" => Some(DerivedFunction::Length),
+            "substr" | "substring" => Some(DerivedFunction::Substring),
+            "replace" => Some(DerivedFunction::Replace),
+            "concat" => Some(DerivedFunction::ConcatenateAll),
+            "abs" => Some(DerivedFunction::Absolute),
+            "round" => Some(DerivedFunction::Round),
+            "floor" => Some(DerivedFunction::Floor),
+            "ceil" | "ceiling" => Some(DerivedFunction::Ceiling),
+            "sqrt" => Some(DerivedFunction::SquareRoot),
+            "pow" | "power" => Some(DerivedFunction::Power),
+            "coalesce" => Some(DerivedFunction::Coalesce),
+            "int" | "integer" | "to_integer" => Some(DerivedFunction::CastToInteger),
+            "float" | "to_float" => Some(DerivedFunction::CastToFloat),
+            "text" | "string" | "to_text" => Some(DerivedFunction::CastToText),
+            "bool" | "boolean" | "to_boolean" => Some(DerivedFunction::CastToBoolean),
+            _ => None,
+        }
+    }
+}
+
+/// A compiled, type-checked expression tree
+#[derive(Debug, Clone)]
+enum DerivedExpressionNode {
+    Literal(DerivedValue),
+    ColumnReference {
+        column_position: usize,
+        column_data_type: CsvColumnDataType,
+    },
+    Negate(Box<DerivedExpressionNode>),
+    Not(Box<DerivedExpressionNode>),
+    And(Box<DerivedExpressionNode>, Box<DerivedExpressionNode>),
+    Or(Box<DerivedExpressionNode>, Box<DerivedExpressionNode>),
+    Binary {
+        binary_operator: DerivedOperator,
+        left_node: Box<DerivedExpressionNode>,
+        right_node: Box<DerivedExpressionNode>,
+        /// Integer arithmetic stays integer; everything else is float
+        result_is_integer: bool,
+    },
+    /// `if(...)` and `case when ... end`: the first true condition wins
+    Conditional {
+        condition_branches: Vec<(DerivedExpressionNode, DerivedExpressionNode)>,
+        else_node: Option<Box<DerivedExpressionNode>>,
+    },
+    FunctionCall {
+        derived_function: DerivedFunction,
+        argument_nodes: Vec<DerivedExpressionNode>,
+    },
+}
+
+/// One derived column ready to be evaluated
+#[derive(Debug, Clone)]
+struct CompiledDerivedColumn {
+    derived_column_definition: DerivedColumnDefinition,
+    root_expression_node: DerivedExpressionNode,
+}
+
+/// A list of derived columns compiled against a set of base columns
+///
+/// Column references point either at a base column or at an earlier
+/// derived column (positions after the base columns).
+#[derive(Debug, Clone)]
+pub struct CompiledDerivedColumns {
+    /// Number of base columns the expressions were compiled against
+    base_column_count: usize,
+
+    /// Derived columns in definition order
+    compiled_columns: Vec<CompiledDerivedColumn>,
+}
+
+impl CompiledDerivedColumns {
+    /// Returns the definitions in order
+    ///
+    /// # Returns
+    /// * `Vec<&DerivedColumnDefinition>` - Names, expressions and types
+    pub fn derived_column_definitions(&self) -> Vec<&DerivedColumnDefinition> {
+        self.compiled_columns
+            .iter()
+            .map(|compiled_column| &compiled_column.derived_column_definition)
+            .collect()
+    }
+
+    /// Computes every derived column for one row
+    ///
+    /// # Arguments
+    /// * `field_values` - The row's trimmed base field values, in column order
+    ///
+    /// # Returns
+    /// * `Vec<String>` - One cell per derived column (empty for missing values)
+    pub fn evaluate_row(&self, field_values: &[String]) -> Vec<String> {
+        let mut extended_row_values: Vec<String> = (0..self.base_column_count)
+            .map(|column_position| field_values.get(column_position).cloned().unwrap_or_default())
+            .collect();
+
+        for compiled_column in &self.compiled_columns {
+            let derived_value = evaluate_derived_node(&compiled_column.root_expression_node, &extended_row_values);
+            extended_row_values.push(derived_value.to_cell_text());
+        }
+
+        extended_row_values.split_off(self.base_column_count)
+    }
+}
+
+/// Parses `name = expression`, checks it, and returns the definition to save
+///
+/// # Arguments
+/// * `definition_text` - e.g. `age_months = age * 12`
+/// * `column_names` - Names the expression may use (base then derived columns)
+/// * `column_data_types` - Their types, in the same order
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DerivedColumnDefinition>` - Name, expression and result type
+///
+/// # Errors
+/// * `DerivedColumnExpressionError` - A missing `=`, an empty or taken name, or
+///   a syntax or type error in the expression, with the character position
+pub fn parse_derived_column_definition(
+    definition_text: &str,
+    column_names: &[String],
+    column_data_types: &[CsvColumnDataType],
+) -> RowsAndColumnsResult<DerivedColumnDefinition> {
+    // Step 1: Split the name from the expression
+    let Some((raw_column_name, raw_expression_text)) = definition_text.split_once('=') else {
+        return Err(create_derived_column_expression_error(
+            definition_text,
+            definition_text.chars().count(),
+            "expected 'name = expression'"
+        ));
+    };
+    let derived_column_name = raw_column_name.trim().trim_matches('`').to_string();
+    if derived_column_name.is_empty() {
+        return Err(create_derived_column_expression_error(definition_text, 0, "the new column needs a name before '='"));
+    }
+    if column_names.contains(&derived_column_name) {
+        return Err(create_derived_column_expression_error(
+            definition_text,
+            0,
+            &format!("a column named '{}' already exists", derived_column_name)
+        ));
+    }
+
+    // Step 2: Check the expression and record its type
+    let derived_expression_text = raw_expression_text.trim().to_string();
+    let (_, derived_data_type) = compile_derived_expression(&derived_expression_text, column_names, column_data_types)?;
+
+    Ok(DerivedColumnDefinition {
+        derived_column_name,
+        derived_expression_text,
+        derived_data_type,
+    })
+}
+
+/// Compiles saved definitions against the base columns, in order
+///
+/// # Arguments
+/// * `column_names` - Base column names
+/// * `column_data_types` - Base column types
+/// * `derived_column_definitions` - Definitions in the order they were saved
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CompiledDerivedColumns>` - The compiled columns
+///
+/// # Errors
+/// * `DerivedColumnExpressionError` - If a saved expression no longer checks
+///   (e.g. a column it uses was renamed), or its type changed
+pub fn compile_derived_columns(
+    column_names: &[String],
+    column_data_types: &[CsvColumnDataType],
+    derived_column_definitions: &[DerivedColumnDefinition],
+) -> RowsAndColumnsResult<CompiledDerivedColumns> {
+    let mut scope_column_names = column_names.to_vec();
+    let mut scope_column_types = column_data_types.to_vec();
+    let mut compiled_columns = Vec::new();
+
+    for derived_column_definition in derived_column_definitions {
+        let (root_expression_node, compiled_data_type) = compile_derived_expression(
+            &derived_column_definition.derived_expression_text,
+            &scope_column_names,
+            &scope_column_types,
+        )?;
+        if compiled_data_type != derived_column_definition.derived_data_type {
+            return Err(create_derived_column_expression_error(
+                &derived_column_definition.derived_expression_text,
+                0,
+                &format!(
+                    "'{}' now gives {} values but was saved as {}",
+                    derived_column_definition.derived_column_name,
+                    compiled_data_type.to_toml_string(),
+                    derived_column_definition.derived_data_type.to_toml_string()
+                )
+            ));
+        }
+
+        scope_column_names.push(derived_column_definition.derived_column_name.clone());
+        scope_column_types.push(compiled_data_type);
+        compiled_columns.push(CompiledDerivedColumn {
+            derived_column_definition: derived_column_definition.clone(),
+            root_expression_node,
+        });
+    }
+
+    Ok(CompiledDerivedColumns {
+        base_column_count: column_names.len(),
+        compiled_columns,
+    })
+}
+
+/// Appends a definition to the derived columns saved in a metadata file
+///
+/// # Arguments
+/// * `metadata_file_path` - The CSV's `.csv_metadata.toml`
+/// * `derived_column_definition` - The new definition
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<DerivedColumnDefinition>>` - All saved definitions
+pub fn save_derived_column_definition(
+    metadata_file_path: &Path,
+    derived_column_definition: &DerivedColumnDefinition,
+) -> RowsAndColumnsResult<Vec<DerivedColumnDefinition>> {
+    let mut derived_column_definitions = read_derived_column_definitions(metadata_file_path)?;
+    derived_column_definitions.push(derived_column_definition.clone());
+    write_derived_column_definitions(metadata_file_path, &derived_column_definitions)?;
+    Ok(derived_column_definitions)
+}
+
+/// Computes statistics for derived columns while streaming the CSV
+///
+/// The derived values are held only long enough to compute the statistics;
+/// nothing is written to disk.
+///
+/// # Arguments
+/// * `csv_analysis_results` - The analyzed base CSV
+/// * `compiled_derived_columns` - Derived columns compiled against its columns
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - One entry per derived
+///   column, numbered after the base columns
+pub fn analyze_derived_columns(
+    csv_analysis_results: &CsvAnalysisResults,
+    compiled_derived_columns: &CompiledDerivedColumns,
+) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+    let derived_column_count = compiled_derived_columns.compiled_columns.len();
+    let mut derived_column_values: Vec<Vec<String>> = vec![Vec::new(); derived_column_count];
+
+    stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_row_index, field_values| {
+            for (column_values, derived_cell) in derived_column_values.iter_mut().zip(compiled_derived_columns.evaluate_row(field_values)) {
+                column_values.push(derived_cell);
+            }
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    compiled_derived_columns.compiled_columns
+        .iter()
+        .zip(&derived_column_values)
+        .enumerate()
+        .map(|(derived_position, (compiled_column, column_values))| {
+            let definition = &compiled_column.derived_column_definition;
+            let empty_value_count = column_values.iter().filter(|cell_value| cell_value.is_empty()).count();
+            let basic_column_info = CsvColumnInformation {
+                column_index: csv_analysis_results.total_column_count + derived_position,
+                column_name: definition.derived_column_name.clone(),
+                detected_data_type: definition.derived_data_type.clone(),
+                non_empty_value_count: column_values.len() - empty_value_count,
+                empty_value_count,
+                sample_values: column_values
+                    .iter()
+                    .filter(|cell_value| !cell_value.is_empty())
+                    .take(DERIVED_COLUMN_SAMPLE_VALUE_COUNT)
+                    .cloned()
+                    .collect(),
+            };
+            build_enhanced_column_information(&basic_column_info, column_values)
+        })
+        .collect()
+}
+
+/// Writes a derived column into a directory store dataset as a real column
+///
+/// The expression is compiled against the dataset's own columns, so derived
+/// columns already materialized there can be used.
+///
+/// # Arguments
+/// * `store_dataset` - The dataset to extend
+/// * `derived_column_definition` - The column to add
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The extended dataset
+///
+/// # Errors
+/// * `DerivedColumnExpressionError` - If the expression does not check against the dataset
+/// * `MetadataError` / `FileSystemError` - If the column cannot be added
+pub fn materialize_derived_column_into_directory_store(
+    store_dataset: &DirectoryStoreDataset,
+    derived_column_definition: &DerivedColumnDefinition,
+) -> RowsAndColumnsResult<DirectoryStoreDataset> {
+    let compiled_derived_columns = compile_derived_columns(
+        &store_dataset.column_names,
+        &store_dataset.column_data_types,
+        std::slice::from_ref(derived_column_definition),
+    )?;
+
+    add_computed_column_to_directory_store(
+        store_dataset,
+        &derived_column_definition.derived_column_name,
+        &derived_column_definition.derived_data_type,
+        |row_values| compiled_derived_columns.evaluate_row(row_values).pop().unwrap_or_default(),
+    )
+}
+
+/// Tokenizes, parses and type-checks one expression
+///
+/// # Returns
+/// * `RowsAndColumnsResult<(DerivedExpressionNode, CsvColumnDataType)>` - The tree and its result type
+fn compile_derived_expression(
+    derived_expression_text: &str,
+    column_names: &[String],
+    column_data_types: &[CsvColumnDataType],
+) -> RowsAndColumnsResult<(DerivedExpressionNode, CsvColumnDataType)> {
+    let derived_tokens = tokenize_derived_expression(derived_expression_text)?;
+    let mut expression_parser = DerivedExpressionParser {
+        derived_expression_text,
+        derived_tokens,
+        current_token_index: 0,
+        column_names,
+        column_data_types,
+    };
+
+    let typed_root = expression_parser.parse_or_expression()?;
+    let trailing_token = expression_parser.peek_token().clone();
+    if trailing_token.token_kind != DerivedTokenKind::EndOfExpression {
+        return Err(expression_parser.error_at(
+            &trailing_token,
+            &format!("unexpected {} after a complete expression", describe_derived_token(&trailing_token.token_kind))
+        ));
+    }
+
+    Ok((typed_root.expression_node, typed_root.value_type))
+}
+
+/// Splits an expression into tokens
+///
+/// # Arguments
+/// * `derived_expression_text` - The expression to tokenize
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<DerivedToken>>` - Tokens ending with `EndOfExpression`
+///
+/// # Errors
+/// * `DerivedColumnExpressionError` - Unterminated quotes, malformed numbers or
+///   characters that cannot start a token
+fn tokenize_derived_expression(derived_expression_text: &str) -> RowsAndColumnsResult<Vec<DerivedToken>> {
+    let expression_characters: Vec<char> = derived_expression_text.chars().collect();
+    let mut derived_tokens = Vec::new();
+    let mut current_position = 0;
+
+    while current_position < expression_characters.len() {
+        let token_start = current_position;
+        let current_character = expression_characters[current_position];
+        let next_character = expression_characters.get(current_position + 1).copied();
+
+        let (token_kind, token_length) = match (current_character, next_character) {
+            (whitespace_character, _) if whitespace_character.is_whitespace() => {
+                current_position += 1;
+                continue;
+            }
+            ('(', _) => (DerivedTokenKind::OpenParenthesis, 1),
+            (')', _) => (DerivedTokenKind::CloseParenthesis, 1),
+            (',', _) => (DerivedTokenKind::Comma, 1),
+            ('|', Some('|')) => (DerivedTokenKind::Operator(DerivedOperator::Concatenate), 2),
+            ('=', Some('=')) => (DerivedTokenKind::Operator(DerivedOperator::Equal), 2),
+            ('=', _) => (DerivedTokenKind::Operator(DerivedOperator::Equal), 1),
+            ('!', Some('=')) | ('<', Some('>')) => (DerivedTokenKind::Operator(DerivedOperator::NotEqual), 2),
+            ('<', Some('=')) => (DerivedTokenKind::Operator(DerivedOperator::LessOrEqual), 2),
+            ('<', _) => (DerivedTokenKind::Operator(DerivedOperator::Less), 1),
+            ('>', Some('=')) => (DerivedTokenKind::Operator(DerivedOperator::GreaterOrEqual), 2),
+            ('>', _) => (DerivedTokenKind::Operator(DerivedOperator::Greater), 1),
+            ('+', _) => (DerivedTokenKind::Operator(DerivedOperator::Add), 1),
+            ('-', _) => (DerivedTokenKind::Operator(DerivedOperator::Subtract), 1),
+            ('*', _) => (DerivedTokenKind::Operator(DerivedOperator::Multiply), 1),
+            ('/', _) => (DerivedTokenKind::Operator(DerivedOperator::Divide), 1),
+            ('%', _) => (DerivedTokenKind::Operator(DerivedOperator::Remainder), 1),
+            ('"' | '\'' | '`', _) => {
+                // Quoted text literal or backquoted column name; a backslash
+                // escapes the quote character and itself
+                let closing_quote = current_character;
+                let mut quoted_content = String::new();
+                let mut quote_position = current_position + 1;
+                loop {
+                    match expression_characters.get(quote_position) {
+                        None => {
+                            return Err(create_derived_column_expression_error(
+                                derived_expression_text,
+                                token_start,
+                                &format!("unterminated {} quote", closing_quote)
+                            ));
+                        }
+                        Some(&'\\') if matches!(expression_characters.get(quote_position + 1), Some(&escaped) if escaped == closing_quote || escaped == '\\') => {
+                            quoted_content.push(expression_characters[quote_position + 1]);
+                            quote_position += 2;
+                        }
+                        Some(&quoted_character) if quoted_character == closing_quote => {
+                            quote_position += 1;
+                            break;
+                        }
+                        Some(&quoted_character) => {
+                            quoted_content.push(quoted_character);
+                            quote_position += 1;
+                        }
+                    }
+                }
+                let token_kind = if closing_quote == '`' {
+                    DerivedTokenKind::QuotedColumnName(quoted_content)
+                } else {
+                    DerivedTokenKind::TextLiteral(quoted_content)
+                };
+                (token_kind, quote_position - token_start)
+            }
+            (digit_or_point, following_character)
+                if digit_or_point.is_ascii_digit()
+                    || (digit_or_point == '.' && following_character.is_some_and(|character| character.is_ascii_digit())) =>
+            {
+                // Signs are unary operators, except directly after an exponent
+                let mut number_end = current_position + 1;
+                while let Some(&number_character) = expression_characters.get(number_end) {
+                    let previous_character = expression_characters[number_end - 1];
+                    let is_exponent_sign = matches!(number_character, '-' | '+') && matches!(previous_character, 'e' | 'E');
+                    if number_character.is_ascii_alphanumeric() || number_character == '.' || is_exponent_sign {
+                        number_end += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let number_text: String = expression_characters[token_start..number_end].iter().collect();
+                let token_kind = match number_text.parse::<i64>() {
+                    Ok(integer_value) => DerivedTokenKind::IntegerLiteral(integer_value),
+                    Err(_) => DerivedTokenKind::FloatLiteral(
+                        number_text.parse::<f64>().ok().filter(|value| value.is_finite()).ok_or_else(|| {
+                            create_derived_column_expression_error(
+                                derived_expression_text,
+                                token_start,
+                                &format!("'{}' is not a valid number", number_text)
+                            )
+                        })?
+                    ),
+                };
+                (token_kind, number_end - token_start)
+            }
+            (word_start, _) if word_start.is_alphabetic() || word_start == '_' => {
+                let mut word_end = current_position;
+                while expression_characters
+                    .get(word_end)
+                    .is_some_and(|&word_character| word_character.is_alphanumeric() || word_character == '_')
+                {
+                    word_end += 1;
+                }
+                let word_text: String = expression_characters[token_start..word_end].iter().collect();
+                let token_kind = match DerivedKeyword::from_word(&word_text) {
+                    Some(derived_keyword) => DerivedTokenKind::Keyword(derived_keyword),
+                    None => DerivedTokenKind::Word(word_text),
+                };
+                (token_kind, word_end - token_start)
+            }
+            _ => {
+                return Err(create_derived_column_expression_error(
+                    derived_expression_text,
+                    token_start,
+                    &format!("unexpected character '{}'", current_character)
+                ));
+            }
+        };
+
+        current_position += token_length;
+        derived_tokens.push(DerivedToken { token_kind, character_position: token_start });
+    }
+
+    derived_tokens.push(DerivedToken {
+        token_kind: DerivedTokenKind::EndOfExpression,
+        character_position: expression_characters.len(),
+    });
+
+    Ok(derived_tokens)
+}
+
+/// Describes a token for error messages
+fn describe_derived_token(token_kind: &DerivedTokenKind) -> String {
+    match token_kind {
+        DerivedTokenKind::Word(word_text) => format!("'{}'", word_text),
+        DerivedTokenKind::QuotedColumnName(column_name) => format!("column `{}`", column_name),
+        DerivedTokenKind::IntegerLiteral(integer_value) => format!("number {}", integer_value),
+        DerivedTokenKind::FloatLiteral(float_value) => format!("number {}", float_value),
+        DerivedTokenKind::TextLiteral(literal_text) => format!("text \"{}\"", literal_text),
+        DerivedTokenKind::Keyword(derived_keyword) => format!("'{:?}'", derived_keyword).to_lowercase(),
+        DerivedTokenKind::Operator(derived_operator) => format!("'{}'", derived_operator.as_operator_text()),
+        DerivedTokenKind::OpenParenthesis => "'('".to_string(),
+        DerivedTokenKind::CloseParenthesis => "')'".to_string(),
+        DerivedTokenKind::Comma => "','".to_string(),
+        DerivedTokenKind::EndOfExpression => "end of expression".to_string(),
+    }
+}
+
+/// Describes a value type for error messages
+fn describe_value_type(value_type: &CsvColumnDataType) -> &'static str {
+    match value_type {
+        CsvColumnDataType::Boolean => "boolean",
+        CsvColumnDataType::Integer => "integer",
+        CsvColumnDataType::Float => "float",
+        CsvColumnDataType::String => "text",
+    }
+}
+
+/// Whether a type is integer or float
+fn is_numeric_type(value_type: &CsvColumnDataType) -> bool {
+    matches!(value_type, CsvColumnDataType::Integer | CsvColumnDataType::Float)
+}
+
+/// The common type of two branches or arguments, if they have one
+fn unify_value_types(first_type: &CsvColumnDataType, second_type: &CsvColumnDataType) -> Option<CsvColumnDataType> {
+    if first_type == second_type {
+        Some(first_type.clone())
+    } else if is_numeric_type(first_type) && is_numeric_type(second_type) {
+        Some(CsvColumnDataType::Float)
+    } else {
+        None
+    }
+}
+
+/// A parsed sub-expression with its type and where it starts
+struct TypedDerivedNode {
+    expression_node: DerivedExpressionNode,
+    value_type: CsvColumnDataType,
+    start_position: usize,
+}
+
+/// Recursive-descent parser that also binds columns and checks types
+struct DerivedExpressionParser<'a> {
+    /// The expression as written, for error messages
+    derived_expression_text: &'a str,
+    /// Tokens ending with `EndOfExpression`
+    derived_tokens: Vec<DerivedToken>,
+    /// Index of the next token to read
+    current_token_index: usize,
+    /// Column names to bind against
+    column_names: &'a [String],
+    /// Column types, in the same order
+    column_data_types: &'a [CsvColumnDataType],
+}
+
+impl DerivedExpressionParser<'_> {
+    /// Builds a positioned error at a token
+    fn error_at(&self, derived_token: &DerivedToken, error_description: &str) -> RowsAndColumnsError {
+        self.error_at_position(derived_token.character_position, error_description)
+    }
+
+    /// Builds a positioned error at a character position
+    fn error_at_position(&self, character_position: usize, error_description: &str) -> RowsAndColumnsError {
+        create_derived_column_expression_error(self.derived_expression_text, character_position, error_description)
+    }
+
+    /// Returns the next token without consuming it
+    fn peek_token(&self) -> &DerivedToken {
+        &self.derived_tokens[self.current_token_index]
+    }
+
+    /// Consumes and returns the next token; `EndOfExpression` is never consumed
+    fn next_token(&mut self) -> DerivedToken {
+        let derived_token = self.derived_tokens[self.current_token_index].clone();
+        if derived_token.token_kind != DerivedTokenKind::EndOfExpression {
+            self.current_token_index += 1;
+        }
+        derived_token
+    }
+
+    /// Consumes the next token if it has the given kind
+    fn accept_token(&mut self, expected_kind: &DerivedTokenKind) -> bool {
+        if &self.peek_token().token_kind == expected_kind {
+            self.current_token_index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the next token, which must have the given kind
+    fn expect_token(&mut self, expected_kind: &DerivedTokenKind) -> RowsAndColumnsResult<()> {
+        let derived_token = self.next_token();
+        if &derived_token.token_kind == expected_kind {
+            Ok(())
+        } else {
+            Err(self.error_at(
+                &derived_token,
+                &format!("expected {} but found {}", describe_derived_token(expected_kind), describe_derived_token(&derived_token.token_kind))
+            ))
+        }
+    }
+
+    /// Checks that a sub-expression is boolean
+    fn require_boolean(&self, typed_node: &TypedDerivedNode, usage_description: &str) -> RowsAndColumnsResult<()> {
+        if typed_node.value_type == CsvColumnDataType::Boolean {
+            Ok(())
+        } else {
+            Err(self.error_at_position(
+                typed_node.start_position,
+                &format!("{} needs a true/false value, not {}", usage_description, describe_value_type(&typed_node.value_type))
+            ))
+        }
+    }
+
+    /// `or_expression := and_expression ("or" and_expression)*`
+    fn parse_or_expression(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut combined_node = self.parse_and_expression()?;
+        while self.accept_token(&DerivedTokenKind::Keyword(DerivedKeyword::Or)) {
+            let right_node = self.parse_and_expression()?;
+            self.require_boolean(&combined_node, "'or'")?;
+            self.require_boolean(&right_node, "'or'")?;
+            combined_node = TypedDerivedNode {
+                expression_node: DerivedExpressionNode::Or(Box::new(combined_node.expression_node), Box::new(right_node.expression_node)),
+                value_type: CsvColumnDataType::Boolean,
+                start_position: combined_node.start_position,
+            };
+        }
+        Ok(combined_node)
+    }
+
+    /// `and_expression := not_expression ("and" not_expression)*`
+    fn parse_and_expression(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut combined_node = self.parse_not_expression()?;
+        while self.accept_token(&DerivedTokenKind::Keyword(DerivedKeyword::And)) {
+            let right_node = self.parse_not_expression()?;
+            self.require_boolean(&combined_node, "'and'")?;
+            self.require_boolean(&right_node, "'and'")?;
+            combined_node = TypedDerivedNode {
+                expression_node: DerivedExpressionNode::And(Box::new(combined_node.expression_node), Box::new(right_node.expression_node)),
+                value_type: CsvColumnDataType::Boolean,
+                start_position: combined_node.start_position,
+            };
+        }
+        Ok(combined_node)
+    }
+
+    /// `not_expression := "not" not_expression | comparison`
+    fn parse_not_expression(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let not_position = self.peek_token().character_position;
+        if self.accept_token(&DerivedTokenKind::Keyword(DerivedKeyword::Not)) {
+            let negated_node = self.parse_not_expression()?;
+            self.require_boolean(&negated_node, "'not'")?;
+            return Ok(TypedDerivedNode {
+                expression_node: DerivedExpressionNode::Not(Box::new(negated_node.expression_node)),
+                value_type: CsvColumnDataType::Boolean,
+                start_position: not_position,
+            });
+        }
+        self.parse_comparison()
+    }
+
+    /// `comparison := concatenation (comparison_operator concatenation)?`
+    fn parse_comparison(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let left_node = self.parse_concatenation()?;
+        let operator_token = self.peek_token().clone();
+        let DerivedTokenKind::Operator(comparison_operator) = operator_token.token_kind else {
+            return Ok(left_node);
+        };
+        if !comparison_operator.is_comparison() {
+            return Ok(left_node);
+        }
+        self.next_token();
+        let right_node = self.parse_concatenation()?;
+
+        if unify_value_types(&left_node.value_type, &right_node.value_type).is_none() {
+            return Err(self.error_at(
+                &operator_token,
+                &format!(
+                    "cannot compare {} with {}",
+                    describe_value_type(&left_node.value_type),
+                    describe_value_type(&right_node.value_type)
+                )
+            ));
+        }
+
+        Ok(TypedDerivedNode {
+            expression_node: DerivedExpressionNode::Binary {
+                binary_operator: comparison_operator,
+                left_node: Box::new(left_node.expression_node),
+                right_node: Box::new(right_node.expression_node),
+                result_is_integer: false,
+            },
+            value_type: CsvColumnDataType::Boolean,
+            start_position: left_node.start_position,
+        })
+    }
+
+    /// `concatenation := additive ("||" additive)*` — any types, joined as text
+    fn parse_concatenation(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut combined_node = self.parse_additive()?;
+        while self.accept_token(&DerivedTokenKind::Operator(DerivedOperator::Concatenate)) {
+            let right_node = self.parse_additive()?;
+            combined_node = TypedDerivedNode {
+                expression_node: DerivedExpressionNode::Binary {
+                    binary_operator: DerivedOperator::Concatenate,
+                    left_node: Box::new(combined_node.expression_node),
+                    right_node: Box::new(right_node.expression_node),
+                    result_is_integer: false,
+                },
+                value_type: CsvColumnDataType::String,
+                start_position: combined_node.start_position,
+            };
+        }
+        Ok(combined_node)
+    }
+
+    /// `additive := multiplicative (("+" | "-") multiplicative)*`
+    fn parse_additive(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut combined_node = self.parse_multiplicative()?;
+        loop {
+            let operator_token = self.peek_token().clone();
+            let arithmetic_operator = match operator_token.token_kind {
+                DerivedTokenKind::Operator(derived_operator @ (DerivedOperator::Add | DerivedOperator::Subtract)) => derived_operator,
+                _ => return Ok(combined_node),
+            };
+            self.next_token();
+            let right_node = self.parse_multiplicative()?;
+            combined_node = self.build_arithmetic_node(arithmetic_operator, &operator_token, combined_node, right_node)?;
+        }
+    }
+
+    /// `multiplicative := unary (("*" | "/" | "%") unary)*`
+    fn parse_multiplicative(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut combined_node = self.parse_unary()?;
+        loop {
+            let operator_token = self.peek_token().clone();
+            let arithmetic_operator = match operator_token.token_kind {
+                DerivedTokenKind::Operator(
+                    derived_operator @ (DerivedOperator::Multiply | DerivedOperator::Divide | DerivedOperator::Remainder)
+                ) => derived_operator,
+                _ => return Ok(combined_node),
+            };
+            self.next_token();
+            let right_node = self.parse_unary()?;
+            combined_node = self.build_arithmetic_node(arithmetic_operator, &operator_token, combined_node, right_node)?;
+        }
+    }
+
+    /// Type-checks an arithmetic operation; integer stays integer except for `/`
+    fn build_arithmetic_node(
+        &self,
+        arithmetic_operator: DerivedOperator,
+        operator_token: &DerivedToken,
+        left_node: TypedDerivedNode,
+        right_node: TypedDerivedNode,
+    ) -> RowsAndColumnsResult<TypedDerivedNode> {
+        for operand_node in [&left_node, &right_node] {
+            if !is_numeric_type(&operand_node.value_type) {
+                let hint = if arithmetic_operator == DerivedOperator::Add && operand_node.value_type == CsvColumnDataType::String {
+                    " (use || to join text)"
+                } else {
+                    ""
+                };
+                return Err(self.error_at(
+                    operator_token,
+                    &format!(
+                        "'{}' needs numbers, not {}{}",
+                        arithmetic_operator.as_operator_text(),
+                        describe_value_type(&operand_node.value_type),
+                        hint
+                    )
+                ));
+            }
+        }
+
+        let result_is_integer = arithmetic_operator != DerivedOperator::Divide
+            && left_node.value_type == CsvColumnDataType::Integer
+            && right_node.value_type == CsvColumnDataType::Integer;
+        Ok(TypedDerivedNode {
+            expression_node: DerivedExpressionNode::Binary {
+                binary_operator: arithmetic_operator,
+                left_node: Box::new(left_node.expression_node),
+                right_node: Box::new(right_node.expression_node),
+                result_is_integer,
+            },
+            value_type: if result_is_integer { CsvColumnDataType::Integer } else { CsvColumnDataType::Float },
+            start_position: left_node.start_position,
+        })
+    }
+
+    /// `unary := "-" unary | primary`
+    fn parse_unary(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let minus_token = self.peek_token().clone();
+        if self.accept_token(&DerivedTokenKind::Operator(DerivedOperator::Subtract)) {
+            let negated_node = self.parse_unary()?;
+            if !is_numeric_type(&negated_node.value_type) {
+                return Err(self.error_at(
+                    &minus_token,
+                    &format!("'-' needs a number, not {}", describe_value_type(&negated_node.value_type))
+                ));
+            }
+            return Ok(TypedDerivedNode {
+                expression_node: DerivedExpressionNode::Negate(Box::new(negated_node.expression_node)),
+                value_type: negated_node.value_type,
+                start_position: minus_token.character_position,
+            });
+        }
+        self.parse_primary()
+    }
+
+    /// `primary := literal | "(" expression ")" | case | function "(" arguments ")" | column`
+    fn parse_primary(&mut self) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let primary_token = self.next_token();
+        let start_position = primary_token.character_position;
+        let literal_node = |derived_value: DerivedValue, value_type: CsvColumnDataType| TypedDerivedNode {
+            expression_node: DerivedExpressionNode::Literal(derived_value),
+            value_type,
+            start_position,
+        };
+
+        match primary_token.token_kind.clone() {
+            DerivedTokenKind::IntegerLiteral(integer_value) => Ok(literal_node(DerivedValue::Integer(integer_value), CsvColumnDataType::Integer)),
+            DerivedTokenKind::FloatLiteral(float_value) => Ok(literal_node(DerivedValue::Float(float_value), CsvColumnDataType::Float)),
+            DerivedTokenKind::TextLiteral(literal_text) => Ok(literal_node(DerivedValue::Text(literal_text), CsvColumnDataType::String)),
+            DerivedTokenKind::Keyword(DerivedKeyword::True) => Ok(literal_node(DerivedValue::Boolean(true), CsvColumnDataType::Boolean)),
+            DerivedTokenKind::Keyword(DerivedKeyword::False) => Ok(literal_node(DerivedValue::Boolean(false), CsvColumnDataType::Boolean)),
+            DerivedTokenKind::Keyword(DerivedKeyword::Case) => self.parse_case_expression(start_position),
+            DerivedTokenKind::OpenParenthesis => {
+                let inner_node = self.parse_or_expression()?;
+                self.expect_token(&DerivedTokenKind::CloseParenthesis)?;
+                Ok(TypedDerivedNode { start_position, ..inner_node })
+            }
+            DerivedTokenKind::Word(word_text) if self.peek_token().token_kind == DerivedTokenKind::OpenParenthesis => {
+                self.next_token();
+                self.parse_function_call(&word_text, &primary_token)
+            }
+            DerivedTokenKind::Word(column_name) | DerivedTokenKind::QuotedColumnName(column_name) => {
+                let column_position = self.column_names
+                    .iter()
+                    .position(|candidate_name| candidate_name == &column_name)
+                    .ok_or_else(|| self.error_at(&primary_token, &format!("unknown column '{}'", column_name)))?;
+                let column_data_type = self.column_data_types[column_position].clone();
+                Ok(TypedDerivedNode {
+                    expression_node: DerivedExpressionNode::ColumnReference { column_position, column_data_type: column_data_type.clone() },
+                    value_type: column_data_type,
+                    start_position,
+                })
+            }
+            other_kind => Err(self.error_at(
+                &primary_token,
+                &format!("expected a value, column or function but found {}", describe_derived_token(&other_kind))
+            )),
+        }
+    }
+
+    /// `case := "case" ("when" expression "then" expression)+ ("else" expression)? "end"`
+    fn parse_case_expression(&mut self, start_position: usize) -> RowsAndColumnsResult<TypedDerivedNode> {
+        let mut condition_branches = Vec::new();
+        let mut result_type: Option<CsvColumnDataType> = None;
+
+        // Every branch result must share one type (integer and float mix to float)
+        let mut merge_branch_type = |parser: &Self, branch_node: &TypedDerivedNode| -> RowsAndColumnsResult<()> {
+            result_type = match &result_type {
+                None => Some(branch_node.value_type.clone()),
+                Some(current_type) => Some(unify_value_types(current_type, &branch_node.value_type).ok_or_else(|| {
+                    parser.error_at_position(
+                        branch_node.start_position,
+                        &format!(
+                            "this branch gives {} but an earlier one gives {}",
+                            describe_value_type(&branch_node.value_type),
+                            describe_value_type(current_type)
+                        )
+                    )
+                })?),
+            };
+            Ok(())
+        };
+
+        while self.accept_token(&DerivedTokenKind::Keyword(DerivedKeyword::When)) {
+            let condition_node = self.parse_or_expression()?;
+            self.require_boolean(&condition_node, "'when'")?;
+            self.expect_token(&DerivedTokenKind::Keyword(DerivedKeyword::Then))?;
+            let branch_node = self.parse_or_expression()?;
+            merge_branch_type(self, &branch_node)?;
+            condition_branches.push((condition_node.expression_node, branch_node.expression_node));
+        }
+        if condition_branches.is_empty() {
+            let unexpected_token = self.peek_token().clone();
+            return Err(self.error_at(&unexpected_token, "'case' needs at least one 'when ... then ...'"));
+        }
+
+        let else_node = if self.accept_token(&DerivedTokenKind::Keyword(DerivedKeyword::Else)) {
+            let else_branch = self.parse_or_expression()?;
+            merge_branch_type(self, &else_branch)?;
+            Some(Box::new(else_branch.expression_node))
+        } else {
+            None
+        };
+        self.expect_token(&DerivedTokenKind::Keyword(DerivedKeyword::End))?;
+
+        Ok(TypedDerivedNode {
+            expression_node: DerivedExpressionNode::Conditional { condition_branches, else_node },
+            value_type: result_type.unwrap_or(CsvColumnDataType::String),
+            start_position,
+        })
+    }
+
+    /// Parses a call's arguments (after the opening parenthesis) and checks them
+    fn parse_function_call(&mut self, function_name: &str, name_token: &DerivedToken) -> RowsAndColumnsResult<TypedDerivedNode> {
+        // Step 1: Arguments up to the closing parenthesis
+        let mut argument_nodes = Vec::new();
+        if !self.accept_token(&DerivedTokenKind::CloseParenthesis) {
+            loop {
+                argument_nodes.push(self.parse_or_expression()?);
+                if self.accept_token(&DerivedTokenKind::Comma) {
+                    continue;
+                }
+                self.expect_token(&DerivedTokenKind::CloseParenthesis)?;
+                break;
+            }
+        }
+
+        let argument_count_error = |expected_description: &str| {
+            self.error_at(
+                name_token,
+                &format!("{}() takes {} but was given {}", function_name, expected_description, argument_nodes.len())
+            )
+        };
+        let argument_type_error = |argument_node: &TypedDerivedNode, expected_description: &str| {
+            self.error_at_position(
+                argument_node.start_position,
+                &format!("{}() needs {} here, not {}", function_name, expected_description, describe_value_type(&argument_node.value_type))
+            )
+        };
+
+        // Step 2: `if(condition, then, else)` is a conditional, not a function
+        if function_name.eq_ignore_ascii_case("if") {
+            if argument_nodes.len() != 3 {
+                return Err(argument_count_error("3 arguments (condition, then, else)"));
+            }
+            self.require_boolean(&argument_nodes[0], "if()'s condition")?;
+            let result_type = unify_value_types(&argument_nodes[1].value_type, &argument_nodes[2].value_type).ok_or_else(|| {
+                argument_type_error(&argument_nodes[2], describe_value_type(&argument_nodes[1].value_type))
+            })?;
+            let mut argument_iterator = argument_nodes.into_iter().map(|argument_node| argument_node.expression_node);
+            let (Some(condition_node), Some(then_node), Some(else_node)) =
+                (argument_iterator.next(), argument_iterator.next(), argument_iterator.next()) else {
+                return Err(self.error_at(name_token, "if() takes 3 arguments"));
+            };
+            return Ok(TypedDerivedNode {
+                expression_node: DerivedExpressionNode::Conditional {
+                    condition_branches: vec![(condition_node, then_node)],
+                    else_node: Some(Box::new(else_node)),
+                },
+                value_type: result_type,
+                start_position: name_token.character_position,
+            });
+        }
+
+        // Step 3: Built-in functions with their argument and result types
+        let derived_function = DerivedFunction::from_name(function_name)
+            .ok_or_else(|| self.error_at(name_token, &format!("unknown function '{}'", function_name)))?;
+        let argument_count = argument_nodes.len();
+        let require_argument_count = |minimum_count: usize, maximum_count: usize, expected_description: &str| {
+            if argument_count < minimum_count || argument_count > maximum_count {
+                Err(argument_count_error(expected_description))
+            } else {
+                Ok(())
+            }
+        };
+        let require_text = |argument_node: &TypedDerivedNode| {
+            if argument_node.value_type == CsvColumnDataType::String { Ok(()) } else { Err(argument_type_error(argument_node, "text")) }
+        };
+        let require_number = |argument_node: &TypedDerivedNode| {
+            if is_numeric_type(&argument_node.value_type) { Ok(()) } else { Err(argument_type_error(argument_node, "a number")) }
+        };
+        let require_integer = |argument_node: &TypedDerivedNode| {
+            if argument_node.value_type == CsvColumnDataType::Integer { Ok(()) } else { Err(argument_type_error(argument_node, "an integer")) }
+        };
+
+        let result_type = match derived_function {
+            DerivedFunction::Upper | DerivedFunction::Lower | DerivedFunction::Trim => {
+                require_argument_count(1, 1, "1 argument")?;
+                require_text(&argument_nodes[0])?;
+                CsvColumnDataType::String
+            }
+            DerivedFunction::Length => {
+                require_argument_count(1, 1, "1 argument")?;
+                require_text(&argument_nodes[0])?;
+                CsvColumnDataType::Integer
+            }
+            DerivedFunction::Substring => {
+                require_argument_count(2, 3, "2 or 3 arguments (text, start[, length])")?;
+                require_text(&argument_nodes[0])?;
+                argument_nodes[1..].iter().try_for_each(require_integer)?;
+                CsvColumnDataType::String
+            }
+            DerivedFunction::Replace => {
+                require_argument_count(3, 3, "3 arguments (text, from, to)")?;
+                argument_nodes.iter().try_for_each(require_text)?;
+                CsvColumnDataType::String
+            }
+            DerivedFunction::ConcatenateAll => {
+                require_argument_count(1, usize::MAX, "at least 1 argument")?;
+                CsvColumnDataType::String
+            }
+            DerivedFunction::Absolute => {
+                require_argument_count(1, 1, "1 argument")?;
+                require_number(&argument_nodes[0])?;
+                argument_nodes[0].value_type.clone()
+            }
+            DerivedFunction::Round => {
+                require_argument_count(1, 2, "1 or 2 arguments (number[, digits])")?;
+                require_number(&argument_nodes[0])?;
+                if argument_count == 2 {
+                    require_integer(&argument_nodes[1])?;
+                    CsvColumnDataType::Float
+                } else {
+                    CsvColumnDataType::Integer
+                }
+            }
+            DerivedFunction::Floor | DerivedFunction::Ceiling => {
+                require_argument_count(1, 1, "1 argument")?;
+                require_number(&argument_nodes[0])?;
+                CsvColumnDataType::Integer
+            }
+            DerivedFunction::SquareRoot => {
+                require_argument_count(1, 1, "1 argument")?;
+                require_number(&argument_nodes[0])?;
+                CsvColumnDataType::Float
+            }
+            DerivedFunction::Power => {
+                require_argument_count(2, 2, "2 arguments (base, exponent)")?;
+                argument_nodes.iter().try_for_each(require_number)?;
+                CsvColumnDataType::Float
+            }
+            DerivedFunction::Coalesce => {
+                require_argument_count(1, usize::MAX, "at least 1 argument")?;
+                let mut result_type = argument_nodes[0].value_type.clone();
+                for argument_node in &argument_nodes[1..] {
+                    result_type = unify_value_types(&result_type, &argument_node.value_type)
+                        .ok_or_else(|| argument_type_error(argument_node, describe_value_type(&result_type)))?;
+                }
+                result_type
+            }
+            DerivedFunction::CastToInteger => {
+                require_argument_count(1, 1, "1 argument")?;
+                CsvColumnDataType::Integer
+            }
+            DerivedFunction::CastToFloat => {
+                require_argument_count(1, 1, "1 argument")?;
+                CsvColumnDataType::Float
+            }
+            DerivedFunction::CastToText => {
+                require_argument_count(1, 1, "1 argument")?;
+                CsvColumnDataType::String
+            }
+            DerivedFunction::CastToBoolean => {
+                require_argument_count(1, 1, "1 argument")?;
+                CsvColumnDataType::Boolean
+            }
+        };
+
+        Ok(TypedDerivedNode {
+            expression_node: DerivedExpressionNode::FunctionCall {
+                derived_function,
+                argument_nodes: argument_nodes.into_iter().map(|argument_node| argument_node.expression_node).collect(),
+            },
+            value_type: result_type,
+            start_position: name_token.character_position,
+        })
+    }
+}
+
+/// Reads a cell as a value of its column's type (empty or unreadable is missing)
+fn read_derived_cell_value(cell_text: &str, column_data_type: &CsvColumnDataType) -> DerivedValue {
+    if cell_text.is_empty() {
+        return DerivedValue::Missing;
+    }
+    match column_data_type {
+        CsvColumnDataType::Integer => match cell_text.parse::<i64>() {
+            Ok(integer_value) => DerivedValue::Integer(integer_value),
+            Err(_) => parse_finite_float(cell_text).map(DerivedValue::Float).unwrap_or(DerivedValue::Missing),
+        },
+        CsvColumnDataType::Float => parse_finite_float(cell_text).map(DerivedValue::Float).unwrap_or(DerivedValue::Missing),
+        CsvColumnDataType::Boolean => parse_boolean_text(cell_text).map(DerivedValue::Boolean).unwrap_or(DerivedValue::Missing),
+        CsvColumnDataType::String => DerivedValue::Text(cell_text.to_string()),
+    }
+}
+
+/// Parses a finite float
+fn parse_finite_float(number_text: &str) -> Option<f64> {
+    number_text.trim().parse::<f64>().ok().filter(|number_value| number_value.is_finite())
+}
+
+/// Parses the boolean spellings the type detection accepts
+fn parse_boolean_text(boolean_text: &str) -> Option<bool> {
+    match boolean_text.trim().to_lowercase().as_str() {
+        "true" | "yes" | "1" | "t" | "y" => Some(true),
+        "false" | "no" | "0" | "f" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// Wraps a float result, turning NaN and infinities into missing values
+fn finite_float_value(float_value: f64) -> DerivedValue {
+    if float_value.is_finite() { DerivedValue::Float(float_value) } else { DerivedValue::Missing }
+}
+
+/// Converts a float to an integer value if it is in range
+fn float_to_integer_value(float_value: f64) -> DerivedValue {
+    if float_value.is_finite() && float_value >= i64::MIN as f64 && float_value < i64::MAX as f64 {
+        DerivedValue::Integer(float_value as i64)
+    } else {
+        DerivedValue::Missing
+    }
+}
+
+/// Evaluates a compiled expression against one row
+///
+/// # Arguments
+/// * `expression_node` - The node to evaluate
+/// * `row_values` - Base values followed by earlier derived values
+///
+/// # Returns
+/// * `DerivedValue` - The result (missing when an input is missing or invalid)
+fn evaluate_derived_node(expression_node: &DerivedExpressionNode, row_values: &[String]) -> DerivedValue {
+    match expression_node {
+        DerivedExpressionNode::Literal(literal_value) => literal_value.clone(),
+        DerivedExpressionNode::ColumnReference { column_position, column_data_type } => {
+            let cell_text = row_values.get(*column_position).map(String::as_str).unwrap_or("");
+            read_derived_cell_value(cell_text, column_data_type)
+        }
+        DerivedExpressionNode::Negate(negated_node) => match evaluate_derived_node(negated_node, row_values) {
+            DerivedValue::Integer(integer_value) => integer_value.checked_neg().map(DerivedValue::Integer).unwrap_or(DerivedValue::Missing),
+            DerivedValue::Float(float_value) => DerivedValue::Float(-float_value),
+            _ => DerivedValue::Missing,
+        },
+        DerivedExpressionNode::Not(negated_node) => match evaluate_derived_node(negated_node, row_values) {
+            DerivedValue::Boolean(boolean_value) => DerivedValue::Boolean(!boolean_value),
+            _ => DerivedValue::Missing,
+        },
+        DerivedExpressionNode::And(left_node, right_node) => {
+            // false wins over missing, as in SQL
+            match (evaluate_derived_node(left_node, row_values), evaluate_derived_node(right_node, row_values)) {
+                (DerivedValue::Boolean(false), _) | (_, DerivedValue::Boolean(false)) => DerivedValue::Boolean(false),
+                (DerivedValue::Boolean(true), DerivedValue::Boolean(true)) => DerivedValue::Boolean(true),
+                _ => DerivedValue::Missing,
+            }
+        }
+        DerivedExpressionNode::Or(left_node, right_node) => {
+            // true wins over missing, as in SQL
+            match (evaluate_derived_node(left_node, row_values), evaluate_derived_node(right_node, row_values)) {
+                (DerivedValue::Boolean(true), _) | (_, DerivedValue::Boolean(true)) => DerivedValue::Boolean(true),
+                (DerivedValue::Boolean(false), DerivedValue::Boolean(false)) => DerivedValue::Boolean(false),
+                _ => DerivedValue::Missing,
+            }
+        }
+        DerivedExpressionNode::Binary { binary_operator, left_node, right_node, result_is_integer } => {
+            let left_value = evaluate_derived_node(left_node, row_values);
+            let right_value = evaluate_derived_node(right_node, row_values);
+            evaluate_binary_operation(*binary_operator, left_value, right_value, *result_is_integer)
+        }
+        DerivedExpressionNode::Conditional { condition_branches, else_node } => {
+            for (condition_node, branch_node) in condition_branches {
+                if evaluate_derived_node(condition_node, row_values) == DerivedValue::Boolean(true) {
+                    return evaluate_derived_node(branch_node, row_values);
+                }
+            }
+            else_node
+                .as_ref()
+                .map(|else_branch| evaluate_derived_node(else_branch, row_values))
+                .unwrap_or(DerivedValue::Missing)
+        }
+        DerivedExpressionNode::FunctionCall { derived_function, argument_nodes } => {
+            let argument_values: Vec<DerivedValue> = argument_nodes
+                .iter()
+                .map(|argument_node| evaluate_derived_node(argument_node, row_values))
+                .collect();
+            evaluate_function_call(*derived_function, argument_values)
+        }
+    }
+}
+
+/// Applies a binary operator to two evaluated operands
+fn evaluate_binary_operation(
+    binary_operator: DerivedOperator,
+    left_value: DerivedValue,
+    right_value: DerivedValue,
+    result_is_integer: bool,
+) -> DerivedValue {
+    if left_value == DerivedValue::Missing || right_value == DerivedValue::Missing {
+        return DerivedValue::Missing;
+    }
+
+    // Step 1: Text joining accepts any types
+    if binary_operator == DerivedOperator::Concatenate {
+        return DerivedValue::Text(left_value.to_cell_text() + &right_value.to_cell_text());
+    }
+
+    // Step 2: Comparisons
+    if binary_operator.is_comparison() {
+        let value_ordering = match (&left_value, &right_value) {
+            (DerivedValue::Text(left_text), DerivedValue::Text(right_text)) => Some(left_text.cmp(right_text)),
+            (DerivedValue::Boolean(left_flag), DerivedValue::Boolean(right_flag)) => Some(left_flag.cmp(right_flag)),
+            _ => match (left_value.as_number(), right_value.as_number()) {
+                (Some(left_number), Some(right_number)) => left_number.partial_cmp(&right_number),
+                _ => None,
+            },
+        };
+        let Some(value_ordering) = value_ordering else {
+            return DerivedValue::Missing;
+        };
+        return DerivedValue::Boolean(match binary_operator {
+            DerivedOperator::Equal => value_ordering == Ordering::Equal,
+            DerivedOperator::NotEqual => value_ordering != Ordering::Equal,
+            DerivedOperator::Less => value_ordering == Ordering::Less,
+            DerivedOperator::LessOrEqual => value_ordering != Ordering::Greater,
+            DerivedOperator::Greater => value_ordering == Ordering::Greater,
+            _ => value_ordering != Ordering::Less,
+        });
+    }
+
+    // Step 3: Integer arithmetic, with overflow and division by zero as missing
+    if result_is_integer && let (DerivedValue::Integer(left_integer), DerivedValue::Integer(right_integer)) = (&left_value, &right_value) {
+        let integer_result = match binary_operator {
+            DerivedOperator::Add => left_integer.checked_add(*right_integer),
+            DerivedOperator::Subtract => left_integer.checked_sub(*right_integer),
+            DerivedOperator::Multiply => left_integer.checked_mul(*right_integer),
+            DerivedOperator::Remainder => left_integer.checked_rem(*right_integer),
+            _ => None,
+        };
+        return integer_result.map(DerivedValue::Integer).unwrap_or(DerivedValue::Missing);
+    }
+
+    // Step 4: Float arithmetic (an integer column holding "2.5" also ends up here)
+    let (Some(left_number), Some(right_number)) = (left_value.as_number(), right_value.as_number()) else {
+        return DerivedValue::Missing;
+    };
+    let float_result = match binary_operator {
+        DerivedOperator::Add => left_number + right_number,
+        DerivedOperator::Subtract => left_number - right_number,
+        DerivedOperator::Multiply => left_number * right_number,
+        DerivedOperator::Divide => left_number / right_number,
+        DerivedOperator::Remainder => left_number % right_number,
+        _ => f64::NAN,
+    };
+    finite_float_value(float_result)
+}
+
+/// Applies a built-in function to its evaluated arguments
+fn evaluate_function_call(derived_function: DerivedFunction, argument_values: Vec<DerivedValue>) -> DerivedValue {
+    // Step 1: Functions that look past missing values
+    match derived_function {
+        DerivedFunction::Coalesce => {
+            return argument_values
+                .into_iter()
+                .find(|argument_value| *argument_value != DerivedValue::Missing)
+                .unwrap_or(DerivedValue::Missing);
+        }
+        DerivedFunction::ConcatenateAll => {
+            return DerivedValue::Text(argument_values.iter().map(DerivedValue::to_cell_text).collect());
+        }
+        _ => {}
+    }
+
+    // Step 2: Every other function gives a missing value for a missing argument
+    if argument_values.contains(&DerivedValue::Missing) {
+        return DerivedValue::Missing;
+    }
+    let text_argument = |argument_position: usize| match argument_values.get(argument_position) {
+        Some(DerivedValue::Text(text_value)) => text_value.as_str(),
+        _ => "",
+    };
+    let number_argument = |argument_position: usize| {
+        argument_values.get(argument_position).and_then(DerivedValue::as_number).unwrap_or(f64::NAN)
+    };
+    let integer_argument = |argument_position: usize| match argument_values.get(argument_position) {
+        Some(DerivedValue::Integer(integer_value)) => Some(*integer_value),
+        _ => None,
+    };
+
+    match derived_function {
+        DerivedFunction::Upper => DerivedValue::Text(text_argument(0).to_uppercase()),
+        DerivedFunction::Lower => DerivedValue::Text(text_argument(0).to_lowercase()),
+        DerivedFunction::Trim => DerivedValue::Text(text_argument(0).trim().to_string()),
+        DerivedFunction::Length => DerivedValue::Integer(text_argument(0).chars().count() as i64),
+        DerivedFunction::Substring => {
+            // 1-based start; positions before the text are clipped
+            let start_number = integer_argument(1).unwrap_or(1);
+            let skipped_count = usize::try_from(start_number.saturating_sub(1)).unwrap_or(0);
+            let taken_count = match integer_argument(2) {
+                Some(length_number) => {
+                    let clipped_length = length_number.saturating_add(start_number.min(1) - 1);
+                    usize::try_from(clipped_length).unwrap_or(0)
+                }
+                None => usize::MAX,
+            };
+            DerivedValue::Text(text_argument(0).chars().skip(skipped_count).take(taken_count).collect())
+        }
+        DerivedFunction::Replace => {
+            if text_argument(1).is_empty() {
+                DerivedValue::Text(text_argument(0).to_string())
+            } else {
+                DerivedValue::Text(text_argument(0).replace(text_argument(1), text_argument(2)))
+            }
+        }
+        DerivedFunction::Absolute => match &argument_values[0] {
+            DerivedValue::Integer(integer_value) => integer_value.checked_abs().map(DerivedValue::Integer).unwrap_or(DerivedValue::Missing),
+            other_value => finite_float_value(other_value.as_number().unwrap_or(f64::NAN).abs()),
+        },
+        DerivedFunction::Round => match integer_argument(1) {
+            Some(digit_count) => {
+                let scale_factor = 10f64.powi(digit_count.clamp(-15, 15) as i32);
+                finite_float_value((number_argument(0) * scale_factor).round() / scale_factor)
+            }
+            None => float_to_integer_value(number_argument(0).round()),
+        },
+        DerivedFunction::Floor => float_to_integer_value(number_argument(0).floor()),
+        DerivedFunction::Ceiling => float_to_integer_value(number_argument(0).ceil()),
+        DerivedFunction::SquareRoot => finite_float_value(number_argument(0).sqrt()),
+        DerivedFunction::Power => finite_float_value(number_argument(0).powf(number_argument(1))),
+        DerivedFunction::CastToInteger => match &argument_values[0] {
+            DerivedValue::Integer(integer_value) => DerivedValue::Integer(*integer_value),
+            DerivedValue::Float(float_value) => float_to_integer_value(float_value.trunc()),
+            DerivedValue::Boolean(boolean_value) => DerivedValue::Integer(i64::from(*boolean_value)),
+            DerivedValue::Text(text_value) => match text_value.trim().parse::<i64>() {
+                Ok(integer_value) => DerivedValue::Integer(integer_value),
+                Err(_) => parse_finite_float(text_value).map(|float_value| float_to_integer_value(float_value.trunc())).unwrap_or(DerivedValue::Missing),
+            },
+            DerivedValue::Missing => DerivedValue::Missing,
+        },
+        DerivedFunction::CastToFloat => match &argument_values[0] {
+            DerivedValue::Boolean(boolean_value) => DerivedValue::Float(if *boolean_value { 1.0 } else { 0.0 }),
+            DerivedValue::Text(text_value) => parse_finite_float(text_value).map(DerivedValue::Float).unwrap_or(DerivedValue::Missing),
+            other_value => other_value.as_number().map(DerivedValue::Float).unwrap_or(DerivedValue::Missing),
+        },
+        DerivedFunction::CastToText => DerivedValue::Text(argument_values[0].to_cell_text()),
+        DerivedFunction::CastToBoolean => match &argument_values[0] {
+            DerivedValue::Boolean(boolean_value) => DerivedValue::Boolean(*boolean_value),
+            DerivedValue::Text(text_value) => parse_boolean_text(text_value).map(DerivedValue::Boolean).unwrap_or(DerivedValue::Missing),
+            other_value => other_value.as_number().map(|number_value| DerivedValue::Boolean(number_value != 0.0)).unwrap_or(DerivedValue::Missing),
+        },
+        DerivedFunction::Coalesce | DerivedFunction::ConcatenateAll => DerivedValue::Missing,
+    }
+}
+
+/// Imports a CSV into the directory store and materializes its saved derived columns
+///
+/// Derived columns whose expressions no longer compile are skipped with a warning.
+///
+/// # Arguments
+/// * `csv_analysis_results` - Analysis results for the CSV (path, header, metadata)
+/// * `csv_imports_directory` - Directory to create the dataset in
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The stored dataset, derived columns included
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the dataset cannot be written
+/// * `RowsAndColumnsError::MetadataError` - If the saved definitions cannot be read
+pub fn import_csv_with_derived_columns(
+    csv_analysis_results: &CsvAnalysisResults,
+    csv_imports_directory: &Path,
+) -> RowsAndColumnsResult<DirectoryStoreDataset> {
+    let mut store_dataset = import_csv_into_directory_store(csv_analysis_results, csv_imports_directory)?;
+    
+    // Saved derived columns become real columns of the stored dataset
+    for derived_column_definition in read_derived_column_definitions(&csv_analysis_results.metadata_file_path)? {
+        match materialize_derived_column_into_directory_store(&store_dataset, &derived_column_definition) {
+            Ok(extended_dataset) => store_dataset = extended_dataset,
+            Err(derived_error @ RowsAndColumnsError::DerivedColumnExpressionError { .. }) => {
+                print_warning_line(&format!(
+                    "Skipping derived column '{}': {}",
+                    derived_column_definition.derived_column_name,
+                    derived_error
+                ));
+            }
+            Err(other_error) => return Err(other_error),
+        }
+    }
+    
+    Ok(store_dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Column names and types shaped like the sample cats file
+    fn create_test_columns() -> (Vec<String>, Vec<CsvColumnDataType>) {
+        (
+            vec!["id".to_string(), "name".to_string(), "age".to_string(), "breed".to_string(), "weight".to_string()],
+            vec![
+                CsvColumnDataType::Integer,
+                CsvColumnDataType::String,
+                CsvColumnDataType::Integer,
+                CsvColumnDataType::String,
+                CsvColumnDataType::Float,
+            ],
+        )
+    }
+
+    /// Converts a list of cell texts into owned strings
+    fn create_test_row(cell_texts: &[&str]) -> Vec<String> {
+        cell_texts.iter().map(|cell_text| cell_text.to_string()).collect()
+    }
+
+    /// Types are inferred, values computed, missing cells propagate
+    #[test]
+    fn test_derived_columns_compute_typed_values() {
+        let (column_names, column_data_types) = create_test_columns();
+        let definition_texts = [
+            "age_months = age * 12",
+            "label = upper(name) || \"-\" || breed",
+            "stage = case when age >= 4 then \"senior\" when age >= 2 then \"adult\" else \"kitten\" end",
+            "half_age = age / 2",
+            "heavy = if(coalesce(weight, 0) > 4.5, true, false)",
+            "older = age_months + 12",
+        ];
+
+        let mut scope_names = column_names.clone();
+        let mut scope_types = column_data_types.clone();
+        let mut definitions = Vec::new();
+        for definition_text in definition_texts {
+            let definition = parse_derived_column_definition(definition_text, &scope_names, &scope_types)
+                .expect("definition should compile");
+            scope_names.push(definition.derived_column_name.clone());
+            scope_types.push(definition.derived_data_type.clone());
+            definitions.push(definition);
+        }
+        let derived_types: Vec<CsvColumnDataType> = definitions.iter().map(|definition| definition.derived_data_type.clone()).collect();
+        assert_eq!(derived_types, vec![
+            CsvColumnDataType::Integer,
+            CsvColumnDataType::String,
+            CsvColumnDataType::String,
+            CsvColumnDataType::Float,
+            CsvColumnDataType::Boolean,
+            CsvColumnDataType::Integer,
+        ]);
+
+        let compiled_columns = compile_derived_columns(&column_names, &column_data_types, &definitions).expect("should compile");
+        assert_eq!(
+            compiled_columns.evaluate_row(&create_test_row(&["1", "Whiskers", "3", "Siamese", "5.5"])),
+            create_test_row(&["36", "WHISKERS-Siamese", "adult", "1.5", "true", "48"])
+        );
+        assert_eq!(
+            compiled_columns.evaluate_row(&create_test_row(&["2", "Luna", "", "", ""])),
+            create_test_row(&["", "", "kitten", "", "false", ""])
+        );
+    }
+
+    /// Type errors and syntax errors point at the problem
+    #[test]
+    fn test_derived_column_errors_have_positions() {
+        let (column_names, column_data_types) = create_test_columns();
+        let expect_error_position = |definition_text: &str, expected_position: usize, expected_fragment: &str| {
+            match parse_derived_column_definition(definition_text, &column_names, &column_data_types) {
+                Err(RowsAndColumnsError::DerivedColumnExpressionError { error_character_position, derived_error_description, .. }) => {
+                    assert_eq!(error_character_position, expected_position, "{}", derived_error_description);
+                    assert!(derived_error_description.contains(expected_fragment), "{}", derived_error_description);
+                }
+                other_result => panic!("Expected an expression error for {}, got {:?}", definition_text, other_result),
+            }
+        };
+
+        expect_error_position("x = name + 1", 5, "use ||");
+        expect_error_position("x = age > \"3\"", 4, "cannot compare");
+        expect_error_position("x = if(age > 1, 1, \"one\")", 15, "needs integer");
+        expect_error_position("x = colour", 0, "unknown column");
+        expect_error_position("x = upper(name", 10, "expected ')'");
+        expect_error_position("age = 1", 0, "already exists");
+    }
+
+    /// Definitions survive a metadata rewrite, feed the on-the-fly statistics and stay with their dataset
+    #[test]
+    fn test_definitions_persist_and_analyze() {
+        use super::super::csv_processor_module::{StreamingCsvExportWriter, analyze_csv_file_structure_and_types};
+
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_derived_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("cats.csv");
+        std::fs::write(&csv_file_path, "id,name,age\n1,Tom,2\n2,Ann,4\n3,Bob,\n").expect("test CSV should be writable");
+
+        let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should succeed");
+        let column_names: Vec<String> = csv_analysis_results.column_information_list.iter().map(|column| column.column_name.clone()).collect();
+        let column_types: Vec<CsvColumnDataType> = csv_analysis_results.column_information_list.iter().map(|column| column.detected_data_type.clone()).collect();
+        let definition = parse_derived_column_definition("age_months = age * 12", &column_names, &column_types).expect("should parse");
+        save_derived_column_definition(&csv_analysis_results.metadata_file_path, &definition).expect("should save");
+
+        // Re-analyzing rewrites the column tables but keeps the definition
+        let reanalyzed_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should succeed");
+        let saved_definitions = read_derived_column_definitions(&reanalyzed_results.metadata_file_path).expect("should read");
+        assert_eq!(saved_definitions, vec![definition]);
+
+        let compiled_columns = compile_derived_columns(&column_names, &column_types, &saved_definitions).expect("should compile");
+        let derived_statistics = analyze_derived_columns(&reanalyzed_results, &compiled_columns).expect("should analyze");
+        assert_eq!(derived_statistics.len(), 1);
+        assert_eq!(derived_statistics[0].basic_info.column_index, 3);
+        assert_eq!(derived_statistics[0].basic_info.empty_value_count, 1);
+        let numerical_statistics = derived_statistics[0].numerical_statistics.as_ref().expect("numeric statistics");
+        assert_eq!(numerical_statistics.max_value, 48.0);
+
+        // A new CSV written over the path starts without the old file's definitions
+        let mut export_writer = StreamingCsvExportWriter::create(&csv_file_path, &column_names[..2], &column_types[..2])
+            .expect("writer should open");
+        export_writer.write_row(&["1".to_string(), "Tom".to_string()]).expect("row should write");
+        let (_, metadata_file_path) = export_writer.finish().expect("writer should finish");
+        assert!(read_derived_column_definitions(&metadata_file_path).expect("should read").is_empty());
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Overflow, division by zero and huge IDs give exact values or missing cells, never a panic
+    #[test]
+    fn test_arithmetic_edge_cases_never_panic() {
+        let (column_names, column_data_types) = create_test_columns();
+        let definitions: Vec<DerivedColumnDefinition> = [
+            "next_id = id + 1",
+            "doubled = id * 2",
+            "negated = -id",
+            "remainder = id % age",
+            "ratio = weight / age",
+        ]
+            .iter()
+            .map(|definition_text| {
+                parse_derived_column_definition(definition_text, &column_names, &column_data_types).expect("definition should compile")
+            })
+            .collect();
+        let compiled_columns = compile_derived_columns(&column_names, &column_data_types, &definitions).expect("should compile");
+
+        // Integers past 2^53 stay exact
+        assert_eq!(
+            compiled_columns.evaluate_row(&create_test_row(&["9007199254740993", "Tom", "2", "Siamese", "4.0"])),
+            create_test_row(&["9007199254740994", "18014398509481986", "-9007199254740993", "1", "2"])
+        );
+        // i64 overflow (including MIN % -1 and -MIN) and division by zero give missing cells
+        assert_eq!(
+            compiled_columns.evaluate_row(&create_test_row(&["9223372036854775807", "Tom", "0", "Siamese", "4.0"])),
+            create_test_row(&["", "", "-9223372036854775807", "", ""])
+        );
+        assert_eq!(
+            compiled_columns.evaluate_row(&create_test_row(&["-9223372036854775808", "Tom", "-1", "Siamese", "4.0"])),
+            create_test_row(&["-9223372036854775807", "", "", "", "-4"])
+        );
+    }
+}
@@ -0,0 +1,788 @@
+// src/directory_store_module.rs
+
+/// Directory-based column store for rows_and_columns
+///
+/// Imported CSV data is kept as ordinary directories and files so that a person
+/// can find any column and row with a file browser and read the value directly.
+/// The original CSV file is never modified.
+///
+/// # Directory Structure
+/// ```text
+/// rows_columns_data/csv_imports/
+/// └── dataset_001_sales/
+///     ├── csv_metadata.toml          # column names, types, row count, source
+///     ├── column_001_id/
+///     │   ├── row_0001/value.txt
+///     │   └── row_0002/value.txt
+///     └── column_002_region/
+///         └── row_0001/value.txt
+/// ```
+///
+/// Row directory numbers are 1-based and zero-padded to at least four digits;
+/// larger datasets simply use more digits (`row_12345`).
+///
+/// # Core Responsibilities
+/// - Import an analyzed CSV file into a new dataset directory, one row at a time
+/// - Write the dataset's `csv_metadata.toml` so it can be reopened later
+/// - Open existing datasets and read individual cell values on demand
+/// - Stream rows through the same visitor shape as `stream_csv_data_rows`
+/// - Let operations read from either source through `DatasetRowSource`
+/// - Append computed columns (e.g. derived columns) to an existing dataset
+///
+/// # Design Philosophy
+/// - Human-readable storage: every value is a plain `value.txt` file
+/// - No-load processing: rows are streamed from the CSV and written immediately
+/// - The metadata file is written last, so an interrupted import is never
+///   mistaken for a complete dataset
+use std::path::{Path, PathBuf};
+
+// Import CSV structures and the shared row streaming loop
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvRowStreamControl,
+    stream_csv_data_rows,
+};
+
+// Import TOML reading/escaping for the dataset metadata file
+use super::metadata_manager_module::{
+    escape_toml_string_value,
+    read_csv_metadata_columns,
+    read_toml_document_file,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+/// File name of the metadata file inside each dataset directory
+pub const DATASET_METADATA_FILE_NAME: &str = "csv_metadata.toml";
+
+/// File name holding a single cell value inside each row directory
+const CELL_VALUE_FILE_NAME: &str = "value.txt";
+
+/// Prefix of dataset directories inside `csv_imports/`
+const DATASET_DIRECTORY_PREFIX: &str = "dataset_";
+
+/// Longest sanitized name fragment used in dataset/column directory names
+const MAXIMUM_DIRECTORY_NAME_FRAGMENT_LENGTH: usize = 40;
+
+/// An imported dataset in the directory store
+#[derive(Debug, Clone)]
+pub struct DirectoryStoreDataset {
+    /// Absolute path of the `dataset_NNN_name` directory
+    pub dataset_directory_path: PathBuf,
+
+    /// Path of the CSV file this dataset was imported from
+    pub source_csv_path: String,
+
+    /// Column names in column order
+    pub column_names: Vec<String>,
+
+    /// Column data types in column order
+    pub column_data_types: Vec<CsvColumnDataType>,
+
+    /// Column directory paths in column order
+    pub column_directory_paths: Vec<PathBuf>,
+
+    /// Number of data rows stored
+    pub total_row_count: usize,
+}
+
+impl DirectoryStoreDataset {
+    /// Returns the dataset directory name (e.g. `dataset_001_sales`)
+    ///
+    /// # Returns
+    /// * `String` - The final path component of the dataset directory
+    pub fn dataset_name(&self) -> String {
+        self.dataset_directory_path
+            .file_name()
+            .map(|directory_name| directory_name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Reads one cell value from the store
+    ///
+    /// # Arguments
+    /// * `column_position` - 0-based column position
+    /// * `data_row_index` - 0-based data row index
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<String>` - The stored value (empty for missing cells)
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::MetadataError` - If the column position is out of range
+    /// * `RowsAndColumnsError::FileSystemError` - If the value file exists but cannot be read
+    pub fn read_cell_value(&self, column_position: usize, data_row_index: usize) -> RowsAndColumnsResult<String> {
+        let column_directory_path = self.column_directory_paths.get(column_position).ok_or_else(|| {
+            create_metadata_error(
+                &format!("Column position {} is out of range for dataset {}", column_position, self.dataset_name()),
+                &self.dataset_directory_path.display().to_string()
+            )
+        })?;
+
+        let cell_value_path = column_directory_path
+            .join(format_row_directory_name(data_row_index))
+            .join(CELL_VALUE_FILE_NAME);
+
+        match std::fs::read_to_string(&cell_value_path) {
+            Ok(cell_value) => Ok(cell_value),
+            Err(io_error) if io_error.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(io_error) => Err(create_file_system_error(
+                &format!("Failed to read cell value: {}", cell_value_path.display()),
+                io_error
+            )),
+        }
+    }
+
+    /// Reads one full row (all columns) from the store
+    ///
+    /// # Arguments
+    /// * `data_row_index` - 0-based data row index
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<String>>` - Values in column order
+    pub fn read_row_values(&self, data_row_index: usize) -> RowsAndColumnsResult<Vec<String>> {
+        (0..self.column_directory_paths.len())
+            .map(|column_position| self.read_cell_value(column_position, data_row_index))
+            .collect()
+    }
+}
+
+/// Streams every row of a dataset through a visitor, one row at a time
+///
+/// Mirrors `stream_csv_data_rows` so analyses can read from either source.
+///
+/// # Arguments
+/// * `store_dataset` - The dataset to read
+/// * `row_visitor` - Called with (0-based data row index, values in column order)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - Number of rows visited, or the first error
+pub fn stream_directory_store_rows<F>(
+    store_dataset: &DirectoryStoreDataset,
+    mut row_visitor: F,
+) -> RowsAndColumnsResult<usize>
+where
+    F: FnMut(usize, &[String]) -> RowsAndColumnsResult<CsvRowStreamControl>,
+{
+    let mut visited_row_count = 0;
+
+    for data_row_index in 0..store_dataset.total_row_count {
+        let row_values = store_dataset.read_row_values(data_row_index)?;
+        let stream_control = row_visitor(data_row_index, &row_values)?;
+        visited_row_count += 1;
+
+        if stream_control == CsvRowStreamControl::StopReading {
+            break;
+        }
+    }
+
+    Ok(visited_row_count)
+}
+
+/// Where an operation reads its rows from: the analyzed CSV file or a dataset
+/// imported into the directory store
+///
+/// Group-by, sort, join and sampling all take this, so each reads either
+/// source through the same column and streaming calls.
+#[derive(Debug, Clone, Copy)]
+pub enum DatasetRowSource<'a> {
+    /// The analyzed CSV file
+    CsvFile(&'a CsvAnalysisResults),
+
+    /// A dataset imported into the directory store
+    DirectoryStore(&'a DirectoryStoreDataset),
+}
+
+impl DatasetRowSource<'_> {
+    /// Returns the column names in column order
+    ///
+    /// # Returns
+    /// * `Vec<String>` - Column names
+    pub fn column_names(&self) -> Vec<String> {
+        match self {
+            DatasetRowSource::CsvFile(csv_analysis_results) => csv_analysis_results.column_information_list
+                .iter()
+                .map(|column_info| column_info.column_name.clone())
+                .collect(),
+            DatasetRowSource::DirectoryStore(store_dataset) => store_dataset.column_names.clone(),
+        }
+    }
+
+    /// Returns the column data types in column order
+    ///
+    /// # Returns
+    /// * `Vec<CsvColumnDataType>` - Column data types
+    pub fn column_data_types(&self) -> Vec<CsvColumnDataType> {
+        match self {
+            DatasetRowSource::CsvFile(csv_analysis_results) => csv_analysis_results.column_information_list
+                .iter()
+                .map(|column_info| column_info.detected_data_type.clone())
+                .collect(),
+            DatasetRowSource::DirectoryStore(store_dataset) => store_dataset.column_data_types.clone(),
+        }
+    }
+
+    /// Returns a short label: the CSV file stem or the dataset directory name
+    ///
+    /// # Returns
+    /// * `String` - The label
+    pub fn source_label(&self) -> String {
+        match self {
+            DatasetRowSource::CsvFile(csv_analysis_results) => csv_analysis_results.csv_file_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            DatasetRowSource::DirectoryStore(store_dataset) => store_dataset.dataset_name(),
+        }
+    }
+
+    /// Streams every data row through a visitor
+    ///
+    /// # Arguments
+    /// * `row_visitor` - Called with (0-based data row index, values in column order)
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<usize>` - Number of rows visited, or the first error
+    pub fn stream_rows<F>(&self, row_visitor: F) -> RowsAndColumnsResult<usize>
+    where
+        F: FnMut(usize, &[String]) -> RowsAndColumnsResult<CsvRowStreamControl>,
+    {
+        match self {
+            DatasetRowSource::CsvFile(csv_analysis_results) => stream_csv_data_rows(
+                &csv_analysis_results.csv_file_path,
+                csv_analysis_results.has_header_row,
+                row_visitor,
+            ),
+            DatasetRowSource::DirectoryStore(store_dataset) => stream_directory_store_rows(store_dataset, row_visitor),
+        }
+    }
+}
+
+/// Formats the row directory name for a 0-based data row index
+///
+/// # Arguments
+/// * `data_row_index` - 0-based data row index
+///
+/// # Returns
+/// * `String` - e.g. `row_0001` for index 0
+pub fn format_row_directory_name(data_row_index: usize) -> String {
+    format!("row_{:04}", data_row_index + 1)
+}
+
+/// Converts a column or file name into a safe, readable directory name fragment
+///
+/// Letters and digits are lowercased and kept; every other run of characters
+/// becomes a single underscore.
+///
+/// # Arguments
+/// * `raw_name` - Column or file name
+///
+/// # Returns
+/// * `String` - Sanitized fragment (`unnamed` if nothing usable remains)
+pub fn sanitize_name_for_directory(raw_name: &str) -> String {
+    let mut sanitized_name = String::new();
+
+    for name_character in raw_name.chars() {
+        if name_character.is_alphanumeric() {
+            sanitized_name.extend(name_character.to_lowercase());
+        } else if !sanitized_name.ends_with('_') {
+            sanitized_name.push('_');
+        }
+    }
+
+    let sanitized_name: String = sanitized_name
+        .trim_matches('_')
+        .chars()
+        .take(MAXIMUM_DIRECTORY_NAME_FRAGMENT_LENGTH)
+        .collect();
+
+    if sanitized_name.is_empty() {
+        String::from("unnamed")
+    } else {
+        sanitized_name
+    }
+}
+
+/// Finds the next unused dataset number in the imports directory
+///
+/// # Arguments
+/// * `csv_imports_directory` - The `csv_imports/` directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - One more than the highest existing dataset number
+fn find_next_dataset_number(csv_imports_directory: &Path) -> RowsAndColumnsResult<usize> {
+    let directory_entries = std::fs::read_dir(csv_imports_directory).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to list imports directory: {}", csv_imports_directory.display()),
+            io_error
+        )
+    })?;
+
+    let mut highest_dataset_number = 0;
+
+    for directory_entry in directory_entries.flatten() {
+        let entry_name = directory_entry.file_name().to_string_lossy().to_string();
+        if let Some(number_and_name) = entry_name.strip_prefix(DATASET_DIRECTORY_PREFIX) {
+            let number_text: String = number_and_name.chars().take_while(|c| c.is_ascii_digit()).collect();
+            if let Ok(dataset_number) = number_text.parse::<usize>() {
+                highest_dataset_number = highest_dataset_number.max(dataset_number);
+            }
+        }
+    }
+
+    Ok(highest_dataset_number + 1)
+}
+
+/// Creates a directory (and parents), mapping failures to a file system error
+///
+/// # Arguments
+/// * `directory_path` - Directory to create
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn create_store_directory(directory_path: &Path) -> RowsAndColumnsResult<()> {
+    std::fs::create_dir_all(directory_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to create store directory: {}", directory_path.display()),
+            io_error
+        )
+    })
+}
+
+/// Imports an analyzed CSV file into a new dataset in the directory store
+///
+/// Rows are streamed from the CSV; each cell is written to
+/// `column_NNN_name/row_NNNN/value.txt` as it is read. Missing trailing fields
+/// are stored as empty values. The metadata file is written after all rows.
+///
+/// # Arguments
+/// * `csv_analysis_results` - Analysis results for the CSV (path, header, columns)
+/// * `csv_imports_directory` - The `csv_imports/` directory of the data root
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The newly created dataset
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If directories or files cannot be written
+pub fn import_csv_into_directory_store(
+    csv_analysis_results: &CsvAnalysisResults,
+    csv_imports_directory: &Path,
+) -> RowsAndColumnsResult<DirectoryStoreDataset> {
+    // Step 1: Choose the dataset directory name
+    let csv_file_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let dataset_number = find_next_dataset_number(csv_imports_directory)?;
+    let dataset_directory_path = csv_imports_directory.join(format!(
+        "{}{:03}_{}",
+        DATASET_DIRECTORY_PREFIX,
+        dataset_number,
+        sanitize_name_for_directory(&csv_file_stem)
+    ));
+    create_store_directory(&dataset_directory_path)?;
+
+    // Step 2: Create one directory per column
+    let mut column_directory_names = Vec::new();
+    let mut column_directory_paths = Vec::new();
+    for column_info in &csv_analysis_results.column_information_list {
+        let column_directory_name = format!(
+            "column_{:03}_{}",
+            column_info.column_index + 1,
+            sanitize_name_for_directory(&column_info.column_name)
+        );
+        let column_directory_path = dataset_directory_path.join(&column_directory_name);
+        create_store_directory(&column_directory_path)?;
+        column_directory_names.push(column_directory_name);
+        column_directory_paths.push(column_directory_path);
+    }
+
+    // Step 3: Stream rows into row directories
+    let stored_row_count = stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |data_row_index, field_values| {
+            let row_directory_name = format_row_directory_name(data_row_index);
+            for (column_position, column_directory_path) in column_directory_paths.iter().enumerate() {
+                let row_directory_path = column_directory_path.join(&row_directory_name);
+                create_store_directory(&row_directory_path)?;
+
+                let cell_value = field_values.get(column_position).map(String::as_str).unwrap_or("");
+                let cell_value_path = row_directory_path.join(CELL_VALUE_FILE_NAME);
+                std::fs::write(&cell_value_path, cell_value).map_err(|io_error| {
+                    create_file_system_error(
+                        &format!("Failed to write cell value: {}", cell_value_path.display()),
+                        io_error
+                    )
+                })?;
+            }
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    // Step 4: Write the dataset metadata last
+    let column_entries: Vec<(String, CsvColumnDataType, String)> = csv_analysis_results.column_information_list
+        .iter()
+        .zip(column_directory_names)
+        .map(|(column_info, column_directory_name)| {
+            (column_info.column_name.clone(), column_info.detected_data_type.clone(), column_directory_name)
+        })
+        .collect();
+    write_dataset_metadata_file(
+        &dataset_directory_path,
+        &csv_analysis_results.csv_file_path.display().to_string(),
+        csv_analysis_results.has_header_row,
+        &column_entries,
+        stored_row_count,
+    )?;
+
+    open_directory_store_dataset(&dataset_directory_path)
+}
+
+/// Writes (or rewrites) a dataset's `csv_metadata.toml`
+///
+/// # Arguments
+/// * `dataset_directory_path` - The `dataset_NNN_name` directory
+/// * `source_csv_path` - Path of the CSV the dataset was imported from
+/// * `has_header_row` - Whether that CSV had a header row
+/// * `column_entries` - (name, data type, directory name) per column, in column order
+/// * `total_row_count` - Number of stored data rows
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn write_dataset_metadata_file(
+    dataset_directory_path: &Path,
+    source_csv_path: &str,
+    has_header_row: bool,
+    column_entries: &[(String, CsvColumnDataType, String)],
+    total_row_count: usize,
+) -> RowsAndColumnsResult<()> {
+    let metadata_file_path = dataset_directory_path.join(DATASET_METADATA_FILE_NAME);
+    let mut toml_content = String::new();
+    toml_content.push_str("# Directory Store Dataset Metadata\n");
+    toml_content.push_str("# Generated by rows_and_columns\n\n");
+    toml_content.push_str(&format!("source_csv_path = \"{}\"\n", escape_toml_string_value(source_csv_path)));
+    toml_content.push_str(&format!("has_header_row = {}\n", has_header_row));
+    toml_content.push_str(&format!("total_columns = {}\n", column_entries.len()));
+    toml_content.push_str(&format!("total_rows = {}\n", total_row_count));
+    toml_content.push('\n');
+
+    for (column_index, (column_name, column_data_type, column_directory_name)) in column_entries.iter().enumerate() {
+        toml_content.push_str(&format!("[column_{}]\n", column_index + 1));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_string_value(column_name)));
+        toml_content.push_str(&format!("data_type = \"{}\"\n", column_data_type.to_toml_string()));
+        toml_content.push_str(&format!("column_index = {}\n", column_index));
+        toml_content.push_str(&format!("directory_name = \"{}\"\n", escape_toml_string_value(column_directory_name)));
+        toml_content.push('\n');
+    }
+
+    std::fs::write(&metadata_file_path, toml_content).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write dataset metadata: {}", metadata_file_path.display()),
+            io_error
+        )
+    })
+}
+
+/// Appends a computed column to an existing dataset
+///
+/// Every row is streamed once; the computed value is written to a new
+/// `column_NNN_name/row_NNNN/value.txt` and the metadata file is rewritten
+/// last, so an interrupted append leaves the dataset as it was.
+///
+/// # Arguments
+/// * `store_dataset` - The dataset to extend
+/// * `new_column_name` - Name of the new column
+/// * `new_column_data_type` - Data type recorded for the new column
+/// * `compute_cell_value` - Returns the new column's value for a row's existing values
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The reopened, extended dataset
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If the column name is already used
+/// * `RowsAndColumnsError::FileSystemError` - If directories or files cannot be written
+pub fn add_computed_column_to_directory_store<F>(
+    store_dataset: &DirectoryStoreDataset,
+    new_column_name: &str,
+    new_column_data_type: &CsvColumnDataType,
+    mut compute_cell_value: F,
+) -> RowsAndColumnsResult<DirectoryStoreDataset>
+where
+    F: FnMut(&[String]) -> String,
+{
+    let metadata_file_path = store_dataset.dataset_directory_path.join(DATASET_METADATA_FILE_NAME);
+    let metadata_path_text = metadata_file_path.display().to_string();
+    if store_dataset.column_names.iter().any(|column_name| column_name == new_column_name) {
+        return Err(create_metadata_error(
+            &format!("Dataset {} already has a column named '{}'", store_dataset.dataset_name(), new_column_name),
+            &metadata_path_text
+        ));
+    }
+
+    // Step 1: Create the new column directory after the existing ones
+    let new_column_directory_name = format!(
+        "column_{:03}_{}",
+        store_dataset.column_names.len() + 1,
+        sanitize_name_for_directory(new_column_name)
+    );
+    let new_column_directory_path = store_dataset.dataset_directory_path.join(&new_column_directory_name);
+    create_store_directory(&new_column_directory_path)?;
+
+    // Step 2: Stream the rows and write each computed value
+    stream_directory_store_rows(store_dataset, |data_row_index, row_values| {
+        let row_directory_path = new_column_directory_path.join(format_row_directory_name(data_row_index));
+        create_store_directory(&row_directory_path)?;
+
+        let cell_value_path = row_directory_path.join(CELL_VALUE_FILE_NAME);
+        std::fs::write(&cell_value_path, compute_cell_value(row_values)).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to write cell value: {}", cell_value_path.display()),
+                io_error
+            )
+        })?;
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    // Step 3: Rewrite the metadata with the new column
+    let has_header_row = read_toml_document_file(&metadata_file_path)?
+        .get_table("")
+        .and_then(|root_table| root_table.get_value("has_header_row"))
+        .and_then(|header_value| header_value.as_boolean())
+        .unwrap_or(true);
+    let mut column_entries: Vec<(String, CsvColumnDataType, String)> = store_dataset.column_names
+        .iter()
+        .zip(&store_dataset.column_data_types)
+        .zip(&store_dataset.column_directory_paths)
+        .map(|((column_name, column_data_type), column_directory_path)| {
+            let column_directory_name = column_directory_path
+                .file_name()
+                .map(|directory_name| directory_name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (column_name.clone(), column_data_type.clone(), column_directory_name)
+        })
+        .collect();
+    column_entries.push((new_column_name.to_string(), new_column_data_type.clone(), new_column_directory_name));
+    write_dataset_metadata_file(
+        &store_dataset.dataset_directory_path,
+        &store_dataset.source_csv_path,
+        has_header_row,
+        &column_entries,
+        store_dataset.total_row_count,
+    )?;
+
+    open_directory_store_dataset(&store_dataset.dataset_directory_path)
+}
+
+/// Opens an existing dataset directory by reading its metadata file
+///
+/// # Arguments
+/// * `dataset_directory_path` - Path of a `dataset_NNN_name` directory
+///
+/// # Returns
+/// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The opened dataset
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the metadata file cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If required metadata keys are missing
+pub fn open_directory_store_dataset(dataset_directory_path: &Path) -> RowsAndColumnsResult<DirectoryStoreDataset> {
+    let metadata_file_path = dataset_directory_path.join(DATASET_METADATA_FILE_NAME);
+    let metadata_path_text = metadata_file_path.display().to_string();
+
+    let metadata_document = read_toml_document_file(&metadata_file_path)?;
+    let root_table = metadata_document.get_table("").ok_or_else(|| {
+        create_metadata_error("Dataset metadata has no root table", &metadata_path_text)
+    })?;
+
+    let total_row_count = root_table.get_value("total_rows")
+        .and_then(|row_count_value| row_count_value.as_integer())
+        .and_then(|row_count_integer| usize::try_from(row_count_integer).ok())
+        .ok_or_else(|| {
+            create_metadata_error("Dataset metadata is missing 'total_rows'", &metadata_path_text)
+        })?;
+
+    let source_csv_path = root_table.get_value("source_csv_path")
+        .and_then(|source_value| source_value.as_text())
+        .unwrap_or("")
+        .to_string();
+
+    let metadata_columns = read_csv_metadata_columns(&metadata_file_path)?;
+
+    let mut column_names = Vec::new();
+    let mut column_data_types = Vec::new();
+    let mut column_directory_paths = Vec::new();
+
+    for metadata_column in metadata_columns {
+        let column_directory_name = metadata_column.store_directory_name.ok_or_else(|| {
+            create_metadata_error(
+                &format!("Column '{}' has no 'directory_name'", metadata_column.column_name),
+                &metadata_path_text
+            )
+        })?;
+        column_directory_paths.push(dataset_directory_path.join(column_directory_name));
+        column_names.push(metadata_column.column_name);
+        column_data_types.push(metadata_column.column_data_type);
+    }
+
+    Ok(DirectoryStoreDataset {
+        dataset_directory_path: dataset_directory_path.to_path_buf(),
+        source_csv_path,
+        column_names,
+        column_data_types,
+        column_directory_paths,
+        total_row_count,
+    })
+}
+
+/// Opens every dataset directory in the imports directory
+///
+/// Only `dataset_NNN_name` directories are considered. A dataset whose
+/// metadata cannot be read is returned with its error, so a caller can warn
+/// about it and still list the others.
+///
+/// # Arguments
+/// * `csv_imports_directory` - The `csv_imports/` directory of the data root
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<(PathBuf, RowsAndColumnsResult<DirectoryStoreDataset>)>>` -
+///   Each dataset directory with its opened dataset or error, sorted by directory name
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the imports directory cannot be listed
+pub fn list_directory_store_datasets(
+    csv_imports_directory: &Path,
+) -> RowsAndColumnsResult<Vec<(PathBuf, RowsAndColumnsResult<DirectoryStoreDataset>)>> {
+    let directory_entries = std::fs::read_dir(csv_imports_directory).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to list imports directory: {}", csv_imports_directory.display()),
+            io_error
+        )
+    })?;
+
+    let mut dataset_directory_paths: Vec<PathBuf> = directory_entries
+        .flatten()
+        .map(|directory_entry| directory_entry.path())
+        .filter(|entry_path| {
+            entry_path.is_dir()
+                && entry_path
+                    .file_name()
+                    .is_some_and(|entry_name| entry_name.to_string_lossy().starts_with(DATASET_DIRECTORY_PREFIX))
+        })
+        .collect();
+    dataset_directory_paths.sort();
+
+    Ok(dataset_directory_paths
+        .into_iter()
+        .map(|dataset_directory_path| {
+            let opened_dataset = open_directory_store_dataset(&dataset_directory_path);
+            (dataset_directory_path, opened_dataset)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csv_processor_module::CsvColumnInformation;
+
+    /// Builds a unique temporary directory for a test
+    fn create_test_directory(test_name: &str) -> PathBuf {
+        let test_directory = std::env::temp_dir().join(format!(
+            "rows_and_columns_store_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("test directory should be creatable");
+        test_directory
+    }
+
+    /// Test directory name sanitizing
+    #[test]
+    fn test_sanitize_name_for_directory() {
+        assert_eq!(sanitize_name_for_directory("Sales Region (EU)"), "sales_region_eu");
+        assert_eq!(sanitize_name_for_directory("***"), "unnamed");
+        assert_eq!(format_row_directory_name(0), "row_0001");
+        assert_eq!(format_row_directory_name(12344), "row_12345");
+    }
+
+    /// Test a full import and reopen round trip
+    #[test]
+    fn test_import_and_reopen_dataset() {
+        let test_directory = create_test_directory("import");
+        let csv_file_path = test_directory.join("people.csv");
+        std::fs::write(&csv_file_path, "name,age\nAda,36\nBob\n").expect("csv should be writable");
+
+        let make_column = |column_index: usize, column_name: &str, detected_data_type: CsvColumnDataType| {
+            CsvColumnInformation {
+                column_index,
+                column_name: column_name.to_string(),
+                detected_data_type,
+                non_empty_value_count: 0,
+                empty_value_count: 0,
+                sample_values: Vec::new(),
+            }
+        };
+
+        let csv_analysis_results = CsvAnalysisResults {
+            csv_file_path: csv_file_path.clone(),
+            has_header_row: true,
+            total_column_count: 2,
+            total_data_row_count: 2,
+            column_information_list: vec![
+                make_column(0, "name", CsvColumnDataType::String),
+                make_column(1, "age", CsvColumnDataType::Integer),
+            ],
+            metadata_file_path: test_directory.join("people.csv_metadata.toml"),
+            metadata_file_already_existed: false,
+        };
+
+        let imports_directory = test_directory.join("csv_imports");
+        std::fs::create_dir_all(&imports_directory).expect("imports directory");
+
+        let imported_dataset = import_csv_into_directory_store(&csv_analysis_results, &imports_directory)
+            .expect("import should succeed");
+        assert_eq!(imported_dataset.dataset_name(), "dataset_001_people");
+        assert_eq!(imported_dataset.total_row_count, 2);
+        assert_eq!(imported_dataset.read_row_values(0).expect("row 0"), vec!["Ada", "36"]);
+        assert_eq!(imported_dataset.read_cell_value(1, 1).expect("missing cell"), "");
+
+        let mut streamed_names = Vec::new();
+        stream_directory_store_rows(&imported_dataset, |_, row_values| {
+            streamed_names.push(row_values[0].clone());
+            Ok(CsvRowStreamControl::ContinueReading)
+        }).expect("streaming should succeed");
+        assert_eq!(streamed_names, vec!["Ada", "Bob"]);
+
+        let reopened_dataset = open_directory_store_dataset(&imported_dataset.dataset_directory_path)
+            .expect("reopen should succeed");
+        assert_eq!(reopened_dataset.column_names, vec!["name", "age"]);
+        assert_eq!(reopened_dataset.column_data_types[1], CsvColumnDataType::Integer);
+
+        let second_dataset = import_csv_into_directory_store(&csv_analysis_results, &imports_directory)
+            .expect("second import should succeed");
+        assert_eq!(second_dataset.dataset_name(), "dataset_002_people");
+
+        std::fs::create_dir_all(imports_directory.join("dataset_003_broken")).expect("dir should be created");
+        std::fs::create_dir_all(imports_directory.join("not_a_dataset")).expect("dir should be created");
+        let listed_datasets = list_directory_store_datasets(&imports_directory).expect("listing should succeed");
+        assert_eq!(listed_datasets.len(), 3);
+        assert!(matches!(&listed_datasets[0].1, Ok(listed_dataset) if listed_dataset.dataset_name() == "dataset_001_people"));
+        assert!(listed_datasets[2].1.is_err());
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test that an incomplete dataset (no metadata) is rejected
+    #[test]
+    fn test_open_rejects_incomplete_dataset() {
+        let test_directory = create_test_directory("incomplete");
+        assert!(open_directory_store_dataset(&test_directory).is_err());
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
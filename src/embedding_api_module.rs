@@ -0,0 +1,729 @@
+// src/embedding_api_module.rs
+
+/// Library API for embedding rows_and_columns in other tools (FF, Lines, ...)
+///
+/// The command line application prints as it works. An embedding application
+/// instead wants the results as values: the statistics to show in its own
+/// panes, a chart as a `String` to place on its screen, a report written to a
+/// buffer. This module is that surface. `CsvDataset::open` analyzes a file
+/// once; everything else reads from the analysis or streams the file again.
+///
+/// ```no_run
+/// use rows_and_columns::{ChartGlyphMode, CsvDataset, render_chart_as_text};
+///
+/// let cats_dataset = CsvDataset::open(std::path::Path::new("data/cats.csv"))?;
+/// print!("{}", cats_dataset.describe_as_text());
+/// let age_histogram = cats_dataset.build_histogram_chart("age")?;
+/// print!("{}", render_chart_as_text(&age_histogram, 60, 15, ChartGlyphMode::Ascii));
+/// # Ok::<(), rows_and_columns::RowsAndColumnsError>(())
+/// ```
+///
+/// # Core Responsibilities
+/// - Open and analyze a CSV file (plain, `.csv.gz`/`.tsv.gz` or a named pipe),
+///   with the caller's work directory and settings
+/// - Describe the columns as text or JSON
+/// - Build charts by column name and render them to a `String` or a writer
+/// - Import into the directory store and export reports to a writer or file
+///
+/// # Design Philosophy
+/// - Return data, never print: every call runs with console output silenced
+/// - Never read `rows_and_columns.toml`: settings come from the caller
+/// - A thin layer over the modules the command line uses, so both give the
+///   same numbers, charts and reports
+/// - Columns are named, not numbered, so callers do not depend on file order
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Import CSV input handling (compressed files and pipes are spooled)
+use super::csv_input_source_module::{
+    CsvInputSource,
+    MultiPassCsvInputFile,
+    resolve_csv_input_source,
+    spool_csv_input_to_work_file,
+};
+
+// Import CSV analysis and the describe text
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    EnhancedCsvColumnInformation,
+    NumericalColumnStatistics,
+    CategoricalColumnStatistics,
+    analyze_csv_file_structure_and_types,
+    format_enhanced_csv_analysis_results,
+    perform_enhanced_statistical_analysis,
+};
+
+// Import chart definitions, builders and the text renderer
+use super::chart_rendering_module::{
+    ChartDefinition,
+    ChartGlyphMode,
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    DEFAULT_MAXIMUM_BAR_COUNT,
+    MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+    build_bar_chart_from_categorical_statistics,
+    build_box_plot_chart_from_statistics,
+    build_histogram_chart_from_csv_column,
+    build_scatter_plot_chart_from_csv_columns,
+    render_chart_as_text_lines,
+};
+
+// Import the SVG renderer
+use super::svg_chart_module::render_chart_as_svg;
+
+// Import JSON output for describe
+use super::json_output_module::analysis_results_to_json;
+
+// Import report building and writing
+use super::analysis_report_module::{AnalysisReportFormat, build_analysis_report_text, write_analysis_report_file};
+
+// Import the directory store and imports with saved derived columns
+use super::directory_store_module::DirectoryStoreDataset;
+use super::derived_column_module::import_csv_with_derived_columns;
+
+// Import silencing of progress, warning and result lines
+use super::console_output_module::run_with_silent_console_output;
+
+// Import the settings an embedding application passes instead of a configuration file
+use super::application_configuration_module::{ApplicationConfiguration, run_with_application_configuration};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_csv_processing_error,
+    create_data_type_validation_error,
+    create_file_system_error,
+};
+
+/// Where `CsvDataset::open_with_options` keeps its work files and which settings it uses
+///
+/// The command line spools into its data directory and reads
+/// `rows_and_columns.toml`; an embedding application passes both here.
+#[derive(Debug, Clone)]
+pub struct CsvDatasetOpenOptions {
+    /// Parent directory for the spooled copies of compressed files and named pipes
+    pub temporary_work_directory: PathBuf,
+
+    /// Type detection, top value and chart settings used for the dataset
+    pub application_configuration: ApplicationConfiguration,
+}
+
+impl Default for CsvDatasetOpenOptions {
+    /// The system temporary directory and the built-in default settings
+    fn default() -> Self {
+        CsvDatasetOpenOptions {
+            temporary_work_directory: std::env::temp_dir(),
+            application_configuration: ApplicationConfiguration::default(),
+        }
+    }
+}
+
+/// An analyzed CSV file, ready to describe, chart, import or export
+///
+/// Opening writes (or refreshes) the file's `.csv_metadata.toml` next to it,
+/// as the command line does. A spooled copy of a compressed file or named
+/// pipe is removed when the dataset is dropped.
+#[derive(Debug)]
+pub struct CsvDataset {
+    /// The file the rows are read from (the original or a spooled copy)
+    csv_input_file: MultiPassCsvInputFile,
+
+    /// Structure, header and detected column types
+    csv_analysis_results: CsvAnalysisResults,
+
+    /// Per-column statistics in file order
+    enhanced_analysis_results: Vec<EnhancedCsvColumnInformation>,
+
+    /// Settings the dataset was opened with, used again for charts and text
+    application_configuration: Arc<ApplicationConfiguration>,
+}
+
+impl CsvDataset {
+    /// Opens a CSV file with the default options and analyzes it
+    ///
+    /// Compressed files and named pipes are copied to the system temporary
+    /// directory, and the built-in default settings are used; see
+    /// `open_with_options` to choose both.
+    ///
+    /// # Arguments
+    /// * `csv_file_path` - Path of the CSV file
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<CsvDataset>` - The analyzed dataset
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the file is missing or cannot be read
+    /// * `RowsAndColumnsError::CsvProcessingError` - If the file is empty or malformed
+    /// * `RowsAndColumnsError::ConfigurationError` - If the path is not valid UTF-8
+    pub fn open(csv_file_path: &Path) -> RowsAndColumnsResult<CsvDataset> {
+        CsvDataset::open_with_options(csv_file_path, &CsvDatasetOpenOptions::default())
+    }
+
+    /// Opens a CSV file and analyzes its structure, types and statistics
+    ///
+    /// Compressed files (`.csv.gz`, `.tsv.gz`) and named pipes are copied to
+    /// the options' work directory first, since they can be read only once.
+    /// The options' settings apply to this dataset only, on every thread.
+    ///
+    /// # Arguments
+    /// * `csv_file_path` - Path of the CSV file
+    /// * `open_options` - Work directory and settings
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<CsvDataset>` - The analyzed dataset
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the file is missing or cannot be read
+    /// * `RowsAndColumnsError::CsvProcessingError` - If the file is empty or malformed
+    /// * `RowsAndColumnsError::ConfigurationError` - If the path is not valid UTF-8
+    pub fn open_with_options(csv_file_path: &Path, open_options: &CsvDatasetOpenOptions) -> RowsAndColumnsResult<CsvDataset> {
+        let application_configuration = Arc::new(open_options.application_configuration.clone());
+        run_with_dataset_settings(&application_configuration, || {
+            // Step 1: Decide whether the file can be read more than once
+            let csv_file_path_text = csv_file_path.to_str().ok_or_else(|| {
+                create_configuration_error(&format!("CSV file path is not valid UTF-8: {}", csv_file_path.display()))
+            })?;
+            let csv_input_file = match resolve_csv_input_source(csv_file_path_text) {
+                CsvInputSource::RegularFile(regular_file_path) => {
+                    MultiPassCsvInputFile::OriginalFile(canonicalize_existing_csv_file_path(&regular_file_path)?)
+                }
+                read_once_source => MultiPassCsvInputFile::Spooled(
+                    spool_csv_input_to_work_file(&read_once_source, &open_options.temporary_work_directory)?
+                ),
+            };
+
+            // Step 2: Analyze structure and types, then the statistics
+            let csv_analysis_results = analyze_csv_file_structure_and_types(csv_input_file.csv_file_path())?;
+            let enhanced_analysis_results = perform_enhanced_statistical_analysis(
+                csv_input_file.csv_file_path(),
+                &csv_analysis_results
+            )?;
+
+            Ok(CsvDataset {
+                csv_input_file,
+                csv_analysis_results,
+                enhanced_analysis_results,
+                application_configuration: Arc::clone(&application_configuration),
+            })
+        })
+    }
+
+    /// Returns the path the rows are read from
+    ///
+    /// # Returns
+    /// * `&PathBuf` - The original file, or its spooled copy
+    pub fn csv_file_path(&self) -> &PathBuf {
+        self.csv_input_file.csv_file_path()
+    }
+
+    /// Returns the structure and type analysis
+    ///
+    /// # Returns
+    /// * `&CsvAnalysisResults` - Header, row and column counts and column types
+    pub fn analysis_results(&self) -> &CsvAnalysisResults {
+        &self.csv_analysis_results
+    }
+
+    /// Returns the per-column statistics in file order
+    ///
+    /// # Returns
+    /// * `&[EnhancedCsvColumnInformation]` - Numerical or categorical statistics per column
+    pub fn column_statistics(&self) -> &[EnhancedCsvColumnInformation] {
+        &self.enhanced_analysis_results
+    }
+
+    /// Returns the column names in file order
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The names (generated names when the file has no header)
+    pub fn column_names(&self) -> Vec<String> {
+        self.csv_analysis_results.column_information_list
+            .iter()
+            .map(|column_info| column_info.column_name.clone())
+            .collect()
+    }
+
+    /// Describes every column as the `describe` subcommand prints it
+    ///
+    /// # Returns
+    /// * `String` - Pandas-style description text
+    pub fn describe_as_text(&self) -> String {
+        run_with_dataset_settings(&self.application_configuration, || {
+            format_enhanced_csv_analysis_results(&self.enhanced_analysis_results)
+        })
+    }
+
+    /// Describes the file and every column as a JSON document
+    ///
+    /// # Returns
+    /// * `String` - The document `describe --format json` prints
+    pub fn describe_as_json(&self) -> String {
+        analysis_results_to_json(&self.csv_analysis_results, &self.enhanced_analysis_results).to_json_text(true)
+    }
+
+    /// Builds a histogram of a numeric column (the file is read again)
+    ///
+    /// # Arguments
+    /// * `column_name` - Name of a numeric column
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ChartDefinition>` - The chart
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If there is no such column
+    /// * `RowsAndColumnsError::DataTypeValidationError` - If the column is not numeric
+    /// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read again
+    pub fn build_histogram_chart(&self, column_name: &str) -> RowsAndColumnsResult<ChartDefinition> {
+        let (column_info, numerical_statistics) = self.find_numerical_column(column_name, "Building a histogram")?;
+        run_with_dataset_settings(&self.application_configuration, || {
+            build_histogram_chart_from_csv_column(
+                self.csv_file_path(),
+                self.csv_analysis_results.has_header_row,
+                column_info.basic_info.column_index,
+                &column_info.basic_info.column_name,
+                numerical_statistics,
+                DEFAULT_HISTOGRAM_BIN_COUNT,
+            )
+        })
+    }
+
+    /// Builds a box plot of a numeric column from its statistics
+    ///
+    /// # Arguments
+    /// * `column_name` - Name of a numeric column
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ChartDefinition>` - The chart
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If there is no such column
+    /// * `RowsAndColumnsError::DataTypeValidationError` - If the column is not numeric
+    pub fn build_box_plot_chart(&self, column_name: &str) -> RowsAndColumnsResult<ChartDefinition> {
+        let (column_info, numerical_statistics) = self.find_numerical_column(column_name, "Building a box plot")?;
+        Ok(build_box_plot_chart_from_statistics(&column_info.basic_info.column_name, numerical_statistics))
+    }
+
+    /// Builds a bar chart of a categorical column's most frequent values
+    ///
+    /// # Arguments
+    /// * `column_name` - Name of a categorical column
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ChartDefinition>` - The chart
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If there is no such column
+    /// * `RowsAndColumnsError::DataTypeValidationError` - If the column is not categorical
+    pub fn build_bar_chart(&self, column_name: &str) -> RowsAndColumnsResult<ChartDefinition> {
+        let column_info = self.find_column(column_name)?;
+        let categorical_statistics: &CategoricalColumnStatistics = column_info.categorical_statistics
+            .as_ref()
+            .ok_or_else(|| create_data_type_validation_error("Building a bar chart", column_name, "categorical column"))?;
+        Ok(build_bar_chart_from_categorical_statistics(
+            &column_info.basic_info.column_name,
+            categorical_statistics,
+            DEFAULT_MAXIMUM_BAR_COUNT,
+        ))
+    }
+
+    /// Builds a scatter plot of one numeric column against another
+    ///
+    /// # Arguments
+    /// * `x_column_name` - Numeric column on the horizontal axis
+    /// * `y_column_name` - Numeric column on the vertical axis
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ChartDefinition>` - The chart
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If a column does not exist
+    /// * `RowsAndColumnsError::DataTypeValidationError` - If a column is not numeric
+    /// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read again
+    pub fn build_scatter_plot_chart(&self, x_column_name: &str, y_column_name: &str) -> RowsAndColumnsResult<ChartDefinition> {
+        let (x_column_info, _) = self.find_numerical_column(x_column_name, "Building a scatter plot")?;
+        let (y_column_info, _) = self.find_numerical_column(y_column_name, "Building a scatter plot")?;
+        run_with_dataset_settings(&self.application_configuration, || {
+            build_scatter_plot_chart_from_csv_columns(
+                self.csv_file_path(),
+                self.csv_analysis_results.has_header_row,
+                x_column_info.basic_info.column_index,
+                &x_column_info.basic_info.column_name,
+                y_column_info.basic_info.column_index,
+                &y_column_info.basic_info.column_name,
+                MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+            )
+        })
+    }
+
+    /// Builds the analysis report as text
+    ///
+    /// # Arguments
+    /// * `report_format` - Markdown, plain text or HTML
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<String>` - The report
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read again for charts
+    pub fn build_report(&self, report_format: AnalysisReportFormat) -> RowsAndColumnsResult<String> {
+        run_with_dataset_settings(&self.application_configuration, || {
+            build_analysis_report_text(&self.csv_analysis_results, &self.enhanced_analysis_results, report_format)
+        })
+    }
+
+    /// Writes the analysis report to any writer (a buffer, a socket, a file)
+    ///
+    /// # Arguments
+    /// * `report_format` - Markdown, plain text or HTML
+    /// * `report_writer` - Where to write the report
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If building or writing the report fails
+    pub fn write_report<W: Write>(&self, report_format: AnalysisReportFormat, report_writer: &mut W) -> RowsAndColumnsResult<()> {
+        let report_text = self.build_report(report_format)?;
+        report_writer
+            .write_all(report_text.as_bytes())
+            .map_err(|io_error| create_file_system_error("Failed to write analysis report", io_error))
+    }
+
+    /// Exports the analysis report to a file (parent directories are created)
+    ///
+    /// # Arguments
+    /// * `report_format` - Markdown, plain text or HTML
+    /// * `report_file_path` - Destination file
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If building or writing the report fails
+    pub fn export_report_file(&self, report_format: AnalysisReportFormat, report_file_path: &Path) -> RowsAndColumnsResult<()> {
+        run_with_dataset_settings(&self.application_configuration, || {
+            write_analysis_report_file(
+                &self.csv_analysis_results,
+                &self.enhanced_analysis_results,
+                report_format,
+                report_file_path,
+            )
+        })
+    }
+
+    /// Imports the rows into a new dataset in the directory store
+    ///
+    /// Derived columns saved in the metadata file become real columns;
+    /// definitions that no longer compile are skipped.
+    ///
+    /// # Arguments
+    /// * `csv_imports_directory` - The `csv_imports/` directory to create the dataset in
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<DirectoryStoreDataset>` - The stored dataset
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the dataset cannot be written
+    /// * `RowsAndColumnsError::MetadataError` - If the saved definitions cannot be read
+    pub fn import_into_directory_store(&self, csv_imports_directory: &Path) -> RowsAndColumnsResult<DirectoryStoreDataset> {
+        run_with_dataset_settings(&self.application_configuration, || {
+            std::fs::create_dir_all(csv_imports_directory).map_err(|io_error| {
+                create_file_system_error(
+                    &format!("Failed to create imports directory: {}", csv_imports_directory.display()),
+                    io_error
+                )
+            })?;
+            import_csv_with_derived_columns(&self.csv_analysis_results, csv_imports_directory)
+        })
+    }
+
+    /// Finds a column's statistics by name
+    ///
+    /// # Arguments
+    /// * `column_name` - Exact column name
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<&EnhancedCsvColumnInformation>` - The column
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If there is no such column
+    fn find_column(&self, column_name: &str) -> RowsAndColumnsResult<&EnhancedCsvColumnInformation> {
+        self.enhanced_analysis_results
+            .iter()
+            .find(|column_info| column_info.basic_info.column_name == column_name)
+            .ok_or_else(|| {
+                create_csv_processing_error(
+                    &format!("No column named '{}' (columns are: {})", column_name, self.column_names().join(", ")),
+                    None,
+                    Some(column_name.to_string())
+                )
+            })
+    }
+
+    /// Finds a numeric column's statistics by name
+    ///
+    /// # Arguments
+    /// * `column_name` - Exact column name
+    /// * `chart_operation_description` - What needs the column, for the error
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<(&EnhancedCsvColumnInformation, &NumericalColumnStatistics)>` - The column
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If there is no such column
+    /// * `RowsAndColumnsError::DataTypeValidationError` - If the column is not numeric
+    fn find_numerical_column(
+        &self,
+        column_name: &str,
+        chart_operation_description: &str,
+    ) -> RowsAndColumnsResult<(&EnhancedCsvColumnInformation, &NumericalColumnStatistics)> {
+        let column_info = self.find_column(column_name)?;
+        let numerical_statistics = column_info.numerical_statistics
+            .as_ref()
+            .ok_or_else(|| create_data_type_validation_error(chart_operation_description, column_name, "numeric column"))?;
+        Ok((column_info, numerical_statistics))
+    }
+}
+
+/// Resolves an existing regular file to an absolute path
+///
+/// # Arguments
+/// * `csv_file_path` - Path as given by the caller
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - The canonical path
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file does not exist or is not a file
+fn canonicalize_existing_csv_file_path(csv_file_path: &Path) -> RowsAndColumnsResult<PathBuf> {
+    let canonical_file_path = csv_file_path.canonicalize().map_err(|io_error| {
+        create_file_system_error(&format!("CSV file not found: {}", csv_file_path.display()), io_error)
+    })?;
+    if !canonical_file_path.is_file() {
+        return Err(create_file_system_error(
+            &format!("CSV path is not a file: {}", csv_file_path.display()),
+            io::Error::from(io::ErrorKind::InvalidInput)
+        ));
+    }
+    Ok(canonical_file_path)
+}
+
+/// Runs library work with console output silenced and a dataset's settings in effect
+///
+/// # Arguments
+/// * `application_configuration` - The settings the dataset was opened with
+/// * `library_operation` - The work to run
+///
+/// # Returns
+/// * `T` - Whatever the closure returns
+fn run_with_dataset_settings<T>(
+    application_configuration: &Arc<ApplicationConfiguration>,
+    library_operation: impl FnOnce() -> T,
+) -> T {
+    run_with_application_configuration(application_configuration, || run_with_silent_console_output(library_operation))
+}
+
+/// Renders a chart as text, ready to place on a terminal screen
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `available_column_count` - Width in characters
+/// * `available_row_count` - Height in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+///
+/// # Returns
+/// * `String` - The chart lines, each ending in a newline
+pub fn render_chart_as_text(
+    chart_definition: &ChartDefinition,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+) -> String {
+    render_chart_as_text_lines(chart_definition, available_column_count, available_row_count, glyph_mode)
+        .into_iter()
+        .map(|chart_line| chart_line + "\n")
+        .collect()
+}
+
+/// Writes a chart as text to any writer
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `available_column_count` - Width in characters
+/// * `available_row_count` - Height in lines
+/// * `glyph_mode` - Unicode or ASCII character set
+/// * `chart_writer` - Where to write the chart
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If writing fails
+pub fn write_chart_as_text<W: Write>(
+    chart_definition: &ChartDefinition,
+    available_column_count: usize,
+    available_row_count: usize,
+    glyph_mode: ChartGlyphMode,
+    chart_writer: &mut W,
+) -> RowsAndColumnsResult<()> {
+    let chart_text = render_chart_as_text(chart_definition, available_column_count, available_row_count, glyph_mode);
+    chart_writer
+        .write_all(chart_text.as_bytes())
+        .map_err(|io_error| create_file_system_error("Failed to write chart text", io_error))
+}
+
+/// Writes a chart as an SVG document to any writer
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `chart_width` - Document width in pixels
+/// * `chart_height` - Document height in pixels
+/// * `chart_writer` - Where to write the SVG
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If writing fails
+pub fn write_chart_as_svg<W: Write>(
+    chart_definition: &ChartDefinition,
+    chart_width: usize,
+    chart_height: usize,
+    chart_writer: &mut W,
+) -> RowsAndColumnsResult<()> {
+    chart_writer
+        .write_all(render_chart_as_svg(chart_definition, chart_width, chart_height).as_bytes())
+        .map_err(|io_error| create_file_system_error("Failed to write SVG chart", io_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart_rendering_module::ChartDefinition;
+    use crate::error_types_module::RowsAndColumnsError;
+    use super::super::application_configuration_module::application_configuration;
+    use super::super::test_fixture_module::build_stored_gzip_member;
+
+    /// Writes a small CSV to its own per-process temp directory and opens it
+    fn open_test_dataset(file_name: &str) -> CsvDataset {
+        let test_directory = std::env::temp_dir().join(format!("{}_{}", file_name.trim_end_matches(".csv"), std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join(file_name);
+        std::fs::write(&csv_file_path, "name,breed,age,weight\nTom,tabby,3,4.5\nMia,siamese,5,3.9\nLeo,tabby,2,5.1\nZoe,persian,7,4.2\n")
+            .expect("test CSV should be writable");
+        CsvDataset::open(&csv_file_path).expect("test CSV should open")
+    }
+
+    /// Test opening returns the analysis and describes it without printing
+    #[test]
+    fn test_open_and_describe_dataset() {
+        let test_dataset = open_test_dataset("embedding_api_describe_test.csv");
+        assert_eq!(test_dataset.column_names(), vec!["name", "breed", "age", "weight"]);
+        assert_eq!(test_dataset.analysis_results().total_data_row_count, 4);
+        assert_eq!(test_dataset.column_statistics().len(), 4);
+
+        let description_text = test_dataset.describe_as_text();
+        assert!(description_text.contains("3. age (integer - continuous)"));
+        assert!(test_dataset.describe_as_json().contains("\"columns\""));
+
+        assert!(matches!(
+            CsvDataset::open(Path::new("/nonexistent/embedding_api_missing.csv")),
+            Err(RowsAndColumnsError::FileSystemError { .. })
+        ));
+
+        let _ = std::fs::remove_dir_all(test_dataset.analysis_results().csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test charts are built by column name and rendered to a String or writer
+    #[test]
+    fn test_build_and_render_charts() {
+        let test_dataset = open_test_dataset("embedding_api_chart_test.csv");
+
+        let age_histogram = test_dataset.build_histogram_chart("age").expect("age is numeric");
+        assert!(matches!(age_histogram, ChartDefinition::Histogram(_)));
+        let chart_text = render_chart_as_text(&age_histogram, 40, 10, ChartGlyphMode::Ascii);
+        assert!(!chart_text.is_empty());
+        assert!(chart_text.is_ascii());
+
+        let mut svg_buffer: Vec<u8> = Vec::new();
+        write_chart_as_svg(&age_histogram, 400, 300, &mut svg_buffer).expect("writing to a Vec cannot fail");
+        assert!(String::from_utf8_lossy(&svg_buffer).starts_with("<svg"));
+
+        assert!(matches!(test_dataset.build_bar_chart("breed"), Ok(ChartDefinition::BarChart(_))));
+        assert!(matches!(test_dataset.build_scatter_plot_chart("age", "weight"), Ok(ChartDefinition::ScatterPlot(_))));
+        assert!(matches!(test_dataset.build_box_plot_chart("breed"), Err(RowsAndColumnsError::DataTypeValidationError { .. })));
+        assert!(matches!(test_dataset.build_histogram_chart("colour"), Err(RowsAndColumnsError::CsvProcessingError { .. })));
+
+        let _ = std::fs::remove_dir_all(test_dataset.analysis_results().csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test reports go to a writer and imports land in the given directory
+    #[test]
+    fn test_write_report_and_import() {
+        let test_dataset = open_test_dataset("embedding_api_import_test.csv");
+
+        let mut report_buffer: Vec<u8> = Vec::new();
+        test_dataset.write_report(AnalysisReportFormat::Markdown, &mut report_buffer).expect("report should build");
+        assert!(String::from_utf8_lossy(&report_buffer).contains("weight"));
+
+        let csv_imports_directory = std::env::temp_dir().join(format!("embedding_api_imports_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&csv_imports_directory);
+        let store_dataset = test_dataset.import_into_directory_store(&csv_imports_directory).expect("import should work");
+        assert_eq!(store_dataset.total_row_count, 4);
+        assert!(store_dataset.dataset_directory_path.starts_with(&csv_imports_directory));
+        let _ = std::fs::remove_dir_all(&csv_imports_directory);
+
+        let _ = std::fs::remove_dir_all(test_dataset.analysis_results().csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test compressed input spools into the given work directory and the
+    /// given settings apply to this dataset, not to the rest of the process
+    #[test]
+    fn test_open_with_options_uses_work_directory_and_settings() {
+        let test_directory = std::env::temp_dir().join(format!("embedding_api_options_test_{}", std::process::id()));
+        let temporary_work_directory = test_directory.join("work");
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let compressed_csv_path = test_directory.join("cats.csv.gz");
+        std::fs::write(
+            &compressed_csv_path,
+            build_stored_gzip_member(b"breed,age\ntabby,3\nsiamese,5\npersian,2\ntabby,7\n"),
+        ).expect("test gzip should be writable");
+
+        let mut open_options = CsvDatasetOpenOptions {
+            temporary_work_directory: temporary_work_directory.clone(),
+            ..CsvDatasetOpenOptions::default()
+        };
+        open_options.application_configuration.top_value_display_count = 1;
+        let test_dataset = CsvDataset::open_with_options(&compressed_csv_path, &open_options).expect("gzip should open");
+
+        assert!(test_dataset.csv_file_path().starts_with(&temporary_work_directory));
+        assert_eq!(test_dataset.analysis_results().total_data_row_count, 4);
+        let description_text = test_dataset.describe_as_text();
+        assert!(description_text.contains("tabby: 50.0%"));
+        assert!(!description_text.contains("siamese: 25.0%"));
+        assert_eq!(application_configuration().top_value_display_count, 5);
+
+        drop(test_dataset);
+        assert_eq!(std::fs::read_dir(&temporary_work_directory).expect("work dir should exist").count(), 0);
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test datasets can be opened from several threads at once
+    #[test]
+    fn test_open_datasets_from_several_threads() {
+        let opening_threads: Vec<_> = (0..4)
+            .map(|thread_number| {
+                std::thread::spawn(move || {
+                    let test_dataset = open_test_dataset(&format!("embedding_api_thread_{}_test.csv", thread_number));
+                    let _ = std::fs::remove_dir_all(test_dataset.analysis_results().csv_file_path.parent().expect("test file has a directory"));
+                    (test_dataset.analysis_results().total_data_row_count, test_dataset.column_names().len())
+                })
+            })
+            .collect();
+
+        for opening_thread in opening_threads {
+            assert_eq!(opening_thread.join().expect("opening thread should not panic"), (4, 4));
+        }
+    }
+}
@@ -71,6 +71,87 @@ pub enum RowsAndColumnsError {
         /// Description of the configuration problem
         configuration_issue_description: String,
     },
+
+    /// Row filter expression could not be parsed or type-checked
+    FilterExpressionError {
+        /// The full filter expression as the user typed it
+        filter_expression_text: String,
+        /// Zero-based character position of the problem within the expression
+        error_character_position: usize,
+        /// Description of what is wrong at that position
+        filter_error_description: String,
+    },
+
+    /// Derived column expression could not be parsed or type-checked
+    DerivedColumnExpressionError {
+        /// The full derived column expression as the user typed it
+        derived_expression_text: String,
+        /// Zero-based character position of the problem within the expression
+        error_character_position: usize,
+        /// Description of what is wrong at that position
+        derived_error_description: String,
+    },
+
+    /// Command line arguments could not be understood
+    CommandLineUsageError {
+        /// Description of what is wrong with the arguments
+        usage_error_description: String,
+        /// The subcommand being parsed, if one was recognized
+        subcommand_name: Option<String>,
+    },
+}
+
+/// Process exit codes, one per error variant
+///
+/// Scripts and CI jobs can tell failures apart without parsing messages.
+/// 1 is left for failures outside the application (e.g. a panic), and 2 is
+/// the conventional code for command line mistakes.
+pub const EXIT_CODE_SUCCESS: i32 = 0;
+pub const EXIT_CODE_COMMAND_LINE_USAGE_ERROR: i32 = 2;
+pub const EXIT_CODE_FILE_SYSTEM_ERROR: i32 = 3;
+pub const EXIT_CODE_CSV_PROCESSING_ERROR: i32 = 4;
+pub const EXIT_CODE_METADATA_ERROR: i32 = 5;
+pub const EXIT_CODE_DATA_TYPE_VALIDATION_ERROR: i32 = 6;
+pub const EXIT_CODE_STATISTICAL_ANALYSIS_ERROR: i32 = 7;
+pub const EXIT_CODE_TUI_RENDERING_ERROR: i32 = 8;
+pub const EXIT_CODE_CONFIGURATION_ERROR: i32 = 9;
+pub const EXIT_CODE_FILTER_EXPRESSION_ERROR: i32 = 10;
+pub const EXIT_CODE_DERIVED_COLUMN_EXPRESSION_ERROR: i32 = 11;
+
+/// Exit codes with a short description, in code order (for help text)
+pub const EXIT_CODE_DESCRIPTIONS: [(i32, &str); 11] = [
+    (EXIT_CODE_SUCCESS, "success"),
+    (EXIT_CODE_COMMAND_LINE_USAGE_ERROR, "command line usage error"),
+    (EXIT_CODE_FILE_SYSTEM_ERROR, "file system error (missing file, permissions, disk)"),
+    (EXIT_CODE_CSV_PROCESSING_ERROR, "CSV processing error"),
+    (EXIT_CODE_METADATA_ERROR, "metadata file error"),
+    (EXIT_CODE_DATA_TYPE_VALIDATION_ERROR, "data type validation error (e.g. validate found invalid values)"),
+    (EXIT_CODE_STATISTICAL_ANALYSIS_ERROR, "statistical analysis error"),
+    (EXIT_CODE_TUI_RENDERING_ERROR, "terminal dashboard error"),
+    (EXIT_CODE_CONFIGURATION_ERROR, "configuration error"),
+    (EXIT_CODE_FILTER_EXPRESSION_ERROR, "filter expression error"),
+    (EXIT_CODE_DERIVED_COLUMN_EXPRESSION_ERROR, "derived column expression error"),
+];
+
+impl RowsAndColumnsError {
+    /// Returns the process exit code for this error
+    ///
+    /// # Returns
+    /// * `i32` - The variant's `EXIT_CODE_*` constant (never 0)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RowsAndColumnsError::FileSystemError { .. } => EXIT_CODE_FILE_SYSTEM_ERROR,
+            RowsAndColumnsError::CsvProcessingError { .. } => EXIT_CODE_CSV_PROCESSING_ERROR,
+            RowsAndColumnsError::MetadataError { .. } => EXIT_CODE_METADATA_ERROR,
+            RowsAndColumnsError::StatisticalAnalysisError { .. } => EXIT_CODE_STATISTICAL_ANALYSIS_ERROR,
+            RowsAndColumnsError::TuiRenderingError { .. } => EXIT_CODE_TUI_RENDERING_ERROR,
+            RowsAndColumnsError::DataTypeValidationError { .. } => EXIT_CODE_DATA_TYPE_VALIDATION_ERROR,
+            RowsAndColumnsError::ConfigurationError { .. } => EXIT_CODE_CONFIGURATION_ERROR,
+            RowsAndColumnsError::FilterExpressionError { .. } => EXIT_CODE_FILTER_EXPRESSION_ERROR,
+            RowsAndColumnsError::DerivedColumnExpressionError { .. } => EXIT_CODE_DERIVED_COLUMN_EXPRESSION_ERROR,
+            RowsAndColumnsError::CommandLineUsageError { .. } => EXIT_CODE_COMMAND_LINE_USAGE_ERROR,
+        }
+    }
 }
 
 impl fmt::Display for RowsAndColumnsError {
@@ -169,6 +250,55 @@ impl fmt::Display for RowsAndColumnsError {
                     configuration_issue_description
                 )
             }
+            
+            RowsAndColumnsError::FilterExpressionError { 
+                filter_expression_text, 
+                error_character_position, 
+                filter_error_description 
+            } => {
+                // Echo the expression with a caret under the offending character
+                write!(
+                    formatter,
+                    "Filter expression error: {} at position {}\n  {}\n  {}^",
+                    filter_error_description,
+                    error_character_position + 1,
+                    filter_expression_text,
+                    " ".repeat(*error_character_position)
+                )
+            }
+            
+            RowsAndColumnsError::DerivedColumnExpressionError { 
+                derived_expression_text, 
+                error_character_position, 
+                derived_error_description 
+            } => {
+                // Echo the expression with a caret under the offending character
+                write!(
+                    formatter,
+                    "Derived column error: {} at position {}\n  {}\n  {}^",
+                    derived_error_description,
+                    error_character_position + 1,
+                    derived_expression_text,
+                    " ".repeat(*error_character_position)
+                )
+            }
+            
+            RowsAndColumnsError::CommandLineUsageError { 
+                usage_error_description, 
+                subcommand_name 
+            } => {
+                // Point at the help text that explains the correct usage
+                let help_command = match subcommand_name {
+                    Some(subcommand_name) => format!("rows_and_columns {} --help", subcommand_name),
+                    None => "rows_and_columns --help".to_string(),
+                };
+                write!(
+                    formatter,
+                    "Usage error: {}\n  Run '{}' for usage.",
+                    usage_error_description,
+                    help_command
+                )
+            }
         }
     }
 }
@@ -324,6 +454,66 @@ pub fn create_configuration_error(configuration_issue_description: &str) -> Rows
     }
 }
 
+/// Helper function to create filter expression errors with a caret position
+/// 
+/// # Arguments
+/// * `filter_expression_text` - The full filter expression
+/// * `error_character_position` - Zero-based character position of the problem
+/// * `filter_error_description` - Description of what is wrong
+/// 
+/// # Returns
+/// * `RowsAndColumnsError` - A properly contextualized filter expression error
+pub fn create_filter_expression_error(
+    filter_expression_text: &str,
+    error_character_position: usize,
+    filter_error_description: &str
+) -> RowsAndColumnsError {
+    RowsAndColumnsError::FilterExpressionError {
+        filter_expression_text: filter_expression_text.to_string(),
+        error_character_position,
+        filter_error_description: filter_error_description.to_string(),
+    }
+}
+
+/// Helper function to create derived column expression errors with a caret position
+/// 
+/// # Arguments
+/// * `derived_expression_text` - The full derived column expression
+/// * `error_character_position` - Zero-based character position of the problem
+/// * `derived_error_description` - Description of what is wrong
+/// 
+/// # Returns
+/// * `RowsAndColumnsError` - A properly contextualized derived column error
+pub fn create_derived_column_expression_error(
+    derived_expression_text: &str,
+    error_character_position: usize,
+    derived_error_description: &str
+) -> RowsAndColumnsError {
+    RowsAndColumnsError::DerivedColumnExpressionError {
+        derived_expression_text: derived_expression_text.to_string(),
+        error_character_position,
+        derived_error_description: derived_error_description.to_string(),
+    }
+}
+
+/// Helper function to create command line usage errors
+/// 
+/// # Arguments
+/// * `usage_error_description` - Description of what is wrong with the arguments
+/// * `subcommand_name` - The subcommand being parsed, if one was recognized
+/// 
+/// # Returns
+/// * `RowsAndColumnsError` - A properly contextualized usage error
+pub fn create_command_line_usage_error(
+    usage_error_description: &str,
+    subcommand_name: Option<&str>
+) -> RowsAndColumnsError {
+    RowsAndColumnsError::CommandLineUsageError {
+        usage_error_description: usage_error_description.to_string(),
+        subcommand_name: subcommand_name.map(|subcommand_name| subcommand_name.to_string()),
+    }
+}
+
 /// Type alias for Results that use RowsAndColumnsError
 /// 
 /// This makes function signatures more readable throughout the codebase.
@@ -393,4 +583,30 @@ mod tests {
             _ => panic!("Expected ConfigurationError"),
         }
     }
+
+    /// Test every variant has its own documented, non-zero exit code
+    #[test]
+    fn test_exit_codes_are_distinct_and_documented() {
+        let one_error_per_variant = [
+            create_file_system_error("Reading", io::Error::new(io::ErrorKind::NotFound, "missing")),
+            create_csv_processing_error("Parsing", None, None),
+            create_metadata_error("Reading metadata", "cats.csv_metadata.toml"),
+            create_statistical_analysis_error("Computing mean", "age"),
+            create_tui_rendering_error("Drawing"),
+            create_data_type_validation_error("Checking", "three", "integer"),
+            create_configuration_error("Reading settings"),
+            create_filter_expression_error("age >", 5, "expected a number"),
+            create_derived_column_expression_error("age *", 5, "expected a number"),
+            create_command_line_usage_error("unknown subcommand", None),
+        ];
+
+        let mut seen_exit_codes = Vec::new();
+        for application_error in &one_error_per_variant {
+            let exit_code = application_error.exit_code();
+            assert_ne!(exit_code, EXIT_CODE_SUCCESS);
+            assert!(!seen_exit_codes.contains(&exit_code), "exit code {} is used twice", exit_code);
+            assert!(EXIT_CODE_DESCRIPTIONS.iter().any(|(described_code, _)| *described_code == exit_code));
+            seen_exit_codes.push(exit_code);
+        }
+    }
 }
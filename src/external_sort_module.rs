@@ -0,0 +1,776 @@
+// src/external_sort_module.rs
+
+/// External merge sort for rows_and_columns ("sort by age descending, then name")
+///
+/// Rows are sorted by one or more columns and written as a new CSV file with
+/// its own `.csv_metadata.toml`. Files larger than memory are handled by
+/// sorting fixed-size runs, spilling them to temporary files, and merging the
+/// runs back together.
+///
+/// # Core Responsibilities
+/// - Parse sort key lists such as `3 desc, 2 asc nulls first`
+/// - Stream rows from the CSV file or the directory store into bounded runs
+/// - Spill sorted runs under `rows_columns_data/temporary_work/` and k-way merge them
+/// - Write the sorted rows and the metadata, then remove the spill files
+///
+/// # Design Philosophy
+/// - Fixed memory budget: a run is spilled as soon as its estimated size
+///   passes `memory_budget_bytes`; merging holds one row per open run
+/// - Type-aware: integer and float columns order numerically, boolean columns
+///   false before true, text columns by character code; empty cells and cells
+///   that cannot be read as the column type are nulls, placed first or last
+/// - Stable: rows with equal keys keep their input order
+/// - Small inputs never touch the disk: a single run is written straight out
+/// - At most `MAXIMUM_MERGE_FAN_IN` runs are open at once; more runs are
+///   merged in several passes so file handle limits are respected
+use std::cmp::Ordering;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import CSV structures, the row stream control and the export writer
+use super::csv_processor_module::{
+    CsvColumnDataType,
+    CsvRowStreamControl,
+    StreamingCsvExportWriter,
+};
+
+// Import the shared row source so sorted rows can come from an imported dataset
+use super::directory_store_module::DatasetRowSource;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_csv_processing_error,
+    create_file_system_error,
+};
+
+/// Default memory budget for the rows held in one run
+pub const DEFAULT_SORT_MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// Largest number of runs merged at once
+const MAXIMUM_MERGE_FAN_IN: usize = 32;
+
+/// Estimated bookkeeping bytes per stored string (pointer, length, capacity)
+const ESTIMATED_STRING_OVERHEAD_BYTES: usize = 24;
+
+/// Estimated bookkeeping bytes per row (vectors, key values, row number)
+const ESTIMATED_ROW_OVERHEAD_BYTES: usize = 64;
+
+/// Separates fields inside a spill file line (ASCII unit separator)
+const SPILL_FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Sort direction for one key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Where empty or unreadable cells go
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullPlacement {
+    NullsFirst,
+    NullsLast,
+}
+
+/// One column to sort by
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKeySpecification {
+    /// 0-based column position
+    pub column_position: usize,
+
+    /// Ascending or descending
+    pub sort_direction: SortDirection,
+
+    /// Whether nulls come before or after all values (independent of direction)
+    pub null_placement: NullPlacement,
+
+    /// Type the cells are read as; `None` uses the column's detected type
+    /// (a join reads an Integer key paired with a Float key as Float)
+    pub key_data_type: Option<CsvColumnDataType>,
+}
+
+/// What to sort by and how much memory a run may use
+#[derive(Debug, Clone)]
+pub struct ExternalSortRequest {
+    /// Sort keys, most significant first
+    pub sort_keys: Vec<SortKeySpecification>,
+
+    /// Estimated bytes of row data held before a run is spilled
+    pub memory_budget_bytes: usize,
+}
+
+/// Outcome of an external sort
+#[derive(Debug, Clone)]
+pub struct ExternalSortSummary {
+    /// Path of the sorted CSV file
+    pub sorted_csv_file_path: PathBuf,
+
+    /// Path of its metadata file
+    pub metadata_file_path: PathBuf,
+
+    /// Number of rows written
+    pub sorted_row_count: usize,
+
+    /// Number of sorted runs spilled to disk (0 when the input fit in memory)
+    pub spilled_run_count: usize,
+
+    /// Number of merge passes over the spilled runs
+    pub merge_pass_count: usize,
+}
+
+/// A typed key value read from a cell, shared by the sort and the join so
+/// both order keys the same way
+#[derive(Debug, Clone)]
+pub enum TypedKeyValue {
+    /// Integer columns keep exact values; f64 cannot tell apart IDs above 2^53
+    Integer(i64),
+    /// Never NaN, and -0.0 is read as 0.0, so equal numbers have equal bits
+    Number(f64),
+    Boolean(bool),
+    Text(String),
+}
+
+impl PartialEq for TypedKeyValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypedKeyValue::Integer(left_integer), TypedKeyValue::Integer(right_integer)) => left_integer == right_integer,
+            (TypedKeyValue::Number(left_number), TypedKeyValue::Number(right_number)) => left_number.to_bits() == right_number.to_bits(),
+            (TypedKeyValue::Boolean(left_flag), TypedKeyValue::Boolean(right_flag)) => left_flag == right_flag,
+            (TypedKeyValue::Text(left_text), TypedKeyValue::Text(right_text)) => left_text == right_text,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TypedKeyValue {}
+
+impl Hash for TypedKeyValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            TypedKeyValue::Integer(integer_value) => integer_value.hash(state),
+            TypedKeyValue::Number(number_value) => number_value.to_bits().hash(state),
+            TypedKeyValue::Boolean(flag_value) => flag_value.hash(state),
+            TypedKeyValue::Text(text_value) => text_value.hash(state),
+        }
+    }
+}
+
+/// A row with its precomputed sort key values
+#[derive(Debug, Clone)]
+struct SortableRow {
+    /// One value per sort key; `None` is a null
+    sort_key_values: Vec<Option<TypedKeyValue>>,
+    /// The row's field values, written out unchanged
+    field_values: Vec<String>,
+}
+
+/// Removes a sort's spill directory when the sort finishes or fails
+struct SpillDirectoryGuard {
+    spill_directory_path: PathBuf,
+}
+
+impl Drop for SpillDirectoryGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.spill_directory_path);
+    }
+}
+
+/// Parses a sort key list such as `3 desc, 2 asc nulls first`
+///
+/// Each entry is a 1-based column number followed by optional `asc`/`desc`
+/// and `nulls first`/`nulls last`. Defaults are ascending with nulls last.
+///
+/// # Arguments
+/// * `sort_key_text` - The comma-separated key list
+/// * `total_column_count` - Number of columns available
+///
+/// # Returns
+/// * `Result<Vec<SortKeySpecification>, String>` - The keys, or a message
+///   describing the first invalid entry
+pub fn parse_sort_key_list(sort_key_text: &str, total_column_count: usize) -> Result<Vec<SortKeySpecification>, String> {
+    let mut sort_keys: Vec<SortKeySpecification> = Vec::new();
+
+    for key_entry in sort_key_text.split(',').map(str::trim).filter(|key_entry| !key_entry.is_empty()) {
+        let entry_words: Vec<String> = key_entry.split_whitespace().map(str::to_lowercase).collect();
+
+        let column_position = entry_words[0].parse::<usize>().ok()
+            .filter(|&column_number| column_number >= 1 && column_number <= total_column_count)
+            .map(|column_number| column_number - 1)
+            .ok_or_else(|| format!("'{}' is not a column number between 1 and {}.", entry_words[0], total_column_count))?;
+
+        if sort_keys.iter().any(|sort_key| sort_key.column_position == column_position) {
+            return Err(format!("Column {} is listed more than once.", column_position + 1));
+        }
+
+        let mut sort_direction = SortDirection::Ascending;
+        let mut null_placement = NullPlacement::NullsLast;
+        let mut remaining_words = entry_words[1..].iter().map(String::as_str);
+
+        while let Some(option_word) = remaining_words.next() {
+            match option_word {
+                "asc" | "ascending" => sort_direction = SortDirection::Ascending,
+                "desc" | "descending" => sort_direction = SortDirection::Descending,
+                "nulls" => match remaining_words.next() {
+                    Some("first") => null_placement = NullPlacement::NullsFirst,
+                    Some("last") => null_placement = NullPlacement::NullsLast,
+                    _ => return Err(format!("In '{}': 'nulls' must be followed by 'first' or 'last'.", key_entry)),
+                },
+                _ => {
+                    return Err(format!(
+                        "In '{}': unknown option '{}' (expected asc, desc, nulls first or nulls last).",
+                        key_entry, option_word
+                    ));
+                }
+            }
+        }
+
+        sort_keys.push(SortKeySpecification { column_position, sort_direction, null_placement, key_data_type: None });
+    }
+
+    Ok(sort_keys)
+}
+
+/// Sorts all rows of a source and writes them as a new CSV file with metadata
+///
+/// # Arguments
+/// * `sort_row_source` - The CSV file or directory store dataset to read
+/// * `sort_request` - Sort keys and memory budget
+/// * `temporary_work_directory` - Parent directory for this sort's spill files
+/// * `output_csv_path` - Where to write the sorted CSV file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<ExternalSortSummary>` - Output paths and run counts
+///
+/// # Errors
+/// * `CsvProcessingError` - No sort keys, or a key outside the column range
+/// * `FileSystemError` - Spill or output files could not be written or read
+pub fn perform_external_sort(
+    sort_row_source: &DatasetRowSource,
+    sort_request: &ExternalSortRequest,
+    temporary_work_directory: &Path,
+    output_csv_path: &Path,
+) -> RowsAndColumnsResult<ExternalSortSummary> {
+    let column_names = sort_row_source.column_names();
+    let column_data_types = sort_row_source.column_data_types();
+
+    // Step 1: Validate the keys
+    if sort_request.sort_keys.is_empty() {
+        return Err(create_csv_processing_error("At least one sort column is required", None, None));
+    }
+    if let Some(invalid_key) = sort_request.sort_keys.iter().find(|sort_key| sort_key.column_position >= column_names.len()) {
+        return Err(create_csv_processing_error(
+            &format!("Sort column {} is outside the {} available columns", invalid_key.column_position + 1, column_names.len()),
+            None,
+            None
+        ));
+    }
+
+    // Step 2: Stream rows into runs, spilling each full run to disk
+    let spill_directory_guard = SpillDirectoryGuard {
+        spill_directory_path: temporary_work_directory.join(format!(
+            "external_sort_{}_{}",
+            std::process::id(),
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or_default()
+        )),
+    };
+    let mut spilled_run_paths: Vec<PathBuf> = Vec::new();
+    let mut current_run_rows: Vec<SortableRow> = Vec::new();
+    let mut current_run_estimated_bytes = 0;
+
+    sort_row_source.stream_rows(|_row_index, field_values| {
+        let sortable_row = build_sortable_row(field_values.to_vec(), &sort_request.sort_keys, &column_data_types);
+        current_run_estimated_bytes += estimate_sortable_row_bytes(&sortable_row);
+        current_run_rows.push(sortable_row);
+
+        if current_run_estimated_bytes >= sort_request.memory_budget_bytes {
+            let run_file_path = spill_directory_guard.spill_directory_path.join(format!("run_{:06}.tmp", spilled_run_paths.len() + 1));
+            sort_rows_in_memory(&mut current_run_rows, &sort_request.sort_keys);
+            write_spill_run_file(&current_run_rows, &run_file_path)?;
+            spilled_run_paths.push(run_file_path);
+            current_run_rows.clear();
+            current_run_estimated_bytes = 0;
+        }
+
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    // Step 3: Open the output and write the header
+    let mut sorted_output_writer = StreamingCsvExportWriter::create(output_csv_path, &column_names, &column_data_types)?;
+
+    // Step 4: Write the rows, straight from memory or by merging the runs
+    let spilled_run_count = spilled_run_paths.len();
+    let mut merge_pass_count = 0;
+
+    if spilled_run_paths.is_empty() {
+        sort_rows_in_memory(&mut current_run_rows, &sort_request.sort_keys);
+        for sortable_row in &current_run_rows {
+            sorted_output_writer.write_row(&sortable_row.field_values)?;
+        }
+    } else {
+        // The last, partly filled run is spilled too so every run merges alike
+        if !current_run_rows.is_empty() {
+            let run_file_path = spill_directory_guard.spill_directory_path.join(format!("run_{:06}.tmp", spilled_run_paths.len() + 1));
+            sort_rows_in_memory(&mut current_run_rows, &sort_request.sort_keys);
+            write_spill_run_file(&current_run_rows, &run_file_path)?;
+            spilled_run_paths.push(run_file_path);
+        }
+        drop(current_run_rows);
+
+        // Reduce the run count until one merge can produce the output
+        let mut intermediate_run_number = 0;
+        while spilled_run_paths.len() > MAXIMUM_MERGE_FAN_IN {
+            merge_pass_count += 1;
+            let mut merged_run_paths = Vec::new();
+            for run_path_group in spilled_run_paths.chunks(MAXIMUM_MERGE_FAN_IN) {
+                intermediate_run_number += 1;
+                let merged_run_path = spill_directory_guard.spill_directory_path
+                    .join(format!("merged_{:06}.tmp", intermediate_run_number));
+                let mut merged_run_writer = BufWriter::new(create_spill_file(&merged_run_path)?);
+                merge_spill_runs(run_path_group, &sort_request.sort_keys, &column_data_types, |sortable_row| {
+                    writeln!(merged_run_writer, "{}", encode_spill_line(&sortable_row.field_values))
+                        .map_err(|io_error| create_file_system_error("Failed to write merged sort run", io_error))
+                })?;
+                merged_run_writer.flush()
+                    .map_err(|io_error| create_file_system_error("Failed to write merged sort run", io_error))?;
+                for consumed_run_path in run_path_group {
+                    let _ = std::fs::remove_file(consumed_run_path);
+                }
+                merged_run_paths.push(merged_run_path);
+            }
+            spilled_run_paths = merged_run_paths;
+        }
+
+        merge_pass_count += 1;
+        merge_spill_runs(&spilled_run_paths, &sort_request.sort_keys, &column_data_types, |sortable_row| {
+            sorted_output_writer.write_row(&sortable_row.field_values)
+        })?;
+    }
+
+    // Step 5: Finish the file and describe it with metadata
+    let (sorted_row_count, metadata_file_path) = sorted_output_writer.finish()?;
+    drop(spill_directory_guard);
+
+    Ok(ExternalSortSummary {
+        sorted_csv_file_path: output_csv_path.to_path_buf(),
+        metadata_file_path,
+        sorted_row_count,
+        spilled_run_count,
+        merge_pass_count,
+    })
+}
+
+/// Reads a row's sort key values according to the column types
+///
+/// # Arguments
+/// * `field_values` - The row's trimmed field values
+/// * `sort_keys` - The sort keys
+/// * `column_data_types` - Column types in column order
+///
+/// # Returns
+/// * `SortableRow` - The row with its key values
+fn build_sortable_row(
+    field_values: Vec<String>,
+    sort_keys: &[SortKeySpecification],
+    column_data_types: &[CsvColumnDataType],
+) -> SortableRow {
+    let sort_key_values = sort_keys
+        .iter()
+        .map(|sort_key| {
+            let cell_text = field_values.get(sort_key.column_position).map(String::as_str).unwrap_or("");
+            let key_data_type = sort_key.key_data_type.as_ref().or(column_data_types.get(sort_key.column_position));
+            read_typed_key_value(cell_text, key_data_type)
+        })
+        .collect();
+
+    SortableRow { sort_key_values, field_values }
+}
+
+/// Reads a cell as a typed key value
+///
+/// # Arguments
+/// * `cell_text` - The trimmed cell
+/// * `key_data_type` - The type the cell is read as (`None` reads it as text)
+///
+/// # Returns
+/// * `Option<TypedKeyValue>` - The value, or `None` (a null) when the cell is
+///   empty or cannot be read as the type
+pub fn read_typed_key_value(cell_text: &str, key_data_type: Option<&CsvColumnDataType>) -> Option<TypedKeyValue> {
+    if cell_text.is_empty() {
+        return None;
+    }
+    match key_data_type {
+        Some(CsvColumnDataType::Integer) => cell_text.parse::<i64>().ok().map(TypedKeyValue::Integer),
+        Some(CsvColumnDataType::Float) => {
+            let number_value = cell_text.parse::<f64>().ok().filter(|number_value| !number_value.is_nan())?;
+            Some(TypedKeyValue::Number(if number_value == 0.0 { 0.0 } else { number_value }))
+        }
+        Some(CsvColumnDataType::Boolean) => match cell_text.to_lowercase().as_str() {
+            "true" | "yes" | "1" | "t" | "y" => Some(TypedKeyValue::Boolean(true)),
+            "false" | "no" | "0" | "f" | "n" => Some(TypedKeyValue::Boolean(false)),
+            _ => None,
+        },
+        _ => Some(TypedKeyValue::Text(cell_text.to_string())),
+    }
+}
+
+/// Orders two key values of the same type (values of different types are equal)
+///
+/// # Arguments
+/// * `left_value` - First value
+/// * `right_value` - Second value
+///
+/// # Returns
+/// * `Ordering` - Ascending order of `left_value` relative to `right_value`
+pub fn compare_typed_key_values(left_value: &TypedKeyValue, right_value: &TypedKeyValue) -> Ordering {
+    match (left_value, right_value) {
+        (TypedKeyValue::Integer(left_integer), TypedKeyValue::Integer(right_integer)) => left_integer.cmp(right_integer),
+        (TypedKeyValue::Number(left_number), TypedKeyValue::Number(right_number)) => left_number.total_cmp(right_number),
+        (TypedKeyValue::Boolean(left_flag), TypedKeyValue::Boolean(right_flag)) => left_flag.cmp(right_flag),
+        (TypedKeyValue::Text(left_text), TypedKeyValue::Text(right_text)) => left_text.cmp(right_text),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Estimates the memory held by a row
+fn estimate_sortable_row_bytes(sortable_row: &SortableRow) -> usize {
+    let field_bytes: usize = sortable_row.field_values
+        .iter()
+        .map(|field_value| field_value.len() + ESTIMATED_STRING_OVERHEAD_BYTES)
+        .sum();
+    let key_bytes: usize = sortable_row.sort_key_values
+        .iter()
+        .map(|key_value| match key_value {
+            Some(TypedKeyValue::Text(key_text)) => key_text.len() + ESTIMATED_STRING_OVERHEAD_BYTES,
+            _ => ESTIMATED_STRING_OVERHEAD_BYTES,
+        })
+        .sum();
+    field_bytes + key_bytes + ESTIMATED_ROW_OVERHEAD_BYTES
+}
+
+/// Compares two rows by the sort keys, most significant key first
+///
+/// # Arguments
+/// * `left_row` - First row
+/// * `right_row` - Second row
+/// * `sort_keys` - Directions and null placements, in key order
+///
+/// # Returns
+/// * `Ordering` - The order of `left_row` relative to `right_row`
+fn compare_sortable_rows(left_row: &SortableRow, right_row: &SortableRow, sort_keys: &[SortKeySpecification]) -> Ordering {
+    for (key_position, sort_key) in sort_keys.iter().enumerate() {
+        let left_value = &left_row.sort_key_values[key_position];
+        let right_value = &right_row.sort_key_values[key_position];
+
+        // Nulls are placed independently of the direction
+        let key_ordering = match (left_value, right_value) {
+            (None, None) => Ordering::Equal,
+            (None, _) => match sort_key.null_placement {
+                NullPlacement::NullsFirst => Ordering::Less,
+                NullPlacement::NullsLast => Ordering::Greater,
+            },
+            (_, None) => match sort_key.null_placement {
+                NullPlacement::NullsFirst => Ordering::Greater,
+                NullPlacement::NullsLast => Ordering::Less,
+            },
+            (Some(left_value), Some(right_value)) => {
+                let value_ordering = compare_typed_key_values(left_value, right_value);
+                match sort_key.sort_direction {
+                    SortDirection::Ascending => value_ordering,
+                    SortDirection::Descending => value_ordering.reverse(),
+                }
+            }
+        };
+
+        if key_ordering != Ordering::Equal {
+            return key_ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Sorts a run in memory (stable, so equal keys keep their input order)
+fn sort_rows_in_memory(run_rows: &mut [SortableRow], sort_keys: &[SortKeySpecification]) {
+    run_rows.sort_by(|left_row, right_row| compare_sortable_rows(left_row, right_row, sort_keys));
+}
+
+/// Encodes field values as one spill file line
+///
+/// Backslashes, line breaks and the field separator are escaped so any cell
+/// text round-trips.
+fn encode_spill_line(field_values: &[String]) -> String {
+    field_values
+        .iter()
+        .map(|field_value| {
+            let mut encoded_field = String::with_capacity(field_value.len());
+            for field_character in field_value.chars() {
+                match field_character {
+                    '\\' => encoded_field.push_str("\\\\"),
+                    '\n' => encoded_field.push_str("\\n"),
+                    '\r' => encoded_field.push_str("\\r"),
+                    SPILL_FIELD_SEPARATOR => encoded_field.push_str("\\u"),
+                    _ => encoded_field.push(field_character),
+                }
+            }
+            encoded_field
+        })
+        .collect::<Vec<_>>()
+        .join(&SPILL_FIELD_SEPARATOR.to_string())
+}
+
+/// Decodes a spill file line back into field values
+fn decode_spill_line(spill_line: &str) -> Vec<String> {
+    spill_line
+        .split(SPILL_FIELD_SEPARATOR)
+        .map(|encoded_field| {
+            let mut decoded_field = String::with_capacity(encoded_field.len());
+            let mut encoded_characters = encoded_field.chars();
+            while let Some(encoded_character) = encoded_characters.next() {
+                if encoded_character != '\\' {
+                    decoded_field.push(encoded_character);
+                    continue;
+                }
+                match encoded_characters.next() {
+                    Some('n') => decoded_field.push('\n'),
+                    Some('r') => decoded_field.push('\r'),
+                    Some('u') => decoded_field.push(SPILL_FIELD_SEPARATOR),
+                    Some(escaped_character) => decoded_field.push(escaped_character),
+                    None => decoded_field.push('\\'),
+                }
+            }
+            decoded_field
+        })
+        .collect()
+}
+
+/// Creates a spill file, creating the spill directory on first use
+fn create_spill_file(spill_file_path: &Path) -> RowsAndColumnsResult<File> {
+    if let Some(spill_directory) = spill_file_path.parent() {
+        std::fs::create_dir_all(spill_directory).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to create sort spill directory: {}", spill_directory.display()), io_error)
+        })?;
+    }
+    File::create(spill_file_path).map_err(|io_error| {
+        create_file_system_error(&format!("Failed to create sort run file: {}", spill_file_path.display()), io_error)
+    })
+}
+
+/// Writes a sorted run to a spill file
+fn write_spill_run_file(run_rows: &[SortableRow], run_file_path: &Path) -> RowsAndColumnsResult<()> {
+    let mut run_writer = BufWriter::new(create_spill_file(run_file_path)?);
+    let write_error = |io_error: std::io::Error| {
+        create_file_system_error(&format!("Failed to write sort run file: {}", run_file_path.display()), io_error)
+    };
+
+    for sortable_row in run_rows {
+        writeln!(run_writer, "{}", encode_spill_line(&sortable_row.field_values)).map_err(write_error)?;
+    }
+    run_writer.flush().map_err(write_error)
+}
+
+/// Merges sorted runs, handing each row to a consumer in sorted order
+///
+/// Each open run contributes its current row; the smallest is emitted and
+/// replaced by that run's next row. Ties go to the earlier run, which keeps
+/// the merge stable.
+///
+/// # Arguments
+/// * `run_file_paths` - The runs to merge, in input order
+/// * `sort_keys` - The sort keys
+/// * `column_data_types` - Column types for reading key values
+/// * `row_consumer` - Called with each row in sorted order
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or the first read or consumer error
+fn merge_spill_runs<F>(
+    run_file_paths: &[PathBuf],
+    sort_keys: &[SortKeySpecification],
+    column_data_types: &[CsvColumnDataType],
+    mut row_consumer: F,
+) -> RowsAndColumnsResult<()>
+where
+    F: FnMut(&SortableRow) -> RowsAndColumnsResult<()>,
+{
+    // Step 1: Open every run and read its first row
+    let mut run_line_readers = Vec::with_capacity(run_file_paths.len());
+    for run_file_path in run_file_paths {
+        let run_file = File::open(run_file_path).map_err(|io_error| {
+            create_file_system_error(&format!("Failed to open sort run file: {}", run_file_path.display()), io_error)
+        })?;
+        run_line_readers.push(BufReader::new(run_file).lines());
+    }
+
+    let mut read_next_row = |run_index: usize| -> RowsAndColumnsResult<Option<SortableRow>> {
+        match run_line_readers[run_index].next() {
+            None => Ok(None),
+            Some(line_result) => {
+                let spill_line = line_result.map_err(|io_error| {
+                    create_file_system_error(
+                        &format!("Failed to read sort run file: {}", run_file_paths[run_index].display()),
+                        io_error
+                    )
+                })?;
+                Ok(Some(build_sortable_row(decode_spill_line(&spill_line), sort_keys, column_data_types)))
+            }
+        }
+    };
+
+    let mut current_run_heads: Vec<Option<SortableRow>> = Vec::with_capacity(run_file_paths.len());
+    for run_index in 0..run_file_paths.len() {
+        current_run_heads.push(read_next_row(run_index)?);
+    }
+
+    // Step 2: Repeatedly emit the smallest head; the fan-in is small, so a
+    // linear scan over the heads is cheaper than maintaining a heap
+    loop {
+        let mut smallest_run_index: Option<usize> = None;
+        for (run_index, run_head) in current_run_heads.iter().enumerate() {
+            let Some(candidate_row) = run_head else { continue };
+            let is_smaller = match smallest_run_index.and_then(|smallest_index| current_run_heads[smallest_index].as_ref()) {
+                None => true,
+                Some(smallest_row) => compare_sortable_rows(candidate_row, smallest_row, sort_keys) == Ordering::Less,
+            };
+            if is_smaller {
+                smallest_run_index = Some(run_index);
+            }
+        }
+
+        let Some(smallest_run_index) = smallest_run_index else { break };
+        if let Some(smallest_row) = current_run_heads[smallest_run_index].take() {
+            row_consumer(&smallest_row)?;
+        }
+        current_run_heads[smallest_run_index] = read_next_row(smallest_run_index)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixture_module::create_test_analysis_results;
+
+    /// Key lists accept directions and null placement, and reject bad entries
+    #[test]
+    fn test_parse_sort_key_list() {
+        let sort_keys = parse_sort_key_list("3 desc, 1, 2 asc nulls first", 3).expect("list should parse");
+        assert_eq!(sort_keys, vec![
+            SortKeySpecification { column_position: 2, sort_direction: SortDirection::Descending, null_placement: NullPlacement::NullsLast, key_data_type: None },
+            SortKeySpecification { column_position: 0, sort_direction: SortDirection::Ascending, null_placement: NullPlacement::NullsLast, key_data_type: None },
+            SortKeySpecification { column_position: 1, sort_direction: SortDirection::Ascending, null_placement: NullPlacement::NullsFirst, key_data_type: None },
+        ]);
+
+        assert!(parse_sort_key_list("4", 3).is_err());
+        assert!(parse_sort_key_list("1 sideways", 3).is_err());
+        assert!(parse_sort_key_list("1, 1 desc", 3).is_err());
+        assert!(parse_sort_key_list("1 nulls", 3).is_err());
+    }
+
+    /// Numeric keys sort numerically, nulls follow their placement, ties stay stable
+    #[test]
+    fn test_in_memory_sort_is_typed_and_stable() {
+        let temporary_directory = std::env::temp_dir().join(format!("rows_and_columns_sort_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temporary_directory).expect("temp dir should be created");
+        let source_csv_path = temporary_directory.join("cats.csv");
+        std::fs::write(&source_csv_path, "age,name\n10,Tom\n9,Ann\n,Zed\n10,Bob\n2,Cy\n").expect("write source");
+        let analysis_results = create_test_analysis_results(
+            &source_csv_path,
+            &[("age", CsvColumnDataType::Integer), ("name", CsvColumnDataType::String)],
+        );
+
+        let output_csv_path = temporary_directory.join("cats_sorted.csv");
+        let sort_summary = perform_external_sort(
+            &DatasetRowSource::CsvFile(&analysis_results),
+            &ExternalSortRequest {
+                sort_keys: parse_sort_key_list("1 desc nulls first", 2).expect("keys"),
+                memory_budget_bytes: DEFAULT_SORT_MEMORY_BUDGET_BYTES,
+            },
+            &temporary_directory.join("work"),
+            &output_csv_path,
+        ).expect("sort should succeed");
+
+        assert_eq!(sort_summary.sorted_row_count, 5);
+        assert_eq!(sort_summary.spilled_run_count, 0);
+        assert_eq!(
+            std::fs::read_to_string(&output_csv_path).expect("read output"),
+            "age,name\n,Zed\n10,Tom\n10,Bob\n9,Ann\n2,Cy\n"
+        );
+        let metadata_text = std::fs::read_to_string(&sort_summary.metadata_file_path).expect("read metadata");
+        assert!(metadata_text.contains("empty_values = 1"));
+
+        let _ = std::fs::remove_dir_all(&temporary_directory);
+    }
+
+    /// A tiny budget forces one run per row and several merge passes
+    #[test]
+    fn test_spilled_runs_merge_to_same_result() {
+        let temporary_directory = std::env::temp_dir().join(format!("rows_and_columns_spill_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temporary_directory).expect("temp dir should be created");
+        let source_csv_path = temporary_directory.join("numbers.csv");
+
+        let row_count = MAXIMUM_MERGE_FAN_IN * 2 + 5;
+        let mut source_text = String::from("value,label\n");
+        for row_number in 0..row_count {
+            source_text.push_str(&format!("{},row {}\n", (row_number * 37) % 101, row_number));
+        }
+        std::fs::write(&source_csv_path, &source_text).expect("write source");
+        let analysis_results = create_test_analysis_results(
+            &source_csv_path,
+            &[("value", CsvColumnDataType::Integer), ("label", CsvColumnDataType::String)],
+        );
+
+        let work_directory = temporary_directory.join("work");
+        let output_csv_path = temporary_directory.join("numbers_sorted.csv");
+        let sort_summary = perform_external_sort(
+            &DatasetRowSource::CsvFile(&analysis_results),
+            &ExternalSortRequest {
+                sort_keys: parse_sort_key_list("1", 2).expect("keys"),
+                memory_budget_bytes: 1,
+            },
+            &work_directory,
+            &output_csv_path,
+        ).expect("sort should succeed");
+
+        assert_eq!(sort_summary.spilled_run_count, row_count);
+        assert_eq!(sort_summary.merge_pass_count, 2);
+
+        let sorted_values: Vec<i64> = std::fs::read_to_string(&output_csv_path)
+            .expect("read output")
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').next().unwrap_or("").parse::<i64>().expect("integer value"))
+            .collect();
+        let mut expected_values = sorted_values.clone();
+        expected_values.sort();
+        assert_eq!(sorted_values.len(), row_count);
+        assert_eq!(sorted_values, expected_values);
+
+        // Spill files are removed once the sort finishes
+        let leftover_entries = std::fs::read_dir(&work_directory).map(|entries| entries.count()).unwrap_or(0);
+        assert_eq!(leftover_entries, 0);
+
+        let _ = std::fs::remove_dir_all(&temporary_directory);
+    }
+
+    /// Integer IDs above 2^53 that differ only in the last digit keep their order
+    #[test]
+    fn test_large_integer_keys_sort_exactly() {
+        let sort_keys = parse_sort_key_list("1", 1).expect("keys");
+        let column_data_types = [CsvColumnDataType::Integer];
+        let mut sortable_rows: Vec<SortableRow> = ["9007199254740993", "9007199254740992", "-9223372036854775808", "9223372036854775807"]
+            .iter()
+            .map(|cell_text| build_sortable_row(vec![cell_text.to_string()], &sort_keys, &column_data_types))
+            .collect();
+
+        sort_rows_in_memory(&mut sortable_rows, &sort_keys);
+
+        let sorted_cells: Vec<&str> = sortable_rows.iter().map(|sortable_row| sortable_row.field_values[0].as_str()).collect();
+        assert_eq!(sorted_cells, vec!["-9223372036854775808", "9007199254740992", "9007199254740993", "9223372036854775807"]);
+        assert_eq!(compare_sortable_rows(&sortable_rows[1], &sortable_rows[2], &sort_keys), Ordering::Less);
+    }
+}
@@ -0,0 +1,370 @@
+// src/file_selection_history_module.rs
+
+/// Recent files and bookmarks for the rows_and_columns file browser
+///
+/// The file browser remembers the CSV files the user chose to analyze and
+/// the directories they bookmarked, so the next session can jump straight
+/// back instead of navigating from the current directory again. Both lists
+/// live in one small TOML file in the data directory:
+///
+/// ```text
+/// # rows_and_columns file selection history
+/// [recent_files]
+/// file_1 = "/home/me/exports/cats.csv"
+/// file_2 = "/home/me/exports/dogs.csv.gz"
+///
+/// [bookmarks]
+/// exports = "/home/me/exports"
+/// ```
+///
+/// # Core Responsibilities
+/// - Load and save `file_selection_history.toml` in rows_columns_data
+/// - Keep the recent files most-recent-first, without duplicates, capped
+/// - Add, replace, remove and look up named directory bookmarks
+///
+/// # Design Philosophy
+/// - A missing file is an empty history, not an error
+/// - Bookmark names are bare TOML keys (letters, digits, `_` and `-`), so
+///   the file stays readable and editable by hand
+/// - Only absolute paths are stored, so the history works from any directory
+use std::path::{Path, PathBuf};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+// Import the vanilla TOML reader and string escaping
+use super::metadata_manager_module::{escape_toml_string_value, parse_toml_document};
+
+/// File name of the history inside the rows_columns_data directory
+pub const FILE_SELECTION_HISTORY_FILE_NAME: &str = "file_selection_history.toml";
+
+/// Most recent files remembered
+pub const MAXIMUM_RECENT_FILE_COUNT: usize = 10;
+
+/// TOML table holding the recent files (`file_1`, `file_2`, ...)
+const RECENT_FILES_TABLE_NAME: &str = "recent_files";
+
+/// TOML table holding the bookmarks (`name = "path"`)
+const BOOKMARKS_TABLE_NAME: &str = "bookmarks";
+
+/// A named directory the browser can jump to
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSelectionBookmark {
+    /// Name typed to jump to the bookmark
+    pub bookmark_name: String,
+
+    /// Absolute path of the bookmarked directory
+    pub bookmarked_directory_path: PathBuf,
+}
+
+/// Recent files and bookmarks of the file browser
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileSelectionHistory {
+    /// Files chosen for analysis, most recent first
+    pub recent_file_paths: Vec<PathBuf>,
+
+    /// Bookmarks sorted by name
+    pub bookmarks: Vec<FileSelectionBookmark>,
+}
+
+impl FileSelectionHistory {
+    /// Loads the history, or an empty one if the file does not exist yet
+    ///
+    /// # Arguments
+    /// * `history_file_path` - Path to `file_selection_history.toml`
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<FileSelectionHistory>` - The stored history
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the file exists but cannot be read
+    /// * `RowsAndColumnsError::MetadataError` - If the file is not valid history TOML
+    pub fn load(history_file_path: &Path) -> RowsAndColumnsResult<FileSelectionHistory> {
+        if !history_file_path.exists() {
+            return Ok(FileSelectionHistory::default());
+        }
+        let history_text = std::fs::read_to_string(history_file_path).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read file selection history: {}", history_file_path.display()),
+                io_error
+            )
+        })?;
+        let source_description = history_file_path.display().to_string();
+        let history_document = parse_toml_document(&history_text, &source_description)?;
+
+        // Step 1: Read the text values of one table in file order
+        let read_path_entries = |table_name: &str| -> RowsAndColumnsResult<Vec<(String, PathBuf)>> {
+            let Some(history_table) = history_document.get_table(table_name) else {
+                return Ok(Vec::new());
+            };
+            history_table.table_entries.iter()
+                .map(|(entry_key, entry_value)| {
+                    entry_value.as_text()
+                        .map(|path_text| (entry_key.clone(), PathBuf::from(path_text)))
+                        .ok_or_else(|| create_metadata_error(
+                            &format!("[{}] {} must be a quoted path", table_name, entry_key),
+                            &source_description
+                        ))
+                })
+                .collect()
+        };
+
+        // Step 2: Rebuild the history through the same rules used when adding entries
+        let mut loaded_history = FileSelectionHistory::default();
+        for (_, recent_file_path) in read_path_entries(RECENT_FILES_TABLE_NAME)?.into_iter().rev() {
+            loaded_history.record_recent_file(&recent_file_path);
+        }
+        for (bookmark_name, bookmarked_directory_path) in read_path_entries(BOOKMARKS_TABLE_NAME)? {
+            loaded_history.add_bookmark(&bookmark_name, &bookmarked_directory_path).map_err(|_| {
+                create_metadata_error(&format!("Invalid bookmark name: {}", bookmark_name), &source_description)
+            })?;
+        }
+        Ok(loaded_history)
+    }
+
+    /// Writes the history, replacing the previous file
+    ///
+    /// # Arguments
+    /// * `history_file_path` - Path to `file_selection_history.toml`
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If the file cannot be written
+    pub fn save(&self, history_file_path: &Path) -> RowsAndColumnsResult<()> {
+        std::fs::write(history_file_path, format_file_selection_history_toml(self)).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to write file selection history: {}", history_file_path.display()),
+                io_error
+            )
+        })
+    }
+
+    /// Puts a file at the top of the recent files
+    ///
+    /// A file already in the list moves to the top; the oldest entry is
+    /// dropped once there are more than `MAXIMUM_RECENT_FILE_COUNT`.
+    ///
+    /// # Arguments
+    /// * `recent_file_path` - Absolute path of the chosen file
+    pub fn record_recent_file(&mut self, recent_file_path: &Path) {
+        self.recent_file_paths.retain(|existing_file_path| existing_file_path != recent_file_path);
+        self.recent_file_paths.insert(0, recent_file_path.to_path_buf());
+        self.recent_file_paths.truncate(MAXIMUM_RECENT_FILE_COUNT);
+    }
+
+    /// Adds a bookmark, replacing one with the same name
+    ///
+    /// # Arguments
+    /// * `bookmark_name` - Letters, digits, `_` and `-`
+    /// * `bookmarked_directory_path` - Absolute path of the directory
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<()>` - Success or error
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::ConfigurationError` - If the name is empty or has other characters
+    pub fn add_bookmark(&mut self, bookmark_name: &str, bookmarked_directory_path: &Path) -> RowsAndColumnsResult<()> {
+        let is_valid_bookmark_name = !bookmark_name.is_empty()
+            && bookmark_name.chars().all(|name_character| {
+                name_character.is_ascii_alphanumeric() || name_character == '_' || name_character == '-'
+            });
+        if !is_valid_bookmark_name {
+            return Err(create_configuration_error(&format!(
+                "Bookmark name '{}' may only use letters, digits, '_' and '-'",
+                bookmark_name
+            )));
+        }
+
+        self.remove_bookmark(bookmark_name);
+        self.bookmarks.push(FileSelectionBookmark {
+            bookmark_name: bookmark_name.to_string(),
+            bookmarked_directory_path: bookmarked_directory_path.to_path_buf(),
+        });
+        self.bookmarks.sort_by(|first_bookmark, second_bookmark| {
+            first_bookmark.bookmark_name.cmp(&second_bookmark.bookmark_name)
+        });
+        Ok(())
+    }
+
+    /// Removes a bookmark by name
+    ///
+    /// # Arguments
+    /// * `bookmark_name` - The bookmark to remove
+    ///
+    /// # Returns
+    /// * `bool` - True if a bookmark was removed
+    pub fn remove_bookmark(&mut self, bookmark_name: &str) -> bool {
+        let bookmark_count_before = self.bookmarks.len();
+        self.bookmarks.retain(|existing_bookmark| existing_bookmark.bookmark_name != bookmark_name);
+        self.bookmarks.len() != bookmark_count_before
+    }
+
+    /// Looks up a bookmarked directory by name
+    ///
+    /// # Arguments
+    /// * `bookmark_name` - The bookmark to find
+    ///
+    /// # Returns
+    /// * `Option<&Path>` - The bookmarked directory, or None if there is no such bookmark
+    pub fn find_bookmark(&self, bookmark_name: &str) -> Option<&Path> {
+        self.bookmarks.iter()
+            .find(|existing_bookmark| existing_bookmark.bookmark_name == bookmark_name)
+            .map(|existing_bookmark| existing_bookmark.bookmarked_directory_path.as_path())
+    }
+}
+
+/// Formats the history as TOML
+///
+/// # Arguments
+/// * `file_selection_history` - The history to write
+///
+/// # Returns
+/// * `String` - `[recent_files]` and `[bookmarks]` tables
+pub fn format_file_selection_history_toml(file_selection_history: &FileSelectionHistory) -> String {
+    let mut toml_content = String::from("# rows_and_columns file selection history\n");
+
+    toml_content.push_str(&format!("[{}]\n", RECENT_FILES_TABLE_NAME));
+    for (recent_file_position, recent_file_path) in file_selection_history.recent_file_paths.iter().enumerate() {
+        toml_content.push_str(&format!(
+            "file_{} = \"{}\"\n",
+            recent_file_position + 1,
+            escape_toml_string_value(&recent_file_path.to_string_lossy())
+        ));
+    }
+
+    toml_content.push_str(&format!("\n[{}]\n", BOOKMARKS_TABLE_NAME));
+    for file_selection_bookmark in &file_selection_history.bookmarks {
+        toml_content.push_str(&format!(
+            "{} = \"{}\"\n",
+            file_selection_bookmark.bookmark_name,
+            escape_toml_string_value(&file_selection_bookmark.bookmarked_directory_path.to_string_lossy())
+        ));
+    }
+
+    toml_content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test recent files move to the top, never repeat and are capped
+    #[test]
+    fn test_record_recent_file_deduplicates_and_caps() {
+        let mut file_selection_history = FileSelectionHistory::default();
+        for file_number in 1..=(MAXIMUM_RECENT_FILE_COUNT + 2) {
+            file_selection_history.record_recent_file(Path::new(&format!("/data/file_{}.csv", file_number)));
+        }
+        file_selection_history.record_recent_file(Path::new("/data/file_5.csv"));
+
+        assert_eq!(file_selection_history.recent_file_paths.len(), MAXIMUM_RECENT_FILE_COUNT);
+        assert_eq!(file_selection_history.recent_file_paths[0], PathBuf::from("/data/file_5.csv"));
+        assert_eq!(file_selection_history.recent_file_paths[1], PathBuf::from("/data/file_12.csv"));
+        assert!(!file_selection_history.recent_file_paths.contains(&PathBuf::from("/data/file_2.csv")));
+        assert_eq!(
+            file_selection_history.recent_file_paths.iter().filter(|recent_file_path| recent_file_path.ends_with("file_5.csv")).count(),
+            1
+        );
+    }
+
+    /// Test bookmarks are validated, replaced by name, found and removed
+    #[test]
+    fn test_bookmarks_add_replace_find_remove() {
+        let mut file_selection_history = FileSelectionHistory::default();
+        file_selection_history.add_bookmark("exports", Path::new("/old/exports")).expect("valid name");
+        file_selection_history.add_bookmark("exports", Path::new("/new/exports")).expect("valid name");
+        file_selection_history.add_bookmark("archive-2024", Path::new("/archive")).expect("valid name");
+
+        assert_eq!(file_selection_history.bookmarks.len(), 2);
+        assert_eq!(file_selection_history.bookmarks[0].bookmark_name, "archive-2024");
+        assert_eq!(file_selection_history.find_bookmark("exports"), Some(Path::new("/new/exports")));
+        assert!(file_selection_history.add_bookmark("my exports", Path::new("/x")).is_err());
+        assert!(file_selection_history.add_bookmark("", Path::new("/x")).is_err());
+
+        assert!(file_selection_history.remove_bookmark("exports"));
+        assert!(!file_selection_history.remove_bookmark("exports"));
+        assert_eq!(file_selection_history.find_bookmark("exports"), None);
+    }
+
+    /// Test the history survives a save and load, and a missing file loads empty
+    #[test]
+    fn test_history_save_and_load_round_trip() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_history_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let history_file_path = test_directory.join(FILE_SELECTION_HISTORY_FILE_NAME);
+        let _ = std::fs::remove_file(&history_file_path);
+        assert_eq!(FileSelectionHistory::load(&history_file_path).expect("missing file is empty"), FileSelectionHistory::default());
+
+        let mut file_selection_history = FileSelectionHistory::default();
+        file_selection_history.record_recent_file(Path::new("/data/older.csv"));
+        file_selection_history.record_recent_file(Path::new("/data/with \"quotes\".csv"));
+        file_selection_history.add_bookmark("data", Path::new("/data")).expect("valid name");
+        file_selection_history.save(&history_file_path).expect("history should be written");
+
+        let loaded_history = FileSelectionHistory::load(&history_file_path).expect("history should load");
+        assert_eq!(loaded_history, file_selection_history);
+
+        std::fs::write(&history_file_path, "[bookmarks]\ndata = 3\n").expect("test file should be writable");
+        assert!(FileSelectionHistory::load(&history_file_path).is_err());
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+
+    /// Test paths with TOML-significant and control characters round-trip in
+    /// order, and hand-edited or truncated files are handled
+    #[test]
+    fn test_odd_paths_and_damaged_history_files() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_history_odd_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let history_file_path = test_directory.join(FILE_SELECTION_HISTORY_FILE_NAME);
+
+        let odd_file_paths = [
+            "C:\\Users\\me\\data.csv",
+            "/data/line\nbreak.csv",
+            "/data/tab\tand\rreturn.csv",
+            "/data/[table] # not = a comment.csv",
+            "/data/bell\u{7}.csv",
+            "/données/日本語.csv",
+            "/data/file_1.csv",
+            "/data/file_2.csv",
+            "/data/file_10.csv",
+            "/data/trailing\\",
+        ];
+        let mut file_selection_history = FileSelectionHistory::default();
+        for odd_file_path in odd_file_paths.iter().rev() {
+            file_selection_history.record_recent_file(Path::new(odd_file_path));
+        }
+        file_selection_history.add_bookmark("odd", Path::new("/data/\"quoted\" dir")).expect("valid name");
+        file_selection_history.save(&history_file_path).expect("history should be written");
+
+        let loaded_history = FileSelectionHistory::load(&history_file_path).expect("history should load");
+        assert_eq!(
+            loaded_history.recent_file_paths,
+            odd_file_paths.iter().map(PathBuf::from).collect::<Vec<_>>()
+        );
+        assert_eq!(loaded_history, file_selection_history);
+
+        // Hand-edited duplicates collapse, keeping the most recent position
+        std::fs::write(&history_file_path, "[recent_files]\nfile_1 = \"/a.csv\"\nfile_2 = \"/b.csv\"\nfile_3 = \"/a.csv\"\n")
+            .expect("test file should be writable");
+        let deduplicated_history = FileSelectionHistory::load(&history_file_path).expect("history should load");
+        assert_eq!(deduplicated_history.recent_file_paths, vec![PathBuf::from("/a.csv"), PathBuf::from("/b.csv")]);
+
+        // A file cut off mid-write, or a bookmark name that is not allowed, is an error
+        let saved_text = format_file_selection_history_toml(&file_selection_history);
+        std::fs::write(&history_file_path, &saved_text[..saved_text.find("line").expect("path is saved")])
+            .expect("test file should be writable");
+        assert!(FileSelectionHistory::load(&history_file_path).is_err());
+        std::fs::write(&history_file_path, "[bookmarks]\n\"my data\" = \"/data\"\n").expect("test file should be writable");
+        assert!(FileSelectionHistory::load(&history_file_path).is_err());
+
+        let _ = std::fs::remove_dir_all(test_directory);
+    }
+}
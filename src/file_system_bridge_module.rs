@@ -5,38 +5,65 @@
 /// 
 /// This module provides file browsing and selection capabilities specifically
 /// tailored for CSV file import operations. It integrates with the FF-style
-/// interface patterns while focusing on CSV file management.
+/// interface patterns while focusing on CSV file management. It is what
+/// `rows_and_columns` shows when started without arguments.
 /// 
 /// # Core Responsibilities
 /// - Browse file system for CSV files using FF-style interface
 /// - Validate CSV file accessibility and basic format
-/// - Provide file selection UI for CSV import operations
+/// - Preview a chosen CSV file (size, delimiter, header, first rows, row
+///   count) and ask before the full analysis starts
+/// - Accept a typed path as a shortcut to a directory or file
+/// - Remember recent files and named directory bookmarks between sessions
+/// - Filter the listing by file name and show or hide dot files on request
 /// - Bridge between file system operations and CSV processing
 /// 
 /// # Design Philosophy
 /// - Minimal, focused interface following FF patterns
 /// - Clear file validation before processing attempts
+/// - A cheap preview first, so a wrong file costs a keystroke, not an analysis
 /// - User-friendly error messages for file access issues
-/// - Integration with binary-relative path management
-
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::{self, Write};
 
 // Import our custom error types for comprehensive error handling
 use super::error_types_module::{
-    RowsAndColumnsError,
     RowsAndColumnsResult,
     create_file_system_error,
-    create_configuration_error,
+    create_command_line_usage_error,
 };
 
-// Import path management for consistent file operations
-use super::manage_absolute_executable_directory_relative_paths::{
-    make_file_path_abs_executabledirectoryrelative_canonicalized_or_error,
-    abs_executable_directory_relative_exists,
+// Import compressed CSV file name detection (.csv.gz, .tsv.gz)
+use super::gzip_decompression_module::is_gzip_compressed_csv_file_name;
+
+// Import the persistent recent files and bookmarks
+use super::file_selection_history_module::FileSelectionHistory;
+
+// Import warnings for history problems that should not stop browsing
+use super::console_output_module::print_warning_line;
+
+// Import the quick preview shown before a file is analyzed
+use super::csv_file_preview_module::{
+    CsvDataRowCountEstimate,
+    CsvFilePreview,
+    build_csv_file_preview,
+    describe_csv_delimiter,
 };
 
+// Import table layout and terminal width for the preview table
+use super::terminal_control_module::{
+    clip_text_to_display_width,
+    format_aligned_text_table_lines,
+    query_terminal_size,
+};
+
+/// Most columns shown in the preview table
+const PREVIEW_MAXIMUM_COLUMN_COUNT: usize = 8;
+
+/// Widest a preview table column may be
+const PREVIEW_MAXIMUM_COLUMN_WIDTH: usize = 16;
+
 /// Information about a discovered CSV file
 /// 
 /// This structure holds all relevant information about a CSV file that
@@ -63,14 +90,20 @@ pub struct CsvFileInformation {
 /// 
 /// This function provides a simple, FF-style terminal interface for browsing
 /// and selecting CSV files for import. It handles user input and file validation.
+/// A chosen CSV file is previewed first and only returned once the user
+/// confirms it.
+/// 
+/// # Arguments
+/// * `history_file_path` - Where recent files and bookmarks are kept
+///   (`file_selection_history.toml` in rows_columns_data)
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<Option<CsvFileInformation>>` - Selected file info or None if cancelled
 /// 
 /// # Errors
 /// * `RowsAndColumnsError::FileSystemError` - If file system access fails
-/// * `RowsAndColumnsError::ConfigurationError` - If no CSV files are found
-pub fn launch_csv_file_selection_interface() -> RowsAndColumnsResult<Option<CsvFileInformation>> {
+/// * `RowsAndColumnsError::CommandLineUsageError` - If input ends before a file is chosen
+pub fn launch_csv_file_selection_interface(history_file_path: &Path) -> RowsAndColumnsResult<Option<CsvFileInformation>> {
     // Display the file selection header
     display_csv_file_selection_header();
     
@@ -83,8 +116,57 @@ pub fn launch_csv_file_selection_interface() -> RowsAndColumnsResult<Option<CsvF
             )
         })?;
     
+    // A broken history file is left alone (not overwritten) so it can be fixed by hand
+    let (file_selection_history, history_is_writable) = match FileSelectionHistory::load(history_file_path) {
+        Ok(loaded_history) => (loaded_history, true),
+        Err(history_error) => {
+            print_warning_line(&format!(
+                "⚠️  Ignoring recent files and bookmarks for this session: {}",
+                history_error
+            ));
+            (FileSelectionHistory::default(), false)
+        }
+    };
+    let mut browser_state = FileBrowserState {
+        current_directory,
+        show_hidden_files: false,
+        file_name_filter: None,
+        file_selection_history,
+        history_file_path: history_is_writable.then(|| history_file_path.to_path_buf()),
+    };
+    
     // Start the interactive file selection process
-    interactive_directory_and_file_selection(&current_directory)
+    interactive_directory_and_file_selection(&mut browser_state)
+}
+
+/// Where the browser is, how it lists directories, and what it remembers
+#[derive(Debug, Clone)]
+struct FileBrowserState {
+    /// The directory being listed
+    current_directory: PathBuf,
+    
+    /// Whether dot files and dot directories are listed
+    show_hidden_files: bool,
+    
+    /// Case-insensitive text file names must contain (directories are always listed)
+    file_name_filter: Option<String>,
+    
+    /// Recent files and bookmarks
+    file_selection_history: FileSelectionHistory,
+    
+    /// Where to save the history, or None when it could not be loaded
+    history_file_path: Option<PathBuf>,
+}
+
+impl FileBrowserState {
+    /// Saves the history, warning instead of failing when it cannot be written
+    fn save_file_selection_history(&self) {
+        if let Some(history_file_path) = &self.history_file_path
+            && let Err(history_error) = self.file_selection_history.save(history_file_path)
+        {
+            print_warning_line(&format!("⚠️  Recent files and bookmarks were not saved: {}", history_error));
+        }
+    }
 }
 
 /// Displays the header for the CSV file selection interface
@@ -96,7 +178,13 @@ fn display_csv_file_selection_header() {
     println!("  CSV File Selection - rows_and_columns");
     println!("═══════════════════════════════════════════════════════════════");
     println!("  Select a CSV file to import and analyze:");
-    println!("  • Enter number to select file/directory");
+    println!("  • Enter number to select file/directory (CSV files are previewed first)");
+    println!("  • Type a path to jump to a directory or file");
+    println!("  • 'r' = recent files, 'r N' = open recent file N");
+    println!("  • 'g' = bookmarks, 'g NAME' = go to bookmark NAME");
+    println!("  • 'mark NAME' / 'unmark NAME' = bookmark / forget this directory");
+    println!("  • 'f TEXT' = only list files whose name contains TEXT, 'f' = list all");
+    println!("  • 'h' = show or hide dot files");
     println!("  • 'b' = back to parent directory");
     println!("  • 'q' = quit file selection");
     println!("  • Enter = refresh current directory");
@@ -110,24 +198,24 @@ fn display_csv_file_selection_header() {
 /// navigate directories and select CSV files using a numbered interface.
 /// 
 /// # Arguments
-/// * `starting_directory` - The directory to begin browsing from
+/// * `browser_state` - Starting directory, listing options and history
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<Option<CsvFileInformation>>` - Selected file or None if cancelled
 fn interactive_directory_and_file_selection(
-    starting_directory: &Path
+    browser_state: &mut FileBrowserState
 ) -> RowsAndColumnsResult<Option<CsvFileInformation>> {
-    let mut current_directory = starting_directory.to_path_buf();
-    
     loop {
         // Display current directory contents
-        let directory_items = scan_directory_for_navigation(&current_directory)?;
+        let directory_items = scan_directory_for_navigation(
+            &browser_state.current_directory,
+            browser_state.show_hidden_files,
+            browser_state.file_name_filter.as_deref(),
+        )?;
         
+        display_directory_contents(browser_state, &directory_items);
         if directory_items.is_empty() {
-            println!("No accessible files or directories found in this location.");
             println!("Press Enter to try refreshing, or 'b' to go back, 'q' to quit.");
-        } else {
-            display_directory_contents(&current_directory, &directory_items);
         }
         
         // Get user input
@@ -136,21 +224,21 @@ fn interactive_directory_and_file_selection(
             create_file_system_error("Failed to flush stdout for user input", io_error)
         })?;
         
-        let mut user_input = String::new();
-        io::stdin().read_line(&mut user_input).map_err(|io_error| {
-            create_file_system_error("Failed to read user input", io_error)
-        })?;
-        
-        let user_input = user_input.trim();
+        let user_input = read_user_input_line()?;
         
         // Process user input
-        match process_user_file_selection_input(user_input, &directory_items, &mut current_directory)? {
+        match process_user_file_selection_input(&user_input, &directory_items, browser_state)? {
             FileSelectionAction::ContinueBrowsing => {
                 // Continue the loop
                 continue;
             }
             FileSelectionAction::FileSelected(csv_file_info) => {
-                return Ok(Some(csv_file_info));
+                // Preview first; the full analysis only starts once confirmed
+                if preview_csv_file_and_confirm_analysis(&csv_file_info)? {
+                    browser_state.file_selection_history.record_recent_file(&csv_file_info.absolute_file_path);
+                    browser_state.save_file_selection_history();
+                    return Ok(Some(csv_file_info));
+                }
             }
             FileSelectionAction::QuitSelection => {
                 return Ok(None);
@@ -159,6 +247,147 @@ fn interactive_directory_and_file_selection(
     }
 }
 
+/// Reads one trimmed line of user input
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - The trimmed line
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If standard input cannot be read
+/// * `RowsAndColumnsError::CommandLineUsageError` - If input ends (nobody is left to choose a file)
+fn read_user_input_line() -> RowsAndColumnsResult<String> {
+    let mut user_input = String::new();
+    let bytes_read = io::stdin().read_line(&mut user_input).map_err(|io_error| {
+        create_file_system_error("Failed to read user input", io_error)
+    })?;
+    if bytes_read == 0 {
+        println!();
+        return Err(create_command_line_usage_error(
+            "Input ended before a CSV file was chosen; pass the file on the command line instead",
+            None
+        ));
+    }
+    Ok(user_input.trim().to_string())
+}
+
+/// Shows the preview of a chosen CSV file and asks whether to analyze it
+/// 
+/// A file that cannot be previewed is reported and not analyzed, so the
+/// user goes back to the list instead of into a failing analysis.
+/// 
+/// # Arguments
+/// * `csv_file_info` - The file chosen in the browser
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<bool>` - True to analyze the file, false to keep browsing
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If user input cannot be read
+/// * `RowsAndColumnsError::CommandLineUsageError` - If input ends at the question
+fn preview_csv_file_and_confirm_analysis(csv_file_info: &CsvFileInformation) -> RowsAndColumnsResult<bool> {
+    if !csv_file_info.is_readable {
+        println!("❌ {} cannot be opened for reading (check its permissions).", csv_file_info.filename_only);
+        println!();
+        return Ok(false);
+    }
+    if csv_file_info.file_size_bytes == 0 {
+        println!("❌ {} is empty; there is nothing to analyze.", csv_file_info.filename_only);
+        println!();
+        return Ok(false);
+    }
+    
+    match build_csv_file_preview(&csv_file_info.absolute_file_path) {
+        Ok(csv_file_preview) => display_csv_file_preview(&csv_file_info.filename_only, &csv_file_preview),
+        Err(preview_error) => {
+            println!("❌ Cannot preview {}: {}", csv_file_info.filename_only, preview_error);
+            println!();
+            return Ok(false);
+        }
+    }
+    
+    loop {
+        print!("Analyze this file? [Enter/y = analyze, n = back to the list]: ");
+        io::stdout().flush().map_err(|io_error| {
+            create_file_system_error("Failed to flush stdout for user input", io_error)
+        })?;
+        match read_user_input_line()?.to_lowercase().as_str() {
+            "" | "y" | "yes" => return Ok(true),
+            "n" | "no" | "b" | "back" => {
+                println!();
+                return Ok(false);
+            }
+            _ => println!("Please answer 'y' or 'n'."),
+        }
+    }
+}
+
+/// Displays the quick preview of a CSV file
+/// 
+/// # Arguments
+/// * `file_name` - Name shown in the preview title
+/// * `csv_file_preview` - The preview to show
+fn display_csv_file_preview(file_name: &str, csv_file_preview: &CsvFilePreview) {
+    let maximum_line_width = query_terminal_size().column_count.saturating_sub(2).max(20);
+    
+    println!();
+    println!("───────────────────────────────────────────────────────────────");
+    println!("  Preview: {}", file_name);
+    println!("───────────────────────────────────────────────────────────────");
+    let compression_note = if csv_file_preview.is_gzip_compressed { " (gzip-compressed)" } else { "" };
+    println!("  Size:       {}{}", format_file_size(csv_file_preview.file_size_bytes), compression_note);
+    println!("  Delimiter:  {}", describe_csv_delimiter(csv_file_preview.sniffed_delimiter));
+    println!("  Columns:    {}", csv_file_preview.header_fields.len());
+    println!("  Rows:       {}", format_data_row_count_estimate(csv_file_preview.data_row_count_estimate));
+    if csv_file_preview.sniffed_delimiter != ',' {
+        println!(
+            "  ⚠️  The analysis splits fields on commas; this file looks {}-separated.",
+            describe_csv_delimiter(csv_file_preview.sniffed_delimiter)
+        );
+    }
+    println!();
+    
+    // Header and first rows, limited to the first few columns
+    let shown_column_count = csv_file_preview.header_fields.len().min(PREVIEW_MAXIMUM_COLUMN_COUNT);
+    let header_cells = csv_file_preview.header_fields[..shown_column_count].to_vec();
+    let body_rows: Vec<Vec<String>> = csv_file_preview.first_data_rows.iter()
+        .map(|data_row| data_row.iter().take(shown_column_count).cloned().collect())
+        .collect();
+    if header_cells.is_empty() {
+        println!("  (The file is empty)");
+    } else {
+        let table_lines = format_aligned_text_table_lines(
+            &header_cells,
+            &body_rows,
+            &vec![false; shown_column_count],
+            PREVIEW_MAXIMUM_COLUMN_WIDTH,
+            '─',
+        );
+        for table_line in table_lines {
+            println!("  {}", clip_text_to_display_width(&table_line, maximum_line_width));
+        }
+        let hidden_column_count = csv_file_preview.header_fields.len() - shown_column_count;
+        if hidden_column_count > 0 {
+            println!("  … and {} more columns", hidden_column_count);
+        }
+    }
+    println!();
+}
+
+/// Formats a row count for the preview ("8", "about 20000", ...)
+/// 
+/// # Arguments
+/// * `data_row_count_estimate` - The counted or estimated number of data rows
+/// 
+/// # Returns
+/// * `String` - The count with a word saying how exact it is
+fn format_data_row_count_estimate(data_row_count_estimate: CsvDataRowCountEstimate) -> String {
+    match data_row_count_estimate {
+        CsvDataRowCountEstimate::Exact(data_row_count) => data_row_count.to_string(),
+        CsvDataRowCountEstimate::Estimated(data_row_count) => format!("about {} (estimated from the first rows)", data_row_count),
+        CsvDataRowCountEstimate::Unknown => "unknown until analyzed (compressed file)".to_string(),
+    }
+}
+
 /// Represents the result of processing user input in file selection
 /// 
 /// This enum clarifies what action should be taken based on user input
@@ -168,7 +397,7 @@ enum FileSelectionAction {
     /// Continue browsing in the current or new directory
     ContinueBrowsing,
     
-    /// User selected a CSV file successfully
+    /// User selected a CSV file (to be previewed and confirmed)
     FileSelected(CsvFileInformation),
     
     /// User chose to quit the selection process
@@ -204,10 +433,19 @@ pub struct DirectoryItem {
 /// 
 /// # Arguments
 /// * `directory_path` - The directory to scan
+/// * `show_hidden_files` - Whether items starting with '.' are included
+/// * `file_name_filter` - Case-insensitive text a file name must contain
+///   (directories are always included, so the filter survives navigation)
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<Vec<DirectoryItem>>` - List of items found or error
-fn scan_directory_for_navigation(directory_path: &Path) -> RowsAndColumnsResult<Vec<DirectoryItem>> {
+fn scan_directory_for_navigation(
+    directory_path: &Path,
+    show_hidden_files: bool,
+    file_name_filter: Option<&str>,
+) -> RowsAndColumnsResult<Vec<DirectoryItem>> {
+    let lowercase_file_name_filter = file_name_filter.map(str::to_lowercase);
+
     let directory_entries = fs::read_dir(directory_path)
         .map_err(|io_error| {
             create_file_system_error(
@@ -240,18 +478,24 @@ fn scan_directory_for_navigation(directory_path: &Path) -> RowsAndColumnsResult<
             None => continue, // Skip items without names
         };
         
-        // Skip hidden files and directories (starting with .)
-        if item_name.starts_with('.') {
+        // Skip hidden files and directories (starting with .) unless asked to show them
+        if item_name.starts_with('.') && !show_hidden_files {
             continue;
         }
         
         let is_directory = entry_metadata.is_dir();
+        
+        // Skip files whose name does not contain the filter text
+        if !is_directory
+            && let Some(lowercase_filter_text) = &lowercase_file_name_filter
+            && !item_name.to_lowercase().contains(lowercase_filter_text.as_str())
+        {
+            continue;
+        }
         let file_size_bytes = if is_directory { None } else { Some(entry_metadata.len()) };
         
-        // Check if this appears to be a CSV file
-        let appears_to_be_csv = !is_directory && 
-            (item_name.to_lowercase().ends_with(".csv") || 
-             item_name.to_lowercase().ends_with(".tsv"));
+        // Check if this appears to be a CSV file (plain or gzip-compressed)
+        let appears_to_be_csv = !is_directory && is_csv_file_name(&item_name);
         
         directory_items.push(DirectoryItem {
             item_name,
@@ -281,16 +525,39 @@ fn scan_directory_for_navigation(directory_path: &Path) -> RowsAndColumnsResult<
     Ok(directory_items)
 }
 
+/// Tells whether a file name looks like a CSV file the browser offers
+/// 
+/// Batch analysis uses the same rule, so it picks up exactly the files the
+/// browser marks as `[CSV]`.
+/// 
+/// # Arguments
+/// * `file_name` - File name without directory
+/// 
+/// # Returns
+/// * `bool` - True for .csv, .tsv, .csv.gz and .tsv.gz (any letter case)
+pub fn is_csv_file_name(file_name: &str) -> bool {
+    let lowercase_file_name = file_name.to_lowercase();
+    lowercase_file_name.ends_with(".csv")
+        || lowercase_file_name.ends_with(".tsv")
+        || is_gzip_compressed_csv_file_name(file_name)
+}
+
 /// Displays directory contents in a numbered list format
 /// 
 /// This function shows the current directory and all items in an FF-style
 /// numbered list, making it easy for users to select items.
 /// 
 /// # Arguments
-/// * `current_directory` - The directory being displayed
+/// * `browser_state` - The directory being displayed and the active listing options
 /// * `directory_items` - The items to display
-fn display_directory_contents(current_directory: &Path, directory_items: &[DirectoryItem]) {
-    println!("Current directory: {}", current_directory.display());
+fn display_directory_contents(browser_state: &FileBrowserState, directory_items: &[DirectoryItem]) {
+    println!("Current directory: {}", browser_state.current_directory.display());
+    if let Some(file_name_filter) = &browser_state.file_name_filter {
+        println!("Filter: files containing '{}' ('f' to list all)", file_name_filter);
+    }
+    if browser_state.show_hidden_files {
+        println!("Dot files: shown ('h' to hide)");
+    }
     println!();
     
     if directory_items.is_empty() {
@@ -340,36 +607,42 @@ fn format_file_size(size_bytes: u64) -> String {
 /// Processes user input during file selection
 /// 
 /// This function interprets user commands and navigates accordingly,
-/// handling directory changes, file selection, and quit commands.
+/// handling directory changes, file selection, history, listing options
+/// and quit commands. Commands win over typed paths of the same name.
 /// 
 /// # Arguments
 /// * `user_input` - The trimmed user input string
 /// * `directory_items` - Available items in current directory
-/// * `current_directory` - Mutable reference to current directory path
+/// * `browser_state` - Mutable browser state (directory, options, history)
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<FileSelectionAction>` - Action to take based on input
 fn process_user_file_selection_input(
     user_input: &str,
     directory_items: &[DirectoryItem],
-    current_directory: &mut PathBuf,
+    browser_state: &mut FileBrowserState,
 ) -> RowsAndColumnsResult<FileSelectionAction> {
+    // Commands with an argument ("g data", "f sales") split at the first space
+    let (command_word, command_argument) = user_input
+        .split_once(' ')
+        .map(|(command_word, command_argument)| (command_word, command_argument.trim()))
+        .unwrap_or((user_input, ""));
     
-    match user_input {
-        "" => {
+    match (command_word, command_argument) {
+        ("", _) => {
             // Empty input means refresh current directory
             Ok(FileSelectionAction::ContinueBrowsing)
         }
         
-        "q" | "quit" => {
+        ("q" | "quit", "") => {
             println!("File selection cancelled.");
             Ok(FileSelectionAction::QuitSelection)
         }
         
-        "b" | "back" => {
+        ("b" | "back", "") => {
             // Go to parent directory
-            if let Some(parent_directory) = current_directory.parent() {
-                *current_directory = parent_directory.to_path_buf();
+            if let Some(parent_directory) = browser_state.current_directory.parent() {
+                browser_state.current_directory = parent_directory.to_path_buf();
                 println!("Moving to parent directory...");
             } else {
                 println!("Already at root directory.");
@@ -377,6 +650,87 @@ fn process_user_file_selection_input(
             Ok(FileSelectionAction::ContinueBrowsing)
         }
         
+        ("h" | "hidden", "") => {
+            browser_state.show_hidden_files = !browser_state.show_hidden_files;
+            println!("Dot files are now {}.", if browser_state.show_hidden_files { "shown" } else { "hidden" });
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("f" | "filter", filter_text) => {
+            if filter_text.is_empty() {
+                browser_state.file_name_filter = None;
+                println!("Listing all files.");
+            } else {
+                browser_state.file_name_filter = Some(filter_text.to_string());
+                println!("Listing files whose name contains '{}'.", filter_text);
+            }
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("r" | "recent", "") => {
+            display_recent_files(&browser_state.file_selection_history);
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("r" | "recent", recent_file_number_text) => {
+            let recent_file_paths = &browser_state.file_selection_history.recent_file_paths;
+            match recent_file_number_text.parse::<usize>() {
+                Ok(recent_file_number) if recent_file_number > 0 && recent_file_number <= recent_file_paths.len() => {
+                    let recent_file_path = recent_file_paths[recent_file_number - 1].clone();
+                    if !recent_file_path.is_file() {
+                        println!("{} no longer exists.", recent_file_path.display());
+                        return Ok(FileSelectionAction::ContinueBrowsing);
+                    }
+                    select_file_by_path(&recent_file_path)
+                }
+                _ => {
+                    println!("There is no recent file {}; 'r' lists them.", recent_file_number_text);
+                    Ok(FileSelectionAction::ContinueBrowsing)
+                }
+            }
+        }
+        
+        ("g" | "go", "") => {
+            display_bookmarks(&browser_state.file_selection_history);
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("g" | "go", bookmark_name) => {
+            match browser_state.file_selection_history.find_bookmark(bookmark_name) {
+                Some(bookmarked_directory_path) if bookmarked_directory_path.is_dir() => {
+                    browser_state.current_directory = bookmarked_directory_path.to_path_buf();
+                    println!("Going to bookmark '{}': {}", bookmark_name, bookmarked_directory_path.display());
+                }
+                Some(bookmarked_directory_path) => {
+                    println!("Bookmark '{}' points to a missing directory: {}", bookmark_name, bookmarked_directory_path.display());
+                }
+                None => println!("No bookmark named '{}'; 'g' lists them.", bookmark_name),
+            }
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("mark", bookmark_name) if !bookmark_name.is_empty() => {
+            let current_directory = browser_state.current_directory.clone();
+            match browser_state.file_selection_history.add_bookmark(bookmark_name, &current_directory) {
+                Ok(()) => {
+                    browser_state.save_file_selection_history();
+                    println!("Bookmarked {} as '{}'.", current_directory.display(), bookmark_name);
+                }
+                Err(bookmark_error) => println!("{}", bookmark_error),
+            }
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
+        ("unmark", bookmark_name) if !bookmark_name.is_empty() => {
+            if browser_state.file_selection_history.remove_bookmark(bookmark_name) {
+                browser_state.save_file_selection_history();
+                println!("Removed bookmark '{}'.", bookmark_name);
+            } else {
+                println!("No bookmark named '{}'.", bookmark_name);
+            }
+            Ok(FileSelectionAction::ContinueBrowsing)
+        }
+        
         _ => {
             // Try to parse as a number for item selection
             match user_input.parse::<usize>() {
@@ -385,17 +739,17 @@ fn process_user_file_selection_input(
                     
                     if selected_item.is_directory {
                         // Navigate into the selected directory
-                        *current_directory = selected_item.absolute_path.clone();
+                        browser_state.current_directory = selected_item.absolute_path.clone();
                         println!("Entering directory: {}", selected_item.item_name);
                         Ok(FileSelectionAction::ContinueBrowsing)
                     } else if selected_item.appears_to_be_csv {
                         // Selected a CSV file
                         let csv_file_info = create_csv_file_information(selected_item)?;
-                        println!("Selected CSV file: {}", selected_item.item_name);
+                        println!("Selected CSV file: {} ({})", selected_item.item_name, csv_file_info.file_size_human_readable);
                         Ok(FileSelectionAction::FileSelected(csv_file_info))
                     } else {
                         // Selected a non-CSV file
-                        println!("Selected file is not a CSV file. Please select a .csv or .tsv file.");
+                        println!("Selected file is not a CSV file. Please select a .csv, .tsv, .csv.gz or .tsv.gz file.");
                         Ok(FileSelectionAction::ContinueBrowsing)
                     }
                 }
@@ -405,15 +759,105 @@ fn process_user_file_selection_input(
                     Ok(FileSelectionAction::ContinueBrowsing)
                 }
                 
-                Err(_) => {
-                    println!("Invalid input. Enter a number, 'b' for back, 'q' to quit, or Enter to refresh.");
-                    Ok(FileSelectionAction::ContinueBrowsing)
-                }
+                Err(_) => process_typed_path_input(user_input, &mut browser_state.current_directory),
             }
         }
     }
 }
 
+/// Displays the recent files, most recent first
+/// 
+/// # Arguments
+/// * `file_selection_history` - The history holding the recent files
+fn display_recent_files(file_selection_history: &FileSelectionHistory) {
+    if file_selection_history.recent_file_paths.is_empty() {
+        println!("No recent files yet; files you analyze from here are remembered.");
+        return;
+    }
+    println!("Recent files ('r N' to open):");
+    for (recent_file_position, recent_file_path) in file_selection_history.recent_file_paths.iter().enumerate() {
+        let missing_note = if recent_file_path.is_file() { "" } else { "  (missing)" };
+        println!("  {:2}. {}{}", recent_file_position + 1, recent_file_path.display(), missing_note);
+    }
+    println!();
+}
+
+/// Displays the bookmarks, sorted by name
+/// 
+/// # Arguments
+/// * `file_selection_history` - The history holding the bookmarks
+fn display_bookmarks(file_selection_history: &FileSelectionHistory) {
+    if file_selection_history.bookmarks.is_empty() {
+        println!("No bookmarks yet; 'mark NAME' bookmarks the current directory.");
+        return;
+    }
+    println!("Bookmarks ('g NAME' to go there):");
+    for file_selection_bookmark in &file_selection_history.bookmarks {
+        println!("  {}  {}", file_selection_bookmark.bookmark_name, file_selection_bookmark.bookmarked_directory_path.display());
+    }
+    println!();
+}
+
+/// Handles input that is not a command or a number as a typed path
+/// 
+/// Relative paths are taken from the directory being browsed. A directory
+/// becomes the new browsing location; a file is selected even without a
+/// CSV extension, since the user named it explicitly.
+/// 
+/// # Arguments
+/// * `typed_path` - The path as typed
+/// * `current_directory` - Mutable reference to current directory path
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<FileSelectionAction>` - Action to take based on the path
+fn process_typed_path_input(
+    typed_path: &str,
+    current_directory: &mut PathBuf,
+) -> RowsAndColumnsResult<FileSelectionAction> {
+    let target_path = current_directory.join(typed_path);
+    let Ok(canonical_target_path) = target_path.canonicalize() else {
+        println!("Not found: {}. Enter a number, a path, 'b' for back, 'q' to quit, or Enter to refresh.", typed_path);
+        return Ok(FileSelectionAction::ContinueBrowsing);
+    };
+    
+    if canonical_target_path.is_dir() {
+        println!("Entering directory: {}", canonical_target_path.display());
+        *current_directory = canonical_target_path;
+        return Ok(FileSelectionAction::ContinueBrowsing);
+    }
+    
+    select_file_by_path(&canonical_target_path)
+}
+
+/// Selects a file named by path (typed, or from the recent files)
+/// 
+/// # Arguments
+/// * `absolute_file_path` - Absolute path of an existing file
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<FileSelectionAction>` - The file, selected for preview
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file size cannot be read
+fn select_file_by_path(absolute_file_path: &Path) -> RowsAndColumnsResult<FileSelectionAction> {
+    let file_size_bytes = fs::metadata(absolute_file_path)
+        .map(|file_metadata| file_metadata.len())
+        .map_err(|io_error| {
+            create_file_system_error(&format!("Failed to read file size: {}", absolute_file_path.display()), io_error)
+        })?;
+    let selected_file_item = DirectoryItem {
+        item_name: absolute_file_path.file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_else(|| absolute_file_path.display().to_string()),
+        absolute_path: absolute_file_path.to_path_buf(),
+        is_directory: false,
+        file_size_bytes: Some(file_size_bytes),
+        appears_to_be_csv: true,
+    };
+    println!("Selected file: {}", selected_file_item.item_name);
+    Ok(FileSelectionAction::FileSelected(create_csv_file_information(&selected_file_item)?))
+}
+
 /// Creates CsvFileInformation from a DirectoryItem
 /// 
 /// This function validates the selected file and creates a comprehensive
@@ -450,10 +894,7 @@ fn create_csv_file_information(directory_item: &DirectoryItem) -> RowsAndColumns
 /// # Returns
 /// * `bool` - True if file appears readable, false otherwise
 fn test_file_readability(file_path: &Path) -> bool {
-    match fs::File::open(file_path) {
-        Ok(_) => true,
-        Err(_) => false,
-    }
+    fs::File::open(file_path).is_ok()
 }
 
 #[cfg(test)]
@@ -499,4 +940,102 @@ mod tests {
         assert!(test_item.appears_to_be_csv);
         assert_eq!(test_item.file_size_bytes, Some(2048));
     }
+    
+    /// Creates browser state for a test directory, with its history saved next to it
+    fn create_test_browser_state(test_directory: &Path) -> FileBrowserState {
+        FileBrowserState {
+            current_directory: test_directory.to_path_buf(),
+            show_hidden_files: false,
+            file_name_filter: None,
+            file_selection_history: FileSelectionHistory::default(),
+            history_file_path: Some(test_directory.join("test_history.toml")),
+        }
+    }
+    
+    #[test]
+    fn test_typed_paths_navigate_and_select() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_browser_{}", std::process::id()));
+        fs::create_dir_all(test_directory.join("exports")).expect("temp dir should be created");
+        fs::write(test_directory.join("exports").join("cats.csv"), "name,age\nTom,3\n").expect("test CSV should be writable");
+        let canonical_test_directory = test_directory.canonicalize().expect("temp dir should resolve");
+        let mut browser_state = create_test_browser_state(&canonical_test_directory);
+        
+        // A relative directory path moves the browser there
+        let directory_action = process_user_file_selection_input("exports", &[], &mut browser_state)
+            .expect("typed directory should be handled");
+        assert!(matches!(directory_action, FileSelectionAction::ContinueBrowsing));
+        assert_eq!(browser_state.current_directory, canonical_test_directory.join("exports"));
+        
+        // A file path selects the file without a number
+        let file_action = process_user_file_selection_input("cats.csv", &[], &mut browser_state)
+            .expect("typed file should be handled");
+        match file_action {
+            FileSelectionAction::FileSelected(csv_file_info) => {
+                assert_eq!(csv_file_info.filename_only, "cats.csv");
+                assert_eq!(csv_file_info.file_size_bytes, 15);
+                assert!(csv_file_info.is_readable);
+            }
+            other_action => panic!("expected a selected file, got {:?}", other_action),
+        }
+        
+        // A missing path keeps browsing in the same place
+        let missing_action = process_user_file_selection_input("missing.csv", &[], &mut browser_state)
+            .expect("missing path should be reported, not fail");
+        assert!(matches!(missing_action, FileSelectionAction::ContinueBrowsing));
+        assert_eq!(browser_state.current_directory, canonical_test_directory.join("exports"));
+        
+        let _ = fs::remove_dir_all(test_directory);
+    }
+    
+    #[test]
+    fn test_bookmarks_recent_files_filter_and_dot_files() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_browser_history_{}", std::process::id()));
+        fs::create_dir_all(test_directory.join("exports")).expect("temp dir should be created");
+        fs::write(test_directory.join("sales.csv"), "a\n1\n").expect("test CSV should be writable");
+        fs::write(test_directory.join("costs.csv"), "a\n1\n").expect("test CSV should be writable");
+        fs::write(test_directory.join(".hidden.csv"), "a\n1\n").expect("test CSV should be writable");
+        let canonical_test_directory = test_directory.canonicalize().expect("temp dir should resolve");
+        let mut browser_state = create_test_browser_state(&canonical_test_directory);
+        let run_input = |user_input: &str, browser_state: &mut FileBrowserState| {
+            process_user_file_selection_input(user_input, &[], browser_state).expect("command should be handled")
+        };
+        let listed_names = |browser_state: &FileBrowserState| -> Vec<String> {
+            scan_directory_for_navigation(
+                &browser_state.current_directory,
+                browser_state.show_hidden_files,
+                browser_state.file_name_filter.as_deref(),
+            )
+            .expect("scan should succeed")
+            .into_iter()
+            .map(|directory_item| directory_item.item_name)
+            .collect()
+        };
+        
+        // Bookmark the directory, leave it, come back by name; the bookmark is saved
+        run_input("mark home", &mut browser_state);
+        run_input("exports", &mut browser_state);
+        run_input("g home", &mut browser_state);
+        assert_eq!(browser_state.current_directory, canonical_test_directory);
+        let saved_history = FileSelectionHistory::load(&canonical_test_directory.join("test_history.toml"))
+            .expect("saved history should load");
+        assert_eq!(saved_history.find_bookmark("home"), Some(canonical_test_directory.as_path()));
+        
+        // Filter file names (directories stay listed), then show dot files
+        assert_eq!(listed_names(&browser_state), vec!["exports", "costs.csv", "sales.csv", "test_history.toml"]);
+        run_input("f SAL", &mut browser_state);
+        assert_eq!(listed_names(&browser_state), vec!["exports", "sales.csv"]);
+        run_input("f", &mut browser_state);
+        run_input("h", &mut browser_state);
+        assert!(listed_names(&browser_state).contains(&".hidden.csv".to_string()));
+        
+        // Recent files open by number
+        browser_state.file_selection_history.record_recent_file(&canonical_test_directory.join("costs.csv"));
+        match run_input("r 1", &mut browser_state) {
+            FileSelectionAction::FileSelected(csv_file_info) => assert_eq!(csv_file_info.filename_only, "costs.csv"),
+            other_action => panic!("expected a selected file, got {:?}", other_action),
+        }
+        assert!(matches!(run_input("r 2", &mut browser_state), FileSelectionAction::ContinueBrowsing));
+        
+        let _ = fs::remove_dir_all(test_directory);
+    }
 }
@@ -0,0 +1,1032 @@
+// src/filter_expression_module.rs
+
+/// Row filter expressions for rows_and_columns ("age >= 3 and breed != \"Persian\"")
+///
+/// A filter expression selects a subset of rows. It is parsed once, checked
+/// against the column names and types from the CSV analysis (and so from the
+/// `.csv_metadata.toml`), and then evaluated row by row while streaming.
+///
+/// # Core Responsibilities
+/// - Tokenize and parse the expression, reporting errors with a caret position
+/// - Resolve column names and type-check every comparison against the column type
+/// - Evaluate the compiled filter against one row of field values
+/// - Stream the matching rows into a new CSV file with its own metadata, which
+///   is then analyzed like any other input (statistics, charts, export)
+///
+/// # Expression Syntax
+/// - Comparisons: `=` (or `==`), `!=` (or `<>`), `<`, `<=`, `>`, `>=`
+/// - Boolean logic: `and`, `or`, `not`, parentheses; `and` binds tighter than `or`
+/// - Membership: `breed in ("Persian", "Siamese")`, `age not in (1, 2)`
+/// - Missing values: `color is null`, `color is not null`
+/// - Text: `name contains "ix"`, `name starts_with "Wh"`, `breed matches "^S.*e$"`
+/// - Column names are bare words (`favorite_food`) or backquoted (`` `first name` ``);
+///   keywords are case-insensitive, text literals use double or single quotes
+///
+/// # Design Philosophy
+/// - Typed: numeric columns compare numerically against number literals, text
+///   columns compare against quoted literals, boolean columns against
+///   `true`/`false`; mismatches are reported before any row is read
+/// - A comparison with an empty or unreadable cell is false, so `age < 3`
+///   never selects rows without an age; `is null` finds the empty ones
+/// - Streaming: only the current row is held in memory
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+// Import CSV structures, the shared row streaming loop and the export writer
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvRowStreamControl,
+    StreamingCsvExportWriter,
+    stream_csv_data_rows,
+};
+
+// Import the regular expression matcher used by `matches`
+use super::regex_pattern_module::{CompiledRegexPattern, compile_regex_pattern};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsError,
+    RowsAndColumnsResult,
+    create_filter_expression_error,
+};
+
+/// Keywords of the filter language (matched case-insensitively)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterKeyword {
+    And,
+    Or,
+    Not,
+    In,
+    Is,
+    Null,
+    Contains,
+    StartsWith,
+    Matches,
+    True,
+    False,
+}
+
+impl FilterKeyword {
+    /// Looks up a bare word as a keyword
+    fn from_word(word_text: &str) -> Option<FilterKeyword> {
+        match word_text.to_ascii_lowercase().as_str() {
+            "and" => Some(FilterKeyword::And),
+            "or" => Some(FilterKeyword::Or),
+            "not" => Some(FilterKeyword::Not),
+            "in" => Some(FilterKeyword::In),
+            "is" => Some(FilterKeyword::Is),
+            "null" => Some(FilterKeyword::Null),
+            "contains" => Some(FilterKeyword::Contains),
+            "starts_with" => Some(FilterKeyword::StartsWith),
+            "matches" => Some(FilterKeyword::Matches),
+            "true" => Some(FilterKeyword::True),
+            "false" => Some(FilterKeyword::False),
+            _ => None,
+        }
+    }
+
+    /// Returns the keyword as written in expressions
+    fn as_keyword_text(&self) -> &'static str {
+        match self {
+            FilterKeyword::And => "and",
+            FilterKeyword::Or => "or",
+            FilterKeyword::Not => "not",
+            FilterKeyword::In => "in",
+            FilterKeyword::Is => "is",
+            FilterKeyword::Null => "null",
+            FilterKeyword::Contains => "contains",
+            FilterKeyword::StartsWith => "starts_with",
+            FilterKeyword::Matches => "matches",
+            FilterKeyword::True => "true",
+            FilterKeyword::False => "false",
+        }
+    }
+}
+
+/// Comparison operators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterComparisonOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl FilterComparisonOperator {
+    /// Checks whether an ordering between cell and literal satisfies the operator
+    fn is_satisfied_by(&self, cell_to_literal_ordering: Ordering) -> bool {
+        match self {
+            FilterComparisonOperator::Equal => cell_to_literal_ordering == Ordering::Equal,
+            FilterComparisonOperator::NotEqual => cell_to_literal_ordering != Ordering::Equal,
+            FilterComparisonOperator::Less => cell_to_literal_ordering == Ordering::Less,
+            FilterComparisonOperator::LessOrEqual => cell_to_literal_ordering != Ordering::Greater,
+            FilterComparisonOperator::Greater => cell_to_literal_ordering == Ordering::Greater,
+            FilterComparisonOperator::GreaterOrEqual => cell_to_literal_ordering != Ordering::Less,
+        }
+    }
+}
+
+/// What a token is
+#[derive(Debug, Clone, PartialEq)]
+enum FilterTokenKind {
+    /// A bare or backquoted column name
+    ColumnName(String),
+    /// A number such as `3`, `-1.5` or `2e3`
+    NumberLiteral {
+        number_value: f64,
+        /// The exact value when written as an integer, for Integer columns
+        exact_integer_value: Option<i64>,
+    },
+    /// A quoted text literal, with escapes resolved
+    TextLiteral(String),
+    /// A language keyword
+    Keyword(FilterKeyword),
+    /// A comparison operator
+    Comparison(FilterComparisonOperator),
+    OpenParenthesis,
+    CloseParenthesis,
+    Comma,
+    EndOfExpression,
+}
+
+/// One token with the character position where it starts
+#[derive(Debug, Clone)]
+struct FilterToken {
+    token_kind: FilterTokenKind,
+    character_position: usize,
+}
+
+/// A literal value, typed to match the column it is compared with
+#[derive(Debug, Clone, PartialEq)]
+enum TypedFilterValue {
+    /// Integer column values and integer literals, compared exactly
+    Integer(i64),
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+}
+
+/// A check on a single column
+#[derive(Debug, Clone)]
+enum ColumnPredicate {
+    /// `column <op> literal`
+    Comparison {
+        comparison_operator: FilterComparisonOperator,
+        comparison_value: TypedFilterValue,
+    },
+    /// `column [not] in (literal, ...)`
+    Membership {
+        member_values: Vec<TypedFilterValue>,
+        is_negated: bool,
+    },
+    /// `column is [not] null`
+    IsNull { is_negated: bool },
+    /// `column contains "text"`
+    Contains(String),
+    /// `column starts_with "text"`
+    StartsWith(String),
+    /// `column matches "regex"`
+    Matches(CompiledRegexPattern),
+}
+
+/// A compiled, type-checked expression tree
+#[derive(Debug, Clone)]
+enum RowFilterNode {
+    Or(Box<RowFilterNode>, Box<RowFilterNode>),
+    And(Box<RowFilterNode>, Box<RowFilterNode>),
+    Not(Box<RowFilterNode>),
+    Predicate {
+        column_index: usize,
+        column_data_type: CsvColumnDataType,
+        column_predicate: ColumnPredicate,
+    },
+}
+
+/// A filter expression ready to be evaluated against rows
+#[derive(Debug, Clone)]
+pub struct CompiledRowFilter {
+    /// The expression as the user typed it
+    pub filter_expression_text: String,
+
+    /// Root of the compiled expression tree
+    root_filter_node: RowFilterNode,
+}
+
+/// Outcome of streaming a CSV file through a filter
+#[derive(Debug, Clone)]
+pub struct RowFilterSummary {
+    /// Path of the CSV file holding the kept rows
+    pub filtered_csv_file_path: PathBuf,
+
+    /// Number of data rows read from the source
+    pub scanned_row_count: usize,
+
+    /// Number of data rows that matched the filter
+    pub kept_row_count: usize,
+}
+
+impl CompiledRowFilter {
+    /// Evaluates the filter against one row
+    ///
+    /// # Arguments
+    /// * `field_values` - The row's trimmed field values, in column order
+    ///
+    /// # Returns
+    /// * `bool` - True when the row is selected
+    pub fn matches_row(&self, field_values: &[String]) -> bool {
+        evaluate_filter_node(&self.root_filter_node, field_values)
+    }
+}
+
+/// Parses and type-checks a filter expression against the analyzed columns
+///
+/// # Arguments
+/// * `filter_expression_text` - The expression, e.g. `age >= 3 and breed != "Persian"`
+/// * `csv_analysis_results` - Column names and types of the file being filtered
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CompiledRowFilter>` - The compiled filter
+///
+/// # Errors
+/// * `FilterExpressionError` - Syntax errors, unknown columns, literals of the
+///   wrong type, or invalid regular expressions, with the character position
+pub fn compile_row_filter_expression(
+    filter_expression_text: &str,
+    csv_analysis_results: &CsvAnalysisResults,
+) -> RowsAndColumnsResult<CompiledRowFilter> {
+    // Step 1: Tokenize
+    let filter_tokens = tokenize_filter_expression(filter_expression_text)?;
+
+    // Step 2: Parse and bind columns in one recursive-descent pass
+    let mut filter_parser = FilterExpressionParser {
+        filter_expression_text,
+        filter_tokens,
+        current_token_index: 0,
+        csv_analysis_results,
+    };
+
+    if filter_parser.peek_token().token_kind == FilterTokenKind::EndOfExpression {
+        return Err(create_filter_expression_error(filter_expression_text, 0, "empty filter expression"));
+    }
+
+    let root_filter_node = filter_parser.parse_or_expression()?;
+
+    // Step 3: Everything must have been consumed
+    let trailing_token = filter_parser.peek_token().clone();
+    if trailing_token.token_kind != FilterTokenKind::EndOfExpression {
+        return Err(filter_parser.error_at(
+            &trailing_token,
+            &format!("unexpected {} after a complete expression (missing 'and' or 'or'?)", describe_filter_token(&trailing_token.token_kind))
+        ));
+    }
+
+    Ok(CompiledRowFilter {
+        filter_expression_text: filter_expression_text.to_string(),
+        root_filter_node,
+    })
+}
+
+/// Streams the rows of a CSV file that match a filter into a new CSV file
+///
+/// The output has a header row with the source column names, and a metadata
+/// file carrying the source column types, so analyzing it keeps those types.
+///
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the source CSV file
+/// * `compiled_row_filter` - The filter to apply
+/// * `output_csv_path` - Where to write the kept rows
+///
+/// # Returns
+/// * `RowsAndColumnsResult<RowFilterSummary>` - Output path and row counts
+///
+/// # Errors
+/// * `FileSystemError` - The output file could not be created or written
+/// * `CsvProcessingError` - The source file could not be read
+pub fn write_filtered_csv_file(
+    csv_analysis_results: &CsvAnalysisResults,
+    compiled_row_filter: &CompiledRowFilter,
+    output_csv_path: &Path,
+) -> RowsAndColumnsResult<RowFilterSummary> {
+    // Step 1: Header row and source column types
+    let column_names: Vec<String> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.column_name.clone())
+        .collect();
+    let column_data_types: Vec<CsvColumnDataType> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.detected_data_type.clone())
+        .collect();
+    let mut export_writer = StreamingCsvExportWriter::create(output_csv_path, &column_names, &column_data_types)?;
+
+    // Step 2: Stream the source, writing each kept row
+    let scanned_row_count = stream_csv_data_rows(
+        &csv_analysis_results.csv_file_path,
+        csv_analysis_results.has_header_row,
+        |_row_index, field_values| {
+            if compiled_row_filter.matches_row(field_values) {
+                export_writer.write_row(field_values)?;
+            }
+            Ok(CsvRowStreamControl::ContinueReading)
+        },
+    )?;
+
+    // Step 3: Metadata with the source column types
+    let (kept_row_count, _metadata_file_path) = export_writer.finish()?;
+
+    Ok(RowFilterSummary {
+        filtered_csv_file_path: output_csv_path.to_path_buf(),
+        scanned_row_count,
+        kept_row_count,
+    })
+}
+
+/// Splits an expression into tokens
+///
+/// # Arguments
+/// * `filter_expression_text` - The expression to tokenize
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<FilterToken>>` - Tokens ending with `EndOfExpression`
+///
+/// # Errors
+/// * `FilterExpressionError` - Unterminated quotes, malformed numbers or
+///   characters that cannot start a token
+fn tokenize_filter_expression(filter_expression_text: &str) -> RowsAndColumnsResult<Vec<FilterToken>> {
+    let expression_characters: Vec<char> = filter_expression_text.chars().collect();
+    let mut filter_tokens = Vec::new();
+    let mut current_position = 0;
+
+    while current_position < expression_characters.len() {
+        let token_start = current_position;
+        let current_character = expression_characters[current_position];
+        let next_character = expression_characters.get(current_position + 1).copied();
+
+        let token_kind = match current_character {
+            _ if current_character.is_whitespace() => {
+                current_position += 1;
+                continue;
+            }
+            '(' => {
+                current_position += 1;
+                FilterTokenKind::OpenParenthesis
+            }
+            ')' => {
+                current_position += 1;
+                FilterTokenKind::CloseParenthesis
+            }
+            ',' => {
+                current_position += 1;
+                FilterTokenKind::Comma
+            }
+            '=' | '!' | '<' | '>' => {
+                let (comparison_operator, operator_length) = match (current_character, next_character) {
+                    ('=', Some('=')) => (FilterComparisonOperator::Equal, 2),
+                    ('=', _) => (FilterComparisonOperator::Equal, 1),
+                    ('!', Some('=')) => (FilterComparisonOperator::NotEqual, 2),
+                    ('<', Some('>')) => (FilterComparisonOperator::NotEqual, 2),
+                    ('<', Some('=')) => (FilterComparisonOperator::LessOrEqual, 2),
+                    ('<', _) => (FilterComparisonOperator::Less, 1),
+                    ('>', Some('=')) => (FilterComparisonOperator::GreaterOrEqual, 2),
+                    ('>', _) => (FilterComparisonOperator::Greater, 1),
+                    _ => {
+                        return Err(create_filter_expression_error(
+                            filter_expression_text,
+                            token_start,
+                            "'!' must be followed by '=' (use 'not' for negation)"
+                        ));
+                    }
+                };
+                current_position += operator_length;
+                FilterTokenKind::Comparison(comparison_operator)
+            }
+            '"' | '\'' | '`' => {
+                // Quoted text literal or backquoted column name; a backslash
+                // escapes the quote character and itself, and is otherwise kept
+                // so regex escapes such as \d pass through unchanged
+                let closing_quote = current_character;
+                let mut quoted_content = String::new();
+                current_position += 1;
+                loop {
+                    match expression_characters.get(current_position) {
+                        None => {
+                            return Err(create_filter_expression_error(
+                                filter_expression_text,
+                                token_start,
+                                &format!("unterminated {} quote", closing_quote)
+                            ));
+                        }
+                        Some(&'\\') if matches!(expression_characters.get(current_position + 1), Some(&escaped) if escaped == closing_quote || escaped == '\\') => {
+                            quoted_content.push(expression_characters[current_position + 1]);
+                            current_position += 2;
+                        }
+                        Some(&quoted_character) if quoted_character == closing_quote => {
+                            current_position += 1;
+                            break;
+                        }
+                        Some(&quoted_character) => {
+                            quoted_content.push(quoted_character);
+                            current_position += 1;
+                        }
+                    }
+                }
+                if closing_quote == '`' {
+                    FilterTokenKind::ColumnName(quoted_content)
+                } else {
+                    FilterTokenKind::TextLiteral(quoted_content)
+                }
+            }
+            _ if current_character.is_ascii_digit()
+                || (matches!(current_character, '-' | '+' | '.')
+                    && next_character.is_some_and(|character| character.is_ascii_digit() || character == '.')) => {
+                current_position += 1;
+                while let Some(&number_character) = expression_characters.get(current_position) {
+                    let previous_character = expression_characters[current_position - 1];
+                    let is_exponent_sign = matches!(number_character, '-' | '+') && matches!(previous_character, 'e' | 'E');
+                    if number_character.is_ascii_alphanumeric() || number_character == '.' || is_exponent_sign {
+                        current_position += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let number_text: String = expression_characters[token_start..current_position].iter().collect();
+                let number_value = number_text.parse::<f64>().ok().filter(|value| value.is_finite()).ok_or_else(|| {
+                    create_filter_expression_error(filter_expression_text, token_start, &format!("'{}' is not a valid number", number_text))
+                })?;
+                FilterTokenKind::NumberLiteral { number_value, exact_integer_value: number_text.parse::<i64>().ok() }
+            }
+            _ if current_character.is_alphabetic() || current_character == '_' => {
+                while expression_characters
+                    .get(current_position)
+                    .is_some_and(|&word_character| word_character.is_alphanumeric() || word_character == '_')
+                {
+                    current_position += 1;
+                }
+                let word_text: String = expression_characters[token_start..current_position].iter().collect();
+                match FilterKeyword::from_word(&word_text) {
+                    Some(filter_keyword) => FilterTokenKind::Keyword(filter_keyword),
+                    None => FilterTokenKind::ColumnName(word_text),
+                }
+            }
+            _ => {
+                return Err(create_filter_expression_error(
+                    filter_expression_text,
+                    token_start,
+                    &format!("unexpected character '{}'", current_character)
+                ));
+            }
+        };
+
+        filter_tokens.push(FilterToken { token_kind, character_position: token_start });
+    }
+
+    filter_tokens.push(FilterToken {
+        token_kind: FilterTokenKind::EndOfExpression,
+        character_position: expression_characters.len(),
+    });
+
+    Ok(filter_tokens)
+}
+
+/// Describes a token for error messages
+fn describe_filter_token(token_kind: &FilterTokenKind) -> String {
+    match token_kind {
+        FilterTokenKind::ColumnName(column_name) => format!("column '{}'", column_name),
+        FilterTokenKind::NumberLiteral { number_value, .. } => format!("number {}", number_value),
+        FilterTokenKind::TextLiteral(literal_text) => format!("text \"{}\"", literal_text),
+        FilterTokenKind::Keyword(filter_keyword) => format!("'{}'", filter_keyword.as_keyword_text()),
+        FilterTokenKind::Comparison(_) => "comparison operator".to_string(),
+        FilterTokenKind::OpenParenthesis => "'('".to_string(),
+        FilterTokenKind::CloseParenthesis => "')'".to_string(),
+        FilterTokenKind::Comma => "','".to_string(),
+        FilterTokenKind::EndOfExpression => "end of expression".to_string(),
+    }
+}
+
+/// Describes a column type for error messages
+fn describe_column_data_type(column_data_type: &CsvColumnDataType) -> &'static str {
+    match column_data_type {
+        CsvColumnDataType::Boolean => "boolean",
+        CsvColumnDataType::Integer => "integer",
+        CsvColumnDataType::Float => "float",
+        CsvColumnDataType::String => "text",
+    }
+}
+
+/// Recursive-descent parser that also binds columns and checks types
+struct FilterExpressionParser<'a> {
+    /// The expression as written, for error messages
+    filter_expression_text: &'a str,
+    /// Tokens ending with `EndOfExpression`
+    filter_tokens: Vec<FilterToken>,
+    /// Index of the next token to read
+    current_token_index: usize,
+    /// Column names and types to bind against
+    csv_analysis_results: &'a CsvAnalysisResults,
+}
+
+impl FilterExpressionParser<'_> {
+    /// Builds a positioned error at a token
+    fn error_at(&self, filter_token: &FilterToken, error_description: &str) -> RowsAndColumnsError {
+        create_filter_expression_error(self.filter_expression_text, filter_token.character_position, error_description)
+    }
+
+    /// Returns the next token without consuming it
+    fn peek_token(&self) -> &FilterToken {
+        &self.filter_tokens[self.current_token_index]
+    }
+
+    /// Consumes and returns the next token; `EndOfExpression` is never consumed
+    fn next_token(&mut self) -> FilterToken {
+        let filter_token = self.filter_tokens[self.current_token_index].clone();
+        if filter_token.token_kind != FilterTokenKind::EndOfExpression {
+            self.current_token_index += 1;
+        }
+        filter_token
+    }
+
+    /// Consumes the next token if it is the given keyword
+    fn accept_keyword(&mut self, expected_keyword: FilterKeyword) -> bool {
+        if self.peek_token().token_kind == FilterTokenKind::Keyword(expected_keyword) {
+            self.current_token_index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `or_expression := and_expression ("or" and_expression)*`
+    fn parse_or_expression(&mut self) -> RowsAndColumnsResult<RowFilterNode> {
+        let mut combined_node = self.parse_and_expression()?;
+        while self.accept_keyword(FilterKeyword::Or) {
+            let right_node = self.parse_and_expression()?;
+            combined_node = RowFilterNode::Or(Box::new(combined_node), Box::new(right_node));
+        }
+        Ok(combined_node)
+    }
+
+    /// `and_expression := not_expression ("and" not_expression)*`
+    fn parse_and_expression(&mut self) -> RowsAndColumnsResult<RowFilterNode> {
+        let mut combined_node = self.parse_not_expression()?;
+        while self.accept_keyword(FilterKeyword::And) {
+            let right_node = self.parse_not_expression()?;
+            combined_node = RowFilterNode::And(Box::new(combined_node), Box::new(right_node));
+        }
+        Ok(combined_node)
+    }
+
+    /// `not_expression := "not" not_expression | "(" or_expression ")" | predicate`
+    fn parse_not_expression(&mut self) -> RowsAndColumnsResult<RowFilterNode> {
+        if self.accept_keyword(FilterKeyword::Not) {
+            return Ok(RowFilterNode::Not(Box::new(self.parse_not_expression()?)));
+        }
+
+        let leading_token = self.next_token();
+        match &leading_token.token_kind {
+            FilterTokenKind::OpenParenthesis => {
+                let inner_node = self.parse_or_expression()?;
+                let closing_token = self.next_token();
+                if closing_token.token_kind != FilterTokenKind::CloseParenthesis {
+                    return Err(self.error_at(
+                        &closing_token,
+                        &format!("expected ')' to close the '(' at position {}", leading_token.character_position + 1)
+                    ));
+                }
+                Ok(inner_node)
+            }
+            FilterTokenKind::ColumnName(column_name) => self.parse_column_predicate(&leading_token, column_name),
+            other_kind => Err(self.error_at(
+                &leading_token,
+                &format!("expected a column name, 'not' or '(' but found {}", describe_filter_token(other_kind))
+            )),
+        }
+    }
+
+    /// Resolves a column name: exact match first, then a unique case-insensitive one
+    fn resolve_column(&self, column_token: &FilterToken, column_name: &str) -> RowsAndColumnsResult<(usize, CsvColumnDataType)> {
+        let column_information_list = &self.csv_analysis_results.column_information_list;
+
+        let exact_match = column_information_list.iter().position(|column_info| column_info.column_name == column_name);
+        let case_insensitive_matches: Vec<usize> = column_information_list
+            .iter()
+            .enumerate()
+            .filter(|(_, column_info)| column_info.column_name.eq_ignore_ascii_case(column_name))
+            .map(|(column_position, _)| column_position)
+            .collect();
+
+        let column_position = match (exact_match, case_insensitive_matches.as_slice()) {
+            (Some(exact_position), _) => exact_position,
+            (None, [only_position]) => *only_position,
+            _ => {
+                let available_column_names: Vec<&str> = column_information_list
+                    .iter()
+                    .map(|column_info| column_info.column_name.as_str())
+                    .collect();
+                return Err(self.error_at(
+                    column_token,
+                    &format!("unknown column '{}' (columns: {})", column_name, available_column_names.join(", "))
+                ));
+            }
+        };
+
+        let column_info = &column_information_list[column_position];
+        Ok((column_info.column_index, column_info.detected_data_type.clone()))
+    }
+
+    /// Parses what follows a column name and type-checks it
+    fn parse_column_predicate(&mut self, column_token: &FilterToken, column_name: &str) -> RowsAndColumnsResult<RowFilterNode> {
+        let (column_index, column_data_type) = self.resolve_column(column_token, column_name)?;
+        let operator_token = self.next_token();
+
+        let column_predicate = match operator_token.token_kind {
+            FilterTokenKind::Comparison(comparison_operator) => {
+                let is_ordering = !matches!(comparison_operator, FilterComparisonOperator::Equal | FilterComparisonOperator::NotEqual);
+                if is_ordering && column_data_type == CsvColumnDataType::Boolean {
+                    return Err(self.error_at(
+                        &operator_token,
+                        &format!("column '{}' is boolean; only '=' and '!=' apply", column_name)
+                    ));
+                }
+                ColumnPredicate::Comparison {
+                    comparison_operator,
+                    comparison_value: self.parse_typed_literal(column_name, &column_data_type)?,
+                }
+            }
+            FilterTokenKind::Keyword(FilterKeyword::In) => ColumnPredicate::Membership {
+                member_values: self.parse_literal_list(column_name, &column_data_type)?,
+                is_negated: false,
+            },
+            FilterTokenKind::Keyword(FilterKeyword::Not) => {
+                if !self.accept_keyword(FilterKeyword::In) {
+                    let misplaced_token = self.peek_token().clone();
+                    return Err(self.error_at(
+                        &misplaced_token,
+                        "expected 'in' after 'not' (write 'not (column ...)' to negate other tests)"
+                    ));
+                }
+                ColumnPredicate::Membership {
+                    member_values: self.parse_literal_list(column_name, &column_data_type)?,
+                    is_negated: true,
+                }
+            }
+            FilterTokenKind::Keyword(FilterKeyword::Is) => {
+                let is_negated = self.accept_keyword(FilterKeyword::Not);
+                if !self.accept_keyword(FilterKeyword::Null) {
+                    let misplaced_token = self.peek_token().clone();
+                    return Err(self.error_at(&misplaced_token, "expected 'null' after 'is' or 'is not'"));
+                }
+                ColumnPredicate::IsNull { is_negated }
+            }
+            FilterTokenKind::Keyword(text_keyword @ (FilterKeyword::Contains | FilterKeyword::StartsWith | FilterKeyword::Matches)) => {
+                if column_data_type != CsvColumnDataType::String {
+                    return Err(self.error_at(
+                        &operator_token,
+                        &format!(
+                            "'{}' needs a text column, but '{}' is {}",
+                            text_keyword.as_keyword_text(),
+                            column_name,
+                            describe_column_data_type(&column_data_type)
+                        )
+                    ));
+                }
+                let literal_token = self.next_token();
+                let FilterTokenKind::TextLiteral(literal_text) = literal_token.token_kind.clone() else {
+                    return Err(self.error_at(
+                        &literal_token,
+                        &format!("'{}' needs a quoted text, found {}", text_keyword.as_keyword_text(), describe_filter_token(&literal_token.token_kind))
+                    ));
+                };
+                match text_keyword {
+                    FilterKeyword::Contains => ColumnPredicate::Contains(literal_text),
+                    FilterKeyword::StartsWith => ColumnPredicate::StartsWith(literal_text),
+                    _ => ColumnPredicate::Matches(self.compile_regex_literal(&literal_token, &literal_text)?),
+                }
+            }
+            ref other_kind => {
+                return Err(self.error_at(
+                    &operator_token,
+                    &format!(
+                        "expected a comparison, 'in', 'is', 'contains', 'starts_with' or 'matches' after column '{}' but found {}",
+                        column_name,
+                        describe_filter_token(other_kind)
+                    )
+                ));
+            }
+        };
+
+        Ok(RowFilterNode::Predicate { column_index, column_data_type, column_predicate })
+    }
+
+    /// Compiles a `matches` pattern, moving regex errors into expression positions
+    fn compile_regex_literal(&self, literal_token: &FilterToken, pattern_text: &str) -> RowsAndColumnsResult<CompiledRegexPattern> {
+        compile_regex_pattern(pattern_text).map_err(|regex_error| match regex_error {
+            RowsAndColumnsError::FilterExpressionError { error_character_position, filter_error_description, .. } => {
+                // +1 skips the opening quote of the literal
+                create_filter_expression_error(
+                    self.filter_expression_text,
+                    literal_token.character_position + 1 + error_character_position,
+                    &filter_error_description
+                )
+            }
+            other_error => other_error,
+        })
+    }
+
+    /// Parses `( literal, literal, ... )` for `in`
+    fn parse_literal_list(&mut self, column_name: &str, column_data_type: &CsvColumnDataType) -> RowsAndColumnsResult<Vec<TypedFilterValue>> {
+        let open_token = self.next_token();
+        if open_token.token_kind != FilterTokenKind::OpenParenthesis {
+            return Err(self.error_at(&open_token, "expected '(' to start the 'in' list"));
+        }
+
+        let mut member_values = vec![self.parse_typed_literal(column_name, column_data_type)?];
+        loop {
+            let separator_token = self.next_token();
+            match separator_token.token_kind {
+                FilterTokenKind::Comma => member_values.push(self.parse_typed_literal(column_name, column_data_type)?),
+                FilterTokenKind::CloseParenthesis => break,
+                ref other_kind => {
+                    return Err(self.error_at(
+                        &separator_token,
+                        &format!("expected ',' or ')' in the 'in' list but found {}", describe_filter_token(other_kind))
+                    ));
+                }
+            }
+        }
+
+        Ok(member_values)
+    }
+
+    /// Parses one literal and checks it fits the column type
+    fn parse_typed_literal(&mut self, column_name: &str, column_data_type: &CsvColumnDataType) -> RowsAndColumnsResult<TypedFilterValue> {
+        let literal_token = self.next_token();
+
+        let typed_value = match (column_data_type, &literal_token.token_kind) {
+            (_, FilterTokenKind::Keyword(FilterKeyword::Null)) => {
+                return Err(self.error_at(&literal_token, "use 'is null' or 'is not null' to test for empty cells"));
+            }
+            (CsvColumnDataType::Integer, FilterTokenKind::NumberLiteral { exact_integer_value: Some(integer_value), .. }) => {
+                TypedFilterValue::Integer(*integer_value)
+            }
+            (CsvColumnDataType::Integer | CsvColumnDataType::Float, FilterTokenKind::NumberLiteral { number_value, .. }) => {
+                TypedFilterValue::Number(*number_value)
+            }
+            (CsvColumnDataType::String, FilterTokenKind::TextLiteral(literal_text)) => TypedFilterValue::Text(literal_text.clone()),
+            (CsvColumnDataType::Boolean, FilterTokenKind::Keyword(FilterKeyword::True)) => TypedFilterValue::Boolean(true),
+            (CsvColumnDataType::Boolean, FilterTokenKind::Keyword(FilterKeyword::False)) => TypedFilterValue::Boolean(false),
+            (_, other_kind) => {
+                let expected_literal = match column_data_type {
+                    CsvColumnDataType::Integer | CsvColumnDataType::Float => "a number",
+                    CsvColumnDataType::String => "a quoted text such as \"value\"",
+                    CsvColumnDataType::Boolean => "true or false",
+                };
+                return Err(self.error_at(
+                    &literal_token,
+                    &format!(
+                        "column '{}' is {}; expected {} but found {}",
+                        column_name,
+                        describe_column_data_type(column_data_type),
+                        expected_literal,
+                        describe_filter_token(other_kind)
+                    )
+                ));
+            }
+        };
+
+        Ok(typed_value)
+    }
+}
+
+/// Reads a cell as a value of the column's type
+///
+/// # Arguments
+/// * `cell_text` - The trimmed cell text
+/// * `column_data_type` - The column type
+///
+/// # Returns
+/// * `Option<TypedFilterValue>` - `None` for empty or unreadable cells
+fn read_typed_cell_value(cell_text: &str, column_data_type: &CsvColumnDataType) -> Option<TypedFilterValue> {
+    if cell_text.is_empty() {
+        return None;
+    }
+
+    match column_data_type {
+        CsvColumnDataType::Integer if let Ok(integer_value) = cell_text.parse::<i64>() => Some(TypedFilterValue::Integer(integer_value)),
+        CsvColumnDataType::Integer | CsvColumnDataType::Float => cell_text
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(TypedFilterValue::Number),
+        CsvColumnDataType::Boolean => match cell_text.to_lowercase().as_str() {
+            "true" | "yes" | "1" | "t" | "y" => Some(TypedFilterValue::Boolean(true)),
+            "false" | "no" | "0" | "f" | "n" => Some(TypedFilterValue::Boolean(false)),
+            _ => None,
+        },
+        CsvColumnDataType::String => Some(TypedFilterValue::Text(cell_text.to_string())),
+    }
+}
+
+/// Orders a cell value against a literal of the same type
+fn compare_typed_values(cell_value: &TypedFilterValue, literal_value: &TypedFilterValue) -> Option<Ordering> {
+    match (cell_value, literal_value) {
+        (TypedFilterValue::Integer(cell_integer), TypedFilterValue::Integer(literal_integer)) => Some(cell_integer.cmp(literal_integer)),
+        (TypedFilterValue::Integer(cell_integer), TypedFilterValue::Number(literal_number)) => {
+            Some(compare_integer_with_number(*cell_integer, *literal_number))
+        }
+        (TypedFilterValue::Number(cell_number), TypedFilterValue::Integer(literal_integer)) => {
+            Some(compare_integer_with_number(*literal_integer, *cell_number).reverse())
+        }
+        (TypedFilterValue::Number(cell_number), TypedFilterValue::Number(literal_number)) => cell_number.partial_cmp(literal_number),
+        (TypedFilterValue::Text(cell_text), TypedFilterValue::Text(literal_text)) => Some(cell_text.cmp(literal_text)),
+        (TypedFilterValue::Boolean(cell_flag), TypedFilterValue::Boolean(literal_flag)) => Some(cell_flag.cmp(literal_flag)),
+        _ => None,
+    }
+}
+
+/// Orders an integer against a finite float exactly, without rounding the integer
+///
+/// # Arguments
+/// * `integer_value` - The integer
+/// * `number_value` - The float (never NaN)
+///
+/// # Returns
+/// * `Ordering` - The order of `integer_value` relative to `number_value`
+fn compare_integer_with_number(integer_value: i64, number_value: f64) -> Ordering {
+    // i64::MAX + 1 is exactly 2^63, so every float at or past it is out of range
+    if number_value >= 9_223_372_036_854_775_808.0 {
+        return Ordering::Less;
+    }
+    if number_value < -9_223_372_036_854_775_808.0 {
+        return Ordering::Greater;
+    }
+
+    let whole_part = number_value.floor();
+    match integer_value.cmp(&(whole_part as i64)) {
+        Ordering::Equal if number_value > whole_part => Ordering::Less,
+        other_ordering => other_ordering,
+    }
+}
+
+/// Evaluates a compiled node against one row
+///
+/// # Arguments
+/// * `filter_node` - The node to evaluate
+/// * `field_values` - The row's trimmed field values
+///
+/// # Returns
+/// * `bool` - The node's truth value for this row
+fn evaluate_filter_node(filter_node: &RowFilterNode, field_values: &[String]) -> bool {
+    match filter_node {
+        RowFilterNode::Or(left_node, right_node) => {
+            evaluate_filter_node(left_node, field_values) || evaluate_filter_node(right_node, field_values)
+        }
+        RowFilterNode::And(left_node, right_node) => {
+            evaluate_filter_node(left_node, field_values) && evaluate_filter_node(right_node, field_values)
+        }
+        RowFilterNode::Not(inner_node) => !evaluate_filter_node(inner_node, field_values),
+        RowFilterNode::Predicate { column_index, column_data_type, column_predicate } => {
+            let cell_text = field_values.get(*column_index).map(String::as_str).unwrap_or("");
+
+            match column_predicate {
+                ColumnPredicate::IsNull { is_negated } => cell_text.is_empty() != *is_negated,
+                ColumnPredicate::Contains(needle_text) => !cell_text.is_empty() && cell_text.contains(needle_text.as_str()),
+                ColumnPredicate::StartsWith(prefix_text) => !cell_text.is_empty() && cell_text.starts_with(prefix_text.as_str()),
+                ColumnPredicate::Matches(regex_pattern) => !cell_text.is_empty() && regex_pattern.is_match(cell_text),
+                ColumnPredicate::Comparison { comparison_operator, comparison_value } => {
+                    read_typed_cell_value(cell_text, column_data_type)
+                        .and_then(|cell_value| compare_typed_values(&cell_value, comparison_value))
+                        .is_some_and(|ordering| comparison_operator.is_satisfied_by(ordering))
+                }
+                ColumnPredicate::Membership { member_values, is_negated } => {
+                    read_typed_cell_value(cell_text, column_data_type).is_some_and(|cell_value| {
+                        let is_member = member_values
+                            .iter()
+                            .any(|member_value| compare_typed_values(&cell_value, member_value) == Some(Ordering::Equal));
+                        is_member != *is_negated
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::determine_metadata_file_path;
+    use super::super::test_fixture_module::create_test_analysis_results;
+
+    /// Builds analysis results for a small cat table without touching the disk
+    fn create_cat_analysis_results() -> CsvAnalysisResults {
+        create_test_analysis_results(
+            Path::new("cats.csv"),
+            &[
+                ("age", CsvColumnDataType::Integer),
+                ("breed", CsvColumnDataType::String),
+                ("is_indoor", CsvColumnDataType::Boolean),
+            ],
+        )
+    }
+
+    /// Turns a literal row into owned field values
+    fn row(field_values: &[&str]) -> Vec<String> {
+        field_values.iter().map(|field_value| field_value.to_string()).collect()
+    }
+
+    /// Typed comparisons, boolean logic, membership, null tests and text operators
+    #[test]
+    fn test_filter_evaluation_is_typed() {
+        let analysis_results = create_cat_analysis_results();
+        let compile = |expression_text: &str| {
+            compile_row_filter_expression(expression_text, &analysis_results).expect("expression should compile")
+        };
+
+        let adult_non_persian = compile("age >= 3 and breed != \"Persian\"");
+        assert!(adult_non_persian.matches_row(&row(&["10", "Siamese", "yes"])));
+        assert!(!adult_non_persian.matches_row(&row(&["10", "Persian", "yes"])));
+        assert!(!adult_non_persian.matches_row(&row(&["2", "Siamese", "yes"])));
+        // Numeric, not lexical: "10" >= 3
+        assert!(compile("age > 9").matches_row(&row(&["10", "Siamese", "yes"])));
+        // Empty cells never satisfy comparisons, but are found by `is null`
+        assert!(!compile("age < 100").matches_row(&row(&["", "Siamese", "yes"])));
+        assert!(compile("age is null or not (breed in ('Persian', 'Siamese'))").matches_row(&row(&["", "Siamese", "no"])));
+        assert!(compile("breed not in ('Persian') AND is_indoor = false").matches_row(&row(&["4", "Bengal", "no"])));
+        assert!(compile("breed starts_with 'Sia' and breed contains 'mes' and breed matches '^S\\w+e$'")
+            .matches_row(&row(&["4", "Siamese", "no"])));
+    }
+
+    /// Syntax and type errors point at the offending character
+    #[test]
+    fn test_filter_errors_report_caret_position() {
+        let analysis_results = create_cat_analysis_results();
+        let error_position = |expression_text: &str| match compile_row_filter_expression(expression_text, &analysis_results) {
+            Err(RowsAndColumnsError::FilterExpressionError { error_character_position, .. }) => error_character_position,
+            other => panic!("Expected FilterExpressionError for {}, got {:?}", expression_text, other),
+        };
+
+        assert_eq!(error_position("age >= \"three\""), 7);
+        assert_eq!(error_position("breed = Persian"), 8);
+        assert_eq!(error_position("agee > 3"), 0);
+        assert_eq!(error_position("age contains \"1\""), 4);
+        assert_eq!(error_position("(age > 3"), 8);
+        assert_eq!(error_position("age > 3 breed = 'x'"), 8);
+        assert_eq!(error_position("breed matches 'ab(c'"), 17);
+        assert_eq!(error_position("breed = 'Persian"), 8);
+
+        let display_text = compile_row_filter_expression("age >= \"three\"", &analysis_results)
+            .expect_err("type mismatch should fail")
+            .to_string();
+        assert!(display_text.contains("age >= \"three\"\n         ^"), "{}", display_text);
+    }
+
+    /// Streaming a file through a filter writes the kept rows and metadata
+    #[test]
+    fn test_write_filtered_csv_file() {
+        let temporary_directory = std::env::temp_dir().join(format!("rows_and_columns_filter_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temporary_directory).expect("temp dir should be created");
+        let source_csv_path = temporary_directory.join("cats.csv");
+        std::fs::write(&source_csv_path, "age,breed,is_indoor\n1,Persian,yes\n5,Siamese,no\n7,,yes\n").expect("write source");
+
+        let mut analysis_results = create_cat_analysis_results();
+        analysis_results.csv_file_path = source_csv_path;
+        let compiled_row_filter = compile_row_filter_expression("age > 3", &analysis_results).expect("compile");
+
+        let output_csv_path = temporary_directory.join("cats_filtered.csv");
+        let filter_summary = write_filtered_csv_file(&analysis_results, &compiled_row_filter, &output_csv_path).expect("filter");
+
+        assert_eq!(filter_summary.scanned_row_count, 3);
+        assert_eq!(filter_summary.kept_row_count, 2);
+        let filtered_text = std::fs::read_to_string(&output_csv_path).expect("read output");
+        assert_eq!(filtered_text, "age,breed,is_indoor\n5,Siamese,no\n7,,yes\n");
+        let metadata_text = std::fs::read_to_string(determine_metadata_file_path(&output_csv_path).expect("metadata path"))
+            .expect("read metadata");
+        assert!(metadata_text.contains("data_type = \"integer\""));
+        assert!(metadata_text.contains("empty_values = 1"));
+
+        let _ = std::fs::remove_dir_all(&temporary_directory);
+    }
+
+    /// Integer columns compare exactly past 2^53 and still compare with fractions
+    #[test]
+    fn test_integer_comparisons_are_exact() {
+        let analysis_results = create_cat_analysis_results();
+        let compile = |expression_text: &str| {
+            compile_row_filter_expression(expression_text, &analysis_results).expect("expression should compile")
+        };
+
+        let exact_id = compile("age = 9007199254740993");
+        assert!(exact_id.matches_row(&row(&["9007199254740993", "Siamese", "yes"])));
+        assert!(!exact_id.matches_row(&row(&["9007199254740992", "Siamese", "yes"])));
+        assert!(compile("age > 9007199254740992").matches_row(&row(&["9007199254740993", "Siamese", "yes"])));
+        assert!(!compile("age in (9007199254740992, 1)").matches_row(&row(&["9007199254740993", "Siamese", "yes"])));
+
+        assert!(compile("age < 2.5").matches_row(&row(&["2", "Siamese", "yes"])));
+        assert!(!compile("age < 2.5").matches_row(&row(&["3", "Siamese", "yes"])));
+        assert!(compile("age >= -2.5").matches_row(&row(&["-2", "Siamese", "yes"])));
+        assert!(compile("age < 1e19").matches_row(&row(&["9223372036854775807", "Siamese", "yes"])));
+        assert!(!compile("age = 3.0e0").matches_row(&row(&["4", "Siamese", "yes"])));
+        assert!(compile("age = 3.0e0").matches_row(&row(&["3", "Siamese", "yes"])));
+    }
+}
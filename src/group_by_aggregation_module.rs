@@ -0,0 +1,846 @@
+// src/group_by_aggregation_module.rs
+
+/// Group-by aggregation for rows_and_columns ("mean age by breed")
+///
+/// Rows are grouped by the values of one or more columns, and for each group
+/// the chosen aggregations (count, sum, mean, min, max, median, std, distinct)
+/// are computed for the chosen numeric columns.
+///
+/// # Core Responsibilities
+/// - Stream rows from the CSV file or the directory store, one row at a time
+/// - Keep a fixed-size accumulator per group and aggregated column
+/// - Format the results as a text table
+/// - Export the results as a new CSV file with its own `.csv_metadata.toml`
+///
+/// # Design Philosophy
+/// - Bounded memory per group: count, sum, mean, std, min and max use running
+///   (Welford) updates; the median uses a reservoir sample of at most
+///   `MEDIAN_RESERVOIR_CAPACITY` values and distinct counting stops tracking
+///   new values after `DISTINCT_VALUE_TRACKING_LIMIT`
+/// - Results that come from a full reservoir or a capped distinct set are
+///   counted and reported as approximate, never silently passed off as exact
+/// - The exported CSV is an ordinary input file: it can be analyzed again
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// Import CSV structures, the row stream control and the export writer
+use super::csv_processor_module::{
+    CsvColumnDataType,
+    CsvRowStreamControl,
+    StreamingCsvExportWriter,
+};
+
+// Import the shared row source so grouped rows can come from an imported dataset
+use super::directory_store_module::DatasetRowSource;
+
+// Import the seeded generator for the median reservoirs
+use super::row_sampling_module::ReservoirSamplingGenerator;
+
+// Import the aligned text table formatter
+use super::terminal_control_module::format_aligned_text_table_lines;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_configuration_error,
+    create_statistical_analysis_error,
+};
+
+/// Largest number of values kept per group for the median
+pub const MEDIAN_RESERVOIR_CAPACITY: usize = 4_096;
+
+/// Largest number of distinct values tracked per group for `distinct`
+pub const DISTINCT_VALUE_TRACKING_LIMIT: usize = 1_000;
+
+/// Largest number of groups before the aggregation is refused
+pub const MAXIMUM_GROUP_COUNT: usize = 100_000;
+
+/// Name of the always-present column holding the number of rows per group
+const GROUP_ROW_COUNT_COLUMN_NAME: &str = "row_count";
+
+/// Label shown in the table for an empty group value
+const EMPTY_GROUP_VALUE_LABEL: &str = "(empty)";
+
+/// Widest column printed in the text table
+const MAXIMUM_TABLE_COLUMN_WIDTH: usize = 24;
+
+/// Seed for the reservoir sampling generator (results are repeatable)
+const RESERVOIR_SAMPLING_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// An aggregation that can be computed for each group
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggregationFunction {
+    /// Number of non-empty numeric values
+    Count,
+
+    /// Sum of the values
+    Sum,
+
+    /// Arithmetic mean
+    Mean,
+
+    /// Smallest value
+    Minimum,
+
+    /// Largest value
+    Maximum,
+
+    /// Median (exact up to `MEDIAN_RESERVOIR_CAPACITY` values per group)
+    Median,
+
+    /// Sample standard deviation (n - 1)
+    StandardDeviation,
+
+    /// Number of distinct non-empty values
+    DistinctCount,
+}
+
+impl AggregationFunction {
+    /// All aggregation functions, in the order they are offered to users
+    pub const ALL: [AggregationFunction; 8] = [
+        AggregationFunction::Count,
+        AggregationFunction::Sum,
+        AggregationFunction::Mean,
+        AggregationFunction::Minimum,
+        AggregationFunction::Maximum,
+        AggregationFunction::Median,
+        AggregationFunction::StandardDeviation,
+        AggregationFunction::DistinctCount,
+    ];
+
+    /// Returns the short name used in prompts and output column names
+    ///
+    /// # Returns
+    /// * `&str` - e.g. `mean`, which yields output columns like `mean_age`
+    pub fn short_name(&self) -> &str {
+        match self {
+            AggregationFunction::Count => "count",
+            AggregationFunction::Sum => "sum",
+            AggregationFunction::Mean => "mean",
+            AggregationFunction::Minimum => "min",
+            AggregationFunction::Maximum => "max",
+            AggregationFunction::Median => "median",
+            AggregationFunction::StandardDeviation => "std",
+            AggregationFunction::DistinctCount => "distinct",
+        }
+    }
+
+    /// Parses a function name as typed by a user
+    ///
+    /// # Arguments
+    /// * `function_name` - e.g. `mean`, `avg`, `stddev` (case-insensitive)
+    ///
+    /// # Returns
+    /// * `Option<AggregationFunction>` - The function, or None if unknown
+    pub fn from_name(function_name: &str) -> Option<AggregationFunction> {
+        match function_name.trim().to_lowercase().as_str() {
+            "count" | "n" => Some(AggregationFunction::Count),
+            "sum" | "total" => Some(AggregationFunction::Sum),
+            "mean" | "avg" | "average" => Some(AggregationFunction::Mean),
+            "min" | "minimum" => Some(AggregationFunction::Minimum),
+            "max" | "maximum" => Some(AggregationFunction::Maximum),
+            "median" => Some(AggregationFunction::Median),
+            "std" | "stddev" | "sd" => Some(AggregationFunction::StandardDeviation),
+            "distinct" | "nunique" | "unique" => Some(AggregationFunction::DistinctCount),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this function always produces a whole number
+    fn produces_whole_numbers(&self) -> bool {
+        matches!(self, AggregationFunction::Count | AggregationFunction::DistinctCount)
+    }
+}
+
+/// What to group by and what to compute
+#[derive(Debug, Clone)]
+pub struct GroupByAggregationRequest {
+    /// 0-based column positions whose values form the group key
+    pub group_column_positions: Vec<usize>,
+
+    /// 0-based column positions of the numeric columns to aggregate
+    pub aggregated_column_positions: Vec<usize>,
+
+    /// Functions computed for every aggregated column
+    pub aggregation_functions: Vec<AggregationFunction>,
+}
+
+/// One output row: a group key and its aggregate values
+#[derive(Debug, Clone)]
+pub struct GroupByResultRow {
+    /// Group key values, one per group column
+    pub group_key_values: Vec<String>,
+
+    /// Aggregate values in `aggregate_column_names` order (None when undefined)
+    pub aggregate_values: Vec<Option<f64>>,
+}
+
+/// The complete result of a group-by aggregation
+#[derive(Debug, Clone)]
+pub struct GroupByAggregationResults {
+    /// Names of the group columns
+    pub group_column_names: Vec<String>,
+
+    /// Data types of the group columns (copied from the source)
+    pub group_column_data_types: Vec<CsvColumnDataType>,
+
+    /// Output column names: `row_count`, then `<function>_<column>`
+    pub aggregate_column_names: Vec<String>,
+
+    /// Whether each aggregate column always holds whole numbers
+    pub aggregate_column_is_whole_number: Vec<bool>,
+
+    /// One row per group, sorted by group key
+    pub result_rows: Vec<GroupByResultRow>,
+
+    /// Number of source rows read
+    pub source_row_count: usize,
+
+    /// Number of (group, column) medians computed from a full reservoir
+    pub approximate_median_count: usize,
+
+    /// Number of (group, column) distinct counts that reached the tracking limit
+    pub capped_distinct_count: usize,
+}
+
+/// Running state for one aggregated column within one group
+#[derive(Debug, Clone, Default)]
+struct GroupColumnAccumulator {
+    /// Number of numeric values seen
+    numeric_value_count: usize,
+
+    /// Sum of numeric values
+    value_sum: f64,
+
+    /// Running mean (Welford)
+    running_mean: f64,
+
+    /// Running sum of squared deviations from the mean (Welford)
+    squared_deviation_sum: f64,
+
+    /// Smallest and largest values seen
+    minimum_value: f64,
+    maximum_value: f64,
+
+    /// Reservoir sample for the median (only filled when the median is requested)
+    median_reservoir: Vec<f64>,
+
+    /// Distinct non-empty values (only filled when `distinct` is requested)
+    distinct_values: HashSet<String>,
+
+    /// True once a new distinct value was seen after the tracking limit
+    distinct_limit_reached: bool,
+}
+
+impl GroupColumnAccumulator {
+    /// Adds one raw cell value to the accumulator
+    ///
+    /// # Arguments
+    /// * `cell_value` - The trimmed cell value
+    /// * `track_median` - Whether the median reservoir is needed
+    /// * `track_distinct` - Whether distinct values are needed
+    /// * `sampling_generator` - Random source for reservoir replacement
+    fn add_cell_value(
+        &mut self,
+        cell_value: &str,
+        track_median: bool,
+        track_distinct: bool,
+        sampling_generator: &mut ReservoirSamplingGenerator,
+    ) {
+        if cell_value.is_empty() {
+            return;
+        }
+
+        // Step 1: Distinct values are tracked on the text, numeric or not
+        if track_distinct && !self.distinct_values.contains(cell_value) {
+            if self.distinct_values.len() < DISTINCT_VALUE_TRACKING_LIMIT {
+                self.distinct_values.insert(cell_value.to_string());
+            } else {
+                self.distinct_limit_reached = true;
+            }
+        }
+
+        // Step 2: Everything else needs a finite number
+        let numeric_value = match cell_value.parse::<f64>() {
+            Ok(parsed_value) if parsed_value.is_finite() => parsed_value,
+            _ => return,
+        };
+
+        self.numeric_value_count += 1;
+        self.value_sum += numeric_value;
+
+        let deviation_before_update = numeric_value - self.running_mean;
+        self.running_mean += deviation_before_update / self.numeric_value_count as f64;
+        self.squared_deviation_sum += deviation_before_update * (numeric_value - self.running_mean);
+
+        if self.numeric_value_count == 1 {
+            self.minimum_value = numeric_value;
+            self.maximum_value = numeric_value;
+        } else {
+            self.minimum_value = self.minimum_value.min(numeric_value);
+            self.maximum_value = self.maximum_value.max(numeric_value);
+        }
+
+        // Step 3: Reservoir sampling (Algorithm R) keeps the median bounded
+        if track_median {
+            if self.median_reservoir.len() < MEDIAN_RESERVOIR_CAPACITY {
+                self.median_reservoir.push(numeric_value);
+            } else {
+                let replacement_index = sampling_generator.next_index_below(self.numeric_value_count);
+                if replacement_index < MEDIAN_RESERVOIR_CAPACITY {
+                    self.median_reservoir[replacement_index] = numeric_value;
+                }
+            }
+        }
+    }
+
+    /// Computes the final value of one aggregation
+    ///
+    /// # Arguments
+    /// * `aggregation_function` - The aggregation to compute
+    ///
+    /// # Returns
+    /// * `Option<f64>` - The value, or None when undefined (e.g. mean of no values)
+    fn finish_aggregation(&self, aggregation_function: AggregationFunction) -> Option<f64> {
+        let has_numeric_values = self.numeric_value_count > 0;
+
+        match aggregation_function {
+            AggregationFunction::Count => Some(self.numeric_value_count as f64),
+            // A sum that overflowed f64 is undefined rather than infinite
+            AggregationFunction::Sum => (has_numeric_values && self.value_sum.is_finite()).then_some(self.value_sum),
+            AggregationFunction::Mean => has_numeric_values.then_some(self.running_mean),
+            AggregationFunction::Minimum => has_numeric_values.then_some(self.minimum_value),
+            AggregationFunction::Maximum => has_numeric_values.then_some(self.maximum_value),
+            AggregationFunction::Median => calculate_median_of_values(&self.median_reservoir),
+            AggregationFunction::StandardDeviation => (self.numeric_value_count > 1)
+                .then(|| (self.squared_deviation_sum / (self.numeric_value_count - 1) as f64).sqrt()),
+            AggregationFunction::DistinctCount => Some(self.distinct_values.len() as f64),
+        }
+    }
+}
+
+/// Running state for one group
+#[derive(Debug, Clone)]
+struct GroupAccumulator {
+    /// Number of source rows in the group
+    group_row_count: usize,
+
+    /// One accumulator per aggregated column
+    column_accumulators: Vec<GroupColumnAccumulator>,
+}
+
+/// Groups rows and computes the requested aggregations in one streaming pass
+///
+/// # Arguments
+/// * `group_by_row_source` - CSV file or directory store dataset to read
+/// * `group_by_request` - Group columns, aggregated columns and functions
+///
+/// # Returns
+/// * `RowsAndColumnsResult<GroupByAggregationResults>` - One row per group
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If no group column is given or a position is out of range
+/// * `RowsAndColumnsError::StatisticalAnalysisError` - If there are more than `MAXIMUM_GROUP_COUNT` groups
+/// * `RowsAndColumnsError::FileSystemError` - If the source cannot be read
+pub fn perform_group_by_aggregation(
+    group_by_row_source: &DatasetRowSource,
+    group_by_request: &GroupByAggregationRequest,
+) -> RowsAndColumnsResult<GroupByAggregationResults> {
+    // Step 1: Validate the request against the source columns
+    let source_column_names = group_by_row_source.column_names();
+    let source_column_data_types = group_by_row_source.column_data_types();
+
+    if group_by_request.group_column_positions.is_empty() {
+        return Err(create_configuration_error("Group-by needs at least one group column"));
+    }
+
+    for &column_position in group_by_request.group_column_positions.iter()
+        .chain(group_by_request.aggregated_column_positions.iter()) {
+        if column_position >= source_column_names.len() {
+            return Err(create_configuration_error(&format!(
+                "Column number {} is out of range (the source has {} columns)",
+                column_position + 1,
+                source_column_names.len()
+            )));
+        }
+    }
+
+    let track_median = group_by_request.aggregation_functions.contains(&AggregationFunction::Median);
+    let track_distinct = group_by_request.aggregation_functions.contains(&AggregationFunction::DistinctCount);
+    let aggregated_column_count = group_by_request.aggregated_column_positions.len();
+
+    // Step 2: Stream rows into per-group accumulators (BTreeMap keeps keys sorted)
+    let mut group_accumulators: BTreeMap<Vec<String>, GroupAccumulator> = BTreeMap::new();
+    let mut sampling_generator = ReservoirSamplingGenerator::from_seed(RESERVOIR_SAMPLING_SEED);
+
+    let source_row_count = group_by_row_source.stream_rows(|_, row_values| {
+        let group_key: Vec<String> = group_by_request.group_column_positions
+            .iter()
+            .map(|&column_position| row_values.get(column_position).cloned().unwrap_or_default())
+            .collect();
+
+        if !group_accumulators.contains_key(&group_key) && group_accumulators.len() >= MAXIMUM_GROUP_COUNT {
+            return Err(create_statistical_analysis_error(
+                &format!("More than {} groups; choose group columns with fewer distinct values", MAXIMUM_GROUP_COUNT),
+                &group_by_request.group_column_positions
+                    .iter()
+                    .map(|&column_position| source_column_names[column_position].as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let group_accumulator = group_accumulators.entry(group_key).or_insert_with(|| GroupAccumulator {
+            group_row_count: 0,
+            column_accumulators: vec![GroupColumnAccumulator::default(); aggregated_column_count],
+        });
+        group_accumulator.group_row_count += 1;
+
+        for (column_accumulator, &column_position) in group_accumulator.column_accumulators
+            .iter_mut()
+            .zip(&group_by_request.aggregated_column_positions) {
+            let cell_value = row_values.get(column_position).map(String::as_str).unwrap_or("");
+            column_accumulator.add_cell_value(cell_value, track_median, track_distinct, &mut sampling_generator);
+        }
+
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    // Step 3: Name the output columns
+    let mut aggregate_column_names = vec![GROUP_ROW_COUNT_COLUMN_NAME.to_string()];
+    let mut aggregate_column_is_whole_number = vec![true];
+    for &column_position in &group_by_request.aggregated_column_positions {
+        for aggregation_function in &group_by_request.aggregation_functions {
+            aggregate_column_names.push(format!("{}_{}", aggregation_function.short_name(), source_column_names[column_position]));
+            aggregate_column_is_whole_number.push(aggregation_function.produces_whole_numbers());
+        }
+    }
+
+    // Step 4: Finish every group
+    let mut approximate_median_count = 0;
+    let mut capped_distinct_count = 0;
+    let mut result_rows = Vec::with_capacity(group_accumulators.len());
+
+    for (group_key_values, group_accumulator) in group_accumulators {
+        let mut aggregate_values = vec![Some(group_accumulator.group_row_count as f64)];
+
+        for column_accumulator in &group_accumulator.column_accumulators {
+            if track_median && column_accumulator.numeric_value_count > MEDIAN_RESERVOIR_CAPACITY {
+                approximate_median_count += 1;
+            }
+            if track_distinct && column_accumulator.distinct_limit_reached {
+                capped_distinct_count += 1;
+            }
+
+            for &aggregation_function in &group_by_request.aggregation_functions {
+                aggregate_values.push(column_accumulator.finish_aggregation(aggregation_function));
+            }
+        }
+
+        result_rows.push(GroupByResultRow { group_key_values, aggregate_values });
+    }
+
+    Ok(GroupByAggregationResults {
+        group_column_names: group_by_request.group_column_positions
+            .iter()
+            .map(|&column_position| source_column_names[column_position].clone())
+            .collect(),
+        group_column_data_types: group_by_request.group_column_positions
+            .iter()
+            .map(|&column_position| source_column_data_types[column_position].clone())
+            .collect(),
+        aggregate_column_names,
+        aggregate_column_is_whole_number,
+        result_rows,
+        source_row_count,
+        approximate_median_count,
+        capped_distinct_count,
+    })
+}
+
+/// Calculates the median of a list of values
+///
+/// # Arguments
+/// * `sample_values` - Values in any order
+///
+/// # Returns
+/// * `Option<f64>` - The median, or None for an empty list
+fn calculate_median_of_values(sample_values: &[f64]) -> Option<f64> {
+    if sample_values.is_empty() {
+        return None;
+    }
+
+    let mut sorted_values = sample_values.to_vec();
+    sorted_values.sort_by(|first_value, second_value| first_value.total_cmp(second_value));
+
+    let middle_index = sorted_values.len() / 2;
+    if sorted_values.len().is_multiple_of(2) {
+        Some((sorted_values[middle_index - 1] + sorted_values[middle_index]) / 2.0)
+    } else {
+        Some(sorted_values[middle_index])
+    }
+}
+
+/// Parses a comma-separated list of aggregation function names
+///
+/// # Arguments
+/// * `function_list_text` - e.g. `count, mean, max`
+///
+/// # Returns
+/// * `Result<Vec<AggregationFunction>, String>` - Functions in the given order
+///   (duplicates removed), or a message naming the unknown function
+pub fn parse_aggregation_function_list(function_list_text: &str) -> Result<Vec<AggregationFunction>, String> {
+    let mut aggregation_functions = Vec::new();
+
+    for function_name in function_list_text.split(',').filter(|name| !name.trim().is_empty()) {
+        let aggregation_function = AggregationFunction::from_name(function_name).ok_or_else(|| {
+            format!(
+                "Unknown aggregation '{}' (choose from {})",
+                function_name.trim(),
+                AggregationFunction::ALL.iter().map(|function| function.short_name()).collect::<Vec<_>>().join(", ")
+            )
+        })?;
+
+        if !aggregation_functions.contains(&aggregation_function) {
+            aggregation_functions.push(aggregation_function);
+        }
+    }
+
+    Ok(aggregation_functions)
+}
+
+/// Formats an aggregate value for the exported CSV file
+///
+/// Whole numbers are written without a decimal point; other values use the
+/// shortest representation that reads back as the same number.
+///
+/// # Arguments
+/// * `aggregate_value` - The value, or None when undefined
+///
+/// # Returns
+/// * `String` - The CSV field (empty for None)
+pub fn format_aggregate_value_for_csv(aggregate_value: Option<f64>) -> String {
+    match aggregate_value {
+        Some(value) if value.fract() == 0.0 && value.abs() < 1e15 => format!("{:.0}", value),
+        Some(value) => format!("{}", value),
+        None => String::new(),
+    }
+}
+
+/// Formats an aggregate value for the text table (at most 4 decimals)
+///
+/// # Arguments
+/// * `aggregate_value` - The value, or None when undefined
+///
+/// # Returns
+/// * `String` - The formatted value (`-` for None)
+fn format_aggregate_value_for_display(aggregate_value: Option<f64>) -> String {
+    match aggregate_value {
+        Some(value) if value.fract() == 0.0 && value.abs() < 1e15 => format!("{:.0}", value),
+        Some(value) => {
+            let fixed_text = format!("{:.4}", value);
+            fixed_text.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+        None => "-".to_string(),
+    }
+}
+
+/// Formats the results as an aligned text table
+///
+/// # Arguments
+/// * `group_by_results` - The aggregation results
+/// * `maximum_row_count` - Most groups to print (the rest are summarized)
+///
+/// # Returns
+/// * `Vec<String>` - Table lines, including any approximation notes
+pub fn format_group_by_table_lines(
+    group_by_results: &GroupByAggregationResults,
+    maximum_row_count: usize,
+) -> Vec<String> {
+    let group_column_count = group_by_results.group_column_names.len();
+
+    // Step 1: Build every displayed cell so column widths can be measured
+    let header_cells: Vec<String> = group_by_results.group_column_names
+        .iter()
+        .chain(&group_by_results.aggregate_column_names)
+        .cloned()
+        .collect();
+
+    let body_rows: Vec<Vec<String>> = group_by_results.result_rows
+        .iter()
+        .take(maximum_row_count)
+        .map(|result_row| {
+            result_row.group_key_values
+                .iter()
+                .map(|group_value| {
+                    if group_value.is_empty() { EMPTY_GROUP_VALUE_LABEL.to_string() } else { group_value.clone() }
+                })
+                .chain(result_row.aggregate_values.iter().map(|&value| format_aggregate_value_for_display(value)))
+                .collect()
+        })
+        .collect();
+
+    // Step 2: Group columns are left-aligned, aggregate columns right-aligned
+    let right_aligned_columns: Vec<bool> = (0..header_cells.len())
+        .map(|column_position| column_position >= group_column_count)
+        .collect();
+    let mut table_lines = format_aligned_text_table_lines(
+        &header_cells,
+        &body_rows,
+        &right_aligned_columns,
+        MAXIMUM_TABLE_COLUMN_WIDTH,
+        '─',
+    );
+
+    // Step 3: Summarize anything not shown or not exact
+    let hidden_group_count = group_by_results.result_rows.len().saturating_sub(maximum_row_count);
+    if hidden_group_count > 0 {
+        table_lines.push(format!("… {} more groups (export to CSV to see all)", hidden_group_count));
+    }
+    if group_by_results.approximate_median_count > 0 {
+        table_lines.push(format!(
+            "Note: {} medians are estimated from a {}-value sample of their group",
+            group_by_results.approximate_median_count, MEDIAN_RESERVOIR_CAPACITY
+        ));
+    }
+    if group_by_results.capped_distinct_count > 0 {
+        table_lines.push(format!(
+            "Note: {} distinct counts stopped at the tracking limit of {} (true count is higher)",
+            group_by_results.capped_distinct_count, DISTINCT_VALUE_TRACKING_LIMIT
+        ));
+    }
+
+    table_lines
+}
+
+/// Writes the results as a new CSV file plus its `.csv_metadata.toml`
+///
+/// Values come from the comma-split CSV reader, so they never contain commas
+/// and are written unchanged; the file reads back exactly as exported.
+///
+/// # Arguments
+/// * `group_by_results` - The aggregation results
+/// * `output_csv_path` - Where to write the CSV file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path of the metadata file written next to the CSV
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If either file cannot be written
+pub fn export_group_by_results_to_csv(
+    group_by_results: &GroupByAggregationResults,
+    output_csv_path: &Path,
+) -> RowsAndColumnsResult<PathBuf> {
+    // Step 1: Group columns keep their source types; aggregates are whole or fractional numbers
+    let output_column_names: Vec<String> = group_by_results.group_column_names
+        .iter()
+        .chain(&group_by_results.aggregate_column_names)
+        .cloned()
+        .collect();
+    let output_column_data_types: Vec<CsvColumnDataType> = group_by_results.group_column_data_types
+        .iter()
+        .cloned()
+        .chain(group_by_results.aggregate_column_is_whole_number.iter().map(|&is_whole_number| {
+            if is_whole_number { CsvColumnDataType::Integer } else { CsvColumnDataType::Float }
+        }))
+        .collect();
+
+    // Step 2: One line per group, streamed to the file; the writer adds the metadata
+    let mut export_writer = StreamingCsvExportWriter::create(output_csv_path, &output_column_names, &output_column_data_types)?;
+
+    for result_row in &group_by_results.result_rows {
+        let row_fields: Vec<String> = result_row.group_key_values
+            .iter()
+            .cloned()
+            .chain(result_row.aggregate_values.iter().map(|&value| format_aggregate_value_for_csv(value)))
+            .collect();
+        export_writer.write_row(&row_fields)?;
+    }
+
+    let (_, metadata_file_path) = export_writer.finish()?;
+    Ok(metadata_file_path)
+}
+
+/// Prints the results table with a short heading
+///
+/// # Arguments
+/// * `group_by_results` - The aggregation results
+pub fn display_group_by_aggregation_results(group_by_results: &GroupByAggregationResults) {
+    println!();
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Group-By: {} ({} groups from {} rows)",
+        group_by_results.group_column_names.join(" × "),
+        group_by_results.result_rows.len(),
+        group_by_results.source_row_count
+    );
+    println!("═══════════════════════════════════════════════════════════════");
+
+    for table_line in format_group_by_table_lines(group_by_results, 50) {
+        println!("  {}", table_line);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixture_module::write_test_csv_analysis_results;
+
+    /// Test mean/median/std/distinct per group, with missing values skipped
+    #[test]
+    fn test_group_by_aggregation_per_group_values() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_group_by_values_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_analysis_results = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "breed,age\nsiamese,2\ntabby,4\nsiamese,4\ntabby,\nsiamese,9\n",
+            &[],
+        );
+        let group_by_request = GroupByAggregationRequest {
+            group_column_positions: vec![0],
+            aggregated_column_positions: vec![1],
+            aggregation_functions: parse_aggregation_function_list("count,mean,median,std,distinct")
+                .expect("functions should parse"),
+        };
+
+        let group_by_results = perform_group_by_aggregation(
+            &DatasetRowSource::CsvFile(&csv_analysis_results),
+            &group_by_request,
+        ).expect("aggregation should succeed");
+
+        assert_eq!(group_by_results.aggregate_column_names,
+            vec!["row_count", "count_age", "mean_age", "median_age", "std_age", "distinct_age"]);
+        assert_eq!(group_by_results.source_row_count, 5);
+        assert_eq!(group_by_results.result_rows.len(), 2);
+
+        let siamese_values = &group_by_results.result_rows[0].aggregate_values;
+        assert_eq!(group_by_results.result_rows[0].group_key_values, vec!["siamese"]);
+        assert_eq!(siamese_values[0], Some(3.0));
+        assert_eq!(siamese_values[2], Some(5.0));
+        assert_eq!(siamese_values[3], Some(4.0));
+        assert!((siamese_values[4].expect("std defined") - 13.0_f64.sqrt()).abs() < 1e-9);
+
+        let tabby_values = &group_by_results.result_rows[1].aggregate_values;
+        assert_eq!(tabby_values[0], Some(2.0));
+        assert_eq!(tabby_values[1], Some(1.0));
+        assert_eq!(tabby_values[4], None);
+        assert_eq!(tabby_values[5], Some(1.0));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test the median reservoir stays bounded and flags the estimate
+    #[test]
+    fn test_median_reservoir_is_bounded() {
+        let mut column_accumulator = GroupColumnAccumulator::default();
+        let mut sampling_generator = ReservoirSamplingGenerator::from_seed(RESERVOIR_SAMPLING_SEED);
+        let total_value_count = MEDIAN_RESERVOIR_CAPACITY * 3;
+
+        for value_index in 0..total_value_count {
+            column_accumulator.add_cell_value(&value_index.to_string(), true, true, &mut sampling_generator);
+        }
+
+        assert_eq!(column_accumulator.median_reservoir.len(), MEDIAN_RESERVOIR_CAPACITY);
+        assert_eq!(column_accumulator.distinct_values.len(), DISTINCT_VALUE_TRACKING_LIMIT);
+        assert!(column_accumulator.distinct_limit_reached);
+
+        let estimated_median = column_accumulator.finish_aggregation(AggregationFunction::Median).expect("median defined");
+        let true_median = (total_value_count - 1) as f64 / 2.0;
+        assert!((estimated_median - true_median).abs() < total_value_count as f64 * 0.05);
+        assert_eq!(column_accumulator.finish_aggregation(AggregationFunction::Maximum), Some((total_value_count - 1) as f64));
+    }
+
+    /// Test exporting results writes a CSV and metadata that read back cleanly
+    #[test]
+    fn test_export_group_by_results_to_csv() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_group_by_export_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_analysis_results = write_test_csv_analysis_results(
+            &test_directory.join("cats.csv"),
+            "breed,is_indoor,age\nsiamese,true,2.5\nsiamese,true,3\ntabby,false,4\n",
+            &[],
+        );
+        let group_by_results = perform_group_by_aggregation(
+            &DatasetRowSource::CsvFile(&csv_analysis_results),
+            &GroupByAggregationRequest {
+                group_column_positions: vec![0, 1],
+                aggregated_column_positions: vec![2],
+                aggregation_functions: vec![AggregationFunction::Sum, AggregationFunction::Maximum],
+            },
+        ).expect("aggregation should succeed");
+
+        let output_csv_path = test_directory.join("grouped.csv");
+        let metadata_file_path = export_group_by_results_to_csv(&group_by_results, &output_csv_path)
+            .expect("export should succeed");
+
+        let exported_text = std::fs::read_to_string(&output_csv_path).expect("exported CSV readable");
+        assert_eq!(exported_text, "breed,is_indoor,row_count,sum_age,max_age\nsiamese,true,2,5.5,3\ntabby,false,1,4,4\n");
+
+        let metadata_text = std::fs::read_to_string(&metadata_file_path).expect("metadata readable");
+        assert!(metadata_text.contains("name = \"sum_age\"\ndata_type = \"float\""));
+        assert!(metadata_text.contains("name = \"row_count\"\ndata_type = \"integer\""));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test awkward cells: short rows, empty keys, non-numeric and non-finite
+    /// text, sums that overflow f64, and values with a large common offset
+    #[test]
+    fn test_group_by_awkward_values() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_group_by_awkward_test_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_analysis_results = write_test_csv_analysis_results(
+            &test_directory.join("awkward.csv"),
+            "breed,age\nbig,1e308\nbig,1e308\noffset,1000000001\noffset,1000000002\noffset,1000000003\n\
+             noisy,abc\nnoisy,NaN\nnoisy,inf\nnoisy,7\nshort\n,5\n",
+            &[],
+        );
+        let group_by_request = GroupByAggregationRequest {
+            group_column_positions: vec![0],
+            aggregated_column_positions: vec![1],
+            aggregation_functions: parse_aggregation_function_list("count,sum,mean,std,distinct")
+                .expect("functions should parse"),
+        };
+
+        let group_by_results = perform_group_by_aggregation(
+            &DatasetRowSource::CsvFile(&csv_analysis_results),
+            &group_by_request,
+        ).expect("aggregation should succeed");
+
+        let group_keys: Vec<&str> = group_by_results.result_rows
+            .iter()
+            .map(|result_row| result_row.group_key_values[0].as_str())
+            .collect();
+        assert_eq!(group_keys, vec!["", "big", "noisy", "offset", "short"]);
+        let values_for = |group_position: usize| &group_by_results.result_rows[group_position].aggregate_values;
+
+        // Overflowing sum is undefined, the running mean is not
+        assert_eq!(values_for(1)[2], None);
+        assert_eq!(values_for(1)[3], Some(1e308));
+
+        // Only "7" is a finite number; every non-empty text is distinct
+        assert_eq!(values_for(2)[1], Some(1.0));
+        assert_eq!(values_for(2)[3], Some(7.0));
+        assert_eq!(values_for(2)[5], Some(4.0));
+
+        // Welford keeps the spread exact despite the 1e9 offset
+        assert!((values_for(3)[4].expect("std defined") - 1.0).abs() < 1e-9);
+
+        // A row without the aggregated cell still counts towards its group
+        assert_eq!(values_for(4)[0], Some(1.0));
+        assert_eq!(values_for(4)[1], Some(0.0));
+        assert_eq!(values_for(4)[3], None);
+
+        let table_lines = format_group_by_table_lines(&group_by_results, 10);
+        assert!(table_lines.iter().any(|table_line| table_line.contains(EMPTY_GROUP_VALUE_LABEL)));
+
+        let out_of_range_request = GroupByAggregationRequest {
+            group_column_positions: vec![0],
+            aggregated_column_positions: vec![2],
+            aggregation_functions: vec![AggregationFunction::Mean],
+        };
+        assert!(perform_group_by_aggregation(&DatasetRowSource::CsvFile(&csv_analysis_results), &out_of_range_request).is_err());
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
@@ -0,0 +1,758 @@
+// src/gzip_decompression_module.rs
+
+/// Gzip decompression (RFC 1952 around RFC 1951 DEFLATE) for rows_and_columns
+///
+/// Archives of `.csv.gz` files are read as a stream of decompressed bytes, so
+/// every CSV reader in the application can consume them like plain text. The
+/// project uses only the standard library, so inflate is implemented here.
+///
+/// # Core Responsibilities
+/// - Parse gzip member headers (optional extra field, name, comment, header CRC)
+/// - Inflate stored, fixed-Huffman and dynamic-Huffman DEFLATE blocks
+/// - Verify each member's CRC32 and length trailer
+/// - Continue across concatenated members (`cat a.gz b.gz`), like `zcat`
+/// - Recognize compressed CSV file names and the gzip magic bytes
+///
+/// # Design Philosophy
+/// - Streaming: `GzipDecompressingReader` implements `Read`; memory is the
+///   32 KiB window plus one output chunk, whatever the file size
+/// - Strict: corrupt data is an `InvalidData` error, never silently wrong rows
+/// - Table-driven Huffman decoding, one lookup per symbol
+use std::io::{self, Read};
+
+/// The two bytes every gzip member starts with
+pub const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+/// Compression method 8 (DEFLATE), the only one gzip defines
+const GZIP_DEFLATE_COMPRESSION_METHOD: u8 = 8;
+
+/// Header flag bits (FTEXT, bit 0, needs no handling)
+const GZIP_FLAG_HEADER_CRC: u8 = 0x02;
+const GZIP_FLAG_EXTRA_FIELD: u8 = 0x04;
+pub const GZIP_FLAG_FILE_NAME: u8 = 0x08;
+const GZIP_FLAG_COMMENT: u8 = 0x10;
+const GZIP_RESERVED_FLAG_BITS: u8 = 0xe0;
+
+/// DEFLATE back-references reach at most 32 KiB back
+const DEFLATE_WINDOW_SIZE: usize = 32 * 1024;
+
+/// Decompressed bytes produced per refill of the output buffer
+const OUTPUT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Compressed bytes read from the source at a time
+const INPUT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Longest Huffman code DEFLATE allows
+const MAXIMUM_HUFFMAN_CODE_LENGTH: usize = 15;
+
+/// End-of-block symbol of the literal/length alphabet
+const END_OF_BLOCK_SYMBOL: u16 = 256;
+
+/// Base lengths and extra bits of length symbols 257..=285
+const LENGTH_SYMBOL_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_SYMBOL_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Base distances and extra bits of distance symbols 0..=29
+const DISTANCE_SYMBOL_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097,
+    6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_SYMBOL_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// Order in which a dynamic block lists the code length code lengths
+const CODE_LENGTH_CODE_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// CRC32 lookup table (IEEE polynomial, reflected), built at compile time
+const CRC32_LOOKUP_TABLE: [u32; 256] = build_crc32_lookup_table();
+
+/// Builds the CRC32 lookup table
+const fn build_crc32_lookup_table() -> [u32; 256] {
+    let mut lookup_table = [0u32; 256];
+    let mut table_index = 0;
+    while table_index < 256 {
+        let mut crc_value = table_index as u32;
+        let mut bit_index = 0;
+        while bit_index < 8 {
+            crc_value = if crc_value & 1 == 1 { 0xedb8_8320 ^ (crc_value >> 1) } else { crc_value >> 1 };
+            bit_index += 1;
+        }
+        lookup_table[table_index] = crc_value;
+        table_index += 1;
+    }
+    lookup_table
+}
+
+/// Continues a CRC32 over more bytes
+///
+/// # Arguments
+/// * `running_crc` - The CRC so far (0 to start)
+/// * `data_bytes` - The next bytes
+///
+/// # Returns
+/// * `u32` - The CRC including `data_bytes`
+pub fn update_crc32_checksum(running_crc: u32, data_bytes: &[u8]) -> u32 {
+    let mut crc_value = !running_crc;
+    for data_byte in data_bytes {
+        crc_value = CRC32_LOOKUP_TABLE[((crc_value ^ u32::from(*data_byte)) & 0xff) as usize] ^ (crc_value >> 8);
+    }
+    !crc_value
+}
+
+/// Checks whether a file name names a gzip-compressed CSV or TSV file
+///
+/// # Arguments
+/// * `file_name` - A file name or path (case does not matter)
+///
+/// # Returns
+/// * `bool` - True for `.csv.gz` and `.tsv.gz`
+pub fn is_gzip_compressed_csv_file_name(file_name: &str) -> bool {
+    let lowercase_file_name = file_name.to_lowercase();
+    lowercase_file_name.ends_with(".csv.gz") || lowercase_file_name.ends_with(".tsv.gz")
+}
+
+/// Builds the error returned for corrupt or unsupported gzip data
+fn invalid_gzip_data_error(problem_description: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("gzip: {}", problem_description))
+}
+
+/// Canonical Huffman code as a lookup table indexed by the next input bits
+///
+/// Entries hold (symbol, code length); length 0 marks a bit pattern that no
+/// code uses (only possible for incomplete codes).
+struct HuffmanDecodingTable {
+    table_entries: Vec<(u16, u8)>,
+    lookup_bit_count: u32,
+}
+
+impl HuffmanDecodingTable {
+    /// Builds the table from the code length of every symbol
+    ///
+    /// # Arguments
+    /// * `symbol_code_lengths` - Code length per symbol (0 = unused)
+    ///
+    /// # Returns
+    /// * `io::Result<HuffmanDecodingTable>` - The table, or an error for over-subscribed lengths
+    fn from_code_lengths(symbol_code_lengths: &[u8]) -> io::Result<Self> {
+        // Step 1: Count codes of each length and reject impossible sets
+        let mut length_counts = [0u32; MAXIMUM_HUFFMAN_CODE_LENGTH + 1];
+        for code_length in symbol_code_lengths {
+            length_counts[usize::from(*code_length)] += 1;
+        }
+        length_counts[0] = 0;
+        let mut remaining_code_space: i64 = 1;
+        for length_count in &length_counts[1..] {
+            remaining_code_space = (remaining_code_space << 1) - i64::from(*length_count);
+            if remaining_code_space < 0 {
+                return Err(invalid_gzip_data_error("over-subscribed Huffman code lengths"));
+            }
+        }
+
+        // Step 2: First canonical code of each length
+        let mut next_code_of_length = [0u32; MAXIMUM_HUFFMAN_CODE_LENGTH + 1];
+        let mut first_code = 0u32;
+        for code_length in 1..=MAXIMUM_HUFFMAN_CODE_LENGTH {
+            first_code = (first_code + length_counts[code_length - 1]) << 1;
+            next_code_of_length[code_length] = first_code;
+        }
+
+        // Step 3: Fill every table slot whose low bits are a code (bits arrive reversed)
+        let lookup_bit_count = u32::from(symbol_code_lengths.iter().copied().max().unwrap_or(0).max(1));
+        let mut table_entries = vec![(0u16, 0u8); 1 << lookup_bit_count];
+        for (symbol, code_length) in symbol_code_lengths.iter().enumerate() {
+            if *code_length == 0 {
+                continue;
+            }
+            let code_length_index = usize::from(*code_length);
+            let canonical_code = next_code_of_length[code_length_index];
+            next_code_of_length[code_length_index] += 1;
+            let reversed_code = canonical_code.reverse_bits() >> (32 - u32::from(*code_length));
+            let mut table_index = reversed_code as usize;
+            while table_index < table_entries.len() {
+                table_entries[table_index] = (symbol as u16, *code_length);
+                table_index += 1 << code_length_index;
+            }
+        }
+
+        Ok(HuffmanDecodingTable { table_entries, lookup_bit_count })
+    }
+}
+
+/// Where the decoder is within the gzip stream
+enum InflateDecoderState {
+    /// Expecting a gzip member header (or the end of the input between members)
+    MemberHeader,
+
+    /// Expecting a DEFLATE block header
+    BlockHeader,
+
+    /// Copying the rest of a stored (uncompressed) block
+    StoredBlock { remaining_byte_count: usize },
+
+    /// Decoding symbols of a Huffman-compressed block
+    HuffmanBlock { literal_length_table: HuffmanDecodingTable, distance_table: HuffmanDecodingTable },
+
+    /// Expecting the member's CRC32 and length trailer
+    MemberTrailer,
+
+    /// All members read
+    Finished,
+
+    /// An earlier error left the stream unusable
+    Failed,
+}
+
+/// A `Read` adapter that decompresses gzip data as it is read
+///
+/// Errors in the compressed data surface as `io::ErrorKind::InvalidData`
+/// (or `UnexpectedEof` for truncated input) from `read`.
+pub struct GzipDecompressingReader<R: Read> {
+    compressed_source: R,
+    input_buffer: Vec<u8>,
+    input_position: usize,
+    input_end: usize,
+    source_exhausted: bool,
+
+    bit_buffer: u64,
+    bit_count: u32,
+
+    decoder_state: InflateDecoderState,
+    final_block_seen: bool,
+    window_bytes: Vec<u8>,
+    window_position: usize,
+
+    output_bytes: Vec<u8>,
+    output_position: usize,
+
+    member_crc32: u32,
+    member_byte_count: u64,
+    member_count: usize,
+}
+
+impl<R: Read> GzipDecompressingReader<R> {
+    /// Wraps a source of gzip-compressed bytes
+    ///
+    /// # Arguments
+    /// * `compressed_source` - The `.gz` file or stream
+    ///
+    /// # Returns
+    /// * `GzipDecompressingReader<R>` - A reader of the decompressed bytes
+    pub fn new(compressed_source: R) -> Self {
+        GzipDecompressingReader {
+            compressed_source,
+            input_buffer: vec![0; INPUT_BUFFER_SIZE],
+            input_position: 0,
+            input_end: 0,
+            source_exhausted: false,
+            bit_buffer: 0,
+            bit_count: 0,
+            decoder_state: InflateDecoderState::MemberHeader,
+            final_block_seen: false,
+            window_bytes: vec![0; DEFLATE_WINDOW_SIZE],
+            window_position: 0,
+            output_bytes: Vec::with_capacity(OUTPUT_CHUNK_SIZE + 258),
+            output_position: 0,
+            member_crc32: 0,
+            member_byte_count: 0,
+            member_count: 0,
+        }
+    }
+
+    /// Reads the next compressed byte, or None at the end of the source
+    fn next_input_byte(&mut self) -> io::Result<Option<u8>> {
+        if self.input_position == self.input_end {
+            if self.source_exhausted {
+                return Ok(None);
+            }
+            loop {
+                match self.compressed_source.read(&mut self.input_buffer) {
+                    Ok(0) => {
+                        self.source_exhausted = true;
+                        return Ok(None);
+                    }
+                    Ok(byte_count) => {
+                        self.input_position = 0;
+                        self.input_end = byte_count;
+                        break;
+                    }
+                    Err(io_error) if io_error.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(io_error) => return Err(io_error),
+                }
+            }
+        }
+        let input_byte = self.input_buffer[self.input_position];
+        self.input_position += 1;
+        Ok(Some(input_byte))
+    }
+
+    /// Tops up the bit buffer to at least `wanted_bit_count` bits, if input allows
+    fn fill_bit_buffer(&mut self, wanted_bit_count: u32) -> io::Result<()> {
+        while self.bit_count < wanted_bit_count {
+            match self.next_input_byte()? {
+                Some(input_byte) => {
+                    self.bit_buffer |= u64::from(input_byte) << self.bit_count;
+                    self.bit_count += 8;
+                }
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `bit_count` bits (at most 32), least significant first
+    fn read_bits(&mut self, bit_count: u32) -> io::Result<u32> {
+        if bit_count == 0 {
+            return Ok(0);
+        }
+        self.fill_bit_buffer(bit_count)?;
+        if self.bit_count < bit_count {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "gzip: compressed data ends too early"));
+        }
+        let bit_value = (self.bit_buffer & ((1u64 << bit_count) - 1)) as u32;
+        self.bit_buffer >>= bit_count;
+        self.bit_count -= bit_count;
+        Ok(bit_value)
+    }
+
+    /// Reads one byte-aligned byte
+    fn read_aligned_byte(&mut self) -> io::Result<u8> {
+        Ok(self.read_bits(8)? as u8)
+    }
+
+    /// Reads a little-endian 16-bit value
+    fn read_u16_little_endian(&mut self) -> io::Result<u16> {
+        Ok(self.read_bits(16)? as u16)
+    }
+
+    /// Reads a little-endian 32-bit value
+    fn read_u32_little_endian(&mut self) -> io::Result<u32> {
+        self.read_bits(32)
+    }
+
+    /// Drops the bits left in the current byte
+    fn align_to_byte_boundary(&mut self) {
+        let partial_bit_count = self.bit_count % 8;
+        self.bit_buffer >>= partial_bit_count;
+        self.bit_count -= partial_bit_count;
+    }
+
+    /// Decodes one symbol with a Huffman table
+    fn decode_huffman_symbol(&mut self, decoding_table: &HuffmanDecodingTable) -> io::Result<u16> {
+        self.fill_bit_buffer(decoding_table.lookup_bit_count)?;
+        let lookup_mask = (1u64 << decoding_table.lookup_bit_count) - 1;
+        let (symbol, code_length) = decoding_table.table_entries[(self.bit_buffer & lookup_mask) as usize];
+        if code_length == 0 {
+            return Err(invalid_gzip_data_error("invalid Huffman code"));
+        }
+        if u32::from(code_length) > self.bit_count {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "gzip: compressed data ends too early"));
+        }
+        self.bit_buffer >>= code_length;
+        self.bit_count -= u32::from(code_length);
+        Ok(symbol)
+    }
+
+    /// Appends a decompressed byte to the output, window and checksum totals
+    fn emit_output_byte(&mut self, output_byte: u8) {
+        self.output_bytes.push(output_byte);
+        self.window_bytes[self.window_position] = output_byte;
+        self.window_position = (self.window_position + 1) % DEFLATE_WINDOW_SIZE;
+        self.member_crc32 = update_crc32_checksum(self.member_crc32, &[output_byte]);
+        self.member_byte_count += 1;
+    }
+
+    /// Reads a gzip member header, or finishes at the end of the input
+    ///
+    /// Only the first member is required; afterwards the end of the input
+    /// simply ends the stream.
+    fn read_member_header(&mut self) -> io::Result<()> {
+        self.fill_bit_buffer(8)?;
+        if self.bit_count == 0 && self.member_count > 0 {
+            self.decoder_state = InflateDecoderState::Finished;
+            return Ok(());
+        }
+
+        let header_bytes = [self.read_aligned_byte()?, self.read_aligned_byte()?];
+        if header_bytes != GZIP_MAGIC_BYTES {
+            return Err(invalid_gzip_data_error("not gzip data (missing the 1f 8b signature)"));
+        }
+        let compression_method = self.read_aligned_byte()?;
+        if compression_method != GZIP_DEFLATE_COMPRESSION_METHOD {
+            return Err(invalid_gzip_data_error(&format!("unknown compression method {}", compression_method)));
+        }
+        let header_flags = self.read_aligned_byte()?;
+        if header_flags & GZIP_RESERVED_FLAG_BITS != 0 {
+            return Err(invalid_gzip_data_error("reserved header flags are set"));
+        }
+        // Modification time (4), extra flags (1) and operating system (1) are not needed
+        for _ in 0..6 {
+            self.read_aligned_byte()?;
+        }
+        if header_flags & GZIP_FLAG_EXTRA_FIELD != 0 {
+            let extra_field_length = self.read_u16_little_endian()?;
+            for _ in 0..extra_field_length {
+                self.read_aligned_byte()?;
+            }
+        }
+        for zero_terminated_flag in [GZIP_FLAG_FILE_NAME, GZIP_FLAG_COMMENT] {
+            if header_flags & zero_terminated_flag != 0 {
+                while self.read_aligned_byte()? != 0 {}
+            }
+        }
+        if header_flags & GZIP_FLAG_HEADER_CRC != 0 {
+            self.read_u16_little_endian()?;
+        }
+
+        self.member_count += 1;
+        self.member_crc32 = 0;
+        self.member_byte_count = 0;
+        self.final_block_seen = false;
+        self.decoder_state = InflateDecoderState::BlockHeader;
+        Ok(())
+    }
+
+    /// Reads a DEFLATE block header and prepares to decode the block
+    fn read_block_header(&mut self) -> io::Result<()> {
+        if self.final_block_seen {
+            self.decoder_state = InflateDecoderState::MemberTrailer;
+            return Ok(());
+        }
+        self.final_block_seen = self.read_bits(1)? == 1;
+        self.decoder_state = match self.read_bits(2)? {
+            0 => {
+                self.align_to_byte_boundary();
+                let stored_length = self.read_u16_little_endian()?;
+                let complement_length = self.read_u16_little_endian()?;
+                if stored_length != !complement_length {
+                    return Err(invalid_gzip_data_error("stored block length check failed"));
+                }
+                InflateDecoderState::StoredBlock { remaining_byte_count: usize::from(stored_length) }
+            }
+            1 => {
+                let mut literal_length_code_lengths = [0u8; 288];
+                literal_length_code_lengths[..144].fill(8);
+                literal_length_code_lengths[144..256].fill(9);
+                literal_length_code_lengths[256..280].fill(7);
+                literal_length_code_lengths[280..].fill(8);
+                InflateDecoderState::HuffmanBlock {
+                    literal_length_table: HuffmanDecodingTable::from_code_lengths(&literal_length_code_lengths)?,
+                    distance_table: HuffmanDecodingTable::from_code_lengths(&[5u8; 30])?,
+                }
+            }
+            2 => self.read_dynamic_huffman_tables()?,
+            _ => return Err(invalid_gzip_data_error("invalid block type 3")),
+        };
+        Ok(())
+    }
+
+    /// Reads the code lengths of a dynamic Huffman block and builds its tables
+    fn read_dynamic_huffman_tables(&mut self) -> io::Result<InflateDecoderState> {
+        // Step 1: How many lengths of each kind follow
+        let literal_length_code_count = self.read_bits(5)? as usize + 257;
+        let distance_code_count = self.read_bits(5)? as usize + 1;
+        let code_length_code_count = self.read_bits(4)? as usize + 4;
+        if literal_length_code_count > 286 || distance_code_count > 30 {
+            return Err(invalid_gzip_data_error("too many length or distance codes"));
+        }
+
+        // Step 2: The code that the code lengths themselves are written in
+        let mut code_length_code_lengths = [0u8; 19];
+        for code_length_symbol in &CODE_LENGTH_CODE_ORDER[..code_length_code_count] {
+            code_length_code_lengths[*code_length_symbol] = self.read_bits(3)? as u8;
+        }
+        let code_length_table = HuffmanDecodingTable::from_code_lengths(&code_length_code_lengths)?;
+
+        // Step 3: Literal/length and distance code lengths, with run-length repeats
+        let total_code_count = literal_length_code_count + distance_code_count;
+        let mut symbol_code_lengths: Vec<u8> = Vec::with_capacity(total_code_count);
+        while symbol_code_lengths.len() < total_code_count {
+            let code_length_symbol = self.decode_huffman_symbol(&code_length_table)?;
+            let (repeated_length, repeat_count) = match code_length_symbol {
+                0..=15 => (code_length_symbol as u8, 1),
+                16 => {
+                    let previous_length = *symbol_code_lengths.last()
+                        .ok_or_else(|| invalid_gzip_data_error("length repeat with no previous length"))?;
+                    (previous_length, 3 + self.read_bits(2)? as usize)
+                }
+                17 => (0, 3 + self.read_bits(3)? as usize),
+                _ => (0, 11 + self.read_bits(7)? as usize),
+            };
+            if symbol_code_lengths.len() + repeat_count > total_code_count {
+                return Err(invalid_gzip_data_error("code length repeat runs past the end"));
+            }
+            symbol_code_lengths.extend(std::iter::repeat_n(repeated_length, repeat_count));
+        }
+        let (literal_length_code_lengths, distance_code_lengths) = symbol_code_lengths.split_at(literal_length_code_count);
+        if literal_length_code_lengths[usize::from(END_OF_BLOCK_SYMBOL)] == 0 {
+            return Err(invalid_gzip_data_error("block has no end-of-block code"));
+        }
+
+        Ok(InflateDecoderState::HuffmanBlock {
+            literal_length_table: HuffmanDecodingTable::from_code_lengths(literal_length_code_lengths)?,
+            distance_table: HuffmanDecodingTable::from_code_lengths(distance_code_lengths)?,
+        })
+    }
+
+    /// Decodes symbols until the output chunk is full or the block ends
+    ///
+    /// # Returns
+    /// * `io::Result<bool>` - True when the block's end-of-block symbol was read
+    fn decode_huffman_block_symbols(
+        &mut self,
+        literal_length_table: &HuffmanDecodingTable,
+        distance_table: &HuffmanDecodingTable,
+    ) -> io::Result<bool> {
+        while self.output_bytes.len() < OUTPUT_CHUNK_SIZE {
+            let literal_length_symbol = self.decode_huffman_symbol(literal_length_table)?;
+            if literal_length_symbol < END_OF_BLOCK_SYMBOL {
+                self.emit_output_byte(literal_length_symbol as u8);
+                continue;
+            }
+            if literal_length_symbol == END_OF_BLOCK_SYMBOL {
+                return Ok(true);
+            }
+
+            // A back-reference: length, then distance
+            let length_index = usize::from(literal_length_symbol - 257);
+            if length_index >= LENGTH_SYMBOL_BASES.len() {
+                return Err(invalid_gzip_data_error("invalid length symbol"));
+            }
+            let match_length = usize::from(LENGTH_SYMBOL_BASES[length_index])
+                + self.read_bits(u32::from(LENGTH_SYMBOL_EXTRA_BITS[length_index]))? as usize;
+            let distance_index = usize::from(self.decode_huffman_symbol(distance_table)?);
+            if distance_index >= DISTANCE_SYMBOL_BASES.len() {
+                return Err(invalid_gzip_data_error("invalid distance symbol"));
+            }
+            let match_distance = usize::from(DISTANCE_SYMBOL_BASES[distance_index])
+                + self.read_bits(u32::from(DISTANCE_SYMBOL_EXTRA_BITS[distance_index]))? as usize;
+            if match_distance as u64 > self.member_byte_count {
+                return Err(invalid_gzip_data_error("back-reference before the start of the data"));
+            }
+            for _ in 0..match_length {
+                let window_index = (self.window_position + DEFLATE_WINDOW_SIZE - match_distance) % DEFLATE_WINDOW_SIZE;
+                self.emit_output_byte(self.window_bytes[window_index]);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Checks the member trailer against what was decompressed
+    fn read_member_trailer(&mut self) -> io::Result<()> {
+        self.align_to_byte_boundary();
+        let stored_crc32 = self.read_u32_little_endian()?;
+        let stored_length = self.read_u32_little_endian()?;
+        let computed_crc32 = self.member_crc32;
+        if stored_crc32 != computed_crc32 {
+            return Err(invalid_gzip_data_error(&format!(
+                "CRC32 mismatch (stored {:08x}, computed {:08x}); the file is corrupt",
+                stored_crc32,
+                computed_crc32
+            )));
+        }
+        if stored_length != self.member_byte_count as u32 {
+            return Err(invalid_gzip_data_error("decompressed length does not match the trailer"));
+        }
+        self.decoder_state = InflateDecoderState::MemberHeader;
+        Ok(())
+    }
+
+    /// Decompresses the next chunk into the output buffer
+    ///
+    /// Leaves the buffer empty only when the stream is finished.
+    fn refill_output_buffer(&mut self) -> io::Result<()> {
+        self.output_bytes.clear();
+        self.output_position = 0;
+
+        while self.output_bytes.len() < OUTPUT_CHUNK_SIZE {
+            // Any error below leaves the Failed placeholder in place
+            match std::mem::replace(&mut self.decoder_state, InflateDecoderState::Failed) {
+                InflateDecoderState::MemberHeader => self.read_member_header()?,
+                InflateDecoderState::BlockHeader => self.read_block_header()?,
+                InflateDecoderState::StoredBlock { mut remaining_byte_count } => {
+                    while remaining_byte_count > 0 && self.output_bytes.len() < OUTPUT_CHUNK_SIZE {
+                        let stored_byte = self.read_aligned_byte()?;
+                        self.emit_output_byte(stored_byte);
+                        remaining_byte_count -= 1;
+                    }
+                    self.decoder_state = if remaining_byte_count == 0 {
+                        InflateDecoderState::BlockHeader
+                    } else {
+                        InflateDecoderState::StoredBlock { remaining_byte_count }
+                    };
+                }
+                InflateDecoderState::HuffmanBlock { literal_length_table, distance_table } => {
+                    let block_finished = self.decode_huffman_block_symbols(&literal_length_table, &distance_table)?;
+                    self.decoder_state = if block_finished {
+                        InflateDecoderState::BlockHeader
+                    } else {
+                        InflateDecoderState::HuffmanBlock { literal_length_table, distance_table }
+                    };
+                }
+                InflateDecoderState::MemberTrailer => self.read_member_trailer()?,
+                InflateDecoderState::Finished => {
+                    self.decoder_state = InflateDecoderState::Finished;
+                    break;
+                }
+                InflateDecoderState::Failed => {
+                    return Err(invalid_gzip_data_error("cannot continue after an earlier error"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for GzipDecompressingReader<R> {
+    fn read(&mut self, destination_buffer: &mut [u8]) -> io::Result<usize> {
+        if self.output_position == self.output_bytes.len() {
+            self.refill_output_buffer()?;
+        }
+        let copied_byte_count = destination_buffer.len().min(self.output_bytes.len() - self.output_position);
+        destination_buffer[..copied_byte_count]
+            .copy_from_slice(&self.output_bytes[self.output_position..self.output_position + copied_byte_count]);
+        self.output_position += copied_byte_count;
+        Ok(copied_byte_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_fixture_module::build_stored_gzip_member;
+
+    /// `gzip -9n` of "id,name\n1,aaaaaaaaaaaaaaaaaaaaaaaa\n2,aaaaaaaaaaaaaaaaaaaabbbb\n" (fixed Huffman, back-references)
+    const SMALL_GZIP_MEMBER: [u8; 40] = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xcb, 0x4c, 0xd1, 0xc9, 0x4b, 0xcc, 0x4d, 0xe5,
+        0x32, 0xd4, 0x49, 0xc4, 0x01, 0xb8, 0x8c, 0xb0, 0x4a, 0x25, 0x01, 0x01, 0x17, 0x00, 0x09, 0x7b, 0x76, 0x1f,
+        0x3e, 0x00, 0x00, 0x00,
+    ];
+    const SMALL_GZIP_MEMBER_TEXT: &str = "id,name\n1,aaaaaaaaaaaaaaaaaaaaaaaa\n2,aaaaaaaaaaaaaaaaaaaabbbb\n";
+
+    /// Decompresses a whole byte slice
+    fn decompress_bytes(compressed_bytes: &[u8]) -> io::Result<String> {
+        let mut decompressed_text = String::new();
+        GzipDecompressingReader::new(compressed_bytes).read_to_string(&mut decompressed_text)?;
+        Ok(decompressed_text)
+    }
+
+    /// Test the CRC32 check value and compressed CSV file names
+    #[test]
+    fn test_crc32_and_file_names() {
+        assert_eq!(update_crc32_checksum(0, b"123456789"), 0xcbf4_3926);
+        assert_eq!(update_crc32_checksum(update_crc32_checksum(0, b"1234"), b"56789"), 0xcbf4_3926);
+        assert!(is_gzip_compressed_csv_file_name("archive/Sales.CSV.gz"));
+        assert!(is_gzip_compressed_csv_file_name("cats.tsv.gz"));
+        assert!(!is_gzip_compressed_csv_file_name("cats.csv"));
+        assert!(!is_gzip_compressed_csv_file_name("backup.tar.gz"));
+    }
+
+    /// Test Huffman and stored members decompress, also when concatenated
+    #[test]
+    fn test_decompresses_huffman_stored_and_concatenated_members() {
+        assert_eq!(decompress_bytes(&SMALL_GZIP_MEMBER).expect("should inflate"), SMALL_GZIP_MEMBER_TEXT);
+
+        let stored_member = build_stored_gzip_member(b"a,b\n1,2\n");
+        assert_eq!(decompress_bytes(&stored_member).expect("should inflate"), "a,b\n1,2\n");
+
+        let mut concatenated_members = SMALL_GZIP_MEMBER.to_vec();
+        concatenated_members.extend_from_slice(&stored_member);
+        assert_eq!(
+            decompress_bytes(&concatenated_members).expect("should inflate"),
+            format!("{}a,b\n1,2\n", SMALL_GZIP_MEMBER_TEXT)
+        );
+    }
+
+    /// Test corrupt, truncated and non-gzip input are errors
+    #[test]
+    fn test_rejects_corrupt_input() {
+        let mut corrupted_member = SMALL_GZIP_MEMBER;
+        corrupted_member[32] ^= 0xff;
+        let crc_error = decompress_bytes(&corrupted_member).expect_err("CRC should not match");
+        assert_eq!(crc_error.kind(), io::ErrorKind::InvalidData);
+        assert!(crc_error.to_string().contains("CRC32 mismatch"));
+
+        let truncated_error = decompress_bytes(&SMALL_GZIP_MEMBER[..20]).expect_err("input is truncated");
+        assert_eq!(truncated_error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let plain_text_error = decompress_bytes(b"id,name\n1,Tom\n").expect_err("not gzip");
+        assert!(plain_text_error.to_string().contains("not gzip data"));
+    }
+
+    /// Writes a fixed-Huffman DEFLATE stream bit by bit for hand-made test members
+    #[derive(Default)]
+    struct FixedHuffmanTestWriter {
+        deflate_bytes: Vec<u8>,
+        written_bit_count: usize,
+    }
+
+    impl FixedHuffmanTestWriter {
+        /// Appends bits least significant first (header fields and extra bits)
+        fn write_bits(&mut self, bit_values: u32, bit_count: usize) {
+            for bit_position in 0..bit_count {
+                if self.written_bit_count.is_multiple_of(8) {
+                    self.deflate_bytes.push(0);
+                }
+                let bit_value = ((bit_values >> bit_position) & 1) as u8;
+                *self.deflate_bytes.last_mut().expect("byte was pushed") |= bit_value << (self.written_bit_count % 8);
+                self.written_bit_count += 1;
+            }
+        }
+
+        /// Appends a Huffman code, most significant bit first
+        fn write_code(&mut self, code_value: u32, code_length: usize) {
+            for bit_position in (0..code_length).rev() {
+                self.write_bits((code_value >> bit_position) & 1, 1);
+            }
+        }
+
+        /// Wraps the stream in a gzip member for the given decompressed bytes
+        fn finish_member(self, decompressed_bytes: &[u8]) -> Vec<u8> {
+            let mut member_bytes = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+            member_bytes.extend_from_slice(&self.deflate_bytes);
+            member_bytes.extend_from_slice(&update_crc32_checksum(0, decompressed_bytes).to_le_bytes());
+            member_bytes.extend_from_slice(&(decompressed_bytes.len() as u32).to_le_bytes());
+            member_bytes
+        }
+    }
+
+    /// Test overlapping back-references that run past an output chunk, and a
+    /// back-reference to before the start of the data
+    #[test]
+    fn test_back_references_at_the_edges() {
+        // 'a', then 300 copies of (length 258, distance 1): 77,401 bytes of 'a'
+        const MATCH_COUNT: usize = 300;
+        let mut long_run_writer = FixedHuffmanTestWriter::default();
+        long_run_writer.write_bits(0b011, 3);
+        long_run_writer.write_code(0x30 + u32::from(b'a'), 8);
+        for _ in 0..MATCH_COUNT {
+            long_run_writer.write_code(0xc0 + (285 - 280), 8);
+            long_run_writer.write_code(0, 5);
+        }
+        long_run_writer.write_code(0, 7);
+        let expected_text = "a".repeat(1 + MATCH_COUNT * 258);
+        assert!(expected_text.len() > OUTPUT_CHUNK_SIZE);
+        let long_run_member = long_run_writer.finish_member(expected_text.as_bytes());
+        assert_eq!(decompress_bytes(&long_run_member).expect("should inflate"), expected_text);
+
+        // A length-3 match (symbol 257) with nothing decompressed yet
+        let mut early_reference_writer = FixedHuffmanTestWriter::default();
+        early_reference_writer.write_bits(0b011, 3);
+        early_reference_writer.write_code(1, 7);
+        early_reference_writer.write_code(0, 5);
+        early_reference_writer.write_code(0, 7);
+        let early_reference_member = early_reference_writer.finish_member(b"");
+        let early_reference_error = decompress_bytes(&early_reference_member).expect_err("reference has no source");
+        assert!(early_reference_error.to_string().contains("before the start"));
+
+        // Reading again after an error reports an error instead of resuming
+        let mut failed_reader = GzipDecompressingReader::new(&early_reference_member[..]);
+        let mut read_buffer = [0u8; 16];
+        assert!(failed_reader.read(&mut read_buffer).is_err());
+        assert!(failed_reader.read(&mut read_buffer).is_err());
+    }
+}
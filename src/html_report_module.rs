@@ -0,0 +1,414 @@
+// src/html_report_module.rs
+
+/// Self-contained HTML analysis report for rows_and_columns
+///
+/// Produces a single `.html` file with no external assets (no scripts, fonts,
+/// stylesheets or images to fetch) so the analysis can be emailed or opened by
+/// colleagues who do not use a terminal.
+///
+/// # Core Responsibilities
+/// - Summary tables for the file and every column
+/// - Inline SVG histograms and box plots (continuous columns) and bar charts
+///   (categorical columns), built from the same chart definitions as the TUI
+/// - One collapsible `<details>` section per column
+///
+/// # Design Philosophy
+/// - Same content as the Markdown/plain-text report (facts, warnings, full
+///   value distributions), laid out for a browser
+/// - All text passes through `escape_xml_text`, so CSV content can never
+///   inject markup
+/// - Collapsing uses native `<details>` elements, so no JavaScript is needed
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Import CSV analysis structures
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    EnhancedCsvColumnInformation,
+};
+
+// Import chart definitions (shared with the TUI) and the SVG renderer
+use super::chart_rendering_module::{
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    build_bar_chart_from_categorical_statistics,
+    build_box_plot_chart_from_statistics,
+    build_histogram_chart_from_csv_column,
+};
+use super::svg_chart_module::{escape_xml_text, render_chart_as_svg};
+
+// Import warnings and shared formatting from the text report
+use super::analysis_report_module::{
+    collect_data_quality_warnings,
+    describe_field_type,
+    format_unix_timestamp_as_utc_text,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::RowsAndColumnsResult;
+
+/// Size of inline charts, in pixels
+const HTML_CHART_WIDTH: usize = 560;
+const HTML_HISTOGRAM_HEIGHT: usize = 260;
+const HTML_BOX_PLOT_HEIGHT: usize = 140;
+
+/// Most bars drawn in a categorical bar chart (the value table lists all values)
+const HTML_MAXIMUM_BAR_COUNT: usize = 20;
+
+/// Pixel height given to each bar of a bar chart
+const HTML_BAR_CHART_ROW_HEIGHT: usize = 22;
+
+/// Value tables longer than this start collapsed
+const HTML_COLLAPSED_VALUE_TABLE_THRESHOLD: usize = 20;
+
+/// Embedded stylesheet (kept small and readable when printed)
+const HTML_REPORT_STYLESHEET: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 70em; padding: 0 1em; color: #222; }
+h1 { font-size: 1.6em; } h2 { margin-top: 1.6em; border-bottom: 1px solid #ccc; }
+table { border-collapse: collapse; margin: 0.6em 0; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.6em; text-align: left; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+th { background: #f2f2f2; }
+details { border: 1px solid #ddd; border-radius: 4px; margin: 0.6em 0; padding: 0.3em 0.8em; }
+summary { cursor: pointer; font-weight: bold; padding: 0.3em 0; }
+.charts { display: flex; flex-wrap: wrap; gap: 1em; align-items: flex-start; }
+.warnings li { color: #8a4b00; }
+.muted { color: #666; }";
+
+/// Appends a table; `number_columns` marks right-aligned numeric columns
+///
+/// # Arguments
+/// * `html_text` - Output buffer
+/// * `header_cells` - Column headings
+/// * `body_rows` - Rows of raw (unescaped) cell text
+/// * `number_columns` - Whether each column holds numbers
+fn append_html_table(html_text: &mut String, header_cells: &[&str], body_rows: &[Vec<String>], number_columns: &[bool]) {
+    html_text.push_str("<table>\n<tr>");
+    for header_cell in header_cells {
+        html_text.push_str(&format!("<th>{}</th>", escape_xml_text(header_cell)));
+    }
+    html_text.push_str("</tr>\n");
+
+    for body_row in body_rows {
+        html_text.push_str("<tr>");
+        for (column_position, cell_text) in body_row.iter().enumerate() {
+            if number_columns.get(column_position).copied().unwrap_or(false) {
+                html_text.push_str(&format!("<td class=\"number\">{}</td>", escape_xml_text(cell_text)));
+            } else {
+                html_text.push_str(&format!("<td>{}</td>", escape_xml_text(cell_text)));
+            }
+        }
+        html_text.push_str("</tr>\n");
+    }
+    html_text.push_str("</table>\n");
+}
+
+/// Builds the complete HTML report
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file facts)
+/// * `enhanced_analysis_results` - Per-column statistics
+///
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - The HTML document
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be re-read for histograms
+pub fn build_html_report_text(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> RowsAndColumnsResult<String> {
+    let csv_file_name = csv_analysis_results.csv_file_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let report_title = format!("CSV Analysis Report: {}", csv_file_name);
+    let generated_seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+
+    // Step 1: Document head and title
+    let mut html_text = String::new();
+    html_text.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html_text.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html_text.push_str(&format!("<title>{}</title>\n", escape_xml_text(&report_title)));
+    html_text.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", HTML_REPORT_STYLESHEET));
+    html_text.push_str(&format!("<h1>{}</h1>\n", escape_xml_text(&report_title)));
+    html_text.push_str(&format!(
+        "<p class=\"muted\">Generated {} by rows_and_columns.</p>\n",
+        format_unix_timestamp_as_utc_text(generated_seconds)
+    ));
+
+    // Step 2: File facts
+    let file_size_text = std::fs::metadata(&csv_analysis_results.csv_file_path)
+        .map(|file_metadata| format!("{} bytes", file_metadata.len()))
+        .unwrap_or_else(|_| "unknown".to_string());
+    html_text.push_str("<h2>File</h2>\n");
+    append_html_table(&mut html_text, &["Property", "Value"], &[
+        vec!["Path".to_string(), csv_analysis_results.csv_file_path.display().to_string()],
+        vec!["Size".to_string(), file_size_text],
+        vec!["Header row".to_string(), if csv_analysis_results.has_header_row { "yes" } else { "no" }.to_string()],
+        vec!["Columns".to_string(), csv_analysis_results.total_column_count.to_string()],
+        vec!["Data rows".to_string(), csv_analysis_results.total_data_row_count.to_string()],
+        vec!["Metadata file".to_string(), csv_analysis_results.metadata_file_path.display().to_string()],
+    ], &[false, false]);
+
+    // Step 3: Column overview
+    html_text.push_str("<h2>Column Overview</h2>\n");
+    let overview_rows: Vec<Vec<String>> = enhanced_analysis_results
+        .iter()
+        .map(|enhanced_column_info| {
+            let basic_info = &enhanced_column_info.basic_info;
+            let (missing_text, summary_text) = match (
+                &enhanced_column_info.numerical_statistics,
+                &enhanced_column_info.categorical_statistics,
+            ) {
+                (Some(numerical_statistics), _) => (
+                    format!("{:.1}%", numerical_statistics.missing_percentage),
+                    format!("mean {:.3}", numerical_statistics.mean_value),
+                ),
+                (None, Some(categorical_statistics)) => (
+                    format!("{:.1}%", categorical_statistics.missing_percentage),
+                    format!("{} unique", categorical_statistics.unique_value_count),
+                ),
+                (None, None) => (String::new(), String::new()),
+            };
+            vec![
+                (basic_info.column_index + 1).to_string(),
+                basic_info.column_name.clone(),
+                basic_info.detected_data_type.to_toml_string().to_string(),
+                describe_field_type(&enhanced_column_info.field_type).to_string(),
+                missing_text,
+                summary_text,
+            ]
+        })
+        .collect();
+    append_html_table(
+        &mut html_text,
+        &["#", "Column", "Data type", "Field type", "Missing", "Summary"],
+        &overview_rows,
+        &[true, false, false, false, true, false],
+    );
+
+    // Step 4: Warnings
+    html_text.push_str("<h2>Warnings</h2>\n");
+    let quality_warnings = collect_data_quality_warnings(csv_analysis_results, enhanced_analysis_results);
+    if quality_warnings.is_empty() {
+        html_text.push_str("<p>No data quality warnings.</p>\n");
+    } else {
+        html_text.push_str("<ul class=\"warnings\">\n");
+        for quality_warning in &quality_warnings {
+            html_text.push_str(&format!("<li>{}</li>\n", escape_xml_text(quality_warning)));
+        }
+        html_text.push_str("</ul>\n");
+    }
+
+    // Step 5: One collapsible section per column
+    html_text.push_str("<h2>Columns</h2>\n");
+    for enhanced_column_info in enhanced_analysis_results {
+        append_html_column_section(&mut html_text, csv_analysis_results, enhanced_column_info)?;
+    }
+
+    html_text.push_str("</body>\n</html>\n");
+    Ok(html_text)
+}
+
+/// Appends the `<details>` section for one column
+///
+/// # Arguments
+/// * `html_text` - Output buffer
+/// * `csv_analysis_results` - Basic analysis results (for streaming histograms)
+/// * `enhanced_column_info` - The column to describe
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or histogram read error
+fn append_html_column_section(
+    html_text: &mut String,
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_column_info: &EnhancedCsvColumnInformation,
+) -> RowsAndColumnsResult<()> {
+    let basic_info = &enhanced_column_info.basic_info;
+    html_text.push_str(&format!(
+        "<details open>\n<summary>{}. {} <span class=\"muted\">({}, {})</span></summary>\n",
+        basic_info.column_index + 1,
+        escape_xml_text(&basic_info.column_name),
+        basic_info.detected_data_type.to_toml_string(),
+        describe_field_type(&enhanced_column_info.field_type)
+    ));
+
+    if let Some(numerical_statistics) = &enhanced_column_info.numerical_statistics {
+        let statistic_rows: Vec<Vec<String>> = [
+            ("min", numerical_statistics.min_value),
+            ("q1", numerical_statistics.q1_value),
+            ("median", numerical_statistics.q2_median_value),
+            ("q3", numerical_statistics.q3_value),
+            ("max", numerical_statistics.max_value),
+            ("mean", numerical_statistics.mean_value),
+            ("std", numerical_statistics.standard_deviation),
+        ]
+            .iter()
+            .map(|(statistic_name, statistic_value)| vec![statistic_name.to_string(), format!("{:.3}", statistic_value)])
+            .chain(std::iter::once(vec![
+                "missing".to_string(),
+                format!("{:.1}%", numerical_statistics.missing_percentage),
+            ]))
+            .collect();
+
+        let histogram_chart = build_histogram_chart_from_csv_column(
+            &csv_analysis_results.csv_file_path,
+            csv_analysis_results.has_header_row,
+            basic_info.column_index,
+            &basic_info.column_name,
+            numerical_statistics,
+            DEFAULT_HISTOGRAM_BIN_COUNT,
+        )?;
+        let box_plot_chart = build_box_plot_chart_from_statistics(&basic_info.column_name, numerical_statistics);
+
+        html_text.push_str("<div class=\"charts\">\n");
+        append_html_table(html_text, &["Statistic", "Value"], &statistic_rows, &[false, true]);
+        html_text.push_str("<div>\n");
+        html_text.push_str(&render_chart_as_svg(&histogram_chart, HTML_CHART_WIDTH, HTML_HISTOGRAM_HEIGHT));
+        html_text.push_str(&render_chart_as_svg(&box_plot_chart, HTML_CHART_WIDTH, HTML_BOX_PLOT_HEIGHT));
+        html_text.push_str("</div>\n</div>\n");
+    }
+
+    if let Some(categorical_statistics) = &enhanced_column_info.categorical_statistics {
+        let mut summary_text = format!(
+            "Unique values: {}. Missing: {:.1}%.",
+            categorical_statistics.unique_value_count,
+            categorical_statistics.missing_percentage
+        );
+        if let Some(mode_value) = &categorical_statistics.mode_value {
+            summary_text.push_str(&format!(" Mode: {} ({:.1}%).", mode_value, categorical_statistics.mode_percentage));
+        }
+        html_text.push_str(&format!("<p>{}</p>\n", escape_xml_text(&summary_text)));
+
+        let bar_chart = build_bar_chart_from_categorical_statistics(
+            &basic_info.column_name,
+            categorical_statistics,
+            HTML_MAXIMUM_BAR_COUNT,
+        );
+        let visible_bar_count = categorical_statistics.value_frequencies.len().clamp(1, HTML_MAXIMUM_BAR_COUNT);
+        html_text.push_str(&render_chart_as_svg(
+            &bar_chart,
+            HTML_CHART_WIDTH,
+            visible_bar_count * HTML_BAR_CHART_ROW_HEIGHT + 80,
+        ));
+
+        // Every value; long lists start collapsed so the page stays scannable
+        let distribution_rows: Vec<Vec<String>> = categorical_statistics.value_frequencies
+            .iter()
+            .map(|value_frequency| vec![
+                value_frequency.value.clone(),
+                value_frequency.count.to_string(),
+                format!("{:.1}%", value_frequency.percentage),
+            ])
+            .collect();
+        let value_table_open_attribute = if distribution_rows.len() > HTML_COLLAPSED_VALUE_TABLE_THRESHOLD { "" } else { " open" };
+        html_text.push_str(&format!(
+            "<details{}>\n<summary>All {} values</summary>\n",
+            value_table_open_attribute,
+            distribution_rows.len()
+        ));
+        append_html_table(html_text, &["Value", "Count", "Percent"], &distribution_rows, &[false, true, true]);
+        html_text.push_str("</details>\n");
+    }
+
+    html_text.push_str("</details>\n");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::{
+        CategoricalColumnStatistics,
+        CategoricalValueFrequency,
+        CsvColumnDataType,
+        CsvFieldType,
+        NumericalColumnStatistics,
+    };
+    use super::super::test_fixture_module::{create_test_column_information, create_test_report_analysis};
+
+    /// Builds the report fixture: a breed column with a value that needs escaping and an age column
+    fn create_test_analysis(file_name: &str) -> (CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>) {
+        create_test_report_analysis(
+            file_name,
+            "breed,age\n<Tabby>,2\nSiamese,4\n",
+            vec![
+                EnhancedCsvColumnInformation {
+                    basic_info: create_test_column_information(0, "breed", CsvColumnDataType::String, 2),
+                    field_type: CsvFieldType::Categorical,
+                    numerical_statistics: None,
+                    categorical_statistics: Some(CategoricalColumnStatistics {
+                        unique_value_count: 2,
+                        value_frequencies: vec![
+                            CategoricalValueFrequency { value: "<Tabby>".to_string(), count: 1, percentage: 50.0 },
+                            CategoricalValueFrequency { value: "Siamese".to_string(), count: 1, percentage: 50.0 },
+                        ],
+                        missing_percentage: 0.0,
+                        mode_value: Some("<Tabby>".to_string()),
+                        mode_percentage: 50.0,
+                    }),
+                },
+                EnhancedCsvColumnInformation {
+                    basic_info: create_test_column_information(1, "age", CsvColumnDataType::Integer, 2),
+                    field_type: CsvFieldType::Continuous,
+                    numerical_statistics: Some(NumericalColumnStatistics {
+                        min_value: 2.0,
+                        q1_value: 2.5,
+                        q2_median_value: 3.0,
+                        q3_value: 3.5,
+                        max_value: 4.0,
+                        mean_value: 3.0,
+                        standard_deviation: 1.0,
+                        missing_percentage: 0.0,
+                    }),
+                    categorical_statistics: None,
+                },
+            ],
+        )
+    }
+
+    /// Test the document is complete and self-contained
+    #[test]
+    fn test_html_report_is_self_contained() {
+        let (csv_analysis_results, enhanced_analysis_results) = create_test_analysis("rows_and_columns_html_contained_test.csv");
+        let html_text = build_html_report_text(&csv_analysis_results, &enhanced_analysis_results).expect("report should build");
+
+        assert!(html_text.starts_with("<!DOCTYPE html>\n"));
+        assert!(html_text.ends_with("</body>\n</html>\n"));
+        assert!(!html_text.contains("<script"));
+        assert!(!html_text.contains("<link"));
+        assert!(!html_text.contains("src="));
+
+        let _ = std::fs::remove_dir_all(csv_analysis_results.csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test each column gets a collapsible section with the right charts
+    #[test]
+    fn test_html_report_column_sections_and_charts() {
+        let (csv_analysis_results, enhanced_analysis_results) = create_test_analysis("rows_and_columns_html_sections_test.csv");
+        let html_text = build_html_report_text(&csv_analysis_results, &enhanced_analysis_results).expect("report should build");
+
+        assert_eq!(html_text.matches("<summary>").count(), 3);
+        assert_eq!(html_text.matches("<svg ").count(), 3);
+        assert!(html_text.contains(">age (histogram)</text>"));
+        assert!(html_text.contains(">age (box plot)</text>"));
+        assert!(html_text.contains(">breed (bar chart)</text>"));
+
+        let _ = std::fs::remove_dir_all(csv_analysis_results.csv_file_path.parent().expect("test file has a directory"));
+    }
+
+    /// Test CSV content is escaped everywhere it appears
+    #[test]
+    fn test_html_report_escapes_csv_values() {
+        let (csv_analysis_results, enhanced_analysis_results) = create_test_analysis("rows_and_columns_html_escape_test.csv");
+        let html_text = build_html_report_text(&csv_analysis_results, &enhanced_analysis_results).expect("report should build");
+
+        assert!(!html_text.contains("<Tabby>"));
+        assert!(html_text.contains("<td>&lt;Tabby&gt;</td>"));
+        assert!(html_text.contains("Mode: &lt;Tabby&gt; (50.0%)."));
+
+        let _ = std::fs::remove_dir_all(csv_analysis_results.csv_file_path.parent().expect("test file has a directory"));
+    }
+}
@@ -0,0 +1,477 @@
+// src/json_output_module.rs
+
+/// Machine-readable JSON output for rows_and_columns
+///
+/// `rows_and_columns --json <csv_file_path>` prints one JSON document to
+/// stdout and nothing else, so the describe output can be piped into other
+/// tools. The serializer is hand-written (no crates).
+///
+/// # Schema (version 1)
+/// ```text
+/// {
+///   "schema_version": 1,
+///   "status": "ok",
+///   "file": {
+///     "path": string, "has_header_row": bool, "total_columns": int,
+///     "total_data_rows": int, "metadata_file_path": string
+///   },
+///   "columns": [
+///     {
+///       "index": int (0-based), "name": string,
+///       "data_type": "boolean" | "integer" | "float" | "string",
+///       "field_type": "categorical" | "continuous",
+///       "numerical_statistics": null | {
+///         "min", "q1", "median", "q3", "max", "mean",
+///         "standard_deviation", "missing_percentage": number
+///       },
+///       "categorical_statistics": null | {
+///         "unique_values": int, "missing_percentage": number,
+///         "mode": string | null, "mode_percentage": number,
+///         "value_frequencies": [ { "value": string, "count": int, "percentage": number } ]
+///       }
+///     }
+///   ],
+///   "warnings": [ string ]
+/// }
+/// ```
+/// On failure the document is
+/// `{ "schema_version": 1, "status": "error", "error": { "kind": string, "message": string, "exit_code": int, ... } }`
+/// where `kind` is the snake_case error variant (e.g. `file_system_error`),
+/// `exit_code` is the process exit code for it, and the remaining keys are
+/// that variant's fields.
+///
+/// Fields are only ever added within a schema version; renaming or removing a
+/// field increments `ANALYSIS_JSON_SCHEMA_VERSION`. Non-finite numbers are
+/// written as `null`.
+///
+/// # Core Responsibilities
+/// - Model JSON values and serialize them (compact or pretty-printed)
+/// - Convert analysis results and errors into documents of the schema above
+///
+/// # Design Philosophy
+/// - Key order is fixed and follows the schema, so output diffs cleanly
+/// - Mirrors `TomlValue` in the metadata manager: a small value enum plus
+///   conversion functions, no generic serialization framework
+use super::csv_processor_module::{
+    CategoricalColumnStatistics,
+    CsvAnalysisResults,
+    CsvFieldType,
+    EnhancedCsvColumnInformation,
+    NumericalColumnStatistics,
+};
+
+// Import the data quality warnings shared with the text reports
+use super::analysis_report_module::collect_data_quality_warnings;
+
+// Import our custom error types (errors are serialized too)
+use super::error_types_module::RowsAndColumnsError;
+
+/// Version of the analysis JSON schema documented above
+pub const ANALYSIS_JSON_SCHEMA_VERSION: i64 = 1;
+
+/// Spaces per indentation level when pretty-printing
+const JSON_INDENT_WIDTH: usize = 2;
+
+/// A JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// `null`
+    Null,
+
+    /// `true` / `false`
+    Boolean(bool),
+
+    /// A whole number
+    Integer(i64),
+
+    /// A floating point number (non-finite values serialize as `null`)
+    Float(f64),
+
+    /// A string
+    Text(String),
+
+    /// An array
+    Array(Vec<JsonValue>),
+
+    /// An object, with keys kept in insertion order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Creates an integer value from a count
+    ///
+    /// # Arguments
+    /// * `count_value` - A non-negative count
+    ///
+    /// # Returns
+    /// * `JsonValue` - `Integer` (saturating at `i64::MAX`)
+    pub fn from_count(count_value: usize) -> JsonValue {
+        JsonValue::Integer(i64::try_from(count_value).unwrap_or(i64::MAX))
+    }
+
+    /// Creates an object from key/value pairs
+    ///
+    /// # Arguments
+    /// * `object_entries` - Keys and values in output order
+    ///
+    /// # Returns
+    /// * `JsonValue` - `Object`
+    pub fn object(object_entries: Vec<(&str, JsonValue)>) -> JsonValue {
+        JsonValue::Object(
+            object_entries
+                .into_iter()
+                .map(|(entry_key, entry_value)| (entry_key.to_string(), entry_value))
+                .collect()
+        )
+    }
+
+    /// Serializes the value as JSON text
+    ///
+    /// # Arguments
+    /// * `pretty_print` - True for indented multi-line output, false for one line
+    ///
+    /// # Returns
+    /// * `String` - The JSON text
+    pub fn to_json_text(&self, pretty_print: bool) -> String {
+        let mut json_text = String::new();
+        write_json_value(&mut json_text, self, pretty_print, 0);
+        json_text
+    }
+}
+
+/// Appends one value to the output, recursing into arrays and objects
+///
+/// # Arguments
+/// * `json_text` - Output buffer
+/// * `json_value` - Value to write
+/// * `pretty_print` - Whether to indent
+/// * `indent_level` - Current nesting depth
+fn write_json_value(json_text: &mut String, json_value: &JsonValue, pretty_print: bool, indent_level: usize) {
+    let write_line_break = |json_text: &mut String, line_indent_level: usize| {
+        if pretty_print {
+            json_text.push('\n');
+            json_text.push_str(&" ".repeat(line_indent_level * JSON_INDENT_WIDTH));
+        }
+    };
+
+    match json_value {
+        JsonValue::Null => json_text.push_str("null"),
+        JsonValue::Boolean(boolean_value) => json_text.push_str(if *boolean_value { "true" } else { "false" }),
+        JsonValue::Integer(integer_value) => json_text.push_str(&integer_value.to_string()),
+        JsonValue::Float(float_value) => {
+            if float_value.is_finite() {
+                json_text.push_str(&float_value.to_string());
+            } else {
+                json_text.push_str("null");
+            }
+        }
+        JsonValue::Text(text_value) => write_json_string(json_text, text_value),
+        JsonValue::Array(array_items) => {
+            if array_items.is_empty() {
+                json_text.push_str("[]");
+                return;
+            }
+            json_text.push('[');
+            for (item_position, array_item) in array_items.iter().enumerate() {
+                if item_position > 0 {
+                    json_text.push(',');
+                }
+                write_line_break(json_text, indent_level + 1);
+                write_json_value(json_text, array_item, pretty_print, indent_level + 1);
+            }
+            write_line_break(json_text, indent_level);
+            json_text.push(']');
+        }
+        JsonValue::Object(object_entries) => {
+            if object_entries.is_empty() {
+                json_text.push_str("{}");
+                return;
+            }
+            json_text.push('{');
+            for (entry_position, (entry_key, entry_value)) in object_entries.iter().enumerate() {
+                if entry_position > 0 {
+                    json_text.push(',');
+                }
+                write_line_break(json_text, indent_level + 1);
+                write_json_string(json_text, entry_key);
+                json_text.push_str(if pretty_print { ": " } else { ":" });
+                write_json_value(json_text, entry_value, pretty_print, indent_level + 1);
+            }
+            write_line_break(json_text, indent_level);
+            json_text.push('}');
+        }
+    }
+}
+
+/// Appends a quoted, escaped JSON string
+///
+/// # Arguments
+/// * `json_text` - Output buffer
+/// * `raw_text` - The string to write
+fn write_json_string(json_text: &mut String, raw_text: &str) {
+    json_text.push('"');
+    for text_character in raw_text.chars() {
+        match text_character {
+            '"' => json_text.push_str("\\\""),
+            '\\' => json_text.push_str("\\\\"),
+            '\n' => json_text.push_str("\\n"),
+            '\r' => json_text.push_str("\\r"),
+            '\t' => json_text.push_str("\\t"),
+            control_character if (control_character as u32) < 0x20 => {
+                json_text.push_str(&format!("\\u{:04x}", control_character as u32));
+            }
+            other_character => json_text.push(other_character),
+        }
+    }
+    json_text.push('"');
+}
+
+/// Converts numerical statistics to a JSON object
+///
+/// # Arguments
+/// * `numerical_statistics` - The statistics to convert
+///
+/// # Returns
+/// * `JsonValue` - Object with the schema's numerical statistics keys
+pub fn numerical_statistics_to_json(numerical_statistics: &NumericalColumnStatistics) -> JsonValue {
+    JsonValue::object(vec![
+        ("min", JsonValue::Float(numerical_statistics.min_value)),
+        ("q1", JsonValue::Float(numerical_statistics.q1_value)),
+        ("median", JsonValue::Float(numerical_statistics.q2_median_value)),
+        ("q3", JsonValue::Float(numerical_statistics.q3_value)),
+        ("max", JsonValue::Float(numerical_statistics.max_value)),
+        ("mean", JsonValue::Float(numerical_statistics.mean_value)),
+        ("standard_deviation", JsonValue::Float(numerical_statistics.standard_deviation)),
+        ("missing_percentage", JsonValue::Float(numerical_statistics.missing_percentage)),
+    ])
+}
+
+/// Converts categorical statistics (with every value frequency) to a JSON object
+///
+/// # Arguments
+/// * `categorical_statistics` - The statistics to convert
+///
+/// # Returns
+/// * `JsonValue` - Object with the schema's categorical statistics keys
+pub fn categorical_statistics_to_json(categorical_statistics: &CategoricalColumnStatistics) -> JsonValue {
+    JsonValue::object(vec![
+        ("unique_values", JsonValue::from_count(categorical_statistics.unique_value_count)),
+        ("missing_percentage", JsonValue::Float(categorical_statistics.missing_percentage)),
+        ("mode", categorical_statistics.mode_value.clone().map(JsonValue::Text).unwrap_or(JsonValue::Null)),
+        ("mode_percentage", JsonValue::Float(categorical_statistics.mode_percentage)),
+        ("value_frequencies", JsonValue::Array(
+            categorical_statistics.value_frequencies
+                .iter()
+                .map(|value_frequency| JsonValue::object(vec![
+                    ("value", JsonValue::Text(value_frequency.value.clone())),
+                    ("count", JsonValue::from_count(value_frequency.count)),
+                    ("percentage", JsonValue::Float(value_frequency.percentage)),
+                ]))
+                .collect()
+        )),
+    ])
+}
+
+/// Converts the complete analysis to a schema-versioned JSON document
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file facts)
+/// * `enhanced_analysis_results` - Per-column statistics
+///
+/// # Returns
+/// * `JsonValue` - The document described in the module documentation
+pub fn analysis_results_to_json(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> JsonValue {
+    let column_values: Vec<JsonValue> = enhanced_analysis_results
+        .iter()
+        .map(|enhanced_column_info| {
+            let basic_info = &enhanced_column_info.basic_info;
+            JsonValue::object(vec![
+                ("index", JsonValue::from_count(basic_info.column_index)),
+                ("name", JsonValue::Text(basic_info.column_name.clone())),
+                ("data_type", JsonValue::Text(basic_info.detected_data_type.to_toml_string().to_string())),
+                ("field_type", JsonValue::Text(match enhanced_column_info.field_type {
+                    CsvFieldType::Categorical => "categorical".to_string(),
+                    CsvFieldType::Continuous => "continuous".to_string(),
+                })),
+                ("numerical_statistics", enhanced_column_info.numerical_statistics
+                    .as_ref()
+                    .map(numerical_statistics_to_json)
+                    .unwrap_or(JsonValue::Null)),
+                ("categorical_statistics", enhanced_column_info.categorical_statistics
+                    .as_ref()
+                    .map(categorical_statistics_to_json)
+                    .unwrap_or(JsonValue::Null)),
+            ])
+        })
+        .collect();
+
+    JsonValue::object(vec![
+        ("schema_version", JsonValue::Integer(ANALYSIS_JSON_SCHEMA_VERSION)),
+        ("status", JsonValue::Text("ok".to_string())),
+        ("file", JsonValue::object(vec![
+            ("path", JsonValue::Text(csv_analysis_results.csv_file_path.display().to_string())),
+            ("has_header_row", JsonValue::Boolean(csv_analysis_results.has_header_row)),
+            ("total_columns", JsonValue::from_count(csv_analysis_results.total_column_count)),
+            ("total_data_rows", JsonValue::from_count(csv_analysis_results.total_data_row_count)),
+            ("metadata_file_path", JsonValue::Text(csv_analysis_results.metadata_file_path.display().to_string())),
+        ])),
+        ("columns", JsonValue::Array(column_values)),
+        ("warnings", JsonValue::Array(
+            collect_data_quality_warnings(csv_analysis_results, enhanced_analysis_results)
+                .into_iter()
+                .map(JsonValue::Text)
+                .collect()
+        )),
+    ])
+}
+
+/// Converts an error to a schema-versioned JSON error document
+///
+/// # Arguments
+/// * `application_error` - The error to report
+///
+/// # Returns
+/// * `JsonValue` - `{ "schema_version", "status": "error", "error": { "kind", "message", ... } }`
+pub fn error_to_json(application_error: &RowsAndColumnsError) -> JsonValue {
+    let optional_text = |optional_value: Option<String>| optional_value.map(JsonValue::Text).unwrap_or(JsonValue::Null);
+
+    let (error_kind, mut detail_entries) = match application_error {
+        RowsAndColumnsError::FileSystemError { operation_description, source_error } => ("file_system_error", vec![
+            ("operation_description", JsonValue::Text(operation_description.clone())),
+            ("source_error", JsonValue::Text(source_error.to_string())),
+        ]),
+        RowsAndColumnsError::CsvProcessingError { csv_operation_description, csv_line_number, csv_column_identifier } => {
+            ("csv_processing_error", vec![
+                ("csv_operation_description", JsonValue::Text(csv_operation_description.clone())),
+                ("csv_line_number", csv_line_number.map(JsonValue::from_count).unwrap_or(JsonValue::Null)),
+                ("csv_column_identifier", optional_text(csv_column_identifier.clone())),
+            ])
+        }
+        RowsAndColumnsError::MetadataError { metadata_operation_description, metadata_file_path } => ("metadata_error", vec![
+            ("metadata_operation_description", JsonValue::Text(metadata_operation_description.clone())),
+            ("metadata_file_path", JsonValue::Text(metadata_file_path.clone())),
+        ]),
+        RowsAndColumnsError::StatisticalAnalysisError { analysis_operation_description, column_name_being_analyzed } => {
+            ("statistical_analysis_error", vec![
+                ("analysis_operation_description", JsonValue::Text(analysis_operation_description.clone())),
+                ("column_name_being_analyzed", JsonValue::Text(column_name_being_analyzed.clone())),
+            ])
+        }
+        RowsAndColumnsError::TuiRenderingError { tui_operation_description } => ("tui_rendering_error", vec![
+            ("tui_operation_description", JsonValue::Text(tui_operation_description.clone())),
+        ]),
+        RowsAndColumnsError::DataTypeValidationError { data_type_operation_description, invalid_value, expected_data_type } => {
+            ("data_type_validation_error", vec![
+                ("data_type_operation_description", JsonValue::Text(data_type_operation_description.clone())),
+                ("invalid_value", JsonValue::Text(invalid_value.clone())),
+                ("expected_data_type", JsonValue::Text(expected_data_type.clone())),
+            ])
+        }
+        RowsAndColumnsError::ConfigurationError { configuration_issue_description } => ("configuration_error", vec![
+            ("configuration_issue_description", JsonValue::Text(configuration_issue_description.clone())),
+        ]),
+        RowsAndColumnsError::FilterExpressionError { filter_expression_text, error_character_position, filter_error_description } => {
+            ("filter_expression_error", vec![
+                ("filter_expression_text", JsonValue::Text(filter_expression_text.clone())),
+                ("error_character_position", JsonValue::from_count(*error_character_position)),
+                ("filter_error_description", JsonValue::Text(filter_error_description.clone())),
+            ])
+        }
+        RowsAndColumnsError::DerivedColumnExpressionError { derived_expression_text, error_character_position, derived_error_description } => {
+            ("derived_column_expression_error", vec![
+                ("derived_expression_text", JsonValue::Text(derived_expression_text.clone())),
+                ("error_character_position", JsonValue::from_count(*error_character_position)),
+                ("derived_error_description", JsonValue::Text(derived_error_description.clone())),
+            ])
+        }
+        RowsAndColumnsError::CommandLineUsageError { usage_error_description, subcommand_name } => {
+            ("command_line_usage_error", vec![
+                ("usage_error_description", JsonValue::Text(usage_error_description.clone())),
+                ("subcommand_name", optional_text(subcommand_name.clone())),
+            ])
+        }
+    };
+
+    let mut error_entries = vec![
+        ("kind", JsonValue::Text(error_kind.to_string())),
+        ("message", JsonValue::Text(application_error.to_string())),
+        ("exit_code", JsonValue::Integer(i64::from(application_error.exit_code()))),
+    ];
+    error_entries.append(&mut detail_entries);
+
+    JsonValue::object(vec![
+        ("schema_version", JsonValue::Integer(ANALYSIS_JSON_SCHEMA_VERSION)),
+        ("status", JsonValue::Text("error".to_string())),
+        ("error", JsonValue::object(error_entries)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::error_types_module::{create_configuration_error, create_csv_processing_error};
+
+    /// Test string escaping, non-finite numbers and empty containers
+    #[test]
+    fn test_compact_serialization_and_escaping() {
+        let json_value = JsonValue::object(vec![
+            ("text", JsonValue::Text("say \"hi\"\\\n\u{1}é".to_string())),
+            ("ratio", JsonValue::Float(0.25)),
+            ("whole", JsonValue::Float(3.0)),
+            ("nan", JsonValue::Float(f64::NAN)),
+            ("items", JsonValue::Array(vec![JsonValue::Integer(-2), JsonValue::Boolean(true), JsonValue::Null])),
+            ("empty", JsonValue::Object(Vec::new())),
+        ]);
+
+        assert_eq!(
+            json_value.to_json_text(false),
+            r#"{"text":"say \"hi\"\\\n\u0001é","ratio":0.25,"whole":3,"nan":null,"items":[-2,true,null],"empty":{}}"#
+        );
+    }
+
+    /// Test pretty-printing indentation
+    #[test]
+    fn test_pretty_serialization() {
+        let json_value = JsonValue::object(vec![
+            ("schema_version", JsonValue::Integer(1)),
+            ("warnings", JsonValue::Array(vec![JsonValue::Text("a".to_string())])),
+            ("columns", JsonValue::Array(Vec::new())),
+        ]);
+
+        assert_eq!(
+            json_value.to_json_text(true),
+            "{\n  \"schema_version\": 1,\n  \"warnings\": [\n    \"a\"\n  ],\n  \"columns\": []\n}"
+        );
+    }
+
+    /// Test error documents carry the kind, message and variant fields
+    #[test]
+    fn test_error_to_json() {
+        let csv_error_json = error_to_json(&create_csv_processing_error("Bad row", Some(7), None)).to_json_text(false);
+        assert!(csv_error_json.starts_with(r#"{"schema_version":1,"status":"error","error":{"kind":"csv_processing_error","message":"CSV processing failed: Bad row at line 7""#));
+        assert!(csv_error_json.ends_with(r#""csv_line_number":7,"csv_column_identifier":null}}"#));
+
+        let configuration_error_json = error_to_json(&create_configuration_error("No file")).to_json_text(false);
+        assert!(configuration_error_json.contains(r#""configuration_issue_description":"No file""#));
+        assert!(configuration_error_json.contains(r#""exit_code":9,"#));
+    }
+
+    /// Test extreme numbers stay valid JSON that parses back to the same value,
+    /// and keys are escaped like values
+    #[test]
+    fn test_extreme_numbers_and_escaped_keys() {
+        for float_value in [f64::MAX, f64::MIN_POSITIVE, 5e-324, -1e-7, 1e21] {
+            let number_text = JsonValue::Float(float_value).to_json_text(false);
+            assert!(number_text.chars().all(|number_character| number_character.is_ascii_digit() || "-.".contains(number_character)));
+            assert_eq!(number_text.parse::<f64>().expect("number should parse back"), float_value);
+        }
+        assert_eq!(JsonValue::Float(f64::NEG_INFINITY).to_json_text(false), "null");
+        assert_eq!(JsonValue::from_count(usize::MAX).to_json_text(false), i64::MAX.to_string());
+
+        let json_value = JsonValue::object(vec![("na\"me\n\u{1f}\u{7f}", JsonValue::Array(vec![JsonValue::Float(f64::INFINITY)]))]);
+        assert_eq!(json_value.to_json_text(false), "{\"na\\\"me\\n\\u001f\u{7f}\":[null]}");
+    }
+}
@@ -8,7 +8,10 @@
 ///
 /// # Usage
 /// ```bash
-/// rows_and_columns
+/// rows_and_columns                                   # choose a CSV file interactively
+/// rows_and_columns analyze data/cats.csv             # analysis + interactive menu
+/// rows_and_columns describe data/cats.csv --format json
+/// rows_and_columns help                              # all subcommands
 /// ```
 ///
 /// # Features
@@ -17,20 +20,15 @@
 /// - Renders ASCII/Unicode TUI charts (histogram, scatter, box-plot)
 /// - Integrates with FF file manager for file selection
 /// - Memory-efficient streaming data processing (no pre-loading)
-
-// ... doc comments ...
-
-mod error_types_module;
-mod manage_absolute_executable_directory_relative_paths;
-mod rows_and_columns_module;
-mod csv_processor_module;
-use rows_and_columns_module::run_rows_and_columns_application;
+/// - The same features as a library for embedding (see src/lib.rs)
+// The application lives in the library crate (src/lib.rs); this binary only runs it
+use rows_and_columns::{EXIT_CODE_SUCCESS, print_error_line, run_rows_and_columns_application};
 
 /// Application entry point - delegates to primary module
 /// 
 /// # Returns
-/// - `Ok(())` on successful execution
-/// - `Err(String)` with error description on failure
+/// - Exit code 0 on successful execution
+/// - The error's `exit_code()` (2-11, one per error variant) on failure
 /// 
 /// # Examples
 /// ```rust
@@ -44,12 +42,12 @@ fn main() {
     if let Err(error_message) = run_rows_and_columns_application() {
         
         // Display error to user with clear context
-        eprintln!("rows_and_columns application error: {}", error_message);
+        print_error_line(&format!("rows_and_columns application error: {}", error_message));
         
-        // Exit with error code indicating failure
-        std::process::exit(1);
+        // Exit with the error variant's own code so scripts can tell failures apart
+        std::process::exit(error_message.exit_code());
     }
     
     // Successful execution - exit with success code
-    std::process::exit(0);
+    std::process::exit(EXIT_CODE_SUCCESS);
 }
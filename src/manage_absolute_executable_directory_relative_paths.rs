@@ -95,7 +95,7 @@ pub fn get_absolute_path_to_executable_parentdirectory() -> Result<PathBuf, io::
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// use manage_absolute_executable_directory_relative_paths::make_input_path_name_abs_executabledirectoryrelative_nocheck;
 ///
 /// // Get an absolute path for "data/config.json" relative to the executable directory
@@ -299,7 +299,7 @@ pub fn make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path(
 ///
 /// # Examples
 ///
-/// ```
+/// ```ignore
 /// // Count subdirectories in "data/team_channels" relative to executable location
 /// let channel_count = count_subdirectories_executabledirectoryrelative_default_zero("data/team_channels");
 /// println!("Found {} team channels", channel_count);
@@ -453,6 +453,132 @@ pub fn prepare_file_parent_directories_abs_executabledirectoryrelative<P: AsRef<
     Ok(path)
 }
 
+/// Where `resolve_application_data_directory_path` found the data directory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataDirectoryResolutionSource {
+    /// Given on the command line
+    CommandLineFlag,
+
+    /// Named by the application's environment variable
+    EnvironmentVariable,
+
+    /// Under `$XDG_DATA_HOME` (or `~/.local/share` when that is unset)
+    XdgDataHome,
+
+    /// Next to the executable (when no home directory is known)
+    ExecutableDirectory,
+}
+
+impl DataDirectoryResolutionSource {
+    /// Short human-readable description, e.g. for a status line
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - e.g. "XDG data directory"
+    pub fn source_description(&self) -> &'static str {
+        match self {
+            DataDirectoryResolutionSource::CommandLineFlag => "command line flag",
+            DataDirectoryResolutionSource::EnvironmentVariable => "environment variable",
+            DataDirectoryResolutionSource::XdgDataHome => "XDG data directory",
+            DataDirectoryResolutionSource::ExecutableDirectory => "executable directory",
+        }
+    }
+}
+
+/// An absolute data directory path and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedApplicationDataDirectory {
+    /// Absolute path of the data directory (it may not exist yet)
+    pub data_directory_path: PathBuf,
+
+    /// Which step of the resolution order produced the path
+    pub resolution_source: DataDirectoryResolutionSource,
+}
+
+/// Resolves where an application keeps its data, for installs where the
+/// executable's directory is read-only (e.g. `/usr/local/bin`).
+///
+/// Resolution order, first match wins:
+/// 1. `command_line_data_directory` (a path given with a command line flag)
+/// 2. The environment variable `data_directory_environment_variable`
+/// 3. `$XDG_DATA_HOME/<data_directory_name>`, or `$HOME/.local/share/<data_directory_name>`
+///    when `XDG_DATA_HOME` is unset or not absolute (as the XDG spec requires)
+/// 4. `<executable directory>/<data_directory_name>`
+///
+/// Relative paths from steps 1 and 2 are resolved against the current working
+/// directory, since that is where the user typed them. Nothing is created.
+///
+/// # Arguments
+///
+/// * `command_line_data_directory` - Directory from the command line, if given
+/// * `data_directory_environment_variable` - e.g. "MY_APP_DATA_DIR"
+/// * `data_directory_name` - Directory name used under the XDG or executable directory
+///
+/// # Returns
+///
+/// * `Result<ResolvedApplicationDataDirectory, io::Error>` - The absolute path and its source
+///
+/// # Errors
+///
+/// - If the current working directory is needed but cannot be determined
+/// - If the executable's directory is needed but cannot be determined
+///
+/// # Examples
+///
+/// ```ignore
+/// let resolved_directory = resolve_application_data_directory_path(None, "MY_APP_DATA_DIR", "my_app")?;
+/// println!("Data in {} ({})", resolved_directory.data_directory_path.display(),
+///     resolved_directory.resolution_source.source_description());
+/// ```
+pub fn resolve_application_data_directory_path(
+    command_line_data_directory: Option<&str>,
+    data_directory_environment_variable: &str,
+    data_directory_name: &str,
+) -> Result<ResolvedApplicationDataDirectory, io::Error> {
+    // Step 1: An explicit directory from the command line
+    if let Some(command_line_directory) = command_line_data_directory
+        && !command_line_directory.is_empty()
+    {
+        return Ok(ResolvedApplicationDataDirectory {
+            data_directory_path: std::path::absolute(command_line_directory)?,
+            resolution_source: DataDirectoryResolutionSource::CommandLineFlag,
+        });
+    }
+
+    // Step 2: An explicit directory from the environment
+    if let Some(environment_directory) = std::env::var_os(data_directory_environment_variable)
+        && !environment_directory.is_empty()
+    {
+        return Ok(ResolvedApplicationDataDirectory {
+            data_directory_path: std::path::absolute(PathBuf::from(environment_directory))?,
+            resolution_source: DataDirectoryResolutionSource::EnvironmentVariable,
+        });
+    }
+
+    // Step 3: The per-user XDG data directory
+    let xdg_data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|xdg_data_home_path| xdg_data_home_path.is_absolute())
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .filter(|home_directory_path| home_directory_path.is_absolute())
+                .map(|home_directory_path| home_directory_path.join(".local").join("share"))
+        });
+    if let Some(xdg_data_home_path) = xdg_data_home {
+        return Ok(ResolvedApplicationDataDirectory {
+            data_directory_path: xdg_data_home_path.join(data_directory_name),
+            resolution_source: DataDirectoryResolutionSource::XdgDataHome,
+        });
+    }
+
+    // Step 4: Fall back to the executable's directory
+    Ok(ResolvedApplicationDataDirectory {
+        data_directory_path: get_absolute_path_to_executable_parentdirectory()?.join(data_directory_name),
+        resolution_source: DataDirectoryResolutionSource::ExecutableDirectory,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -676,4 +802,27 @@ mod tests {
         assert!(existing_file.exists());
         assert!(existing_file.is_file());
     }
+    
+    // Test a command line data directory wins and is made absolute against the CWD
+    #[test]
+    fn test_resolve_application_data_directory_from_command_line() {
+        let relative_directory = resolve_application_data_directory_path(
+            Some("my_data"), "TEST_UNSET_DATA_DIRECTORY_VARIABLE", "test_app_data"
+        ).unwrap();
+        assert_eq!(relative_directory.resolution_source, DataDirectoryResolutionSource::CommandLineFlag);
+        assert_eq!(relative_directory.data_directory_path, env::current_dir().unwrap().join("my_data"));
+        
+        let absolute_directory = resolve_application_data_directory_path(
+            Some("/srv/app_data"), "TEST_UNSET_DATA_DIRECTORY_VARIABLE", "test_app_data"
+        ).unwrap();
+        assert_eq!(absolute_directory.data_directory_path, PathBuf::from("/srv/app_data"));
+        
+        // Without a flag or variable the directory name ends the resolved path
+        let default_directory = resolve_application_data_directory_path(
+            None, "TEST_UNSET_DATA_DIRECTORY_VARIABLE", "test_app_data"
+        ).unwrap();
+        assert_ne!(default_directory.resolution_source, DataDirectoryResolutionSource::CommandLineFlag);
+        assert!(default_directory.data_directory_path.ends_with("test_app_data"));
+        assert!(default_directory.data_directory_path.is_absolute());
+    }
 }
\ No newline at end of file
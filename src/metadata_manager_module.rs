@@ -0,0 +1,622 @@
+// src/metadata_manager_module.rs
+
+/// TOML metadata reading for rows_and_columns
+///
+/// The CSV metadata files (`<name>.csv_metadata.toml`) and the directory store's
+/// `csv_metadata.toml` are written by this project. This module reads them back
+/// with a small, dependency-free TOML subset parser and turns them into typed
+/// column descriptions.
+///
+/// # Supported TOML Subset
+/// - Comments (`# ...`) and blank lines
+/// - Tables: `[table_name]`
+/// - Key/value pairs: `key = value`
+/// - Values: basic strings (`"..."` with `\"`, `\\`, `\n`, `\t` escapes),
+///   integers, floats, and booleans
+///
+/// # Core Responsibilities
+/// - Parse TOML text into an ordered document of tables and values
+/// - Escape strings for writing TOML safely
+/// - Read CSV metadata files into `CsvMetadataColumn` descriptions
+/// - Read and write derived column definitions (`[derived_column_N]` tables),
+///   which always follow the column tables at the end of a metadata file
+///
+/// # Design Philosophy
+/// - Vanilla Rust: no TOML crate, only what this project writes is supported
+/// - Clear errors: parse failures report the line number and the file path
+use std::path::Path;
+
+// Import column data type definitions
+use super::csv_processor_module::CsvColumnDataType;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_metadata_error,
+};
+
+/// A single parsed TOML value
+#[derive(Debug, Clone, PartialEq)]
+pub enum TomlValue {
+    /// Basic string value
+    Text(String),
+
+    /// Integer value
+    Integer(i64),
+
+    /// Floating point value
+    Float(f64),
+
+    /// Boolean value
+    Boolean(bool),
+}
+
+impl TomlValue {
+    /// Returns the value as text if it is a string
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The string content, or None for other types
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            TomlValue::Text(text_value) => Some(text_value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an integer if it is one
+    ///
+    /// # Returns
+    /// * `Option<i64>` - The integer, or None for other types
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            TomlValue::Integer(integer_value) => Some(*integer_value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a boolean if it is one
+    ///
+    /// # Returns
+    /// * `Option<bool>` - The boolean, or None for other types
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            TomlValue::Boolean(boolean_value) => Some(*boolean_value),
+            _ => None,
+        }
+    }
+}
+
+/// One TOML table (the root table has an empty name)
+#[derive(Debug, Clone)]
+pub struct TomlTable {
+    /// Table name from `[table_name]`, or empty for root-level keys
+    pub table_name: String,
+
+    /// Key/value pairs in file order
+    pub table_entries: Vec<(String, TomlValue)>,
+}
+
+impl TomlTable {
+    /// Looks up a value by key in this table
+    ///
+    /// # Arguments
+    /// * `entry_key` - The key to find
+    ///
+    /// # Returns
+    /// * `Option<&TomlValue>` - The value, or None if the key is absent
+    pub fn get_value(&self, entry_key: &str) -> Option<&TomlValue> {
+        self.table_entries
+            .iter()
+            .find(|(candidate_key, _)| candidate_key == entry_key)
+            .map(|(_, entry_value)| entry_value)
+    }
+}
+
+/// A parsed TOML document: tables in file order, root table first
+#[derive(Debug, Clone)]
+pub struct TomlDocument {
+    /// All tables, starting with the (possibly empty) root table
+    pub document_tables: Vec<TomlTable>,
+}
+
+impl TomlDocument {
+    /// Looks up a table by name (use "" for the root table)
+    ///
+    /// # Arguments
+    /// * `table_name` - The table to find
+    ///
+    /// # Returns
+    /// * `Option<&TomlTable>` - The table, or None if absent
+    pub fn get_table(&self, table_name: &str) -> Option<&TomlTable> {
+        self.document_tables
+            .iter()
+            .find(|candidate_table| candidate_table.table_name == table_name)
+    }
+}
+
+/// One column as described by a CSV metadata file
+#[derive(Debug, Clone)]
+pub struct CsvMetadataColumn {
+    /// 0-based column index in the CSV
+    pub column_index: usize,
+
+    /// Column name
+    pub column_name: String,
+
+    /// Declared data type
+    pub column_data_type: CsvColumnDataType,
+
+    /// Directory name in the directory store, if the metadata belongs to one
+    pub store_directory_name: Option<String>,
+}
+
+/// A computed column saved in a CSV metadata file (`age_months = age * 12`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedColumnDefinition {
+    /// Name of the new column
+    pub derived_column_name: String,
+
+    /// Expression text as the user typed it
+    pub derived_expression_text: String,
+
+    /// Result type determined when the expression was checked
+    pub derived_data_type: CsvColumnDataType,
+}
+
+/// Prefix of the tables holding derived column definitions
+const DERIVED_COLUMN_TABLE_PREFIX: &str = "derived_column_";
+
+/// Parses TOML text in the supported subset
+///
+/// # Arguments
+/// * `toml_text` - The TOML source text
+/// * `source_description` - File path or label used in error messages
+///
+/// # Returns
+/// * `RowsAndColumnsResult<TomlDocument>` - The parsed document or a metadata error
+///
+/// # Errors
+/// * `RowsAndColumnsError::MetadataError` - If a line cannot be parsed
+pub fn parse_toml_document(toml_text: &str, source_description: &str) -> RowsAndColumnsResult<TomlDocument> {
+    let mut document_tables = vec![TomlTable {
+        table_name: String::new(),
+        table_entries: Vec::new(),
+    }];
+
+    for (line_index, raw_line) in toml_text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let trimmed_line = raw_line.trim();
+
+        // Skip blank lines and whole-line comments
+        if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+            continue;
+        }
+
+        // Table header
+        if trimmed_line.starts_with('[') {
+            let header_without_comment = strip_trailing_toml_comment(trimmed_line);
+            if !header_without_comment.ends_with(']') || header_without_comment.len() < 3 {
+                return Err(create_metadata_error(
+                    &format!("Malformed table header on line {}: {}", line_number, trimmed_line),
+                    source_description
+                ));
+            }
+            let table_name = header_without_comment[1..header_without_comment.len() - 1].trim().to_string();
+            document_tables.push(TomlTable {
+                table_name,
+                table_entries: Vec::new(),
+            });
+            continue;
+        }
+
+        // Key = value
+        let (raw_key, raw_value) = trimmed_line.split_once('=').ok_or_else(|| {
+            create_metadata_error(
+                &format!("Expected 'key = value' on line {}: {}", line_number, trimmed_line),
+                source_description
+            )
+        })?;
+
+        let entry_key = raw_key.trim().trim_matches('"').to_string();
+        if entry_key.is_empty() {
+            return Err(create_metadata_error(
+                &format!("Empty key on line {}", line_number),
+                source_description
+            ));
+        }
+
+        let entry_value = parse_toml_value(raw_value.trim()).ok_or_else(|| {
+            create_metadata_error(
+                &format!("Unsupported or malformed value on line {}: {}", line_number, raw_value.trim()),
+                source_description
+            )
+        })?;
+
+        // There is always at least the root table, so last_mut() cannot fail
+        if let Some(current_table) = document_tables.last_mut() {
+            current_table.table_entries.push((entry_key, entry_value));
+        }
+    }
+
+    Ok(TomlDocument { document_tables })
+}
+
+/// Removes a trailing `# comment` that is outside of any string
+///
+/// # Arguments
+/// * `toml_fragment` - A line or value fragment
+///
+/// # Returns
+/// * `&str` - The fragment without the comment, trimmed
+fn strip_trailing_toml_comment(toml_fragment: &str) -> &str {
+    let mut inside_string = false;
+    let mut previous_was_backslash = false;
+
+    for (byte_position, fragment_character) in toml_fragment.char_indices() {
+        match fragment_character {
+            '"' if !previous_was_backslash => inside_string = !inside_string,
+            '#' if !inside_string => return toml_fragment[..byte_position].trim(),
+            _ => {}
+        }
+        previous_was_backslash = fragment_character == '\\' && !previous_was_backslash;
+    }
+
+    toml_fragment.trim()
+}
+
+/// Parses a single TOML value
+///
+/// # Arguments
+/// * `raw_value_text` - Trimmed text after the `=` sign
+///
+/// # Returns
+/// * `Option<TomlValue>` - Parsed value or None if unsupported/malformed
+fn parse_toml_value(raw_value_text: &str) -> Option<TomlValue> {
+    if raw_value_text.starts_with('"') {
+        return parse_toml_basic_string(raw_value_text).map(TomlValue::Text);
+    }
+
+    let value_text = strip_trailing_toml_comment(raw_value_text);
+
+    match value_text {
+        "true" => return Some(TomlValue::Boolean(true)),
+        "false" => return Some(TomlValue::Boolean(false)),
+        _ => {}
+    }
+
+    // TOML allows underscores between digits
+    let numeric_text = value_text.replace('_', "");
+
+    if let Ok(integer_value) = numeric_text.parse::<i64>() {
+        return Some(TomlValue::Integer(integer_value));
+    }
+
+    if let Ok(float_value) = numeric_text.parse::<f64>() {
+        return Some(TomlValue::Float(float_value));
+    }
+
+    None
+}
+
+/// Parses a double-quoted TOML basic string, ignoring any trailing comment
+///
+/// # Arguments
+/// * `quoted_text` - Text starting with `"`
+///
+/// # Returns
+/// * `Option<String>` - The unescaped string content, or None if unterminated
+fn parse_toml_basic_string(quoted_text: &str) -> Option<String> {
+    let mut unescaped_text = String::new();
+    let mut string_characters = quoted_text.chars().skip(1);
+
+    while let Some(string_character) = string_characters.next() {
+        match string_character {
+            '"' => {
+                // Anything after the closing quote must be a comment or nothing
+                let remaining_text: String = string_characters.collect();
+                let remaining_trimmed = remaining_text.trim();
+                if remaining_trimmed.is_empty() || remaining_trimmed.starts_with('#') {
+                    return Some(unescaped_text);
+                }
+                return None;
+            }
+            '\\' => match string_characters.next()? {
+                'n' => unescaped_text.push('\n'),
+                't' => unescaped_text.push('\t'),
+                'r' => unescaped_text.push('\r'),
+                '"' => unescaped_text.push('"'),
+                '\\' => unescaped_text.push('\\'),
+                other_character => {
+                    unescaped_text.push('\\');
+                    unescaped_text.push(other_character);
+                }
+            },
+            other_character => unescaped_text.push(other_character),
+        }
+    }
+
+    // No closing quote found
+    None
+}
+
+/// Escapes text for use inside a TOML basic string
+///
+/// # Arguments
+/// * `raw_text` - Text to escape
+///
+/// # Returns
+/// * `String` - Escaped text (without surrounding quotes)
+pub fn escape_toml_string_value(raw_text: &str) -> String {
+    let mut escaped_text = String::with_capacity(raw_text.len());
+
+    for text_character in raw_text.chars() {
+        match text_character {
+            '"' => escaped_text.push_str("\\\""),
+            '\\' => escaped_text.push_str("\\\\"),
+            '\n' => escaped_text.push_str("\\n"),
+            '\t' => escaped_text.push_str("\\t"),
+            '\r' => escaped_text.push_str("\\r"),
+            other_character => escaped_text.push(other_character),
+        }
+    }
+
+    escaped_text
+}
+
+/// Reads and parses a TOML file
+///
+/// # Arguments
+/// * `toml_file_path` - Path to the TOML file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<TomlDocument>` - Parsed document or error
+pub fn read_toml_document_file(toml_file_path: &Path) -> RowsAndColumnsResult<TomlDocument> {
+    let toml_text = std::fs::read_to_string(toml_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to read TOML file: {}", toml_file_path.display()),
+            io_error
+        )
+    })?;
+
+    parse_toml_document(&toml_text, &toml_file_path.display().to_string())
+}
+
+/// Reads the column descriptions from a CSV metadata TOML file
+///
+/// Column tables are named `column_1`, `column_2`, ... and hold `name`,
+/// `data_type` and `column_index` keys. Columns are returned ordered by index.
+///
+/// # Arguments
+/// * `metadata_file_path` - Path to the metadata TOML file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<CsvMetadataColumn>>` - Columns in index order
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If required keys are missing or invalid
+pub fn read_csv_metadata_columns(metadata_file_path: &Path) -> RowsAndColumnsResult<Vec<CsvMetadataColumn>> {
+    let metadata_document = read_toml_document_file(metadata_file_path)?;
+    let metadata_path_text = metadata_file_path.display().to_string();
+    let mut metadata_columns = Vec::new();
+
+    for metadata_table in &metadata_document.document_tables {
+        if !metadata_table.table_name.starts_with("column_") {
+            continue;
+        }
+
+        let column_name = metadata_table.get_value("name")
+            .and_then(|name_value| name_value.as_text())
+            .ok_or_else(|| {
+                create_metadata_error(
+                    &format!("Table [{}] is missing a string 'name'", metadata_table.table_name),
+                    &metadata_path_text
+                )
+            })?
+            .to_string();
+
+        let data_type_text = metadata_table.get_value("data_type")
+            .and_then(|data_type_value| data_type_value.as_text())
+            .ok_or_else(|| {
+                create_metadata_error(
+                    &format!("Table [{}] is missing a string 'data_type'", metadata_table.table_name),
+                    &metadata_path_text
+                )
+            })?;
+
+        let column_data_type = CsvColumnDataType::from_toml_string(data_type_text).ok_or_else(|| {
+            create_metadata_error(
+                &format!("Unknown data_type '{}' in table [{}]", data_type_text, metadata_table.table_name),
+                &metadata_path_text
+            )
+        })?;
+
+        let column_index = metadata_table.get_value("column_index")
+            .and_then(|index_value| index_value.as_integer())
+            .and_then(|index_integer| usize::try_from(index_integer).ok())
+            .ok_or_else(|| {
+                create_metadata_error(
+                    &format!("Table [{}] is missing a non-negative integer 'column_index'", metadata_table.table_name),
+                    &metadata_path_text
+                )
+            })?;
+
+        let store_directory_name = metadata_table.get_value("directory_name")
+            .and_then(|directory_value| directory_value.as_text())
+            .map(|directory_text| directory_text.to_string());
+
+        metadata_columns.push(CsvMetadataColumn {
+            column_index,
+            column_name,
+            column_data_type,
+            store_directory_name,
+        });
+    }
+
+    metadata_columns.sort_by_key(|metadata_column| metadata_column.column_index);
+
+    Ok(metadata_columns)
+}
+
+/// Reads the derived column definitions from a CSV metadata TOML file
+///
+/// Tables are named `derived_column_1`, `derived_column_2`, ... and hold
+/// `name`, `expression` and `data_type` keys. A missing file has no definitions.
+///
+/// # Arguments
+/// * `metadata_file_path` - Path to the metadata TOML file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<DerivedColumnDefinition>>` - Definitions in file order
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file exists but cannot be read
+/// * `RowsAndColumnsError::MetadataError` - If required keys are missing or invalid
+pub fn read_derived_column_definitions(metadata_file_path: &Path) -> RowsAndColumnsResult<Vec<DerivedColumnDefinition>> {
+    if !metadata_file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let metadata_document = read_toml_document_file(metadata_file_path)?;
+    let metadata_path_text = metadata_file_path.display().to_string();
+    let mut derived_column_definitions = Vec::new();
+
+    for metadata_table in &metadata_document.document_tables {
+        if !metadata_table.table_name.starts_with(DERIVED_COLUMN_TABLE_PREFIX) {
+            continue;
+        }
+
+        let read_text_entry = |entry_key: &str| {
+            metadata_table.get_value(entry_key)
+                .and_then(|entry_value| entry_value.as_text())
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    create_metadata_error(
+                        &format!("Table [{}] is missing a string '{}'", metadata_table.table_name, entry_key),
+                        &metadata_path_text
+                    )
+                })
+        };
+
+        let data_type_text = read_text_entry("data_type")?;
+        let derived_data_type = CsvColumnDataType::from_toml_string(&data_type_text).ok_or_else(|| {
+            create_metadata_error(
+                &format!("Unknown data_type '{}' in table [{}]", data_type_text, metadata_table.table_name),
+                &metadata_path_text
+            )
+        })?;
+
+        derived_column_definitions.push(DerivedColumnDefinition {
+            derived_column_name: read_text_entry("name")?,
+            derived_expression_text: read_text_entry("expression")?,
+            derived_data_type,
+        });
+    }
+
+    Ok(derived_column_definitions)
+}
+
+/// Formats derived column definitions as TOML tables
+///
+/// # Arguments
+/// * `derived_column_definitions` - Definitions in order
+///
+/// # Returns
+/// * `String` - `[derived_column_N]` tables, or an empty string for no definitions
+pub fn format_derived_column_definitions_toml(derived_column_definitions: &[DerivedColumnDefinition]) -> String {
+    let mut toml_content = String::new();
+
+    for (definition_position, derived_column_definition) in derived_column_definitions.iter().enumerate() {
+        toml_content.push_str(&format!("[{}{}]\n", DERIVED_COLUMN_TABLE_PREFIX, definition_position + 1));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_string_value(&derived_column_definition.derived_column_name)));
+        toml_content.push_str(&format!("expression = \"{}\"\n", escape_toml_string_value(&derived_column_definition.derived_expression_text)));
+        toml_content.push_str(&format!("data_type = \"{}\"\n", derived_column_definition.derived_data_type.to_toml_string()));
+        toml_content.push('\n');
+    }
+
+    toml_content
+}
+
+/// Replaces the derived column definitions in an existing metadata file
+///
+/// Everything before the first `[derived_column_N]` table is kept as written;
+/// the definitions are appended after it.
+///
+/// # Arguments
+/// * `metadata_file_path` - Path to the metadata TOML file
+/// * `derived_column_definitions` - The complete new list of definitions
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read or written
+pub fn write_derived_column_definitions(
+    metadata_file_path: &Path,
+    derived_column_definitions: &[DerivedColumnDefinition],
+) -> RowsAndColumnsResult<()> {
+    let existing_toml_text = std::fs::read_to_string(metadata_file_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to read metadata file: {}", metadata_file_path.display()),
+            io_error
+        )
+    })?;
+
+    let derived_table_header = format!("[{}", DERIVED_COLUMN_TABLE_PREFIX);
+    let mut toml_content: String = existing_toml_text
+        .lines()
+        .take_while(|toml_line| !toml_line.trim_start().starts_with(&derived_table_header))
+        .map(|toml_line| format!("{}\n", toml_line))
+        .collect();
+    toml_content.push_str(&format_derived_column_definitions_toml(derived_column_definitions));
+
+    std::fs::write(metadata_file_path, toml_content).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write metadata file: {}", metadata_file_path.display()),
+            io_error
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test parsing of the metadata format this project writes
+    #[test]
+    fn test_parse_metadata_style_document() {
+        let toml_text = "# CSV Metadata File\ntotal_columns = 2\n\n[column_1]\nname = \"id\"\ndata_type = \"integer\"\ncolumn_index = 0\n\n[column_2]\nname = \"say \\\"hi\\\"\" # comment\ndata_type = \"string\"\ncolumn_index = 1\n";
+        let parsed_document = parse_toml_document(toml_text, "test").expect("document should parse");
+
+        let root_table = parsed_document.get_table("").expect("root table");
+        assert_eq!(root_table.get_value("total_columns"), Some(&TomlValue::Integer(2)));
+
+        let second_column = parsed_document.get_table("column_2").expect("column_2 table");
+        assert_eq!(second_column.get_value("name").and_then(|value| value.as_text()), Some("say \"hi\""));
+    }
+
+    /// Test that malformed lines produce a metadata error with the line number
+    #[test]
+    fn test_parse_error_reports_line_number() {
+        let parse_result = parse_toml_document("[ok]\nthis line is wrong\n", "broken.toml");
+        match parse_result {
+            Err(parse_error) => {
+                let error_text = parse_error.to_string();
+                assert!(error_text.contains("line 2"));
+                assert!(error_text.contains("broken.toml"));
+            }
+            Ok(_) => panic!("Expected a parse error"),
+        }
+    }
+
+    /// Test that escaping and parsing round-trip
+    #[test]
+    fn test_escape_round_trip() {
+        let original_text = "a \"quoted\" \\ path\nwith newline";
+        let toml_line = format!("value = \"{}\"", escape_toml_string_value(original_text));
+        let parsed_document = parse_toml_document(&toml_line, "test").expect("should parse");
+        let parsed_value = parsed_document.get_table("").and_then(|table| table.get_value("value")).cloned();
+        assert_eq!(parsed_value, Some(TomlValue::Text(original_text.to_string())));
+    }
+}
@@ -0,0 +1,694 @@
+// src/regex_pattern_module.rs
+
+/// Small regular expression matcher for rows_and_columns filter expressions
+///
+/// The filter language's `matches` operator needs regular expressions, and the
+/// project uses only the standard library, so this module provides a compact
+/// matcher covering the commonly used syntax.
+///
+/// # Core Responsibilities
+/// - Parse a pattern into a node tree, reporting errors with a character position
+/// - Compile the node tree into a small instruction program
+/// - Search a text for any place where the pattern matches
+///
+/// # Supported Syntax
+/// - Literal characters, `.` (any character), `^` and `$` anchors
+/// - Character classes `[abc]`, `[a-z]`, `[^0-9]` and the shorthands
+///   `\d \w \s \D \W \S`; `\n` and `\t`; any other escaped character is literal
+/// - Groups `( )`, alternation `|`, and greedy quantifiers `* + ? {m} {m,} {m,n}`
+///
+/// # Design Philosophy
+/// - Search semantics: `matches "ab"` is true when "ab" appears anywhere; use
+///   `^` and `$` to require a whole-value match
+/// - Matching simulates the compiled program as a set of threads that advance
+///   together one character at a time (a Thompson/Pike NFA simulation); each
+///   instruction is visited at most once per text position, so matching time is
+///   linear in the text length and no input can recurse or backtrack without bound
+use super::error_types_module::{RowsAndColumnsError, RowsAndColumnsResult, create_filter_expression_error};
+
+/// Largest count accepted inside a `{m,n}` quantifier
+const MAXIMUM_REPETITION_COUNT: usize = 1_000;
+
+/// Largest compiled program accepted; nested counted quantifiers multiply
+const MAXIMUM_PROGRAM_INSTRUCTION_COUNT: usize = 100_000;
+
+/// One node of a parsed regular expression
+#[derive(Debug, Clone)]
+enum RegexPatternNode {
+    /// A single literal character
+    LiteralCharacter(char),
+
+    /// `.`: any single character
+    AnyCharacter,
+
+    /// `[...]` or a shorthand such as `\d`
+    CharacterClass {
+        /// Inclusive character ranges; single characters are `(c, c)`
+        character_ranges: Vec<(char, char)>,
+        /// True for `[^...]` and the upper-case shorthands
+        is_negated: bool,
+    },
+
+    /// `^`: matches only at the start of the text
+    StartOfText,
+
+    /// `$`: matches only at the end of the text
+    EndOfText,
+
+    /// `a|b|c`, and every group; each branch is a sequence of nodes
+    Alternation(Vec<Vec<RegexPatternNode>>),
+
+    /// A node followed by a quantifier
+    Repetition {
+        /// The quantified node
+        repeated_node: Box<RegexPatternNode>,
+        /// Fewest allowed repetitions
+        minimum_count: usize,
+        /// Most allowed repetitions, or `None` for unbounded
+        maximum_count: Option<usize>,
+    },
+}
+
+/// One instruction of a compiled regular expression program
+#[derive(Debug, Clone)]
+enum RegexInstruction {
+    /// Consumes one character equal to this one
+    MatchCharacter(char),
+
+    /// Consumes any one character
+    MatchAnyCharacter,
+
+    /// Consumes one character inside (or, when negated, outside) the ranges
+    MatchCharacterClass {
+        /// Inclusive character ranges; single characters are `(c, c)`
+        character_ranges: Vec<(char, char)>,
+        /// True for `[^...]` and the upper-case shorthands
+        is_negated: bool,
+    },
+
+    /// Continues only at the start of the text
+    AssertStartOfText,
+
+    /// Continues only at the end of the text
+    AssertEndOfText,
+
+    /// Continues at both instruction indexes
+    Split(usize, usize),
+
+    /// Continues at the instruction index
+    Jump(usize),
+
+    /// The whole pattern has matched
+    MatchFound,
+}
+
+impl RegexInstruction {
+    /// Checks whether a consuming instruction accepts the character
+    ///
+    /// # Arguments
+    /// * `candidate_character` - The text character at the thread's position
+    ///
+    /// # Returns
+    /// * `bool` - True when the instruction consumes the character; always
+    ///   false for non-consuming instructions
+    fn accepts_character(&self, candidate_character: char) -> bool {
+        match self {
+            RegexInstruction::MatchCharacter(literal_character) => *literal_character == candidate_character,
+            RegexInstruction::MatchAnyCharacter => true,
+            RegexInstruction::MatchCharacterClass { character_ranges, is_negated } => {
+                let is_in_class = character_ranges
+                    .iter()
+                    .any(|&(range_start, range_end)| range_start <= candidate_character && candidate_character <= range_end);
+                is_in_class != *is_negated
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A parsed regular expression ready for matching
+#[derive(Debug, Clone)]
+pub struct CompiledRegexPattern {
+    /// Instruction program; execution starts at index 0
+    program_instructions: Vec<RegexInstruction>,
+}
+
+impl CompiledRegexPattern {
+    /// Checks whether the pattern matches anywhere in the text
+    ///
+    /// # Arguments
+    /// * `candidate_text` - The text to search
+    ///
+    /// # Returns
+    /// * `bool` - True when some substring matches the pattern
+    pub fn is_match(&self, candidate_text: &str) -> bool {
+        let candidate_characters: Vec<char> = candidate_text.chars().collect();
+        let mut visited_generations = vec![usize::MAX; self.program_instructions.len()];
+        let mut pending_instructions = Vec::new();
+        let mut current_threads = Vec::new();
+        let mut next_threads = Vec::new();
+
+        for current_position in 0..=candidate_characters.len() {
+            // Step 1: A fresh thread at every position makes this a search, not a prefix match
+            if self.add_thread(0, current_position, &candidate_characters, &mut visited_generations, &mut pending_instructions, &mut current_threads) {
+                return true;
+            }
+
+            // Step 2: Advance every waiting thread over the next character
+            let Some(&current_character) = candidate_characters.get(current_position) else {
+                break;
+            };
+            next_threads.clear();
+            for &thread_instruction in &current_threads {
+                if self.program_instructions[thread_instruction].accepts_character(current_character)
+                    && self.add_thread(
+                        thread_instruction + 1,
+                        current_position + 1,
+                        &candidate_characters,
+                        &mut visited_generations,
+                        &mut pending_instructions,
+                        &mut next_threads,
+                    )
+                {
+                    return true;
+                }
+            }
+            std::mem::swap(&mut current_threads, &mut next_threads);
+        }
+
+        false
+    }
+
+    /// Follows non-consuming instructions from one start and queues the threads
+    ///
+    /// # Arguments
+    /// * `start_instruction` - Instruction index the new thread starts at
+    /// * `thread_position` - Text position of the new thread
+    /// * `candidate_characters` - The text being searched, for the anchors
+    /// * `visited_generations` - Per instruction, the last position it was visited at
+    /// * `pending_instructions` - Reusable work stack
+    /// * `thread_list` - Receives the consuming instructions reached
+    ///
+    /// # Returns
+    /// * `bool` - True when `MatchFound` is reachable without consuming input
+    fn add_thread(
+        &self,
+        start_instruction: usize,
+        thread_position: usize,
+        candidate_characters: &[char],
+        visited_generations: &mut [usize],
+        pending_instructions: &mut Vec<usize>,
+        thread_list: &mut Vec<usize>,
+    ) -> bool {
+        pending_instructions.clear();
+        pending_instructions.push(start_instruction);
+
+        while let Some(instruction_index) = pending_instructions.pop() {
+            // Step 1: Each instruction joins the list at most once per position,
+            // which also stops empty loops such as `(a*)*`
+            if visited_generations[instruction_index] == thread_position {
+                continue;
+            }
+            visited_generations[instruction_index] = thread_position;
+
+            match &self.program_instructions[instruction_index] {
+                RegexInstruction::Jump(target_instruction) => pending_instructions.push(*target_instruction),
+                RegexInstruction::Split(first_instruction, second_instruction) => {
+                    pending_instructions.push(*second_instruction);
+                    pending_instructions.push(*first_instruction);
+                }
+                RegexInstruction::AssertStartOfText => {
+                    if thread_position == 0 {
+                        pending_instructions.push(instruction_index + 1);
+                    }
+                }
+                RegexInstruction::AssertEndOfText => {
+                    if thread_position == candidate_characters.len() {
+                        pending_instructions.push(instruction_index + 1);
+                    }
+                }
+                RegexInstruction::MatchFound => return true,
+                _ => thread_list.push(instruction_index),
+            }
+        }
+
+        false
+    }
+}
+
+/// Parses a regular expression pattern
+///
+/// # Arguments
+/// * `pattern_text` - The pattern to parse
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CompiledRegexPattern>` - The compiled pattern
+///
+/// # Errors
+/// * `FilterExpressionError` - Positioned at the offending character of the
+///   pattern (unclosed group or class, nothing to repeat, bad repetition count)
+pub fn compile_regex_pattern(pattern_text: &str) -> RowsAndColumnsResult<CompiledRegexPattern> {
+    let mut pattern_parser = RegexPatternParser {
+        pattern_text,
+        pattern_characters: pattern_text.chars().collect(),
+        current_position: 0,
+    };
+
+    let root_pattern_node = pattern_parser.parse_alternation()?;
+
+    // Step 1: The only way to stop early at the top level is an unmatched `)`
+    if pattern_parser.current_position < pattern_parser.pattern_characters.len() {
+        return Err(pattern_parser.error_at(pattern_parser.current_position, "unmatched ')' in regex"));
+    }
+
+    // Step 2: Lower the node tree to an instruction program
+    let mut program_instructions = Vec::new();
+    if !emit_node_instructions(&root_pattern_node, &mut program_instructions) {
+        return Err(pattern_parser.error_at(
+            0,
+            &format!("regex is too large once repetition counts are expanded (limit {} instructions)", MAXIMUM_PROGRAM_INSTRUCTION_COUNT)
+        ));
+    }
+    program_instructions.push(RegexInstruction::MatchFound);
+
+    Ok(CompiledRegexPattern { program_instructions })
+}
+
+/// Appends the instructions for one node to the program
+///
+/// Counted quantifiers are expanded into copies of their node: `a{2,4}` becomes
+/// `aa` followed by two optional `a`, and unbounded quantifiers become a loop.
+///
+/// # Arguments
+/// * `pattern_node` - The node to compile
+/// * `program_instructions` - The program being built
+///
+/// # Returns
+/// * `bool` - False when the program grew past `MAXIMUM_PROGRAM_INSTRUCTION_COUNT`
+fn emit_node_instructions(pattern_node: &RegexPatternNode, program_instructions: &mut Vec<RegexInstruction>) -> bool {
+    if program_instructions.len() > MAXIMUM_PROGRAM_INSTRUCTION_COUNT {
+        return false;
+    }
+
+    match pattern_node {
+        RegexPatternNode::LiteralCharacter(literal_character) => {
+            program_instructions.push(RegexInstruction::MatchCharacter(*literal_character));
+        }
+        RegexPatternNode::AnyCharacter => program_instructions.push(RegexInstruction::MatchAnyCharacter),
+        RegexPatternNode::CharacterClass { character_ranges, is_negated } => {
+            program_instructions.push(RegexInstruction::MatchCharacterClass {
+                character_ranges: character_ranges.clone(),
+                is_negated: *is_negated,
+            });
+        }
+        RegexPatternNode::StartOfText => program_instructions.push(RegexInstruction::AssertStartOfText),
+        RegexPatternNode::EndOfText => program_instructions.push(RegexInstruction::AssertEndOfText),
+        RegexPatternNode::Alternation(alternative_branches) => {
+            // Step 1: Every branch but the last is `Split(branch, next split)`
+            // and ends with a jump past the remaining branches
+            let mut exit_jump_indexes = Vec::new();
+            for (branch_index, branch_nodes) in alternative_branches.iter().enumerate() {
+                let is_last_branch = branch_index + 1 == alternative_branches.len();
+                let split_index = program_instructions.len();
+                if !is_last_branch {
+                    program_instructions.push(RegexInstruction::Split(split_index + 1, 0));
+                }
+
+                for branch_node in branch_nodes {
+                    if !emit_node_instructions(branch_node, program_instructions) {
+                        return false;
+                    }
+                }
+
+                if !is_last_branch {
+                    exit_jump_indexes.push(program_instructions.len());
+                    program_instructions.push(RegexInstruction::Jump(0));
+                    program_instructions[split_index] = RegexInstruction::Split(split_index + 1, program_instructions.len());
+                }
+            }
+
+            // Step 2: Point the exit jumps past the last branch
+            let exit_index = program_instructions.len();
+            for jump_index in exit_jump_indexes {
+                program_instructions[jump_index] = RegexInstruction::Jump(exit_index);
+            }
+        }
+        RegexPatternNode::Repetition { repeated_node, minimum_count, maximum_count } => {
+            // Step 1: The required copies
+            for _ in 0..*minimum_count {
+                if !emit_node_instructions(repeated_node, program_instructions) {
+                    return false;
+                }
+            }
+
+            match maximum_count {
+                // Step 2a: Each optional copy may be skipped to the end
+                Some(maximum_count) => {
+                    let mut skip_split_indexes = Vec::new();
+                    for _ in *minimum_count..*maximum_count {
+                        let split_index = program_instructions.len();
+                        skip_split_indexes.push(split_index);
+                        program_instructions.push(RegexInstruction::Split(split_index + 1, 0));
+                        if !emit_node_instructions(repeated_node, program_instructions) {
+                            return false;
+                        }
+                    }
+                    let exit_index = program_instructions.len();
+                    for split_index in skip_split_indexes {
+                        program_instructions[split_index] = RegexInstruction::Split(split_index + 1, exit_index);
+                    }
+                }
+                // Step 2b: An unbounded tail loops back to its split
+                None => {
+                    let loop_index = program_instructions.len();
+                    program_instructions.push(RegexInstruction::Split(loop_index + 1, 0));
+                    if !emit_node_instructions(repeated_node, program_instructions) {
+                        return false;
+                    }
+                    program_instructions.push(RegexInstruction::Jump(loop_index));
+                    program_instructions[loop_index] = RegexInstruction::Split(loop_index + 1, program_instructions.len());
+                }
+            }
+        }
+    }
+
+    program_instructions.len() <= MAXIMUM_PROGRAM_INSTRUCTION_COUNT
+}
+
+/// Recursive-descent parser state for one pattern
+struct RegexPatternParser<'a> {
+    /// The pattern as written, for error messages
+    pattern_text: &'a str,
+    /// The pattern split into characters
+    pattern_characters: Vec<char>,
+    /// Index of the next character to read
+    current_position: usize,
+}
+
+impl RegexPatternParser<'_> {
+    /// Builds a positioned error for this pattern
+    fn error_at(&self, error_character_position: usize, error_description: &str) -> RowsAndColumnsError {
+        create_filter_expression_error(self.pattern_text, error_character_position, error_description)
+    }
+
+    /// Returns the next character without consuming it
+    fn peek_character(&self) -> Option<char> {
+        self.pattern_characters.get(self.current_position).copied()
+    }
+
+    /// Parses `branch|branch|...` up to the end of the pattern or a `)`
+    fn parse_alternation(&mut self) -> RowsAndColumnsResult<RegexPatternNode> {
+        let mut alternative_branches = vec![self.parse_sequence()?];
+
+        while self.peek_character() == Some('|') {
+            self.current_position += 1;
+            alternative_branches.push(self.parse_sequence()?);
+        }
+
+        Ok(RegexPatternNode::Alternation(alternative_branches))
+    }
+
+    /// Parses quantified atoms until `|`, `)` or the end of the pattern
+    fn parse_sequence(&mut self) -> RowsAndColumnsResult<Vec<RegexPatternNode>> {
+        let mut sequence_nodes = Vec::new();
+
+        while let Some(next_character) = self.peek_character() {
+            if next_character == '|' || next_character == ')' {
+                break;
+            }
+
+            let atom_node = self.parse_atom()?;
+            sequence_nodes.push(self.parse_optional_quantifier(atom_node)?);
+        }
+
+        Ok(sequence_nodes)
+    }
+
+    /// Parses one atom: literal, `.`, anchor, escape, class or group
+    fn parse_atom(&mut self) -> RowsAndColumnsResult<RegexPatternNode> {
+        let atom_position = self.current_position;
+        let atom_character = self.pattern_characters[atom_position];
+        self.current_position += 1;
+
+        match atom_character {
+            '(' => {
+                let group_node = self.parse_alternation()?;
+                if self.peek_character() != Some(')') {
+                    return Err(self.error_at(atom_position, "unclosed '(' in regex"));
+                }
+                self.current_position += 1;
+                Ok(group_node)
+            }
+            '[' => self.parse_character_class(atom_position),
+            '.' => Ok(RegexPatternNode::AnyCharacter),
+            '^' => Ok(RegexPatternNode::StartOfText),
+            '$' => Ok(RegexPatternNode::EndOfText),
+            '\\' => {
+                let escaped_character = self.peek_character()
+                    .ok_or_else(|| self.error_at(atom_position, "pattern ends with a lone '\\'"))?;
+                self.current_position += 1;
+                Ok(build_escape_node(escaped_character))
+            }
+            '*' | '+' | '?' | '{' => Err(self.error_at(atom_position, &format!("nothing to repeat before '{}'", atom_character))),
+            _ => Ok(RegexPatternNode::LiteralCharacter(atom_character)),
+        }
+    }
+
+    /// Parses the body of `[...]`; the opening bracket is already consumed
+    fn parse_character_class(&mut self, open_bracket_position: usize) -> RowsAndColumnsResult<RegexPatternNode> {
+        let mut character_ranges = Vec::new();
+        let is_negated = self.peek_character() == Some('^');
+        if is_negated {
+            self.current_position += 1;
+        }
+
+        let mut is_first_member = true;
+        loop {
+            let member_character = self.peek_character()
+                .ok_or_else(|| self.error_at(open_bracket_position, "unclosed '[' in regex"))?;
+            self.current_position += 1;
+
+            // Step 1: `]` closes the class, except as the very first member
+            if member_character == ']' && !is_first_member {
+                break;
+            }
+            is_first_member = false;
+
+            // Step 2: Shorthand classes and escaped characters
+            let range_start = if member_character == '\\' {
+                let escaped_character = self.peek_character()
+                    .ok_or_else(|| self.error_at(open_bracket_position, "unclosed '[' in regex"))?;
+                self.current_position += 1;
+                match build_escape_node(escaped_character) {
+                    RegexPatternNode::CharacterClass { character_ranges: shorthand_ranges, is_negated: false } => {
+                        character_ranges.extend(shorthand_ranges);
+                        continue;
+                    }
+                    RegexPatternNode::LiteralCharacter(literal_character) => literal_character,
+                    _ => return Err(self.error_at(self.current_position - 2, "negated shorthand classes are not supported inside [...]")),
+                }
+            } else {
+                member_character
+            };
+
+            // Step 3: `a-z` ranges; a `-` before `]` is a literal dash
+            let is_range = self.peek_character() == Some('-')
+                && self.pattern_characters.get(self.current_position + 1).is_some_and(|&end| end != ']');
+            if is_range {
+                let range_end = self.pattern_characters[self.current_position + 1];
+                if range_end < range_start {
+                    return Err(self.error_at(self.current_position - 1, "character range is out of order"));
+                }
+                self.current_position += 2;
+                character_ranges.push((range_start, range_end));
+            } else {
+                character_ranges.push((range_start, range_start));
+            }
+        }
+
+        Ok(RegexPatternNode::CharacterClass { character_ranges, is_negated })
+    }
+
+    /// Wraps the atom in a repetition when a quantifier follows
+    fn parse_optional_quantifier(&mut self, atom_node: RegexPatternNode) -> RowsAndColumnsResult<RegexPatternNode> {
+        let quantifier_position = self.current_position;
+        let (minimum_count, maximum_count) = match self.peek_character() {
+            Some('{') => self.parse_counted_quantifier()?,
+            Some(quantifier_character @ ('*' | '+' | '?')) => {
+                self.current_position += 1;
+                match quantifier_character {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                }
+            }
+            _ => return Ok(atom_node),
+        };
+
+        if matches!(atom_node, RegexPatternNode::StartOfText | RegexPatternNode::EndOfText) {
+            return Err(self.error_at(quantifier_position, "an anchor cannot be repeated"));
+        }
+
+        Ok(RegexPatternNode::Repetition {
+            repeated_node: Box::new(atom_node),
+            minimum_count,
+            maximum_count,
+        })
+    }
+
+    /// Parses `{m}`, `{m,}` or `{m,n}` and consumes it
+    fn parse_counted_quantifier(&mut self) -> RowsAndColumnsResult<(usize, Option<usize>)> {
+        let open_brace_position = self.current_position;
+        let closing_offset = self.pattern_characters[open_brace_position..]
+            .iter()
+            .position(|&character| character == '}')
+            .ok_or_else(|| self.error_at(open_brace_position, "unclosed '{' in regex"))?;
+
+        let count_text: String = self.pattern_characters[open_brace_position + 1..open_brace_position + closing_offset]
+            .iter()
+            .collect();
+        let parse_count = |count_part: &str| -> Option<usize> {
+            count_part.trim().parse::<usize>().ok().filter(|&count| count <= MAXIMUM_REPETITION_COUNT)
+        };
+
+        let parsed_counts = match count_text.split_once(',') {
+            None => parse_count(&count_text).map(|exact_count| (exact_count, Some(exact_count))),
+            Some((minimum_part, maximum_part)) if maximum_part.trim().is_empty() => {
+                parse_count(minimum_part).map(|minimum_count| (minimum_count, None))
+            }
+            Some((minimum_part, maximum_part)) => match (parse_count(minimum_part), parse_count(maximum_part)) {
+                (Some(minimum_count), Some(maximum_count)) if minimum_count <= maximum_count => {
+                    Some((minimum_count, Some(maximum_count)))
+                }
+                _ => None,
+            },
+        };
+
+        let (minimum_count, maximum_count) = parsed_counts.ok_or_else(|| {
+            self.error_at(
+                open_brace_position,
+                &format!("invalid repetition count {{{}}} (expected {{m}}, {{m,}} or {{m,n}} up to {})", count_text, MAXIMUM_REPETITION_COUNT)
+            )
+        })?;
+
+        self.current_position = open_brace_position + closing_offset + 1;
+        Ok((minimum_count, maximum_count))
+    }
+}
+
+/// Builds the node for an escaped character (`\d`, `\n`, `\.` ...)
+///
+/// # Arguments
+/// * `escaped_character` - The character after the backslash
+///
+/// # Returns
+/// * `RegexPatternNode` - A class for shorthands, otherwise a literal
+fn build_escape_node(escaped_character: char) -> RegexPatternNode {
+    let digit_ranges = vec![('0', '9')];
+    let word_ranges = vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+    let space_ranges = vec![(' ', ' '), ('\t', '\r')];
+
+    match escaped_character {
+        'd' => RegexPatternNode::CharacterClass { character_ranges: digit_ranges, is_negated: false },
+        'w' => RegexPatternNode::CharacterClass { character_ranges: word_ranges, is_negated: false },
+        's' => RegexPatternNode::CharacterClass { character_ranges: space_ranges, is_negated: false },
+        'D' => RegexPatternNode::CharacterClass { character_ranges: digit_ranges, is_negated: true },
+        'W' => RegexPatternNode::CharacterClass { character_ranges: word_ranges, is_negated: true },
+        'S' => RegexPatternNode::CharacterClass { character_ranges: space_ranges, is_negated: true },
+        'n' => RegexPatternNode::LiteralCharacter('\n'),
+        't' => RegexPatternNode::LiteralCharacter('\t'),
+        _ => RegexPatternNode::LiteralCharacter(escaped_character),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Literals, anchors, classes and shorthands
+    #[test]
+    fn test_regex_literals_anchors_and_classes() {
+        let siamese_pattern = compile_regex_pattern("^Sia").expect("pattern should compile");
+        assert!(siamese_pattern.is_match("Siamese"));
+        assert!(!siamese_pattern.is_match("Not Siamese"));
+
+        let digit_pattern = compile_regex_pattern(r"^\d+$").expect("pattern should compile");
+        assert!(digit_pattern.is_match("2024"));
+        assert!(!digit_pattern.is_match("20x4"));
+        assert!(!digit_pattern.is_match(""));
+
+        let class_pattern = compile_regex_pattern("[^a-z]at").expect("pattern should compile");
+        assert!(class_pattern.is_match("the Cat"));
+        assert!(!class_pattern.is_match("the cat"));
+    }
+
+    /// Alternation inside groups and the different quantifiers
+    #[test]
+    fn test_regex_groups_alternation_and_quantifiers() {
+        let colour_pattern = compile_regex_pattern("^(black|white)( and (black|white))?$").expect("pattern should compile");
+        assert!(colour_pattern.is_match("black"));
+        assert!(colour_pattern.is_match("white and black"));
+        assert!(!colour_pattern.is_match("black and grey"));
+
+        let counted_pattern = compile_regex_pattern("^a{2,3}b*$").expect("pattern should compile");
+        assert!(counted_pattern.is_match("aab"));
+        assert!(counted_pattern.is_match("aaabbb"));
+        assert!(!counted_pattern.is_match("ab"));
+        assert!(!counted_pattern.is_match("aaaab"));
+
+        let empty_loop_pattern = compile_regex_pattern("^(a*)*$").expect("pattern should compile");
+        assert!(empty_loop_pattern.is_match("aaa"));
+        assert!(!empty_loop_pattern.is_match("aab"));
+    }
+
+    /// Malformed patterns report the offending position
+    #[test]
+    fn test_regex_compile_errors_have_positions() {
+        for (bad_pattern, expected_position) in [("ab(cd", 2), ("[abc", 0), ("*a", 0), ("a{x}", 1), ("ab)", 2)] {
+            match compile_regex_pattern(bad_pattern) {
+                Err(RowsAndColumnsError::FilterExpressionError { error_character_position, .. }) => {
+                    assert_eq!(error_character_position, expected_position, "pattern {}", bad_pattern);
+                }
+                other => panic!("Expected FilterExpressionError for {}, got {:?}", bad_pattern, other),
+            }
+        }
+    }
+
+    /// Very long cells are matched without recursion, so the stack cannot overflow
+    #[test]
+    fn test_regex_long_input_does_not_overflow_the_stack() {
+        let leading_wildcard_pattern = compile_regex_pattern("^.*x").expect("pattern should compile");
+        let mut long_cell_value = "a".repeat(200_000);
+        assert!(!leading_wildcard_pattern.is_match(&long_cell_value));
+        long_cell_value.push('x');
+        assert!(leading_wildcard_pattern.is_match(&long_cell_value));
+
+        let repeated_group_pattern = compile_regex_pattern("^(ab)+$").expect("pattern should compile");
+        assert!(repeated_group_pattern.is_match(&"ab".repeat(100_000)));
+    }
+
+    /// Patterns that are exponential for a backtracker finish in linear time
+    #[test]
+    fn test_regex_pathological_patterns_finish_quickly() {
+        let start_time = std::time::Instant::now();
+
+        let ambiguous_alternation_pattern = compile_regex_pattern("^(a|a)*b").expect("pattern should compile");
+        assert!(!ambiguous_alternation_pattern.is_match(&"a".repeat(30)));
+        assert!(ambiguous_alternation_pattern.is_match(&format!("{}b", "a".repeat(30))));
+
+        let nested_star_pattern = compile_regex_pattern("^(a*)*$").expect("pattern should compile");
+        assert!(!nested_star_pattern.is_match(&format!("{}!", "a".repeat(10_000))));
+
+        let counted_optional_pattern = compile_regex_pattern("^(a?){30}a{30}$").expect("pattern should compile");
+        assert!(counted_optional_pattern.is_match(&"a".repeat(30)));
+
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(5), "matching took {:?}", start_time.elapsed());
+    }
+
+    /// Nested counted quantifiers cannot expand into an unbounded program
+    #[test]
+    fn test_regex_oversized_expansion_is_rejected() {
+        assert!(compile_regex_pattern("(a{1000}){1000}").is_err());
+        assert!(compile_regex_pattern("(a{100}){100}").is_ok());
+    }
+}
@@ -0,0 +1,482 @@
+// src/row_sampling_module.rs
+
+/// Random row sampling for rows_and_columns ("1,000 random rows of a huge file")
+///
+/// A sample is drawn in a single pass over the rows and written as a new CSV
+/// file with its own `.csv_metadata.toml`, so it can be analyzed like any
+/// other file for a quick, approximate describe.
+///
+/// # Core Responsibilities
+/// - Uniform sampling of N rows with a reservoir (every row equally likely)
+/// - Stratified sampling: up to N rows from each value of a categorical
+///   column, so rare categories are as well represented as common ones
+/// - Seeded random generator shared with other reservoirs (group-by medians)
+/// - Write the sampled rows in their original order, plus metadata
+///
+/// # Design Philosophy
+/// - One pass, bounded memory: only the reservoirs are held, never the file
+/// - Reproducible: the same seed and input always give the same sample
+/// - Sampled rows keep their input order so the sample reads like the file
+/// - Rows come from the CSV file or the directory store, like sort and join
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// Import the row stream control and the export writer
+use super::csv_processor_module::{CsvRowStreamControl, StreamingCsvExportWriter};
+
+// Import the shared row source so sampled rows can come from an imported dataset
+use super::directory_store_module::DatasetRowSource;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_csv_processing_error,
+    create_statistical_analysis_error,
+};
+
+/// Seed used when the user does not give one
+pub const DEFAULT_SAMPLING_SEED: u64 = 42;
+
+/// Largest number of strata before a stratified sample is refused
+pub const MAXIMUM_STRATUM_COUNT: usize = 10_000;
+
+/// Label shown for the stratum of empty values
+const EMPTY_STRATUM_LABEL: &str = "(empty)";
+
+/// Small xorshift generator used for reservoir sampling
+#[derive(Debug, Clone)]
+pub struct ReservoirSamplingGenerator {
+    generator_state: u64,
+}
+
+impl ReservoirSamplingGenerator {
+    /// Creates a generator whose sequence depends only on the seed
+    ///
+    /// The seed is scrambled first (splitmix64), so nearby seeds such as
+    /// 1 and 2 give unrelated sequences and 0 is a valid seed.
+    ///
+    /// # Arguments
+    /// * `sampling_seed` - Any value; the same seed gives the same sequence
+    ///
+    /// # Returns
+    /// * `ReservoirSamplingGenerator` - The seeded generator
+    pub fn from_seed(sampling_seed: u64) -> ReservoirSamplingGenerator {
+        let mut scrambled_seed = sampling_seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        scrambled_seed = (scrambled_seed ^ (scrambled_seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        scrambled_seed = (scrambled_seed ^ (scrambled_seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        scrambled_seed ^= scrambled_seed >> 31;
+
+        // xorshift never leaves the all-zero state
+        ReservoirSamplingGenerator {
+            generator_state: if scrambled_seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { scrambled_seed },
+        }
+    }
+
+    /// Returns a value in `0..upper_bound` (upper_bound must be positive)
+    pub fn next_index_below(&mut self, upper_bound: usize) -> usize {
+        self.generator_state ^= self.generator_state << 13;
+        self.generator_state ^= self.generator_state >> 7;
+        self.generator_state ^= self.generator_state << 17;
+        (self.generator_state % upper_bound as u64) as usize
+    }
+}
+
+/// How rows are chosen
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowSamplingMethod {
+    /// Every row has the same chance; at most `sample_row_count` rows
+    Uniform {
+        sample_row_count: usize,
+    },
+
+    /// Up to `rows_per_stratum` rows for each distinct value of a column
+    Stratified {
+        /// 0-based position of the column whose values form the strata
+        stratum_column_position: usize,
+        rows_per_stratum: usize,
+    },
+}
+
+/// What to sample and with which seed
+#[derive(Debug, Clone)]
+pub struct RowSamplingRequest {
+    /// Uniform or stratified
+    pub sampling_method: RowSamplingMethod,
+
+    /// Seed for the random generator
+    pub sampling_seed: u64,
+}
+
+/// Rows seen and kept for one stratum
+#[derive(Debug, Clone, PartialEq)]
+pub struct StratumSampleCount {
+    /// The column value (`(empty)` for empty cells)
+    pub stratum_value: String,
+
+    /// Rows with this value in the input
+    pub population_row_count: usize,
+
+    /// Rows with this value in the sample
+    pub sampled_row_count: usize,
+}
+
+/// Outcome of drawing a sample
+#[derive(Debug, Clone)]
+pub struct RowSampleSummary {
+    /// Path of the sample CSV file
+    pub sampled_csv_file_path: PathBuf,
+
+    /// Path of its metadata file
+    pub metadata_file_path: PathBuf,
+
+    /// Rows read from the input
+    pub scanned_row_count: usize,
+
+    /// Rows written to the sample
+    pub sampled_row_count: usize,
+
+    /// Per-stratum counts, sorted by value (empty for uniform samples)
+    pub stratum_sample_counts: Vec<StratumSampleCount>,
+}
+
+/// A fixed-capacity reservoir of (row index, row values)
+#[derive(Debug, Default)]
+struct RowReservoir {
+    /// Rows offered so far
+    offered_row_count: usize,
+    /// Rows currently kept
+    kept_rows: Vec<(usize, Vec<String>)>,
+}
+
+impl RowReservoir {
+    /// Offers one row (Algorithm R): the first `capacity` rows are kept, after
+    /// that row `n` replaces a random kept row with probability `capacity / n`
+    fn offer_row(
+        &mut self,
+        row_index: usize,
+        row_values: &[String],
+        capacity: usize,
+        sampling_generator: &mut ReservoirSamplingGenerator,
+    ) {
+        self.offered_row_count += 1;
+        if self.kept_rows.len() < capacity {
+            self.kept_rows.push((row_index, row_values.to_vec()));
+            return;
+        }
+        let replacement_slot = sampling_generator.next_index_below(self.offered_row_count);
+        if replacement_slot < capacity {
+            self.kept_rows[replacement_slot] = (row_index, row_values.to_vec());
+        }
+    }
+}
+
+/// Draws a uniform or stratified sample in one pass and writes it as a CSV
+///
+/// # Arguments
+/// * `sample_row_source` - Where the rows are read from
+/// * `sampling_request` - Method, size and seed
+/// * `output_csv_path` - Where to write the sample; metadata is written next to it
+///
+/// # Returns
+/// * `RowsAndColumnsResult<RowSampleSummary>` - Paths and counts
+///
+/// # Errors
+/// * `CsvProcessingError` - A zero sample size or a stratum column out of range
+/// * `StatisticalAnalysisError` - More than `MAXIMUM_STRATUM_COUNT` strata
+/// * `FileSystemError` - The rows or the output could not be read or written
+pub fn perform_row_sampling(
+    sample_row_source: &DatasetRowSource,
+    sampling_request: &RowSamplingRequest,
+    output_csv_path: &Path,
+) -> RowsAndColumnsResult<RowSampleSummary> {
+    let column_names = sample_row_source.column_names();
+    let column_data_types = sample_row_source.column_data_types();
+
+    // Step 1: Validate the request
+    let (reservoir_capacity, stratum_column_position) = match sampling_request.sampling_method {
+        RowSamplingMethod::Uniform { sample_row_count } => (sample_row_count, None),
+        RowSamplingMethod::Stratified { stratum_column_position, rows_per_stratum } => {
+            (rows_per_stratum, Some(stratum_column_position))
+        }
+    };
+    if reservoir_capacity == 0 {
+        return Err(create_csv_processing_error("The sample size must be at least 1 row", None, None));
+    }
+    if let Some(stratum_column_position) = stratum_column_position
+        && stratum_column_position >= column_names.len() {
+        return Err(create_csv_processing_error(
+            &format!("Stratum column {} is outside the {} available columns", stratum_column_position + 1, column_names.len()),
+            None,
+            None
+        ));
+    }
+
+    // Step 2: One pass, one reservoir per stratum (a single one for uniform samples)
+    let mut sampling_generator = ReservoirSamplingGenerator::from_seed(sampling_request.sampling_seed);
+    let mut stratum_reservoirs: BTreeMap<String, RowReservoir> = BTreeMap::new();
+
+    let scanned_row_count = sample_row_source.stream_rows(|row_index, row_values| {
+        let stratum_value = match stratum_column_position {
+            Some(stratum_column_position) => row_values.get(stratum_column_position).cloned().unwrap_or_default(),
+            None => String::new(),
+        };
+
+        if !stratum_reservoirs.contains_key(&stratum_value) && stratum_reservoirs.len() >= MAXIMUM_STRATUM_COUNT {
+            return Err(create_statistical_analysis_error(
+                &format!("More than {} strata; choose a column with fewer distinct values", MAXIMUM_STRATUM_COUNT),
+                stratum_column_position
+                    .map(|stratum_column_position| column_names[stratum_column_position].as_str())
+                    .unwrap_or_default()
+            ));
+        }
+
+        stratum_reservoirs
+            .entry(stratum_value)
+            .or_default()
+            .offer_row(row_index, row_values, reservoir_capacity, &mut sampling_generator);
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+
+    // Step 3: Count per stratum, then put the kept rows back in input order
+    let stratum_sample_counts = match stratum_column_position {
+        Some(_) => stratum_reservoirs
+            .iter()
+            .map(|(stratum_value, stratum_reservoir)| StratumSampleCount {
+                stratum_value: if stratum_value.is_empty() { EMPTY_STRATUM_LABEL.to_string() } else { stratum_value.clone() },
+                population_row_count: stratum_reservoir.offered_row_count,
+                sampled_row_count: stratum_reservoir.kept_rows.len(),
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut sampled_rows: Vec<(usize, Vec<String>)> = stratum_reservoirs
+        .into_values()
+        .flat_map(|stratum_reservoir| stratum_reservoir.kept_rows)
+        .collect();
+    sampled_rows.sort_by_key(|(row_index, _)| *row_index);
+
+    // Step 4: Write the sample and describe it with metadata
+    let mut sample_output_writer = StreamingCsvExportWriter::create(output_csv_path, &column_names, &column_data_types)?;
+    for (_, row_values) in &sampled_rows {
+        sample_output_writer.write_row(row_values)?;
+    }
+    let (sampled_row_count, metadata_file_path) = sample_output_writer.finish()?;
+
+    Ok(RowSampleSummary {
+        sampled_csv_file_path: output_csv_path.to_path_buf(),
+        metadata_file_path,
+        scanned_row_count,
+        sampled_row_count,
+        stratum_sample_counts,
+    })
+}
+
+/// Displays what a sample contains
+///
+/// # Arguments
+/// * `sample_summary` - The result of `perform_row_sampling`
+pub fn display_row_sample_summary(sample_summary: &RowSampleSummary) {
+    println!("✅ Sampled {} of {} rows into: {}",
+        sample_summary.sampled_row_count,
+        sample_summary.scanned_row_count,
+        sample_summary.sampled_csv_file_path.display()
+    );
+    if !sample_summary.stratum_sample_counts.is_empty() {
+        println!("   {} strata (sampled / in file):", sample_summary.stratum_sample_counts.len());
+        for stratum_count in &sample_summary.stratum_sample_counts {
+            println!("     {}: {} / {}",
+                stratum_count.stratum_value,
+                stratum_count.sampled_row_count,
+                stratum_count.population_row_count
+            );
+        }
+    }
+    println!("   Metadata: {}", sample_summary.metadata_file_path.display());
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::{
+        CsvAnalysisResults,
+        analyze_csv_file_structure_and_types,
+        parse_csv_line_into_fields,
+    };
+
+    /// Writes a CSV with `row_count` rows: id, and a group that is "rare" for every 10th row
+    fn create_sampling_test_csv(test_name: &str, row_count: usize) -> (PathBuf, CsvAnalysisResults) {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_sampling_{}_{}", test_name, std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("rows.csv");
+        let mut csv_text = String::from("id,group\n");
+        for row_number in 1..=row_count {
+            let group_value = if row_number % 10 == 0 { "rare" } else { "common" };
+            csv_text.push_str(&format!("{},{}\n", row_number, group_value));
+        }
+        std::fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+        let analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should succeed");
+        (test_directory, analysis_results)
+    }
+
+    /// Reads the id column of a written sample
+    fn read_sampled_ids(sampled_csv_file_path: &Path) -> Vec<usize> {
+        std::fs::read_to_string(sampled_csv_file_path)
+            .expect("sample should be readable")
+            .lines()
+            .skip(1)
+            .map(|csv_line| parse_csv_line_into_fields(csv_line)[0].parse::<usize>().expect("numeric id"))
+            .collect()
+    }
+
+    /// Test a uniform sample has the requested size, input order and repeats with its seed
+    #[test]
+    fn test_uniform_sample_is_reproducible() {
+        let (test_directory, analysis_results) = create_sampling_test_csv("uniform", 500);
+        let draw_sample = |sampling_seed: u64, output_name: &str| {
+            perform_row_sampling(
+                &DatasetRowSource::CsvFile(&analysis_results),
+                &RowSamplingRequest {
+                    sampling_method: RowSamplingMethod::Uniform { sample_row_count: 50 },
+                    sampling_seed,
+                },
+                &test_directory.join(output_name),
+            ).expect("sampling should succeed")
+        };
+
+        let first_summary = draw_sample(7, "first.csv");
+        let repeated_summary = draw_sample(7, "repeated.csv");
+        let other_seed_summary = draw_sample(8, "other.csv");
+        assert_eq!(first_summary.scanned_row_count, 500);
+        assert_eq!(first_summary.sampled_row_count, 50);
+        assert!(first_summary.metadata_file_path.exists());
+
+        let first_ids = read_sampled_ids(&first_summary.sampled_csv_file_path);
+        assert_eq!(first_ids.len(), 50);
+        assert!(first_ids.windows(2).all(|id_pair| id_pair[0] < id_pair[1]));
+        assert_eq!(first_ids, read_sampled_ids(&repeated_summary.sampled_csv_file_path));
+        assert_ne!(first_ids, read_sampled_ids(&other_seed_summary.sampled_csv_file_path));
+
+        // Later rows must be reachable, not just the first 50
+        assert!(first_ids.iter().any(|&row_id| row_id > 250));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test a stratified sample balances a rare category with a common one
+    #[test]
+    fn test_stratified_sample_balances_strata() {
+        let (test_directory, analysis_results) = create_sampling_test_csv("stratified", 300);
+        let sample_summary = perform_row_sampling(
+            &DatasetRowSource::CsvFile(&analysis_results),
+            &RowSamplingRequest {
+                sampling_method: RowSamplingMethod::Stratified { stratum_column_position: 1, rows_per_stratum: 20 },
+                sampling_seed: DEFAULT_SAMPLING_SEED,
+            },
+            &test_directory.join("stratified.csv"),
+        ).expect("sampling should succeed");
+
+        assert_eq!(sample_summary.sampled_row_count, 40);
+        assert_eq!(sample_summary.stratum_sample_counts, vec![
+            StratumSampleCount { stratum_value: "common".to_string(), population_row_count: 270, sampled_row_count: 20 },
+            StratumSampleCount { stratum_value: "rare".to_string(), population_row_count: 30, sampled_row_count: 20 },
+        ]);
+        let rare_id_count = read_sampled_ids(&sample_summary.sampled_csv_file_path)
+            .iter()
+            .filter(|&&row_id| row_id % 10 == 0)
+            .count();
+        assert_eq!(rare_id_count, 20);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test small inputs are kept whole and bad requests are refused
+    #[test]
+    fn test_small_input_and_invalid_requests() {
+        let (test_directory, analysis_results) = create_sampling_test_csv("small", 5);
+        let sample_summary = perform_row_sampling(
+            &DatasetRowSource::CsvFile(&analysis_results),
+            &RowSamplingRequest {
+                sampling_method: RowSamplingMethod::Uniform { sample_row_count: 100 },
+                sampling_seed: 0,
+            },
+            &test_directory.join("whole.csv"),
+        ).expect("sampling should succeed");
+        assert_eq!(read_sampled_ids(&sample_summary.sampled_csv_file_path), vec![1, 2, 3, 4, 5]);
+
+        for invalid_method in [
+            RowSamplingMethod::Uniform { sample_row_count: 0 },
+            RowSamplingMethod::Stratified { stratum_column_position: 5, rows_per_stratum: 2 },
+        ] {
+            let sampling_result = perform_row_sampling(
+                &DatasetRowSource::CsvFile(&analysis_results),
+                &RowSamplingRequest { sampling_method: invalid_method, sampling_seed: 1 },
+                &test_directory.join("invalid.csv"),
+            );
+            assert!(sampling_result.is_err());
+        }
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test every row is equally likely to be kept, including the first
+    /// `capacity` rows and the last row (an off-by-one in Algorithm R skews these)
+    #[test]
+    fn test_reservoir_inclusion_is_uniform() {
+        const OFFERED_ROW_COUNT: usize = 10;
+        const RESERVOIR_CAPACITY: usize = 3;
+        const SEED_COUNT: u64 = 3_000;
+
+        let mut inclusion_counts = [0usize; OFFERED_ROW_COUNT];
+        for sampling_seed in 0..SEED_COUNT {
+            let mut sampling_generator = ReservoirSamplingGenerator::from_seed(sampling_seed);
+            let mut row_reservoir = RowReservoir::default();
+            for row_index in 0..OFFERED_ROW_COUNT {
+                row_reservoir.offer_row(row_index, &[], RESERVOIR_CAPACITY, &mut sampling_generator);
+            }
+            for (row_index, _) in &row_reservoir.kept_rows {
+                inclusion_counts[*row_index] += 1;
+            }
+        }
+
+        // Expected 900 each; the standard deviation is about 25
+        let expected_count = SEED_COUNT as f64 * RESERVOIR_CAPACITY as f64 / OFFERED_ROW_COUNT as f64;
+        for inclusion_count in inclusion_counts {
+            assert!((inclusion_count as f64 - expected_count).abs() < 150.0, "{:?}", inclusion_counts);
+        }
+    }
+
+    /// Test empty and missing stratum values share the `(empty)` stratum and
+    /// short rows are written back out
+    #[test]
+    fn test_stratified_sample_with_empty_and_missing_values() {
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_sampling_empty_strata_{}", std::process::id()));
+        std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+        let csv_file_path = test_directory.join("rows.csv");
+        std::fs::write(&csv_file_path, "id,group,note\n1,a,x\n2,,plain\n3,a,z\n4\n")
+            .expect("test CSV should be writable");
+        let analysis_results = analyze_csv_file_structure_and_types(&csv_file_path).expect("analysis should succeed");
+
+        let sample_summary = perform_row_sampling(
+            &DatasetRowSource::CsvFile(&analysis_results),
+            &RowSamplingRequest {
+                sampling_method: RowSamplingMethod::Stratified { stratum_column_position: 1, rows_per_stratum: 5 },
+                sampling_seed: 7,
+            },
+            &test_directory.join("sample.csv"),
+        ).expect("sampling should succeed");
+
+        assert_eq!(sample_summary.stratum_sample_counts, vec![
+            StratumSampleCount { stratum_value: EMPTY_STRATUM_LABEL.to_string(), population_row_count: 2, sampled_row_count: 2 },
+            StratumSampleCount { stratum_value: "a".to_string(), population_row_count: 2, sampled_row_count: 2 },
+        ]);
+        assert_eq!(read_sampled_ids(&sample_summary.sampled_csv_file_path), vec![1, 2, 3, 4]);
+
+        let sample_text = std::fs::read_to_string(&sample_summary.sampled_csv_file_path).expect("sample should be readable");
+        let second_row_fields = parse_csv_line_into_fields(sample_text.lines().nth(2).expect("second sampled row"));
+        assert_eq!(second_row_fields, vec!["2", "", "plain"]);
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
@@ -13,10 +13,12 @@
 /// - Integrate with file selection and TUI dashboard modules
 /// 
 /// # Directory Structure Created
-/// ```
+/// ```text
 /// rows_columns_data/
 /// ├── csv_imports/           # Imported CSV datasets
-/// └── analysis_cache/        # Computed statistics cache
+/// ├── analysis_cache/        # Computed statistics cache
+/// ├── temporary_work/        # Short-lived spill files (e.g. external sort runs)
+/// └── file_selection_history.toml  # Recent files and bookmarks of the file browser
 /// ```
 /// 
 /// # Design Philosophy
@@ -25,69 +27,309 @@
 /// - No pre-loading: on-demand data processing for scalability
 /// - Clear error handling with comprehensive user feedback
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::io::{self, Write};
+use std::sync::OnceLock;
 
 use crate::csv_processor_module::EnhancedCsvColumnInformation;
 
 // Import enhanced CSV analysis capabilities
 use super::csv_processor_module::{
+    analyze_csv_basic_structure,
     analyze_csv_file_structure_and_types,
+    analyze_csv_reader_in_single_pass,
     CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvFieldType,
+    CsvRowStreamControl,
+    StreamingCsvExportWriter,
+    determine_metadata_file_path,
+    is_boolean_value,
     perform_enhanced_statistical_analysis,
     display_enhanced_csv_analysis_results,
+    stream_csv_data_rows,
+};
+
+// Import correlation analysis across continuous columns
+use super::correlation_analysis_module::{
+    CorrelationAnalysisResults,
+    display_correlation_analysis_results,
+    perform_correlation_analysis,
+};
+
+// Import crosstab / chi-square analysis for categorical pairs
+use super::crosstab_analysis_module::{
+    display_crosstab_analysis_results,
+    list_crosstab_candidate_positions,
+    perform_crosstab_analysis,
+};
+
+// Import quiet-mode console output and the JSON serializer
+use super::console_output_module::{
+    finish_standard_output,
+    is_standard_output_closed,
+    print_output_line,
+    print_output_text,
+    print_progress_line,
+    print_result_line,
+    print_warning_line,
+    set_headless_console_output,
+    set_quiet_console_output,
+};
+use super::json_output_module::{JsonValue, analysis_results_to_json, error_to_json};
+
+// Import Markdown / plain-text / HTML report export
+use super::analysis_report_module::{
+    AnalysisReportFormat,
+    determine_default_report_file_path,
+    write_analysis_report_file,
+};
+
+// Import group-by aggregation and its CSV export
+use super::group_by_aggregation_module::{
+    GroupByAggregationRequest,
+    display_group_by_aggregation_results,
+    export_group_by_results_to_csv,
+    parse_aggregation_function_list,
+    perform_group_by_aggregation,
+};
+
+// Import the full-screen dashboard
+use super::tui_dashboard_module::launch_tui_dashboard;
+
+// Import row filter expressions and the streaming filtered-file writer
+use super::filter_expression_module::{CompiledRowFilter, compile_row_filter_expression, write_filtered_csv_file};
+
+// Import the external merge sort
+use super::external_sort_module::{
+    DEFAULT_SORT_MEMORY_BUDGET_BYTES,
+    ExternalSortRequest,
+    parse_sort_key_list,
+    perform_external_sort,
+};
+
+// Import joins between two datasets
+use super::dataset_join_module::{
+    DEFAULT_JOIN_MEMORY_BUDGET_BYTES,
+    DatasetJoinRequest,
+    JoinType,
+    display_dataset_join_summary,
+    perform_dataset_join,
+};
+
+// Import derived columns (name = expression) saved in the metadata file
+use super::derived_column_module::{
+    analyze_derived_columns,
+    compile_derived_columns,
+    import_csv_with_derived_columns,
+    materialize_derived_column_into_directory_store,
+    parse_derived_column_definition,
+    save_derived_column_definition,
+};
+use super::metadata_manager_module::{read_csv_metadata_columns, read_derived_column_definitions};
+
+// Import seeded uniform and stratified row sampling
+use super::row_sampling_module::{
+    DEFAULT_SAMPLING_SEED,
+    RowSamplingMethod,
+    RowSamplingRequest,
+    display_row_sample_summary,
+    perform_row_sampling,
+};
+
+// Import subcommand parsing and help text
+use super::command_line_interface_module::{
+    CommandLineSubcommand,
+    ParsedCommandLine,
+    ParsedSubcommandArguments,
+    format_general_help_text,
+    format_subcommand_help_text,
+    parse_command_line_arguments,
+    resolve_column_selection,
+};
+
+// Import validation of values against the declared column types
+use super::csv_validation_module::{
+    csv_validation_report_to_json,
+    CsvValidationReport,
+    display_csv_validation_report,
+    validate_csv_file_against_column_types,
+};
+
+// Import standard input, named pipe and .csv.gz support (single pass, or spooled when needed)
+use super::csv_input_source_module::{
+    CsvInputSource,
+    MultiPassCsvInputFile,
+    open_csv_input_stream,
+    resolve_csv_input_source,
+    spool_csv_input_to_work_file,
+};
+
+// Import the aligned table layout used for the dataset list
+use super::terminal_control_module::format_aligned_text_table_lines;
+
+// Import the file browser shown when no arguments are given
+use super::file_system_bridge_module::launch_csv_file_selection_interface;
+use super::file_selection_history_module::FILE_SELECTION_HISTORY_FILE_NAME;
+
+// Import batch analysis of whole directories
+use super::batch_analysis_module::{
+    BatchAnalysisOptions,
+    BatchAnalysisSummary,
+    BatchFileOutcome,
+    batch_summary_to_json,
+    find_batch_csv_files,
+    run_batch_analysis,
+};
+
+// Import chart building and file output for the plot subcommand
+use super::chart_rendering_module::{
+    ChartDefinition,
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    DEFAULT_MAXIMUM_BAR_COUNT,
+    MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+    build_bar_chart_from_categorical_statistics,
+    build_histogram_chart_from_csv_column,
+    build_scatter_plot_chart_from_csv_columns,
+};
+use super::svg_chart_module::{DEFAULT_SVG_CHART_HEIGHT, DEFAULT_SVG_CHART_WIDTH, get_chart_title, write_chart_svg_file};
+use super::bitmap_chart_module::{BitmapImageFormat, write_chart_bitmap_file};
+
+// Import the directory store and the data grid viewer
+use super::directory_store_module::{
+    DatasetRowSource,
+    DirectoryStoreDataset,
+    list_directory_store_datasets,
+    open_directory_store_dataset,
+    sanitize_name_for_directory,
 };
+use super::data_grid_viewer_module::{GridRowSource, launch_data_grid_viewer};
 
 // Import our custom error types for comprehensive error handling
 use super::error_types_module::{
-    RowsAndColumnsError, 
+    RowsAndColumnsError,
     RowsAndColumnsResult,
+    create_command_line_usage_error,
+    create_data_type_validation_error,
     create_file_system_error,
     create_configuration_error
 };
 
 // Import the path management module for binary-relative operations
 use super::manage_absolute_executable_directory_relative_paths::{
+    DataDirectoryResolutionSource,
+    ResolvedApplicationDataDirectory,
+    make_input_path_name_abs_executabledirectoryrelative_nocheck,
     make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path,
-    get_absolute_path_to_executable_parentdirectory
+    get_absolute_path_to_executable_parentdirectory,
+    resolve_application_data_directory_path,
 };
-/// Configuration constants for directory structure
-/// 
-/// These define the standard directory names used throughout the application
-/// for organizing CSV data and analysis results.
-const ROWS_COLUMNS_ROOT_DIRECTORY_NAME: &str = "rows_columns_data";
-const CSV_IMPORTS_SUBDIRECTORY_NAME: &str = "csv_imports";
-const ANALYSIS_CACHE_SUBDIRECTORY_NAME: &str = "analysis_cache";
+
+// Import the configuration file (directory names and other defaults)
+use super::application_configuration_module::{application_configuration, load_application_configuration};
+
+/// Widest column in the `datasets` table (longer source paths are clipped)
+const MAXIMUM_DATASET_TABLE_COLUMN_WIDTH: usize = 60;
 
 /// Primary application entry point for rows_and_columns CSV analysis system
 /// 
-/// This function handles command line arguments and initializes the application environment.
-/// It supports direct CSV file processing via command line arguments, similar to the 'lines' pattern.
+/// This function parses the command line into a subcommand and runs it. Only
+/// `analyze` (and the interactive file prompt) shows the startup banner and the
+/// menu; the other subcommands print just their result, so they can be used in
+/// scripts and pipes.
 /// 
 /// # Command Line Usage
-/// * `rows_and_columns` - Interactive mode (future implementation)
-/// * `rows_and_columns <csv_file_path>` - Process specific CSV file
-/// * `rows_and_columns --help` - Show usage information
+/// * `rows_and_columns` - Ask for a CSV file, then analyze it
+/// * `rows_and_columns <subcommand> [arguments] [options]` - Run one subcommand
+///   (`analyze`, `describe`, `import`, `export`, `plot`, `query`, `validate`, `datasets`)
+/// * `rows_and_columns <csv_file_path>` - Same as `analyze <csv_file_path>`
+/// * `rows_and_columns --json <csv_file_path>` - Same as `describe <csv_file_path> --format json`
+/// * `rows_and_columns help [subcommand]` - Show usage information
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or detailed error information
 /// 
 /// # Errors
+/// * `RowsAndColumnsError::CommandLineUsageError` - If the arguments cannot be parsed
 /// * `RowsAndColumnsError::FileSystemError` - If directory creation or file access fails
-/// * `RowsAndColumnsError::ConfigurationError` - If setup validation fails
+/// * `RowsAndColumnsError::ConfigurationError` - If rows_and_columns.toml is invalid or setup validation fails
+/// * `RowsAndColumnsError::DataTypeValidationError` - If `validate` finds invalid values
 /// 
 /// # Examples
 /// ```bash
-/// # Process a specific CSV file
+/// # Analyze a file and open the menu
 /// rows_and_columns data/customers.csv
 /// 
-/// # Show help
-/// rows_and_columns --help
+/// # Pipe the statistics into another tool
+/// rows_and_columns describe data/customers.csv --format json | jq '.columns[].name'
+/// 
+/// # Show the options of one subcommand
+/// rows_and_columns help query
 /// ```
 pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<()> {
-    // Parse command line arguments
-    let command_line_arguments: Vec<String> = env::args().collect();
+    // Step 1: Parse command line arguments (without the program name)
+    let command_line_arguments: Vec<String> = env::args().skip(1).collect();
+    let parsed_command_line = parse_command_line_arguments(&command_line_arguments)?;
+    
+    // Step 2: Load rows_and_columns.toml (help and version work without it)
+    if matches!(
+        parsed_command_line,
+        ParsedCommandLine::InteractiveFileSelection { .. } | ParsedCommandLine::RunSubcommand(_)
+    ) {
+        load_application_configuration()?;
+    }
     
+    // Step 3: Run what was asked for
+    run_parsed_command_line(parsed_command_line)?;
+    
+    // Step 4: Report a failed stdout write (a reader that closed the pipe early is not a failure)
+    finish_standard_output()
+}
+
+/// Runs help, version, the interactive file prompt or a subcommand
+/// 
+/// # Arguments
+/// * `parsed_command_line` - What the command line asked for
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or the command's error
+fn run_parsed_command_line(parsed_command_line: ParsedCommandLine) -> RowsAndColumnsResult<()> {
+    match parsed_command_line {
+        ParsedCommandLine::ShowGeneralHelp => {
+            display_usage_help_information();
+            Ok(())
+        }
+        ParsedCommandLine::ShowSubcommandHelp(subcommand) => {
+            print_output_text(&format_subcommand_help_text(subcommand));
+            Ok(())
+        }
+        ParsedCommandLine::ShowVersion => {
+            print_output_line(&format!("rows_and_columns {}", env!("CARGO_PKG_VERSION")));
+            Ok(())
+        }
+        ParsedCommandLine::InteractiveFileSelection { data_directory_argument } => {
+            // No command line arguments - browse for a CSV file, preview it, then analyze
+            select_application_data_directory(data_directory_argument.as_deref())?;
+            let directory_paths = start_interactive_application_session()?;
+            let history_file_path = directory_paths.rows_columns_root_directory.join(FILE_SELECTION_HISTORY_FILE_NAME);
+            let Some(selected_csv_file) = launch_csv_file_selection_interface(&history_file_path)? else {
+                return Ok(());
+            };
+            process_csv_file_from_command_line(
+                &selected_csv_file.absolute_file_path.to_string_lossy(),
+                &directory_paths,
+                true,
+            )
+        }
+        ParsedCommandLine::RunSubcommand(subcommand_arguments) => run_command_line_subcommand(&subcommand_arguments),
+    }
+}
+
+/// Shows the banner and prepares the directories for a menu-driven session
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<ApplicationDirectoryPaths>` - The verified directories
+fn start_interactive_application_session() -> RowsAndColumnsResult<ApplicationDirectoryPaths> {
     // Step 1: Display startup information to user
     display_application_startup_banner();
     
@@ -100,285 +342,999 @@ pub fn run_rows_and_columns_application() -> RowsAndColumnsResult<()> {
     // Step 4: Display success information to user
     display_directory_setup_success(&directory_paths);
     
-    // Step 5: Process command line arguments
-    if command_line_arguments.len() > 1 {
-        match command_line_arguments[1].as_str() {
-            "--help" | "-h" | "help" => {
-                display_usage_help_information();
-                return Ok(());
-            }
-            _ => {
-                // Treat the first argument as a CSV file path
-                let csv_file_path = &command_line_arguments[1];
-                return process_csv_file_from_command_line(csv_file_path, &directory_paths);
-            }
+    Ok(directory_paths)
+}
+
+/// Runs one parsed subcommand
+/// 
+/// Apart from `analyze`, subcommands suppress progress lines so stdout holds
+/// only their result; warnings still go to stderr. With `--headless`, emoji
+/// are removed and `analyze` runs without the banner and the menu.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - The parsed subcommand, positional argument and flags
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or the subcommand's error
+fn run_command_line_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    let json_output_requested = subcommand_arguments.flag_value("format") == Some("json");
+    let analyze_with_progress_output =
+        subcommand_arguments.subcommand == CommandLineSubcommand::Analyze && !json_output_requested;
+    set_headless_console_output(subcommand_arguments.headless_mode);
+    set_quiet_console_output(!analyze_with_progress_output);
+    
+    // Choose the data directory once the console flags decide where warnings go
+    select_application_data_directory(subcommand_arguments.data_directory_argument.as_deref())?;
+    
+    if analyze_with_progress_output {
+        let csv_file_path_argument = subcommand_arguments.required_positional_argument()?;
+        
+        // Headless runs skip the banner and the menu; nobody is there to answer it
+        if subcommand_arguments.headless_mode {
+            let directory_paths = initialize_application_directory_structure()?;
+            validate_directory_structure_initialization(&directory_paths)?;
+            return process_csv_file_from_command_line(csv_file_path_argument, &directory_paths, false);
         }
-    } else {
-        // No command line arguments - start interactive file input
-        let csv_file_path_from_qa = interactive_csv_file_path_input()?;
-        return process_csv_file_from_command_line(&csv_file_path_from_qa, &directory_paths);
+        let directory_paths = start_interactive_application_session()?;
+        let open_interactive_menu = !subcommand_arguments.has_switch("no-interactive");
+        return process_csv_file_from_command_line(csv_file_path_argument, &directory_paths, open_interactive_menu);
     }
     
-    Ok(())
+    match subcommand_arguments.subcommand {
+        CommandLineSubcommand::Analyze | CommandLineSubcommand::Describe => {
+            run_describe_subcommand(subcommand_arguments, json_output_requested)
+        }
+        CommandLineSubcommand::Import => run_import_subcommand(subcommand_arguments),
+        CommandLineSubcommand::Export => run_export_subcommand(subcommand_arguments),
+        CommandLineSubcommand::Plot => run_plot_subcommand(subcommand_arguments),
+        CommandLineSubcommand::Query if json_output_requested => {
+            print_json_document_or_error(|| collect_query_rows_as_json(subcommand_arguments))
+        }
+        CommandLineSubcommand::Query => run_query_subcommand(subcommand_arguments),
+        CommandLineSubcommand::Validate => run_validate_subcommand(subcommand_arguments, json_output_requested),
+        CommandLineSubcommand::Datasets => run_datasets_subcommand(json_output_requested),
+        CommandLineSubcommand::Batch if json_output_requested => {
+            print_json_document_or_error(|| Ok(batch_summary_to_json(&run_batch_subcommand_analysis(subcommand_arguments)?)))
+        }
+        CommandLineSubcommand::Batch => run_batch_subcommand(subcommand_arguments),
+    }
 }
 
-/// Displays usage help information for command line interface
+/// Prints a JSON document, or a JSON error document if building it failed
 /// 
-/// This function shows users how to use the rows_and_columns application
-/// with various command line options and file processing modes.
-fn display_usage_help_information() {
-    println!("USAGE:");
-    println!("  rows_and_columns <csv_file_path>     Process a specific CSV file");
-    println!("  rows_and_columns --help              Show this help information");
-    println!();
-    println!("EXAMPLES:");
-    println!("  rows_and_columns data/customers.csv");
-    println!("  rows_and_columns /home/user/sales_data.csv");
-    println!("  rows_and_columns ../reports/quarterly.csv");
-    println!();
-    println!("FEATURES:");
-    println!("  • Directory-based CSV data storage for scalability");
-    println!("  • Pandas-style statistical analysis");
-    println!("  • ASCII/Unicode TUI charts and visualizations");
-    println!("  • Binary-relative path management for portability");
-    println!();
+/// Progress lines are suppressed and warnings go to stderr, so stdout holds
+/// only JSON. A failure is printed as a JSON error document before the error
+/// is returned (so the exit code is still non-zero).
+/// 
+/// # Arguments
+/// * `build_json_document` - Produces the document to print
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or the error that was reported as JSON
+fn print_json_document_or_error<F>(build_json_document: F) -> RowsAndColumnsResult<()>
+where
+    F: FnOnce() -> RowsAndColumnsResult<JsonValue>,
+{
+    set_quiet_console_output(true);
+    
+    match build_json_document() {
+        Ok(json_document) => {
+            print_output_line(&json_document.to_json_text(true));
+            Ok(())
+        }
+        Err(subcommand_error) => {
+            print_output_line(&error_to_json(&subcommand_error).to_json_text(true));
+            Err(subcommand_error)
+        }
+    }
 }
 
-/// Interactive Q&A to get CSV file path from user
+/// Validates the positional CSV path and runs the basic and statistical analysis
+/// 
+/// Standard input and named pipes are spooled first, since callers read the
+/// rows again afterwards (charts); keep the returned file alive until done.
 /// 
-/// This function provides a user-friendly interface for selecting a CSV file
-/// when no command line arguments are provided. It includes validation and
-/// helpful prompts to guide the user.
+/// # Arguments
+/// * `subcommand_arguments` - Arguments holding the CSV file path
 /// 
 /// # Returns
-/// * `RowsAndColumnsResult<String>` - The validated CSV file path or error
+/// * `RowsAndColumnsResult<(MultiPassCsvInputFile, CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)>` - The file and both analyses
+fn analyze_csv_file_for_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+) -> RowsAndColumnsResult<(MultiPassCsvInputFile, CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)> {
+    let csv_input_file = open_csv_file_argument_for_multiple_passes(subcommand_arguments.required_positional_argument()?)?;
+    let csv_analysis_results = analyze_csv_file_structure_and_types(csv_input_file.csv_file_path())?;
+    let enhanced_analysis_results = perform_enhanced_statistical_analysis(
+        csv_input_file.csv_file_path(),
+        &csv_analysis_results
+    )?;
+    Ok((csv_input_file, csv_analysis_results, enhanced_analysis_results))
+}
+
+/// Runs the basic and statistical analysis, reading a stream only once
 /// 
-/// # Errors
-/// * `RowsAndColumnsError::FileSystemError` - If file input/validation fails
-/// * `RowsAndColumnsError::ConfigurationError` - If user cancels or invalid input
-fn interactive_csv_file_path_input() -> RowsAndColumnsResult<String> {
-    println!("No CSV file specified. Let's find one to analyze!");
-    println!();
-    
-    loop {
-        // Display helpful instructions
-        display_file_input_instructions();
-        
-        // Get user input
-        print!("Enter CSV file path: ");
-        io::stdout().flush().map_err(|io_error| {
-            create_file_system_error("Failed to flush stdout for user input", io_error)
-        })?;
-        
-        let mut user_file_path_input = String::new();
-        io::stdin().read_line(&mut user_file_path_input).map_err(|io_error| {
-            create_file_system_error("Failed to read user input", io_error)
-        })?;
-        
-        let trimmed_file_path = user_file_path_input.trim();
-        
-        // Handle special commands
-        match trimmed_file_path.to_lowercase().as_str() {
-            "" => {
-                println!("Please enter a file path, or 'quit' to exit.");
-                println!();
-                continue;
-            }
-            "quit" | "q" | "exit" => {
-                println!("Goodbye!");
-                return Err(create_configuration_error("User chose to quit file selection"));
-            }
-            "help" | "h" | "?" => {
-                display_detailed_file_input_help();
-                continue;
-            }
-            _ => {
-                // Try to validate the provided file path
-                match validate_user_provided_csv_file_path(trimmed_file_path) {
-                    Ok(validated_path) => {
-                        println!("✓ Found CSV file: {}", validated_path);
-                        println!();
-                        return Ok(validated_path);
-                    }
-                    Err(validation_error) => {
-                        println!("❌ File issue: {}", validation_error);
-                        println!("Please try again, or type 'help' for assistance.");
-                        println!();
-                        continue;
-                    }
-                }
-            }
-        }
+/// For callers that need nothing but the analysis (`describe`): standard
+/// input and named pipes are analyzed as they are read, without a copy.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - Arguments holding the CSV file path or `-`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<(CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)>` - Both analyses
+fn analyze_csv_input_in_single_pass_for_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+) -> RowsAndColumnsResult<(CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)> {
+    let csv_input_source = resolve_csv_input_source(subcommand_arguments.required_positional_argument()?);
+    if !csv_input_source.is_read_once_stream() {
+        let (_, csv_analysis_results, enhanced_analysis_results) = analyze_csv_file_for_subcommand(subcommand_arguments)?;
+        return Ok((csv_analysis_results, enhanced_analysis_results));
     }
+    analyze_csv_reader_in_single_pass(open_csv_input_stream(&csv_input_source)?, &csv_input_source.source_label())
 }
 
-/// Displays helpful instructions for file input
-/// 
-/// This function shows users what kind of input is expected and what
-/// commands are available during the file selection process.
-fn display_file_input_instructions() {
-    println!("📁 File Selection Help:");
-    println!("  • Enter the path to your CSV file (absolute or relative)");
-    println!("  • Examples:");
-    println!("    data/customers.csv");
-    println!("    /home/user/reports/sales.csv");
-    println!("    ../datasets/analysis_data.csv");
-    println!("  • Special commands:");
-    println!("    'help' - Show detailed help");
-    println!("    'quit' - Exit the application");
-    println!();
+/// Resolves `--columns` against a file's columns (all columns when it is not given)
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - Arguments that may hold `--columns`
+/// * `column_names` - The file's column names in order
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<usize>>` - 0-based column positions
+fn select_columns_for_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+    column_names: &[String],
+) -> RowsAndColumnsResult<Vec<usize>> {
+    match subcommand_arguments.flag_value("columns") {
+        Some(column_list_text) => resolve_column_selection(column_list_text, column_names, subcommand_arguments.subcommand),
+        None => Ok((0..column_names.len()).collect()),
+    }
+}
+
+/// Returns the analyzed column names in file order
+fn list_analyzed_column_names(csv_analysis_results: &CsvAnalysisResults) -> Vec<String> {
+    csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.column_name.clone())
+        .collect()
+}
+
+/// Keeps the per-column results of the selected columns, in selection order
+fn keep_selected_enhanced_columns(
+    enhanced_analysis_results: Vec<EnhancedCsvColumnInformation>,
+    selected_column_positions: &[usize],
+) -> Vec<EnhancedCsvColumnInformation> {
+    let mut enhanced_columns_by_position: Vec<Option<EnhancedCsvColumnInformation>> =
+        enhanced_analysis_results.into_iter().map(Some).collect();
+    selected_column_positions
+        .iter()
+        .filter_map(|column_position| enhanced_columns_by_position.get_mut(*column_position).and_then(Option::take))
+        .collect()
+}
+
+/// Returns the lowercase extension of a path given on the command line
+fn lowercase_file_extension(file_path_text: &str) -> Option<String> {
+    Path::new(file_path_text)
+        .extension()
+        .map(|file_extension| file_extension.to_string_lossy().to_lowercase())
 }
 
-/// Displays detailed help for file input process
+/// `describe` (and `analyze --format json`): prints the column statistics
 /// 
-/// This function provides comprehensive guidance for users who need
-/// more help with file path specification and troubleshooting.
-fn display_detailed_file_input_help() {
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  Detailed File Input Help");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
-    
-    println!("FILE PATH FORMATS:");
-    println!("  Absolute paths (full path from root):");
-    println!("    Linux/Mac: /home/username/data/file.csv");
-    println!("    Windows:   C:\\Users\\username\\data\\file.csv");
-    println!();
-    
-    println!("  Relative paths (from current directory):");
-    println!("    Same directory:    file.csv");
-    println!("    Subdirectory:      data/file.csv");
-    println!("    Parent directory:  ../file.csv");
-    println!();
-    
-    println!("FILE REQUIREMENTS:");
-    println!("  • File must exist and be readable");
-    println!("  • File extension should be .csv or .tsv");
-    println!("  • File should contain comma-separated values");
-    println!();
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and optional `--columns`
+/// * `json_output_requested` - Print the JSON document instead of text
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_describe_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+    json_output_requested: bool,
+) -> RowsAndColumnsResult<()> {
+    let describe_selected_columns = || -> RowsAndColumnsResult<(CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>)> {
+        let (csv_analysis_results, enhanced_analysis_results) = analyze_csv_input_in_single_pass_for_subcommand(subcommand_arguments)?;
+        let selected_column_positions = select_columns_for_subcommand(
+            subcommand_arguments,
+            &list_analyzed_column_names(&csv_analysis_results)
+        )?;
+        Ok((csv_analysis_results, keep_selected_enhanced_columns(enhanced_analysis_results, &selected_column_positions)))
+    };
     
-    println!("TROUBLESHOOTING:");
-    println!("  • Check file path spelling and capitalization");
-    println!("  • Ensure file permissions allow reading");
-    println!("  • Use tab completion in your terminal if available");
-    println!("  • Try absolute path if relative path doesn't work");
-    println!();
+    if json_output_requested {
+        return print_json_document_or_error(|| {
+            let (csv_analysis_results, selected_enhanced_results) = describe_selected_columns()?;
+            Ok(analysis_results_to_json(&csv_analysis_results, &selected_enhanced_results))
+        });
+    }
     
-    println!("EXAMPLES OF VALID PATHS:");
-    println!("  ./data/customers.csv");
-    println!("  ~/Documents/spreadsheet.csv");
-    println!("  /tmp/analysis_data.csv");
-    println!();
+    let (_, selected_enhanced_results) = describe_selected_columns()?;
+    display_enhanced_csv_analysis_results(&selected_enhanced_results)
+}
+
+/// `import`: stores a CSV file (and its saved derived columns) in the directory store
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and optional `--output` imports directory
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_import_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    // Step 1: Analyze the file (this also writes its metadata)
+    let csv_input_file = open_csv_file_argument_for_multiple_passes(subcommand_arguments.required_positional_argument()?)?;
+    let csv_analysis_results = analyze_csv_file_structure_and_types(csv_input_file.csv_file_path())?;
+    
+    // Step 2: Store into --output, or the application's csv_imports/ directory
+    let csv_imports_directory = match subcommand_arguments.flag_value("output") {
+        Some(output_directory_text) => {
+            let output_directory = PathBuf::from(output_directory_text);
+            std::fs::create_dir_all(&output_directory).map_err(|io_error| {
+                create_file_system_error(
+                    &format!("Failed to create imports directory: {}", output_directory.display()),
+                    io_error
+                )
+            })?;
+            output_directory
+        }
+        None => initialize_application_directory_structure()?.csv_imports_directory,
+    };
+    let store_dataset = import_csv_with_derived_columns(&csv_analysis_results, &csv_imports_directory)?;
     
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
+    print_result_line(&format!("✅ Stored {} rows × {} columns in: {}",
+        store_dataset.total_row_count,
+        store_dataset.column_names.len(),
+        store_dataset.dataset_directory_path.display()
+    ));
+    Ok(())
 }
 
-/// Validates a user-provided CSV file path with helpful error messages
+/// `export`: writes the analysis report as Markdown, plain text or HTML
 /// 
-/// This function checks the file path and provides specific, actionable
-/// error messages to help users resolve file access issues.
+/// The format comes from `--format`, else from the `--output` extension, else Markdown.
 /// 
 /// # Arguments
-/// * `user_file_path` - The file path provided by the user
+/// * `subcommand_arguments` - CSV path, `--format`, `--columns` and `--output`
 /// 
 /// # Returns
-/// * `Result<String, String>` - Validated path or user-friendly error message
-fn validate_user_provided_csv_file_path(user_file_path: &str) -> Result<String, String> {
-    // Convert to PathBuf for easier manipulation
-    let file_path = PathBuf::from(user_file_path);
-    
-    // Check if file exists
-    if !file_path.exists() {
-        return Err(format!(
-            "File not found: '{}'. Please check the path and try again.",
-            user_file_path
-        ));
-    }
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_export_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    // Step 1: Analyze and keep the selected columns
+    let (_csv_input_file, csv_analysis_results, enhanced_analysis_results) = analyze_csv_file_for_subcommand(subcommand_arguments)?;
+    let selected_column_positions = select_columns_for_subcommand(
+        subcommand_arguments,
+        &list_analyzed_column_names(&csv_analysis_results)
+    )?;
+    let selected_enhanced_results = keep_selected_enhanced_columns(enhanced_analysis_results, &selected_column_positions);
+    
+    // Step 2: Pick the format and destination
+    let output_path_text = subcommand_arguments.flag_value("output");
+    let format_name = subcommand_arguments.flag_value("format")
+        .map(str::to_string)
+        .or_else(|| output_path_text.and_then(lowercase_file_extension));
+    let report_format = match format_name.as_deref() {
+        Some("txt") | Some("text") => AnalysisReportFormat::PlainText,
+        Some("html") | Some("htm") => AnalysisReportFormat::Html,
+        _ => AnalysisReportFormat::Markdown,
+    };
+    let report_file_path = match output_path_text {
+        Some(output_path_text) => PathBuf::from(output_path_text),
+        None => determine_default_report_file_path(
+            &csv_analysis_results,
+            &initialize_application_directory_structure()?.analysis_cache_directory,
+            report_format,
+        ),
+    };
     
-    // Check if it's actually a file (not a directory)
-    if !file_path.is_file() {
-        if file_path.is_dir() {
-            return Err(format!(
-                "'{}' is a directory, not a file. Please specify a CSV file within this directory.",
-                user_file_path
-            ));
-        } else {
-            return Err(format!(
-                "'{}' exists but is not a regular file. Please specify a CSV file.",
-                user_file_path
-            ));
-        }
-    }
+    // Step 3: Write the report
+    write_analysis_report_file(&csv_analysis_results, &selected_enhanced_results, report_format, &report_file_path)?;
+    print_result_line(&format!("✅ {} report written to: {}", report_format.display_name(), report_file_path.display()));
+    Ok(())
+}
+
+/// `plot`: writes a histogram, bar chart or scatter plot as SVG, PNG or PPM
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path, `--columns` (one or two), `--format` and `--output`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_plot_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    // Step 1: The columns decide the chart
+    let column_list_text = subcommand_arguments.flag_value("columns").ok_or_else(|| {
+        create_command_line_usage_error(
+            "plot needs --columns with one or two columns, e.g. --columns age or --columns age,weight",
+            Some("plot")
+        )
+    })?;
+    let (_csv_input_file, csv_analysis_results, enhanced_analysis_results) = analyze_csv_file_for_subcommand(subcommand_arguments)?;
+    let selected_column_positions = resolve_column_selection(
+        column_list_text,
+        &list_analyzed_column_names(&csv_analysis_results),
+        subcommand_arguments.subcommand
+    )?;
     
-    // Check file extension (with helpful suggestions)
-    let file_extension = file_path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.to_lowercase());
+    // Step 2: Pick the image format: --format, else the --output extension, else SVG
+    let output_path_text = subcommand_arguments.flag_value("output");
+    let format_name = match subcommand_arguments.flag_value("format") {
+        Some(format_name) => format_name.to_string(),
+        None => match output_path_text.and_then(lowercase_file_extension) {
+            Some(output_extension) if ["svg", "png", "ppm"].contains(&output_extension.as_str()) => output_extension,
+            Some(output_extension) => {
+                return Err(create_command_line_usage_error(
+                    &format!("cannot tell the image format from '.{}'; add --format svg, png or ppm", output_extension),
+                    Some("plot")
+                ));
+            }
+            None => "svg".to_string(),
+        },
+    };
+    let bitmap_image_format = match format_name.as_str() {
+        "png" => Some(BitmapImageFormat::Png),
+        "ppm" => Some(BitmapImageFormat::Ppm),
+        _ => None,
+    };
     
-    match file_extension.as_deref() {
-        Some("csv") | Some("tsv") => {
-            // Perfect - this looks like a CSV file
-        }
-        Some(other_extension) => {
-            println!("⚠️  Warning: File extension '{}' is not typical for CSV files.", other_extension);
-            println!("   Proceeding anyway, but ensure this contains comma-separated values.");
-        }
+    // Step 3: Build the chart and write it
+    let chart_definition = build_chart_for_plot_subcommand(
+        &csv_analysis_results,
+        &enhanced_analysis_results,
+        &selected_column_positions
+    )?;
+    let chart_file_path = match output_path_text {
+        Some(output_path_text) => PathBuf::from(output_path_text),
         None => {
-            println!("⚠️  Warning: File has no extension.");
-            println!("   Proceeding anyway, but ensure this contains comma-separated values.");
+            let csv_file_stem = csv_analysis_results.csv_file_path
+                .file_stem()
+                .map(|file_stem| file_stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            initialize_application_directory_structure()?.analysis_cache_directory.join(format!(
+                "{}_{}.{}",
+                sanitize_name_for_directory(&csv_file_stem),
+                sanitize_name_for_directory(get_chart_title(&chart_definition)),
+                bitmap_image_format.map(|image_format| image_format.file_extension()).unwrap_or("svg")
+            ))
         }
+    };
+    match bitmap_image_format {
+        Some(image_format) => write_chart_bitmap_file(
+            &chart_definition,
+            DEFAULT_SVG_CHART_WIDTH,
+            DEFAULT_SVG_CHART_HEIGHT,
+            image_format,
+            &chart_file_path,
+        )?,
+        None => write_chart_svg_file(&chart_definition, DEFAULT_SVG_CHART_WIDTH, DEFAULT_SVG_CHART_HEIGHT, &chart_file_path)?,
     }
     
-    // Try to convert to absolute path
-    let absolute_file_path = match file_path.canonicalize() {
-        Ok(abs_path) => abs_path,
-        Err(_) => {
-            return Err(format!(
-                "Cannot resolve absolute path for '{}'. Check file permissions and path validity.",
-                user_file_path
-            ));
-        }
+    print_result_line(&format!("✅ Chart \"{}\" written to: {}", get_chart_title(&chart_definition), chart_file_path.display()));
+    Ok(())
+}
+
+/// Chooses and builds the chart for the plotted columns
+/// 
+/// One numeric column gives a histogram, one categorical column a bar chart,
+/// and two numeric columns a scatter plot of the second against the first.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `enhanced_analysis_results` - Per-column statistics in file order
+/// * `selected_column_positions` - The plotted columns (0-based)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<ChartDefinition>` - The chart
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::CommandLineUsageError` - If the columns cannot be plotted together
+fn build_chart_for_plot_subcommand(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    selected_column_positions: &[usize],
+) -> RowsAndColumnsResult<ChartDefinition> {
+    let find_column_info = |column_position: usize| {
+        enhanced_analysis_results
+            .iter()
+            .find(|column_info| column_info.basic_info.column_index == column_position)
+            .ok_or_else(|| create_command_line_usage_error(
+                &format!("column {} has no statistics to plot", column_position + 1),
+                Some("plot")
+            ))
     };
     
-    // Return the absolute path as a string
-    Ok(absolute_file_path.to_string_lossy().to_string())
+    match selected_column_positions {
+        [column_position] => {
+            let column_info = find_column_info(*column_position)?;
+            let column_name = &column_info.basic_info.column_name;
+            if let Some(numerical_statistics) = &column_info.numerical_statistics {
+                build_histogram_chart_from_csv_column(
+                    &csv_analysis_results.csv_file_path,
+                    csv_analysis_results.has_header_row,
+                    *column_position,
+                    column_name,
+                    numerical_statistics,
+                    DEFAULT_HISTOGRAM_BIN_COUNT,
+                )
+            } else if let Some(categorical_statistics) = &column_info.categorical_statistics {
+                Ok(build_bar_chart_from_categorical_statistics(column_name, categorical_statistics, DEFAULT_MAXIMUM_BAR_COUNT))
+            } else {
+                Err(create_command_line_usage_error(&format!("column '{}' has no values to plot", column_name), Some("plot")))
+            }
+        }
+        [x_column_position, y_column_position] => {
+            let x_column_info = find_column_info(*x_column_position)?;
+            let y_column_info = find_column_info(*y_column_position)?;
+            for column_info in [x_column_info, y_column_info] {
+                if column_info.numerical_statistics.is_none() {
+                    return Err(create_command_line_usage_error(
+                        &format!(
+                            "a scatter plot needs two numeric columns, but '{}' is categorical",
+                            column_info.basic_info.column_name
+                        ),
+                        Some("plot")
+                    ));
+                }
+            }
+            build_scatter_plot_chart_from_csv_columns(
+                &csv_analysis_results.csv_file_path,
+                csv_analysis_results.has_header_row,
+                *x_column_position,
+                &x_column_info.basic_info.column_name,
+                *y_column_position,
+                &y_column_info.basic_info.column_name,
+                MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+            )
+        }
+        _ => Err(create_command_line_usage_error(
+            &format!("plot takes one or two columns, not {}", selected_column_positions.len()),
+            Some("plot")
+        )),
+    }
 }
 
-// /// Processes a CSV file specified via command line argument
-// /// 
-// /// This function validates the provided CSV file path, converts it to an absolute path,
-// /// and prepares it for processing. This follows the 'lines' pattern of direct file processing.
-// /// 
-// /// # Arguments
-// /// * `csv_file_path_argument` - The CSV file path provided as command line argument
-// /// * `directory_paths` - The application directory structure for data storage
-// /// 
-// /// # Returns
-// /// * `RowsAndColumnsResult<()>` - Success or detailed error information
-// /// 
-// /// # Errors
-// /// * `RowsAndColumnsError::FileSystemError` - If file access or validation fails
-// /// * `RowsAndColumnsError::ConfigurationError` - If file is not a valid CSV
-// fn process_csv_file_from_command_line(
-//     csv_file_path_argument: &str,
-//     directory_paths: &ApplicationDirectoryPaths,
-// ) -> RowsAndColumnsResult<()> {
-//     println!("Processing CSV file: {}", csv_file_path_argument);
-//     println!();
+/// A `query` ready to stream: the file, the kept columns, the filter and the limit
+struct PreparedRowQuery {
+    /// The queried file (a spooled copy lives as long as the query)
+    csv_input_file: MultiPassCsvInputFile,
     
-//     // Step 1: Validate the provided file path
-//     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+    /// Analysis of the queried file
+    csv_analysis_results: CsvAnalysisResults,
     
-//     // Step 2: Display file information
-//     display_csv_file_processing_information(&csv_file_absolute_path)?;
+    /// 0-based positions of the output columns
+    selected_column_positions: Vec<usize>,
     
-//     // Step 3: TODO - In next implementation phase, this will:
-//     // - Parse CSV headers and create metadata
-//     // - Create column directory structure
-//     // - Process CSV data into directory-based storage
-//     // - Generate statistical analysis
-//     // - Show TUI dashboard options
+    /// The `--where` filter, if given
+    compiled_row_filter: Option<CompiledRowFilter>,
+    
+    /// The `--limit`, if given
+    maximum_matching_row_count: Option<usize>,
+}
+
+impl PreparedRowQuery {
+    /// Names of the output columns
+    fn selected_column_names(&self) -> Vec<String> {
+        self.selected_column_positions
+            .iter()
+            .map(|column_position| self.csv_analysis_results.column_information_list[*column_position].column_name.clone())
+            .collect()
+    }
+    
+    /// Types of the output columns
+    fn selected_column_data_types(&self) -> Vec<CsvColumnDataType> {
+        self.selected_column_positions
+            .iter()
+            .map(|column_position| self.csv_analysis_results.column_information_list[*column_position].detected_data_type.clone())
+            .collect()
+    }
+    
+    /// Streams the matching rows, projected to the output columns
+    /// 
+    /// # Arguments
+    /// * `matching_row_visitor` - Called with each kept row's output values; may stop the scan
+    /// 
+    /// # Returns
+    /// * `RowsAndColumnsResult<(usize, usize)>` - (rows scanned, rows kept)
+    fn stream_matching_rows<F>(&self, mut matching_row_visitor: F) -> RowsAndColumnsResult<(usize, usize)>
+    where
+        F: FnMut(&[String]) -> RowsAndColumnsResult<CsvRowStreamControl>,
+    {
+        let mut kept_row_count = 0;
+        let scanned_row_count = stream_csv_data_rows(
+            self.csv_input_file.csv_file_path(),
+            self.csv_analysis_results.has_header_row,
+            |_data_row_index, field_values| {
+                if self.maximum_matching_row_count.is_some_and(|maximum_row_count| kept_row_count >= maximum_row_count) {
+                    return Ok(CsvRowStreamControl::StopReading);
+                }
+                if let Some(compiled_row_filter) = &self.compiled_row_filter
+                    && !compiled_row_filter.matches_row(field_values) {
+                    return Ok(CsvRowStreamControl::ContinueReading);
+                }
+                let projected_values: Vec<String> = self.selected_column_positions
+                    .iter()
+                    .map(|column_position| field_values.get(*column_position).cloned().unwrap_or_default())
+                    .collect();
+                let stream_control = matching_row_visitor(&projected_values)?;
+                kept_row_count += 1;
+                Ok(stream_control)
+            }
+        )?;
+        Ok((scanned_row_count, kept_row_count))
+    }
+}
+
+/// Analyzes the file and compiles the `query` flags
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path, `--where`, `--columns` and `--limit`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<PreparedRowQuery>` - The query, ready to stream
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FilterExpressionError` - If `--where` does not compile
+/// * `RowsAndColumnsError::CommandLineUsageError` - If `--columns` or `--limit` is invalid
+fn prepare_row_query(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<PreparedRowQuery> {
+    let csv_input_file = open_csv_file_argument_for_multiple_passes(subcommand_arguments.required_positional_argument()?)?;
+    let csv_analysis_results = analyze_csv_file_structure_and_types(csv_input_file.csv_file_path())?;
+    let selected_column_positions = select_columns_for_subcommand(
+        subcommand_arguments,
+        &list_analyzed_column_names(&csv_analysis_results)
+    )?;
+    let compiled_row_filter = subcommand_arguments.flag_value("where")
+        .map(|filter_expression_text| compile_row_filter_expression(filter_expression_text, &csv_analysis_results))
+        .transpose()?;
+    let maximum_matching_row_count = subcommand_arguments.flag_value("limit")
+        .map(|limit_text| {
+            limit_text.parse::<usize>().map_err(|_| {
+                create_command_line_usage_error(&format!("--limit must be a whole number, not '{}'", limit_text), Some("query"))
+            })
+        })
+        .transpose()?;
+    
+    Ok(PreparedRowQuery {
+        csv_input_file,
+        csv_analysis_results,
+        selected_column_positions,
+        compiled_row_filter,
+        maximum_matching_row_count,
+    })
+}
+
+/// `query` (CSV output): prints the matching rows, or writes them with `--output`
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and query flags
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_query_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    let prepared_row_query = prepare_row_query(subcommand_arguments)?;
+    let selected_column_names = prepared_row_query.selected_column_names();
+    
+    match subcommand_arguments.flag_value("output") {
+        Some(output_path_text) => {
+            // Written files get metadata, like every other CSV export
+            let output_csv_path = PathBuf::from(output_path_text);
+            let mut export_writer = StreamingCsvExportWriter::create(
+                &output_csv_path,
+                &selected_column_names,
+                &prepared_row_query.selected_column_data_types()
+            )?;
+            let (scanned_row_count, _) = prepared_row_query.stream_matching_rows(|projected_values| {
+                export_writer.write_row(projected_values)?;
+                Ok(CsvRowStreamControl::ContinueReading)
+            })?;
+            let (written_row_count, metadata_file_path) = export_writer.finish()?;
+            print_result_line(&format!("✅ Kept {} of {} rows: {}", written_row_count, scanned_row_count, output_csv_path.display()));
+            print_output_line(&format!("   Metadata: {}", metadata_file_path.display()));
+        }
+        None => {
+            // Reading stops once the reader of the pipe has gone away (`| head`)
+            print_output_line(&selected_column_names.join(","));
+            prepared_row_query.stream_matching_rows(|projected_values| {
+                print_output_line(&projected_values.join(","));
+                Ok(if is_standard_output_closed() { CsvRowStreamControl::StopReading } else { CsvRowStreamControl::ContinueReading })
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// `query --format json`: collects the matching rows as typed JSON objects
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and query flags
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<JsonValue>` - `{ "scanned_rows", "matching_rows", "rows": [ { column: value } ] }`
+fn collect_query_rows_as_json(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<JsonValue> {
+    let prepared_row_query = prepare_row_query(subcommand_arguments)?;
+    let selected_column_names = prepared_row_query.selected_column_names();
+    let selected_column_data_types = prepared_row_query.selected_column_data_types();
+    
+    let mut row_objects = Vec::new();
+    let (scanned_row_count, kept_row_count) = prepared_row_query.stream_matching_rows(|projected_values| {
+        row_objects.push(JsonValue::Object(
+            selected_column_names
+                .iter()
+                .zip(projected_values.iter().zip(&selected_column_data_types))
+                .map(|(column_name, (field_value, column_data_type))| {
+                    (column_name.clone(), convert_field_value_to_typed_json(field_value, column_data_type))
+                })
+                .collect()
+        ));
+        Ok(CsvRowStreamControl::ContinueReading)
+    })?;
+    
+    Ok(JsonValue::object(vec![
+        ("scanned_rows", JsonValue::from_count(scanned_row_count)),
+        ("matching_rows", JsonValue::from_count(kept_row_count)),
+        ("rows", JsonValue::Array(row_objects)),
+    ]))
+}
+
+/// Converts a CSV value to JSON by its column type (text when it does not parse)
+fn convert_field_value_to_typed_json(field_value: &str, column_data_type: &CsvColumnDataType) -> JsonValue {
+    if field_value.is_empty() {
+        return JsonValue::Null;
+    }
+    let lowercase_value = field_value.to_lowercase();
+    match column_data_type {
+        CsvColumnDataType::Integer => field_value.parse::<i64>()
+            .map(JsonValue::Integer)
+            .unwrap_or_else(|_| JsonValue::Text(field_value.to_string())),
+        CsvColumnDataType::Float => field_value.parse::<f64>()
+            .map(JsonValue::Float)
+            .unwrap_or_else(|_| JsonValue::Text(field_value.to_string())),
+        CsvColumnDataType::Boolean if is_boolean_value(&lowercase_value) => {
+            JsonValue::Boolean(matches!(lowercase_value.as_str(), "true" | "yes" | "1" | "t" | "y"))
+        }
+        _ => JsonValue::Text(field_value.to_string()),
+    }
+}
+
+/// `validate`: checks values against the declared column types
+/// 
+/// The report is printed in full (text or JSON); when anything is invalid a
+/// `DataTypeValidationError` is returned afterwards so the exit code says so.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and optional `--columns`
+/// * `json_output_requested` - Print the JSON report instead of text
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success when every checked value is valid
+fn run_validate_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+    json_output_requested: bool,
+) -> RowsAndColumnsResult<()> {
+    let validation_outcome = validate_csv_file_for_subcommand(subcommand_arguments);
+    if json_output_requested {
+        match &validation_outcome {
+            Ok(validation_report) => print_output_line(&csv_validation_report_to_json(validation_report).to_json_text(true)),
+            Err(validation_error) => print_output_line(&error_to_json(validation_error).to_json_text(true)),
+        }
+    }
+    let validation_report = validation_outcome?;
+    if !json_output_requested {
+        display_csv_validation_report(&validation_report);
+    }
+    if validation_report.is_valid() {
+        return Ok(());
+    }
+    
+    // Name the first problem in the error; the report above lists the rest
+    let (first_invalid_value, expected_data_type) = validation_report.column_reports
+        .iter()
+        .find_map(|column_report| {
+            column_report.invalid_value_examples.first().map(|invalid_example| {
+                (invalid_example.invalid_value.clone(), column_report.declared_data_type.to_toml_string().to_string())
+            })
+        })
+        .or_else(|| {
+            validation_report.ragged_row_examples.first().map(|(_, field_count)| {
+                (format!("{} fields", field_count), format!("{} fields", validation_report.expected_field_count))
+            })
+        })
+        .unwrap_or_default();
+    Err(create_data_type_validation_error(
+        &format!(
+            "{} invalid values and {} ragged rows in {}",
+            validation_report.total_invalid_value_count(),
+            validation_report.ragged_row_count,
+            validation_report.csv_file_path.display()
+        ),
+        &first_invalid_value,
+        &expected_data_type
+    ))
+}
+
+/// Reads the declared column types and validates the file against them
+/// 
+/// The declared types are read from the existing metadata file without
+/// re-analyzing (which would overwrite them with detected types). A file
+/// without metadata is analyzed first, so it is checked against detected types.
+/// Compressed and streamed input is read from a temporary copy whose metadata
+/// is removed with it, so it is always checked against detected types, and the
+/// report names the input as given rather than the copy.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - CSV path and optional `--columns`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<CsvValidationReport>` - The report
+fn validate_csv_file_for_subcommand(
+    subcommand_arguments: &ParsedSubcommandArguments,
+) -> RowsAndColumnsResult<CsvValidationReport> {
+    let csv_file_path_argument = subcommand_arguments.required_positional_argument()?;
+    let csv_input_file = open_csv_file_argument_for_multiple_passes(csv_file_path_argument)?;
+    let csv_file_absolute_path = csv_input_file.csv_file_path();
+    let metadata_file_path = determine_metadata_file_path(csv_file_absolute_path)?;
+    
+    // Step 1: Declared types (a first run saves the detected ones, except for a temporary copy)
+    if !metadata_file_path.exists() {
+        match &csv_input_file {
+            MultiPassCsvInputFile::OriginalFile(_) => print_warning_line(&format!(
+                "No metadata file yet; checking against detected types (saved to {})",
+                metadata_file_path.display()
+            )),
+            MultiPassCsvInputFile::Spooled(_) => print_warning_line(&format!(
+                "{}: no metadata is kept for compressed or streamed input; checking against detected types",
+                resolve_csv_input_source(csv_file_path_argument).source_label().display()
+            )),
+        }
+        analyze_csv_file_structure_and_types(csv_file_absolute_path)?;
+    }
+    let declared_columns = read_csv_metadata_columns(&metadata_file_path)?;
+    let (has_header_row, _, _) = analyze_csv_basic_structure(csv_file_absolute_path)?;
+    
+    // Step 2: Check the selected columns
+    let declared_column_names: Vec<String> = declared_columns
+        .iter()
+        .map(|declared_column| declared_column.column_name.clone())
+        .collect();
+    let selected_column_positions = select_columns_for_subcommand(subcommand_arguments, &declared_column_names)?;
+    let mut validation_report = validate_csv_file_against_column_types(
+        csv_file_absolute_path,
+        has_header_row,
+        &declared_columns,
+        &selected_column_positions
+    )?;
+    
+    // Step 3: Name the input as given, not the temporary copy that is about to be removed
+    if let MultiPassCsvInputFile::Spooled(_) = csv_input_file {
+        validation_report.csv_file_path = resolve_csv_input_source(csv_file_path_argument).source_label();
+    }
+    Ok(validation_report)
+}
+
+/// `datasets`: lists the datasets in the directory store
+/// 
+/// # Arguments
+/// * `json_output_requested` - Print a JSON document instead of a table
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_datasets_subcommand(json_output_requested: bool) -> RowsAndColumnsResult<()> {
+    let open_store_datasets = |directory_paths: &ApplicationDirectoryPaths| -> RowsAndColumnsResult<Vec<DirectoryStoreDataset>> {
+        let mut store_datasets = Vec::new();
+        for (dataset_directory_path, opened_dataset) in list_directory_store_datasets(&directory_paths.csv_imports_directory)? {
+            match opened_dataset {
+                Ok(store_dataset) => store_datasets.push(store_dataset),
+                Err(open_error) => {
+                    print_warning_line(&format!("Skipping {}: {}", dataset_directory_path.display(), open_error));
+                }
+            }
+        }
+        Ok(store_datasets)
+    };
+    
+    if json_output_requested {
+        return print_json_document_or_error(|| {
+            let directory_paths = initialize_application_directory_structure()?;
+            let dataset_values = open_store_datasets(&directory_paths)?
+                .iter()
+                .map(|store_dataset| JsonValue::object(vec![
+                    ("name", JsonValue::Text(store_dataset.dataset_name())),
+                    ("path", JsonValue::Text(store_dataset.dataset_directory_path.display().to_string())),
+                    ("source_csv_path", JsonValue::Text(store_dataset.source_csv_path.clone())),
+                    ("total_rows", JsonValue::from_count(store_dataset.total_row_count)),
+                    ("columns", JsonValue::Array(
+                        store_dataset.column_names.iter().cloned().map(JsonValue::Text).collect()
+                    )),
+                ]))
+                .collect();
+            Ok(JsonValue::object(vec![
+                ("data_directory", JsonValue::Text(directory_paths.rows_columns_root_directory.display().to_string())),
+                ("data_directory_source", JsonValue::Text(
+                    describe_data_directory_source(directory_paths.data_directory_source)
+                )),
+                ("datasets", JsonValue::Array(dataset_values)),
+            ]))
+        });
+    }
+    
+    let directory_paths = initialize_application_directory_structure()?;
+    let store_datasets = open_store_datasets(&directory_paths)?;
+    let data_directory_line = format!(
+        "Data directory: {} ({})",
+        directory_paths.rows_columns_root_directory.display(),
+        describe_data_directory_source(directory_paths.data_directory_source)
+    );
+    if store_datasets.is_empty() {
+        print_output_line("No datasets yet. Import one with: rows_and_columns import <csv_file>");
+        print_output_line(&data_directory_line);
+        return Ok(());
+    }
+    let header_cells: Vec<String> = ["Dataset", "Rows", "Columns", "Source CSV"].iter().map(|heading| heading.to_string()).collect();
+    let body_rows: Vec<Vec<String>> = store_datasets
+        .iter()
+        .map(|store_dataset| vec![
+            store_dataset.dataset_name(),
+            store_dataset.total_row_count.to_string(),
+            store_dataset.column_names.len().to_string(),
+            store_dataset.source_csv_path.clone(),
+        ])
+        .collect();
+    for table_line in format_aligned_text_table_lines(
+        &header_cells,
+        &body_rows,
+        &[false, true, true, false],
+        MAXIMUM_DATASET_TABLE_COLUMN_WIDTH,
+        '─',
+    ) {
+        print_output_line(&table_line);
+    }
+    print_output_line("");
+    print_output_line(&data_directory_line);
+    Ok(())
+}
+
+/// `batch`: analyzes every CSV file of a directory and writes `batch_index.md`
+/// 
+/// Files that fail are listed with their error; the batch still succeeds.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - Directory, `--recursive`, `--report` and `--output`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_batch_subcommand(subcommand_arguments: &ParsedSubcommandArguments) -> RowsAndColumnsResult<()> {
+    let batch_analysis_summary = run_batch_subcommand_analysis(subcommand_arguments)?;
+    
+    for file_result in &batch_analysis_summary.file_results {
+        match &file_result.file_outcome {
+            BatchFileOutcome::Analyzed { total_data_row_count, total_column_count, schema_label, .. } => {
+                print_result_line(&format!(
+                    "✅ {}: {} rows × {} columns (schema {})",
+                    file_result.relative_file_name, total_data_row_count, total_column_count, schema_label
+                ));
+            }
+            BatchFileOutcome::Failed { error_message } => {
+                print_result_line(&format!("❌ {}: {}", file_result.relative_file_name, error_message));
+            }
+        }
+    }
+    print_result_line(&format!(
+        "📋 {} analyzed, {} failed, {} distinct schemas",
+        batch_analysis_summary.analyzed_file_count(),
+        batch_analysis_summary.failed_file_count(),
+        batch_analysis_summary.schema_groups.len()
+    ));
+    print_result_line(&format!("✅ Batch index written to: {}", batch_analysis_summary.index_file_path.display()));
+    Ok(())
+}
+
+/// Resolves the batch directory and options, then runs the batch
+/// 
+/// The reports go to `--output`, else to `analysis_cache/batch_<directory>/`.
+/// 
+/// # Arguments
+/// * `subcommand_arguments` - Directory, `--recursive`, `--report` and `--output`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<BatchAnalysisSummary>` - Per-file results and schema groups
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the path is not a directory or holds no CSV files
+/// * `RowsAndColumnsError::FileSystemError` - If the directory cannot be searched or the output written
+fn run_batch_subcommand_analysis(
+    subcommand_arguments: &ParsedSubcommandArguments,
+) -> RowsAndColumnsResult<BatchAnalysisSummary> {
+    // Step 1: The argument must be a directory with CSV files in it
+    let batch_directory_path = PathBuf::from(subcommand_arguments.required_positional_argument()?);
+    if !batch_directory_path.is_dir() {
+        return Err(create_configuration_error(&format!(
+            "Not a directory: {} (use `describe` for a single file)",
+            batch_directory_path.display()
+        )));
+    }
+    let include_subdirectories = subcommand_arguments.has_switch("recursive");
+    if find_batch_csv_files(&batch_directory_path, include_subdirectories)?.is_empty() {
+        let recursive_hint = if include_subdirectories { "" } else { " (add --recursive to search subdirectories)" };
+        return Err(create_configuration_error(&format!(
+            "No CSV files found in {}{}",
+            batch_directory_path.display(),
+            recursive_hint
+        )));
+    }
+    
+    // Step 2: Pick the report format and output directory
+    let report_format = match subcommand_arguments.flag_value("report") {
+        Some("txt") => AnalysisReportFormat::PlainText,
+        Some("html") => AnalysisReportFormat::Html,
+        _ => AnalysisReportFormat::Markdown,
+    };
+    let directory_paths = initialize_application_directory_structure()?;
+    let batch_output_directory = match subcommand_arguments.flag_value("output") {
+        Some(output_directory_text) => PathBuf::from(output_directory_text),
+        None => {
+            let batch_directory_name = batch_directory_path
+                .canonicalize()
+                .unwrap_or_else(|_| batch_directory_path.clone())
+                .file_name()
+                .map(|directory_name| sanitize_name_for_directory(&directory_name.to_string_lossy()))
+                .unwrap_or_else(|| "directory".to_string());
+            directory_paths.analysis_cache_directory.join(format!("batch_{}", batch_directory_name))
+        }
+    };
+    
+    // Step 3: Analyze every file
+    print_progress_line(&format!("🔍 Analyzing CSV files in {} ...", batch_directory_path.display()));
+    run_batch_analysis(&batch_directory_path, &BatchAnalysisOptions {
+        include_subdirectories,
+        report_format,
+        batch_output_directory,
+        temporary_work_directory: directory_paths.temporary_work_directory.clone(),
+    })
+}
+
+/// Displays usage help information for command line interface
+/// 
+/// The subcommand list and examples come from the command line specifications,
+/// so the help always matches what the parser accepts.
+fn display_usage_help_information() {
+    print_output_text(&format_general_help_text());
+    println!();
+    println!("FEATURES:");
+    println!("  • Directory-based CSV data storage for scalability");
+    println!("  • Pandas-style statistical analysis");
+    println!("  • ASCII/Unicode TUI charts and visualizations");
+    println!("  • Binary-relative path management for portability");
+    println!();
+}
+
+// /// Processes a CSV file specified via command line argument
+// /// 
+// /// This function validates the provided CSV file path, converts it to an absolute path,
+// /// and prepares it for processing. This follows the 'lines' pattern of direct file processing.
+// /// 
+// /// # Arguments
+// /// * `csv_file_path_argument` - The CSV file path provided as command line argument
+// /// * `directory_paths` - The application directory structure for data storage
+// /// 
+// /// # Returns
+// /// * `RowsAndColumnsResult<()>` - Success or detailed error information
+// /// 
+// /// # Errors
+// /// * `RowsAndColumnsError::FileSystemError` - If file access or validation fails
+// /// * `RowsAndColumnsError::ConfigurationError` - If file is not a valid CSV
+// fn process_csv_file_from_command_line(
+//     csv_file_path_argument: &str,
+//     directory_paths: &ApplicationDirectoryPaths,
+// ) -> RowsAndColumnsResult<()> {
+//     println!("Processing CSV file: {}", csv_file_path_argument);
+//     println!();
+    
+//     // Step 1: Validate the provided file path
+//     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+    
+//     // Step 2: Display file information
+//     display_csv_file_processing_information(&csv_file_absolute_path)?;
+    
+//     // Step 3: TODO - In next implementation phase, this will:
+//     // - Parse CSV headers and create metadata
+//     // - Create column directory structure
+//     // - Process CSV data into directory-based storage
+//     // - Generate statistical analysis
+//     // - Show TUI dashboard options
     
 //     println!("✓ CSV file validated and ready for processing");
 //     println!("  File: {}", csv_file_absolute_path.display());
@@ -416,109 +1372,989 @@ fn validate_user_provided_csv_file_path(user_file_path: &str) -> Result<String,
 //     println!("Processing CSV file: {}", csv_file_path_argument);
 //     println!();
     
-//     // Step 1: Validate the provided file path
-//     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+//     // Step 1: Validate the provided file path
+//     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+    
+//     // Step 2: Display basic file information
+//     display_csv_file_processing_information(&csv_file_absolute_path)?;
+    
+//     // Step 3: Analyze CSV structure and column types
+//     let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
+    
+//     // Step 4: Display detailed analysis results
+//     display_csv_analysis_results(&csv_analysis_results)?;
+    
+//     // Step 5: Show next steps for user
+//     display_csv_processing_completion_status(&csv_analysis_results, directory_paths);
+    
+//     Ok(())
+// }
+
+/// Launches interactive menu system after CSV analysis is complete
+/// 
+/// This function provides a logical progression of options based on what
+/// has been accomplished so far (CSV analysis) and what can be done next.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - The basic CSV analysis results
+/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// * `correlation_analysis_results` - Correlation matrices across continuous columns
+/// * `directory_paths` - Application directory structure
+/// * `active_row_filter_text` - The filter expression when this menu works on a
+///   filtered subset (option 8); quitting then returns to the full data's menu
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn launch_interactive_post_analysis_menu(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    directory_paths: &ApplicationDirectoryPaths,
+    active_row_filter_text: Option<&str>,
+) -> RowsAndColumnsResult<()> {
+    match active_row_filter_text {
+        Some(row_filter_text) => {
+            println!("Working on the rows where: {}", row_filter_text);
+            println!("Every option below now uses only these rows; 'q' returns to the full data.");
+        }
+        None => println!("Analysis complete! Choose your next step:"),
+    }
+    println!();
+    
+    // Dataset imported into the directory store during this session, if any
+    let mut loaded_store_dataset: Option<DirectoryStoreDataset> = None;
+    
+    loop {
+        // Display menu options
+        display_post_analysis_main_menu();
+        
+        // Get user selection
+        let user_selection = get_user_menu_selection("Selection")?;
+        
+        // Process user choice
+        match user_selection.to_lowercase().as_str() {
+            "1" | "review" | "types" | "edit" => {
+                println!("🔧 Column data type review selected.");
+                println!("This will allow you to verify and modify detected column types.");
+                println!("(Implementation coming in next step)");
+                println!();
+            }
+            "2" | "load" | "import" | "directory" => {
+                println!("📂 Loading data into directory structure...");
+                println!("Data will be stored in: {}", directory_paths.csv_imports_directory.display());
+                let store_dataset = import_csv_with_derived_columns(
+                    csv_analysis_results,
+                    &directory_paths.csv_imports_directory
+                )?;
+                println!("✅ Stored {} rows × {} columns in: {}",
+                    store_dataset.total_row_count,
+                    store_dataset.column_names.len(),
+                    store_dataset.dataset_directory_path.display()
+                );
+                println!("   Each value is at column_NNN_name/row_NNNN/value.txt");
+                println!();
+                loaded_store_dataset = Some(store_dataset);
+            }
+            "3" | "export" | "report" | "save" => {
+                run_export_report_menu_action(csv_analysis_results, enhanced_analysis_results, directory_paths)?;
+            }
+            "4" | "dashboard" | "tui" | "charts" => {
+                launch_tui_dashboard(
+                    csv_analysis_results,
+                    enhanced_analysis_results,
+                    correlation_analysis_results,
+                    &directory_paths.analysis_cache_directory,
+                )?;
+                println!();
+            }
+            "5" | "rows" | "grid" | "view" => {
+                // Prefer the directory store copy when the data has been loaded
+                let grid_row_source = match &loaded_store_dataset {
+                    Some(store_dataset) => GridRowSource::DirectoryStore(store_dataset.clone()),
+                    None => GridRowSource::open_csv_file(csv_analysis_results)?,
+                };
+                launch_data_grid_viewer(&grid_row_source)?;
+                println!();
+            }
+            "6" | "crosstab" | "chi" | "chisquare" => {
+                run_crosstab_menu_action(csv_analysis_results, enhanced_analysis_results)?;
+            }
+            "7" | "group" | "groupby" | "aggregate" => {
+                run_group_by_menu_action(
+                    csv_analysis_results,
+                    enhanced_analysis_results,
+                    loaded_store_dataset.as_ref(),
+                    directory_paths,
+                )?;
+            }
+            "8" | "filter" | "where" | "subset" => {
+                run_filter_rows_menu_action(csv_analysis_results, directory_paths)?;
+            }
+            "9" | "sort" | "order" => {
+                run_sort_rows_menu_action(csv_analysis_results, loaded_store_dataset.as_ref(), directory_paths)?;
+            }
+            "10" | "join" | "merge" => {
+                run_join_datasets_menu_action(csv_analysis_results, loaded_store_dataset.as_ref(), directory_paths)?;
+            }
+            "11" | "derive" | "derived" | "compute" => {
+                if let Some(extended_dataset) = run_derived_column_menu_action(csv_analysis_results, loaded_store_dataset.as_ref())? {
+                    loaded_store_dataset = Some(extended_dataset);
+                }
+            }
+            "12" | "sample" | "random" => {
+                run_sample_rows_menu_action(csv_analysis_results, loaded_store_dataset.as_ref(), directory_paths)?;
+            }
+            "q" | "quit" | "exit" if active_row_filter_text.is_some() => {
+                println!("Leaving the filtered subset; back to the full data.");
+                println!();
+                return Ok(());
+            }
+            "q" | "quit" | "exit" => {
+                println!("Thank you for using rows_and_columns!");
+                println!("Your analysis results and metadata have been saved.");
+                return Ok(());
+            }
+            "help" | "h" | "?" => {
+                display_post_analysis_menu_help();
+            }
+            "" => {
+                println!("Please enter a selection (1-12, q) or 'help' for assistance.");
+                println!();
+            }
+            _ => {
+                println!("Invalid selection: '{}'", user_selection);
+                println!("Please choose 1-12 or q, or type 'help' for assistance.");
+                println!();
+            }
+        }
+    }
+}
+
+/// Displays the main post-analysis menu options
+/// 
+/// This shows users what they can do next after CSV analysis is complete,
+/// following a logical progression from analysis → data loading → advanced features.
+fn display_post_analysis_main_menu() {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  What would you like to do next?");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  1. Review/Edit Column Data Types");
+    println!("  2. 'Load' Data into No-Load DataFrame (not in active memory)");
+    println!("  3. Export Current Analysis Report");
+    println!("  4. Open Full-Screen Dashboard (statistics and charts)");
+    println!("  5. Browse Rows in Data Grid Viewer");
+    println!("  6. Crosstab & Chi-Square Test (two categorical columns)");
+    println!("  7. Group-By Aggregation (e.g. mean age by breed)");
+    println!("  8. Filter Rows (e.g. age >= 3 and breed != \"Persian\")");
+    println!("  9. Sort Rows into a New CSV (e.g. age descending, then name)");
+    println!(" 10. Join with Another CSV or Stored Dataset (e.g. on owner_id)");
+    println!(" 11. Add a Derived Column (e.g. age_months = age * 12)");
+    println!(" 12. Random Sample of Rows (uniform or balanced by a column)");
+    println!("  q. Quit");
+    println!();
+    println!("  💡 Tip: Data loading (option 2) enables visualizations and advanced analysis");
+    println!("  Type 'help' for detailed descriptions of each option.");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+}
+
+/// Asks for two categorical columns and displays their crosstab analysis
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `enhanced_analysis_results` - Per-column results (categorical statistics)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_crosstab_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+) -> RowsAndColumnsResult<()> {
+    let candidate_positions = list_crosstab_candidate_positions(enhanced_analysis_results);
+    
+    if candidate_positions.len() < 2 {
+        println!("A crosstab needs at least two categorical columns; this file has {}.", candidate_positions.len());
+        println!();
+        return Ok(());
+    }
+    
+    println!("Categorical columns:");
+    for &candidate_position in &candidate_positions {
+        let basic_info = &enhanced_analysis_results[candidate_position].basic_info;
+        println!("  {}. {}", basic_info.column_index + 1, basic_info.column_name);
+    }
+    println!();
+    
+    // Column numbers as shown above (1-based CSV column numbers)
+    let mut selected_positions = Vec::new();
+    for prompt_text in ["Row column number", "Column column number"] {
+        let user_input = get_user_menu_selection(prompt_text)?;
+        let matching_position = user_input.parse::<usize>().ok().and_then(|column_number| {
+            candidate_positions.iter().copied().find(|&candidate_position| {
+                enhanced_analysis_results[candidate_position].basic_info.column_index + 1 == column_number
+            })
+        });
+        
+        match matching_position {
+            Some(column_position) => selected_positions.push(column_position),
+            None => {
+                println!("'{}' is not one of the categorical column numbers listed above.", user_input);
+                println!();
+                return Ok(());
+            }
+        }
+    }
+    
+    if selected_positions[0] == selected_positions[1] {
+        println!("Please choose two different columns.");
+        println!();
+        return Ok(());
+    }
+    
+    let crosstab_results = perform_crosstab_analysis(
+        csv_analysis_results,
+        &enhanced_analysis_results[selected_positions[0]],
+        &enhanced_analysis_results[selected_positions[1]],
+    )?;
+    display_crosstab_analysis_results(&crosstab_results);
+    
+    Ok(())
+}
+
+/// Asks for a report format and destination, then writes the analysis report
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file facts)
+/// * `enhanced_analysis_results` - Per-column statistics
+/// * `directory_paths` - Application directories (default reports go to `analysis_cache/`)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_export_report_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    // Step 1: Choose the format(s)
+    let format_input = get_user_menu_selection("Report format: 1 = Markdown, 2 = plain text, 3 = HTML, 4 = all [1]")?;
+    let report_formats = match format_input.to_lowercase().as_str() {
+        "" | "1" | "md" | "markdown" => vec![AnalysisReportFormat::Markdown],
+        "2" | "txt" | "text" => vec![AnalysisReportFormat::PlainText],
+        "3" | "html" => vec![AnalysisReportFormat::Html],
+        "4" | "all" => vec![AnalysisReportFormat::Markdown, AnalysisReportFormat::PlainText, AnalysisReportFormat::Html],
+        _ => {
+            println!("Invalid format: '{}'. Please choose 1, 2, 3 or 4.", format_input);
+            println!();
+            return Ok(());
+        }
+    };
+    
+    // Step 2: Choose the destination (the extension always follows the format)
+    let default_report_path = determine_default_report_file_path(
+        csv_analysis_results,
+        &directory_paths.analysis_cache_directory,
+        report_formats[0],
+    );
+    let path_input = get_user_menu_selection(&format!("Report file path [{}]", default_report_path.display()))?;
+    
+    // Step 3: Write each report
+    for report_format in report_formats {
+        let report_file_path = if path_input.is_empty() {
+            determine_default_report_file_path(csv_analysis_results, &directory_paths.analysis_cache_directory, report_format)
+        } else {
+            PathBuf::from(&path_input).with_extension(report_format.file_extension())
+        };
+        write_analysis_report_file(csv_analysis_results, enhanced_analysis_results, report_format, &report_file_path)?;
+        println!("✅ {} report written to: {}", report_format.display_name(), report_file_path.display());
+    }
+    println!();
+    
+    Ok(())
+}
+
+/// Asks for group and aggregate columns, runs the group-by and offers an export
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `enhanced_analysis_results` - Per-column results (field types for the column list)
+/// * `loaded_store_dataset` - Directory store dataset to read instead of the CSV, if loaded
+/// * `directory_paths` - Application directories (exports go to `analysis_cache/`)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_group_by_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    loaded_store_dataset: Option<&DirectoryStoreDataset>,
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    println!("Columns:");
+    for column_info in enhanced_analysis_results {
+        let field_type_label = match column_info.field_type {
+            CsvFieldType::Categorical => "categorical",
+            CsvFieldType::Continuous => "numeric",
+        };
+        println!("  {}. {} ({})", column_info.basic_info.column_index + 1, column_info.basic_info.column_name, field_type_label);
+    }
+    println!();
+    
+    // Step 1: Collect the request (1-based column numbers as listed above)
+    let total_column_count = enhanced_analysis_results.len();
+    let group_column_input = get_user_menu_selection("Group by column numbers (e.g. 2 or 2,3)")?;
+    let group_column_positions = match parse_column_number_list(&group_column_input, total_column_count) {
+        Ok(column_positions) if !column_positions.is_empty() => column_positions,
+        Ok(_) => {
+            println!("Please choose at least one column to group by.");
+            println!();
+            return Ok(());
+        }
+        Err(parse_message) => {
+            println!("{}", parse_message);
+            println!();
+            return Ok(());
+        }
+    };
+    
+    let aggregated_column_input = get_user_menu_selection("Numeric column numbers to aggregate (blank for row counts only)")?;
+    let aggregated_column_positions = match parse_column_number_list(&aggregated_column_input, total_column_count) {
+        Ok(column_positions) => column_positions,
+        Err(parse_message) => {
+            println!("{}", parse_message);
+            println!();
+            return Ok(());
+        }
+    };
+    
+    let mut aggregation_functions = Vec::new();
+    if !aggregated_column_positions.is_empty() {
+        let function_input = get_user_menu_selection("Aggregations: count,sum,mean,min,max,median,std,distinct [count,mean]")?;
+        let function_list_text = if function_input.is_empty() { "count,mean" } else { function_input.as_str() };
+        aggregation_functions = match parse_aggregation_function_list(function_list_text) {
+            Ok(parsed_functions) => parsed_functions,
+            Err(parse_message) => {
+                println!("{}", parse_message);
+                println!();
+                return Ok(());
+            }
+        };
+    }
+    
+    // Step 2: Aggregate from the directory store when loaded, otherwise the CSV
+    let group_by_row_source = match loaded_store_dataset {
+        Some(store_dataset) => DatasetRowSource::DirectoryStore(store_dataset),
+        None => DatasetRowSource::CsvFile(csv_analysis_results),
+    };
+    let group_by_results = perform_group_by_aggregation(&group_by_row_source, &GroupByAggregationRequest {
+        group_column_positions,
+        aggregated_column_positions,
+        aggregation_functions,
+    })?;
+    display_group_by_aggregation_results(&group_by_results);
+    
+    // Step 3: Offer the export
+    let export_answer = get_user_menu_selection("Export as CSV + metadata? (y/n)")?;
+    if matches!(export_answer.to_lowercase().as_str(), "y" | "yes") {
+        let source_stem = csv_analysis_results.csv_file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let export_file_name = format!(
+            "{}_by_{}.csv",
+            sanitize_name_for_directory(&source_stem),
+            sanitize_name_for_directory(&group_by_results.group_column_names.join("_"))
+        );
+        let output_csv_path = directory_paths.analysis_cache_directory.join(export_file_name);
+        let metadata_file_path = export_group_by_results_to_csv(&group_by_results, &output_csv_path)?;
+        println!("✅ Exported {} groups to: {}", group_by_results.result_rows.len(), output_csv_path.display());
+        println!("   Metadata: {}", metadata_file_path.display());
+    }
+    println!();
+    
+    Ok(())
+}
+
+/// Asks for a filter expression, writes the matching rows and opens a menu on them
+/// 
+/// The kept rows are streamed into `analysis_cache/<file>_filtered.csv` with
+/// metadata carrying the source column types. That file is then analyzed like
+/// any input, so statistics, charts and exports in the nested menu describe the
+/// subset only.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the file being filtered
+/// * `directory_paths` - Application directory structure
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error; expression errors are shown
+///   with a caret and do not leave the menu
+fn run_filter_rows_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    // Step 1: Show the columns with the types the expression is checked against
+    println!("Columns:");
+    for column_info in &csv_analysis_results.column_information_list {
+        println!("  {} ({})", column_info.column_name, column_info.detected_data_type.to_toml_string());
+    }
+    println!("Example: age >= 3 and breed != \"Persian\"   (type 'help' at the menu for the full syntax)");
+    println!();
+    
+    let filter_expression_text = get_user_menu_selection("Filter expression (blank to cancel)")?;
+    if filter_expression_text.is_empty() {
+        println!();
+        return Ok(());
+    }
+    
+    // Step 2: Compile; syntax and type errors are reported without leaving the menu
+    let compiled_row_filter = match compile_row_filter_expression(&filter_expression_text, csv_analysis_results) {
+        Ok(compiled_row_filter) => compiled_row_filter,
+        Err(filter_error @ RowsAndColumnsError::FilterExpressionError { .. }) => {
+            println!("{}", filter_error);
+            println!();
+            return Ok(());
+        }
+        Err(other_error) => return Err(other_error),
+    };
+    
+    // Step 3: Stream the matching rows into their own CSV file
+    let source_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let output_csv_path = directory_paths.analysis_cache_directory
+        .join(format!("{}_filtered.csv", sanitize_name_for_directory(&source_stem)));
+    let filter_summary = write_filtered_csv_file(csv_analysis_results, &compiled_row_filter, &output_csv_path)?;
+    
+    println!("✅ Kept {} of {} rows: {}",
+        filter_summary.kept_row_count,
+        filter_summary.scanned_row_count,
+        filter_summary.filtered_csv_file_path.display()
+    );
+    println!();
+    
+    if filter_summary.kept_row_count == 0 {
+        println!("No rows match this filter; nothing to analyze.");
+        println!();
+        return Ok(());
+    }
+    
+    // Step 4: Analyze the subset and hand it to a nested menu
+    let filtered_analysis_results = analyze_csv_file_structure_and_types(&filter_summary.filtered_csv_file_path)?;
+    let filtered_enhanced_results = perform_enhanced_statistical_analysis(
+        &filter_summary.filtered_csv_file_path,
+        &filtered_analysis_results
+    )?;
+    let filtered_correlation_results = perform_correlation_analysis(
+        &filtered_analysis_results,
+        &filtered_enhanced_results
+    )?;
+    
+    display_enhanced_csv_analysis_results(&filtered_enhanced_results)?;
+    display_correlation_analysis_results(&filtered_correlation_results);
+    
+    launch_interactive_post_analysis_menu(
+        &filtered_analysis_results,
+        &filtered_enhanced_results,
+        &filtered_correlation_results,
+        directory_paths,
+        Some(&compiled_row_filter.filter_expression_text),
+    )
+}
+
+/// Asks for sort columns and writes the sorted rows as a new CSV file
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the file being sorted
+/// * `loaded_store_dataset` - The directory store copy, when loaded (option 2)
+/// * `directory_paths` - Application directory structure
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_sort_rows_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    loaded_store_dataset: Option<&DirectoryStoreDataset>,
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    println!("Columns:");
+    for column_info in &csv_analysis_results.column_information_list {
+        println!("  {}. {} ({})",
+            column_info.column_index + 1,
+            column_info.column_name,
+            column_info.detected_data_type.to_toml_string()
+        );
+    }
+    println!();
+    
+    // Step 1: Collect the keys (1-based column numbers as listed above)
+    let sort_key_input = get_user_menu_selection("Sort by (e.g. 3 desc, 2 or 4 asc nulls first)")?;
+    let sort_keys = match parse_sort_key_list(&sort_key_input, csv_analysis_results.total_column_count) {
+        Ok(sort_keys) if !sort_keys.is_empty() => sort_keys,
+        Ok(_) => {
+            println!("Please choose at least one column to sort by.");
+            println!();
+            return Ok(());
+        }
+        Err(parse_message) => {
+            println!("{}", parse_message);
+            println!();
+            return Ok(());
+        }
+    };
+    
+    // Step 2: Sort from the directory store when loaded, otherwise the CSV
+    let sort_row_source = match loaded_store_dataset {
+        Some(store_dataset) => DatasetRowSource::DirectoryStore(store_dataset),
+        None => DatasetRowSource::CsvFile(csv_analysis_results),
+    };
+    let source_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let sorted_column_names: Vec<String> = sort_keys
+        .iter()
+        .map(|sort_key| csv_analysis_results.column_information_list[sort_key.column_position].column_name.clone())
+        .collect();
+    let output_csv_path = directory_paths.analysis_cache_directory.join(format!(
+        "{}_sorted_by_{}.csv",
+        sanitize_name_for_directory(&source_stem),
+        sanitize_name_for_directory(&sorted_column_names.join("_"))
+    ));
+    
+    let sort_summary = perform_external_sort(
+        &sort_row_source,
+        &ExternalSortRequest {
+            sort_keys,
+            memory_budget_bytes: DEFAULT_SORT_MEMORY_BUDGET_BYTES,
+        },
+        &directory_paths.temporary_work_directory,
+        &output_csv_path,
+    )?;
+    
+    println!("✅ Sorted {} rows into: {}", sort_summary.sorted_row_count, sort_summary.sorted_csv_file_path.display());
+    if sort_summary.spilled_run_count > 0 {
+        println!("   Used {} sorted runs on disk and {} merge pass(es)",
+            sort_summary.spilled_run_count,
+            sort_summary.merge_pass_count
+        );
+    }
+    println!("   Metadata: {}", sort_summary.metadata_file_path.display());
+    println!();
+    
+    Ok(())
+}
+
+/// Joins the current data with another CSV file or stored dataset
+/// 
+/// Asks for the other side, the join type and the key columns of each side,
+/// then writes the joined CSV to analysis_cache/ and shows how many rows on
+/// each side found no match. Key type mismatches are reported without
+/// leaving the menu.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the current (left) file
+/// * `loaded_store_dataset` - The directory store copy, when loaded (option 2)
+/// * `directory_paths` - Application directory structure
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+fn run_join_datasets_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    loaded_store_dataset: Option<&DirectoryStoreDataset>,
+    directory_paths: &ApplicationDirectoryPaths,
+) -> RowsAndColumnsResult<()> {
+    // Step 1: Open the other side: a dataset directory or a CSV file
+    let other_path_input = get_user_menu_selection("Other CSV file or stored dataset directory (blank to cancel)")?;
+    if other_path_input.is_empty() {
+        println!();
+        return Ok(());
+    }
+    let other_path = PathBuf::from(other_path_input.trim_matches(|quote_character| quote_character == '"' || quote_character == '\''));
+    let other_store_dataset;
+    let other_csv_input_file;
+    let other_csv_analysis_results;
+    let right_row_source = if other_path.is_dir() {
+        other_store_dataset = open_directory_store_dataset(&other_path)?;
+        DatasetRowSource::DirectoryStore(&other_store_dataset)
+    } else if other_path.is_file() {
+        // A .csv.gz is joined from a decompressed copy that lives until the join is done
+        other_csv_input_file = open_csv_file_argument_for_multiple_passes(&other_path.to_string_lossy())?;
+        other_csv_analysis_results = analyze_csv_file_structure_and_types(other_csv_input_file.csv_file_path())?;
+        DatasetRowSource::CsvFile(&other_csv_analysis_results)
+    } else {
+        println!("Not found: {}", other_path.display());
+        println!();
+        return Ok(());
+    };
+    let left_row_source = match loaded_store_dataset {
+        Some(store_dataset) => DatasetRowSource::DirectoryStore(store_dataset),
+        None => DatasetRowSource::CsvFile(csv_analysis_results),
+    };
+    
+    // Step 2: Show both sides' columns and collect the join type and keys
+    for (side_title, join_row_source) in [("This file", &left_row_source), ("Other", &right_row_source)] {
+        println!("{} ({}) columns:", side_title, join_row_source.source_label());
+        for (column_position, (column_name, column_data_type)) in join_row_source.column_names()
+            .iter()
+            .zip(join_row_source.column_data_types())
+            .enumerate()
+        {
+            println!("  {}. {} ({})", column_position + 1, column_name, column_data_type.to_toml_string());
+        }
+    }
+    println!();
     
-//     // Step 2: Display basic file information
-//     display_csv_file_processing_information(&csv_file_absolute_path)?;
+    let join_type_input = get_user_menu_selection("Join type: inner, left, right or full [inner]")?;
+    let join_type = if join_type_input.is_empty() {
+        JoinType::Inner
+    } else {
+        match JoinType::from_user_text(&join_type_input) {
+            Some(join_type) => join_type,
+            None => {
+                println!("Unknown join type '{}'; use inner, left, right or full.", join_type_input);
+                println!();
+                return Ok(());
+            }
+        }
+    };
     
-//     // Step 3: Analyze CSV structure and column types
-//     let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
+    let left_key_input = get_user_menu_selection("Key column number(s) in this file (e.g. 1 or 1, 3)")?;
+    let right_key_input = get_user_menu_selection("Matching key column number(s) in the other (same order)")?;
+    let key_positions = parse_column_number_list(&left_key_input, left_row_source.column_names().len())
+        .and_then(|left_key_positions| {
+            parse_column_number_list(&right_key_input, right_row_source.column_names().len())
+                .map(|right_key_positions| (left_key_positions, right_key_positions))
+        });
+    let (left_key_positions, right_key_positions) = match key_positions {
+        Ok((left_key_positions, right_key_positions))
+            if !left_key_positions.is_empty() && left_key_positions.len() == right_key_positions.len() =>
+        {
+            (left_key_positions, right_key_positions)
+        }
+        Ok(_) => {
+            println!("Please give the same number of key columns (at least one) for each side.");
+            println!();
+            return Ok(());
+        }
+        Err(parse_message) => {
+            println!("{}", parse_message);
+            println!();
+            return Ok(());
+        }
+    };
     
-//     // Step 4: Display detailed analysis results
-//     display_csv_analysis_results(&csv_analysis_results)?;
+    // Step 3: Join into analysis_cache/<left>_<type>_join_<right>.csv
+    let left_label = left_row_source.source_label();
+    let right_label = right_row_source.source_label();
+    let output_csv_path = directory_paths.analysis_cache_directory.join(format!(
+        "{}_{}_join_{}.csv",
+        sanitize_name_for_directory(&left_label),
+        join_type.short_name(),
+        sanitize_name_for_directory(&right_label)
+    ));
+    
+    let join_result = perform_dataset_join(
+        &left_row_source,
+        &right_row_source,
+        &DatasetJoinRequest {
+            join_type,
+            left_key_positions,
+            right_key_positions,
+            memory_budget_bytes: DEFAULT_JOIN_MEMORY_BUDGET_BYTES,
+        },
+        &directory_paths.temporary_work_directory,
+        &output_csv_path,
+    );
+    match join_result {
+        Ok(join_summary) => display_dataset_join_summary(&join_summary, &left_label, &right_label),
+        Err(type_error @ RowsAndColumnsError::DataTypeValidationError { .. }) => {
+            println!("{}", type_error);
+            println!();
+        }
+        Err(other_error) => return Err(other_error),
+    }
     
-//     // Step 5: Show next steps for user
-//     display_csv_processing_completion_status(&csv_analysis_results, directory_paths);
+    Ok(())
+}
+
+/// Defines a derived column, saves it and shows its statistics
+/// 
+/// The definition is checked against the current columns and earlier derived
+/// columns, appended to the CSV's metadata file and computed on the fly. When
+/// the data is loaded in the directory store it is also written there as a
+/// real column. Expression errors are shown with a caret without leaving the menu.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the current file
+/// * `loaded_store_dataset` - The directory store copy, when loaded (option 2)
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<Option<DirectoryStoreDataset>>` - The extended dataset
+///   when the column was written into the directory store
+fn run_derived_column_menu_action(
+    csv_analysis_results: &CsvAnalysisResults,
+    loaded_store_dataset: Option<&DirectoryStoreDataset>,
+) -> RowsAndColumnsResult<Option<DirectoryStoreDataset>> {
+    // Step 1: Show the columns an expression can use, derived ones included
+    let saved_definitions = read_derived_column_definitions(&csv_analysis_results.metadata_file_path)?;
+    let mut column_names: Vec<String> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.column_name.clone())
+        .collect();
+    let mut column_data_types: Vec<CsvColumnDataType> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.detected_data_type.clone())
+        .collect();
+    
+    println!("Columns:");
+    for (column_name, column_data_type) in column_names.iter().zip(&column_data_types) {
+        println!("  {} ({})", column_name, column_data_type.to_toml_string());
+    }
+    for saved_definition in &saved_definitions {
+        println!("  {} ({}) = {}",
+            saved_definition.derived_column_name,
+            saved_definition.derived_data_type.to_toml_string(),
+            saved_definition.derived_expression_text
+        );
+        column_names.push(saved_definition.derived_column_name.clone());
+        column_data_types.push(saved_definition.derived_data_type.clone());
+    }
+    println!("Example: label = upper(name) || \"-\" || breed   (type 'help' at the menu for more)");
+    println!();
     
-//     Ok(())
-// }
+    let definition_text = get_user_menu_selection("New column (name = expression, blank to cancel)")?;
+    if definition_text.is_empty() {
+        println!();
+        return Ok(None);
+    }
+    
+    // Step 2: Check the definition; syntax and type errors stay in the menu
+    let derived_column_definition = match parse_derived_column_definition(&definition_text, &column_names, &column_data_types) {
+        Ok(derived_column_definition) => derived_column_definition,
+        Err(derived_error @ RowsAndColumnsError::DerivedColumnExpressionError { .. }) => {
+            println!("{}", derived_error);
+            println!();
+            return Ok(None);
+        }
+        Err(other_error) => return Err(other_error),
+    };
+    
+    // Step 3: Save it with the other definitions and compute it on the fly
+    save_derived_column_definition(&csv_analysis_results.metadata_file_path, &derived_column_definition)?;
+    println!("✅ Saved {} ({}) in: {}",
+        derived_column_definition.derived_column_name,
+        derived_column_definition.derived_data_type.to_toml_string(),
+        csv_analysis_results.metadata_file_path.display()
+    );
+    println!();
+    display_derived_column_analysis(csv_analysis_results)?;
+    
+    // Step 4: Write it into the directory store when the data is loaded there
+    match loaded_store_dataset {
+        Some(store_dataset) => {
+            let extended_dataset = materialize_derived_column_into_directory_store(store_dataset, &derived_column_definition)?;
+            println!("✅ Stored {} in: {}",
+                derived_column_definition.derived_column_name,
+                extended_dataset.dataset_directory_path.display()
+            );
+            println!();
+            Ok(Some(extended_dataset))
+        }
+        None => {
+            println!("💡 Tip: loading the data (option 2) also stores derived columns as real columns");
+            println!();
+            Ok(None)
+        }
+    }
+}
 
-/// Launches interactive menu system after CSV analysis is complete
+/// Draws a uniform or stratified random sample into a new CSV
 /// 
-/// This function provides a logical progression of options based on what
-/// has been accomplished so far (CSV analysis) and what can be done next.
+/// Asks for the sample size, an optional column to balance on and a seed,
+/// writes the sample to analysis_cache/ and can describe it straight away
+/// as a quick approximation of the full file.
 /// 
 /// # Arguments
-/// * `csv_analysis_results` - The basic CSV analysis results
-/// * `enhanced_analysis_results` - The enhanced statistical analysis results
+/// * `csv_analysis_results` - Analysis of the file being sampled
+/// * `loaded_store_dataset` - The directory store copy, when loaded (option 2)
 /// * `directory_paths` - Application directory structure
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or error
-fn launch_interactive_post_analysis_menu(
+fn run_sample_rows_menu_action(
     csv_analysis_results: &CsvAnalysisResults,
-    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    loaded_store_dataset: Option<&DirectoryStoreDataset>,
     directory_paths: &ApplicationDirectoryPaths,
 ) -> RowsAndColumnsResult<()> {
-    println!("Analysis complete! Choose your next step:");
+    let sample_row_source = match loaded_store_dataset {
+        Some(store_dataset) => DatasetRowSource::DirectoryStore(store_dataset),
+        None => DatasetRowSource::CsvFile(csv_analysis_results),
+    };
+    let column_names = sample_row_source.column_names();
+    
+    println!("Columns:");
+    for (column_position, (column_name, column_data_type)) in column_names
+        .iter()
+        .zip(sample_row_source.column_data_types())
+        .enumerate()
+    {
+        println!("  {}. {} ({})", column_position + 1, column_name, column_data_type.to_toml_string());
+    }
     println!();
     
-    loop {
-        // Display menu options
-        display_post_analysis_main_menu();
-        
-        // Get user selection
-        let user_selection = get_user_menu_selection("Selection")?;
-        
-        // Process user choice
-        match user_selection.to_lowercase().as_str() {
-            "1" | "review" | "types" | "edit" => {
-                println!("🔧 Column data type review selected.");
-                println!("This will allow you to verify and modify detected column types.");
-                println!("(Implementation coming in next step)");
-                println!();
-            }
-            "2" | "load" | "import" | "directory" => {
-                println!("📂 Data loading into directory structure selected.");
-                println!("This will create the scalable directory-based storage system.");
-                println!("(Implementation coming in next step)");
-                println!();
-            }
-            "3" | "export" | "report" | "save" => {
-                println!("📄 Export analysis report selected.");
-                println!("This will save the current analysis to a file.");
-                println!("(Implementation coming in next step)");
+    // Step 1: Size, optional stratum column and seed
+    let sample_size_input = get_user_menu_selection("Rows to sample (per stratum when stratified) [1000]")?;
+    let sample_row_count = if sample_size_input.is_empty() {
+        1000
+    } else {
+        match sample_size_input.parse::<usize>() {
+            Ok(sample_row_count) if sample_row_count > 0 => sample_row_count,
+            _ => {
+                println!("Please enter a whole number of rows greater than 0.");
                 println!();
-            }
-            "4" | "quit" | "exit" | "q" => {
-                println!("Thank you for using rows_and_columns!");
-                println!("Your analysis results and metadata have been saved.");
                 return Ok(());
             }
-            "help" | "h" | "?" => {
-                display_post_analysis_menu_help();
-            }
-            "" => {
-                println!("Please enter a selection (1-4) or 'help' for assistance.");
-                println!();
-            }
-            _ => {
-                println!("Invalid selection: '{}'", user_selection);
-                println!("Please choose 1-4, or type 'help' for assistance.");
+        }
+    };
+    
+    let stratum_input = get_user_menu_selection("Balance on column number (blank for a uniform sample)")?;
+    let stratum_column_position = match parse_column_number_list(&stratum_input, column_names.len()) {
+        Ok(column_positions) if column_positions.len() <= 1 => column_positions.first().copied(),
+        Ok(_) => {
+            println!("Please choose a single column to balance on.");
+            println!();
+            return Ok(());
+        }
+        Err(parse_message) => {
+            println!("{}", parse_message);
+            println!();
+            return Ok(());
+        }
+    };
+    
+    let seed_input = get_user_menu_selection(&format!("Seed [{}]", DEFAULT_SAMPLING_SEED))?;
+    let sampling_seed = if seed_input.is_empty() {
+        DEFAULT_SAMPLING_SEED
+    } else {
+        match seed_input.parse::<u64>() {
+            Ok(sampling_seed) => sampling_seed,
+            Err(_) => {
+                println!("The seed must be a whole number such as {}.", DEFAULT_SAMPLING_SEED);
                 println!();
+                return Ok(());
             }
         }
+    };
+    
+    // Step 2: Sample into analysis_cache/<file>_sample[_by_<column>]_<size>_seed_<seed>.csv
+    let source_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (sampling_method, stratum_file_part) = match stratum_column_position {
+        Some(stratum_column_position) => (
+            RowSamplingMethod::Stratified { stratum_column_position, rows_per_stratum: sample_row_count },
+            format!("_by_{}", sanitize_name_for_directory(&column_names[stratum_column_position])),
+        ),
+        None => (RowSamplingMethod::Uniform { sample_row_count }, String::new()),
+    };
+    let output_csv_path = directory_paths.analysis_cache_directory.join(format!(
+        "{}_sample{}_{}_seed_{}.csv",
+        sanitize_name_for_directory(&source_stem),
+        stratum_file_part,
+        sample_row_count,
+        sampling_seed
+    ));
+    
+    let sample_summary = perform_row_sampling(
+        &sample_row_source,
+        &RowSamplingRequest { sampling_method, sampling_seed },
+        &output_csv_path,
+    )?;
+    display_row_sample_summary(&sample_summary);
+    
+    // Step 3: Optionally describe the sample as a quick approximation
+    if sample_summary.sampled_row_count == 0 {
+        return Ok(());
+    }
+    let describe_input = get_user_menu_selection("Describe the sample now? (y/N)")?;
+    if matches!(describe_input.to_lowercase().as_str(), "y" | "yes") {
+        let sample_analysis_results = analyze_csv_file_structure_and_types(&sample_summary.sampled_csv_file_path)?;
+        let sample_enhanced_results = perform_enhanced_statistical_analysis(
+            &sample_summary.sampled_csv_file_path,
+            &sample_analysis_results
+        )?;
+        println!("Approximate statistics from {} of {} rows (seed {}):",
+            sample_summary.sampled_row_count,
+            sample_summary.scanned_row_count,
+            sampling_seed
+        );
+        println!();
+        display_enhanced_csv_analysis_results(&sample_enhanced_results)?;
+    }
+    
+    Ok(())
+}
+
+/// Computes and displays statistics for the derived columns saved for a file
+/// 
+/// Nothing is printed when the file has no derived columns.
+/// 
+/// # Arguments
+/// * `csv_analysis_results` - Analysis of the file whose metadata holds the definitions
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+/// 
+/// # Errors
+/// * `DerivedColumnExpressionError` - If a saved definition no longer fits the columns
+fn display_derived_column_analysis(csv_analysis_results: &CsvAnalysisResults) -> RowsAndColumnsResult<()> {
+    let saved_definitions = read_derived_column_definitions(&csv_analysis_results.metadata_file_path)?;
+    if saved_definitions.is_empty() {
+        return Ok(());
+    }
+    
+    let column_names: Vec<String> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.column_name.clone())
+        .collect();
+    let column_data_types: Vec<CsvColumnDataType> = csv_analysis_results.column_information_list
+        .iter()
+        .map(|column_info| column_info.detected_data_type.clone())
+        .collect();
+    let compiled_derived_columns = compile_derived_columns(&column_names, &column_data_types, &saved_definitions)?;
+    let derived_analysis_results = analyze_derived_columns(csv_analysis_results, &compiled_derived_columns)?;
+    
+    print_output_line("Derived columns (computed on the fly from the metadata definitions):");
+    for derived_column_definition in compiled_derived_columns.derived_column_definitions() {
+        print_output_line(&format!("  {} = {}", derived_column_definition.derived_column_name, derived_column_definition.derived_expression_text));
     }
+    print_output_line("");
+    display_enhanced_csv_analysis_results(&derived_analysis_results)
 }
 
-/// Displays the main post-analysis menu options
+/// Parses a comma-separated list of 1-based column numbers
 /// 
-/// This shows users what they can do next after CSV analysis is complete,
-/// following a logical progression from analysis → data loading → advanced features.
-fn display_post_analysis_main_menu() {
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  What would you like to do next?");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  1. Review/Edit Column Data Types");
-    println!("  2. 'Load' Data into No-Load DataFrame (not in active memory)");
-    println!("  3. Export Current Analysis Report");
-    println!("  4. Quit");
-    println!();
-    println!("  💡 Tip: Data loading (option 2) enables visualizations and advanced analysis");
-    println!("  Type 'help' for detailed descriptions of each option.");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
+/// # Arguments
+/// * `column_number_text` - e.g. `2, 3` (blank yields an empty list)
+/// * `total_column_count` - Number of columns available
+/// 
+/// # Returns
+/// * `Result<Vec<usize>, String>` - 0-based column positions (duplicates removed),
+///   or a message describing the first invalid entry
+fn parse_column_number_list(column_number_text: &str, total_column_count: usize) -> Result<Vec<usize>, String> {
+    let mut column_positions = Vec::new();
+    
+    for number_text in column_number_text.split(',').map(str::trim).filter(|number_text| !number_text.is_empty()) {
+        let column_number = number_text.parse::<usize>().ok()
+            .filter(|&column_number| column_number >= 1 && column_number <= total_column_count)
+            .ok_or_else(|| format!("'{}' is not a column number between 1 and {}.", number_text, total_column_count))?;
+        
+        if !column_positions.contains(&(column_number - 1)) {
+            column_positions.push(column_number - 1);
+        }
+    }
+    
+    Ok(column_positions)
 }
 
 /// Gets user menu selection with proper input handling and validation
@@ -534,6 +2370,8 @@ fn display_post_analysis_main_menu() {
 /// 
 /// # Errors
 /// * `RowsAndColumnsError::FileSystemError` - If input/output operations fail
+/// * `RowsAndColumnsError::CommandLineUsageError` - If stdin has ended (e.g. a cron
+///   job with no terminal), instead of asking again forever
 fn get_user_menu_selection(prompt_text: &str) -> RowsAndColumnsResult<String> {
     print!("{}: ", prompt_text);
     
@@ -542,11 +2380,21 @@ fn get_user_menu_selection(prompt_text: &str) -> RowsAndColumnsResult<String> {
         create_file_system_error("Failed to flush stdout for menu input prompt", io_error)
     })?;
     
-    // Read user input from stdin
+    // Read user input from stdin; end of input means nobody can answer
     let mut user_input = String::new();
-    io::stdin().read_line(&mut user_input).map_err(|io_error| {
+    let bytes_read = io::stdin().read_line(&mut user_input).map_err(|io_error| {
         create_file_system_error("Failed to read user input from stdin", io_error)
     })?;
+    if bytes_read == 0 {
+        println!();
+        return Err(create_command_line_usage_error(
+            &format!(
+                "Input ended while waiting for '{}'; for unattended runs use --headless or analyze --no-interactive",
+                prompt_text
+            ),
+            None
+        ));
+    }
     
     // Return trimmed input (removes newlines and whitespace)
     Ok(user_input.trim().to_string())
@@ -577,13 +2425,86 @@ fn display_post_analysis_menu_help() {
     println!();
     
     println!("3. Export Current Analysis Report");
-    println!("   • Saves statistical analysis results to a file");
-    println!("   • Includes column types, statistics, and metadata");
-    println!("   • Useful for documentation and sharing results");
-    println!("   • Can be done before or after data loading");
+    println!("   • Saves statistical analysis results as Markdown (.md), plain text (.txt)");
+    println!("     and/or a self-contained HTML page (.html) with inline SVG charts");
+    println!("   • File facts, per-column statistics, full value distributions, warnings");
+    println!("   • Embedded ASCII histograms and bar charts");
+    println!("   • Written to analysis_cache/ unless you enter another path");
+    println!();
+    
+    println!("4. Open Full-Screen Dashboard (statistics and charts)");
+    println!("   • Column list, statistics, and chart panes in one screen");
+    println!("   • Navigate with arrow keys or h/j/k/l, 'q' to return here");
+    println!("   • Histograms, box plots, scatter plots, bar charts, correlation heatmap");
+    println!("   • Press 's' (SVG), 'p' (PNG) or 'P' (PPM) to save the current chart in analysis_cache/");
+    println!("   • Prints a line-based version when output is not a terminal");
+    println!();
+    
+    println!("5. Browse Rows in Data Grid Viewer");
+    println!("   • Scrollable table of the actual rows, read one screen at a time");
+    println!("   • Reads from the directory store once data is loaded (option 2)");
+    println!("   • ':' jumps to a row, '/' searches, '-' hides and 'p' pins columns");
+    println!("   • Prints the first rows as a table when output is not a terminal");
+    println!();
+    
+    println!("6. Crosstab & Chi-Square Test (two categorical columns)");
+    println!("   • Contingency table with row/column percentages and expected counts");
+    println!("   • Chi-square statistic, degrees of freedom, p-value, and Cramér's V");
+    println!("   • Shaded grid showing how each row's values are distributed");
+    println!("   • Example: breed × is_indoor");
+    println!();
+    
+    println!("7. Group-By Aggregation (e.g. mean age by breed)");
+    println!("   • Group rows by one or more columns");
+    println!("   • count, sum, mean, min, max, median, std, distinct per group");
+    println!("   • Reads from the directory store once data is loaded (option 2)");
+    println!("   • Results can be exported as a new CSV file with metadata");
+    println!();
+    
+    println!("8. Filter Rows (e.g. age >= 3 and breed != \"Persian\")");
+    println!("   • Comparisons = != < <= > >=, and/or/not, parentheses");
+    println!("   • breed in (\"Persian\", \"Siamese\"), color is null, color is not null");
+    println!("   • name contains \"ix\", name starts_with \"Wh\", breed matches \"^S.*e$\"");
+    println!("   • Number columns take numbers, text columns quoted text, boolean columns true/false");
+    println!("   • Matching rows are written to analysis_cache/ and analyzed on their own;");
+    println!("     statistics, charts and exports then use only those rows ('q' goes back)");
+    println!();
+    
+    println!("9. Sort Rows into a New CSV (e.g. age descending, then name)");
+    println!("   • One or more columns, each asc/desc with nulls first/last");
+    println!("   • Numbers sort numerically, text by character, false before true");
+    println!("   • Files larger than memory are sorted in runs under temporary_work/ and merged");
+    println!("   • Reads from the directory store once data is loaded (option 2)");
+    println!("   • The sorted CSV and its metadata are written to analysis_cache/");
+    println!();
+    
+    println!("10. Join with Another CSV or Stored Dataset (e.g. on owner_id)");
+    println!("   • inner, left, right or full outer join on one or more key columns");
+    println!("   • Key types must agree in both metadata files (integer and float may mix)");
+    println!("   • Same-named keys become one column; other shared names get _left/_right");
+    println!("   • Rows with an empty key never match; unmatched counts are shown for each side");
+    println!("   • The smaller side is held in memory when it fits, otherwise both are sorted on disk");
+    println!();
+    
+    println!("11. Add a Derived Column (e.g. age_months = age * 12)");
+    println!("   • name = expression, using other columns and earlier derived columns");
+    println!("   • Arithmetic + - * / %, text joining with ||, comparisons, and/or/not");
+    println!("   • if(cond, a, b) and case when cond then a ... else b end");
+    println!("   • Functions: upper lower trim length substr replace concat abs round floor");
+    println!("     ceil sqrt pow coalesce, casts int float text bool");
+    println!("   • The definition is saved in the .csv_metadata.toml and its statistics are");
+    println!("     computed on the fly; loading (option 2) stores it as a real column");
+    println!();
+    
+    println!("12. Random Sample of Rows (uniform or balanced by a column)");
+    println!("   • Uniform: N rows, each row equally likely, drawn in one pass (reservoir)");
+    println!("   • Stratified: up to N rows for each value of a categorical column");
+    println!("   • The same seed always gives the same sample; rows keep their file order");
+    println!("   • The sample CSV and its metadata are written to analysis_cache/");
+    println!("   • Optionally describe the sample right away for quick approximate statistics");
     println!();
     
-    println!("4. Quit");
+    println!("q. Quit");
     println!("   • Exit the application safely");
     println!("   • Analysis results and metadata files are preserved");
     println!("   • You can restart analysis later with the same CSV file");
@@ -605,6 +2526,7 @@ fn display_post_analysis_menu_help() {
 /// # Arguments
 /// * `csv_file_path_argument` - The CSV file path provided as command line argument
 /// * `directory_paths` - The application directory structure for data storage
+/// * `open_interactive_menu` - Show next steps and the menu (false for `--no-interactive`)
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or detailed error information
@@ -616,33 +2538,64 @@ fn display_post_analysis_menu_help() {
 fn process_csv_file_from_command_line(
     csv_file_path_argument: &str,
     directory_paths: &ApplicationDirectoryPaths,
+    open_interactive_menu: bool,
 ) -> RowsAndColumnsResult<()> {
-    println!("Processing CSV file: {}", csv_file_path_argument);
-    println!();
+    print_output_line(&format!("Processing CSV file: {}", csv_file_path_argument));
+    print_output_line("");
     
-    // Step 1: Validate the provided file path
-    let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+    // Step 1: Validate the provided file path (standard input and pipes are spooled)
+    let csv_input_file = open_csv_file_argument_for_multiple_passes(csv_file_path_argument)?;
+    let csv_file_absolute_path = csv_input_file.csv_file_path();
     
     // Step 2: Display basic file information
-    display_csv_file_processing_information(&csv_file_absolute_path)?;
+    display_csv_file_processing_information(csv_file_absolute_path)?;
     
     // Step 3: Analyze CSV structure and column types (basic analysis)
-    let csv_analysis_results = analyze_csv_file_structure_and_types(&csv_file_absolute_path)?;
+    let csv_analysis_results = analyze_csv_file_structure_and_types(csv_file_absolute_path)?;
     
     // Step 4: Perform enhanced statistical analysis
     let enhanced_analysis_results = perform_enhanced_statistical_analysis(
-        &csv_file_absolute_path,
+        csv_file_absolute_path,
         &csv_analysis_results
     )?;
     
-    // Step 5: Display comprehensive analysis results
+    // Step 5: Correlate continuous columns with each other
+    let correlation_analysis_results = perform_correlation_analysis(
+        &csv_analysis_results,
+        &enhanced_analysis_results
+    )?;
+    
+    // Step 6: Display comprehensive analysis results
     display_enhanced_csv_analysis_results(&enhanced_analysis_results)?;
+    match display_derived_column_analysis(&csv_analysis_results) {
+        Ok(()) => {}
+        Err(derived_error @ RowsAndColumnsError::DerivedColumnExpressionError { .. }) => {
+            print_warning_line(&format!("Skipping saved derived columns: {}", derived_error));
+        }
+        Err(other_error) => return Err(other_error),
+    }
+    display_correlation_analysis_results(&correlation_analysis_results);
     
-    // Step 6: Display completion status and next steps
+    if !open_interactive_menu {
+        return Ok(());
+    }
+    if resolve_csv_input_source(csv_file_path_argument) == CsvInputSource::StandardInput {
+        // The menu reads answers from standard input, which the CSV came through
+        print_warning_line("The CSV was read from standard input, so the interactive menu is not available; analyze a file to use it.");
+        return Ok(());
+    }
+    
+    // Step 7: Display completion status and next steps
     display_enhanced_csv_processing_completion_status(&csv_analysis_results, directory_paths);
     
-    // Step 7: Launch interactive menu for next steps
-    launch_interactive_post_analysis_menu(&csv_analysis_results, &enhanced_analysis_results, directory_paths)?;
+    // Step 8: Launch interactive menu for next steps
+    launch_interactive_post_analysis_menu(
+        &csv_analysis_results,
+        &enhanced_analysis_results,
+        &correlation_analysis_results,
+        directory_paths,
+        None,
+    )?;
 
     Ok(())
 }
@@ -714,13 +2667,14 @@ fn display_enhanced_csv_processing_completion_status(
     println!();
     
     // Show user how to access files and rerun analysis
-    let filename_only = analysis_results.csv_file_path
-        .file_stem()
-        .and_then(|stem| stem.to_str())
-        .unwrap_or("unknown");
+    let metadata_file_status = if analysis_results.metadata_file_already_existed {
+        "updated existing"
+    } else {
+        "created new"
+    };
     
     println!("File references:");
-    println!("  Metadata: {}", analysis_results.metadata_file_path.display());
+    println!("  Metadata: {} ({})", analysis_results.metadata_file_path.display(), metadata_file_status);
     println!("  Original:  {}", analysis_results.csv_file_path.display());
     println!();
     println!("To reprocess this file:");
@@ -846,6 +2800,36 @@ fn display_enhanced_csv_processing_completion_status(
 //     println!();
 // }
 
+/// Opens a command line CSV argument for analysis that reads it several times
+/// 
+/// Regular files are validated and read in place. Standard input (`-`) and
+/// named pipes can be read only once, so they are spooled to a copy under
+/// rows_columns_data/temporary_work that is removed when the result is dropped.
+/// 
+/// # Arguments
+/// * `csv_file_path_argument` - The file path string from command line, or `-`
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<MultiPassCsvInputFile>` - The file to read
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file is missing or spooling fails
+/// * `RowsAndColumnsError::ConfigurationError` - If the path is not a file
+fn open_csv_file_argument_for_multiple_passes(csv_file_path_argument: &str) -> RowsAndColumnsResult<MultiPassCsvInputFile> {
+    match resolve_csv_input_source(csv_file_path_argument) {
+        CsvInputSource::RegularFile(_) => Ok(MultiPassCsvInputFile::OriginalFile(
+            validate_csv_file_path_from_argument(csv_file_path_argument)?
+        )),
+        read_once_source => {
+            let directory_paths = initialize_application_directory_structure()?;
+            Ok(MultiPassCsvInputFile::Spooled(spool_csv_input_to_work_file(
+                &read_once_source,
+                &directory_paths.temporary_work_directory
+            )?))
+        }
+    }
+}
+
 /// Validates a CSV file path provided as command line argument
 /// 
 /// This function checks if the provided path exists, is accessible, and appears
@@ -889,13 +2873,13 @@ fn validate_csv_file_path_from_argument(csv_file_path_argument: &str) -> RowsAnd
             // File appears to be CSV format
         }
         Some(other_extension) => {
-            println!("Warning: File extension '{}' is not typical for CSV files.", other_extension);
-            println!("         Proceeding anyway, but ensure this is a comma-separated values file.");
-            println!();
+            print_warning_line(&format!("Warning: File extension '{}' is not typical for CSV files.", other_extension));
+            print_warning_line("         Proceeding anyway, but ensure this is a comma-separated values file.");
+            print_progress_line("");
         }
         None => {
-            println!("Warning: File has no extension. Ensure this is a comma-separated values file.");
-            println!();
+            print_warning_line("Warning: File has no extension. Ensure this is a comma-separated values file.");
+            print_progress_line("");
         }
     }
     
@@ -939,12 +2923,12 @@ fn display_csv_file_processing_information(csv_file_absolute_path: &PathBuf) ->
         .and_then(|name| name.to_str())
         .unwrap_or("unknown");
     
-    println!("CSV File Information:");
-    println!("  Name: {}", filename_only);
-    println!("  Path: {}", csv_file_absolute_path.display());
-    println!("  Size: {} ({} bytes)", file_size_human_readable, file_size_bytes);
-    println!("  Type: CSV/Text file");
-    println!();
+    print_output_line("CSV File Information:");
+    print_output_line(&format!("  Name: {}", filename_only));
+    print_output_line(&format!("  Path: {}", csv_file_absolute_path.display()));
+    print_output_line(&format!("  Size: {} ({} bytes)", file_size_human_readable, file_size_bytes));
+    print_output_line("  Type: CSV/Text file");
+    print_output_line("");
     
     Ok(())
 }
@@ -1006,26 +2990,101 @@ pub struct ApplicationDirectoryPaths {
     
     /// Absolute path to the analysis_cache subdirectory
     pub analysis_cache_directory: PathBuf,
+    
+    /// Absolute path to the temporary_work subdirectory (spill files)
+    pub temporary_work_directory: PathBuf,
+    
+    /// Which step of the resolution order chose the data directory
+    pub data_directory_source: DataDirectoryResolutionSource,
+}
+
+/// Environment variable naming the data directory (step 2 of the resolution order)
+const DATA_DIRECTORY_ENVIRONMENT_VARIABLE: &str = "ROWS_AND_COLUMNS_DATA_DIR";
+
+/// Data directory chosen at startup (set once by `select_application_data_directory`)
+static SELECTED_APPLICATION_DATA_DIRECTORY: OnceLock<ResolvedApplicationDataDirectory> = OnceLock::new();
+
+/// Resolves the data directory for this run and keeps it for the rest of the process
+/// 
+/// See `resolve_application_data_directory_path` for the resolution order:
+/// `--data-dir`, `ROWS_AND_COLUMNS_DATA_DIR`, the XDG data directory, then the
+/// executable's directory. When the XDG directory is chosen but an older
+/// `rows_columns_data` directory sits next to the executable, a warning says
+/// how to keep using it.
+/// 
+/// # Arguments
+/// * `data_directory_argument` - Value of the global `--data-dir` option, if given
+/// 
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+/// 
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the working or executable directory cannot be determined
+fn select_application_data_directory(data_directory_argument: Option<&str>) -> RowsAndColumnsResult<()> {
+    // Step 1: Resolve the directory and where it came from
+    let resolved_data_directory = resolve_application_data_directory_path(
+        data_directory_argument,
+        DATA_DIRECTORY_ENVIRONMENT_VARIABLE,
+        &application_configuration().root_directory_name,
+    ).map_err(|io_error| create_file_system_error("Failed to resolve the data directory", io_error))?;
+    
+    // Step 2: Point out data left next to the executable by earlier versions
+    if resolved_data_directory.resolution_source == DataDirectoryResolutionSource::XdgDataHome
+        && let Ok(executable_relative_data_directory) = make_input_path_name_abs_executabledirectoryrelative_nocheck(
+            &application_configuration().root_directory_name
+        )
+        && executable_relative_data_directory.is_dir()
+        && !resolved_data_directory.data_directory_path.exists()
+    {
+        print_warning_line(&format!(
+            "⚠️  Found data next to the executable in {}; pass --data-dir {} (or set {}) to keep using it",
+            executable_relative_data_directory.display(),
+            executable_relative_data_directory.display(),
+            DATA_DIRECTORY_ENVIRONMENT_VARIABLE
+        ));
+    }
+    
+    // Step 3: Keep it for every later initialize_application_directory_structure call
+    let _ = SELECTED_APPLICATION_DATA_DIRECTORY.set(resolved_data_directory);
+    Ok(())
+}
+
+/// Describes where the data directory came from, for status lines
+/// 
+/// # Arguments
+/// * `resolution_source` - The resolution step that produced the directory
+/// 
+/// # Returns
+/// * `String` - e.g. "from --data-dir" or "XDG data directory"
+fn describe_data_directory_source(resolution_source: DataDirectoryResolutionSource) -> String {
+    match resolution_source {
+        DataDirectoryResolutionSource::CommandLineFlag => String::from("from --data-dir"),
+        DataDirectoryResolutionSource::EnvironmentVariable => format!("from {}", DATA_DIRECTORY_ENVIRONMENT_VARIABLE),
+        other_source => other_source.source_description().to_string(),
+    }
 }
 
 /// Initializes the complete directory structure for the application
 /// 
-/// This function creates all necessary directories using binary-relative paths
-/// and returns the absolute paths for use throughout the application. It ensures
-/// the directory structure is ready for CSV data storage and analysis operations.
+/// This function creates all necessary directories under the data directory
+/// chosen by `select_application_data_directory` and returns the absolute paths
+/// for use throughout the application. It ensures the directory structure is
+/// ready for CSV data storage and analysis operations.
 /// 
 /// # Returns
 /// * `RowsAndColumnsResult<ApplicationDirectoryPaths>` - All directory paths or error
 /// 
 /// # Errors
 /// * `RowsAndColumnsError::FileSystemError` - If any directory creation fails
+/// * `RowsAndColumnsError::ConfigurationError` - If the data directory path is not valid UTF-8
 /// 
 /// # Directory Structure Created
-/// ```
-/// executable_directory/
-/// └── rows_columns_data/
-///     ├── csv_imports/
-///     └── analysis_cache/
+/// The default names, which `[directories]` in `rows_and_columns.toml` can change:
+/// ```text
+/// rows_columns_data/          (--data-dir, $ROWS_AND_COLUMNS_DATA_DIR, XDG or next to the executable)
+/// ├── csv_imports/
+/// ├── analysis_cache/
+/// └── temporary_work/
 /// ```
 fn initialize_application_directory_structure() -> RowsAndColumnsResult<ApplicationDirectoryPaths> {
     // Get the executable's parent directory for reference
@@ -1037,12 +3096,31 @@ fn initialize_application_directory_structure() -> RowsAndColumnsResult<Applicat
             )
         })?;
     
-    // Create the main rows_columns_data directory
+    // Directory names come from rows_and_columns.toml (or the defaults)
+    let configured_directory_names = application_configuration();
+    
+    // Use the data directory selected at startup (or resolve it now without a flag)
+    let resolved_data_directory = match SELECTED_APPLICATION_DATA_DIRECTORY.get() {
+        Some(selected_data_directory) => selected_data_directory.clone(),
+        None => resolve_application_data_directory_path(
+            None,
+            DATA_DIRECTORY_ENVIRONMENT_VARIABLE,
+            &configured_directory_names.root_directory_name,
+        ).map_err(|io_error| create_file_system_error("Failed to resolve the data directory", io_error))?,
+    };
+    let data_directory_path_text = resolved_data_directory.data_directory_path.to_str().ok_or_else(|| {
+        create_configuration_error(&format!(
+            "Data directory path is not valid UTF-8: {}",
+            resolved_data_directory.data_directory_path.display()
+        ))
+    })?;
+    
+    // Create the main rows_columns_data directory (an absolute path is used as is)
     let rows_columns_root_directory = make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path(
-        ROWS_COLUMNS_ROOT_DIRECTORY_NAME
+        data_directory_path_text
     ).map_err(|io_error| {
         create_file_system_error(
-            &format!("Failed to create main directory: {}", ROWS_COLUMNS_ROOT_DIRECTORY_NAME),
+            &format!("Failed to create main directory: {}", data_directory_path_text),
             io_error
         )
     })?;
@@ -1050,8 +3128,8 @@ fn initialize_application_directory_structure() -> RowsAndColumnsResult<Applicat
     // Create the csv_imports subdirectory
     let csv_imports_relative_path = format!(
         "{}/{}",
-        ROWS_COLUMNS_ROOT_DIRECTORY_NAME,
-        CSV_IMPORTS_SUBDIRECTORY_NAME
+        data_directory_path_text,
+        configured_directory_names.csv_imports_directory_name
     );
     
     let csv_imports_directory = make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path(
@@ -1066,8 +3144,8 @@ fn initialize_application_directory_structure() -> RowsAndColumnsResult<Applicat
     // Create the analysis_cache subdirectory
     let analysis_cache_relative_path = format!(
         "{}/{}",
-        ROWS_COLUMNS_ROOT_DIRECTORY_NAME,
-        ANALYSIS_CACHE_SUBDIRECTORY_NAME
+        data_directory_path_text,
+        configured_directory_names.analysis_cache_directory_name
     );
     
     let analysis_cache_directory = make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path(
@@ -1079,12 +3157,30 @@ fn initialize_application_directory_structure() -> RowsAndColumnsResult<Applicat
         )
     })?;
     
+    // Create the temporary_work subdirectory
+    let temporary_work_relative_path = format!(
+        "{}/{}",
+        data_directory_path_text,
+        configured_directory_names.temporary_work_directory_name
+    );
+    
+    let temporary_work_directory = make_verify_or_create_executabledirectoryrelative_canonicalized_dir_path(
+        &temporary_work_relative_path
+    ).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to create temporary work directory: {}", temporary_work_relative_path),
+            io_error
+        )
+    })?;
+    
     // Return the complete directory structure information
     Ok(ApplicationDirectoryPaths {
         executable_parent_directory,
         rows_columns_root_directory,
         csv_imports_directory,
         analysis_cache_directory,
+        temporary_work_directory,
+        data_directory_source: resolved_data_directory.resolution_source,
     })
 }
 
@@ -1157,6 +3253,13 @@ fn validate_directory_structure_initialization(
         ));
     }
     
+    // Validate temporary_work subdirectory
+    if !directory_paths.temporary_work_directory.is_dir() {
+        return Err(create_configuration_error(
+            "Temporary work directory does not exist or is not a directory"
+        ));
+    }
+    
     // All validations passed
     Ok(())
 }
@@ -1176,7 +3279,7 @@ fn display_directory_setup_success(directory_paths: &ApplicationDirectoryPaths)
     println!("    {}", directory_paths.executable_parent_directory.display());
     println!();
     
-    println!("  Data Storage Root:");
+    println!("  Data Storage Root ({}):", describe_data_directory_source(directory_paths.data_directory_source));
     println!("    {}", directory_paths.rows_columns_root_directory.display());
     println!();
     
@@ -1188,6 +3291,10 @@ fn display_directory_setup_success(directory_paths: &ApplicationDirectoryPaths)
     println!("    {}", directory_paths.analysis_cache_directory.display());
     println!();
     
+    println!("  Temporary Work Directory:");
+    println!("    {}", directory_paths.temporary_work_directory.display());
+    println!();
+    
     println!("═══════════════════════════════════════════════════════════════");
     println!();
 }
@@ -1195,23 +3302,27 @@ fn display_directory_setup_success(directory_paths: &ApplicationDirectoryPaths)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
+    use crate::application_configuration_module::ApplicationConfiguration;
     
-    /// Test that the directory structure constants are reasonable
+    /// Test that the default directory names are reasonable
     #[test]
     fn test_directory_constants() {
-        // Directory names should not be empty
-        assert!(!ROWS_COLUMNS_ROOT_DIRECTORY_NAME.is_empty());
-        assert!(!CSV_IMPORTS_SUBDIRECTORY_NAME.is_empty());
-        assert!(!ANALYSIS_CACHE_SUBDIRECTORY_NAME.is_empty());
+        let default_configuration = ApplicationConfiguration::default();
+        let default_directory_names = [
+            &default_configuration.root_directory_name,
+            &default_configuration.csv_imports_directory_name,
+            &default_configuration.analysis_cache_directory_name,
+            &default_configuration.temporary_work_directory_name,
+        ];
         
-        // Directory names should not contain path separators
-        assert!(!ROWS_COLUMNS_ROOT_DIRECTORY_NAME.contains('/'));
-        assert!(!ROWS_COLUMNS_ROOT_DIRECTORY_NAME.contains('\\'));
-        assert!(!CSV_IMPORTS_SUBDIRECTORY_NAME.contains('/'));
-        assert!(!CSV_IMPORTS_SUBDIRECTORY_NAME.contains('\\'));
-        assert!(!ANALYSIS_CACHE_SUBDIRECTORY_NAME.contains('/'));
-        assert!(!ANALYSIS_CACHE_SUBDIRECTORY_NAME.contains('\\'));
+        for default_directory_name in default_directory_names {
+            // Directory names should not be empty
+            assert!(!default_directory_name.is_empty());
+            
+            // Directory names should not contain path separators
+            assert!(!default_directory_name.contains('/'));
+            assert!(!default_directory_name.contains('\\'));
+        }
     }
     
     /// Test the ApplicationDirectoryPaths structure
@@ -1223,6 +3334,8 @@ mod tests {
             rows_columns_root_directory: PathBuf::from("/test/exe/rows_columns_data"),
             csv_imports_directory: PathBuf::from("/test/exe/rows_columns_data/csv_imports"),
             analysis_cache_directory: PathBuf::from("/test/exe/rows_columns_data/analysis_cache"),
+            temporary_work_directory: PathBuf::from("/test/exe/rows_columns_data/temporary_work"),
+            data_directory_source: DataDirectoryResolutionSource::ExecutableDirectory,
         };
         
         // Verify the structure can be created and accessed
@@ -1230,6 +3343,7 @@ mod tests {
         assert!(test_paths.rows_columns_root_directory.to_string_lossy().contains("rows_columns_data"));
         assert!(test_paths.csv_imports_directory.to_string_lossy().contains("csv_imports"));
         assert!(test_paths.analysis_cache_directory.to_string_lossy().contains("analysis_cache"));
+        assert!(test_paths.temporary_work_directory.to_string_lossy().contains("temporary_work"));
         
         // Test that the structure can be cloned
         let cloned_paths = test_paths.clone();
@@ -1240,16 +3354,17 @@ mod tests {
     #[test]
     fn test_directory_path_construction() {
         // Test path construction logic
+        let default_configuration = ApplicationConfiguration::default();
         let csv_imports_path = format!(
             "{}/{}",
-            ROWS_COLUMNS_ROOT_DIRECTORY_NAME,
-            CSV_IMPORTS_SUBDIRECTORY_NAME
+            default_configuration.root_directory_name,
+            default_configuration.csv_imports_directory_name
         );
         
         let analysis_cache_path = format!(
             "{}/{}",
-            ROWS_COLUMNS_ROOT_DIRECTORY_NAME,
-            ANALYSIS_CACHE_SUBDIRECTORY_NAME
+            default_configuration.root_directory_name,
+            default_configuration.analysis_cache_directory_name
         );
         
         // Verify paths are constructed correctly
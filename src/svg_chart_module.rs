@@ -0,0 +1,1007 @@
+// src/svg_chart_module.rs
+
+/// SVG chart rendering for rows_and_columns
+///
+/// Lays out the same `ChartDefinition` values that the TUI draws as text into
+/// pixel-space drawing primitives (rectangles, lines, text, circles) with
+/// axes, tick marks, tick labels and a title, and writes those primitives as
+/// standalone SVG markup. The markup can be embedded inline in an HTML report
+/// or saved as a `.svg` file; the bitmap module rasterizes the same layout.
+///
+/// # Core Responsibilities
+/// - Lay out charts as `ChartDrawing` primitives
+/// - Convert drawings to SVG with proper XML escaping
+/// - Choose "nice" axis tick values (1, 2, 5 × 10ⁿ steps)
+/// - Render histograms, box plots, bar charts, scatter plots and heatmaps at a
+///   caller-chosen size
+/// - Save rendered charts as `.svg` files
+///
+/// # Design Philosophy
+/// - One chart model, several renderers: layout decisions come from the
+///   chart data, not from where the chart will be shown
+/// - Plain SVG 1.1 with inline attributes only (no CSS, scripts or fonts), so
+///   files open the same in browsers, editors and publishing tools
+use std::path::Path;
+
+use super::chart_rendering_module::{
+    BarChartData,
+    BoxPlotChartData,
+    ChartDefinition,
+    HeatmapChartData,
+    HistogramChartData,
+    ScatterPlotChartData,
+    format_compact_axis_value,
+};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+};
+
+/// Size of saved chart files when the caller has no preference, in pixels
+pub const DEFAULT_SVG_CHART_WIDTH: usize = 800;
+pub const DEFAULT_SVG_CHART_HEIGHT: usize = 500;
+
+/// Fill colour of bars and boxes
+const CHART_DATA_FILL_COLOUR: ChartColour = ChartColour { red: 0x4e, green: 0x79, blue: 0xa7 };
+
+/// Colour of axes, whiskers and text
+const CHART_FOREGROUND_COLOUR: ChartColour = ChartColour { red: 0x33, green: 0x33, blue: 0x33 };
+
+/// Colour of horizontal/vertical grid lines
+const CHART_GRID_COLOUR: ChartColour = ChartColour { red: 0xdd, green: 0xdd, blue: 0xdd };
+
+/// Colour behind everything else
+pub const CHART_BACKGROUND_COLOUR: ChartColour = ChartColour { red: 0xff, green: 0xff, blue: 0xff };
+
+/// Font size of tick labels and titles, in pixels
+const CHART_LABEL_FONT_SIZE: f64 = 11.0;
+const CHART_TITLE_FONT_SIZE: f64 = 14.0;
+
+/// Space reserved around the plot area, in pixels
+const CHART_TOP_MARGIN: f64 = 30.0;
+const CHART_RIGHT_MARGIN: f64 = 20.0;
+const CHART_BOTTOM_MARGIN: f64 = 36.0;
+const CHART_LEFT_MARGIN: f64 = 56.0;
+
+/// Approximate tick count requested from the tick chooser
+const CHART_TARGET_TICK_COUNT: usize = 6;
+
+/// Largest tick index whose tick value is still computed exactly (2^53)
+const MAXIMUM_EXACT_TICK_INDEX: f64 = 9_007_199_254_740_992.0;
+
+/// Longest category label drawn next to a horizontal bar, in characters
+const CHART_MAXIMUM_BAR_LABEL_LENGTH: usize = 18;
+
+/// Radius and opacity of scatter plot points (overlaps stay visible)
+const CHART_SCATTER_POINT_RADIUS: f64 = 2.5;
+const CHART_SCATTER_POINT_OPACITY: f64 = 0.6;
+
+/// Heatmap cell colours for coefficients of +1 and -1 (0 is white)
+const CHART_HEATMAP_POSITIVE_RGB: (f64, f64, f64) = (33.0, 102.0, 172.0);
+const CHART_HEATMAP_NEGATIVE_RGB: (f64, f64, f64) = (178.0, 24.0, 43.0);
+
+/// Smallest chart the renderer lays out (smaller requests are enlarged)
+const CHART_MINIMUM_CHART_WIDTH: usize = 160;
+const CHART_MINIMUM_CHART_HEIGHT: usize = 100;
+
+/// Escapes text for use in XML/HTML content and attribute values
+///
+/// # Arguments
+/// * `raw_text` - Text that may contain `<`, `>`, `&` or quotes
+///
+/// # Returns
+/// * `String` - Text safe to place between tags or inside double quotes
+pub fn escape_xml_text(raw_text: &str) -> String {
+    let mut escaped_text = String::with_capacity(raw_text.len());
+    for text_character in raw_text.chars() {
+        match text_character {
+            '&' => escaped_text.push_str("&amp;"),
+            '<' => escaped_text.push_str("&lt;"),
+            '>' => escaped_text.push_str("&gt;"),
+            '"' => escaped_text.push_str("&quot;"),
+            '\'' => escaped_text.push_str("&#39;"),
+            other_character => escaped_text.push(other_character),
+        }
+    }
+    escaped_text
+}
+
+/// An RGB colour used in chart drawings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartColour {
+    /// Red channel (0-255)
+    pub red: u8,
+
+    /// Green channel (0-255)
+    pub green: u8,
+
+    /// Blue channel (0-255)
+    pub blue: u8,
+}
+
+impl ChartColour {
+    /// Returns the colour in `#rrggbb` notation
+    ///
+    /// # Returns
+    /// * `String` - e.g. `#4e79a7`
+    pub fn to_hex_text(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// Which point of a text run its anchor position refers to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartTextAnchor {
+    /// Anchor at the start (left edge) of the text
+    Start,
+
+    /// Anchor at the centre of the text
+    Middle,
+
+    /// Anchor at the end (right edge) of the text
+    End,
+}
+
+/// One primitive of a laid-out chart, in pixel coordinates
+#[derive(Debug, Clone)]
+pub enum ChartDrawingElement {
+    /// Filled rectangle with a thin outline
+    Rectangle {
+        left_x: f64,
+        top_y: f64,
+        rectangle_width: f64,
+        rectangle_height: f64,
+        fill_colour: ChartColour,
+        outline_colour: ChartColour,
+    },
+
+    /// Straight line
+    Line {
+        start_x: f64,
+        start_y: f64,
+        end_x: f64,
+        end_y: f64,
+        stroke_colour: ChartColour,
+        stroke_width: f64,
+    },
+
+    /// Single line of text; vertical text reads bottom to top
+    Text {
+        anchor_x: f64,
+        baseline_y: f64,
+        text_content: String,
+        text_anchor: ChartTextAnchor,
+        font_size: f64,
+        text_colour: ChartColour,
+        is_vertical: bool,
+    },
+
+    /// Filled, possibly translucent circle
+    Circle {
+        centre_x: f64,
+        centre_y: f64,
+        circle_radius: f64,
+        fill_colour: ChartColour,
+        fill_opacity: f64,
+    },
+}
+
+/// A chart laid out as drawing primitives, ready for any output format
+#[derive(Debug, Clone)]
+pub struct ChartDrawing {
+    /// Drawing width in pixels
+    pub drawing_width: f64,
+
+    /// Drawing height in pixels
+    pub drawing_height: f64,
+
+    /// Primitives in painting order (later elements cover earlier ones)
+    pub drawing_elements: Vec<ChartDrawingElement>,
+}
+
+/// Accumulates drawing primitives for one chart
+struct ChartDrawingBuilder {
+    /// Drawing width in pixels
+    drawing_width: f64,
+
+    /// Drawing height in pixels
+    drawing_height: f64,
+
+    /// Primitives added so far
+    drawing_elements: Vec<ChartDrawingElement>,
+}
+
+impl ChartDrawingBuilder {
+    /// Starts an empty drawing of the given size
+    fn new(drawing_width: f64, drawing_height: f64) -> ChartDrawingBuilder {
+        ChartDrawingBuilder { drawing_width, drawing_height, drawing_elements: Vec::new() }
+    }
+
+    /// Adds a filled rectangle
+    fn add_rectangle(&mut self, left_x: f64, top_y: f64, rectangle_width: f64, rectangle_height: f64, fill_colour: ChartColour) {
+        self.drawing_elements.push(ChartDrawingElement::Rectangle {
+            left_x,
+            top_y,
+            rectangle_width: rectangle_width.max(0.0),
+            rectangle_height: rectangle_height.max(0.0),
+            fill_colour,
+            outline_colour: CHART_FOREGROUND_COLOUR,
+        });
+    }
+
+    /// Adds a straight line
+    fn add_line(&mut self, start_x: f64, start_y: f64, end_x: f64, end_y: f64, stroke_colour: ChartColour, stroke_width: f64) {
+        self.drawing_elements.push(ChartDrawingElement::Line { start_x, start_y, end_x, end_y, stroke_colour, stroke_width });
+    }
+
+    /// Adds horizontal text
+    fn add_text(&mut self, anchor_x: f64, baseline_y: f64, text_content: &str, text_anchor: ChartTextAnchor, font_size: f64) {
+        self.drawing_elements.push(ChartDrawingElement::Text {
+            anchor_x,
+            baseline_y,
+            text_content: text_content.to_string(),
+            text_anchor,
+            font_size,
+            text_colour: CHART_FOREGROUND_COLOUR,
+            is_vertical: false,
+        });
+    }
+
+    /// Adds a filled circle
+    fn add_circle(&mut self, centre_x: f64, centre_y: f64, circle_radius: f64, fill_colour: ChartColour, fill_opacity: f64) {
+        self.drawing_elements.push(ChartDrawingElement::Circle { centre_x, centre_y, circle_radius, fill_colour, fill_opacity });
+    }
+
+    /// Adds text rotated a quarter turn anticlockwise, centred on the anchor (for vertical axis labels)
+    fn add_vertical_text(&mut self, anchor_x: f64, anchor_y: f64, text_content: &str, font_size: f64) {
+        self.drawing_elements.push(ChartDrawingElement::Text {
+            anchor_x,
+            baseline_y: anchor_y,
+            text_content: text_content.to_string(),
+            text_anchor: ChartTextAnchor::Middle,
+            font_size,
+            text_colour: CHART_FOREGROUND_COLOUR,
+            is_vertical: true,
+        });
+    }
+
+    /// Adds the chart title centred at the top
+    fn add_title(&mut self, title_text: &str) {
+        let centre_x = self.drawing_width / 2.0;
+        self.add_text(centre_x, CHART_TOP_MARGIN - 10.0, title_text, ChartTextAnchor::Middle, CHART_TITLE_FONT_SIZE);
+    }
+
+    /// Returns the finished drawing
+    fn finish_drawing(self) -> ChartDrawing {
+        ChartDrawing {
+            drawing_width: self.drawing_width,
+            drawing_height: self.drawing_height,
+            drawing_elements: self.drawing_elements,
+        }
+    }
+}
+
+/// Converts a laid-out chart to a standalone SVG document
+///
+/// # Arguments
+/// * `chart_drawing` - The laid-out chart
+///
+/// # Returns
+/// * `String` - SVG markup (starting with `<svg`)
+pub fn convert_chart_drawing_to_svg(chart_drawing: &ChartDrawing) -> String {
+    let mut element_markup = String::new();
+
+    for drawing_element in &chart_drawing.drawing_elements {
+        match drawing_element {
+            ChartDrawingElement::Rectangle { left_x, top_y, rectangle_width, rectangle_height, fill_colour, outline_colour } => {
+                element_markup.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"0.5\"/>\n",
+                    left_x, top_y, rectangle_width, rectangle_height, fill_colour.to_hex_text(), outline_colour.to_hex_text()
+                ));
+            }
+            ChartDrawingElement::Line { start_x, start_y, end_x, end_y, stroke_colour, stroke_width } => {
+                element_markup.push_str(&format!(
+                    "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                    start_x, start_y, end_x, end_y, stroke_colour.to_hex_text(), stroke_width
+                ));
+            }
+            ChartDrawingElement::Text { anchor_x, baseline_y, text_content, text_anchor, font_size, text_colour, is_vertical } => {
+                let anchor_name = match text_anchor {
+                    ChartTextAnchor::Start => "start",
+                    ChartTextAnchor::Middle => "middle",
+                    ChartTextAnchor::End => "end",
+                };
+                let rotation_attribute = if *is_vertical {
+                    format!(" transform=\"rotate(-90 {:.1} {:.1})\"", anchor_x, baseline_y)
+                } else {
+                    String::new()
+                };
+                element_markup.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\"{} text-anchor=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"{}\">{}</text>\n",
+                    anchor_x, baseline_y, rotation_attribute, anchor_name, font_size, text_colour.to_hex_text(), escape_xml_text(text_content)
+                ));
+            }
+            ChartDrawingElement::Circle { centre_x, centre_y, circle_radius, fill_colour, fill_opacity } => {
+                element_markup.push_str(&format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+                    centre_x, centre_y, circle_radius, fill_colour.to_hex_text(), fill_opacity
+                ));
+            }
+        }
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n{}</svg>\n",
+        CHART_BACKGROUND_COLOUR.to_hex_text(),
+        element_markup,
+        w = chart_drawing.drawing_width,
+        h = chart_drawing.drawing_height,
+    )
+}
+
+/// Maps data values to pixel positions along one axis
+#[derive(Debug, Clone, Copy)]
+struct ChartAxisScale {
+    /// Data value at the start of the axis
+    domain_start: f64,
+
+    /// Data value at the end of the axis
+    domain_end: f64,
+
+    /// Pixel position of the start of the axis
+    pixel_start: f64,
+
+    /// Pixel position of the end of the axis
+    pixel_end: f64,
+}
+
+impl ChartAxisScale {
+    /// Converts a data value to a pixel position
+    fn to_pixel(self, data_value: f64) -> f64 {
+        // Halving first keeps differences between huge values finite
+        let half_domain_span = self.domain_end / 2.0 - self.domain_start / 2.0;
+        if half_domain_span == 0.0 {
+            return (self.pixel_start + self.pixel_end) / 2.0;
+        }
+        let relative_position = (data_value / 2.0 - self.domain_start / 2.0) / half_domain_span;
+        self.pixel_start + relative_position * (self.pixel_end - self.pixel_start)
+    }
+}
+
+/// Chooses evenly spaced, round tick values covering a range
+///
+/// Steps are 1, 2 or 5 times a power of ten. The returned ticks start at or
+/// below `range_minimum` and end at or above `range_maximum`, so they can also
+/// serve as the axis domain.
+///
+/// # Arguments
+/// * `range_minimum` - Smallest value to cover
+/// * `range_maximum` - Largest value to cover
+/// * `target_tick_count` - Roughly how many ticks are wanted
+///
+/// # Returns
+/// * `Vec<f64>` - Ascending tick values (at least two)
+pub fn calculate_nice_axis_ticks(range_minimum: f64, range_maximum: f64, target_tick_count: usize) -> Vec<f64> {
+    let (range_lower_value, range_upper_value) = if range_minimum <= range_maximum {
+        (range_minimum, range_maximum)
+    } else {
+        (range_maximum, range_minimum)
+    };
+
+    if !range_lower_value.is_finite() || !range_upper_value.is_finite() {
+        return vec![0.0, 1.0];
+    }
+    let (mut lower_value, mut upper_value) = (range_lower_value, range_upper_value);
+    if lower_value == upper_value {
+        let padding = if lower_value == 0.0 { 1.0 } else { lower_value.abs() * 0.1 };
+        lower_value -= padding;
+        upper_value += padding;
+    }
+
+    // Step 1: Pick the step size closest to the requested tick count
+    let rough_step = (upper_value - lower_value) / target_tick_count.max(1) as f64;
+    let step_magnitude = 10f64.powf(rough_step.log10().floor());
+    let step_multiplier = match rough_step / step_magnitude {
+        relative_step if relative_step <= 1.5 => 1.0,
+        relative_step if relative_step <= 3.5 => 2.0,
+        relative_step if relative_step <= 7.5 => 5.0,
+        _ => 10.0,
+    };
+    let tick_step = step_multiplier * step_magnitude;
+
+    // Step 2: Extend outward to whole steps
+    let first_tick_position = (lower_value / tick_step).floor();
+    let last_tick_position = (upper_value / tick_step).ceil();
+
+    // Rounding to the step's decimals removes noise such as 0.6000000000000001
+    let decimal_scale = 10f64.powi((-step_magnitude.log10().round()).max(0.0) as i32);
+
+    // Spans that overflow f64 or are too small to divide into steps get the
+    // range itself as the only two ticks
+    let step_is_usable = tick_step.is_finite()
+        && tick_step > 0.0
+        && first_tick_position.abs() <= MAXIMUM_EXACT_TICK_INDEX
+        && last_tick_position.abs() <= MAXIMUM_EXACT_TICK_INDEX
+        && decimal_scale.is_finite();
+    if !step_is_usable {
+        return vec![range_lower_value, range_upper_value];
+    }
+    let first_tick_index = first_tick_position as i64;
+    let last_tick_index = last_tick_position as i64;
+
+    let axis_ticks: Vec<f64> = (first_tick_index..=last_tick_index.max(first_tick_index + 1))
+        .map(|tick_index| (tick_index as f64 * tick_step * decimal_scale).round() / decimal_scale)
+        .collect();
+
+    // Ticks beyond f64::MAX happen when the range reaches its edge
+    if axis_ticks.iter().all(|tick_value| tick_value.is_finite()) {
+        axis_ticks
+    } else {
+        vec![range_lower_value, range_upper_value]
+    }
+}
+
+/// Lays out a chart definition as drawing primitives
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `chart_width` - Drawing width in pixels
+/// * `chart_height` - Drawing height in pixels
+///
+/// # Returns
+/// * `ChartDrawing` - Primitives for the SVG writer or the bitmap rasterizer
+pub fn lay_out_chart_drawing(chart_definition: &ChartDefinition, chart_width: usize, chart_height: usize) -> ChartDrawing {
+    let mut drawing_builder = ChartDrawingBuilder::new(
+        chart_width.max(CHART_MINIMUM_CHART_WIDTH) as f64,
+        chart_height.max(CHART_MINIMUM_CHART_HEIGHT) as f64,
+    );
+
+    match chart_definition {
+        ChartDefinition::Histogram(histogram_data) => lay_out_histogram(&mut drawing_builder, histogram_data),
+        ChartDefinition::BoxPlot(box_plot_data) => lay_out_box_plot(&mut drawing_builder, box_plot_data),
+        ChartDefinition::BarChart(bar_chart_data) => lay_out_bar_chart(&mut drawing_builder, bar_chart_data),
+        ChartDefinition::ScatterPlot(scatter_plot_data) => lay_out_scatter_plot(&mut drawing_builder, scatter_plot_data),
+        ChartDefinition::Heatmap(heatmap_data) => lay_out_heatmap(&mut drawing_builder, heatmap_data),
+    }
+
+    drawing_builder.finish_drawing()
+}
+
+/// Renders a chart definition as a standalone SVG document
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `chart_width` - Document width in pixels
+/// * `chart_height` - Document height in pixels
+///
+/// # Returns
+/// * `String` - SVG markup (starting with `<svg`)
+pub fn render_chart_as_svg(chart_definition: &ChartDefinition, chart_width: usize, chart_height: usize) -> String {
+    convert_chart_drawing_to_svg(&lay_out_chart_drawing(chart_definition, chart_width, chart_height))
+}
+
+/// Renders a chart and saves it as an `.svg` file
+///
+/// # Arguments
+/// * `chart_definition` - The chart to draw
+/// * `chart_width` - Document width in pixels
+/// * `chart_height` - Document height in pixels
+/// * `svg_file_path` - Where to write (parent directories are created)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the directory or file cannot be written
+pub fn write_chart_svg_file(
+    chart_definition: &ChartDefinition,
+    chart_width: usize,
+    chart_height: usize,
+    svg_file_path: &Path,
+) -> RowsAndColumnsResult<()> {
+    if let Some(parent_directory) = svg_file_path.parent()
+        && !parent_directory.as_os_str().is_empty() {
+        std::fs::create_dir_all(parent_directory).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to create chart directory: {}", parent_directory.display()),
+                io_error
+            )
+        })?;
+    }
+
+    let svg_document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
+        render_chart_as_svg(chart_definition, chart_width, chart_height)
+    );
+    std::fs::write(svg_file_path, svg_document).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to write SVG chart: {}", svg_file_path.display()),
+            io_error
+        )
+    })
+}
+
+/// Returns the title of any chart definition
+///
+/// # Arguments
+/// * `chart_definition` - The chart in question
+///
+/// # Returns
+/// * `&str` - The chart title, e.g. `age (histogram)`
+pub fn get_chart_title(chart_definition: &ChartDefinition) -> &str {
+    match chart_definition {
+        ChartDefinition::Histogram(histogram_data) => &histogram_data.chart_title,
+        ChartDefinition::BoxPlot(box_plot_data) => &box_plot_data.chart_title,
+        ChartDefinition::BarChart(bar_chart_data) => &bar_chart_data.chart_title,
+        ChartDefinition::ScatterPlot(scatter_plot_data) => &scatter_plot_data.chart_title,
+        ChartDefinition::Heatmap(heatmap_data) => &heatmap_data.chart_title,
+    }
+}
+
+/// Returns the plot area as (left, top, right, bottom) pixel edges
+fn calculate_plot_area(drawing_builder: &ChartDrawingBuilder, left_margin: f64) -> (f64, f64, f64, f64) {
+    (
+        left_margin,
+        CHART_TOP_MARGIN,
+        drawing_builder.drawing_width - CHART_RIGHT_MARGIN,
+        drawing_builder.drawing_height - CHART_BOTTOM_MARGIN,
+    )
+}
+
+/// Draws a horizontal value axis with ticks and tick labels
+///
+/// Vertical grid lines reach up to `grid_top` when it is given.
+fn draw_horizontal_value_axis(drawing_builder: &mut ChartDrawingBuilder, x_scale: &ChartAxisScale, axis_ticks: &[f64], grid_top: Option<f64>, plot_bottom: f64) {
+    drawing_builder.add_line(x_scale.pixel_start, plot_bottom, x_scale.pixel_end, plot_bottom, CHART_FOREGROUND_COLOUR, 1.0);
+    for &tick_value in axis_ticks {
+        let tick_x = x_scale.to_pixel(tick_value);
+        if let Some(grid_top_y) = grid_top {
+            drawing_builder.add_line(tick_x, grid_top_y, tick_x, plot_bottom, CHART_GRID_COLOUR, 0.5);
+        }
+        drawing_builder.add_line(tick_x, plot_bottom, tick_x, plot_bottom + 4.0, CHART_FOREGROUND_COLOUR, 1.0);
+        drawing_builder.add_text(tick_x, plot_bottom + 16.0, &format_compact_axis_value(tick_value), ChartTextAnchor::Middle, CHART_LABEL_FONT_SIZE);
+    }
+}
+
+/// Draws a vertical value axis with ticks, tick labels and horizontal grid lines
+fn draw_vertical_value_axis(drawing_builder: &mut ChartDrawingBuilder, y_scale: &ChartAxisScale, axis_ticks: &[f64], plot_left: f64, plot_right: f64) {
+    drawing_builder.add_line(plot_left, y_scale.pixel_start, plot_left, y_scale.pixel_end, CHART_FOREGROUND_COLOUR, 1.0);
+    for &tick_value in axis_ticks {
+        let tick_y = y_scale.to_pixel(tick_value);
+        drawing_builder.add_line(plot_left, tick_y, plot_right, tick_y, CHART_GRID_COLOUR, 0.5);
+        drawing_builder.add_line(plot_left - 4.0, tick_y, plot_left, tick_y, CHART_FOREGROUND_COLOUR, 1.0);
+        drawing_builder.add_text(plot_left - 7.0, tick_y + 4.0, &format_compact_axis_value(tick_value), ChartTextAnchor::End, CHART_LABEL_FONT_SIZE);
+    }
+}
+
+/// Draws a histogram: vertical bars over a value axis, counts on the y axis
+fn lay_out_histogram(drawing_builder: &mut ChartDrawingBuilder, histogram_data: &HistogramChartData) {
+    drawing_builder.add_title(&histogram_data.chart_title);
+    let (plot_left, plot_top, plot_right, plot_bottom) = calculate_plot_area(drawing_builder, CHART_LEFT_MARGIN);
+
+    // Count axis ticks are whole numbers, so keep at least a step of one
+    let largest_bin_count = histogram_data.bin_counts.iter().copied().max().unwrap_or(0);
+    let count_ticks: Vec<f64> = calculate_nice_axis_ticks(0.0, largest_bin_count.max(1) as f64, CHART_TARGET_TICK_COUNT)
+        .into_iter()
+        .filter(|tick_value| tick_value.fract() == 0.0 && *tick_value >= 0.0)
+        .collect();
+    let count_axis_top = count_ticks.last().copied().unwrap_or(1.0).max(1.0);
+    let y_scale = ChartAxisScale { domain_start: 0.0, domain_end: count_axis_top, pixel_start: plot_bottom, pixel_end: plot_top };
+    draw_vertical_value_axis(drawing_builder, &y_scale, &count_ticks, plot_left, plot_right);
+    drawing_builder.add_vertical_text(CHART_LABEL_FONT_SIZE + 2.0, (plot_top + plot_bottom) / 2.0, "count", CHART_LABEL_FONT_SIZE);
+
+    // Bars sit exactly on their bin edges; value ticks are only drawn inside the data range
+    let x_scale = ChartAxisScale {
+        domain_start: histogram_data.minimum_value,
+        domain_end: histogram_data.maximum_value,
+        pixel_start: plot_left,
+        pixel_end: plot_right,
+    };
+    let bin_count = histogram_data.bin_counts.len().max(1);
+    let bin_pixel_width = (plot_right - plot_left) / bin_count as f64;
+    for (bin_index, &bin_count_value) in histogram_data.bin_counts.iter().enumerate() {
+        let bar_top = y_scale.to_pixel(bin_count_value as f64);
+        drawing_builder.add_rectangle(
+            plot_left + bin_index as f64 * bin_pixel_width,
+            bar_top,
+            bin_pixel_width,
+            plot_bottom - bar_top,
+            CHART_DATA_FILL_COLOUR,
+        );
+    }
+
+    let value_ticks: Vec<f64> = if histogram_data.maximum_value > histogram_data.minimum_value {
+        calculate_nice_axis_ticks(histogram_data.minimum_value, histogram_data.maximum_value, CHART_TARGET_TICK_COUNT)
+            .into_iter()
+            .filter(|&tick_value| tick_value >= histogram_data.minimum_value && tick_value <= histogram_data.maximum_value)
+            .collect()
+    } else {
+        vec![histogram_data.minimum_value]
+    };
+    draw_horizontal_value_axis(drawing_builder, &x_scale, &value_ticks, None, plot_bottom);
+}
+
+/// Draws a horizontal box plot: whiskers from min to max, box from q1 to q3
+fn lay_out_box_plot(drawing_builder: &mut ChartDrawingBuilder, box_plot_data: &BoxPlotChartData) {
+    drawing_builder.add_title(&box_plot_data.chart_title);
+    let (plot_left, plot_top, plot_right, plot_bottom) = calculate_plot_area(drawing_builder, CHART_LEFT_MARGIN / 2.0);
+
+    let value_ticks = calculate_nice_axis_ticks(box_plot_data.minimum_value, box_plot_data.maximum_value, CHART_TARGET_TICK_COUNT);
+    let x_scale = ChartAxisScale {
+        domain_start: value_ticks[0],
+        domain_end: value_ticks[value_ticks.len() - 1],
+        pixel_start: plot_left,
+        pixel_end: plot_right,
+    };
+    draw_horizontal_value_axis(drawing_builder, &x_scale, &value_ticks, Some(plot_top), plot_bottom);
+
+    let centre_y = (plot_top + plot_bottom) / 2.0;
+    let box_half_height = ((plot_bottom - plot_top) * 0.3).min(30.0);
+    let minimum_x = x_scale.to_pixel(box_plot_data.minimum_value);
+    let first_quartile_x = x_scale.to_pixel(box_plot_data.first_quartile_value);
+    let median_x = x_scale.to_pixel(box_plot_data.median_value);
+    let third_quartile_x = x_scale.to_pixel(box_plot_data.third_quartile_value);
+    let maximum_x = x_scale.to_pixel(box_plot_data.maximum_value);
+
+    // Whiskers with end caps
+    drawing_builder.add_line(minimum_x, centre_y, first_quartile_x, centre_y, CHART_FOREGROUND_COLOUR, 1.0);
+    drawing_builder.add_line(third_quartile_x, centre_y, maximum_x, centre_y, CHART_FOREGROUND_COLOUR, 1.0);
+    for whisker_end_x in [minimum_x, maximum_x] {
+        drawing_builder.add_line(whisker_end_x, centre_y - box_half_height / 2.0, whisker_end_x, centre_y + box_half_height / 2.0, CHART_FOREGROUND_COLOUR, 1.0);
+    }
+
+    // Box and median line
+    drawing_builder.add_rectangle(
+        first_quartile_x,
+        centre_y - box_half_height,
+        third_quartile_x - first_quartile_x,
+        box_half_height * 2.0,
+        CHART_DATA_FILL_COLOUR,
+    );
+    drawing_builder.add_line(median_x, centre_y - box_half_height, median_x, centre_y + box_half_height, CHART_BACKGROUND_COLOUR, 2.0);
+}
+
+/// Draws a horizontal bar chart: one labelled bar per category
+fn lay_out_bar_chart(drawing_builder: &mut ChartDrawingBuilder, bar_chart_data: &BarChartData) {
+    drawing_builder.add_title(&bar_chart_data.chart_title);
+
+    // Labels need more room on the left than numeric tick labels do
+    let longest_label_length = bar_chart_data.chart_bars
+        .iter()
+        .map(|chart_bar| chart_bar.bar_label.chars().count().min(CHART_MAXIMUM_BAR_LABEL_LENGTH))
+        .max()
+        .unwrap_or(0);
+    let label_margin = (longest_label_length as f64 * CHART_LABEL_FONT_SIZE * 0.6 + 12.0).max(CHART_LEFT_MARGIN);
+    let (plot_left, plot_top, plot_right, plot_bottom) = calculate_plot_area(drawing_builder, label_margin);
+
+    if bar_chart_data.chart_bars.is_empty() {
+        drawing_builder.add_text((plot_left + plot_right) / 2.0, (plot_top + plot_bottom) / 2.0, "(no values)", ChartTextAnchor::Middle, CHART_LABEL_FONT_SIZE);
+        return;
+    }
+
+    let largest_bar_count = bar_chart_data.chart_bars.iter().map(|chart_bar| chart_bar.bar_count).max().unwrap_or(0);
+    let count_ticks: Vec<f64> = calculate_nice_axis_ticks(0.0, largest_bar_count.max(1) as f64, CHART_TARGET_TICK_COUNT)
+        .into_iter()
+        .filter(|tick_value| tick_value.fract() == 0.0 && *tick_value >= 0.0)
+        .collect();
+    let x_scale = ChartAxisScale {
+        domain_start: 0.0,
+        domain_end: count_ticks.last().copied().unwrap_or(1.0).max(1.0),
+        pixel_start: plot_left,
+        pixel_end: plot_right,
+    };
+    draw_horizontal_value_axis(drawing_builder, &x_scale, &count_ticks, Some(plot_top), plot_bottom);
+
+    let bar_slot_height = (plot_bottom - plot_top) / bar_chart_data.chart_bars.len() as f64;
+    for (bar_position, chart_bar) in bar_chart_data.chart_bars.iter().enumerate() {
+        let slot_top = plot_top + bar_position as f64 * bar_slot_height;
+        let bar_right = x_scale.to_pixel(chart_bar.bar_count as f64);
+        drawing_builder.add_rectangle(plot_left, slot_top + bar_slot_height * 0.15, bar_right - plot_left, bar_slot_height * 0.7, CHART_DATA_FILL_COLOUR);
+
+        let mut bar_label: String = chart_bar.bar_label.chars().take(CHART_MAXIMUM_BAR_LABEL_LENGTH).collect();
+        if chart_bar.bar_label.chars().count() > CHART_MAXIMUM_BAR_LABEL_LENGTH {
+            bar_label.push('…');
+        }
+        let label_baseline = slot_top + bar_slot_height / 2.0 + CHART_LABEL_FONT_SIZE / 3.0;
+        drawing_builder.add_text(plot_left - 6.0, label_baseline, &bar_label, ChartTextAnchor::End, CHART_LABEL_FONT_SIZE);
+        drawing_builder.add_text(
+            bar_right + 4.0,
+            label_baseline,
+            &format!("{} ({:.1}%)", chart_bar.bar_count, chart_bar.bar_percentage),
+            ChartTextAnchor::Start,
+            CHART_LABEL_FONT_SIZE,
+        );
+    }
+    drawing_builder.add_line(plot_left, plot_top, plot_left, plot_bottom, CHART_FOREGROUND_COLOUR, 1.0);
+
+    if bar_chart_data.omitted_category_count > 0 {
+        let note_y = drawing_builder.drawing_height - 4.0;
+        drawing_builder.add_text(
+            plot_right,
+            note_y,
+            &format!("+{} more categories not shown", bar_chart_data.omitted_category_count),
+            ChartTextAnchor::End,
+            CHART_LABEL_FONT_SIZE,
+        );
+    }
+}
+
+/// Draws a scatter plot: one translucent point per (x, y) pair, with axis labels
+fn lay_out_scatter_plot(drawing_builder: &mut ChartDrawingBuilder, scatter_plot_data: &ScatterPlotChartData) {
+    drawing_builder.add_title(&scatter_plot_data.chart_title);
+
+    // Extra room on the left and bottom for the axis labels
+    let (plot_left, plot_top, plot_right, plot_bottom) = calculate_plot_area(drawing_builder, CHART_LEFT_MARGIN + 14.0);
+    let plot_bottom = plot_bottom - 14.0;
+
+    if scatter_plot_data.plot_points.is_empty() {
+        drawing_builder.add_text((plot_left + plot_right) / 2.0, (plot_top + plot_bottom) / 2.0, "(no numeric pairs)", ChartTextAnchor::Middle, CHART_LABEL_FONT_SIZE);
+        return;
+    }
+
+    let (mut minimum_x, mut maximum_x, mut minimum_y, mut maximum_y) = (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY);
+    for &(x_value, y_value) in &scatter_plot_data.plot_points {
+        minimum_x = minimum_x.min(x_value);
+        maximum_x = maximum_x.max(x_value);
+        minimum_y = minimum_y.min(y_value);
+        maximum_y = maximum_y.max(y_value);
+    }
+
+    // Axes span whole tick steps so every point lies inside the plot area
+    let x_ticks = calculate_nice_axis_ticks(minimum_x, maximum_x, CHART_TARGET_TICK_COUNT);
+    let y_ticks = calculate_nice_axis_ticks(minimum_y, maximum_y, CHART_TARGET_TICK_COUNT);
+    let x_scale = ChartAxisScale {
+        domain_start: x_ticks[0],
+        domain_end: x_ticks[x_ticks.len() - 1],
+        pixel_start: plot_left,
+        pixel_end: plot_right,
+    };
+    let y_scale = ChartAxisScale {
+        domain_start: y_ticks[0],
+        domain_end: y_ticks[y_ticks.len() - 1],
+        pixel_start: plot_bottom,
+        pixel_end: plot_top,
+    };
+    draw_vertical_value_axis(drawing_builder, &y_scale, &y_ticks, plot_left, plot_right);
+    draw_horizontal_value_axis(drawing_builder, &x_scale, &x_ticks, Some(plot_top), plot_bottom);
+
+    for &(x_value, y_value) in &scatter_plot_data.plot_points {
+        drawing_builder.add_circle(
+            x_scale.to_pixel(x_value),
+            y_scale.to_pixel(y_value),
+            CHART_SCATTER_POINT_RADIUS,
+            CHART_DATA_FILL_COLOUR,
+            CHART_SCATTER_POINT_OPACITY,
+        );
+    }
+
+    // Axis labels
+    let label_baseline = drawing_builder.drawing_height - 6.0;
+    drawing_builder.add_text((plot_left + plot_right) / 2.0, label_baseline, &scatter_plot_data.x_axis_label, ChartTextAnchor::Middle, CHART_LABEL_FONT_SIZE);
+    drawing_builder.add_vertical_text(CHART_LABEL_FONT_SIZE + 2.0, (plot_top + plot_bottom) / 2.0, &scatter_plot_data.y_axis_label, CHART_LABEL_FONT_SIZE);
+
+    if scatter_plot_data.points_were_capped {
+        drawing_builder.add_text(
+            plot_right,
+            label_baseline,
+            &format!("first {} points shown", scatter_plot_data.plot_points.len()),
+            ChartTextAnchor::End,
+            CHART_LABEL_FONT_SIZE,
+        );
+    }
+}
+
+/// Returns the fill colour of a heatmap cell (white at 0, blue at +1, red at -1)
+fn calculate_heatmap_cell_colour(coefficient_value: f64) -> ChartColour {
+    let (target_red, target_green, target_blue) = if coefficient_value >= 0.0 {
+        CHART_HEATMAP_POSITIVE_RGB
+    } else {
+        CHART_HEATMAP_NEGATIVE_RGB
+    };
+    let blend_weight = coefficient_value.abs().min(1.0);
+    let blend_channel = |target_channel: f64| (255.0 + (target_channel - 255.0) * blend_weight).round() as u8;
+
+    ChartColour { red: blend_channel(target_red), green: blend_channel(target_green), blue: blend_channel(target_blue) }
+}
+
+/// Draws a heatmap: a shaded, labelled square grid with the value in each cell
+fn lay_out_heatmap(drawing_builder: &mut ChartDrawingBuilder, heatmap_data: &HeatmapChartData) {
+    drawing_builder.add_title(&heatmap_data.chart_title);
+
+    let axis_label_count = heatmap_data.axis_labels.len();
+    if axis_label_count == 0 {
+        drawing_builder.add_text(drawing_builder.drawing_width / 2.0, drawing_builder.drawing_height / 2.0, "(no columns)", ChartTextAnchor::Middle, CHART_LABEL_FONT_SIZE);
+        return;
+    }
+
+    // Rows are labelled by name on the left, columns by number along the top
+    let longest_label_length = heatmap_data.axis_labels
+        .iter()
+        .map(|axis_label| axis_label.chars().count().min(CHART_MAXIMUM_BAR_LABEL_LENGTH) + 4)
+        .max()
+        .unwrap_or(0);
+    let label_margin = longest_label_length as f64 * CHART_LABEL_FONT_SIZE * 0.6 + 12.0;
+    let grid_top = CHART_TOP_MARGIN + CHART_LABEL_FONT_SIZE + 6.0;
+    let available_width = drawing_builder.drawing_width - label_margin - CHART_RIGHT_MARGIN;
+    let available_height = drawing_builder.drawing_height - grid_top - CHART_RIGHT_MARGIN;
+    let cell_size = (available_width.min(available_height) / axis_label_count as f64).max(1.0);
+
+    for (column_position, _) in heatmap_data.axis_labels.iter().enumerate() {
+        drawing_builder.add_text(
+            label_margin + (column_position as f64 + 0.5) * cell_size,
+            grid_top - 6.0,
+            &(column_position + 1).to_string(),
+            ChartTextAnchor::Middle,
+            CHART_LABEL_FONT_SIZE,
+        );
+    }
+
+    for (row_position, axis_label) in heatmap_data.axis_labels.iter().enumerate() {
+        let cell_top = grid_top + row_position as f64 * cell_size;
+        let mut row_label: String = axis_label.chars().take(CHART_MAXIMUM_BAR_LABEL_LENGTH).collect();
+        if axis_label.chars().count() > CHART_MAXIMUM_BAR_LABEL_LENGTH {
+            row_label.push('…');
+        }
+        drawing_builder.add_text(
+            label_margin - 6.0,
+            cell_top + cell_size / 2.0 + CHART_LABEL_FONT_SIZE / 3.0,
+            &format!("{}. {}", row_position + 1, row_label),
+            ChartTextAnchor::End,
+            CHART_LABEL_FONT_SIZE,
+        );
+
+        for column_position in 0..axis_label_count {
+            let cell_left = label_margin + column_position as f64 * cell_size;
+            let cell_value = heatmap_data.cell_values
+                .get(row_position)
+                .and_then(|row_values| row_values.get(column_position))
+                .copied()
+                .flatten();
+
+            match cell_value {
+                Some(coefficient_value) => {
+                    drawing_builder.add_rectangle(cell_left, cell_top, cell_size, cell_size, calculate_heatmap_cell_colour(coefficient_value));
+                    // Values are only legible when the cell is big enough; strong colours get light text
+                    if cell_size >= CHART_LABEL_FONT_SIZE * 3.0 {
+                        drawing_builder.drawing_elements.push(ChartDrawingElement::Text {
+                            anchor_x: cell_left + cell_size / 2.0,
+                            baseline_y: cell_top + cell_size / 2.0 + CHART_LABEL_FONT_SIZE / 3.0,
+                            text_content: format!("{:.2}", coefficient_value),
+                            text_anchor: ChartTextAnchor::Middle,
+                            font_size: CHART_LABEL_FONT_SIZE,
+                            text_colour: if coefficient_value.abs() > 0.6 { CHART_BACKGROUND_COLOUR } else { CHART_FOREGROUND_COLOUR },
+                            is_vertical: false,
+                        });
+                    }
+                }
+                None => drawing_builder.add_rectangle(cell_left, cell_top, cell_size, cell_size, CHART_GRID_COLOUR),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::chart_rendering_module::BarChartBar;
+
+    /// Test nice ticks cover the range with round steps
+    #[test]
+    fn test_calculate_nice_axis_ticks() {
+        assert_eq!(calculate_nice_axis_ticks(0.0, 10.0, 5), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert_eq!(calculate_nice_axis_ticks(0.13, 0.92, 4), vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+        assert_eq!(calculate_nice_axis_ticks(-3.0, 47.0, 5), vec![-10.0, 0.0, 10.0, 20.0, 30.0, 40.0, 50.0]);
+
+        let constant_range_ticks = calculate_nice_axis_ticks(5.0, 5.0, 5);
+        assert!(constant_range_ticks[0] < 5.0 && constant_range_ticks[constant_range_ticks.len() - 1] > 5.0);
+    }
+
+    /// Test escaping of markup characters
+    #[test]
+    fn test_escape_xml_text() {
+        assert_eq!(escape_xml_text("a<b & \"c\" 'd'>"), "a&lt;b &amp; &quot;c&quot; &#39;d&#39;&gt;");
+    }
+
+    /// Test a bar chart renders one bar per category with escaped labels
+    #[test]
+    fn test_render_bar_chart_as_svg() {
+        let chart_definition = ChartDefinition::BarChart(BarChartData {
+            chart_title: "breed <bar chart>".to_string(),
+            chart_bars: vec![
+                BarChartBar { bar_label: "Tabby".to_string(), bar_count: 6, bar_percentage: 60.0 },
+                BarChartBar { bar_label: "R&B".to_string(), bar_count: 4, bar_percentage: 40.0 },
+            ],
+            omitted_category_count: 0,
+        });
+
+        let svg_markup = render_chart_as_svg(&chart_definition, 400, 200);
+        assert!(svg_markup.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" height=\"200\""));
+        assert!(svg_markup.trim_end().ends_with("</svg>"));
+        assert!(svg_markup.contains(">breed &lt;bar chart&gt;</text>"));
+        assert!(svg_markup.contains(">R&amp;B</text>"));
+        assert!(svg_markup.contains(">6 (60.0%)</text>"));
+        assert_eq!(svg_markup.matches(&format!("fill=\"{}\"", CHART_DATA_FILL_COLOUR.to_hex_text())).count(), 2);
+    }
+
+    /// Test a scatter plot file has one point per pair and labelled axes
+    #[test]
+    fn test_write_scatter_plot_svg_file() {
+        let chart_definition = ChartDefinition::ScatterPlot(ScatterPlotChartData {
+            chart_title: "weight vs age (scatter)".to_string(),
+            x_axis_label: "age".to_string(),
+            y_axis_label: "weight".to_string(),
+            plot_points: vec![(1.0, 3.5), (2.0, 4.0), (9.0, 5.25)],
+            points_were_capped: false,
+        });
+        let test_directory = std::env::temp_dir().join(format!("rows_and_columns_svg_test_{}", std::process::id()));
+        let svg_file_path = test_directory.join(format!("{}.svg", get_chart_title(&chart_definition).replace(' ', "_")));
+
+        write_chart_svg_file(&chart_definition, DEFAULT_SVG_CHART_WIDTH, DEFAULT_SVG_CHART_HEIGHT, &svg_file_path)
+            .expect("SVG file should be writable");
+        let svg_document = std::fs::read_to_string(&svg_file_path).expect("SVG file should be readable");
+
+        assert!(svg_document.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg "));
+        assert_eq!(svg_document.matches("<circle ").count(), 3);
+        assert!(svg_document.contains(">age</text>"));
+        assert!(svg_document.contains("transform=\"rotate(-90 ") && svg_document.contains(">weight</text>"));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    /// Test ranges that overflow f64 or are too narrow to divide into steps
+    /// still give ascending, finite ticks that cover the range
+    #[test]
+    fn test_nice_axis_ticks_for_extreme_ranges() {
+        for (range_minimum, range_maximum) in [(-1e308, 1e308), (f64::MAX, f64::MAX), (0.0, 5e-324), (1e-310, 3e-310), (1e16, 1e16 + 4.0)] {
+            let axis_ticks = calculate_nice_axis_ticks(range_minimum, range_maximum, CHART_TARGET_TICK_COUNT);
+            assert!(axis_ticks.len() >= 2 && axis_ticks.len() <= 4 * CHART_TARGET_TICK_COUNT);
+            assert!(axis_ticks.iter().all(|tick_value| tick_value.is_finite()));
+            assert!(axis_ticks.windows(2).all(|tick_pair| tick_pair[0] <= tick_pair[1]));
+            assert!(axis_ticks[0] <= range_minimum && axis_ticks[axis_ticks.len() - 1] >= range_maximum);
+        }
+    }
+
+    /// Test a scatter plot spanning the whole f64 range keeps every point
+    /// inside the plot, and degenerate charts render without numbers like NaN
+    #[test]
+    fn test_extreme_and_empty_charts_have_finite_coordinates() {
+        let scatter_plot = ChartDefinition::ScatterPlot(ScatterPlotChartData {
+            chart_title: "extremes".to_string(),
+            x_axis_label: "x".to_string(),
+            y_axis_label: "y".to_string(),
+            plot_points: vec![(-1e308, 0.0), (1e308, 5e-324), (0.0, f64::MAX)],
+            points_were_capped: false,
+        });
+        let empty_heatmap = ChartDefinition::Heatmap(HeatmapChartData {
+            chart_title: "empty".to_string(),
+            axis_labels: Vec::new(),
+            cell_values: Vec::new(),
+        });
+        let single_value_histogram = ChartDefinition::Histogram(HistogramChartData {
+            chart_title: "constant".to_string(),
+            minimum_value: 7.0,
+            maximum_value: 7.0,
+            bin_counts: vec![3],
+        });
+
+        for chart_definition in [&scatter_plot, &empty_heatmap, &single_value_histogram] {
+            let svg_markup = render_chart_as_svg(chart_definition, 0, 0);
+            assert!(!svg_markup.contains("NaN") && !svg_markup.contains("inf"), "{}", svg_markup);
+        }
+
+        let chart_drawing = lay_out_chart_drawing(&scatter_plot, DEFAULT_SVG_CHART_WIDTH, DEFAULT_SVG_CHART_HEIGHT);
+        let point_positions: Vec<(f64, f64)> = chart_drawing.drawing_elements
+            .iter()
+            .filter_map(|drawing_element| match drawing_element {
+                ChartDrawingElement::Circle { centre_x, centre_y, .. } => Some((*centre_x, *centre_y)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(point_positions.len(), 3);
+        assert!(point_positions.iter().all(|&(centre_x, centre_y)| {
+            (0.0..=DEFAULT_SVG_CHART_WIDTH as f64).contains(&centre_x) && (0.0..=DEFAULT_SVG_CHART_HEIGHT as f64).contains(&centre_y)
+        }));
+    }
+}
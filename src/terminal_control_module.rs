@@ -0,0 +1,783 @@
+// src/terminal_control_module.rs
+
+/// Low-level terminal control for the rows_and_columns full-screen interfaces
+///
+/// This module provides everything the full-screen TUI needs from the terminal
+/// without any third party crates and without unsafe code: raw-mode switching,
+/// the alternate screen buffer, terminal size queries, and keyboard decoding.
+///
+/// # Core Responsibilities
+/// - Detect whether standard input/output are attached to a TTY
+/// - Enter and reliably restore raw mode (via the POSIX `stty` utility)
+/// - Switch to and from the alternate screen buffer
+/// - Query the terminal size so layouts can be redrawn on resize
+/// - Decode raw key bytes (arrows, paging keys, characters) into `KeyPress` values
+/// - Measure and fit text by terminal display width (wide CJK/emoji, combining marks)
+///
+/// # Design Philosophy
+/// - Vanilla Rust: termios settings are changed with `stty` against `/dev/tty`
+///   instead of FFI, so no `unsafe` blocks are needed
+/// - Restore-on-drop: the raw mode session restores the saved terminal settings
+///   even if the caller returns early with an error
+/// - Polling reads: raw mode uses `min 0 time 1`, so reads time out every 100ms,
+///   which lets callers notice terminal resizes without signal handlers
+/// - Size queries run `stty`, so idle screens re-query the size only once a
+///   second (and right after input) through `TerminalSizeTracker`
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_tui_rendering_error,
+};
+
+/// Path of the controlling terminal device on POSIX systems
+const CONTROLLING_TERMINAL_DEVICE_PATH: &str = "/dev/tty";
+
+/// Fallback terminal size used when the size cannot be queried
+const FALLBACK_TERMINAL_COLUMN_COUNT: usize = 80;
+const FALLBACK_TERMINAL_ROW_COUNT: usize = 24;
+
+/// How long an idle full-screen loop keeps using a queried terminal size
+const TERMINAL_SIZE_REQUERY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// ANSI escape sequences used for full-screen rendering
+const ENTER_ALTERNATE_SCREEN_SEQUENCE: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN_SEQUENCE: &str = "\x1b[?1049l";
+const HIDE_CURSOR_SEQUENCE: &str = "\x1b[?25l";
+const SHOW_CURSOR_SEQUENCE: &str = "\x1b[?25h";
+const MOVE_CURSOR_HOME_SEQUENCE: &str = "\x1b[H";
+const CLEAR_TO_END_OF_LINE_SEQUENCE: &str = "\x1b[K";
+const CLEAR_ENTIRE_SCREEN_SEQUENCE: &str = "\x1b[2J";
+
+/// A single decoded key press from the terminal
+///
+/// Raw terminal input arrives as bytes and escape sequences; this enum gives
+/// the rest of the application a clear, named representation of each key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyPress {
+    /// Up arrow key (`ESC [ A`)
+    ArrowUp,
+
+    /// Down arrow key (`ESC [ B`)
+    ArrowDown,
+
+    /// Right arrow key (`ESC [ C`)
+    ArrowRight,
+
+    /// Left arrow key (`ESC [ D`)
+    ArrowLeft,
+
+    /// Page Up key (`ESC [ 5 ~`)
+    PageUp,
+
+    /// Page Down key (`ESC [ 6 ~`)
+    PageDown,
+
+    /// Home key (`ESC [ H` or `ESC [ 1 ~`)
+    Home,
+
+    /// End key (`ESC [ F` or `ESC [ 4 ~`)
+    End,
+
+    /// Enter / Return key
+    Enter,
+
+    /// Escape key pressed on its own
+    Escape,
+
+    /// Backspace or Delete-left key
+    Backspace,
+
+    /// Tab key
+    Tab,
+
+    /// Ctrl-C (byte 0x03); raw mode turns off the interrupt signal, so the
+    /// screens treat it as a quit key instead
+    Interrupt,
+
+    /// Any printable character (including multi-byte UTF-8 characters)
+    Character(char),
+}
+
+/// Terminal dimensions measured in character cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerminalSize {
+    /// Number of character columns (width)
+    pub column_count: usize,
+
+    /// Number of character rows (height)
+    pub row_count: usize,
+}
+
+/// Checks whether the application is attached to an interactive terminal
+///
+/// Full-screen mode needs both a TTY on standard output (to draw) and on
+/// standard input (so the user can actually press keys). When either is
+/// redirected, callers should fall back to line-based output.
+///
+/// # Returns
+/// * `bool` - True if both stdin and stdout are terminals
+pub fn is_interactive_terminal_available() -> bool {
+    io::stdout().is_terminal() && io::stdin().is_terminal()
+}
+
+/// An active raw-mode, alternate-screen terminal session
+///
+/// Creating a session saves the current terminal settings, switches the
+/// terminal into raw mode with polling reads, enters the alternate screen,
+/// and hides the cursor. Dropping the session restores everything, so the
+/// user's shell is never left in a broken state.
+pub struct TerminalRawModeSession {
+    /// Handle to `/dev/tty` used for reading key presses
+    terminal_input_device: File,
+
+    /// Output of `stty -g`, used to restore the original settings on drop
+    saved_terminal_settings: String,
+}
+
+impl TerminalRawModeSession {
+    /// Enters raw mode and the alternate screen buffer
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<TerminalRawModeSession>` - The active session or error
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::FileSystemError` - If `/dev/tty` cannot be opened
+    /// * `RowsAndColumnsError::TuiRenderingError` - If `stty` fails or is unavailable
+    pub fn enter_raw_mode_session() -> RowsAndColumnsResult<TerminalRawModeSession> {
+        // Step 1: Save the current terminal settings so they can be restored later
+        let saved_terminal_settings = run_stty_command_against_terminal(&["-g"])?;
+        let saved_terminal_settings = saved_terminal_settings.trim().to_string();
+
+        if saved_terminal_settings.is_empty() {
+            return Err(create_tui_rendering_error(
+                "stty returned empty terminal settings; cannot safely enter raw mode"
+            ));
+        }
+
+        // Step 2: Raw mode, no echo, and reads that time out after 100ms (min 0 time 1)
+        run_stty_command_against_terminal(&["raw", "-echo", "min", "0", "time", "1"])?;
+
+        // Step 3: Open the terminal device for key input
+        let terminal_input_device = File::open(CONTROLLING_TERMINAL_DEVICE_PATH)
+            .map_err(|io_error| {
+                // Best effort: put the terminal back before reporting the error
+                let _ = run_stty_command_against_terminal(&[saved_terminal_settings.as_str()]);
+                create_file_system_error(
+                    "Failed to open controlling terminal for keyboard input",
+                    io_error
+                )
+            })?;
+
+        let raw_mode_session = TerminalRawModeSession {
+            terminal_input_device,
+            saved_terminal_settings,
+        };
+
+        // Step 4: Switch to the alternate screen and hide the cursor
+        write_escape_sequences_to_stdout(&[
+            ENTER_ALTERNATE_SCREEN_SEQUENCE,
+            HIDE_CURSOR_SEQUENCE,
+            CLEAR_ENTIRE_SCREEN_SEQUENCE,
+        ])?;
+
+        Ok(raw_mode_session)
+    }
+
+    /// Reads all key presses currently available, waiting at most ~100ms
+    ///
+    /// An empty list means the read timed out with no input, which callers
+    /// use as an opportunity to check for terminal resizes.
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<KeyPress>>` - Decoded key presses (possibly empty)
+    pub fn read_available_key_presses(&mut self) -> RowsAndColumnsResult<Vec<KeyPress>> {
+        let mut raw_input_bytes = [0u8; 64];
+
+        let bytes_read_count = match self.terminal_input_device.read(&mut raw_input_bytes) {
+            Ok(byte_count) => byte_count,
+            Err(io_error) if io_error.kind() == io::ErrorKind::Interrupted => 0,
+            Err(io_error) => {
+                return Err(create_file_system_error(
+                    "Failed to read key press from terminal",
+                    io_error
+                ));
+            }
+        };
+
+        Ok(decode_key_press_bytes(&raw_input_bytes[..bytes_read_count]))
+    }
+}
+
+impl Drop for TerminalRawModeSession {
+    /// Restores the cursor, main screen, and original terminal settings
+    fn drop(&mut self) {
+        // Errors are ignored here: there is no caller left to report them to,
+        // and every step is attempted regardless of earlier failures.
+        let _ = write_escape_sequences_to_stdout(&[
+            SHOW_CURSOR_SEQUENCE,
+            LEAVE_ALTERNATE_SCREEN_SEQUENCE,
+        ]);
+        let _ = run_stty_command_against_terminal(&[self.saved_terminal_settings.as_str()]);
+    }
+}
+
+/// Runs `stty` with its standard input connected to the controlling terminal
+///
+/// `stty` operates on whatever terminal is its standard input, so we connect
+/// `/dev/tty` explicitly rather than relying on our own stdin.
+///
+/// # Arguments
+/// * `stty_arguments` - Arguments passed to `stty`
+///
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - Captured standard output from `stty`
+fn run_stty_command_against_terminal(stty_arguments: &[&str]) -> RowsAndColumnsResult<String> {
+    let terminal_device_for_stty = File::open(CONTROLLING_TERMINAL_DEVICE_PATH)
+        .map_err(|io_error| {
+            create_file_system_error(
+                "Failed to open controlling terminal for stty",
+                io_error
+            )
+        })?;
+
+    let stty_output = Command::new("stty")
+        .args(stty_arguments)
+        .stdin(Stdio::from(terminal_device_for_stty))
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|io_error| {
+            create_file_system_error(
+                "Failed to run stty (required for full-screen terminal mode)",
+                io_error
+            )
+        })?;
+
+    if !stty_output.status.success() {
+        return Err(create_tui_rendering_error(
+            &format!("stty {} exited with status {}", stty_arguments.join(" "), stty_output.status)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&stty_output.stdout).to_string())
+}
+
+/// Writes escape sequences to standard output and flushes immediately
+///
+/// # Arguments
+/// * `escape_sequences` - Sequences to write, in order
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or I/O error
+fn write_escape_sequences_to_stdout(escape_sequences: &[&str]) -> RowsAndColumnsResult<()> {
+    let mut standard_output = io::stdout().lock();
+
+    for escape_sequence in escape_sequences {
+        standard_output.write_all(escape_sequence.as_bytes()).map_err(|io_error| {
+            create_file_system_error("Failed to write terminal control sequence", io_error)
+        })?;
+    }
+
+    standard_output.flush().map_err(|io_error| {
+        create_file_system_error("Failed to flush terminal control sequence", io_error)
+    })
+}
+
+/// Queries the current terminal size
+///
+/// Uses `stty size` (which prints "rows columns"), falling back to the
+/// `COLUMNS`/`LINES` environment variables and finally to 80x24.
+///
+/// # Returns
+/// * `TerminalSize` - The best available terminal size
+pub fn query_terminal_size() -> TerminalSize {
+    // First choice: ask the terminal directly
+    if let Ok(stty_size_output) = run_stty_command_against_terminal(&["size"])
+        && let Some(terminal_size) = parse_stty_size_output(&stty_size_output) {
+        return terminal_size;
+    }
+
+    // Second choice: environment variables set by many shells
+    let environment_column_count = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns_text| columns_text.trim().parse::<usize>().ok());
+    let environment_row_count = std::env::var("LINES")
+        .ok()
+        .and_then(|lines_text| lines_text.trim().parse::<usize>().ok());
+
+    TerminalSize {
+        column_count: environment_column_count.unwrap_or(FALLBACK_TERMINAL_COLUMN_COUNT),
+        row_count: environment_row_count.unwrap_or(FALLBACK_TERMINAL_ROW_COUNT),
+    }
+}
+
+/// Caches the terminal size between redraws of a full-screen loop
+///
+/// Querying the size spawns `stty`, which is too costly to do on every 100ms
+/// poll. The tracker re-queries after input (when the user is likely to have
+/// just resized the window) and otherwise once per `TERMINAL_SIZE_REQUERY_INTERVAL`.
+#[derive(Debug, Clone)]
+pub struct TerminalSizeTracker {
+    /// The most recently queried size
+    cached_terminal_size: TerminalSize,
+
+    /// When `cached_terminal_size` was queried
+    last_query_instant: Instant,
+}
+
+impl TerminalSizeTracker {
+    /// Queries the size once and starts tracking it
+    ///
+    /// # Returns
+    /// * `TerminalSizeTracker` - A tracker holding the current size
+    pub fn start_tracking() -> TerminalSizeTracker {
+        TerminalSizeTracker {
+            cached_terminal_size: query_terminal_size(),
+            last_query_instant: Instant::now(),
+        }
+    }
+
+    /// Returns the terminal size, querying again only when needed
+    ///
+    /// # Arguments
+    /// * `input_just_arrived` - True when the last poll read key presses
+    ///
+    /// # Returns
+    /// * `TerminalSize` - The cached or freshly queried size
+    pub fn current_terminal_size(&mut self, input_just_arrived: bool) -> TerminalSize {
+        if input_just_arrived || self.last_query_instant.elapsed() >= TERMINAL_SIZE_REQUERY_INTERVAL {
+            self.cached_terminal_size = query_terminal_size();
+            self.last_query_instant = Instant::now();
+        }
+        self.cached_terminal_size
+    }
+}
+
+/// Parses the output of `stty size` ("rows columns")
+///
+/// # Arguments
+/// * `stty_size_output` - Raw text printed by `stty size`
+///
+/// # Returns
+/// * `Option<TerminalSize>` - Parsed size, or None if the output is malformed or zero
+fn parse_stty_size_output(stty_size_output: &str) -> Option<TerminalSize> {
+    let mut size_fields = stty_size_output.split_whitespace();
+    let row_count = size_fields.next()?.parse::<usize>().ok()?;
+    let column_count = size_fields.next()?.parse::<usize>().ok()?;
+
+    if row_count == 0 || column_count == 0 {
+        return None;
+    }
+
+    Some(TerminalSize { column_count, row_count })
+}
+
+/// Decodes raw terminal input bytes into key presses
+///
+/// Handles CSI escape sequences for arrows and paging keys, control bytes
+/// for Enter/Tab/Backspace, and UTF-8 text for printable characters.
+/// Unknown escape sequences are skipped rather than reported as characters.
+///
+/// # Arguments
+/// * `raw_input_bytes` - Bytes returned by a single terminal read
+///
+/// # Returns
+/// * `Vec<KeyPress>` - Decoded key presses in input order
+pub fn decode_key_press_bytes(raw_input_bytes: &[u8]) -> Vec<KeyPress> {
+    let mut decoded_key_presses = Vec::new();
+    let mut byte_position = 0;
+
+    while byte_position < raw_input_bytes.len() {
+        let current_byte = raw_input_bytes[byte_position];
+
+        match current_byte {
+            0x1b => {
+                // Escape: either a lone Escape key or the start of a CSI sequence
+                let next_byte = raw_input_bytes.get(byte_position + 1).copied();
+
+                if next_byte != Some(b'[') && next_byte != Some(b'O') {
+                    decoded_key_presses.push(KeyPress::Escape);
+                    byte_position += 1;
+                    continue;
+                }
+
+                // Find the final byte of the sequence (an ASCII letter or '~')
+                let mut sequence_end_position = byte_position + 2;
+                while sequence_end_position < raw_input_bytes.len() {
+                    let sequence_byte = raw_input_bytes[sequence_end_position];
+                    if sequence_byte.is_ascii_alphabetic() || sequence_byte == b'~' {
+                        break;
+                    }
+                    sequence_end_position += 1;
+                }
+
+                if sequence_end_position >= raw_input_bytes.len() {
+                    // Truncated sequence: treat the escape byte as a lone Escape
+                    decoded_key_presses.push(KeyPress::Escape);
+                    break;
+                }
+
+                let sequence_body = &raw_input_bytes[byte_position + 2..=sequence_end_position];
+                if let Some(decoded_key) = decode_escape_sequence_body(sequence_body) {
+                    decoded_key_presses.push(decoded_key);
+                }
+
+                byte_position = sequence_end_position + 1;
+            }
+            b'\r' | b'\n' => {
+                decoded_key_presses.push(KeyPress::Enter);
+                byte_position += 1;
+            }
+            b'\t' => {
+                decoded_key_presses.push(KeyPress::Tab);
+                byte_position += 1;
+            }
+            0x7f | 0x08 => {
+                decoded_key_presses.push(KeyPress::Backspace);
+                byte_position += 1;
+            }
+            0x03 => {
+                decoded_key_presses.push(KeyPress::Interrupt);
+                byte_position += 1;
+            }
+            0x00..=0x1f => {
+                // Other control bytes have no meaning in our interfaces
+                byte_position += 1;
+            }
+            _ => {
+                // Decode one UTF-8 character (1 to 4 bytes)
+                let utf8_character_length = match current_byte {
+                    0x00..=0x7f => 1,
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    _ => 4,
+                };
+                let character_end_position = (byte_position + utf8_character_length).min(raw_input_bytes.len());
+
+                if let Ok(character_text) = std::str::from_utf8(&raw_input_bytes[byte_position..character_end_position])
+                    && let Some(decoded_character) = character_text.chars().next() {
+                    decoded_key_presses.push(KeyPress::Character(decoded_character));
+                }
+
+                byte_position = character_end_position;
+            }
+        }
+    }
+
+    decoded_key_presses
+}
+
+/// Decodes the body of a CSI/SS3 escape sequence (the bytes after `ESC [`)
+///
+/// # Arguments
+/// * `sequence_body` - Parameter bytes plus final byte, e.g. `A` or `5~`
+///
+/// # Returns
+/// * `Option<KeyPress>` - The decoded key, or None for unsupported sequences
+fn decode_escape_sequence_body(sequence_body: &[u8]) -> Option<KeyPress> {
+    match sequence_body {
+        b"A" => Some(KeyPress::ArrowUp),
+        b"B" => Some(KeyPress::ArrowDown),
+        b"C" => Some(KeyPress::ArrowRight),
+        b"D" => Some(KeyPress::ArrowLeft),
+        b"H" | b"1~" | b"7~" => Some(KeyPress::Home),
+        b"F" | b"4~" | b"8~" => Some(KeyPress::End),
+        b"5~" => Some(KeyPress::PageUp),
+        b"6~" => Some(KeyPress::PageDown),
+        _ => None,
+    }
+}
+
+/// Draws a complete frame of text lines to the terminal
+///
+/// Each line is clipped to the terminal width, every row is cleared to the
+/// end of line, and unused rows below the content are blanked. The whole frame
+/// is written with a single system call to avoid flicker.
+///
+/// # Arguments
+/// * `frame_lines` - The text lines to display, top to bottom
+/// * `terminal_size` - Current terminal dimensions
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or I/O error
+pub fn draw_full_screen_frame(
+    frame_lines: &[String],
+    terminal_size: TerminalSize,
+) -> RowsAndColumnsResult<()> {
+    let mut frame_buffer = String::new();
+    frame_buffer.push_str(MOVE_CURSOR_HOME_SEQUENCE);
+
+    for screen_row_index in 0..terminal_size.row_count {
+        let line_text = frame_lines
+            .get(screen_row_index)
+            .map(|line| clip_text_to_display_width(line, terminal_size.column_count))
+            .unwrap_or_default();
+
+        frame_buffer.push_str(&line_text);
+        frame_buffer.push_str(CLEAR_TO_END_OF_LINE_SEQUENCE);
+
+        // In raw mode a newline does not return the carriage, so send both
+        if screen_row_index + 1 < terminal_size.row_count {
+            frame_buffer.push_str("\r\n");
+        }
+    }
+
+    let mut standard_output = io::stdout().lock();
+    standard_output.write_all(frame_buffer.as_bytes()).map_err(|io_error| {
+        create_file_system_error("Failed to draw full-screen frame", io_error)
+    })?;
+    standard_output.flush().map_err(|io_error| {
+        create_file_system_error("Failed to flush full-screen frame", io_error)
+    })
+}
+
+/// Clips text to at most the given number of characters
+///
+/// # Arguments
+/// * `text_to_clip` - The text to clip
+/// * `maximum_character_count` - Maximum number of characters to keep
+///
+/// # Returns
+/// * `String` - The clipped text
+pub fn clip_text_to_character_count(text_to_clip: &str, maximum_character_count: usize) -> String {
+    text_to_clip.chars().take(maximum_character_count).collect()
+}
+
+/// Pads or clips text to exactly the given number of characters
+///
+/// # Arguments
+/// * `text_to_fit` - The text to pad or clip
+/// * `exact_character_count` - The resulting width in characters
+///
+/// # Returns
+/// * `String` - Text of exactly `exact_character_count` characters
+pub fn fit_text_to_character_count(text_to_fit: &str, exact_character_count: usize) -> String {
+    let mut fitted_text = clip_text_to_character_count(text_to_fit, exact_character_count);
+    let current_character_count = fitted_text.chars().count();
+
+    for _ in current_character_count..exact_character_count {
+        fitted_text.push(' ');
+    }
+
+    fitted_text
+}
+
+/// Returns how many terminal cells a character occupies
+///
+/// This is a compact approximation of the Unicode East Asian Width rules:
+/// control characters and combining marks take no cells, wide CJK and emoji
+/// ranges take two, and everything else takes one.
+///
+/// # Arguments
+/// * `text_character` - The character to measure
+///
+/// # Returns
+/// * `usize` - 0, 1, or 2 terminal cells
+pub fn calculate_character_display_width(text_character: char) -> usize {
+    let code_point = text_character as u32;
+
+    // Control characters and zero-width/combining code points
+    if code_point < 0x20
+        || (0x7F..0xA0).contains(&code_point)
+        || (0x0300..=0x036F).contains(&code_point)
+        || (0x200B..=0x200F).contains(&code_point)
+        || (0x20D0..=0x20FF).contains(&code_point)
+        || (0xFE00..=0xFE0F).contains(&code_point)
+    {
+        return 0;
+    }
+
+    // Wide (double-cell) ranges
+    let is_wide_character = (0x1100..=0x115F).contains(&code_point)
+        || (0x2E80..=0x303E).contains(&code_point)
+        || (0x3041..=0x33FF).contains(&code_point)
+        || (0x3400..=0x4DBF).contains(&code_point)
+        || (0x4E00..=0x9FFF).contains(&code_point)
+        || (0xA000..=0xA4CF).contains(&code_point)
+        || (0xAC00..=0xD7A3).contains(&code_point)
+        || (0xF900..=0xFAFF).contains(&code_point)
+        || (0xFE30..=0xFE4F).contains(&code_point)
+        || (0xFF00..=0xFF60).contains(&code_point)
+        || (0xFFE0..=0xFFE6).contains(&code_point)
+        || (0x1F300..=0x1F64F).contains(&code_point)
+        || (0x1F900..=0x1F9FF).contains(&code_point)
+        || (0x20000..=0x3FFFD).contains(&code_point);
+
+    if is_wide_character { 2 } else { 1 }
+}
+
+/// Returns how many terminal cells a string occupies
+///
+/// # Arguments
+/// * `text_to_measure` - The text to measure
+///
+/// # Returns
+/// * `usize` - Total display width in terminal cells
+pub fn calculate_text_display_width(text_to_measure: &str) -> usize {
+    text_to_measure.chars().map(calculate_character_display_width).sum()
+}
+
+/// Clips text so that it occupies at most the given number of terminal cells
+///
+/// # Arguments
+/// * `text_to_clip` - The text to clip
+/// * `maximum_display_width` - Maximum number of terminal cells
+///
+/// # Returns
+/// * `String` - The clipped text (a wide character that would straddle the edge is dropped)
+pub fn clip_text_to_display_width(text_to_clip: &str, maximum_display_width: usize) -> String {
+    let mut clipped_text = String::new();
+    let mut used_display_width = 0;
+
+    for text_character in text_to_clip.chars() {
+        let character_width = calculate_character_display_width(text_character);
+        if used_display_width + character_width > maximum_display_width {
+            break;
+        }
+        used_display_width += character_width;
+        clipped_text.push(text_character);
+    }
+
+    clipped_text
+}
+
+/// Pads or clips text to exactly the given number of terminal cells
+///
+/// # Arguments
+/// * `text_to_fit` - The text to pad or clip
+/// * `exact_display_width` - The resulting width in terminal cells
+///
+/// # Returns
+/// * `String` - Text occupying exactly `exact_display_width` cells
+pub fn fit_text_to_display_width(text_to_fit: &str, exact_display_width: usize) -> String {
+    let mut fitted_text = clip_text_to_display_width(text_to_fit, exact_display_width);
+    let current_display_width = calculate_text_display_width(&fitted_text);
+
+    for _ in current_display_width..exact_display_width {
+        fitted_text.push(' ');
+    }
+
+    fitted_text
+}
+
+/// Formats rows of cells as an aligned text table
+///
+/// Column widths are measured in terminal cells and capped at
+/// `maximum_column_width`; longer cells are clipped. A separator line made of
+/// `separator_character` follows the header.
+///
+/// # Arguments
+/// * `header_cells` - Column headings
+/// * `body_rows` - Table rows (each with one cell per heading)
+/// * `right_aligned_columns` - Whether each column is right-aligned (e.g. numbers)
+/// * `maximum_column_width` - Widest any column may be
+/// * `separator_character` - Character used for the line under the header
+///
+/// # Returns
+/// * `Vec<String>` - Header, separator and body lines (columns joined by two spaces)
+pub fn format_aligned_text_table_lines(
+    header_cells: &[String],
+    body_rows: &[Vec<String>],
+    right_aligned_columns: &[bool],
+    maximum_column_width: usize,
+    separator_character: char,
+) -> Vec<String> {
+    let column_widths: Vec<usize> = (0..header_cells.len())
+        .map(|column_position| {
+            body_rows.iter()
+                .filter_map(|body_row| body_row.get(column_position))
+                .chain(std::iter::once(&header_cells[column_position]))
+                .map(|cell_text| calculate_text_display_width(cell_text))
+                .max()
+                .unwrap_or(1)
+                .min(maximum_column_width)
+        })
+        .collect();
+
+    let format_table_row = |row_cells: &[String]| -> String {
+        column_widths.iter()
+            .enumerate()
+            .map(|(column_position, &column_width)| {
+                let cell_text = row_cells.get(column_position).map(String::as_str).unwrap_or("");
+                let cell_width = calculate_text_display_width(cell_text);
+                let is_right_aligned = right_aligned_columns.get(column_position).copied().unwrap_or(false);
+                if is_right_aligned && cell_width < column_width {
+                    format!("{}{}", " ".repeat(column_width - cell_width), cell_text)
+                } else {
+                    fit_text_to_display_width(cell_text, column_width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table_lines = vec![format_table_row(header_cells)];
+    table_lines.push(
+        column_widths.iter()
+            .map(|&column_width| separator_character.to_string().repeat(column_width))
+            .collect::<Vec<_>>()
+            .join("  ")
+    );
+    for body_row in body_rows {
+        table_lines.push(format_table_row(body_row));
+    }
+
+    table_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test decoding of arrow keys, paging keys and plain characters
+    #[test]
+    fn test_decode_key_press_bytes() {
+        let decoded_keys = decode_key_press_bytes(b"\x1b[A\x1b[Bjk\x1b[5~\r");
+        assert_eq!(decoded_keys, vec![
+            KeyPress::ArrowUp,
+            KeyPress::ArrowDown,
+            KeyPress::Character('j'),
+            KeyPress::Character('k'),
+            KeyPress::PageUp,
+            KeyPress::Enter,
+        ]);
+
+        // A lone escape byte is the Escape key
+        assert_eq!(decode_key_press_bytes(b"\x1b"), vec![KeyPress::Escape]);
+
+        // Ctrl-C arrives as a byte, not a signal, while in raw mode
+        assert_eq!(decode_key_press_bytes(b"j\x03"), vec![KeyPress::Character('j'), KeyPress::Interrupt]);
+
+        // Multi-byte UTF-8 characters decode to a single character
+        assert_eq!(decode_key_press_bytes("é".as_bytes()), vec![KeyPress::Character('é')]);
+    }
+
+    /// Test parsing of `stty size` output
+    #[test]
+    fn test_parse_stty_size_output() {
+        assert_eq!(
+            parse_stty_size_output("40 120\n"),
+            Some(TerminalSize { column_count: 120, row_count: 40 })
+        );
+        assert_eq!(parse_stty_size_output("0 0"), None);
+        assert_eq!(parse_stty_size_output("garbage"), None);
+    }
+
+    /// Test text fitting helpers used by the layout code
+    #[test]
+    fn test_fit_text_to_character_count() {
+        assert_eq!(fit_text_to_character_count("abc", 5), "abc  ");
+        assert_eq!(fit_text_to_character_count("abcdef", 3), "abc");
+        assert_eq!(clip_text_to_character_count("héllo", 2), "hé");
+        assert_eq!(fit_text_to_display_width("日本", 5), "日本 ");
+        assert_eq!(fit_text_to_display_width("日本", 3), "日 ");
+        assert_eq!(calculate_text_display_width("e\u{301}x"), 2);
+    }
+}
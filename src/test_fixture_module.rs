@@ -0,0 +1,129 @@
+// src/test_fixture_module.rs
+
+//! Shared fixtures for the module tests: analysis results for small CSV files,
+//! built from column names and types without running the full analysis, and
+//! gzip data for the compressed input tests
+
+// Import standard library components
+use std::path::Path;
+
+// Import the gzip header flag and checksum for hand-built gzip members
+use super::gzip_decompression_module::{GZIP_FLAG_FILE_NAME, update_crc32_checksum};
+
+// Import the analysis result types the fixtures build
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvColumnDataType,
+    CsvColumnInformation,
+    EnhancedCsvColumnInformation,
+};
+
+/// Builds basic column information with the given non-empty count and no samples
+pub fn create_test_column_information(
+    column_index: usize,
+    column_name: &str,
+    detected_data_type: CsvColumnDataType,
+    non_empty_value_count: usize,
+) -> CsvColumnInformation {
+    CsvColumnInformation {
+        column_index,
+        column_name: column_name.to_string(),
+        detected_data_type,
+        non_empty_value_count,
+        empty_value_count: 0,
+        sample_values: Vec::new(),
+    }
+}
+
+/// Builds analysis results for a CSV file without reading or analyzing it
+///
+/// # Arguments
+/// * `csv_file_path` - The file the results point at (it need not exist)
+/// * `column_definitions` - Column names and detected types, in file order
+pub fn create_test_analysis_results(
+    csv_file_path: &Path,
+    column_definitions: &[(&str, CsvColumnDataType)],
+) -> CsvAnalysisResults {
+    CsvAnalysisResults {
+        csv_file_path: csv_file_path.to_path_buf(),
+        has_header_row: true,
+        total_column_count: column_definitions.len(),
+        total_data_row_count: 0,
+        column_information_list: column_definitions
+            .iter()
+            .enumerate()
+            .map(|(column_index, (column_name, detected_data_type))| {
+                create_test_column_information(column_index, column_name, detected_data_type.clone(), 0)
+            })
+            .collect(),
+        metadata_file_path: csv_file_path.with_extension("csv_metadata.toml"),
+        metadata_file_already_existed: false,
+    }
+}
+
+/// Writes a CSV file and returns analysis results pointing at it
+///
+/// Column names come from the header line; columns past the end of
+/// `column_types` are typed as String.
+pub fn write_test_csv_analysis_results(
+    csv_file_path: &Path,
+    csv_text: &str,
+    column_types: &[CsvColumnDataType],
+) -> CsvAnalysisResults {
+    std::fs::write(csv_file_path, csv_text).expect("test CSV should be writable");
+
+    let column_definitions: Vec<(&str, CsvColumnDataType)> = csv_text
+        .lines()
+        .next()
+        .unwrap_or("")
+        .split(',')
+        .enumerate()
+        .map(|(column_index, column_name)| {
+            (column_name, column_types.get(column_index).cloned().unwrap_or(CsvColumnDataType::String))
+        })
+        .collect();
+
+    let mut csv_analysis_results = create_test_analysis_results(csv_file_path, &column_definitions);
+    csv_analysis_results.total_data_row_count = csv_text.lines().count() - 1;
+    csv_analysis_results
+}
+
+/// Writes a CSV to its own per-process temp directory and pairs analysis
+/// results for it with the given enhanced columns, as the report builders expect
+///
+/// The caller removes the directory (the parent of `csv_file_path`) when done.
+pub fn create_test_report_analysis(
+    file_name: &str,
+    csv_text: &str,
+    enhanced_analysis_results: Vec<EnhancedCsvColumnInformation>,
+) -> (CsvAnalysisResults, Vec<EnhancedCsvColumnInformation>) {
+    let test_directory = std::env::temp_dir().join(format!("{}_{}", file_name.trim_end_matches(".csv"), std::process::id()));
+    std::fs::create_dir_all(&test_directory).expect("temp dir should be created");
+    let csv_file_path = test_directory.join(file_name);
+    std::fs::write(&csv_file_path, csv_text).expect("test CSV should be writable");
+
+    let csv_analysis_results = CsvAnalysisResults {
+        csv_file_path: csv_file_path.clone(),
+        has_header_row: true,
+        total_column_count: enhanced_analysis_results.len(),
+        total_data_row_count: csv_text.lines().count() - 1,
+        column_information_list: enhanced_analysis_results.iter().map(|column_info| column_info.basic_info.clone()).collect(),
+        metadata_file_path: csv_file_path.with_extension("csv_metadata.toml"),
+        metadata_file_already_existed: false,
+    };
+
+    (csv_analysis_results, enhanced_analysis_results)
+}
+
+/// Builds a gzip member holding `data_bytes` in one stored block, with a file name
+pub fn build_stored_gzip_member(data_bytes: &[u8]) -> Vec<u8> {
+    let mut member_bytes = vec![0x1f, 0x8b, 0x08, GZIP_FLAG_FILE_NAME, 0, 0, 0, 0, 0, 0xff];
+    member_bytes.extend_from_slice(b"data.csv\0");
+    member_bytes.push(0x01);
+    member_bytes.extend_from_slice(&(data_bytes.len() as u16).to_le_bytes());
+    member_bytes.extend_from_slice(&(!(data_bytes.len() as u16)).to_le_bytes());
+    member_bytes.extend_from_slice(data_bytes);
+    member_bytes.extend_from_slice(&update_crc32_checksum(0, data_bytes).to_le_bytes());
+    member_bytes.extend_from_slice(&(data_bytes.len() as u32).to_le_bytes());
+    member_bytes
+}
@@ -0,0 +1,1010 @@
+// src/tui_dashboard_module.rs
+
+/// Full-screen interactive TUI dashboard for rows_and_columns
+///
+/// This module presents the results of an enhanced CSV analysis in a single
+/// full-screen view with three panes: a column list, a statistics pane fed by
+/// `EnhancedCsvColumnInformation`, and a chart pane. When standard output is
+/// not a terminal, the same content is printed in a line-based form instead.
+///
+/// # Layout
+/// ```text
+/// ┌ title bar ─────────────────────────────────────────────┐
+/// │ Columns       │ Statistics pane                         │
+/// │ > 1. id       │                                         │
+/// │   2. name     ├─────────────────────────────────────────┤
+/// │   ...         │ Chart pane                              │
+/// └ key help / status bar ─────────────────────────────────┘
+/// ```
+///
+/// # Keys
+/// - `↑`/`↓` or `k`/`j`: move within the focused pane
+/// - `←`/`→` or `h`/`l`, `Tab`: move focus between panes
+/// - `PgUp`/`PgDn`: scroll the statistics pane
+/// - `Home`/`End` or `g`/`G`: first/last column
+/// - `c`: cycle chart type (continuous columns include the correlation heatmap),
+///   `x`: cycle scatter plot partner column
+/// - `s`/`p`/`P`: save the current chart as an SVG/PNG/PPM file in the chart
+///   export directory
+/// - `u`: toggle Unicode/ASCII glyphs, `q`/`Esc`/`Ctrl-C`: leave the dashboard
+///
+/// # Design Philosophy
+/// - Charts that need row data (histogram, scatter) are computed on demand
+///   for the highlighted column only, then cached
+/// - Keys are polled every 100ms; the terminal size is re-checked after input
+///   and once a second while idle, so resizes trigger a redraw
+/// - Graceful degradation: no TTY means plain line-based output
+use std::collections::HashMap;
+use std::path::Path;
+
+// Import analysis structures
+use super::csv_processor_module::{
+    CsvAnalysisResults,
+    CsvFieldType,
+    EnhancedCsvColumnInformation,
+};
+
+// Import chart definitions and the text renderer
+use super::chart_rendering_module::{
+    ChartDefinition,
+    ChartGlyphMode,
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    DEFAULT_MAXIMUM_BAR_COUNT,
+    MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+    build_bar_chart_from_categorical_statistics,
+    build_box_plot_chart_from_statistics,
+    build_histogram_chart_from_csv_column,
+    build_scatter_plot_chart_from_csv_columns,
+    render_chart_as_text_lines,
+};
+
+// Import the SVG writer for saving charts
+use super::svg_chart_module::{
+    DEFAULT_SVG_CHART_HEIGHT,
+    DEFAULT_SVG_CHART_WIDTH,
+    get_chart_title,
+    write_chart_svg_file,
+};
+
+// Import the bitmap writer for saving charts as PNG
+use super::bitmap_chart_module::{BitmapImageFormat, write_chart_bitmap_file};
+
+// Import file name sanitizing for saved chart files
+use super::directory_store_module::sanitize_name_for_directory;
+
+// Import correlation results for the heatmap chart
+use super::correlation_analysis_module::{
+    CorrelationAnalysisResults,
+    build_correlation_heatmap_chart,
+};
+
+// Import terminal control for full-screen mode
+use super::terminal_control_module::{
+    KeyPress,
+    TerminalRawModeSession,
+    TerminalSize,
+    TerminalSizeTracker,
+    draw_full_screen_frame,
+    fit_text_to_character_count,
+    is_interactive_terminal_available,
+};
+
+// Import the configured chart glyphs
+use super::application_configuration_module::application_configuration;
+
+// Import our custom error types for comprehensive error handling
+use super::error_types_module::RowsAndColumnsResult;
+
+/// Smallest terminal the full-screen layout can use
+const MINIMUM_DASHBOARD_COLUMN_COUNT: usize = 40;
+const MINIMUM_DASHBOARD_ROW_COUNT: usize = 12;
+
+/// Maximum width of the column list pane
+const MAXIMUM_COLUMN_LIST_PANE_WIDTH: usize = 30;
+
+/// Chart size used by the line-based (non-TTY) dashboard
+const LINE_MODE_CHART_COLUMN_COUNT: usize = 72;
+const LINE_MODE_CHART_ROW_COUNT: usize = 14;
+
+/// The pane that currently receives navigation keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DashboardPane {
+    /// Column list on the left
+    ColumnList,
+
+    /// Statistics pane on the upper right
+    StatisticsPane,
+
+    /// Chart pane on the lower right
+    ChartPane,
+}
+
+impl DashboardPane {
+    /// Returns the pane to the right (wrapping around)
+    fn next_pane(self) -> DashboardPane {
+        match self {
+            DashboardPane::ColumnList => DashboardPane::StatisticsPane,
+            DashboardPane::StatisticsPane => DashboardPane::ChartPane,
+            DashboardPane::ChartPane => DashboardPane::ColumnList,
+        }
+    }
+
+    /// Returns the pane to the left (wrapping around)
+    fn previous_pane(self) -> DashboardPane {
+        match self {
+            DashboardPane::ColumnList => DashboardPane::ChartPane,
+            DashboardPane::StatisticsPane => DashboardPane::ColumnList,
+            DashboardPane::ChartPane => DashboardPane::StatisticsPane,
+        }
+    }
+}
+
+/// The kinds of chart the chart pane can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DashboardChartKind {
+    /// Histogram of a continuous column
+    Histogram,
+
+    /// Box plot of a continuous column
+    BoxPlot,
+
+    /// Scatter plot of a continuous column against a partner column
+    ScatterPlot,
+
+    /// Bar chart of a categorical column
+    BarChart,
+
+    /// Pearson correlation heatmap of all continuous columns
+    CorrelationHeatmap,
+}
+
+/// File formats the dashboard can save the current chart in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DashboardChartFileFormat {
+    /// Vector image (`s` key)
+    Svg,
+
+    /// Raster image: PNG (`p` key) or PPM (`P` key)
+    Bitmap(BitmapImageFormat),
+}
+
+/// Mutable state of the running dashboard
+struct DashboardState {
+    /// Index into the enhanced results of the highlighted column
+    selected_column_position: usize,
+
+    /// First visible row of the column list
+    column_list_scroll_offset: usize,
+
+    /// First visible row of the statistics pane
+    statistics_scroll_offset: usize,
+
+    /// Pane receiving navigation keys
+    focused_pane: DashboardPane,
+
+    /// Position in the list of chart kinds available for the selected column
+    chart_kind_position: usize,
+
+    /// Position in the list of continuous partner columns for scatter plots
+    scatter_partner_position: usize,
+
+    /// Unicode or ASCII glyphs
+    glyph_mode: ChartGlyphMode,
+
+    /// Charts already computed, keyed by (column position, kind, partner position)
+    chart_cache: HashMap<(usize, DashboardChartKind, usize), Result<ChartDefinition, String>>,
+
+    /// One-line message shown in the status bar
+    status_message: String,
+}
+
+/// Launches the dashboard for analyzed CSV results
+///
+/// Uses the full-screen interface when running in an interactive terminal and
+/// falls back to line-based output otherwise (pipes, files, CI logs).
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header info)
+/// * `enhanced_analysis_results` - Per-column statistics to display
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+/// * `chart_export_directory` - Where the `s` and `p` keys save chart files
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or terminal/IO error
+pub fn launch_tui_dashboard(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    chart_export_directory: &Path,
+) -> RowsAndColumnsResult<()> {
+    if enhanced_analysis_results.is_empty() {
+        println!("No columns to display in the dashboard.");
+        return Ok(());
+    }
+
+    if !is_interactive_terminal_available() {
+        display_line_based_dashboard(csv_analysis_results, enhanced_analysis_results, correlation_analysis_results);
+        return Ok(());
+    }
+
+    run_full_screen_dashboard(csv_analysis_results, enhanced_analysis_results, correlation_analysis_results, chart_export_directory)
+}
+
+/// Prints every column's statistics and chart as plain lines
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results
+/// * `enhanced_analysis_results` - Per-column statistics to display
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+fn display_line_based_dashboard(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+) {
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Dashboard (line mode: output is not an interactive terminal)");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!();
+
+    for (column_position, enhanced_column_info) in enhanced_analysis_results.iter().enumerate() {
+        for statistics_line in build_column_statistics_text_lines(enhanced_column_info) {
+            println!("{}", statistics_line);
+        }
+        println!();
+
+        // Line mode shows the primary chart for each column
+        let primary_chart_kind = list_available_chart_kinds(enhanced_analysis_results, column_position)
+            .first()
+            .copied();
+
+        if let Some(chart_kind) = primary_chart_kind {
+            match build_dashboard_chart(
+                csv_analysis_results,
+                enhanced_analysis_results,
+                correlation_analysis_results,
+                column_position,
+                chart_kind,
+                0,
+            ) {
+                Ok(chart_definition) => {
+                    let chart_lines = render_chart_as_text_lines(
+                        &chart_definition,
+                        LINE_MODE_CHART_COLUMN_COUNT,
+                        LINE_MODE_CHART_ROW_COUNT,
+                        application_configuration().chart_glyph_mode,
+                    );
+                    for chart_line in chart_lines {
+                        println!("  {}", chart_line);
+                    }
+                }
+                Err(chart_error_message) => {
+                    println!("  Chart unavailable: {}", chart_error_message);
+                }
+            }
+        }
+
+        println!();
+    }
+}
+
+/// Runs the interactive full-screen dashboard loop
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results
+/// * `enhanced_analysis_results` - Per-column statistics to display
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+/// * `chart_export_directory` - Where the `s` and `p` keys save chart files
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or terminal/IO error
+fn run_full_screen_dashboard(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    chart_export_directory: &Path,
+) -> RowsAndColumnsResult<()> {
+    // The session restores the terminal when it goes out of scope
+    let mut raw_mode_session = TerminalRawModeSession::enter_raw_mode_session()?;
+
+    let mut dashboard_state = DashboardState {
+        selected_column_position: 0,
+        column_list_scroll_offset: 0,
+        statistics_scroll_offset: 0,
+        focused_pane: DashboardPane::ColumnList,
+        chart_kind_position: 0,
+        scatter_partner_position: 0,
+        glyph_mode: application_configuration().chart_glyph_mode,
+        chart_cache: HashMap::new(),
+        status_message: String::from("Ready"),
+    };
+
+    let mut terminal_size_tracker = TerminalSizeTracker::start_tracking();
+    let mut last_drawn_terminal_size: Option<TerminalSize> = None;
+    let mut input_just_arrived = false;
+    let mut redraw_needed = true;
+
+    loop {
+        // Redraw on state change or terminal resize
+        let current_terminal_size = terminal_size_tracker.current_terminal_size(input_just_arrived);
+        if last_drawn_terminal_size != Some(current_terminal_size) {
+            redraw_needed = true;
+        }
+
+        if redraw_needed {
+            let frame_lines = build_dashboard_frame_lines(
+                csv_analysis_results,
+                enhanced_analysis_results,
+                correlation_analysis_results,
+                &mut dashboard_state,
+                current_terminal_size,
+            );
+            draw_full_screen_frame(&frame_lines, current_terminal_size)?;
+            last_drawn_terminal_size = Some(current_terminal_size);
+            redraw_needed = false;
+        }
+
+        // Wait (up to ~100ms) for key presses
+        let key_presses = raw_mode_session.read_available_key_presses()?;
+        input_just_arrived = !key_presses.is_empty();
+
+        for key_press in key_presses {
+            // Saving needs the analysis results, so it is handled here rather than as a state change
+            let chart_file_format = match key_press {
+                KeyPress::Character('s') => Some(DashboardChartFileFormat::Svg),
+                KeyPress::Character('p') => Some(DashboardChartFileFormat::Bitmap(BitmapImageFormat::Png)),
+                KeyPress::Character('P') => Some(DashboardChartFileFormat::Bitmap(BitmapImageFormat::Ppm)),
+                _ => None,
+            };
+            if let Some(chart_file_format) = chart_file_format {
+                save_selected_dashboard_chart_to_file(
+                    csv_analysis_results,
+                    enhanced_analysis_results,
+                    correlation_analysis_results,
+                    &mut dashboard_state,
+                    chart_export_directory,
+                    chart_file_format,
+                );
+                redraw_needed = true;
+                continue;
+            }
+
+            let should_quit = apply_dashboard_key_press(
+                &key_press,
+                enhanced_analysis_results,
+                &mut dashboard_state,
+            );
+            if should_quit {
+                return Ok(());
+            }
+            redraw_needed = true;
+        }
+    }
+}
+
+/// Applies one key press to the dashboard state
+///
+/// # Arguments
+/// * `key_press` - The decoded key
+/// * `enhanced_analysis_results` - Per-column statistics (for bounds)
+/// * `dashboard_state` - State to update
+///
+/// # Returns
+/// * `bool` - True if the dashboard should close
+fn apply_dashboard_key_press(
+    key_press: &KeyPress,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    dashboard_state: &mut DashboardState,
+) -> bool {
+    let last_column_position = enhanced_analysis_results.len().saturating_sub(1);
+    let available_chart_kind_count = list_available_chart_kinds(
+        enhanced_analysis_results,
+        dashboard_state.selected_column_position,
+    ).len().max(1);
+
+    match key_press {
+        KeyPress::Character('q') | KeyPress::Character('Q') | KeyPress::Escape | KeyPress::Interrupt => return true,
+
+        KeyPress::ArrowDown | KeyPress::Character('j') => match dashboard_state.focused_pane {
+            DashboardPane::ColumnList => {
+                if dashboard_state.selected_column_position < last_column_position {
+                    select_dashboard_column(dashboard_state, dashboard_state.selected_column_position + 1);
+                }
+            }
+            DashboardPane::StatisticsPane => dashboard_state.statistics_scroll_offset += 1,
+            DashboardPane::ChartPane => {
+                dashboard_state.chart_kind_position = (dashboard_state.chart_kind_position + 1) % available_chart_kind_count;
+            }
+        },
+
+        KeyPress::ArrowUp | KeyPress::Character('k') => match dashboard_state.focused_pane {
+            DashboardPane::ColumnList => {
+                if dashboard_state.selected_column_position > 0 {
+                    select_dashboard_column(dashboard_state, dashboard_state.selected_column_position - 1);
+                }
+            }
+            DashboardPane::StatisticsPane => {
+                dashboard_state.statistics_scroll_offset = dashboard_state.statistics_scroll_offset.saturating_sub(1);
+            }
+            DashboardPane::ChartPane => {
+                dashboard_state.chart_kind_position =
+                    (dashboard_state.chart_kind_position + available_chart_kind_count - 1) % available_chart_kind_count;
+            }
+        },
+
+        KeyPress::ArrowRight | KeyPress::Character('l') | KeyPress::Tab => {
+            dashboard_state.focused_pane = dashboard_state.focused_pane.next_pane();
+        }
+
+        KeyPress::ArrowLeft | KeyPress::Character('h') => {
+            dashboard_state.focused_pane = dashboard_state.focused_pane.previous_pane();
+        }
+
+        KeyPress::PageDown => dashboard_state.statistics_scroll_offset += 10,
+        KeyPress::PageUp => {
+            dashboard_state.statistics_scroll_offset = dashboard_state.statistics_scroll_offset.saturating_sub(10);
+        }
+
+        KeyPress::Home | KeyPress::Character('g') => select_dashboard_column(dashboard_state, 0),
+        KeyPress::End | KeyPress::Character('G') => select_dashboard_column(dashboard_state, last_column_position),
+
+        KeyPress::Character('c') => {
+            dashboard_state.chart_kind_position = (dashboard_state.chart_kind_position + 1) % available_chart_kind_count;
+        }
+        KeyPress::Character('x') => dashboard_state.scatter_partner_position += 1,
+
+        KeyPress::Character('u') => {
+            dashboard_state.glyph_mode = match dashboard_state.glyph_mode {
+                ChartGlyphMode::Unicode => ChartGlyphMode::Ascii,
+                ChartGlyphMode::Ascii => ChartGlyphMode::Unicode,
+            };
+            dashboard_state.status_message = format!("Glyph mode: {:?}", dashboard_state.glyph_mode);
+        }
+
+        _ => {}
+    }
+
+    false
+}
+
+/// Moves the highlight to another column and resets per-column view state
+///
+/// # Arguments
+/// * `dashboard_state` - State to update
+/// * `new_column_position` - Index of the column to highlight
+fn select_dashboard_column(dashboard_state: &mut DashboardState, new_column_position: usize) {
+    dashboard_state.selected_column_position = new_column_position;
+    dashboard_state.statistics_scroll_offset = 0;
+    dashboard_state.chart_kind_position = 0;
+    dashboard_state.scatter_partner_position = 0;
+}
+
+/// Lists the chart kinds that make sense for a column
+///
+/// # Arguments
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `column_position` - Index of the column in question
+///
+/// # Returns
+/// * `Vec<DashboardChartKind>` - Available kinds, primary kind first
+fn list_available_chart_kinds(
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    column_position: usize,
+) -> Vec<DashboardChartKind> {
+    let enhanced_column_info = &enhanced_analysis_results[column_position];
+
+    match enhanced_column_info.field_type {
+        CsvFieldType::Continuous if enhanced_column_info.numerical_statistics.is_some() => {
+            let mut chart_kinds = vec![DashboardChartKind::Histogram, DashboardChartKind::BoxPlot];
+            if !list_scatter_partner_positions(enhanced_analysis_results, column_position).is_empty() {
+                chart_kinds.push(DashboardChartKind::ScatterPlot);
+                chart_kinds.push(DashboardChartKind::CorrelationHeatmap);
+            }
+            chart_kinds
+        }
+        CsvFieldType::Categorical if enhanced_column_info.categorical_statistics.is_some() => {
+            vec![DashboardChartKind::BarChart]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Lists other continuous columns that can be scatter-plotted against a column
+///
+/// # Arguments
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `column_position` - Index of the column on the y axis
+///
+/// # Returns
+/// * `Vec<usize>` - Positions of candidate x-axis columns
+fn list_scatter_partner_positions(
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    column_position: usize,
+) -> Vec<usize> {
+    enhanced_analysis_results
+        .iter()
+        .enumerate()
+        .filter(|(candidate_position, candidate_info)| {
+            *candidate_position != column_position
+                && candidate_info.field_type == CsvFieldType::Continuous
+                && candidate_info.numerical_statistics.is_some()
+        })
+        .map(|(candidate_position, _)| candidate_position)
+        .collect()
+}
+
+/// Builds a chart for the dashboard
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (file path, header flag)
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap
+/// * `column_position` - Index of the charted column
+/// * `chart_kind` - Which chart to build
+/// * `scatter_partner_position` - Which partner column to use for scatter plots
+///
+/// # Returns
+/// * `Result<ChartDefinition, String>` - The chart, or a message explaining why not
+fn build_dashboard_chart(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    column_position: usize,
+    chart_kind: DashboardChartKind,
+    scatter_partner_position: usize,
+) -> Result<ChartDefinition, String> {
+    let enhanced_column_info = &enhanced_analysis_results[column_position];
+    let column_name = &enhanced_column_info.basic_info.column_name;
+    let column_index = enhanced_column_info.basic_info.column_index;
+
+    match chart_kind {
+        DashboardChartKind::Histogram => {
+            let numerical_statistics = enhanced_column_info.numerical_statistics.as_ref()
+                .ok_or_else(|| "no numerical statistics".to_string())?;
+            build_histogram_chart_from_csv_column(
+                &csv_analysis_results.csv_file_path,
+                csv_analysis_results.has_header_row,
+                column_index,
+                column_name,
+                numerical_statistics,
+                DEFAULT_HISTOGRAM_BIN_COUNT,
+            ).map_err(|chart_error| chart_error.to_string())
+        }
+        DashboardChartKind::BoxPlot => {
+            let numerical_statistics = enhanced_column_info.numerical_statistics.as_ref()
+                .ok_or_else(|| "no numerical statistics".to_string())?;
+            Ok(build_box_plot_chart_from_statistics(column_name, numerical_statistics))
+        }
+        DashboardChartKind::BarChart => {
+            let categorical_statistics = enhanced_column_info.categorical_statistics.as_ref()
+                .ok_or_else(|| "no categorical statistics".to_string())?;
+            Ok(build_bar_chart_from_categorical_statistics(
+                column_name,
+                categorical_statistics,
+                DEFAULT_MAXIMUM_BAR_COUNT,
+            ))
+        }
+        DashboardChartKind::ScatterPlot => {
+            let partner_positions = list_scatter_partner_positions(enhanced_analysis_results, column_position);
+            if partner_positions.is_empty() {
+                return Err("no other continuous column to plot against".to_string());
+            }
+            let partner_info = &enhanced_analysis_results[partner_positions[scatter_partner_position % partner_positions.len()]];
+            build_scatter_plot_chart_from_csv_columns(
+                &csv_analysis_results.csv_file_path,
+                csv_analysis_results.has_header_row,
+                partner_info.basic_info.column_index,
+                &partner_info.basic_info.column_name,
+                column_index,
+                column_name,
+                MAXIMUM_SCATTER_PLOT_POINT_COUNT,
+            ).map_err(|chart_error| chart_error.to_string())
+        }
+        DashboardChartKind::CorrelationHeatmap => {
+            Ok(build_correlation_heatmap_chart(&correlation_analysis_results.pearson_matrix))
+        }
+    }
+}
+
+/// Builds the statistics pane text for one column
+///
+/// # Arguments
+/// * `enhanced_column_info` - The column to describe
+///
+/// # Returns
+/// * `Vec<String>` - Text lines (not yet clipped to any width)
+pub fn build_column_statistics_text_lines(enhanced_column_info: &EnhancedCsvColumnInformation) -> Vec<String> {
+    let basic_info = &enhanced_column_info.basic_info;
+    let mut statistics_lines = Vec::new();
+
+    let field_type_name = match enhanced_column_info.field_type {
+        CsvFieldType::Categorical => "categorical",
+        CsvFieldType::Continuous => "continuous",
+    };
+
+    statistics_lines.push(format!(
+        "{}. {} ({} - {})",
+        basic_info.column_index + 1,
+        basic_info.column_name,
+        basic_info.detected_data_type.to_toml_string(),
+        field_type_name
+    ));
+
+    statistics_lines.push(format!(
+        "Type-detection sample: {} non-empty, {} empty",
+        basic_info.non_empty_value_count,
+        basic_info.empty_value_count
+    ));
+
+    if !basic_info.sample_values.is_empty() {
+        statistics_lines.push(format!("Samples: {}", basic_info.sample_values.join(", ")));
+    }
+
+    if let Some(numerical_stats) = &enhanced_column_info.numerical_statistics {
+        statistics_lines.push(format!("min:    {:.3}", numerical_stats.min_value));
+        statistics_lines.push(format!("q1:     {:.3}", numerical_stats.q1_value));
+        statistics_lines.push(format!("median: {:.3}", numerical_stats.q2_median_value));
+        statistics_lines.push(format!("q3:     {:.3}", numerical_stats.q3_value));
+        statistics_lines.push(format!("max:    {:.3}", numerical_stats.max_value));
+        statistics_lines.push(format!("mean:   {:.3}", numerical_stats.mean_value));
+        statistics_lines.push(format!("stdev:  {:.3}", numerical_stats.standard_deviation));
+        statistics_lines.push(format!("%missing: {:.1}%", numerical_stats.missing_percentage));
+    }
+
+    if let Some(categorical_stats) = &enhanced_column_info.categorical_statistics {
+        statistics_lines.push(format!("Unique values: {}", categorical_stats.unique_value_count));
+        statistics_lines.push(format!("%missing: {:.1}%", categorical_stats.missing_percentage));
+        if let Some(mode_value) = &categorical_stats.mode_value {
+            statistics_lines.push(format!("Mode: {} ({:.1}%)", mode_value, categorical_stats.mode_percentage));
+        }
+        statistics_lines.push("Value distribution:".to_string());
+        for value_frequency in &categorical_stats.value_frequencies {
+            statistics_lines.push(format!(
+                "  {}: {:.1}% ({} values)",
+                value_frequency.value,
+                value_frequency.percentage,
+                value_frequency.count
+            ));
+        }
+    }
+
+    statistics_lines
+}
+
+/// Builds every line of one full-screen frame
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+/// * `dashboard_state` - Current state (chart cache and scroll offsets are updated)
+/// * `terminal_size` - Size of the terminal to fill
+///
+/// # Returns
+/// * `Vec<String>` - One string per terminal row
+fn build_dashboard_frame_lines(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    dashboard_state: &mut DashboardState,
+    terminal_size: TerminalSize,
+) -> Vec<String> {
+    let screen_width = terminal_size.column_count;
+    let screen_height = terminal_size.row_count;
+
+    if screen_width < MINIMUM_DASHBOARD_COLUMN_COUNT || screen_height < MINIMUM_DASHBOARD_ROW_COUNT {
+        return vec![
+            "Terminal too small for the dashboard.".to_string(),
+            format!("Need at least {}x{}; press q to leave.", MINIMUM_DASHBOARD_COLUMN_COUNT, MINIMUM_DASHBOARD_ROW_COUNT),
+        ];
+    }
+
+    let (vertical_separator, horizontal_separator, focus_marker) = match dashboard_state.glyph_mode {
+        ChartGlyphMode::Unicode => ('│', '─', '▶'),
+        ChartGlyphMode::Ascii => ('|', '-', '>'),
+    };
+
+    // Pane geometry: title row, body rows, status row
+    let body_row_count = screen_height - 2;
+    let column_list_width = (screen_width / 4).clamp(16, MAXIMUM_COLUMN_LIST_PANE_WIDTH);
+    let right_pane_width = screen_width - column_list_width - 1;
+    let statistics_pane_row_count = body_row_count / 2;
+    let chart_pane_row_count = body_row_count - statistics_pane_row_count - 1;
+
+    // ----- Column list pane -----
+    let mut column_list_lines = vec![format_pane_header("Columns", dashboard_state.focused_pane == DashboardPane::ColumnList, focus_marker)];
+    let visible_column_row_count = body_row_count - 1;
+
+    // Keep the selection visible
+    if dashboard_state.selected_column_position < dashboard_state.column_list_scroll_offset {
+        dashboard_state.column_list_scroll_offset = dashboard_state.selected_column_position;
+    } else if dashboard_state.selected_column_position >= dashboard_state.column_list_scroll_offset + visible_column_row_count {
+        dashboard_state.column_list_scroll_offset = dashboard_state.selected_column_position + 1 - visible_column_row_count;
+    }
+
+    for (column_position, enhanced_column_info) in enhanced_analysis_results
+        .iter()
+        .enumerate()
+        .skip(dashboard_state.column_list_scroll_offset)
+        .take(visible_column_row_count)
+    {
+        let selection_prefix = if column_position == dashboard_state.selected_column_position { "> " } else { "  " };
+        column_list_lines.push(format!(
+            "{}{}. {}",
+            selection_prefix,
+            enhanced_column_info.basic_info.column_index + 1,
+            enhanced_column_info.basic_info.column_name
+        ));
+    }
+
+    // ----- Statistics pane -----
+    let selected_column_info = &enhanced_analysis_results[dashboard_state.selected_column_position];
+    let all_statistics_lines = build_column_statistics_text_lines(selected_column_info);
+    let maximum_statistics_scroll = all_statistics_lines.len().saturating_sub(statistics_pane_row_count - 1);
+    dashboard_state.statistics_scroll_offset = dashboard_state.statistics_scroll_offset.min(maximum_statistics_scroll);
+
+    let mut statistics_pane_lines = vec![format_pane_header("Statistics", dashboard_state.focused_pane == DashboardPane::StatisticsPane, focus_marker)];
+    statistics_pane_lines.extend(
+        all_statistics_lines
+            .into_iter()
+            .skip(dashboard_state.statistics_scroll_offset)
+            .take(statistics_pane_row_count - 1)
+    );
+
+    // ----- Chart pane -----
+    let mut chart_pane_lines = vec![format_pane_header("Chart", dashboard_state.focused_pane == DashboardPane::ChartPane, focus_marker)];
+    let chart_glyph_mode = dashboard_state.glyph_mode;
+
+    match get_or_build_selected_dashboard_chart(
+        csv_analysis_results,
+        enhanced_analysis_results,
+        correlation_analysis_results,
+        dashboard_state,
+    ) {
+        None => chart_pane_lines.push("(no chart available for this column)".to_string()),
+        Some(Ok(chart_definition)) => {
+            chart_pane_lines.extend(render_chart_as_text_lines(
+                chart_definition,
+                right_pane_width,
+                chart_pane_row_count - 1,
+                chart_glyph_mode,
+            ));
+        }
+        Some(Err(chart_error_message)) => {
+            chart_pane_lines.push(format!("Chart unavailable: {}", chart_error_message));
+        }
+    }
+
+    // ----- Assemble the frame -----
+    let mut frame_lines = Vec::with_capacity(screen_height);
+
+    let csv_file_name = csv_analysis_results.csv_file_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    frame_lines.push(fit_text_to_character_count(
+        &format!(
+            " rows_and_columns dashboard | {} | {} rows x {} columns",
+            csv_file_name,
+            csv_analysis_results.total_data_row_count,
+            csv_analysis_results.total_column_count
+        ),
+        screen_width,
+    ));
+
+    let horizontal_rule: String = std::iter::repeat_n(horizontal_separator, right_pane_width).collect();
+
+    for body_row_index in 0..body_row_count {
+        let left_text = column_list_lines.get(body_row_index).cloned().unwrap_or_default();
+
+        let right_text = if body_row_index < statistics_pane_row_count {
+            statistics_pane_lines.get(body_row_index).cloned().unwrap_or_default()
+        } else if body_row_index == statistics_pane_row_count {
+            horizontal_rule.clone()
+        } else {
+            chart_pane_lines
+                .get(body_row_index - statistics_pane_row_count - 1)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        frame_lines.push(format!(
+            "{}{}{}",
+            fit_text_to_character_count(&left_text, column_list_width),
+            vertical_separator,
+            fit_text_to_character_count(&right_text, right_pane_width)
+        ));
+    }
+
+    let key_help_text = "j/k move  h/l pane  c chart  x scatter-partner  s/p/P save svg/png/ppm  u glyphs  q quit";
+    frame_lines.push(fit_text_to_character_count(
+        &format!(" {} | {}", key_help_text, dashboard_state.status_message),
+        screen_width,
+    ));
+
+    frame_lines
+}
+
+/// Returns the chart currently shown in the chart pane, building and caching it if needed
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+/// * `dashboard_state` - Current state (selection and chart cache)
+///
+/// # Returns
+/// * `Option<&Result<ChartDefinition, String>>` - None when the column has no chart kinds
+fn get_or_build_selected_dashboard_chart<'a>(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    dashboard_state: &'a mut DashboardState,
+) -> Option<&'a Result<ChartDefinition, String>> {
+    let available_chart_kinds = list_available_chart_kinds(enhanced_analysis_results, dashboard_state.selected_column_position);
+    if available_chart_kinds.is_empty() {
+        return None;
+    }
+
+    let chart_kind = available_chart_kinds[dashboard_state.chart_kind_position % available_chart_kinds.len()];
+    let cache_key = (dashboard_state.selected_column_position, chart_kind, dashboard_state.scatter_partner_position);
+
+    Some(dashboard_state.chart_cache
+        .entry(cache_key)
+        .or_insert_with(|| {
+            build_dashboard_chart(
+                csv_analysis_results,
+                enhanced_analysis_results,
+                correlation_analysis_results,
+                cache_key.0,
+                chart_kind,
+                cache_key.2,
+            )
+        }))
+}
+
+/// Saves the chart shown in the chart pane as an SVG, PNG or PPM file and reports the outcome in the status bar
+///
+/// The file is named after the CSV file and the chart title, e.g.
+/// `cats_age_histogram.svg`, and an existing file of that name is replaced.
+///
+/// # Arguments
+/// * `csv_analysis_results` - Basic analysis results (the CSV file name)
+/// * `enhanced_analysis_results` - All analyzed columns
+/// * `correlation_analysis_results` - Correlation matrices for the heatmap chart
+/// * `dashboard_state` - Current state (status message is updated)
+/// * `chart_export_directory` - Directory the file is written to
+/// * `chart_file_format` - SVG or a bitmap format
+fn save_selected_dashboard_chart_to_file(
+    csv_analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &[EnhancedCsvColumnInformation],
+    correlation_analysis_results: &CorrelationAnalysisResults,
+    dashboard_state: &mut DashboardState,
+    chart_export_directory: &Path,
+    chart_file_format: DashboardChartFileFormat,
+) {
+    let csv_file_stem = csv_analysis_results.csv_file_path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "csv".to_string());
+
+    let status_message = match get_or_build_selected_dashboard_chart(
+        csv_analysis_results,
+        enhanced_analysis_results,
+        correlation_analysis_results,
+        dashboard_state,
+    ) {
+        None => "No chart to save for this column".to_string(),
+        Some(Err(chart_error_message)) => format!("Chart unavailable: {}", chart_error_message),
+        Some(Ok(chart_definition)) => {
+            let file_extension = match chart_file_format {
+                DashboardChartFileFormat::Svg => "svg",
+                DashboardChartFileFormat::Bitmap(image_format) => image_format.file_extension(),
+            };
+            let chart_file_path = chart_export_directory.join(format!(
+                "{}_{}.{}",
+                sanitize_name_for_directory(&csv_file_stem),
+                sanitize_name_for_directory(get_chart_title(chart_definition)),
+                file_extension
+            ));
+            let save_result = match chart_file_format {
+                DashboardChartFileFormat::Svg => write_chart_svg_file(
+                    chart_definition,
+                    DEFAULT_SVG_CHART_WIDTH,
+                    DEFAULT_SVG_CHART_HEIGHT,
+                    &chart_file_path,
+                ),
+                DashboardChartFileFormat::Bitmap(image_format) => write_chart_bitmap_file(
+                    chart_definition,
+                    DEFAULT_SVG_CHART_WIDTH,
+                    DEFAULT_SVG_CHART_HEIGHT,
+                    image_format,
+                    &chart_file_path,
+                ),
+            };
+            match save_result {
+                Ok(()) => format!("Saved {}", chart_file_path.display()),
+                Err(save_error) => format!("Save failed: {}", save_error),
+            }
+        }
+    };
+
+    dashboard_state.status_message = status_message;
+}
+
+/// Formats a pane header, marking the focused pane
+///
+/// # Arguments
+/// * `pane_title` - Title text
+/// * `pane_is_focused` - Whether this pane has focus
+/// * `focus_marker` - Glyph used to mark focus
+///
+/// # Returns
+/// * `String` - The header text
+fn format_pane_header(pane_title: &str, pane_is_focused: bool, focus_marker: char) -> String {
+    if pane_is_focused {
+        format!("{} {}", focus_marker, pane_title)
+    } else {
+        format!("  {}", pane_title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::csv_processor_module::{
+        CategoricalColumnStatistics,
+        CategoricalValueFrequency,
+        CsvColumnDataType,
+        CsvColumnInformation,
+    };
+
+    /// Builds a small categorical column for tests
+    fn create_test_categorical_column() -> EnhancedCsvColumnInformation {
+        EnhancedCsvColumnInformation {
+            basic_info: CsvColumnInformation {
+                column_index: 3,
+                column_name: "breed".to_string(),
+                detected_data_type: CsvColumnDataType::String,
+                non_empty_value_count: 2,
+                empty_value_count: 0,
+                sample_values: vec!["Tabby".to_string(), "Persian".to_string()],
+            },
+            field_type: CsvFieldType::Categorical,
+            numerical_statistics: None,
+            categorical_statistics: Some(CategoricalColumnStatistics {
+                unique_value_count: 2,
+                value_frequencies: vec![
+                    CategoricalValueFrequency { value: "Tabby".to_string(), count: 1, percentage: 50.0 },
+                    CategoricalValueFrequency { value: "Persian".to_string(), count: 1, percentage: 50.0 },
+                ],
+                missing_percentage: 0.0,
+                mode_value: Some("Tabby".to_string()),
+                mode_percentage: 50.0,
+            }),
+        }
+    }
+
+    /// Test that the statistics pane lists every categorical value
+    #[test]
+    fn test_statistics_lines_include_full_distribution() {
+        let statistics_lines = build_column_statistics_text_lines(&create_test_categorical_column());
+        assert!(statistics_lines[0].contains("breed"));
+        assert!(statistics_lines.iter().any(|line| line.contains("Tabby")));
+        assert!(statistics_lines.iter().any(|line| line.contains("Persian")));
+    }
+
+    /// Test pane focus cycling in both directions
+    #[test]
+    fn test_dashboard_pane_cycling() {
+        assert_eq!(DashboardPane::ColumnList.next_pane(), DashboardPane::StatisticsPane);
+        assert_eq!(DashboardPane::ChartPane.next_pane(), DashboardPane::ColumnList);
+        assert_eq!(DashboardPane::ColumnList.previous_pane(), DashboardPane::ChartPane);
+    }
+
+    /// Test that categorical columns offer a bar chart only
+    #[test]
+    fn test_available_chart_kinds_for_categorical_column() {
+        let analyzed_columns = vec![create_test_categorical_column()];
+        assert_eq!(list_available_chart_kinds(&analyzed_columns, 0), vec![DashboardChartKind::BarChart]);
+    }
+}